@@ -1,50 +1,169 @@
 //! Build script for infs CLI.
 //!
 //! Sets compile-time environment variables for version information.
+//!
+//! With the `bundled-docs` feature, also packs the documentation pages in
+//! [`docs::PAGES`] into `$OUT_DIR/docs.tar.gz` for `infs docs`.
+//!
+//! Variables that cannot be determined, such as the commit when building
+//! from a source tarball without git, are not set; `infs version` reports
+//! them as "unknown".
 
 use std::process::Command;
 
 fn main() {
-    // Set git commit hash
-    let commit = get_git_commit();
-    println!("cargo:rustc-env=INFS_GIT_COMMIT={commit}");
+    // Set git revision and commit date
+    if let Some(commit) = get_git_commit() {
+        println!("cargo:rustc-env=INFS_GIT_COMMIT={commit}");
+    }
+    if let Some(date) = get_git_commit_date() {
+        println!("cargo:rustc-env=INFS_GIT_COMMIT_DATE={date}");
+    }
 
-    // Rerun if git HEAD changes (path relative to workspace root)
+    // Set build profile ("debug" or "release")
+    if let Ok(profile) = std::env::var("PROFILE") {
+        println!("cargo:rustc-env=INFS_BUILD_PROFILE={profile}");
+    }
+
+    // Set build timestamp (honors SOURCE_DATE_EPOCH for reproducible builds)
+    println!(
+        "cargo:rustc-env=INFS_BUILD_TIMESTAMP={}",
+        get_build_timestamp()
+    );
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Rerun if git HEAD or the index changes (path relative to workspace root)
     if let Some(workspace_root) = get_workspace_root() {
         println!("cargo:rerun-if-changed={workspace_root}/.git/HEAD");
+        println!("cargo:rerun-if-changed={workspace_root}/.git/index");
     }
+
+    #[cfg(feature = "bundled-docs")]
+    docs::bundle();
+}
+
+/// Gets the build time as seconds since the Unix epoch.
+fn get_build_timestamp() -> u64 {
+    if let Some(epoch) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+    {
+        return epoch;
+    }
+
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
 }
 
 /// Gets the workspace root directory.
 fn get_workspace_root() -> Option<String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !path.is_empty() {
-            return Some(path);
-        }
+    git_output(&["rev-parse", "--show-toplevel"])
+}
+
+/// Runs git with `args` and returns its trimmed output, if any.
+fn git_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
     }
-    None
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
 }
 
-/// Gets the short git commit hash.
-fn get_git_commit() -> String {
-    let output = Command::new("git")
-        .args(["rev-parse", "--short", "HEAD"])
-        .output();
+/// Gets the git revision, e.g. `v0.1.0-3-gabc1234-dirty` or `abc1234`.
+fn get_git_commit() -> Option<String> {
+    git_output(&["describe", "--always", "--dirty"])
+}
 
-    if let Ok(output) = output
-        && output.status.success()
-    {
-        let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !hash.is_empty() {
-            return hash;
+/// Gets the committer date of HEAD in ISO 8601 format.
+fn get_git_commit_date() -> Option<String> {
+    git_output(&["log", "-1", "--format=%cI"])
+}
+
+#[cfg(feature = "bundled-docs")]
+mod docs {
+    use std::fs::File;
+    use std::path::PathBuf;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    /// Bundled pages as topic and Markdown source, relative to this crate.
+    ///
+    /// Each page is stored as `<topic>.md` and `<topic>.html`.
+    pub const PAGES: &[(&str, &str)] = &[
+        ("overview", "../../README.md"),
+        ("cli", "README.md"),
+        ("inference-toml", "docs/inference-toml.md"),
+        ("type-system", "../../core/type-checker/docs/type-system.md"),
+        ("type-errors", "../../core/type-checker/docs/errors.md"),
+        ("install-linux", "../../book/installation_linux.md"),
+        ("install-macos", "../../book/installation_macos.md"),
+        ("install-windows", "../../book/installation_windows.md"),
+    ];
+
+    /// Writes `$OUT_DIR/docs.tar.gz`.
+    ///
+    /// Pages whose source is missing, as in a published crate without the
+    /// rest of the workspace, are left out with a warning. Entries carry no
+    /// timestamps, so the archive only changes with the pages.
+    pub fn bundle() {
+        let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("docs.tar.gz");
+        let file = File::create(&out).expect("failed to create docs archive");
+        let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::best()));
+
+        for (topic, path) in PAGES {
+            let source = crate_dir.join(path);
+            println!("cargo:rerun-if-changed={}", source.display());
+            let Ok(markdown) = std::fs::read_to_string(&source) else {
+                println!(
+                    "cargo:warning=documentation page {} not found, '{topic}' is not bundled",
+                    source.display()
+                );
+                continue;
+            };
+            append(&mut archive, &format!("{topic}.md"), markdown.as_bytes());
+            append(
+                &mut archive,
+                &format!("{topic}.html"),
+                html_page(topic, &markdown).as_bytes(),
+            );
         }
+
+        archive
+            .into_inner()
+            .and_then(GzEncoder::finish)
+            .expect("failed to write docs archive");
     }
 
-    "unknown".to_string()
+    fn append(archive: &mut tar::Builder<GzEncoder<File>>, name: &str, data: &[u8]) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_cksum();
+        archive
+            .append_data(&mut header, name, data)
+            .expect("failed to add page to docs archive");
+    }
+
+    /// Renders `markdown` as a standalone HTML page.
+    fn html_page(topic: &str, markdown: &str) -> String {
+        use pulldown_cmark::{Options, Parser, html};
+
+        let mut body = String::new();
+        html::push_html(&mut body, Parser::new_ext(markdown, Options::ENABLE_TABLES));
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{topic} - Inference documentation</title>\n\
+             <style>body {{ max-width: 50em; margin: 2em auto; padding: 0 1em; \
+             font-family: sans-serif; line-height: 1.5; }} \
+             pre {{ background: #f4f4f4; padding: 0.5em; overflow-x: auto; }} \
+             table {{ border-collapse: collapse; }} \
+             td, th {{ border: 1px solid #ccc; padding: 0.2em 0.5em; }}</style>\n\
+             </head>\n<body>\n{body}</body>\n</html>\n"
+        )
+    }
 }
@@ -0,0 +1,182 @@
+//! Source-level wasmtime backtraces.
+//!
+//! When a WASM function traps, wasmtime prints a backtrace whose frames name
+//! the module and the function:
+//!
+//! ```text
+//! error while executing at wasm backtrace:
+//!     0:   0x1d - trap.wasm!divide
+//!     1:   0x2b - trap.wasm!helper
+//! ```
+//!
+//! `infc` writes a function name table next to the binary
+//! (`out/<name>.names.json`) with the source line of every function. `infs run`
+//! uses it to rewrite those frames as
+//!
+//! ```text
+//!     0:   0x1d - at divide (src/main.inf:3)
+//!     1:   0x2b - at helper (src/main.inf:7)
+//! ```
+//!
+//! Frames of functions missing from the table, such as runtime intrinsics,
+//! are left unchanged, as is everything when the table is missing (older
+//! toolchains don't write it) or can't be read.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Extension of the function name table written by `infc`.
+const NAMES_EXTENSION: &str = "names.json";
+
+/// Source lines of the functions compiled into a WASM module.
+#[derive(Debug, Deserialize)]
+pub struct FunctionTable {
+    /// Source file the module was compiled from.
+    path: String,
+    functions: Vec<FunctionEntry>,
+}
+
+/// A function and the 1-based line of its definition.
+#[derive(Debug, Deserialize)]
+struct FunctionEntry {
+    name: String,
+    line: u32,
+}
+
+impl FunctionTable {
+    /// Loads the table `infc` wrote next to `wasm_path`.
+    ///
+    /// Returns `None` if there is no table or it can't be parsed.
+    pub fn load_for(wasm_path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(wasm_path.with_extension(NAMES_EXTENSION)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Rewrites the wasmtime backtrace frames of `stderr` that name a
+    /// function in the table. Other lines are returned unchanged.
+    #[must_use]
+    pub fn rewrite(&self, stderr: &str) -> String {
+        stderr
+            .split_inclusive('\n')
+            .map(|line| self.rewrite_frame(line).unwrap_or_else(|| line.to_string()))
+            .collect()
+    }
+
+    /// Rewrites a single `N: 0xADDR - module!function` line.
+    fn rewrite_frame(&self, line: &str) -> Option<String> {
+        let (prefix, frame) = line.split_once(" - ")?;
+        if !is_frame_prefix(prefix) {
+            return None;
+        }
+        let body = frame.trim_end_matches(['\n', '\r']);
+        let (_module, function) = body.split_once('!')?;
+        let entry = self.functions.iter().find(|entry| entry.name == function)?;
+        let ending = &frame[body.len()..];
+        Some(format!(
+            "{prefix} - at {} ({}:{}){ending}",
+            entry.name, self.path, entry.line
+        ))
+    }
+}
+
+/// Returns true for the `N:   0xADDR` part of a backtrace frame.
+fn is_frame_prefix(prefix: &str) -> bool {
+    let Some((index, address)) = prefix.trim_start().split_once(':') else {
+        return false;
+    };
+    let Some(hex) = address.trim_start().strip_prefix("0x") else {
+        return false;
+    };
+    !index.is_empty()
+        && index.chars().all(|c| c.is_ascii_digit())
+        && !hex.is_empty()
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    /// Stderr of wasmtime for a trap two calls deep.
+    const TRAP_STDERR: &str = "\
+Error: failed to run main module `out/trap.wasm`
+
+Caused by:
+    0: failed to invoke `run`
+    1: error while executing at wasm backtrace:
+           0:   0x1d - trap.wasm!divide
+           1:   0x2b - trap.wasm!helper
+           2:   0x35 - trap.wasm!run
+           3:   0x40 - <unknown>!<wasm function 3>
+    2: wasm trap: integer divide by zero
+";
+
+    fn table() -> FunctionTable {
+        serde_json::from_str(
+            r#"{"functions":[{"line":1,"name":"divide"},{"line":5,"name":"helper"},{"line":9,"name":"run"}],"path":"src/trap.inf"}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rewrite_maps_nested_frames_to_source() {
+        assert_eq!(
+            table().rewrite(TRAP_STDERR),
+            "\
+Error: failed to run main module `out/trap.wasm`
+
+Caused by:
+    0: failed to invoke `run`
+    1: error while executing at wasm backtrace:
+           0:   0x1d - at divide (src/trap.inf:1)
+           1:   0x2b - at helper (src/trap.inf:5)
+           2:   0x35 - at run (src/trap.inf:9)
+           3:   0x40 - <unknown>!<wasm function 3>
+    2: wasm trap: integer divide by zero
+"
+        );
+    }
+
+    #[test]
+    fn rewrite_keeps_unknown_frames_and_other_lines() {
+        let table = table();
+        for line in [
+            "           0:   0x1d - trap.wasm!intrinsic\n",
+            "Error: helper - trap.wasm!helper\n",
+            "    1: error while executing at wasm backtrace:",
+        ] {
+            assert_eq!(table.rewrite(line), line);
+        }
+    }
+
+    #[test]
+    fn rewrite_keeps_windows_line_endings() {
+        assert_eq!(
+            table().rewrite("  0:   0x1d - trap.wasm!divide\r\n"),
+            "  0:   0x1d - at divide (src/trap.inf:1)\r\n"
+        );
+    }
+
+    #[test]
+    fn load_for_reads_table_next_to_wasm() {
+        let dir = temp_test_dir("backtrace");
+        let wasm_path = dir.join("trap.wasm64.wasm");
+        assert!(FunctionTable::load_for(&wasm_path).is_none());
+
+        std::fs::write(
+            dir.join("trap.wasm64.names.json"),
+            r#"{"functions":[{"line":1,"name":"divide"}],"path":"trap.inf"}"#,
+        )
+        .unwrap();
+        let table = FunctionTable::load_for(&wasm_path).unwrap();
+        assert_eq!(table.path, "trap.inf");
+        assert_eq!(table.functions[0].name, "divide");
+
+        std::fs::write(dir.join("trap.wasm64.names.json"), "not json").unwrap();
+        assert!(FunctionTable::load_for(&wasm_path).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
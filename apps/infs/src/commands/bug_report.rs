@@ -0,0 +1,544 @@
+//! Bug report command for the infs CLI.
+//!
+//! Collects the environment details needed to triage an issue into a single
+//! Markdown document that can be pasted into a bug report.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs bug-report                     # Print the report to stdout
+//! infs bug-report --output report.md  # Write the report to a file
+//! ```
+//!
+//! ## Contents
+//!
+//! - infs version, commit, and build date
+//! - OS, architecture, and C library
+//! - Installed toolchains with metadata and the default version
+//! - `PATH` entries that contain Inference binaries
+//! - `coqc` and `wasmtime` versions
+//! - `Inference.toml` from the current directory, with authors redacted
+//! - The last lines of the most recent log in `out/`
+//!
+//! Every section is best-effort: a failure is reported as
+//! `unavailable: <reason>` instead of aborting the report. Nothing is
+//! fetched from the network, and the values of environment variables that
+//! look like secrets (such as `GITHUB_TOKEN`) are scrubbed from the output.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+
+use crate::commands::version::{build_date, git_commit};
+use crate::toolchain::compat::{ExternalTool, tool_version_line};
+use crate::toolchain::http::{DOWNLOAD_TIMEOUT_ENV, HTTP_TIMEOUT_ENV};
+use crate::toolchain::paths::INFERENCE_HOME_ENV;
+use crate::toolchain::platform::FORCE_LIBC_ENV;
+use crate::toolchain::{Platform, ToolchainPaths};
+
+/// Number of trailing log lines included in the report.
+const LOG_TAIL_LINES: usize = 50;
+
+/// Placeholder for redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Name fragments that mark an environment variable as secret.
+const SECRET_MARKERS: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "API_KEY",
+    "CREDENTIAL",
+    "PRIVATE_KEY",
+];
+
+/// Environment variables that affect infs and are safe to include.
+const REPORTED_ENV_VARS: &[&str] = &[
+    "INFC_PATH",
+    INFERENCE_HOME_ENV,
+    "INFS_DIST_SERVER",
+    FORCE_LIBC_ENV,
+    "INFS_NO_TUI",
+    HTTP_TIMEOUT_ENV,
+    DOWNLOAD_TIMEOUT_ENV,
+];
+
+/// Binaries looked up in `PATH` entries.
+const PATH_BINARIES: &[&str] = &["infs", "infc", "inf-llc", "rust-lld"];
+
+/// Arguments for the bug-report command.
+#[derive(Args)]
+pub struct BugReportArgs {
+    /// Write the report to a file instead of stdout.
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
+/// Executes the bug-report command.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be written to the output file.
+/// Failures while collecting individual sections are included in the report.
+pub fn execute(args: &BugReportArgs) -> Result<()> {
+    let paths = ToolchainPaths::new().map_err(|e| e.to_string());
+    let project_dir = std::env::current_dir().map_err(|e| e.to_string());
+    let report = generate_report(
+        paths.as_ref().map_err(String::as_str),
+        project_dir.as_deref().map_err(String::as_str),
+    );
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, &report)
+                .with_context(|| format!("Failed to write report to {}", path.display()))?;
+            println!("Bug report written to {}", path.display());
+        }
+        None => print!("{report}"),
+    }
+
+    Ok(())
+}
+
+/// Builds the full Markdown report with secrets scrubbed.
+fn generate_report(
+    paths: std::result::Result<&ToolchainPaths, &str>,
+    project_dir: std::result::Result<&Path, &str>,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## infs bug report");
+
+    push_section(&mut out, "infs", &infs_section());
+    push_section(&mut out, "System", &system_section());
+    push_section(
+        &mut out,
+        "Toolchains",
+        &paths.map_or_else(unavailable, toolchains_section),
+    );
+    push_section(
+        &mut out,
+        "PATH entries with Inference binaries",
+        &path_section(),
+    );
+    push_section(&mut out, "External tools", &external_tools_section());
+    push_section(&mut out, "Environment", &environment_section());
+    push_section(
+        &mut out,
+        "Project manifest",
+        &project_dir.map_or_else(unavailable, manifest_section),
+    );
+    push_section(
+        &mut out,
+        "Latest log",
+        &project_dir.map_or_else(unavailable, log_section),
+    );
+
+    redact_secrets(&out, &secret_values())
+}
+
+/// Appends a `###` section to the report.
+fn push_section(out: &mut String, title: &str, body: &str) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "### {title}");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{}", body.trim_end());
+}
+
+/// Formats a failed section.
+fn unavailable(reason: impl std::fmt::Display) -> String {
+    format!("unavailable: {reason}")
+}
+
+fn infs_section() -> String {
+    format!(
+        "- Version: {}\n- Commit: {}\n- Build date: {}",
+        env!("CARGO_PKG_VERSION"),
+        git_commit(),
+        build_date()
+    )
+}
+
+fn system_section() -> String {
+    let platform = Platform::detect();
+    let libc = match &platform {
+        Ok(platform) => platform
+            .libc()
+            .map_or_else(|| "n/a".to_string(), |libc| libc.to_string()),
+        Err(e) => unavailable(e),
+    };
+
+    format!(
+        "- OS: {}\n- Arch: {}\n- Platform: {}\n- libc: {libc}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        platform.map_or_else(unavailable, |p| p.to_string()),
+    )
+}
+
+fn toolchains_section(paths: &ToolchainPaths) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "- Root: {}", paths.root.display());
+
+    let default_version = paths.get_default_version();
+    match &default_version {
+        Ok(Some(version)) => {
+            let _ = writeln!(out, "- Default: {version}");
+        }
+        Ok(None) => {
+            let _ = writeln!(out, "- Default: (none)");
+        }
+        Err(e) => {
+            let _ = writeln!(out, "- Default: {}", unavailable(e));
+        }
+    }
+
+    match paths.list_installed_versions() {
+        Ok(versions) if versions.is_empty() => {
+            let _ = writeln!(out, "- Installed: (none)");
+        }
+        Ok(versions) => {
+            let _ = writeln!(out, "- Installed:");
+            for version in versions {
+                let installed = paths.read_metadata(&version).map_or_else(
+                    || "no metadata".to_string(),
+                    |metadata| format!("installed {}", metadata.installed_at),
+                );
+                let _ = writeln!(out, "  - {version} ({installed})");
+            }
+        }
+        Err(e) => {
+            let _ = writeln!(out, "- Installed: {}", unavailable(e));
+        }
+    }
+
+    out
+}
+
+fn path_section() -> String {
+    let Some(path_var) = std::env::var_os("PATH") else {
+        return unavailable("PATH is not set");
+    };
+
+    let ext = std::env::consts::EXE_SUFFIX;
+    let mut out = String::new();
+    for dir in std::env::split_paths(&path_var) {
+        let found: Vec<&str> = PATH_BINARIES
+            .iter()
+            .copied()
+            .filter(|name| dir.join(format!("{name}{ext}")).is_file())
+            .collect();
+        if !found.is_empty() {
+            let _ = writeln!(out, "- {}: {}", dir.display(), found.join(", "));
+        }
+    }
+
+    if out.is_empty() {
+        "(none)".to_string()
+    } else {
+        out
+    }
+}
+
+fn external_tools_section() -> String {
+    [ExternalTool::Coqc, ExternalTool::Wasmtime]
+        .into_iter()
+        .map(|tool| {
+            let name = tool.binary_name();
+            let version = if which::which(name).is_err() {
+                unavailable("not found in PATH")
+            } else {
+                tool_version_line(tool)
+                    .unwrap_or_else(|| unavailable(format!("cannot run '{name} --version'")))
+            };
+            format!("- {name}: {version}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn environment_section() -> String {
+    REPORTED_ENV_VARS
+        .iter()
+        .map(|name| match std::env::var(name) {
+            Ok(value) => format!("- {name}={value}"),
+            Err(_) => format!("- {name} (not set)"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn manifest_section(project_dir: &Path) -> String {
+    let manifest_path = project_dir.join("Inference.toml");
+    if !manifest_path.is_file() {
+        return format!("(no Inference.toml in {})", project_dir.display());
+    }
+
+    match read_redacted_manifest(&manifest_path) {
+        Ok(content) => format!("```toml\n{}\n```", content.trim_end()),
+        Err(e) => unavailable(format!("{e:#}")),
+    }
+}
+
+/// Reads a project manifest and replaces its author list.
+fn read_redacted_manifest(path: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut manifest: toml::Table =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    if let Some(package) = manifest
+        .get_mut("package")
+        .and_then(toml::Value::as_table_mut)
+        && package.contains_key("authors")
+    {
+        package.insert(
+            "authors".to_string(),
+            toml::Value::Array(vec![toml::Value::String(REDACTED.to_string())]),
+        );
+    }
+
+    toml::to_string(&manifest).context("Failed to serialize manifest")
+}
+
+fn log_section(project_dir: &Path) -> String {
+    match latest_log(&project_dir.join("out")) {
+        Ok(path) => match tail_lines(&path, LOG_TAIL_LINES) {
+            Ok(tail) => format!("{}:\n\n```text\n{}\n```", path.display(), tail.trim_end()),
+            Err(e) => unavailable(format!("{e:#}")),
+        },
+        Err(e) => unavailable(format!("{e:#}")),
+    }
+}
+
+/// Finds the most recently modified `.log` file in a directory.
+fn latest_log(dir: &Path) -> Result<PathBuf> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Cannot read {}", dir.display()))?;
+
+    let latest = entries
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|path| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified);
+
+    match latest {
+        Some((_, path)) => Ok(path),
+        None => bail!("no log files in {}", dir.display()),
+    }
+}
+
+/// Returns the last `count` lines of a file.
+fn tail_lines(path: &Path, count: usize) -> Result<String> {
+    let bytes = std::fs::read(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let text = String::from_utf8_lossy(&bytes);
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(count);
+    Ok(lines[start..].join("\n"))
+}
+
+/// Returns whether an environment variable name looks like it holds a secret.
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+/// Returns the values of environment variables with secret-looking names.
+fn secret_values() -> Vec<String> {
+    std::env::vars_os()
+        .filter(|(name, _)| name.to_str().is_some_and(is_secret_name))
+        .filter_map(|(_, value)| value.into_string().ok())
+        .collect()
+}
+
+/// Scrubs secrets from report text.
+///
+/// Known secret values are replaced wherever they appear, and
+/// `NAME=value` assignments with secret-looking names are masked even when
+/// the variable is not set in the current environment.
+fn redact_secrets(text: &str, secrets: &[String]) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| s.len() >= 4)
+        .collect();
+    // Longest first, so a secret containing another is replaced whole.
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+    let mut text = text.to_string();
+    for secret in secrets {
+        text = text.replace(secret, REDACTED);
+    }
+
+    text.split_inclusive('\n')
+        .map(|line| {
+            line.split(' ')
+                .map(|token| match token.split_once('=') {
+                    Some((name, value)) if is_secret_name(name.trim_start_matches(['-', '$'])) => {
+                        let ending = if value.ends_with('\n') { "\n" } else { "" };
+                        format!("{name}={REDACTED}{ending}")
+                    }
+                    _ => token.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::env;
+
+    #[test]
+    fn redact_secrets_replaces_values_and_assignments() {
+        let text = "push with ghp_abcdef123456\nGITHUB_TOKEN=other-value ok\nCOUNT=3\n";
+        let redacted = redact_secrets(text, &["ghp_abcdef123456".to_string()]);
+
+        assert!(!redacted.contains("ghp_abcdef123456"));
+        assert!(!redacted.contains("other-value"));
+        assert!(redacted.contains("push with <redacted>\n"));
+        assert!(redacted.contains("GITHUB_TOKEN=<redacted> ok\n"));
+        assert!(redacted.contains("COUNT=3\n"));
+    }
+
+    #[test]
+    fn redact_secrets_ignores_short_values() {
+        let redacted = redact_secrets("a b c", &["b".to_string(), String::new()]);
+        assert_eq!(redacted, "a b c");
+    }
+
+    #[test]
+    fn manifest_section_redacts_authors() {
+        let dir = temp_test_dir("bug_report_manifest");
+        std::fs::write(
+            dir.join("Inference.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\
+             authors = [\"Jane Doe <jane@example.com>\"]\n",
+        )
+        .expect("Should write manifest");
+
+        let section = manifest_section(&dir);
+        assert!(section.starts_with("```toml"));
+        assert!(section.contains("name = \"demo\""));
+        assert!(section.contains(REDACTED));
+        assert!(!section.contains("Jane Doe"));
+        assert!(!section.contains("jane@example.com"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn manifest_section_reports_parse_errors() {
+        let dir = temp_test_dir("bug_report_bad_manifest");
+        std::fs::write(dir.join("Inference.toml"), "[package\n").expect("Should write manifest");
+
+        assert!(manifest_section(&dir).starts_with("unavailable: Failed to parse"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn log_section_uses_tail_of_most_recent_log() {
+        let dir = temp_test_dir("bug_report_logs");
+        let out_dir = dir.join("out");
+        std::fs::create_dir_all(&out_dir).expect("Should create out dir");
+
+        let old = out_dir.join("old.log");
+        std::fs::write(&old, "old log\n").expect("Should write old log");
+        let old_time = std::time::SystemTime::now() - std::time::Duration::from_mins(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .and_then(|f| f.set_modified(old_time))
+            .expect("Should set mtime");
+
+        let lines: Vec<String> = (1..=60).map(|i| format!("line {i}")).collect();
+        std::fs::write(out_dir.join("build.log"), lines.join("\n")).expect("Should write log");
+
+        let section = log_section(&dir);
+        assert!(section.contains("build.log"));
+        assert!(section.contains("line 11\n"));
+        assert!(section.contains("line 60"));
+        assert!(!section.contains("line 10\n"));
+        assert!(!section.contains("old log"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn log_section_without_logs_is_unavailable() {
+        let dir = temp_test_dir("bug_report_no_logs");
+        assert!(log_section(&dir).starts_with("unavailable: Cannot read"));
+        std::fs::create_dir_all(dir.join("out")).expect("Should create out dir");
+        assert!(log_section(&dir).starts_with("unavailable: no log files"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn report_contains_sections_and_no_secrets() {
+        let dir = temp_test_dir("bug_report_full");
+        let paths = ToolchainPaths::with_root(dir.join("home"));
+        std::fs::create_dir_all(paths.toolchain_bin_dir("0.1.0")).expect("Should create bin");
+        paths
+            .set_default_version("0.1.0")
+            .expect("Should set default");
+
+        let token = "ghp_bugreporttesttoken0123456789";
+        std::fs::create_dir_all(dir.join("out")).expect("Should create out dir");
+        std::fs::write(
+            dir.join("out").join("build.log"),
+            format!("Authorization: Bearer {token}\n"),
+        )
+        .expect("Should write log");
+
+        // SAFETY: This test runs serially and removes the variable below.
+        unsafe {
+            env::set_var("GITHUB_TOKEN", token);
+        }
+        let report = generate_report(Ok(&paths), Ok(&dir));
+        // SAFETY: Cleanup - restoring previous state.
+        unsafe {
+            env::remove_var("GITHUB_TOKEN");
+        }
+
+        for section in [
+            "## infs bug report",
+            "### infs",
+            "### System",
+            "### Toolchains",
+            "### PATH entries with Inference binaries",
+            "### External tools",
+            "### Environment",
+            "### Project manifest",
+            "### Latest log",
+        ] {
+            assert!(report.contains(section), "Missing section: {section}");
+        }
+        assert!(report.contains(&format!("- Version: {}", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("- Default: 0.1.0"));
+        assert!(report.contains("  - 0.1.0 (no metadata)"));
+        assert!(report.contains("(no Inference.toml in"));
+        assert!(report.contains("Authorization: Bearer <redacted>"));
+        assert!(!report.contains(token));
+        assert!(!report.contains("GITHUB_TOKEN"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn report_sections_degrade_when_unavailable() {
+        let report = generate_report(Err("no home"), Err("no cwd"));
+        assert!(report.contains("### Toolchains\n\nunavailable: no home"));
+        assert!(report.contains("### Latest log\n\nunavailable: no cwd"));
+    }
+}
@@ -12,13 +12,47 @@
 //!
 //! Phases execute in canonical order (parse -> analyze -> codegen) regardless
 //! of the order flags appear on the command line. Each phase depends on the previous.
+//!
+//! ## Targets
+//!
+//! `--target` selects the WebAssembly target. Without it, `[build] target`
+//! from the project's `Inference.toml` is used, defaulting to `wasm32`.
+//!
+//! ## Optimization
+//!
+//! `--release` and `--debug` override `[build] optimize` from the manifest
+//! (default `debug`). See [`crate::project::optimize`] for how the level is
+//! passed to `infc`.
+//!
+//! ## Features
+//!
+//! The `default` features from `[features]` in the manifest are enabled,
+//! plus those given with `--features`, and every feature they imply.
+//! `--no-default-features` leaves out the defaults. See
+//! [`crate::project::features`] for how they reach the program.
+//!
+//! ## Dependency files
+//!
+//! `--emit-deps <PATH>` writes a Makefile-syntax dependency file listing the
+//! outputs and the files the build read, for build systems wrapping
+//! `infs build`. See [`crate::project::depfile`].
+//!
+//! ## Diagnostics
+//!
+//! Compiler errors are rendered with source context by
+//! [`crate::diagnostics::run_infc`] when the toolchain supports it.
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::diagnostics::run_infc;
 use crate::errors::InfsError;
+use crate::project::depfile;
+use crate::project::features::{add_feature_defines, resolve_features};
+use crate::project::optimize::{Optimize, add_opt_level, resolve_optimize};
+use crate::project::target::{Target, ensure_infc_supports, resolve_target};
 use crate::toolchain::find_infc;
 
 /// Arguments for the build command.
@@ -36,6 +70,10 @@ use crate::toolchain::find_infc;
 ///
 /// - `-o`: Generate WASM binary file in `out/` directory
 /// - `-v`: Generate Rocq (.v) translation in `out/` directory
+/// - `--target`: WebAssembly target, overriding the manifest
+/// - `--release` / `--debug`: Optimization level, overriding the manifest
+/// - `--features` / `--no-default-features`: Features to enable
+/// - `--emit-deps`: Write a Makefile dependency file for the outputs
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct BuildArgs {
@@ -61,6 +99,30 @@ pub struct BuildArgs {
     /// Generate Rocq (.v) translation file.
     #[clap(short = 'v', action = clap::ArgAction::SetTrue)]
     pub generate_v_output: bool,
+
+    /// WebAssembly target [default: `[build] target` from Inference.toml, or wasm32].
+    #[clap(long, value_enum)]
+    pub target: Option<Target>,
+
+    /// Build with optimizations [default: `[build] optimize` from Inference.toml, or debug].
+    #[clap(long, conflicts_with = "debug")]
+    pub release: bool,
+
+    /// Build without optimizations, overriding `[build] optimize`.
+    #[clap(long)]
+    pub debug: bool,
+
+    /// Features to enable in addition to the default ones (comma-separated).
+    #[clap(long, value_delimiter = ',', value_name = "FEATURES")]
+    pub features: Vec<String>,
+
+    /// Do not enable the `default` features from Inference.toml.
+    #[clap(long)]
+    pub no_default_features: bool,
+
+    /// Write a Makefile-syntax dependency file for the outputs to PATH.
+    #[clap(long, value_name = "PATH")]
+    pub emit_deps: Option<PathBuf>,
 }
 
 /// Executes the build command with the given arguments.
@@ -69,20 +131,29 @@ pub struct BuildArgs {
 ///
 /// 1. Validates that the source file exists
 /// 2. Ensures at least one phase flag is specified
-/// 3. Locates the infc compiler binary
-/// 4. Builds and executes the infc command with appropriate flags
-/// 5. Propagates exit code from infc
+/// 3. Resolves the target, optimization level, and features from the flags
+///    and the project manifest
+/// 4. Locates the infc compiler binary and checks it supports the target
+/// 5. Builds and executes the infc command with appropriate flags
+/// 6. Propagates exit code from infc, or writes the dependency file
+///    requested with `--emit-deps` on success
 ///
 /// ## Errors
 ///
 /// Returns an error if:
 /// - The source file does not exist
 /// - No phase flags are specified
-/// - infc compiler cannot be found
+/// - The project manifest cannot be parsed or names an unknown target or
+///   optimization level
+/// - A requested feature is not declared in the manifest
+/// - infc compiler cannot be found or does not support the target or features
+/// - `--emit-deps` is given without `-o` or `-v`, or the dependency file
+///   cannot be written
 /// - infc exits with non-zero code (as `InfsError::ProcessExitCode`)
 pub fn execute(args: &BuildArgs) -> Result<()> {
     if !args.path.exists() {
-        bail!("Path not found: {}", args.path.display());
+        return Err(InfsError::file_not_found(args.path.clone()))
+            .with_context(|| format!("Path not found: {}", args.path.display()));
     }
 
     let need_parse = args.parse;
@@ -92,8 +163,15 @@ pub fn execute(args: &BuildArgs) -> Result<()> {
     if !(need_parse || need_analyze || need_codegen) {
         bail!("At least one of --parse, --analyze, or --codegen must be specified");
     }
+    if args.emit_deps.is_some() && !(args.generate_wasm_output || args.generate_v_output) {
+        bail!("--emit-deps requires an output: pass -o, -v, or both");
+    }
 
+    let target = resolve_target(args.target, &args.path)?;
+    let optimize = resolve_optimize(Optimize::from_flags(args.release, args.debug), &args.path)?;
+    let features = resolve_features(&args.features, args.no_default_features, &args.path)?;
     let infc_path = find_infc()?;
+    ensure_infc_supports(&infc_path, target)?;
 
     let mut cmd = Command::new(&infc_path);
     cmd.arg(&args.path);
@@ -113,15 +191,26 @@ pub fn execute(args: &BuildArgs) -> Result<()> {
     if args.generate_v_output {
         cmd.arg("-v");
     }
+    if target != Target::default() {
+        cmd.arg("--target").arg(target.as_str());
+    }
+    add_feature_defines(&mut cmd, &infc_path, &features)?;
+    if need_codegen {
+        add_opt_level(&mut cmd, &infc_path, optimize)?;
+    }
 
-    let status = cmd
-        .stdin(std::process::Stdio::inherit())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status()
-        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+    let status = run_infc(&mut cmd, &infc_path)?;
 
     if status.success() {
+        if let Some(path) = &args.emit_deps {
+            let outputs = depfile::build_outputs(
+                &args.path,
+                target,
+                args.generate_wasm_output,
+                args.generate_v_output,
+            )?;
+            depfile::write(path, &args.path, &outputs)?;
+        }
         Ok(())
     } else {
         let code = status.code().unwrap_or(1);
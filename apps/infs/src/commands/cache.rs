@@ -0,0 +1,173 @@
+//! Cache command for the infs CLI.
+//!
+//! Manages the data infs caches under its home directory.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs cache path          # Print the cache directory
+//! infs cache clear         # Remove cached data such as the release manifest
+//! infs cache clear --all   # Also remove downloaded toolchain archives
+//! ```
+//!
+//! The manifest cache is refreshed after 15 minutes anyway; clearing it
+//! forces the next command to fetch the manifest again, e.g. after a release
+//! or when the cache file is corrupt. Downloaded archives are only reused to
+//! resume interrupted installs, so removing them is always safe.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::errors::InfsError;
+use crate::toolchain::ToolchainPaths;
+use crate::toolchain::download::format_bytes;
+use crate::toolchain::lock::ToolchainLock;
+
+/// Arguments for the cache command.
+#[derive(Args)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+/// Subcommands for cache management.
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Print the cache directory.
+    Path,
+
+    /// Remove cached data.
+    Clear(CacheClearArgs),
+}
+
+/// Arguments for `cache clear`.
+#[derive(Args)]
+pub struct CacheClearArgs {
+    /// Also remove downloaded toolchain archives.
+    #[clap(long)]
+    pub all: bool,
+}
+
+/// Executes the cache command.
+///
+/// # Errors
+///
+/// Returns an error if the infs home directory cannot be determined or a
+/// cache directory cannot be removed.
+pub fn execute(args: &CacheArgs) -> Result<()> {
+    let paths = ToolchainPaths::new()?;
+    match &args.command {
+        CacheCommand::Path => {
+            println!("{}", paths.cache_dir().display());
+            Ok(())
+        }
+        CacheCommand::Clear(clear_args) => {
+            let freed = clear(&paths, clear_args.all)?;
+            if freed.is_empty() {
+                println!("Cache is already empty.");
+            } else {
+                let total: u64 = freed.iter().map(|(_, size)| size).sum();
+                for (dir, size) in &freed {
+                    println!("Removed {} ({})", dir.display(), format_bytes(*size));
+                }
+                println!("Freed {}", format_bytes(total));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Removes the cache directory, and the downloads directory if `all` is set.
+///
+/// Clearing downloads takes the toolchain lock, since an install in
+/// progress extracts into that directory.
+///
+/// Returns each removed directory with the number of bytes it held.
+/// Directories that do not exist are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the toolchain lock cannot be taken or a directory
+/// cannot be removed.
+fn clear(paths: &ToolchainPaths, all: bool) -> Result<Vec<(PathBuf, u64)>> {
+    let mut dirs = vec![paths.cache_dir()];
+    let _lock = if all {
+        dirs.push(paths.downloads.clone());
+        Some(ToolchainLock::acquire(paths)?)
+    } else {
+        None
+    };
+
+    let mut freed = Vec::new();
+    for dir in dirs {
+        if std::fs::symlink_metadata(&dir).is_err() {
+            continue;
+        }
+        let size = dir_size(&dir);
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| InfsError::io_error(format!("Failed to remove {}", dir.display()), e))?;
+        freed.push((dir, size));
+    }
+    Ok(freed)
+}
+
+/// Returns the total size of the files under `path`, without following
+/// symlinks. Entries that cannot be read are not counted.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| dir_size(&entry.path()))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn temp_root(name: &str) -> ToolchainPaths {
+        let paths = ToolchainPaths::with_root(temp_test_dir(&format!("cache_{name}")));
+        std::fs::create_dir_all(paths.cache_dir()).unwrap();
+        std::fs::create_dir_all(&paths.downloads).unwrap();
+        std::fs::write(paths.cache_dir().join("manifest.json"), "{}").unwrap();
+        std::fs::write(paths.downloads.join("infc.tar.gz"), [0u8; 100]).unwrap();
+        paths
+    }
+
+    #[test]
+    fn clear_removes_manifest_cache_and_keeps_downloads() {
+        let paths = temp_root("clear");
+
+        let freed = clear(&paths, false).unwrap();
+        assert_eq!(freed, [(paths.cache_dir(), 2)]);
+        assert!(!paths.cache_dir().join("manifest.json").exists());
+        assert!(paths.downloads.join("infc.tar.gz").exists());
+
+        assert!(clear(&paths, false).unwrap().is_empty());
+        std::fs::remove_dir_all(&paths.root).unwrap();
+    }
+
+    #[test]
+    fn clear_all_also_removes_downloads() {
+        let paths = temp_root("clear_all");
+
+        let freed = clear(&paths, true).unwrap();
+        assert_eq!(
+            freed,
+            [(paths.cache_dir(), 2), (paths.downloads.clone(), 100)]
+        );
+        assert!(!paths.downloads.exists());
+        assert!(paths.root.exists());
+        std::fs::remove_dir_all(&paths.root).unwrap();
+    }
+}
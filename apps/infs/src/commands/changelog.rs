@@ -0,0 +1,90 @@
+//! Changelog command for the infs CLI.
+//!
+//! Prints the release notes of a toolchain version from the release
+//! manifest, as plain text.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs changelog          # Latest stable version
+//! infs changelog 0.2.0    # Specific version
+//! infs changelog "^0.2"   # Newest version matching a requirement
+//! infs changelog --pre    # Latest version, including prereleases
+//! ```
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::toolchain::manifest::{VersionEntry, fetch_manifest, resolve_version};
+use crate::toolchain::release_notes::strip_markdown;
+
+/// Arguments for the changelog command.
+#[derive(Args)]
+pub struct ChangelogArgs {
+    /// Version to show (defaults to the latest stable version).
+    pub version: Option<String>,
+
+    /// Allow prerelease versions when resolving "latest" or a version range.
+    #[clap(long = "pre")]
+    pub pre: bool,
+}
+
+/// Executes the changelog command.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be fetched or the version is
+/// not in it.
+pub async fn execute(args: &ChangelogArgs) -> Result<()> {
+    let manifest = fetch_manifest().await?;
+    let entry = resolve_version(&manifest, args.version.as_deref(), args.pre)?;
+    print!("{}", format_changelog(entry));
+    Ok(())
+}
+
+/// Formats the release notes of `entry` for the terminal.
+fn format_changelog(entry: &VersionEntry) -> String {
+    let notes = entry
+        .notes
+        .as_deref()
+        .map(strip_markdown)
+        .unwrap_or_default();
+    if notes.is_empty() {
+        return format!("No release notes for {}.\n", entry.version);
+    }
+    format!("Release notes for {}:\n\n{notes}\n", entry.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(notes: Option<&str>) -> VersionEntry {
+        VersionEntry {
+            version: "0.2.0".to_string(),
+            stable: true,
+            notes: notes.map(String::from),
+            files: vec![],
+        }
+    }
+
+    #[test]
+    fn format_changelog_prints_stripped_notes() {
+        assert_eq!(
+            format_changelog(&entry(Some("## Fixes\n\n* **Faster** builds\n"))),
+            "Release notes for 0.2.0:\n\nFixes\n\n- Faster builds\n"
+        );
+    }
+
+    #[test]
+    fn format_changelog_without_notes() {
+        assert_eq!(
+            format_changelog(&entry(None)),
+            "No release notes for 0.2.0.\n"
+        );
+        assert_eq!(
+            format_changelog(&entry(Some("\n"))),
+            "No release notes for 0.2.0.\n"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Clean command for the infs CLI.
+//!
+//! Removes build artifacts from an Inference project.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs clean           # Remove out/ in the current project
+//! infs clean ./path    # Remove out/ in the project at ./path
+//! infs clean --all     # Also remove the Rocq proofs output directory
+//! ```
+//!
+//! The proofs directory is `[verification] output-dir` from `Inference.toml`
+//! (`proofs/` by default). As a safety check, a directory that resolves to
+//! the project root itself or to a location outside it (through `..`, an
+//! absolute path, or a symlink) is never removed.
+//!
+//! `out/` can always be rebuilt, but the proofs directory may hold proofs
+//! written by hand, so in a terminal `--all` asks before removing it unless
+//! `--yes` is given.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use crate::confirm::{self, Confirmer, TerminalConfirmer};
+use crate::errors::InfsError;
+use crate::project::manifest::{InferenceToml, MANIFEST_FILE};
+
+/// Directory that `build -o` and `run` write WASM output to.
+const OUT_DIR: &str = "out";
+
+/// Arguments for the clean command.
+#[derive(Args)]
+pub struct CleanArgs {
+    /// Project directory (defaults to current directory).
+    #[clap(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Also remove the verification output directory.
+    #[clap(long)]
+    pub all: bool,
+}
+
+/// Executes the clean command.
+///
+/// # Errors
+///
+/// Returns an error if the directory is not an Inference project, the
+/// manifest cannot be read, a directory to remove lies outside the project,
+/// or removal fails.
+pub fn execute(args: &CleanArgs) -> Result<()> {
+    clean_project(args, confirm::assume_yes(), &mut TerminalConfirmer)
+}
+
+/// Removes the artifacts selected by `args`, asking `confirmer` before
+/// removing the proofs directory unless `assume_yes` is set.
+fn clean_project(args: &CleanArgs, assume_yes: bool, confirmer: &mut dyn Confirmer) -> Result<()> {
+    let manifest_path = args.path.join(MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Err(InfsError::file_not_found(manifest_path))
+            .with_context(|| format!("'{}' is not an Inference project", args.path.display()));
+    }
+
+    let mut dirs = vec![String::from(OUT_DIR)];
+    if args.all {
+        let manifest = InferenceToml::from_file(&manifest_path)?;
+        let proofs = manifest.verification.output_dir;
+        if std::fs::symlink_metadata(args.path.join(&proofs)).is_ok()
+            && !confirm::confirm(
+                confirmer,
+                assume_yes,
+                &format!("Remove the proofs directory {proofs}?"),
+            )?
+        {
+            println!("Keeping {proofs}");
+        } else {
+            dirs.push(proofs);
+        }
+    }
+
+    let mut removed = 0;
+    for dir in &dirs {
+        if remove_artifact_dir(&args.path, Path::new(dir))? {
+            println!("Removed {dir}");
+            removed += 1;
+        }
+    }
+    if removed == 0 {
+        println!("Nothing to clean.");
+    }
+    Ok(())
+}
+
+/// Removes `dir`, relative to the project at `root`, if it exists.
+///
+/// Returns whether anything was removed.
+///
+/// # Errors
+///
+/// Returns an error if `dir` is not strictly inside `root` or cannot be
+/// removed.
+fn remove_artifact_dir(root: &Path, dir: &Path) -> Result<bool> {
+    let path = root.join(dir);
+    if std::fs::symlink_metadata(&path).is_err() {
+        return Ok(false);
+    }
+
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", root.display()))?;
+    let resolved = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    if resolved == root || !resolved.starts_with(&root) {
+        return Err(InfsError::invalid_arguments(format!(
+            "refusing to remove '{}': it resolves to {}, which is not inside the project at {}",
+            dir.display(),
+            resolved.display(),
+            root.display()
+        ))
+        .into());
+    }
+
+    std::fs::remove_dir_all(&resolved)
+        .map_err(|e| InfsError::io_error(format!("Failed to remove {}", resolved.display()), e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = temp_test_dir(&format!("clean_{name}"));
+        std::fs::create_dir_all(dir.join("out")).unwrap();
+        std::fs::write(dir.join("out").join("main.wasm"), b"\0asm").unwrap();
+        dir
+    }
+
+    #[test]
+    fn removes_directory_inside_project() {
+        let root = temp_project("inside");
+        assert!(remove_artifact_dir(&root, Path::new("out")).unwrap());
+        assert!(!root.join("out").exists());
+        assert!(!remove_artifact_dir(&root, Path::new("out")).unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn refuses_directories_outside_project() {
+        let root = temp_project("outside");
+        let sibling = root.with_extension("sibling");
+        std::fs::create_dir_all(&sibling).unwrap();
+
+        let relative = Path::new("..").join(sibling.file_name().unwrap());
+        let err = remove_artifact_dir(&root, &relative).unwrap_err();
+        assert!(err.to_string().contains("refusing to remove"), "{err}");
+        assert!(remove_artifact_dir(&root, Path::new(".")).is_err());
+        assert!(remove_artifact_dir(&root, &sibling).is_err());
+        assert!(sibling.exists());
+        assert!(root.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+        std::fs::remove_dir_all(&sibling).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_symlink_pointing_outside_project() {
+        let root = temp_project("symlink");
+        let target = root.with_extension("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::os::unix::fs::symlink(&target, root.join("proofs")).unwrap();
+
+        assert!(remove_artifact_dir(&root, Path::new("proofs")).is_err());
+        assert!(target.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+        std::fs::remove_dir_all(&target).unwrap();
+    }
+
+    #[test]
+    fn proofs_are_removed_only_when_confirmed() {
+        use crate::confirm::testing::{Answer, NeverAsked};
+
+        let root = temp_project("confirm");
+        InferenceToml::new("confirm")
+            .write_to_file(&root.join(MANIFEST_FILE))
+            .unwrap();
+        std::fs::create_dir_all(root.join("proofs")).unwrap();
+        let args = CleanArgs {
+            path: root.clone(),
+            all: true,
+        };
+
+        let mut answer = Answer::new(false);
+        clean_project(&args, false, &mut answer).unwrap();
+        assert_eq!(answer.asked, ["Remove the proofs directory proofs/?"]);
+        assert!(!root.join("out").exists());
+        assert!(root.join("proofs").exists());
+
+        clean_project(&args, true, &mut NeverAsked).unwrap();
+        assert!(!root.join("proofs").exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -12,6 +12,7 @@ use anyhow::{Result, bail};
 use clap::Args;
 
 use crate::toolchain::ToolchainPaths;
+use crate::toolchain::lock::ToolchainLock;
 use crate::toolchain::manifest::{fetch_manifest, find_version};
 
 /// Arguments for the default command.
@@ -65,6 +66,7 @@ pub async fn execute(args: &DefaultArgs) -> Result<()> {
         }
     }
 
+    let _lock = ToolchainLock::acquire(&paths)?;
     let current_default = paths.get_default_version()?;
     if current_default.as_deref() == Some(version.as_str()) {
         println!("Toolchain {version} is already the default.");
@@ -0,0 +1,424 @@
+//! Docs command for the infs CLI.
+//!
+//! Shows the documentation bundled into the binary, for machines without
+//! network access.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs docs                # Show the overview
+//! infs docs cli            # Show the CLI reference
+//! infs docs type           # A unique prefix is enough (type-system)
+//! infs docs cli --open     # Open the HTML page in a browser
+//! infs docs --list         # List the topics
+//! ```
+//!
+//! ## Bundling
+//!
+//! The documentation is only embedded when infs is built with the
+//! `bundled-docs` feature: `build.rs` then packs the language, CLI, and
+//! installation pages, as Markdown and HTML, into a compressed archive; the
+//! build fails if it exceeds [`BUNDLE_SIZE_BUDGET`]. Without the feature
+//! nothing is embedded, and `infs docs` points to the online documentation.
+//!
+//! On first use the archive is extracted to `~/.inference/cache/docs/`, in
+//! a directory named after its checksum, so a newer infs never shows the
+//! pages of an older one.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+
+use crate::output::{self, Stream, Style};
+use crate::toolchain::ToolchainPaths;
+
+/// Documentation archive embedded by `build.rs`.
+#[cfg(feature = "bundled-docs")]
+const BUNDLE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/docs.tar.gz"));
+
+/// Documentation archive; empty without the `bundled-docs` feature.
+#[cfg(not(feature = "bundled-docs"))]
+const BUNDLE: &[u8] = &[];
+
+/// Largest allowed size of the embedded archive, in bytes.
+///
+/// Without the `bundled-docs` feature the archive is empty, so the feature
+/// adds nothing to the binary but the code of this command.
+pub const BUNDLE_SIZE_BUDGET: usize = 256 * 1024;
+
+const _: () = assert!(
+    BUNDLE.len() <= BUNDLE_SIZE_BUDGET,
+    "the documentation archive exceeds BUNDLE_SIZE_BUDGET"
+);
+
+/// Topic shown when none is given.
+const DEFAULT_TOPIC: &str = "overview";
+
+/// File written after a complete extraction.
+const EXTRACTED_MARKER: &str = ".extracted";
+
+/// Online documentation, suggested when none is bundled.
+const DOCS_URL: &str = "https://inference-lang.org";
+
+/// Arguments for the docs command.
+#[derive(Args)]
+pub struct DocsArgs {
+    /// Topic to show (defaults to `overview`); a unique prefix is enough.
+    pub topic: Option<String>,
+
+    /// List the bundled topics.
+    #[clap(long, conflicts_with_all = ["topic", "open"])]
+    pub list: bool,
+
+    /// Open the HTML page in a browser instead of printing it.
+    #[clap(long)]
+    pub open: bool,
+}
+
+/// Executes the docs command.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - infs was built without the `bundled-docs` feature
+/// - The archive cannot be extracted to the cache directory
+/// - The topic is unknown or ambiguous
+/// - No browser can be started with `--open`
+pub fn execute(args: &DocsArgs) -> Result<()> {
+    if BUNDLE.is_empty() {
+        bail!(
+            "This infs was built without bundled documentation (the `bundled-docs` feature).\n\
+             Read the documentation at {DOCS_URL}"
+        );
+    }
+    let dir = bundle_dir(&ToolchainPaths::new()?.cache_dir(), BUNDLE);
+    let topics = ensure_extracted(BUNDLE, &dir)?;
+
+    if args.list {
+        for topic in &topics {
+            println!("{topic}");
+        }
+        return Ok(());
+    }
+
+    let topic = find_topic(&topics, args.topic.as_deref().unwrap_or(DEFAULT_TOPIC))?;
+    if args.open {
+        let page = dir.join(format!("{topic}.html"));
+        open_in_browser(&page)?;
+        println!("Opened {}", page.display());
+    } else {
+        let path = dir.join(format!("{topic}.md"));
+        let markdown = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        print!(
+            "{}",
+            render_markdown(&markdown, output::colors_enabled(Stream::Stdout))
+        );
+    }
+    Ok(())
+}
+
+/// Returns the directory under `cache_dir` that `archive` is extracted to.
+fn bundle_dir(cache_dir: &Path, archive: &[u8]) -> PathBuf {
+    let digest = hex::encode(Sha256::digest(archive));
+    cache_dir.join("docs").join(&digest[..16])
+}
+
+/// Extracts `archive` to `dir` unless a previous run already did, and
+/// returns the topics, sorted.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be extracted.
+pub fn ensure_extracted(archive: &[u8], dir: &Path) -> Result<Vec<String>> {
+    if !dir.join(EXTRACTED_MARKER).exists() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        tar::Archive::new(GzDecoder::new(archive))
+            .unpack(dir)
+            .with_context(|| format!("Failed to extract documentation to {}", dir.display()))?;
+        std::fs::write(dir.join(EXTRACTED_MARKER), "")
+            .with_context(|| format!("Failed to write {}", dir.display()))?;
+    }
+    topics(dir)
+}
+
+/// Returns the topics in `dir`, i.e. the stems of its Markdown pages.
+fn topics(dir: &Path) -> Result<Vec<String>> {
+    let mut topics: Vec<String> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".md").map(String::from)
+        })
+        .collect();
+    topics.sort();
+    Ok(topics)
+}
+
+/// Finds the topic named `query`, or the only one starting with it.
+///
+/// # Errors
+///
+/// Returns an error listing the candidates if no topic or several match.
+pub fn find_topic<'a>(topics: &'a [String], query: &str) -> Result<&'a str> {
+    if let Some(topic) = topics.iter().find(|topic| *topic == query) {
+        return Ok(topic);
+    }
+    let matches: Vec<&str> = topics
+        .iter()
+        .filter(|topic| topic.starts_with(query))
+        .map(String::as_str)
+        .collect();
+    match matches.as_slice() {
+        [topic] => Ok(topic),
+        [] => bail!(
+            "Unknown documentation topic '{query}'. Available topics: {}",
+            topics.join(", ")
+        ),
+        _ => bail!(
+            "Documentation topic '{query}' is ambiguous: {}",
+            matches.join(", ")
+        ),
+    }
+}
+
+/// Renders Markdown for the terminal.
+///
+/// Headings are bold when `colors` is set, code blocks are indented and
+/// kept verbatim, and inline markup is reduced to its text (see
+/// [`render_inline`]). Tables and other lines are printed as written.
+#[must_use]
+pub fn render_markdown(markdown: &str, colors: bool) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            let _ = writeln!(out, "    {line}");
+            continue;
+        }
+        let heading = trimmed.trim_start_matches('#');
+        if heading.len() < trimmed.len() && heading.starts_with(' ') {
+            let text = render_inline(heading.trim());
+            let _ = writeln!(out, "{}", output::apply(Style::Emphasis, &text, colors));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            let _ = writeln!(out, "{indent}- {}", render_inline(item));
+        } else if trimmed.starts_with('|') {
+            let _ = writeln!(out, "{line}");
+        } else {
+            let _ = writeln!(out, "{}", render_inline(line));
+        }
+    }
+    out
+}
+
+/// Removes backticks and `**` markers, and reduces links to their label.
+///
+/// The text of code spans is kept as written, so identifiers such as
+/// `INFS_DIST_SERVER` survive.
+fn render_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            let after = &rest[1..];
+            if let Some(end) = after.find('`') {
+                out.push_str(&after[..end]);
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            rest = after;
+            continue;
+        }
+        if let Some((label, after)) = link(rest) {
+            out.push_str(&render_inline(label));
+            rest = after;
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Parses `[label](url)` or `![label](url)` at the start of `text`.
+///
+/// Returns the label and the text after the link.
+fn link(text: &str) -> Option<(&str, &str)> {
+    let text = text.strip_prefix('!').unwrap_or(text);
+    let rest = text.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let after_label = &rest[label_end + 2..];
+    let url_end = after_label.find(')')?;
+    Some((&rest[..label_end], &after_label[url_end + 1..]))
+}
+
+/// Opens `page` with the platform's default browser.
+fn open_in_browser(page: &Path) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(page).status().with_context(|| {
+        format!(
+            "Failed to start a browser; open {} manually",
+            page.display()
+        )
+    })?;
+    if !status.success() {
+        bail!(
+            "Failed to open {} in a browser; open it manually",
+            page.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    /// Builds a documentation archive with the given pages.
+    fn archive(pages: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+        for (name, content) in pages {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn extracts_archive_once_and_lists_topics() {
+        let bundle = archive(&[
+            ("cli.md", "# CLI\n"),
+            ("cli.html", "<h1>CLI</h1>\n"),
+            ("overview.md", "# Overview\n"),
+            ("overview.html", "<h1>Overview</h1>\n"),
+        ]);
+        let dir = bundle_dir(&temp_test_dir("docs_extract"), &bundle);
+
+        assert_eq!(
+            ensure_extracted(&bundle, &dir).unwrap(),
+            ["cli", "overview"]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("cli.html")).unwrap(),
+            "<h1>CLI</h1>\n"
+        );
+
+        // A second run reuses the extracted pages.
+        std::fs::write(dir.join("cli.md"), "# Edited\n").unwrap();
+        ensure_extracted(&bundle, &dir).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("cli.md")).unwrap(),
+            "# Edited\n"
+        );
+        std::fs::remove_dir_all(dir.parent().unwrap().parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn bundles_with_different_content_use_different_directories() {
+        let cache = Path::new("/cache");
+        let first = bundle_dir(cache, &archive(&[("a.md", "a")]));
+        let second = bundle_dir(cache, &archive(&[("a.md", "b")]));
+        assert_ne!(first, second);
+        assert!(first.starts_with("/cache/docs"));
+    }
+
+    #[test]
+    fn find_topic_accepts_exact_name_or_unique_prefix() {
+        let topics: Vec<String> = ["cli", "install-linux", "install-macos", "type-system"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(find_topic(&topics, "cli").unwrap(), "cli");
+        assert_eq!(find_topic(&topics, "type").unwrap(), "type-system");
+        assert_eq!(find_topic(&topics, "install-mac").unwrap(), "install-macos");
+
+        let ambiguous = find_topic(&topics, "install").unwrap_err().to_string();
+        assert!(
+            ambiguous.contains("install-linux, install-macos"),
+            "{ambiguous}"
+        );
+        let unknown = find_topic(&topics, "stdlib").unwrap_err().to_string();
+        assert!(unknown.contains("Available topics: cli,"), "{unknown}");
+    }
+
+    #[test]
+    fn render_markdown_styles_headings_and_keeps_code() {
+        let markdown = "\
+# Title
+
+Set `INFS_DIST_SERVER` to a **mirror**, see [the guide](https://example.com).
+
+  * item with `code`
+
+```bash
+infs install --pre   # *not* markup
+```
+
+| a | b |
+";
+        assert_eq!(
+            render_markdown(markdown, false),
+            "\
+Title
+
+Set INFS_DIST_SERVER to a mirror, see the guide.
+
+  - item with code
+
+    infs install --pre   # *not* markup
+
+| a | b |
+"
+        );
+        assert!(render_markdown("## Usage\n", true).starts_with("\x1b[1mUsage"));
+    }
+
+    #[cfg(not(feature = "bundled-docs"))]
+    #[test]
+    fn nothing_is_embedded_without_feature() {
+        assert!(BUNDLE.is_empty());
+    }
+
+    #[cfg(feature = "bundled-docs")]
+    #[test]
+    fn embedded_archive_has_default_topic() {
+        // The size budget itself is checked at compile time.
+        let dir = temp_test_dir("docs_embedded");
+        let topics = ensure_extracted(BUNDLE, &dir).unwrap();
+        assert!(topics.iter().any(|topic| topic == DEFAULT_TOPIC));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -6,33 +6,65 @@
 //! ## Usage
 //!
 //! ```bash
-//! infs doctor
+//! infs doctor         # Report problems
+//! infs doctor --fix   # Fix what can be fixed locally, then report
 //! ```
 //!
 //! ## Checks Performed
 //!
+//! - infs binary and toolchain bin directory on PATH
 //! - Platform detection
 //! - Toolchain directory existence
 //! - Default toolchain configuration
 //! - inf-llc binary presence
 //! - rust-lld binary presence
 //! - libLLVM shared library (Linux only)
+//! - coqc and wasmtime versions against the compatibility table
+//! - Leftover ~/.infs root, several infc on PATH, and repeated PATH blocks
+//!   in shell profiles
+//! - Broken symlinks and non-executable toolchain binaries
+//!
+//! ## Fixes
+//!
+//! With `--fix`, executable permissions, broken or missing symlinks and the
+//! PATH entry in the shell profile are repaired first (see
+//! [`fix`](crate::toolchain::fix)). Each change is listed, problems that
+//! need a manual step are listed as warnings, and the checks then run on the
+//! repaired installation.
 
 use anyhow::Result;
+use clap::Args;
 
+use crate::output::{self, Stream, Style};
 use crate::toolchain::ToolchainPaths;
 use crate::toolchain::conflict::{detect_path_conflicts, format_doctor_conflict_warning};
 use crate::toolchain::doctor::{DoctorCheckStatus, run_all_checks};
+use crate::toolchain::fix::{FixOutcome, apply_fixes};
+use crate::toolchain::lock::ToolchainLock;
+
+/// Arguments for the doctor command.
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Fix broken symlinks, executable permissions and PATH before checking.
+    #[clap(long)]
+    pub fix: bool,
+}
 
 /// Executes the doctor command.
 ///
-/// Runs all health checks and displays the results.
+/// Runs all health checks and displays the results, after applying the
+/// fixes when `--fix` is given.
 ///
 /// # Errors
 ///
-/// Returns an error if critical checks fail to execute (not if they report failures).
-#[allow(clippy::unnecessary_wraps, clippy::unused_async)]
-pub async fn execute() -> Result<()> {
+/// Returns an error if the toolchain directory cannot be determined or
+/// locked for `--fix`. Checks that report failures are not errors.
+#[allow(clippy::unused_async)]
+pub async fn execute(args: &DoctorArgs) -> Result<()> {
+    if args.fix {
+        run_fixes(&ToolchainPaths::new()?)?;
+    }
+
     println!("Checking Inference toolchain installation...");
     println!();
 
@@ -42,7 +74,12 @@ pub async fn execute() -> Result<()> {
     let mut has_warnings = false;
 
     for check in &checks {
-        let prefix = check.prefix();
+        let style = match check.status {
+            DoctorCheckStatus::Ok => Style::Success,
+            DoctorCheckStatus::Warning => Style::Warning,
+            DoctorCheckStatus::Error => Style::Error,
+        };
+        let prefix = output::paint(Stream::Stdout, style, check.prefix());
         println!("  {prefix} {}: {}", check.name, check.message);
         match check.status {
             DoctorCheckStatus::Ok => {}
@@ -56,7 +93,10 @@ pub async fn execute() -> Result<()> {
         if !conflicts.is_empty() {
             has_warnings = true;
             println!();
-            println!("  [WARN] PATH conflict detected:");
+            println!(
+                "  {} PATH conflict detected:",
+                output::paint(Stream::Stdout, Style::Warning, "[WARN]")
+            );
             for line in format_doctor_conflict_warning(&conflicts) {
                 if !line.is_empty() {
                     println!("         {line}");
@@ -77,3 +117,29 @@ pub async fn execute() -> Result<()> {
 
     Ok(())
 }
+
+/// Applies the fixes and prints what changed and what could not be fixed.
+///
+/// # Errors
+///
+/// Returns an error if the toolchain lock cannot be taken.
+fn run_fixes(paths: &ToolchainPaths) -> Result<()> {
+    let _lock = ToolchainLock::acquire(paths)?;
+    println!("Fixing Inference toolchain installation...");
+    println!();
+
+    let outcomes = apply_fixes(paths);
+    if outcomes.is_empty() {
+        println!("  Nothing to fix.");
+    }
+    for outcome in &outcomes {
+        let (style, prefix, name, message) = match outcome {
+            FixOutcome::Fixed { name, change } => (Style::Success, "[FIXED]", name, change),
+            FixOutcome::NotFixable { name, reason } => (Style::Warning, "[WARN]", name, reason),
+        };
+        let prefix = output::paint(Stream::Stdout, style, prefix);
+        println!("  {prefix} {name}: {message}");
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Env command for the infs CLI.
+//!
+//! Prints a compact summary of the environment infs runs in, combining
+//! the details reported by `infs version --verbose`, `infs doctor`, and
+//! `infs which`. The text output is meant to be pasted into issues and chat.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs env          # Key/value block
+//! infs env --json   # Output in JSON format
+//! ```
+//!
+//! ## Output Format
+//!
+//! ```text
+//! infs_version:         0.1.0
+//! git_commit:           abc1234
+//! build_date:           2025-01-01T00:00:00Z
+//! platform:             linux-x64
+//! os:                   linux
+//! arch:                 x86_64
+//! libc:                 glibc
+//! inference_home:       /home/user/.inference
+//! default_toolchain:    0.2.0
+//! installed_toolchains: 0.1.0, 0.2.0
+//! infc:                 /home/user/.inference/toolchains/0.2.0/bin/infc (via managed toolchain)
+//! coqc:                 /usr/bin/coqc (The Rocq Prover, version 9.0.0)
+//! wasmtime:             not found
+//! ```
+//!
+//! Each probe degrades independently: a failure is reported in place of the
+//! value instead of aborting the command.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+use crate::commands::version::{build_date, git_commit};
+use crate::commands::which::{ResolvedInfc, resolve_infc_report};
+use crate::toolchain::compat::{ExternalTool, tool_version_line};
+use crate::toolchain::{Platform, ToolchainPaths};
+
+/// Arguments for the env command.
+#[derive(Args)]
+pub struct EnvArgs {
+    /// Show the report in JSON format.
+    #[clap(long, short = 'j')]
+    pub json: bool,
+}
+
+/// Detection result for an external tool.
+#[derive(Debug, Clone, Serialize)]
+struct ToolReport {
+    name: String,
+    found: bool,
+    path: Option<String>,
+    version: Option<String>,
+}
+
+/// Full report for the env command.
+#[derive(Debug, Clone, Serialize)]
+struct EnvReport {
+    infs_version: String,
+    git_commit: String,
+    build_date: String,
+    platform: Option<String>,
+    os: String,
+    arch: String,
+    libc: Option<String>,
+    inference_home: Option<String>,
+    default_toolchain: Option<String>,
+    installed_toolchains: Vec<String>,
+    infc: Option<ResolvedInfc>,
+    infc_error: Option<String>,
+    tools: Vec<ToolReport>,
+}
+
+/// Executes the env command.
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+pub fn execute(args: &EnvArgs) -> Result<()> {
+    let paths = ToolchainPaths::new().ok();
+    let report = collect_report(paths.as_ref());
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_text(&report));
+    }
+
+    Ok(())
+}
+
+/// Gathers the environment details.
+///
+/// `paths` is `None` when the infs home directory cannot be determined.
+fn collect_report(paths: Option<&ToolchainPaths>) -> EnvReport {
+    let platform = Platform::detect().ok();
+    let default_toolchain = paths.and_then(|p| p.get_default_version().ok().flatten());
+    let installed_toolchains = paths
+        .and_then(|p| p.list_installed_versions().ok())
+        .unwrap_or_default();
+    let (infc, infc_error) = resolve_infc_report();
+
+    EnvReport {
+        infs_version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: git_commit().to_string(),
+        build_date: build_date(),
+        platform: platform.map(|p| p.to_string()),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        libc: platform
+            .and_then(Platform::libc)
+            .map(|libc| libc.to_string()),
+        inference_home: paths.map(|p| p.root.display().to_string()),
+        default_toolchain,
+        installed_toolchains,
+        infc,
+        infc_error,
+        tools: [ExternalTool::Coqc, ExternalTool::Wasmtime]
+            .into_iter()
+            .map(probe_tool)
+            .collect(),
+    }
+}
+
+/// Looks up an external tool in `PATH` and asks it for its version.
+fn probe_tool(tool: ExternalTool) -> ToolReport {
+    let name = tool.binary_name();
+    let path = which::which(name).ok();
+
+    ToolReport {
+        name: name.to_string(),
+        found: path.is_some(),
+        version: path.as_ref().and_then(|_| tool_version_line(tool)),
+        path: path.map(|p| p.display().to_string()),
+    }
+}
+
+/// Formats the report as an aligned key/value block.
+fn format_text(report: &EnvReport) -> String {
+    let none = || "(none)".to_string();
+    let unknown = || "unknown".to_string();
+
+    let infc = match (&report.infc, &report.infc_error) {
+        (Some(infc), _) => format!("{} (via {})", infc.path, infc.source),
+        (None, Some(error)) => error.clone(),
+        (None, None) => "not found".to_string(),
+    };
+
+    let mut rows = vec![
+        ("infs_version", report.infs_version.clone()),
+        ("git_commit", report.git_commit.clone()),
+        ("build_date", report.build_date.clone()),
+        ("platform", report.platform.clone().unwrap_or_else(unknown)),
+        ("os", report.os.clone()),
+        ("arch", report.arch.clone()),
+        (
+            "libc",
+            report.libc.clone().unwrap_or_else(|| "n/a".to_string()),
+        ),
+        (
+            "inference_home",
+            report.inference_home.clone().unwrap_or_else(unknown),
+        ),
+        (
+            "default_toolchain",
+            report.default_toolchain.clone().unwrap_or_else(none),
+        ),
+        (
+            "installed_toolchains",
+            if report.installed_toolchains.is_empty() {
+                none()
+            } else {
+                report.installed_toolchains.join(", ")
+            },
+        ),
+        ("infc", infc),
+    ];
+
+    for tool in &report.tools {
+        let value = match (&tool.path, &tool.version) {
+            (Some(path), Some(version)) => format!("{path} ({version})"),
+            (Some(path), None) => format!("{path} (version unknown)"),
+            (None, _) => "not found".to_string(),
+        };
+        rows.push((tool.name.as_str(), value));
+    }
+
+    let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0) + 1;
+    let mut out = String::new();
+    for (key, value) in rows {
+        let _ = writeln!(out, "{:width$} {value}", format!("{key}:"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::env;
+
+    #[test]
+    #[serial_test::serial]
+    fn report_includes_version_platform_and_toolchains() {
+        let root = temp_test_dir("env_report");
+        let paths = ToolchainPaths::with_root(root.clone());
+        for version in ["0.1.0", "0.2.0"] {
+            std::fs::create_dir_all(paths.toolchain_bin_dir(version))
+                .expect("Should create toolchain dir");
+        }
+        paths
+            .set_default_version("0.2.0")
+            .expect("Should set default");
+
+        let report = collect_report(Some(&paths));
+        let text = format_text(&report);
+
+        assert_eq!(report.infs_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.os, env::consts::OS);
+        assert_eq!(report.arch, env::consts::ARCH);
+        assert_eq!(
+            report.platform,
+            Platform::detect().ok().map(|p| p.to_string())
+        );
+        assert_eq!(report.default_toolchain.as_deref(), Some("0.2.0"));
+        assert_eq!(report.installed_toolchains, vec!["0.1.0", "0.2.0"]);
+        assert_eq!(report.inference_home, Some(root.display().to_string()));
+
+        assert!(text.contains(&format!(
+            "infs_version:         {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(text.contains("platform:"));
+        assert!(text.contains("installed_toolchains: 0.1.0, 0.2.0"));
+        assert!(text.contains("coqc:"));
+        assert!(text.contains("wasmtime:"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn report_degrades_without_inference_home() {
+        let report = collect_report(None);
+        let text = format_text(&report);
+
+        assert!(report.inference_home.is_none());
+        assert!(report.installed_toolchains.is_empty());
+        assert!(text.contains("inference_home:"));
+        assert!(text.contains("unknown"));
+        assert!(text.contains("default_toolchain:"));
+        assert!(text.contains("(none)"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn json_report_has_stable_field_names() {
+        let report = collect_report(None);
+        let json: serde_json::Value =
+            serde_json::to_value(&report).expect("Should serialize report");
+
+        for field in [
+            "infs_version",
+            "git_commit",
+            "platform",
+            "os",
+            "arch",
+            "inference_home",
+            "default_toolchain",
+            "installed_toolchains",
+            "infc",
+            "tools",
+        ] {
+            assert!(json.get(field).is_some(), "missing field {field}");
+        }
+        assert_eq!(json["tools"][0]["name"], "coqc");
+        assert_eq!(json["tools"][1]["name"], "wasmtime");
+    }
+}
@@ -0,0 +1,180 @@
+//! Format command for the infs CLI.
+//!
+//! Formats WebAssembly text (`.wat`) files with `wat-fmt`, such as the ones
+//! written by `infs build --emit wat`.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs fmt                      # Print every .wat file in the current project, formatted
+//! infs fmt out/main.wat         # Print one file, formatted
+//! infs fmt --in-place           # Rewrite files that are not formatted
+//! infs fmt --check              # Exit with code 1 if any file is not formatted
+//! ```
+//!
+//! Directories are searched recursively; hidden directories (such as
+//! `.git`) are skipped. `--check` prints a unified diff for every file that
+//! would change, like `rustfmt --check`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use similar::TextDiff;
+
+use crate::errors::InfsError;
+
+/// Extension of the files that `fmt` formats.
+const WAT_EXTENSION: &str = "wat";
+
+/// Arguments for the fmt command.
+#[derive(Args)]
+pub struct FmtArgs {
+    /// File or directory to format (defaults to current directory).
+    #[clap(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Report files that are not formatted instead of printing them.
+    ///
+    /// Exits with code 1 if any file would change.
+    #[clap(long, conflicts_with = "in_place")]
+    pub check: bool,
+
+    /// Rewrite files that are not formatted.
+    #[clap(long = "in-place")]
+    pub in_place: bool,
+}
+
+/// Executes the fmt command.
+///
+/// # Errors
+///
+/// Returns an error if the path does not exist, a file cannot be read or
+/// written, or with `--check` if any file is not formatted.
+pub fn execute(args: &FmtArgs) -> Result<()> {
+    if !args.path.exists() {
+        return Err(InfsError::file_not_found(args.path.clone()).into());
+    }
+    let files = wat_files(&args.path)?;
+    if files.is_empty() {
+        println!("No .wat files found in {}", args.path.display());
+        return Ok(());
+    }
+
+    let mut unformatted = 0;
+    for file in &files {
+        let source = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let formatted = format_source(&source);
+        if args.check {
+            if formatted != source {
+                print!("{}", unified_diff(file, &source, &formatted));
+                unformatted += 1;
+            }
+        } else if args.in_place {
+            if formatted != source {
+                std::fs::write(file, &formatted).map_err(|e| {
+                    InfsError::io_error(format!("Failed to write {}", file.display()), e)
+                })?;
+                println!("Formatted {}", file.display());
+            }
+        } else {
+            print!("{formatted}");
+        }
+    }
+
+    if unformatted > 0 {
+        eprintln!("{unformatted} of {} file(s) are not formatted", files.len());
+        return Err(InfsError::process_exit_code(1).into());
+    }
+    Ok(())
+}
+
+/// Formats WebAssembly text, ending non-empty output with a newline.
+#[must_use]
+pub fn format_source(source: &str) -> String {
+    let mut formatted = wat_fmt::format(source);
+    if !formatted.is_empty() && !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+    formatted
+}
+
+/// Returns the `.wat` files at `path`, sorted.
+///
+/// A file is returned as is, whatever its extension. Directories are
+/// searched recursively, skipping hidden directories.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be read.
+fn wat_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory {}", dir.display()))?;
+        for entry in entries {
+            let entry =
+                entry.with_context(|| format!("Failed to read directory {}", dir.display()))?;
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                if !entry.file_name().to_string_lossy().starts_with('.') {
+                    dirs.push(entry_path);
+                }
+            } else if entry_path
+                .extension()
+                .is_some_and(|ext| ext == WAT_EXTENSION)
+            {
+                files.push(entry_path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns a unified diff from `old` to `new` for `path`.
+fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    let path = path.display().to_string();
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&path, &path)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    #[test]
+    fn formatted_output_is_stable() {
+        let formatted = format_source("(module (func $f i32.const 1 drop))");
+        assert!(formatted.starts_with("(module\n  (func $f"));
+        assert!(formatted.ends_with(")\n"));
+        assert_eq!(format_source(&formatted), formatted);
+        assert_eq!(format_source(""), "");
+    }
+
+    #[test]
+    fn finds_wat_files_recursively_skipping_hidden_directories() {
+        let dir = temp_test_dir("fmt_walk");
+        std::fs::create_dir_all(dir.join("out/nested")).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join("out/main.wat"), "(module)").unwrap();
+        std::fs::write(dir.join("out/nested/lib.wat"), "(module)").unwrap();
+        std::fs::write(dir.join("out/main.wasm"), b"\0asm").unwrap();
+        std::fs::write(dir.join(".git/ignored.wat"), "(module)").unwrap();
+
+        let files = wat_files(&dir).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.join("out/main.wat"), dir.join("out/nested/lib.wat")]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
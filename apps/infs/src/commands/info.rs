@@ -0,0 +1,636 @@
+//! Info command for the infs CLI.
+//!
+//! Inspects a build artifact without running it:
+//!
+//! - `.wasm` modules are parsed and summarized: section sizes, imports,
+//!   exports, memory and table limits, and custom sections.
+//! - `.v` files generated for Rocq are scanned for theorem and lemma names,
+//!   and their SHA-256 hash is printed.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs info out/main.wasm          # Summarize a WASM module
+//! infs info proofs/main.v          # List theorems and the file hash
+//! infs info out/main.wasm --json   # Output in JSON format
+//! ```
+//!
+//! ## Output Format
+//!
+//! ```text
+//! File:    out/trivial.wasm
+//! Kind:    wasm
+//! Size:    125 bytes
+//!
+//! Sections:
+//!   type                 5 bytes
+//!   function             2 bytes
+//!   memory               3 bytes
+//!   global               8 bytes
+//!   export              24 bytes
+//!   code                 6 bytes
+//!   custom "name"       55 bytes
+//!
+//! Imports: (none)
+//!
+//! Exports:
+//!   memory       memory 0
+//!   hello_world  func 0
+//!
+//! Memories:
+//!   0: min 2 pages, no max
+//! ...
+//! ```
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use inf_wasmparser::{Encoding, ExternalKind, Parser, Payload, TypeRef};
+use serde::Serialize;
+
+use crate::errors::InfsError;
+use crate::toolchain::verify::compute_sha256;
+
+/// Rocq vernacular keywords that introduce a named proof obligation.
+const THEOREM_KEYWORDS: &[&str] = &[
+    "Theorem",
+    "Lemma",
+    "Corollary",
+    "Proposition",
+    "Fact",
+    "Remark",
+];
+
+/// Arguments for the info command.
+#[derive(Args)]
+pub struct InfoArgs {
+    /// Path to a `.wasm` module or a generated `.v` file.
+    pub path: PathBuf,
+
+    /// Show the report in JSON format.
+    #[clap(long, short = 'j')]
+    pub json: bool,
+}
+
+/// Report for an inspected file, tagged by file kind.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum InfoReport {
+    Wasm(WasmInfo),
+    Rocq(RocqInfo),
+}
+
+/// Summary of a WASM module.
+#[derive(Debug, Clone, Default, Serialize)]
+struct WasmInfo {
+    path: String,
+    size: u64,
+    sections: Vec<SectionInfo>,
+    imports: Vec<ImportInfo>,
+    exports: Vec<ExportInfo>,
+    memories: Vec<MemoryInfo>,
+    tables: Vec<TableInfo>,
+    custom_sections: Vec<SectionInfo>,
+}
+
+/// A section and the size of its contents in bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct SectionInfo {
+    name: String,
+    size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ImportInfo {
+    module: String,
+    name: String,
+    kind: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ExportInfo {
+    name: String,
+    kind: &'static str,
+    index: u32,
+}
+
+/// Limits of a linear memory, in 64 KiB pages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct MemoryInfo {
+    initial: u64,
+    maximum: Option<u64>,
+    memory64: bool,
+    shared: bool,
+    imported: bool,
+}
+
+/// Limits of a table, in elements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TableInfo {
+    element_type: String,
+    initial: u64,
+    maximum: Option<u64>,
+    table64: bool,
+    imported: bool,
+}
+
+/// Summary of a generated Rocq file.
+#[derive(Debug, Clone, Serialize)]
+struct RocqInfo {
+    path: String,
+    size: u64,
+    sha256: String,
+    theorems: Vec<TheoremInfo>,
+}
+
+/// A named theorem-like declaration and the line it starts on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TheoremInfo {
+    kind: String,
+    name: String,
+    line: usize,
+}
+
+/// Executes the info command.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file does not exist or cannot be read
+/// - The file is neither `.wasm` nor `.v`
+/// - A `.wasm` file is not a valid WebAssembly module
+pub fn execute(args: &InfoArgs) -> Result<()> {
+    if !args.path.exists() {
+        return Err(InfsError::file_not_found(args.path.clone()))
+            .with_context(|| format!("Path not found: {}", args.path.display()));
+    }
+
+    let report = inspect(&args.path)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_text(&report));
+    }
+
+    Ok(())
+}
+
+/// Builds the report for `path`, choosing the inspector by file extension.
+fn inspect(path: &Path) -> Result<InfoReport> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("wasm") => {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let mut info = inspect_wasm(&bytes).with_context(|| {
+                format!("Failed to parse WebAssembly module {}", path.display())
+            })?;
+            info.path = path.display().to_string();
+            Ok(InfoReport::Wasm(info))
+        }
+        Some("v") => {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Ok(InfoReport::Rocq(RocqInfo {
+                path: path.display().to_string(),
+                size: content.len() as u64,
+                sha256: compute_sha256(path)?,
+                theorems: scan_theorems(&content),
+            }))
+        }
+        _ => Err(InfsError::invalid_arguments(format!(
+            "cannot inspect {}: expected a .wasm or .v file",
+            path.display()
+        ))
+        .into()),
+    }
+}
+
+/// Parses a WASM module and collects its layout.
+///
+/// Function bodies are not decoded, so modules using the non-deterministic
+/// instruction extensions are summarized like any other module.
+fn inspect_wasm(bytes: &[u8]) -> Result<WasmInfo> {
+    let mut info = WasmInfo {
+        size: bytes.len() as u64,
+        ..WasmInfo::default()
+    };
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        let payload = payload?;
+        if let Some((id, range)) = payload.as_section() {
+            let name = match &payload {
+                Payload::CustomSection(reader) => format!("custom \"{}\"", reader.name()),
+                _ => section_name(id).to_string(),
+            };
+            info.sections.push(SectionInfo {
+                name,
+                size: range.len(),
+            });
+        }
+
+        match payload {
+            Payload::Version {
+                encoding: Encoding::Component,
+                ..
+            } => {
+                anyhow::bail!("WebAssembly components are not supported, only core modules");
+            }
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import?;
+                    match import.ty {
+                        TypeRef::Memory(memory) => info.memories.push(MemoryInfo {
+                            initial: memory.initial,
+                            maximum: memory.maximum,
+                            memory64: memory.memory64,
+                            shared: memory.shared,
+                            imported: true,
+                        }),
+                        TypeRef::Table(table) => info.tables.push(TableInfo {
+                            element_type: table.element_type.to_string(),
+                            initial: table.initial,
+                            maximum: table.maximum,
+                            table64: table.table64,
+                            imported: true,
+                        }),
+                        _ => {}
+                    }
+                    info.imports.push(ImportInfo {
+                        module: import.module.to_string(),
+                        name: import.name.to_string(),
+                        kind: type_ref_kind(&import.ty),
+                    });
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory?;
+                    info.memories.push(MemoryInfo {
+                        initial: memory.initial,
+                        maximum: memory.maximum,
+                        memory64: memory.memory64,
+                        shared: memory.shared,
+                        imported: false,
+                    });
+                }
+            }
+            Payload::TableSection(reader) => {
+                for table in reader {
+                    let table = table?.ty;
+                    info.tables.push(TableInfo {
+                        element_type: table.element_type.to_string(),
+                        initial: table.initial,
+                        maximum: table.maximum,
+                        table64: table.table64,
+                        imported: false,
+                    });
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    info.exports.push(ExportInfo {
+                        name: export.name.to_string(),
+                        kind: external_kind(export.kind),
+                        index: export.index,
+                    });
+                }
+            }
+            Payload::CustomSection(reader) => info.custom_sections.push(SectionInfo {
+                name: reader.name().to_string(),
+                size: reader.data().len(),
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+/// Returns the name of a core module section by id.
+fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+fn type_ref_kind(ty: &TypeRef) -> &'static str {
+    match ty {
+        TypeRef::Func(_) => "func",
+        TypeRef::Table(_) => "table",
+        TypeRef::Memory(_) => "memory",
+        TypeRef::Global(_) => "global",
+        TypeRef::Tag(_) => "tag",
+    }
+}
+
+fn external_kind(kind: ExternalKind) -> &'static str {
+    match kind {
+        ExternalKind::Func => "func",
+        ExternalKind::Table => "table",
+        ExternalKind::Memory => "memory",
+        ExternalKind::Global => "global",
+        ExternalKind::Tag => "tag",
+    }
+}
+
+/// Finds theorem-like declarations in Rocq source.
+///
+/// This is a line-based scan rather than a parser: a declaration is found
+/// when a line starts with one of [`THEOREM_KEYWORDS`] followed by a name.
+fn scan_theorems(content: &str) -> Vec<TheoremInfo> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let mut words = line.split_whitespace();
+            let keyword = words.next()?;
+            if !THEOREM_KEYWORDS.contains(&keyword) {
+                return None;
+            }
+            let name: String = words
+                .next()?
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '\'' | '.'))
+                .collect();
+            if name.is_empty() {
+                return None;
+            }
+            Some(TheoremInfo {
+                kind: keyword.to_string(),
+                name,
+                line: index + 1,
+            })
+        })
+        .collect()
+}
+
+/// Formats the report as aligned text.
+fn format_text(report: &InfoReport) -> String {
+    let mut out = String::new();
+    match report {
+        InfoReport::Wasm(info) => {
+            let _ = writeln!(out, "File:    {}", info.path);
+            let _ = writeln!(out, "Kind:    wasm");
+            let _ = writeln!(out, "Size:    {} bytes", info.size);
+
+            let sections = info
+                .sections
+                .iter()
+                .map(|s| format!("{:<16} {:>6} bytes", s.name, s.size))
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Sections", &sections);
+
+            let imports = info
+                .imports
+                .iter()
+                .map(|i| format!("{}.{}  {}", i.module, i.name, i.kind))
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Imports", &imports);
+
+            let width = info.exports.iter().map(|e| e.name.len()).max().unwrap_or(0);
+            let exports = info
+                .exports
+                .iter()
+                .map(|e| format!("{:<width$}  {} {}", e.name, e.kind, e.index))
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Exports", &exports);
+
+            let memories = info
+                .memories
+                .iter()
+                .enumerate()
+                .map(|(index, m)| {
+                    let mut line = format!(
+                        "{index}: {}{}",
+                        format_limits(m.initial, m.maximum, "pages"),
+                        if m.memory64 { ", 64-bit" } else { "" }
+                    );
+                    if m.shared {
+                        line.push_str(", shared");
+                    }
+                    if m.imported {
+                        line.push_str(", imported");
+                    }
+                    line
+                })
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Memories", &memories);
+
+            let tables = info
+                .tables
+                .iter()
+                .enumerate()
+                .map(|(index, t)| {
+                    format!(
+                        "{index}: {} {}{}",
+                        t.element_type,
+                        format_limits(t.initial, t.maximum, "elements"),
+                        if t.imported { ", imported" } else { "" }
+                    )
+                })
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Tables", &tables);
+
+            let custom = info
+                .custom_sections
+                .iter()
+                .map(|s| format!("{} ({} bytes)", s.name, s.size))
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Custom sections", &custom);
+        }
+        InfoReport::Rocq(info) => {
+            let _ = writeln!(out, "File:    {}", info.path);
+            let _ = writeln!(out, "Kind:    rocq");
+            let _ = writeln!(out, "Size:    {} bytes", info.size);
+            let _ = writeln!(out, "SHA256:  {}", info.sha256);
+
+            let theorems = info
+                .theorems
+                .iter()
+                .map(|t| format!("{} {} (line {})", t.kind, t.name, t.line))
+                .collect::<Vec<_>>();
+            write_list(&mut out, "Theorems", &theorems);
+        }
+    }
+    out
+}
+
+/// Formats memory or table limits in `unit`.
+fn format_limits(initial: u64, maximum: Option<u64>, unit: &str) -> String {
+    match maximum {
+        Some(maximum) => format!("min {initial} {unit}, max {maximum} {unit}"),
+        None => format!("min {initial} {unit}, no max"),
+    }
+}
+
+/// Appends a titled, indented list, or `(none)` when `items` is empty.
+fn write_list(out: &mut String, title: &str, items: &[String]) {
+    out.push('\n');
+    if items.is_empty() {
+        let _ = writeln!(out, "{title}: (none)");
+        return;
+    }
+    let _ = writeln!(out, "{title}:");
+    for item in items {
+        let _ = writeln!(out, "  {item}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    fn wasm_fixture(name: &str) -> WasmInfo {
+        let bytes = std::fs::read(fixture(name)).expect("Should read fixture");
+        inspect_wasm(&bytes).expect("Should parse fixture")
+    }
+
+    #[test]
+    fn trivial_module_lists_exports_and_memory() {
+        let info = wasm_fixture("trivial.wasm");
+
+        assert_eq!(info.size, 125);
+        assert_eq!(
+            info.exports,
+            vec![
+                ExportInfo {
+                    name: "memory".to_string(),
+                    kind: "memory",
+                    index: 0,
+                },
+                ExportInfo {
+                    name: "hello_world".to_string(),
+                    kind: "func",
+                    index: 0,
+                },
+            ]
+        );
+        assert!(info.imports.is_empty());
+        assert_eq!(info.memories.len(), 1);
+        assert_eq!(info.memories[0].initial, 2);
+        assert!(!info.memories[0].imported);
+        assert!(info.tables.is_empty());
+    }
+
+    #[test]
+    fn section_sizes_add_up_to_the_file_size() {
+        let info = wasm_fixture("trivial.wasm");
+        let names = info
+            .sections
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "type",
+                "function",
+                "memory",
+                "global",
+                "export",
+                "code",
+                "custom \"name\""
+            ]
+        );
+        assert_eq!(info.custom_sections.len(), 1);
+        assert_eq!(info.custom_sections[0].name, "name");
+
+        // Header plus, per section, one id byte, a one-byte LEB128 size, and
+        // the contents.
+        let total: usize = info.sections.iter().map(|s| 2 + s.size).sum();
+        assert_eq!(total as u64 + 8, info.size);
+    }
+
+    #[test]
+    fn module_with_nondet_instructions_is_summarized() {
+        let info = wasm_fixture("nondet.wasm");
+        assert!(!info.exports.is_empty());
+        assert!(info.sections.iter().any(|s| s.name == "code"));
+    }
+
+    #[test]
+    fn invalid_module_is_rejected() {
+        assert!(inspect_wasm(b"\0asm\x01\0\0\0\x07\xff").is_err());
+        assert!(inspect_wasm(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn scan_theorems_finds_named_declarations() {
+        let content = "Definition f := 1.\n\
+                       Theorem f_is_one : f = 1.\n\
+                       Proof. reflexivity. Qed.\n\
+                       \n  Lemma helper' (n : nat) : n = n.\n\
+                       (* Theorem commented_out : True. *)\n\
+                       Corollary c: True.\n";
+        let theorems = scan_theorems(content);
+        assert_eq!(
+            theorems
+                .iter()
+                .map(|t| (t.kind.as_str(), t.name.as_str(), t.line))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Theorem", "f_is_one", 2),
+                ("Lemma", "helper'", 5),
+                ("Corollary", "c", 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_extension_is_rejected() {
+        let err = inspect(&fixture("trivial.inf")).unwrap_err();
+        assert!(err.to_string().contains("expected a .wasm or .v file"));
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(crate::errors::EXIT_INVALID_ARGUMENTS)
+        );
+    }
+
+    #[test]
+    fn text_output_lists_sections_and_exports() {
+        let mut info = wasm_fixture("trivial.wasm");
+        info.path = "trivial.wasm".to_string();
+        let text = format_text(&InfoReport::Wasm(info));
+
+        assert!(text.contains("Kind:    wasm"));
+        assert!(text.contains("custom \"name\""));
+        assert!(text.contains("Imports: (none)"));
+        assert!(text.contains("  hello_world  func 0"));
+        assert!(text.contains("  0: min 2 pages, no max"));
+    }
+
+    #[test]
+    fn json_report_is_tagged_by_kind() {
+        let report = inspect(&fixture("theorems.v")).expect("Should inspect");
+        let json = serde_json::to_value(&report).expect("Should serialize");
+
+        assert_eq!(json["kind"], "rocq");
+        assert_eq!(json["sha256"].as_str().map(str::len), Some(64));
+        assert_eq!(json["theorems"][0]["name"], "add_comm");
+    }
+}
@@ -14,6 +14,8 @@
 //! - Creates `Inference.toml` in the current directory
 //! - Creates `src/main.inf` with a basic entry point
 //! - If `.git/` exists, creates `.gitignore` and `.gitkeep` files (without overwriting)
+//! - Records the template in `Inference.toml` and `.inference/scaffold.lock`
+//!   for `infs scaffold upgrade`
 //! - Project name defaults to the directory name if not provided
 
 use anyhow::Result;
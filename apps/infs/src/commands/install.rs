@@ -9,30 +9,153 @@
 //! infs install          # Install latest stable version
 //! infs install 0.1.0    # Install specific version
 //! infs install latest   # Explicitly install latest stable
+//! infs install "^0.2"   # Install the highest 0.2.x version
+//! infs install 0.2 --yes # Same, without asking which 0.2.x to install
+//! infs install --pre    # Install the latest version, including prereleases
+//! infs install --no-modify-path  # Leave shell profiles and the registry alone
+//! infs install --progress plain   # Log lines instead of an in-place bar
+//! infs install --refresh          # Ignore the cached release manifest
+//!
+//! # Install from a local archive without network access
+//! infs install --from ./infc-linux-x64-0.1.0.tar.gz
+//! infs install --from ./toolchain.zip --as-version 0.1.0 --checksum abc123...
 //! ```
+//!
+//! ## Signatures
+//!
+//! With `security.public_key` set in `config.toml`, downloaded archives are
+//! checked against the minisign signature published at
+//! `{artifact.url}.minisig`; local archives against a `.minisig` file next
+//! to the archive. An unsigned archive, or any archive without a configured
+//! key, is installed with a warning unless `security.require_signatures` is
+//! set.
+//!
+//! ## Version Selection
+//!
+//! When a version range matches several releases, or only prereleases, and
+//! both stdin and stdout are terminals, the matches are listed and the user
+//! picks one. `--yes` and non-interactive runs install the highest match
+//! (or fail with a hint to use `--pre` if only prereleases match).
+//!
+//! ## Progress
+//!
+//! On a terminal, download and extraction progress update a single line in
+//! place. When stdout is not a terminal (as in CI logs), or with
+//! `--progress plain`, a line is printed each time progress advances by
+//! 10%, at most every 5 seconds, followed by a summary when the phase
+//! finishes. `--progress none` prints only the steps.
+//!
+//! ## Manifest Cache
+//!
+//! When the release manifest is read from the cache, its age is printed to
+//! stderr, since a version published since then is not listed in it.
+//! `--refresh` fetches the manifest from the distribution server instead.
 
-use anyhow::Result;
-use clap::Args;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+
+use crate::confirm;
+use crate::errors::InfsError;
+use crate::logging::{self, Level, debug};
+use crate::output;
+use crate::toolchain::ProgressEvent;
+use crate::toolchain::compat::{SUPPORTED_TOOLCHAINS, toolchain_range_warning};
 use crate::toolchain::conflict::{detect_path_conflicts, format_conflict_warning};
-use crate::toolchain::paths::ToolchainMetadata;
-use crate::toolchain::{
-    Platform, ToolchainPaths, download_file, extract_archive, fetch_artifact,
-    set_executable_permissions, verify_checksum,
+use crate::toolchain::download::{
+    estimate_remaining, format_bytes, format_duration, format_progress, format_speed,
+};
+use crate::toolchain::installer::{
+    InstallCallback, InstallEvent, InstallOptions, InstallPhase, InstallRequest,
+    InstalledToolchain, Installer,
+};
+use crate::toolchain::manifest::{
+    Manifest, VersionEntry, is_prerelease, load_manifest, version_choices,
+};
+use crate::toolchain::shell::{
+    ConfigureResult, configure_path, format_manual_path_instruction, format_result_message,
 };
 
 /// Arguments for the install command.
 #[derive(Args)]
 pub struct InstallArgs {
-    /// Version to install (e.g., "0.1.0" or "latest").
+    /// Version to install (e.g., "0.1.0", "latest", or a range like "^0.2").
     ///
-    /// If omitted, installs the latest stable version.
+    /// If omitted, installs the latest stable version. A semver range
+    /// installs the highest matching version from the manifest.
     #[clap(default_value = "latest")]
     pub version: String,
+
+    /// Allow prerelease versions when resolving "latest" or a version range.
+    #[clap(long = "pre")]
+    pub pre: bool,
+
+    /// Install from a local `.zip` or `.tar.gz` archive instead of downloading.
+    ///
+    /// The version is taken from `--as-version`, the positional version
+    /// argument, or the archive file name (e.g., `infc-linux-x64-0.1.0.tar.gz`).
+    #[clap(long = "from", value_name = "ARCHIVE")]
+    pub from: Option<PathBuf>,
+
+    /// Version to register a local archive as (requires `--from`).
+    #[clap(long = "as-version", value_name = "VERSION", requires = "from")]
+    pub as_version: Option<String>,
+
+    /// Expected SHA256 checksum of a local archive (requires `--from`).
+    #[clap(long = "checksum", value_name = "SHA256", requires = "from")]
+    pub checksum: Option<String>,
+
+    /// Do not add the toolchain bin directory to PATH on first install.
+    ///
+    /// Symlinks are still created in `~/.inference/bin`; the shell profile
+    /// (or the Windows registry) is left untouched and the line to add
+    /// manually is printed instead.
+    #[clap(long = "no-modify-path")]
+    pub no_modify_path: bool,
+
+    /// How to show download and extraction progress.
+    #[clap(long, value_enum, default_value_t = ProgressMode::Auto)]
+    pub progress: ProgressMode,
+
+    /// Fetch the release manifest even if a cached copy exists.
+    #[clap(long)]
+    pub refresh: bool,
+}
+
+/// How installation progress is shown, as selected by `--progress`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Update a line in place on a terminal, `plain` otherwise.
+    #[default]
+    Auto,
+    /// Print a line every 10%, at most every 5 seconds.
+    Plain,
+    /// Print only the steps, without progress.
+    None,
+}
+
+impl ProgressMode {
+    /// Resolves `auto` to `plain` when stdout is not a terminal.
+    ///
+    /// After resolving, `Auto` means in-place progress on a terminal.
+    #[must_use]
+    fn resolve(self, is_tty: bool) -> Self {
+        match self {
+            Self::Auto if !is_tty => Self::Plain,
+            mode => mode,
+        }
+    }
 }
 
 /// Executes the install command.
 ///
+/// The work is done by [`Installer`]; this command picks the version,
+/// prints progress, and configures PATH after the first installation.
+///
 /// # Process
 ///
 /// 1. Detect the current platform
@@ -40,8 +163,9 @@ pub struct InstallArgs {
 /// 3. Find the artifact for the requested version and platform
 /// 4. Download the archive with progress display
 /// 5. Verify the SHA256 checksum
-/// 6. Extract to the toolchains directory
-/// 7. Set as default if it's the first installation
+/// 6. Verify the minisign signature, if one is published
+/// 7. Extract to the toolchains directory
+/// 8. Set as default if it's the first installation
 ///
 /// # Errors
 ///
@@ -51,89 +175,484 @@ pub struct InstallArgs {
 /// - Version is not found
 /// - Download fails
 /// - Checksum verification fails
+/// - The signature is invalid, or missing while signatures are required
 /// - Extraction fails
 pub async fn execute(args: &InstallArgs) -> Result<()> {
-    let platform = Platform::detect()?;
-    let paths = ToolchainPaths::new()?;
+    if let Some(archive_path) = &args.from {
+        return execute_local(args, archive_path);
+    }
 
-    paths.ensure_directories()?;
+    let mut installer = Installer::new()?;
 
-    let version_arg = if args.version == "latest" {
+    let mut version = if args.version == "latest" {
         None
     } else {
-        Some(args.version.as_str())
+        Some(args.version.clone())
     };
 
-    println!("Fetching release manifest...");
-    let (version, artifact) = fetch_artifact(version_arg, platform).await?;
-
-    // Handle the case when the requested version is already installed.
-    // If no default toolchain is set (e.g., user manually removed the default file
-    // or installed via another method), we set this version as default to ensure
-    // the toolchain is usable. This provides a graceful recovery path.
-    if paths.is_version_installed(&version) {
-        let current_default = paths.get_default_version()?;
-        if current_default.is_none() {
-            println!("Toolchain version {version} is already installed.");
-            println!("Setting {version} as default toolchain...");
-            paths.set_default_version(&version)?;
-            paths.update_symlinks(&version)?;
-        } else {
-            println!("Toolchain version {version} is already installed.");
-        }
-        return Ok(());
+    let (manifest, source) = load_manifest(args.refresh)
+        .await
+        .context("Failed to download release manifest")?;
+    if let Some(notice) = source.staleness_notice() {
+        eprintln!("{notice}");
+    }
+    if let Some(requested) = &version
+        && !confirm::assume_yes()
+        && is_interactive()
+        && let Some(selected) = select_version(&manifest, requested, args.pre)?
+    {
+        version = Some(selected);
     }
+    installer = installer.with_manifest(manifest);
+
+    let request = InstallRequest {
+        version,
+        options: InstallOptions {
+            include_prerelease: args.pre,
+            ..InstallOptions::default()
+        },
+    };
+    let toolchain = installer
+        .install(request, cli_progress(args.progress))
+        .await?;
+    report(&installer, &toolchain, args.no_modify_path);
+    Ok(())
+}
 
-    println!("Installing toolchain version {version} for {platform}...");
+/// Returns whether the user can answer prompts on this terminal.
+fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
 
-    let archive_filename = artifact.filename();
-    let archive_path = paths.download_path(archive_filename);
+/// Asks which version to install when `requested` is ambiguous.
+///
+/// Returns `None` if the request is not ambiguous (see [`version_choices`]).
+/// An empty answer or end of input selects the highest match.
+fn select_version(
+    manifest: &Manifest,
+    requested: &str,
+    include_prerelease: bool,
+) -> Result<Option<String>> {
+    let choices = version_choices(manifest, requested, include_prerelease);
+    if choices.is_empty() {
+        return Ok(None);
+    }
 
-    println!("Downloading from {}...", artifact.url);
-    download_file(&artifact.url, &archive_path).await?;
+    if choices.iter().all(|entry| is_prerelease(entry)) && !include_prerelease {
+        println!("Only prereleases match '{requested}':");
+    } else {
+        println!("Multiple versions match '{requested}':");
+    }
+    for (index, entry) in choices.iter().enumerate() {
+        println!("  {}", format_choice(index, entry));
+    }
 
-    println!("Verifying checksum...");
-    verify_checksum(&archive_path, &artifact.sha256)?;
+    let stdin = std::io::stdin();
+    loop {
+        print!("Select a version [1-{}] (default 1): ", choices.len());
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        if stdin.read_line(&mut answer)? == 0 {
+            println!();
+            return Ok(Some(choices[0].version.clone()));
+        }
+        match parse_choice(&answer, choices.len()) {
+            Some(index) => return Ok(Some(choices[index].version.clone())),
+            None => println!("Enter a number between 1 and {}.", choices.len()),
+        }
+    }
+}
 
-    println!("Extracting...");
-    let toolchain_dir = paths.toolchain_dir(&version);
-    extract_archive(&archive_path, &toolchain_dir)?;
+/// Formats the prompt line for the version at `index` (0-based).
+fn format_choice(index: usize, entry: &VersionEntry) -> String {
+    let tag = if is_prerelease(entry) {
+        " (prerelease)"
+    } else {
+        ""
+    };
+    format!("{}) {}{tag}", index + 1, entry.version)
+}
 
-    set_executable_permissions(&toolchain_dir)?;
+/// Parses a 1-based answer to the version prompt into an index.
+///
+/// An empty answer selects the first (highest) choice.
+fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Some(0);
+    }
+    answer
+        .parse::<usize>()
+        .ok()
+        .filter(|choice| (1..=count).contains(choice))
+        .map(|choice| choice - 1)
+}
 
-    let metadata = ToolchainMetadata::now();
-    paths.write_metadata(&version, &metadata)?;
+/// Installs a toolchain from a local archive, skipping the network entirely.
+fn execute_local(args: &InstallArgs, archive_path: &Path) -> Result<()> {
+    let version = resolve_local_version(args, archive_path)?;
+    let installer = Installer::new()?;
 
-    let installed_versions = paths.list_installed_versions()?;
-    let is_first_install = installed_versions.len() == 1 && installed_versions[0] == version;
-    let current_default = paths.get_default_version()?;
+    if !installer.paths().is_version_installed(&version) {
+        println!(
+            "Installing toolchain version {version} from {}...",
+            archive_path.display()
+        );
+    }
+    let toolchain = installer.install_archive(
+        archive_path,
+        &version,
+        args.checksum.as_deref(),
+        InstallOptions::default(),
+        &cli_progress(args.progress),
+    )?;
+    report(&installer, &toolchain, args.no_modify_path);
+    Ok(())
+}
 
-    if is_first_install || current_default.is_none() {
-        println!("Setting {version} as default toolchain...");
-        paths.set_default_version(&version)?;
-        paths.update_symlinks(&version)?;
+/// Prints the outcome of an installation and configures PATH after the
+/// first one.
+pub(crate) fn report(installer: &Installer, toolchain: &InstalledToolchain, no_modify_path: bool) {
+    let version = &toolchain.version;
+    if toolchain.already_installed {
+        println!("Toolchain version {version} is already installed.");
+        if toolchain.made_default {
+            println!("Set {version} as the default toolchain.");
+        }
+        return;
     }
 
-    println!("Toolchain {version} installed successfully.");
+    if toolchain.made_default {
+        println!("Set {version} as the default toolchain.");
+    }
+    output::success(format_args!("Toolchain {version} installed successfully."));
+    if let Some(warning) = toolchain_range_warning(&SUPPORTED_TOOLCHAINS, version) {
+        output::warn(warning);
+    }
 
-    if is_first_install {
+    let bin = &installer.paths().bin;
+    if toolchain.first_install {
         println!();
-        configure_shell_path(&paths);
+        configure_shell_path(bin, !no_modify_path, configure_path);
     }
 
-    let conflicts = detect_path_conflicts(&paths.bin);
+    let conflicts = detect_path_conflicts(bin);
     if !conflicts.is_empty() {
         eprintln!();
         eprintln!("{}", format_conflict_warning(&conflicts));
     }
 
-    if current_default.is_some() && current_default.as_deref() != Some(&version) {
+    if toolchain.previous_default.is_some() && !toolchain.made_default {
         println!("Run 'infs default {version}' to make it the default toolchain.");
     }
+}
+
+/// Determines the version to register a local archive as.
+///
+/// Priority: `--as-version`, the positional version (unless `latest`),
+/// then a version embedded in the archive file name. The version names the
+/// toolchain directory, so an explicit one must be a semantic version.
+fn resolve_local_version(args: &InstallArgs, archive_path: &Path) -> Result<String> {
+    let explicit = args
+        .as_version
+        .as_deref()
+        .or((args.version != "latest").then_some(args.version.as_str()));
+    if let Some(version) = explicit {
+        return semver::Version::parse(version.trim_start_matches('v'))
+            .map(|v| v.to_string())
+            .map_err(|_| {
+                InfsError::invalid_arguments(format!(
+                    "invalid toolchain version '{version}'; expected a semantic version such as 0.2.0"
+                ))
+                .into()
+            });
+    }
+    match version_from_archive_name(archive_path) {
+        Some(version) => Ok(version),
+        None => bail!(
+            "Cannot determine the toolchain version from '{}'.\n\
+             Pass it explicitly with --as-version <VERSION>.",
+            archive_path.display()
+        ),
+    }
+}
 
-    std::fs::remove_file(&archive_path).ok();
+/// Extracts a semantic version from an archive file name.
+///
+/// The extension is dropped and the stem is split on `-`. The first
+/// dash-separated suffix that parses as a semver version (with an optional
+/// leading `v`) wins, so prerelease suffixes are kept:
+/// `infc-linux-x64-v0.2.0-alpha.tar.gz` -> `0.2.0-alpha`.
+fn version_from_archive_name(archive_path: &Path) -> Option<String> {
+    let name = archive_path.file_name()?.to_str()?;
+    let stem = [".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
 
-    Ok(())
+    let parts: Vec<&str> = stem.split('-').collect();
+    (0..parts.len()).find_map(|i| {
+        let candidate = parts[i..].join("-");
+        let candidate = candidate.trim_start_matches('v');
+        semver::Version::parse(candidate)
+            .ok()
+            .map(|v| v.to_string())
+    })
+}
+
+/// Minimum interval between extraction progress updates in milliseconds.
+const EXTRACT_PROGRESS_INTERVAL_MS: u128 = 100;
+
+/// Minimum interval between plain progress lines.
+const PLAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Percentage by which progress must advance before the next plain line.
+const PLAIN_PROGRESS_STEP: u64 = 10;
+
+/// Returns a callback printing installer progress to stdout in `mode`.
+pub(crate) fn cli_progress(mode: ProgressMode) -> InstallCallback {
+    let mode = mode.resolve(std::io::stdout().is_terminal());
+    let printer = Mutex::new(ProgressPrinter::new(mode, std::io::stdout()));
+    Arc::new(move |event| {
+        printer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .print(event, Instant::now());
+    })
+}
+
+/// Text rendering of [`InstallEvent`]s.
+///
+/// Steps are printed one per line. Download and extraction progress update
+/// a single line in place in [`ProgressMode::Auto`], are printed as
+/// occasional lines in [`ProgressMode::Plain`], and are left out in
+/// [`ProgressMode::None`].
+struct ProgressPrinter<W> {
+    mode: ProgressMode,
+    out: W,
+    phase: Option<InstallPhase>,
+    step_started: Option<Instant>,
+    download_total: u64,
+    downloaded: u64,
+    last_extract_update: Option<Instant>,
+    cadence: PlainCadence,
+}
+
+impl<W: Write> ProgressPrinter<W> {
+    fn new(mode: ProgressMode, out: W) -> Self {
+        Self {
+            mode,
+            out,
+            phase: None,
+            step_started: None,
+            download_total: 0,
+            downloaded: 0,
+            last_extract_update: None,
+            cadence: PlainCadence::default(),
+        }
+    }
+
+    /// Prints `event`, which happened at `now`.
+    fn print(&mut self, event: InstallEvent, now: Instant) {
+        // Progress is informational output; warnings are always shown.
+        if !logging::enabled(Level::Info) && !matches!(event, InstallEvent::Warning(_)) {
+            return;
+        }
+        let live = self.mode == ProgressMode::Auto;
+        match event {
+            InstallEvent::PhaseEntered(phase) => {
+                debug!("Phase: {}", phase.label());
+                self.phase = Some(phase);
+                self.cadence.start(now);
+            }
+            InstallEvent::StepStarted(step) => {
+                self.step_started = Some(now);
+                if live && self.phase == Some(InstallPhase::Extracting) {
+                    let _ = write!(self.out, "{step}...");
+                    let _ = self.out.flush();
+                } else {
+                    let _ = writeln!(self.out, "{step}...");
+                }
+            }
+            InstallEvent::StepCompleted(step) => {
+                if live && self.phase == Some(InstallPhase::Extracting) {
+                    let _ = writeln!(self.out);
+                }
+                match self.step_started.take() {
+                    Some(started) if self.mode == ProgressMode::Plain => {
+                        let elapsed = now.saturating_duration_since(started);
+                        let _ = writeln!(self.out, "{step}: done in {}", format_duration(elapsed));
+                    }
+                    _ => debug!("{step}: done"),
+                }
+            }
+            InstallEvent::Warning(warning) => output::warn(warning),
+            InstallEvent::Download(event) => self.print_download(event, now),
+            InstallEvent::ExtractProgress { processed, total } => {
+                self.print_extract(processed, total, now);
+            }
+        }
+    }
+
+    /// Prints download progress.
+    fn print_download(&mut self, event: ProgressEvent, now: Instant) {
+        match (event, self.mode) {
+            (ProgressEvent::Started { url, total }, _) => {
+                debug!("Downloading {url} ({total} bytes)");
+                self.download_total = total;
+                self.downloaded = 0;
+                self.cadence.start(now);
+            }
+            (ProgressEvent::Progress { downloaded, speed }, ProgressMode::Auto) => {
+                self.downloaded = downloaded;
+                #[allow(clippy::cast_precision_loss)]
+                let speed = speed as f64;
+                let _ = write!(
+                    self.out,
+                    "\r{}",
+                    format_progress(downloaded, self.download_total, speed)
+                );
+                let _ = self.out.flush();
+            }
+            (ProgressEvent::Progress { downloaded, speed }, ProgressMode::Plain) => {
+                self.downloaded = downloaded;
+                if self
+                    .cadence
+                    .due(now, percent(downloaded, self.download_total))
+                {
+                    let progress = plain_download_line(downloaded, self.download_total, speed);
+                    let _ = writeln!(self.out, "{progress}");
+                }
+            }
+            (ProgressEvent::Completed | ProgressEvent::Failed { .. }, ProgressMode::Auto) => {
+                let _ = writeln!(self.out);
+            }
+            (ProgressEvent::Completed, ProgressMode::Plain) => {
+                let bytes = self.downloaded.max(self.download_total);
+                let elapsed = self.cadence.elapsed(now);
+                let _ = writeln!(self.out, "{}", plain_download_summary(bytes, elapsed));
+            }
+            _ => {}
+        }
+    }
+
+    /// Prints extraction progress.
+    fn print_extract(&mut self, processed: u64, total: u64, now: Instant) {
+        match self.mode {
+            ProgressMode::Auto => {
+                let due = self.last_extract_update.is_none_or(|t| {
+                    now.duration_since(t).as_millis() >= EXTRACT_PROGRESS_INTERVAL_MS
+                });
+                if due || processed == total {
+                    let _ = write!(
+                        self.out,
+                        "\rExtracting archive... {processed}/{total} files"
+                    );
+                    let _ = self.out.flush();
+                    self.last_extract_update = Some(now);
+                }
+            }
+            ProgressMode::Plain => {
+                if processed == total {
+                    let elapsed = format_duration(self.cadence.elapsed(now));
+                    let _ = writeln!(self.out, "Extracted {total} files in {elapsed}");
+                } else if self.cadence.due(now, percent(processed, total)) {
+                    let percent = percent(processed, total).unwrap_or(0);
+                    let _ = writeln!(self.out, "Extracted {percent}% ({processed}/{total} files)");
+                }
+            }
+            ProgressMode::None => {}
+        }
+    }
+}
+
+/// Decides when the next plain progress line is printed.
+///
+/// A line is due once progress has advanced by [`PLAIN_PROGRESS_STEP`]
+/// percent since the last one and [`PLAIN_PROGRESS_INTERVAL`] has passed.
+/// Without a known total, only the interval applies.
+#[derive(Debug, Default)]
+struct PlainCadence {
+    /// When the tracked operation started.
+    started: Option<Instant>,
+    /// When the last line was printed, and the percentage it showed.
+    last: Option<(Instant, u64)>,
+}
+
+impl PlainCadence {
+    /// Starts tracking an operation at `now`.
+    fn start(&mut self, now: Instant) {
+        *self = Self {
+            started: Some(now),
+            last: None,
+        };
+    }
+
+    /// Returns the time since the operation started.
+    fn elapsed(&self, now: Instant) -> Duration {
+        self.started.map_or(Duration::ZERO, |started| {
+            now.saturating_duration_since(started)
+        })
+    }
+
+    /// Returns whether a line showing `percent` is due at `now`, and if so
+    /// records it as printed.
+    fn due(&mut self, now: Instant, percent: Option<u64>) -> bool {
+        let started = *self.started.get_or_insert(now);
+        let (since, shown) = self.last.unwrap_or((started, 0));
+        if now.saturating_duration_since(since) < PLAIN_PROGRESS_INTERVAL {
+            return false;
+        }
+        if let Some(percent) = percent
+            && percent / PLAIN_PROGRESS_STEP <= shown / PLAIN_PROGRESS_STEP
+        {
+            return false;
+        }
+        self.last = Some((now, percent.unwrap_or(0)));
+        true
+    }
+}
+
+/// Returns `done` as a percentage of `total`, or `None` if the total is
+/// unknown.
+fn percent(done: u64, total: u64) -> Option<u64> {
+    (total > 0).then(|| done.min(total) * 100 / total)
+}
+
+/// Formats a plain download progress line, e.g.
+/// `Downloaded 40% (80.00 MB/200.00 MB, 12.00 MB/s, ETA 10s)`.
+fn plain_download_line(downloaded: u64, total: u64, speed: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let speed_str = format_speed(speed as f64);
+    match percent(downloaded, total) {
+        Some(percent) => {
+            let eta = estimate_remaining(downloaded, total, speed)
+                .map(|eta| format!(", ETA {}", format_duration(eta)))
+                .unwrap_or_default();
+            format!(
+                "Downloaded {percent}% ({}/{}, {speed_str}{eta})",
+                format_bytes(downloaded),
+                format_bytes(total)
+            )
+        }
+        None => format!("Downloaded {} ({speed_str})", format_bytes(downloaded)),
+    }
+}
+
+/// Formats the line printed when a download finishes, e.g.
+/// `Downloaded 200.00 MB in 16s (12.50 MB/s)`.
+fn plain_download_summary(bytes: u64, elapsed: Duration) -> String {
+    let duration = format_duration(elapsed);
+    if elapsed.is_zero() {
+        return format!("Downloaded {} in {duration}", format_bytes(bytes));
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let speed = bytes as f64 / elapsed.as_secs_f64();
+    format!(
+        "Downloaded {} in {duration} ({})",
+        format_bytes(bytes),
+        format_speed(speed)
+    )
 }
 
 /// Configures the user's PATH environment.
@@ -141,26 +660,287 @@ pub async fn execute(args: &InstallArgs) -> Result<()> {
 /// On Unix systems, attempts to automatically add the bin directory to PATH
 /// by modifying the user's shell profile. On Windows, modifies the user's
 /// PATH environment variable in the registry.
-fn configure_shell_path(paths: &ToolchainPaths) {
-    use crate::toolchain::shell::{configure_path, format_result_message};
+///
+/// When `modify_path` is `false` (`--no-modify-path`), `configure` is never
+/// called and the manual instruction is printed instead.
+fn configure_shell_path(
+    bin_path: &Path,
+    modify_path: bool,
+    configure: impl FnOnce(&Path) -> Result<ConfigureResult>,
+) {
+    if !modify_path {
+        println!(
+            "Skipping PATH configuration (--no-modify-path). To use the toolchain, add to your PATH:"
+        );
+        println!("  {}", format_manual_path_instruction(bin_path));
+        return;
+    }
 
-    match configure_path(&paths.bin) {
+    match configure(bin_path) {
         Ok(result) => {
-            let message = format_result_message(&result, &paths.bin);
+            let message = format_result_message(&result, bin_path);
             println!("{message}");
         }
         Err(e) => {
-            eprintln!("Warning: Could not configure PATH automatically: {e}");
-            #[cfg(unix)]
-            {
-                println!("To use the toolchain, add to your shell profile:");
-                println!("  export PATH=\"{}:$PATH\"", paths.bin.display());
-            }
-            #[cfg(windows)]
-            {
-                println!("To use the toolchain, add to your PATH environment variable:");
-                println!("  {}", paths.bin.display());
-            }
+            output::warn(format_args!("Could not configure PATH automatically: {e}"));
+            println!("To use the toolchain, add to your PATH:");
+            println!("  {}", format_manual_path_instruction(bin_path));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn local_args(as_version: Option<&str>) -> InstallArgs {
+        InstallArgs {
+            version: String::from("latest"),
+            pre: false,
+            from: Some(PathBuf::from("unused")),
+            as_version: as_version.map(String::from),
+            checksum: None,
+            no_modify_path: false,
+            progress: ProgressMode::Auto,
+            refresh: false,
+        }
+    }
+
+    /// Feeds `events`, each at the given number of seconds after the
+    /// start, to a printer in `mode` and returns what it printed.
+    fn printed(mode: ProgressMode, events: Vec<(u64, InstallEvent)>) -> String {
+        let start = Instant::now();
+        let mut printer = ProgressPrinter::new(mode, Vec::new());
+        for (secs, event) in events {
+            printer.print(event, start + Duration::from_secs(secs));
+        }
+        String::from_utf8(printer.out).unwrap()
+    }
+
+    /// Events for a 100 MB download at 1 MB/s, one progress event per second.
+    fn download_events() -> Vec<(u64, InstallEvent)> {
+        const MB: u64 = 1024 * 1024;
+        let mut events = vec![
+            (0, InstallEvent::PhaseEntered(InstallPhase::Downloading)),
+            (
+                0,
+                InstallEvent::StepStarted(String::from("Downloading toolchain v0.1.0")),
+            ),
+            (
+                0,
+                InstallEvent::Download(ProgressEvent::Started {
+                    url: String::from("https://example.com/infc.tar.gz"),
+                    total: 100 * MB,
+                }),
+            ),
+        ];
+        events.extend((1..=100).map(|secs| {
+            (
+                secs,
+                InstallEvent::Download(ProgressEvent::Progress {
+                    downloaded: secs * MB,
+                    speed: MB,
+                }),
+            )
+        }));
+        events.push((100, InstallEvent::Download(ProgressEvent::Completed)));
+        events.push((
+            100,
+            InstallEvent::StepCompleted(String::from("Downloading toolchain v0.1.0")),
+        ));
+        events
+    }
+
+    #[test]
+    fn progress_mode_auto_is_plain_without_terminal() {
+        assert_eq!(ProgressMode::Auto.resolve(true), ProgressMode::Auto);
+        assert_eq!(ProgressMode::Auto.resolve(false), ProgressMode::Plain);
+        assert_eq!(ProgressMode::None.resolve(true), ProgressMode::None);
+        assert_eq!(ProgressMode::Plain.resolve(true), ProgressMode::Plain);
+    }
+
+    #[test]
+    fn plain_progress_prints_every_ten_percent_and_a_summary() {
+        let output = printed(ProgressMode::Plain, download_events());
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(!output.contains('\r'), "{output:?}");
+        assert_eq!(lines.first(), Some(&"Downloading toolchain v0.1.0..."));
+        assert_eq!(
+            lines[1],
+            "Downloaded 10% (10.00 MB/100.00 MB, 1.00 MB/s, ETA 1m 30s)"
+        );
+        let progress: Vec<&str> = lines
+            .iter()
+            .filter(|line| line.starts_with("Downloaded ") && line.contains('%'))
+            .copied()
+            .collect();
+        assert_eq!(progress.len(), 10, "{output}");
+        assert!(progress[9].starts_with("Downloaded 100% "), "{output}");
+        assert_eq!(
+            &lines[lines.len() - 2..],
+            [
+                "Downloaded 100.00 MB in 1m 40s (1.00 MB/s)",
+                "Downloading toolchain v0.1.0: done in 1m 40s",
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_progress_waits_five_seconds_between_lines() {
+        let mut cadence = PlainCadence::default();
+        let start = Instant::now();
+        cadence.start(start);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // A fast download crosses 10% steps faster than every 5 seconds
+        assert!(!cadence.due(at(1), Some(50)));
+        assert!(cadence.due(at(5), Some(60)));
+        assert!(!cadence.due(at(6), Some(90)));
+        // Slow progress prints nothing until the next 10% step
+        assert!(!cadence.due(at(20), Some(65)));
+        assert!(cadence.due(at(21), Some(70)));
+        // Without a total only the interval applies
+        assert!(cadence.due(at(26), None));
+    }
+
+    #[test]
+    fn plain_progress_reports_extraction_and_none_prints_only_steps() {
+        let mut events = vec![
+            (0, InstallEvent::PhaseEntered(InstallPhase::Extracting)),
+            (
+                0,
+                InstallEvent::StepStarted(String::from("Extracting archive")),
+            ),
+        ];
+        events.extend((1..=10).map(|secs| {
+            (
+                secs * 2,
+                InstallEvent::ExtractProgress {
+                    processed: secs * 100,
+                    total: 1000,
+                },
+            )
+        }));
+        events.push((
+            20,
+            InstallEvent::StepCompleted(String::from("Extracting archive")),
+        ));
+
+        assert_eq!(
+            printed(ProgressMode::Plain, events.clone()),
+            "Extracting archive...\n\
+             Extracted 30% (300/1000 files)\n\
+             Extracted 60% (600/1000 files)\n\
+             Extracted 90% (900/1000 files)\n\
+             Extracted 1000 files in 20s\n\
+             Extracting archive: done in 20s\n"
+        );
+        assert_eq!(
+            printed(ProgressMode::None, events),
+            "Extracting archive...\n"
+        );
+
+        let output = printed(ProgressMode::None, download_events());
+        assert_eq!(output, "Downloading toolchain v0.1.0...\n");
+    }
+
+    #[test]
+    fn version_from_archive_name_parses_common_layouts() {
+        let cases = [
+            ("infc-linux-x64-0.2.0.tar.gz", Some("0.2.0")),
+            ("infc-linux-x64-v0.2.0.zip", Some("0.2.0")),
+            ("toolchain-0.1.0-alpha.tgz", Some("0.1.0-alpha")),
+            ("infc-linux-x64.tar.gz", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                version_from_archive_name(Path::new(name)).as_deref(),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_local_version_prefers_explicit_version() {
+        let archive = Path::new("infc-linux-x64-0.2.0.tar.gz");
+
+        let args = local_args(Some("v0.3.0"));
+        assert_eq!(resolve_local_version(&args, archive).unwrap(), "0.3.0");
+
+        let mut args = local_args(None);
+        args.version = String::from("0.4.0");
+        assert_eq!(resolve_local_version(&args, archive).unwrap(), "0.4.0");
+
+        let args = local_args(None);
+        assert_eq!(resolve_local_version(&args, archive).unwrap(), "0.2.0");
+
+        assert!(resolve_local_version(&args, Path::new("toolchain.zip")).is_err());
+
+        for bad in ["../x", "latest-ish", "0.2"] {
+            let mut args = local_args(Some(bad));
+            let err = resolve_local_version(&args, archive).unwrap_err();
+            assert!(
+                err.to_string().contains("invalid toolchain version"),
+                "{err}"
+            );
+            args.as_version = None;
+            args.version = bad.to_string();
+            assert!(resolve_local_version(&args, archive).is_err());
+        }
+    }
+
+    #[test]
+    fn no_modify_path_skips_configure_path() {
+        let bin = Path::new("/tmp/infs_test_bin");
+
+        let called = Cell::new(false);
+        configure_shell_path(bin, false, |_| {
+            called.set(true);
+            Ok(ConfigureResult::AlreadyConfigured {
+                profile: PathBuf::new(),
+            })
+        });
+        assert!(!called.get());
+
+        configure_shell_path(bin, true, |path| {
+            called.set(true);
+            assert_eq!(path, bin);
+            Ok(ConfigureResult::AlreadyConfigured {
+                profile: PathBuf::new(),
+            })
+        });
+        assert!(called.get());
+    }
+
+    #[test]
+    fn format_choice_numbers_from_one_and_tags_prereleases() {
+        let entry = |version: &str, stable: bool| VersionEntry {
+            version: version.to_string(),
+            stable,
+            notes: None,
+            files: vec![],
+        };
+        assert_eq!(format_choice(0, &entry("0.2.4", true)), "1) 0.2.4");
+        assert_eq!(
+            format_choice(2, &entry("0.3.0-alpha", false)),
+            "3) 0.3.0-alpha (prerelease)"
+        );
+        assert_eq!(
+            format_choice(1, &entry("0.3.0", false)),
+            "2) 0.3.0 (prerelease)"
+        );
+    }
+
+    #[test]
+    fn parse_choice_accepts_numbers_in_range_and_defaults_to_first() {
+        assert_eq!(parse_choice("\n", 3), Some(0));
+        assert_eq!(parse_choice(" 2 \n", 3), Some(1));
+        assert_eq!(parse_choice("3", 3), Some(2));
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("two", 3), None);
+    }
+}
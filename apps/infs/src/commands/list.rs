@@ -54,6 +54,12 @@ pub async fn execute() -> Result<()> {
         }
         if let Some(meta) = metadata {
             info_parts.push(format!("installed {}", meta.installed_ago()));
+            if let Some(label) = meta.signature.label() {
+                info_parts.push(label.to_string());
+            }
+            if let Some(label) = meta.source.label() {
+                info_parts.push(label.to_string());
+            }
         }
 
         let marker = if is_default { "*" } else { " " };
@@ -0,0 +1,333 @@
+//! Manifest command for the infs CLI.
+//!
+//! Shows which artifact `infs install` would fetch for a version and
+//! platform, without downloading it. Useful for manual downloads and for
+//! mirroring releases.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs manifest                                   # Latest version, current platform
+//! infs manifest --version 0.2.0                   # Specific version
+//! infs manifest --version 0.2.0 --platform macos  # Another platform
+//! infs manifest --pre                             # Latest version, including prereleases
+//! infs manifest --json                            # Output in JSON format
+//! ```
+//!
+//! ## Output Format
+//!
+//! ```text
+//! Version:  0.2.0
+//! Platform: linux-x64
+//! URL:      https://.../infc-linux-x64.tar.gz
+//! Size:     12.34 MB
+//! SHA256:   e3b0c442... (valid)
+//! ```
+
+use std::fmt::Write as _;
+
+use anyhow::{Result, bail};
+use clap::Args;
+use serde::Serialize;
+
+use crate::toolchain::download::{fetch_content_length, format_bytes};
+use crate::toolchain::manifest::{FileEntry, libc_mismatch_warning};
+use crate::toolchain::verify::is_valid_sha256;
+use crate::toolchain::{Platform, fetch_artifact};
+
+/// Arguments for the manifest command.
+#[derive(Args)]
+pub struct ManifestArgs {
+    /// Version to resolve (defaults to the latest stable version).
+    #[clap(long)]
+    pub version: Option<String>,
+
+    /// Target platform: linux-x64, macos-arm64, or windows-x64
+    /// (defaults to the current platform).
+    #[clap(long)]
+    pub platform: Option<String>,
+
+    /// Allow prerelease versions when resolving "latest" or a version range.
+    #[clap(long = "pre")]
+    pub pre: bool,
+
+    /// Show the resolved artifact in JSON format.
+    #[clap(long, short = 'j')]
+    pub json: bool,
+}
+
+/// Resolved artifact details for a version and platform.
+#[derive(Debug, Clone, Serialize)]
+struct ArtifactReport {
+    version: String,
+    platform: String,
+    url: String,
+    filename: String,
+    size: Option<u64>,
+    sha256: String,
+    checksum_status: &'static str,
+    warning: Option<String>,
+}
+
+/// Executes the manifest command.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The platform is not recognized or cannot be detected
+/// - The manifest cannot be fetched
+/// - The version or platform artifact is not in the manifest
+/// - JSON serialization fails
+pub async fn execute(args: &ManifestArgs) -> Result<()> {
+    let platform = match args.platform.as_deref() {
+        Some(value) => parse_platform(value)?,
+        None => Platform::detect()?,
+    };
+
+    let version_arg = args.version.as_deref().filter(|v| *v != "latest");
+    let (version, artifact, _) = fetch_artifact(version_arg, platform, args.pre).await?;
+
+    // The size is informational only, so a failed HEAD request is not an error.
+    let size = fetch_content_length(&artifact.url).await.ok().flatten();
+    let report = build_report(version, platform, &artifact, size);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_text(&report));
+    }
+
+    Ok(())
+}
+
+/// Parses the `--platform` argument.
+fn parse_platform(value: &str) -> Result<Platform> {
+    match Platform::parse(value) {
+        Some(platform) => Ok(platform),
+        None => bail!(
+            "Unknown platform '{value}'. Supported platforms: {}",
+            Platform::supported_ids()
+        ),
+    }
+}
+
+/// Builds the report for a resolved artifact.
+fn build_report(
+    version: String,
+    platform: Platform,
+    artifact: &FileEntry,
+    size: Option<u64>,
+) -> ArtifactReport {
+    let checksum_status = if artifact.sha256.is_empty() {
+        "missing"
+    } else if is_valid_sha256(&artifact.sha256) {
+        "valid"
+    } else {
+        "malformed"
+    };
+
+    ArtifactReport {
+        version,
+        platform: platform.as_str().to_string(),
+        url: artifact.url.clone(),
+        filename: artifact.filename().to_string(),
+        size,
+        sha256: artifact.sha256.clone(),
+        checksum_status,
+        warning: libc_mismatch_warning(platform, artifact),
+    }
+}
+
+/// Formats the report for human-readable output.
+fn format_text(report: &ArtifactReport) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Version:  {}", report.version);
+    let _ = writeln!(out, "Platform: {}", report.platform);
+    let _ = writeln!(out, "URL:      {}", report.url);
+    match report.size {
+        Some(size) => {
+            let _ = writeln!(out, "Size:     {} ({size} bytes)", format_bytes(size));
+        }
+        None => {
+            let _ = writeln!(out, "Size:     unknown");
+        }
+    }
+    if report.sha256.is_empty() {
+        let _ = writeln!(out, "SHA256:   ({})", report.checksum_status);
+    } else {
+        let _ = writeln!(
+            out,
+            "SHA256:   {} ({})",
+            report.sha256, report.checksum_status
+        );
+    }
+    if let Some(warning) = &report.warning {
+        let _ = writeln!(out, "Warning:  {warning}");
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::env;
+
+    use crate::toolchain::manifest::CACHE_SCHEMA_VERSION;
+    use crate::toolchain::paths::INFERENCE_HOME_ENV;
+
+    const SAMPLE_MANIFEST: &str = r#"[
+        {
+            "version": "0.1.0",
+            "stable": true,
+            "files": [
+                {
+                    "url": "https://example.com/0.1.0/infc-linux-x64.tar.gz",
+                    "sha256": "abc123def456abc123def456abc123def456abc123def456abc123def456abc1"
+                }
+            ]
+        },
+        {
+            "version": "0.2.0",
+            "stable": true,
+            "files": [
+                {
+                    "url": "https://example.com/0.2.0/infc-linux-x64.tar.gz",
+                    "sha256": "def456abc123def456abc123def456abc123def456abc123def456abc123def4"
+                },
+                {
+                    "url": "https://example.com/0.2.0/infc-macos-arm64.tar.gz",
+                    "sha256": "not-a-checksum"
+                }
+            ]
+        },
+        {
+            "version": "0.3.0-alpha",
+            "stable": false,
+            "files": [
+                {
+                    "url": "https://example.com/0.3.0-alpha/infc-linux-x64.tar.gz",
+                    "sha256": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                }
+            ]
+        }
+    ]"#;
+
+    /// Resolves an artifact through `fetch_artifact`, serving the sample
+    /// manifest from the manifest cache so no network access is needed.
+    fn resolve_from_sample(
+        version: Option<&str>,
+        platform: Platform,
+        include_prerelease: bool,
+    ) -> Result<(String, FileEntry)> {
+        let root = temp_test_dir("manifest_cmd");
+        let cache_dir = root.join("cache");
+        std::fs::create_dir_all(&cache_dir).expect("Should create cache dir");
+        let manifest: serde_json::Value =
+            serde_json::from_str(SAMPLE_MANIFEST).expect("Should parse sample manifest");
+        let cached = serde_json::json!({
+            "schema_version": CACHE_SCHEMA_VERSION,
+            "manifest": manifest,
+            "timestamp": 0,
+        });
+        std::fs::write(cache_dir.join("manifest.json"), cached.to_string())
+            .expect("Should write cache");
+
+        // SAFETY: Callers run serially and the variable is removed below.
+        unsafe {
+            env::set_var(INFERENCE_HOME_ENV, &root);
+        }
+        let runtime = tokio::runtime::Runtime::new().expect("Should create runtime");
+        let result = runtime.block_on(fetch_artifact(version, platform, include_prerelease));
+        // SAFETY: Cleanup - restoring previous state.
+        unsafe {
+            env::remove_var(INFERENCE_HOME_ENV);
+        }
+
+        std::fs::remove_dir_all(&root).ok();
+        result.map(|(version, artifact, _)| (version, artifact))
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn sample_manifest_resolves_to_expected_url() {
+        let (version, artifact) =
+            resolve_from_sample(Some("0.1.0"), Platform::LinuxX64, false).expect("Should resolve");
+        let report = build_report(version, Platform::LinuxX64, &artifact, Some(2048));
+        let text = format_text(&report);
+
+        assert!(text.contains("Version:  0.1.0"));
+        assert!(text.contains("URL:      https://example.com/0.1.0/infc-linux-x64.tar.gz\n"));
+        assert!(text.contains("Size:     2.00 KB (2048 bytes)"));
+        assert!(text.contains("(valid)"));
+        assert_eq!(report.filename, "infc-linux-x64.tar.gz");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn sample_manifest_resolves_latest_for_other_platform() {
+        let (version, artifact) =
+            resolve_from_sample(None, Platform::MacosArm64, false).expect("Should resolve");
+        let report = build_report(version, Platform::MacosArm64, &artifact, None);
+
+        assert_eq!(report.version, "0.2.0");
+        assert_eq!(
+            report.url,
+            "https://example.com/0.2.0/infc-macos-arm64.tar.gz"
+        );
+        assert_eq!(report.checksum_status, "malformed");
+        assert!(format_text(&report).contains("Size:     unknown"));
+
+        let json = serde_json::to_value(&report).expect("Should serialize");
+        assert_eq!(json["url"], report.url);
+        assert_eq!(json["size"], serde_json::Value::Null);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn sample_manifest_reports_missing_platform() {
+        let err = resolve_from_sample(Some("0.1.0"), Platform::WindowsX64, false)
+            .expect_err("Should fail for missing platform");
+        assert!(err.to_string().contains("windows-x64"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn sample_manifest_skips_prerelease_without_pre() {
+        let (version, _) =
+            resolve_from_sample(None, Platform::LinuxX64, false).expect("Should resolve");
+        assert_eq!(version, "0.2.0");
+
+        let err = resolve_from_sample(Some("^0.3"), Platform::LinuxX64, false)
+            .expect_err("Should skip prerelease");
+        assert!(err.to_string().contains("--pre"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn sample_manifest_selects_prerelease_with_pre() {
+        let (version, artifact) =
+            resolve_from_sample(Some("latest"), Platform::LinuxX64, true).expect("Should resolve");
+        assert_eq!(version, "0.3.0-alpha");
+        assert_eq!(
+            artifact.url,
+            "https://example.com/0.3.0-alpha/infc-linux-x64.tar.gz"
+        );
+
+        let (version, _) =
+            resolve_from_sample(Some("^0.3"), Platform::LinuxX64, true).expect("Should resolve");
+        assert_eq!(version, "0.3.0-alpha");
+    }
+
+    #[test]
+    fn parse_platform_rejects_unknown_values() {
+        assert_eq!(parse_platform("macos").ok(), Some(Platform::MacosArm64));
+        let err = parse_platform("solaris").expect_err("Should reject");
+        assert!(
+            err.to_string()
+                .contains("linux-x64, macos-arm64, windows-x64")
+        );
+    }
+}
@@ -7,11 +7,18 @@
 //! - [`build`] - Compile Inference source files
 //! - [`run`] - Build and execute WASM with wasmtime
 //! - [`version`] - Display version information
+//! - [`bug_report`] - Collect environment details for bug reports
+//! - [`env`] - Print a summary of the infs environment
+//! - [`info`] - Inspect a WASM module or generated Rocq file
+//! - [`docs`] - Show the bundled documentation
 //!
 //! ## Project Management Commands
 //!
 //! - [`new`] - Create a new Inference project
 //! - [`init`] - Initialize an existing directory as an Inference project
+//! - [`scaffold_cmd`] - Upgrade a project's template files
+//! - [`clean`] - Remove build artifacts
+//! - [`fmt`] - Format WebAssembly text files
 //!
 //! ## Toolchain Management Commands
 //!
@@ -19,19 +26,38 @@
 //! - [`uninstall`] - Remove toolchain versions
 //! - [`list`] - List installed toolchains
 //! - [`versions`] - List available remote versions
+//! - [`changelog`] - Show the release notes of a version
+//! - [`manifest`] - Show the artifact URL for a version and platform
 //! - [`default`] - Set default toolchain version
 //! - [`doctor`] - Check installation health
+//! - [`repair`] - Repair an installed toolchain
+//! - [`which`] - Show resolved toolchain binary paths
+//! - [`cache`] - Show or clear cached data
+//! - [`toolchain_cmd`] - Run binaries from a specific toolchain
 //! - [`self_cmd`] - Manage infs itself
 
+pub mod bug_report;
 pub mod build;
+pub mod cache;
+pub mod changelog;
+pub mod clean;
 pub mod default;
+pub mod docs;
 pub mod doctor;
+pub mod env;
+pub mod fmt;
+pub mod info;
 pub mod init;
 pub mod install;
 pub mod list;
+pub mod manifest;
 pub mod new;
+pub mod repair;
 pub mod run;
+pub mod scaffold_cmd;
 pub mod self_cmd;
+pub mod toolchain_cmd;
 pub mod uninstall;
 pub mod version;
 pub mod versions;
+pub mod which;
@@ -6,8 +6,14 @@
 //!
 //! ```bash
 //! infs new myproject                    # Create project in current directory
-//! infs new myproject --no-git           # Skip git initialization
+//! infs new myproject --vcs jj           # Initialize a jujutsu repository
+//! infs new myproject --vcs none         # Skip version control initialization
+//! infs new myproject --no-git           # Same as --vcs none
+//! infs new myproject --ci github        # Add a GitHub Actions workflow
 //! infs new myproject ./path             # Create in specified directory
+//! infs new myproject --template lib     # Use ~/.inference/templates/lib/
+//! infs new myproject --edition 2024     # Set the language edition
+//! infs new myproject --license MIT --author "Ada <ada@example.com>"
 //! ```
 //!
 //! ## Project Structure
@@ -24,13 +30,38 @@
 //! +-- proofs/
 //! |   +-- .gitkeep
 //! +-- .gitignore
+//! +-- .github/workflows/verify.yml   (with --ci github)
+//! +-- .inference/scaffold.lock
 //! ```
+//!
+//! The template choices are recorded in `Inference.toml` and the generated
+//! files in `.inference/scaffold.lock`, for `infs scaffold upgrade`.
+//!
+//! ## User Templates
+//!
+//! `--template <name>` copies the files of `~/.inference/templates/<name>/`
+//! into the project after the standard files, replacing them where paths
+//! overlap. Text files can use `{{project_name}}`, `{{edition}}`,
+//! `{{year}}`, and `{{author}}` (the `--author` values, if given). The
+//! built-in template is called `default`.
+//!
+//! ## Package Metadata
+//!
+//! `--description`, `--author` (repeatable), and `--license` fill in the
+//! optional fields of `[package]`. The license must be an SPDX license
+//! expression; the fields left unset stay as commented examples. Without
+//! `--author`, a project initialized with git gets the author from
+//! `git config user.name` and `user.email`, if they are set.
 
 use anyhow::Result;
 use clap::Args;
 use std::path::PathBuf;
 
-use crate::project::create_project;
+use crate::project::manifest::{DEFAULT_EDITION, PackageMetadata};
+use crate::project::scaffold::{TEMPLATE_NAME, create_project_with_template};
+use crate::project::template::{ProjectTemplate, get_template};
+use crate::project::{CiProvider, Vcs};
+use crate::toolchain::ToolchainPaths;
 
 /// Arguments for the `new` command.
 #[derive(Args)]
@@ -46,13 +77,45 @@ pub struct NewArgs {
     #[clap(default_value = ".")]
     pub path: PathBuf,
 
-    /// Skip git repository initialization.
+    /// Version control system to initialize.
     ///
-    /// By default, `infs new` initializes a git repository in the
-    /// new project directory. Use this flag to create a project
-    /// without git.
-    #[clap(long = "no-git", action = clap::ArgAction::SetTrue)]
+    /// A missing VCS binary produces a warning and the project is
+    /// created without a repository.
+    #[clap(long, value_enum, default_value_t = Vcs::Git)]
+    pub vcs: Vcs,
+
+    /// Skip version control initialization (alias for `--vcs none`).
+    #[clap(long = "no-git", action = clap::ArgAction::SetTrue, conflicts_with = "vcs")]
     pub no_git: bool,
+
+    /// Add continuous integration configuration for the given provider.
+    #[clap(long, value_enum)]
+    pub ci: Option<CiProvider>,
+
+    /// Template to create the project from: `default`, or the name of a
+    /// directory in `~/.inference/templates/`.
+    #[clap(long, default_value = TEMPLATE_NAME)]
+    pub template: String,
+
+    /// Language edition recorded in `Inference.toml`.
+    #[clap(long, default_value = DEFAULT_EDITION)]
+    pub edition: String,
+
+    /// Description recorded in `Inference.toml`.
+    #[clap(long)]
+    pub description: Option<String>,
+
+    /// Author recorded in `Inference.toml`; repeat for several authors.
+    ///
+    /// Defaults to the git `user.name` and `user.email` when a git
+    /// repository is initialized.
+    #[clap(long = "author", value_name = "AUTHOR")]
+    pub authors: Vec<String>,
+
+    /// License recorded in `Inference.toml`, as an SPDX license expression
+    /// such as `MIT` or `Apache-2.0 OR MIT`.
+    #[clap(long)]
+    pub license: Option<String>,
 }
 
 /// Executes the `new` command.
@@ -63,17 +126,39 @@ pub struct NewArgs {
 ///
 /// Returns an error if:
 /// - The project name is invalid (reserved word or invalid characters)
+/// - The edition is not a known edition
+/// - The license is not an SPDX license expression
 /// - The target directory already exists
+/// - The template does not exist or cannot be rendered
 /// - File creation fails
 pub fn execute(args: &NewArgs) -> Result<()> {
-    let init_git = !args.no_git;
+    let vcs = if args.no_git { Vcs::None } else { args.vcs };
     let parent = if args.path.as_os_str() == "." {
         None
     } else {
         Some(args.path.as_path())
     };
 
-    let project_path = create_project(&args.name, parent, init_git)?;
+    let template = if args.template == TEMPLATE_NAME {
+        ProjectTemplate::Builtin
+    } else {
+        get_template(&args.template, &ToolchainPaths::new()?.templates_dir())?
+    };
+
+    let metadata = PackageMetadata {
+        description: args.description.clone(),
+        authors: args.authors.clone(),
+        license: args.license.clone(),
+    };
+    let project_path = create_project_with_template(
+        &args.name,
+        parent,
+        vcs,
+        args.ci,
+        &args.edition,
+        &metadata,
+        &template,
+    )?;
 
     println!("Created project '{}'", args.name);
     println!();
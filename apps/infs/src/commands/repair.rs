@@ -0,0 +1,273 @@
+//! Repair command for the infs CLI.
+//!
+//! Restores a damaged toolchain installation without re-downloading it.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs repair          # Repair the default toolchain
+//! infs repair 0.1.0    # Repair version 0.1.0
+//! ```
+//!
+//! ## Repairs Performed
+//!
+//! - Executable permissions are re-applied (Unix only)
+//! - Missing installation metadata is rewritten
+//! - Symlinks in the bin directory are recreated when the version is the default
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+
+use crate::toolchain::lock::ToolchainLock;
+use crate::toolchain::paths::ToolchainMetadata;
+use crate::toolchain::{Platform, ToolchainPaths, set_executable_permissions};
+
+/// Arguments for the repair command.
+#[derive(Args)]
+pub struct RepairArgs {
+    /// Version to repair (defaults to the current default toolchain).
+    pub version: Option<String>,
+}
+
+/// Summary of the repairs applied to a toolchain.
+#[derive(Debug, Default)]
+pub struct RepairReport {
+    /// Symlinks that were broken before the repair.
+    pub broken_symlinks: Vec<String>,
+    /// Whether the installation metadata had to be rewritten.
+    pub metadata_restored: bool,
+    /// Whether the bin directory symlinks were recreated.
+    pub relinked: bool,
+}
+
+/// Executes the repair command.
+///
+/// # Process
+///
+/// 1. Resolve the version (argument or current default)
+/// 2. Verify the toolchain directory and its binaries exist
+/// 3. Re-apply executable permissions and rewrite missing metadata
+/// 4. Recreate symlinks if the version is the default
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - No version is given and no default toolchain is set
+/// - The version is not installed
+/// - A toolchain binary is missing
+/// - Permissions, metadata, or symlinks cannot be written
+pub fn execute(args: &RepairArgs) -> Result<()> {
+    let paths = ToolchainPaths::new()?;
+
+    let version = match &args.version {
+        Some(version) => version.clone(),
+        None => paths.get_default_version()?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No default toolchain set.\n\
+                 Specify a version with 'infs repair <version>', or run 'infs install'."
+            )
+        })?,
+    };
+
+    println!("Repairing toolchain {version}...");
+
+    let report = repair_toolchain(&paths, &version)?;
+
+    for name in &report.broken_symlinks {
+        println!("  Restored broken symlink: {name}");
+    }
+    if report.metadata_restored {
+        println!("  Rewrote missing installation metadata");
+    }
+    if report.relinked {
+        println!("  Recreated symlinks in {}", paths.bin.display());
+    }
+
+    println!("Toolchain {version} repaired successfully.");
+
+    Ok(())
+}
+
+/// Repairs an installed toolchain version in place.
+///
+/// # Errors
+///
+/// Returns an error if the version is not installed, a managed binary is
+/// missing from the toolchain directory, or any repair step fails.
+pub fn repair_toolchain(paths: &ToolchainPaths, version: &str) -> Result<RepairReport> {
+    let _lock = ToolchainLock::acquire(paths)?;
+    if !paths.is_version_installed(version) {
+        bail!(
+            "Toolchain version {version} is not installed.\n\
+             Run 'infs install {version}' to install it."
+        );
+    }
+
+    let ext = Platform::detect()?.executable_extension();
+    for name in ToolchainPaths::MANAGED_BINARIES {
+        let binary = format!("{name}{ext}");
+        if !paths.binary_path(version, &binary).exists() {
+            bail!(
+                "{binary} is missing from toolchain {version} and cannot be repaired locally.\n\
+                 Run 'infs uninstall {version}' and 'infs install {version}' to reinstall it."
+            );
+        }
+    }
+
+    let mut report = RepairReport::default();
+
+    let toolchain_dir = paths.toolchain_dir(version);
+    set_executable_permissions(&toolchain_dir).with_context(|| {
+        format!(
+            "Failed to set executable permissions in {}",
+            toolchain_dir.display()
+        )
+    })?;
+
+    if paths.read_metadata(version).is_none() {
+        paths.write_metadata(version, &ToolchainMetadata::now())?;
+        report.metadata_restored = true;
+    }
+
+    if paths.get_default_version()?.as_deref() == Some(version) {
+        report.broken_symlinks = paths.validate_symlinks();
+        paths.update_symlinks(version)?;
+        report.relinked = true;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    /// Creates a fake installed toolchain with all managed binaries.
+    fn install_fake_toolchain(paths: &ToolchainPaths, version: &str) {
+        let bin_dir = paths.toolchain_bin_dir(version);
+        std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+        let ext = std::env::consts::EXE_SUFFIX;
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            std::fs::write(bin_dir.join(format!("{name}{ext}")), "#!/bin/sh\necho ok\n")
+                .expect("Should write binary");
+        }
+    }
+
+    #[test]
+    fn repair_missing_version_suggests_install() {
+        let root = temp_test_dir("repair_missing");
+        let paths = ToolchainPaths::with_root(root.clone());
+
+        let err = repair_toolchain(&paths, "0.1.0").expect_err("Should fail");
+        assert!(err.to_string().contains("infs install 0.1.0"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn repair_missing_binary_suggests_reinstall() {
+        let root = temp_test_dir("repair_missing_binary");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_fake_toolchain(&paths, "0.1.0");
+        let ext = std::env::consts::EXE_SUFFIX;
+        std::fs::remove_file(
+            paths
+                .toolchain_bin_dir("0.1.0")
+                .join(format!("inf-llc{ext}")),
+        )
+        .expect("Should remove binary");
+
+        let err = repair_toolchain(&paths, "0.1.0").expect_err("Should fail");
+        let message = err.to_string();
+        assert!(message.contains("inf-llc"));
+        assert!(message.contains("infs install 0.1.0"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn repair_rewrites_missing_metadata() {
+        let root = temp_test_dir("repair_metadata");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_fake_toolchain(&paths, "0.1.0");
+
+        let report = repair_toolchain(&paths, "0.1.0").expect("Should repair");
+        assert!(report.metadata_restored);
+        assert!(paths.read_metadata("0.1.0").is_some());
+
+        let report = repair_toolchain(&paths, "0.1.0").expect("Should repair");
+        assert!(!report.metadata_restored);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn repair_non_default_does_not_touch_symlinks() {
+        let root = temp_test_dir("repair_non_default");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_fake_toolchain(&paths, "0.1.0");
+        install_fake_toolchain(&paths, "0.2.0");
+        paths
+            .set_default_version("0.2.0")
+            .expect("Should set default");
+
+        let report = repair_toolchain(&paths, "0.1.0").expect("Should repair");
+        assert!(!report.relinked);
+        assert!(!paths.bin.exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn repair_restores_broken_symlinks() {
+        let root = temp_test_dir("repair_symlinks");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_fake_toolchain(&paths, "0.1.0");
+        paths
+            .set_default_version("0.1.0")
+            .expect("Should set default");
+
+        std::fs::create_dir_all(&paths.bin).expect("Should create bin dir");
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            std::os::unix::fs::symlink(root.join("gone").join(name), paths.symlink_path(name))
+                .expect("Should create broken symlink");
+        }
+        assert_eq!(paths.validate_symlinks().len(), 3);
+
+        let report = repair_toolchain(&paths, "0.1.0").expect("Should repair");
+        assert!(report.relinked);
+        assert_eq!(report.broken_symlinks.len(), 3);
+        assert!(paths.validate_symlinks().is_empty());
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            let target = std::fs::read_link(paths.symlink_path(name)).expect("Should be symlink");
+            assert_eq!(target, paths.binary_path("0.1.0", name));
+        }
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn repair_restores_executable_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_test_dir("repair_permissions");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_fake_toolchain(&paths, "0.1.0");
+        let infc = paths.binary_path("0.1.0", "infc");
+        std::fs::set_permissions(&infc, std::fs::Permissions::from_mode(0o644))
+            .expect("Should set permissions");
+
+        repair_toolchain(&paths, "0.1.0").expect("Should repair");
+
+        let mode = std::fs::metadata(&infc)
+            .expect("Should get metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -7,11 +7,25 @@
 //! ## Execution Pipeline
 //!
 //! 1. **Validate** - Check source file exists
-//! 2. **Check** - Verify wasmtime is available in PATH
+//! 2. **Check** - Verify wasmtime is available in PATH and warn if its version
+//!    is outside the compatibility table
 //! 3. **Locate** - Find the infc compiler binary
-//! 4. **Compile** - Call infc with `--parse --codegen -o` to generate WASM
+//! 4. **Compile** - Call infc with `--parse --codegen -o` to generate WASM,
+//!    adding `--target` for targets other than `wasm32`, `--opt-level`
+//!    for the optimization level, and `-D feature=NAME` for each enabled
+//!    feature
 //! 5. **Execute** - Run WASM with wasmtime using `--invoke`
 //!
+//! If the program traps, wasmtime's backtrace frames are rewritten to the
+//! source functions and lines they come from (see [`crate::backtrace`]).
+//!
+//! The target comes from `--target`, then `[build] target` in the project's
+//! `Inference.toml`, then defaults to `wasm32`. Likewise the optimization
+//! level comes from `--release`/`--debug`, then `[build] optimize`, then
+//! defaults to `debug`. Features are the `default` ones from `[features]`
+//! (unless `--no-default-features` is given) plus those passed with
+//! `--features`, see [`crate::project::features`].
+//!
 //! ## Entry Points
 //!
 //! By default, the `main` function is invoked. Use `--entry-point` to call
@@ -36,7 +50,13 @@ use clap::Args;
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::backtrace::FunctionTable;
+use crate::diagnostics::run_infc;
 use crate::errors::InfsError;
+use crate::project::features::{Features, add_feature_defines, resolve_features};
+use crate::project::optimize::{Optimize, add_opt_level, resolve_optimize};
+use crate::project::target::{Target, ensure_infc_supports, resolve_target};
+use crate::toolchain::compat::{ExternalTool, warn_if_incompatible};
 use crate::toolchain::find_infc;
 
 /// Arguments for the run command.
@@ -55,6 +75,26 @@ pub struct RunArgs {
     #[clap(long, default_value = "main")]
     pub entry_point: String,
 
+    /// WebAssembly target [default: `[build] target` from Inference.toml, or wasm32].
+    #[clap(long, value_enum)]
+    pub target: Option<Target>,
+
+    /// Build with optimizations [default: `[build] optimize` from Inference.toml, or debug].
+    #[clap(long, conflicts_with = "debug")]
+    pub release: bool,
+
+    /// Build without optimizations, overriding `[build] optimize`.
+    #[clap(long)]
+    pub debug: bool,
+
+    /// Features to enable in addition to the default ones (comma-separated).
+    #[clap(long, value_delimiter = ',', value_name = "FEATURES")]
+    pub features: Vec<String>,
+
+    /// Do not enable the `default` features from Inference.toml.
+    #[clap(long)]
+    pub no_default_features: bool,
+
     /// Arguments to pass to the invoked function.
     ///
     /// For functions other than `main`, these are passed directly as function arguments.
@@ -67,9 +107,10 @@ pub struct RunArgs {
 ///
 /// ## Execution Flow
 ///
-/// 1. Validates source file exists
+/// 1. Validates source file exists and resolves the target, optimization
+///    level, and features
 /// 2. Checks for wasmtime availability
-/// 3. Locates the infc compiler
+/// 3. Locates the infc compiler and checks it supports the target
 /// 4. Compiles source to WASM via infc subprocess
 /// 5. Executes WASM with wasmtime
 /// 6. Propagates exit code from wasmtime
@@ -84,20 +125,30 @@ pub struct RunArgs {
 ///
 /// Returns an error if:
 /// - The source file does not exist
+/// - The project manifest cannot be parsed or names an unknown target or
+///   optimization level
+/// - A requested feature is not declared in the manifest
 /// - wasmtime is not found in PATH
-/// - infc compiler cannot be found
+/// - infc compiler cannot be found or does not support the target or features
 /// - Compilation fails
 /// - WASM execution fails
 pub fn execute(args: &RunArgs) -> Result<()> {
     if !args.path.exists() {
-        bail!("Path not found: {}", args.path.display());
+        return Err(InfsError::file_not_found(args.path.clone()))
+            .with_context(|| format!("Path not found: {}", args.path.display()));
     }
 
+    let target = resolve_target(args.target, &args.path)?;
+    let optimize = resolve_optimize(Optimize::from_flags(args.release, args.debug), &args.path)?;
+    let features = resolve_features(&args.features, args.no_default_features, &args.path)?;
+
     check_wasmtime_availability()?;
+    warn_if_incompatible(ExternalTool::Wasmtime);
 
     let infc_path = find_infc()?;
+    ensure_infc_supports(&infc_path, target)?;
 
-    let wasm_path = compile_to_wasm(&infc_path, &args.path)?;
+    let wasm_path = compile_to_wasm(&infc_path, &args.path, target, optimize, &features)?;
 
     run_wasmtime(&wasm_path, &args.entry_point, &args.args)
 }
@@ -105,13 +156,13 @@ pub fn execute(args: &RunArgs) -> Result<()> {
 /// Checks if wasmtime is available in PATH.
 fn check_wasmtime_availability() -> Result<()> {
     if which::which("wasmtime").is_err() {
-        bail!(
+        return Err(InfsError::tool_not_found("wasmtime")).context(
             "wasmtime not found in PATH.\n\n\
             wasmtime is a WebAssembly runtime. To install:\n  \
             - macOS: brew install wasmtime\n  \
             - Linux: curl https://wasmtime.dev/install.sh -sSf | bash\n  \
             - Windows: winget install wasmtime\n  \
-            - Or visit: https://wasmtime.dev/"
+            - Or visit: https://wasmtime.dev/",
         );
     }
     Ok(())
@@ -120,20 +171,29 @@ fn check_wasmtime_availability() -> Result<()> {
 /// Compiles source file to WASM binary using infc subprocess.
 ///
 /// Calls infc with `--parse --codegen -o` flags to generate the WASM file
-/// in the `out/` directory.
-fn compile_to_wasm(infc_path: &PathBuf, source_path: &PathBuf) -> Result<PathBuf> {
+/// in the `out/` directory. Non-default targets are passed with `--target`
+/// and produce `out/<name>.<target>.wasm`. The optimization level is passed
+/// with `--opt-level` when infc supports it, and each enabled feature with
+/// `-D feature=NAME`.
+fn compile_to_wasm(
+    infc_path: &PathBuf,
+    source_path: &PathBuf,
+    target: Target,
+    optimize: Optimize,
+    features: &Features,
+) -> Result<PathBuf> {
     let mut cmd = Command::new(infc_path);
     cmd.arg(source_path)
         .arg("--parse")
         .arg("--codegen")
         .arg("-o");
+    if target != Target::default() {
+        cmd.arg("--target").arg(target.as_str());
+    }
+    add_opt_level(&mut cmd, infc_path, optimize)?;
+    add_feature_defines(&mut cmd, infc_path, features)?;
 
-    let status = cmd
-        .stdin(std::process::Stdio::inherit())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status()
-        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+    let status = run_infc(&mut cmd, infc_path)?;
 
     if !status.success() {
         let code = status.code().unwrap_or(1);
@@ -146,7 +206,7 @@ fn compile_to_wasm(infc_path: &PathBuf, source_path: &PathBuf) -> Result<PathBuf
         .to_str()
         .unwrap_or("module");
 
-    let wasm_path = PathBuf::from("out").join(format!("{source_fname}.wasm"));
+    let wasm_path = PathBuf::from("out").join(target.wasm_file_name(source_fname));
 
     if !wasm_path.exists() {
         bail!(
@@ -166,6 +226,8 @@ fn compile_to_wasm(infc_path: &PathBuf, source_path: &PathBuf) -> Result<PathBuf
 ///
 /// Stderr is captured and only displayed if wasmtime fails, to suppress
 /// the experimental feature warnings about `--invoke` that appear on success.
+/// Backtrace frames in it are mapped to source lines using the function name
+/// table next to the WASM file, when there is one.
 ///
 /// Returns `Ok(())` on success, or `Err(InfsError::ProcessExitCode)` if wasmtime
 /// exits with a non-zero code. This allows the caller to propagate the exit code
@@ -200,7 +262,11 @@ fn run_wasmtime(wasm_path: &PathBuf, entry_point: &str, args: &[String]) -> Resu
     } else {
         // Only show stderr on failure (hides experimental warnings on success)
         if !output.stderr.is_empty() {
-            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match FunctionTable::load_for(wasm_path) {
+                Some(table) => eprint!("{}", table.rewrite(&stderr)),
+                None => eprint!("{stderr}"),
+            }
         }
         let code = output.status.code().unwrap_or(1);
         Err(InfsError::process_exit_code(code).into())
@@ -0,0 +1,206 @@
+//! Scaffold command for the infs CLI.
+//!
+//! Brings the files generated by `infs new` and `infs init` up to date with
+//! the current project template.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs scaffold upgrade           # Upgrade the project in the current directory
+//! infs scaffold upgrade ./path    # Upgrade the project at ./path
+//! infs scaffold upgrade --diff    # Also print patches for conflicting files
+//! ```
+//!
+//! Files the user has not edited since they were generated are replaced.
+//! Edited files are left unchanged, and listed as conflicts if the template
+//! changed them. `src/` is never
+//! touched; see [`crate::project::upgrade`].
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+
+use crate::errors::InfsError;
+use crate::project::manifest::MANIFEST_FILE;
+use crate::project::scaffold::TEMPLATE_VERSION;
+use crate::project::upgrade::{FileAction, UpgradeReport, upgrade_project};
+
+/// Arguments for the scaffold command.
+#[derive(Args)]
+pub struct ScaffoldArgs {
+    #[command(subcommand)]
+    pub command: ScaffoldCommand,
+}
+
+/// Subcommands for managing a project's template files.
+#[derive(Subcommand)]
+pub enum ScaffoldCommand {
+    /// Re-render the project template and apply changes to unmodified files.
+    ///
+    /// Edited files are left unchanged, and reported as conflicts if the
+    /// template changed them.
+    Upgrade(ScaffoldUpgradeArgs),
+}
+
+/// Arguments for the scaffold upgrade subcommand.
+#[derive(Args)]
+pub struct ScaffoldUpgradeArgs {
+    /// Project directory (defaults to current directory).
+    #[clap(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Show a patch from each conflicting file to the new template.
+    #[clap(long)]
+    pub diff: bool,
+}
+
+/// Executes the scaffold command.
+///
+/// # Errors
+///
+/// Returns an error if the directory is not an Inference project or a
+/// template file cannot be read or written.
+pub fn execute(args: &ScaffoldArgs) -> Result<()> {
+    match &args.command {
+        ScaffoldCommand::Upgrade(upgrade_args) => {
+            let manifest_path = upgrade_args.path.join(MANIFEST_FILE);
+            if !manifest_path.exists() {
+                return Err(InfsError::file_not_found(manifest_path)).with_context(|| {
+                    format!(
+                        "'{}' is not an Inference project",
+                        upgrade_args.path.display()
+                    )
+                });
+            }
+            let report = upgrade_project(&upgrade_args.path)?;
+            print!("{}", format_report(&report, upgrade_args.diff));
+            Ok(())
+        }
+    }
+}
+
+/// Formats the upgrade report, optionally with patches for conflicts.
+fn format_report(report: &UpgradeReport, show_diff: bool) -> String {
+    let mut out = String::new();
+
+    if report.inferred_template {
+        let _ = writeln!(
+            out,
+            "No [template] section found; added one to {MANIFEST_FILE} based on the files present."
+        );
+    }
+    match report.from_version {
+        Some(version) if version != TEMPLATE_VERSION => {
+            let _ = writeln!(
+                out,
+                "Upgrading project template from version {version} to {TEMPLATE_VERSION}"
+            );
+        }
+        _ => {
+            let _ = writeln!(
+                out,
+                "Checking project template (version {TEMPLATE_VERSION})"
+            );
+        }
+    }
+
+    for change in &report.changes {
+        let label = match change.action {
+            FileAction::Created => "created",
+            FileAction::Updated => "updated",
+            FileAction::UpToDate => continue,
+            FileAction::Conflict(reason) => {
+                let _ = writeln!(out, "  conflict  {} ({})", change.path, reason.describe());
+                continue;
+            }
+        };
+        let _ = writeln!(out, "  {label:<9} {}", change.path);
+    }
+
+    let created = report.count(FileAction::Created);
+    let updated = report.count(FileAction::Updated);
+    let conflicts = report.conflicts().count();
+    if created + updated + conflicts == 0 {
+        let _ = writeln!(out, "Project template is up to date.");
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "{created} created, {updated} updated, {conflicts} conflict{}.",
+        if conflicts == 1 { "" } else { "s" }
+    );
+
+    if conflicts > 0 {
+        if show_diff {
+            for change in report.conflicts() {
+                if let Some(diff) = &change.diff {
+                    out.push('\n');
+                    out.push_str(diff);
+                }
+            }
+        } else {
+            let _ = writeln!(
+                out,
+                "Conflicting files were left unchanged. Run 'infs scaffold upgrade --diff' to see the template changes."
+            );
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::upgrade::{ConflictReason, FileChange};
+
+    fn change(path: &str, action: FileAction) -> FileChange {
+        FileChange {
+            path: path.to_string(),
+            action,
+            diff: matches!(action, FileAction::Conflict(_))
+                .then(|| format!("--- a/{path}\n+++ b/{path}\n")),
+        }
+    }
+
+    #[test]
+    fn report_lists_changes_and_conflicts() {
+        let report = UpgradeReport {
+            from_version: Some(0),
+            inferred_template: false,
+            changes: vec![
+                change("proofs/.gitkeep", FileAction::UpToDate),
+                change(".gitignore", FileAction::Updated),
+                change(
+                    ".github/workflows/verify.yml",
+                    FileAction::Conflict(ConflictReason::Modified),
+                ),
+            ],
+        };
+
+        let text = format_report(&report, false);
+        assert!(text.contains(&format!("from version 0 to {TEMPLATE_VERSION}")));
+        assert!(!text.contains("proofs/.gitkeep"));
+        assert!(text.contains("  updated   .gitignore\n"));
+        assert!(text.contains("  conflict  .github/workflows/verify.yml (modified locally)"));
+        assert!(text.contains("0 created, 1 updated, 1 conflict."));
+        assert!(text.contains("--diff"));
+        assert!(!text.contains("+++ b/"));
+
+        let with_diff = format_report(&report, true);
+        assert!(with_diff.contains("+++ b/.github/workflows/verify.yml"));
+    }
+
+    #[test]
+    fn report_for_current_project_says_up_to_date() {
+        let report = UpgradeReport {
+            from_version: Some(TEMPLATE_VERSION),
+            inferred_template: false,
+            changes: vec![change(".gitignore", FileAction::UpToDate)],
+        };
+        let text = format_report(&report, false);
+        assert!(text.contains("Project template is up to date."));
+    }
+}
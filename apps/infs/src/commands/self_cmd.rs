@@ -11,9 +11,12 @@
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand};
 
+use crate::output;
+use crate::toolchain::manifest::libc_mismatch_warning;
+use crate::toolchain::verify::verify_digest;
 use crate::toolchain::{
     Platform, ToolchainPaths, download_file, extract_archive, fetch_manifest, latest_stable,
-    latest_version, verify_checksum,
+    latest_version,
 };
 
 /// Arguments for the self command.
@@ -102,6 +105,9 @@ async fn execute_update() -> Result<()> {
     let artifact = latest_entry
         .find_infs_artifact(platform)
         .with_context(|| format!("No infs binary available for platform {platform}"))?;
+    if let Some(warning) = libc_mismatch_warning(platform, artifact) {
+        output::warn(warning);
+    }
 
     println!("Updating infs from {current_version} to {latest_version}...");
 
@@ -109,10 +115,10 @@ async fn execute_update() -> Result<()> {
     let download_path = paths.download_path(download_filename);
 
     println!("Downloading from {}...", artifact.url);
-    download_file(&artifact.url, &download_path).await?;
+    let outcome = download_file(&artifact.url, &download_path).await?;
 
     println!("Verifying checksum...");
-    verify_checksum(&download_path, &artifact.sha256)?;
+    verify_digest(&download_path, &outcome.sha256, &artifact.sha256)?;
 
     println!("Extracting...");
     let temp_dir = paths.downloads.join(format!("infs-{latest_version}-temp"));
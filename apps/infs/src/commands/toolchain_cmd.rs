@@ -0,0 +1,394 @@
+//! Toolchain command for the infs CLI.
+//!
+//! Runs binaries from a specific installed toolchain without changing the
+//! default, e.g. to compare the output of two compiler versions, and moves
+//! installed toolchains to machines without network access.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs toolchain run 0.1.0 -- infc --version   # Run infc from toolchain 0.1.0
+//! infs +0.1.0 build example.inf --codegen -o   # Build with toolchain 0.1.0's infc
+//! infs toolchain export 0.1.0 -o bundle.tar.zst # Pack toolchain 0.1.0 into a bundle
+//! infs toolchain import bundle.tar.zst          # Install it on another machine
+//! ```
+//!
+//! The command is looked up in the toolchain's `bin` directory (or its root
+//! for `infc`), and that `bin` directory is prepended to the child's `PATH`
+//! so helper binaries like `inf-llc` and `rust-lld` come from the same
+//! toolchain. The child's exit code is propagated.
+//!
+//! Bundles are described in [`crate::toolchain::bundle`]. An imported
+//! toolchain is registered like an installed one: it becomes the default if
+//! it is the first, and `infs list` shows it as imported.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+
+use crate::commands::install::{ProgressMode, cli_progress, report};
+use crate::errors::InfsError;
+use crate::output;
+use crate::toolchain::bundle::{default_bundle_name, export_bundle};
+use crate::toolchain::installer::{InstallOptions, Installer};
+use crate::toolchain::{Platform, ToolchainPaths};
+
+/// Arguments for the toolchain command.
+#[derive(Args)]
+pub struct ToolchainArgs {
+    #[command(subcommand)]
+    pub command: ToolchainCommand,
+}
+
+/// Subcommands for working with specific toolchains.
+#[derive(Subcommand)]
+pub enum ToolchainCommand {
+    /// Run a binary from a specific toolchain version.
+    ///
+    /// The default toolchain is not changed.
+    Run(ToolchainRunArgs),
+
+    /// Pack an installed toolchain into a bundle for offline machines.
+    Export(ToolchainExportArgs),
+
+    /// Install a toolchain from a bundle made by `infs toolchain export`.
+    Import(ToolchainImportArgs),
+}
+
+/// Arguments for the toolchain run subcommand.
+#[derive(Args)]
+pub struct ToolchainRunArgs {
+    /// Installed toolchain version to use.
+    pub version: String,
+
+    /// Command to run (e.g. infc, inf-llc, rust-lld) followed by its arguments.
+    #[arg(last = true, required = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+}
+
+/// Arguments for the toolchain export subcommand.
+#[derive(Args)]
+pub struct ToolchainExportArgs {
+    /// Installed toolchain version to export.
+    pub version: String,
+
+    /// Bundle file to write.
+    ///
+    /// Defaults to `inference-toolchain-<version>-<platform>.tar.zst` in the
+    /// current directory.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for the toolchain import subcommand.
+#[derive(Args)]
+pub struct ToolchainImportArgs {
+    /// Bundle file to import.
+    pub bundle: PathBuf,
+
+    /// Replace the toolchain if the same version is already installed.
+    #[arg(long = "force")]
+    pub force: bool,
+
+    /// Make the imported toolchain the default.
+    #[arg(long = "default")]
+    pub make_default: bool,
+
+    /// Do not add the toolchain bin directory to PATH on first install.
+    #[arg(long = "no-modify-path")]
+    pub no_modify_path: bool,
+}
+
+/// Executes the toolchain command.
+///
+/// # Errors
+///
+/// Returns an error if the subcommand fails. A non-zero exit status of the
+/// child process is returned as [`InfsError::ProcessExitCode`].
+pub fn execute(args: &ToolchainArgs) -> Result<()> {
+    match &args.command {
+        ToolchainCommand::Run(run_args) => {
+            let paths = ToolchainPaths::new()?;
+            let code = run_in_toolchain(&paths, &run_args.version, &run_args.command)?;
+            if code == 0 {
+                Ok(())
+            } else {
+                Err(InfsError::process_exit_code(code).into())
+            }
+        }
+        ToolchainCommand::Export(export_args) => export(export_args),
+        ToolchainCommand::Import(import_args) => import(import_args),
+    }
+}
+
+/// Writes a bundle of an installed toolchain.
+fn export(args: &ToolchainExportArgs) -> Result<()> {
+    let paths = ToolchainPaths::new()?;
+    let platform = Platform::detect()?;
+    let bundle = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(default_bundle_name(&args.version, platform)));
+
+    let manifest = export_bundle(&paths, &args.version, platform, &bundle)?;
+    output::success(format_args!(
+        "Exported toolchain {} ({} files) to {}",
+        manifest.version,
+        manifest.files.len(),
+        bundle.display()
+    ));
+    println!(
+        "Run 'infs toolchain import {}' on the target machine.",
+        bundle.display()
+    );
+    Ok(())
+}
+
+/// Installs a toolchain from a bundle.
+fn import(args: &ToolchainImportArgs) -> Result<()> {
+    let installer = Installer::new()?;
+    println!("Importing toolchain from {}...", args.bundle.display());
+    let options = InstallOptions {
+        make_default: args.make_default,
+        ..InstallOptions::default()
+    };
+    let toolchain = installer.import_bundle(
+        &args.bundle,
+        args.force,
+        options,
+        &cli_progress(ProgressMode::Auto),
+    )?;
+    report(&installer, &toolchain, args.no_modify_path);
+    Ok(())
+}
+
+/// Splits a leading `+version` argument off the command line.
+///
+/// `infs +0.1.0 build ...` returns `Some("0.1.0")` and the arguments with the
+/// prefix removed. Other command lines are returned unchanged.
+#[must_use]
+pub fn take_version_prefix(mut args: Vec<OsString>) -> (Option<String>, Vec<OsString>) {
+    let version = args
+        .get(1)
+        .and_then(|arg| arg.to_str())
+        .and_then(|arg| arg.strip_prefix('+'))
+        .filter(|version| !version.is_empty())
+        .map(String::from);
+
+    if version.is_some() {
+        args.remove(1);
+    }
+
+    (version, args)
+}
+
+/// Resolves a command name to a binary inside a toolchain version.
+///
+/// # Errors
+///
+/// Returns an error if the version is not installed, the command contains a
+/// path separator, or no matching binary exists in the toolchain.
+pub fn resolve_command(paths: &ToolchainPaths, version: &str, command: &str) -> Result<PathBuf> {
+    if !paths.is_version_installed(version) {
+        return Err(InfsError::toolchain_not_found(version)).with_context(|| {
+            format!(
+                "Toolchain version {version} is not installed.\n\
+                 Run 'infs install {version}' to install it."
+            )
+        });
+    }
+
+    if command.is_empty() || command.contains(['/', '\\']) {
+        bail!("Invalid command '{command}': expected a binary name from the toolchain");
+    }
+
+    let ext = Platform::detect()?.executable_extension();
+    let with_ext = paths.binary_path(version, &format!("{command}{ext}"));
+    if with_ext.is_file() {
+        return Ok(with_ext);
+    }
+    let plain = paths.toolchain_bin_dir(version).join(command);
+    if plain.is_file() {
+        return Ok(plain);
+    }
+
+    Err(InfsError::tool_not_found(command)).with_context(|| {
+        format!(
+            "'{command}' was not found in toolchain {version} ({}).",
+            paths.toolchain_bin_dir(version).display()
+        )
+    })
+}
+
+/// Runs a command from a toolchain version and returns its exit code.
+///
+/// `command` holds the binary name followed by its arguments. The toolchain's
+/// `bin` directory is prepended to the child's `PATH`.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be resolved or spawned.
+pub fn run_in_toolchain(paths: &ToolchainPaths, version: &str, command: &[String]) -> Result<i32> {
+    let Some((name, args)) = command.split_first() else {
+        bail!("No command given. Usage: infs toolchain run <version> -- <command> [args...]");
+    };
+
+    let binary = resolve_command(paths, version, name)?;
+
+    let bin_dir = paths.toolchain_bin_dir(version);
+    let mut search_path = vec![bin_dir];
+    if let Some(existing) = std::env::var_os("PATH") {
+        search_path.extend(std::env::split_paths(&existing));
+    }
+    let search_path = std::env::join_paths(search_path).context("Failed to build PATH")?;
+
+    let status = Command::new(&binary)
+        .args(args)
+        .env("PATH", search_path)
+        .stdin(std::process::Stdio::inherit())
+        .stdout(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::inherit())
+        .status()
+        .with_context(|| format!("Failed to execute {}", binary.display()))?;
+
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn take_version_prefix_strips_leading_plus() {
+        let (version, rest) = take_version_prefix(args(&["infs", "+0.1.0", "build", "a.inf"]));
+        assert_eq!(version.as_deref(), Some("0.1.0"));
+        assert_eq!(rest, args(&["infs", "build", "a.inf"]));
+    }
+
+    #[test]
+    fn take_version_prefix_ignores_other_arguments() {
+        let original = args(&["infs", "build", "+0.1.0"]);
+        let (version, rest) = take_version_prefix(original.clone());
+        assert_eq!(version, None);
+        assert_eq!(rest, original);
+
+        let (version, rest) = take_version_prefix(args(&["infs", "+"]));
+        assert_eq!(version, None);
+        assert_eq!(rest, args(&["infs", "+"]));
+    }
+
+    #[test]
+    fn resolve_command_requires_installed_version() {
+        let root = temp_test_dir("toolchain_run_missing");
+        let paths = ToolchainPaths::with_root(root.clone());
+
+        let err = resolve_command(&paths, "0.1.0", "infc").expect_err("Should fail");
+        assert!(err.to_string().contains("infs install 0.1.0"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn resolve_command_rejects_paths_and_unknown_binaries() {
+        let root = temp_test_dir("toolchain_run_unknown");
+        let paths = ToolchainPaths::with_root(root.clone());
+        std::fs::create_dir_all(paths.toolchain_bin_dir("0.1.0")).expect("Should create bin");
+
+        let err = resolve_command(&paths, "0.1.0", "../infc").expect_err("Should fail");
+        assert!(err.to_string().contains("Invalid command"));
+
+        let err = resolve_command(&paths, "0.1.0", "nope").expect_err("Should fail");
+        assert!(err.to_string().contains("not found in toolchain 0.1.0"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn resolve_command_finds_infc_at_toolchain_root() {
+        let root = temp_test_dir("toolchain_run_root_infc");
+        let paths = ToolchainPaths::with_root(root.clone());
+        let ext = std::env::consts::EXE_SUFFIX;
+        std::fs::create_dir_all(paths.toolchain_bin_dir("0.1.0")).expect("Should create bin");
+        let infc = paths.toolchain_dir("0.1.0").join(format!("infc{ext}"));
+        std::fs::write(&infc, "").expect("Should write infc");
+
+        let resolved = resolve_command(&paths, "0.1.0", "infc").expect("Should resolve");
+        assert_eq!(resolved, infc);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    mod unix {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+
+        /// Writes an executable shell script into a toolchain's bin directory.
+        fn write_script(paths: &ToolchainPaths, version: &str, name: &str, body: &str) {
+            let bin_dir = paths.toolchain_bin_dir(version);
+            std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+            let script = bin_dir.join(name);
+            std::fs::write(&script, format!("#!/bin/sh\n{body}\n")).expect("Should write script");
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                .expect("Should set permissions");
+        }
+
+        #[test]
+        fn run_in_toolchain_propagates_exit_code_and_args() {
+            let root = temp_test_dir("toolchain_run_exit");
+            let paths = ToolchainPaths::with_root(root.clone());
+            let out = root.join("args.txt");
+            write_script(
+                &paths,
+                "0.1.0",
+                "infc",
+                &format!("echo \"$@\" > {}\nexit 7", out.display()),
+            );
+
+            let command = vec![
+                "infc".to_string(),
+                "--parse".to_string(),
+                "a.inf".to_string(),
+            ];
+            let code = run_in_toolchain(&paths, "0.1.0", &command).expect("Should run");
+
+            assert_eq!(code, 7);
+            let recorded = std::fs::read_to_string(&out).expect("Should read args");
+            assert_eq!(recorded.trim(), "--parse a.inf");
+
+            std::fs::remove_dir_all(&root).ok();
+        }
+
+        #[test]
+        fn run_in_toolchain_prepends_bin_dir_to_path() {
+            let root = temp_test_dir("toolchain_run_path");
+            let paths = ToolchainPaths::with_root(root.clone());
+            let out = root.join("helper.txt");
+            // The helper is only reachable through PATH.
+            write_script(
+                &paths,
+                "0.2.0",
+                "inf-llc",
+                &format!("echo from-0.2.0 > {}", out.display()),
+            );
+            write_script(&paths, "0.2.0", "infc", "inf-llc");
+
+            let code =
+                run_in_toolchain(&paths, "0.2.0", &["infc".to_string()]).expect("Should run");
+
+            assert_eq!(code, 0);
+            let recorded = std::fs::read_to_string(&out).expect("Should read helper output");
+            assert_eq!(recorded.trim(), "from-0.2.0");
+
+            std::fs::remove_dir_all(&root).ok();
+        }
+    }
+}
@@ -6,12 +6,18 @@
 //!
 //! ```bash
 //! infs uninstall 0.1.0    # Remove version 0.1.0
+//! infs uninstall 0.1.0 -y # Remove without asking
 //! ```
+//!
+//! In a terminal, the removal is confirmed first unless `--yes` is given.
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
 
+use crate::confirm::{self, Confirmer, TerminalConfirmer};
+use crate::output::{self, Stream, Style};
 use crate::toolchain::ToolchainPaths;
+use crate::toolchain::lock::ToolchainLock;
 
 /// Arguments for the uninstall command.
 #[derive(Args)]
@@ -26,8 +32,9 @@ pub struct UninstallArgs {
 ///
 /// 1. Check if the version is installed
 /// 2. Check if it's the current default version
-/// 3. Remove the toolchain directory
-/// 4. Update symlinks if necessary
+/// 3. Confirm the removal
+/// 4. Remove the toolchain directory
+/// 5. Update symlinks if necessary
 ///
 /// # Errors
 ///
@@ -37,8 +44,28 @@ pub struct UninstallArgs {
 #[allow(clippy::unused_async)]
 pub async fn execute(args: &UninstallArgs) -> Result<()> {
     let paths = ToolchainPaths::new()?;
-    let version = &args.version;
+    uninstall_toolchain(
+        &paths,
+        &args.version,
+        confirm::assume_yes(),
+        &mut TerminalConfirmer,
+    )
+}
 
+/// Removes toolchain `version` after asking `confirmer`, unless
+/// `assume_yes` is set.
+///
+/// # Errors
+///
+/// Returns an error if the version is not installed, the answer cannot be
+/// read, or the toolchain or its symlinks cannot be updated.
+fn uninstall_toolchain(
+    paths: &ToolchainPaths,
+    version: &str,
+    assume_yes: bool,
+    confirmer: &mut dyn Confirmer,
+) -> Result<()> {
+    let _lock = ToolchainLock::acquire(paths)?;
     if !paths.is_version_installed(version) {
         bail!("Toolchain version {version} is not installed.");
     }
@@ -47,7 +74,19 @@ pub async fn execute(args: &UninstallArgs) -> Result<()> {
     let is_default = default_version.as_deref() == Some(version);
 
     if is_default {
-        println!("Warning: {version} is the current default toolchain.");
+        println!(
+            "{} {version} is the current default toolchain.",
+            output::paint(Stream::Stdout, Style::Warning, "Warning:")
+        );
+    }
+
+    if !confirm::confirm(
+        confirmer,
+        assume_yes,
+        &format!("Uninstall toolchain {version}?"),
+    )? {
+        println!("Uninstall cancelled.");
+        return Ok(());
     }
 
     println!("Uninstalling toolchain version {version}...");
@@ -86,7 +125,52 @@ pub async fn execute(args: &UninstallArgs) -> Result<()> {
         }
     }
 
-    println!("Toolchain {version} uninstalled successfully.");
+    output::success(format_args!(
+        "Toolchain {version} uninstalled successfully."
+    ));
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confirm::testing::{Answer, NeverAsked};
+    use crate::test_support::temp_test_dir;
+
+    fn temp_paths_with(version: &str) -> ToolchainPaths {
+        let paths = ToolchainPaths::with_root(temp_test_dir("uninstall"));
+        std::fs::create_dir_all(paths.toolchain_bin_dir(version)).unwrap();
+        paths
+    }
+
+    #[test]
+    fn assume_yes_uninstalls_without_prompting() {
+        let paths = temp_paths_with("0.1.0");
+        uninstall_toolchain(&paths, "0.1.0", true, &mut NeverAsked).unwrap();
+        assert!(!paths.is_version_installed("0.1.0"));
+        std::fs::remove_dir_all(&paths.root).ok();
+    }
+
+    #[test]
+    fn declined_prompt_keeps_the_toolchain() {
+        let paths = temp_paths_with("0.1.0");
+        let mut answer = Answer::new(false);
+        uninstall_toolchain(&paths, "0.1.0", false, &mut answer).unwrap();
+        assert_eq!(answer.asked, ["Uninstall toolchain 0.1.0?"]);
+        assert!(paths.is_version_installed("0.1.0"));
+
+        let mut answer = Answer::new(true);
+        uninstall_toolchain(&paths, "0.1.0", false, &mut answer).unwrap();
+        assert!(!paths.is_version_installed("0.1.0"));
+        std::fs::remove_dir_all(&paths.root).ok();
+    }
+
+    #[test]
+    fn missing_version_fails_before_prompting() {
+        let paths = temp_paths_with("0.1.0");
+        let err = uninstall_toolchain(&paths, "0.2.0", false, &mut NeverAsked).unwrap_err();
+        assert!(err.to_string().contains("not installed"), "{err}");
+        std::fs::remove_dir_all(&paths.root).ok();
+    }
+}
@@ -1,11 +1,30 @@
 //! Version command for the infs CLI.
 //!
 //! Displays version information for the infs toolchain.
-//! In verbose mode, shows additional details including build date,
-//! platform, and compiler version.
+//! In verbose mode, shows additional details including the git revision,
+//! build date, build profile, platform, and the range of toolchain
+//! versions this build supports.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs version            # infs 0.1.0
+//! infs version --verbose  # Build information
+//! infs version --json     # Build information as JSON
+//! ```
+//!
+//! The git revision, commit date, and profile are captured by `build.rs`.
+//! Builds from a source tarball without git report them as "unknown".
 
 use anyhow::Result;
 use clap::Args;
+use serde::Serialize;
+
+use crate::toolchain::compat::{SUPPORTED_TOOLCHAINS, ToolchainRange};
+use crate::toolchain::paths::format_timestamp_iso8601;
+
+/// Value reported for build details that could not be determined.
+const UNKNOWN: &str = "unknown";
 
 /// Arguments for the version command.
 #[derive(Args)]
@@ -13,6 +32,59 @@ pub struct VersionArgs {
     /// Show detailed version information including build date, platform, and features.
     #[clap(short = 'v', long = "verbose", action = clap::ArgAction::SetTrue)]
     pub verbose: bool,
+
+    /// Output detailed version information as JSON.
+    #[clap(long = "json", action = clap::ArgAction::SetTrue)]
+    pub json: bool,
+}
+
+/// Build details of this infs binary.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    version: String,
+    commit: String,
+    commit_date: String,
+    build_date: String,
+    profile: String,
+    platform: String,
+    min_toolchain: String,
+    max_toolchain: String,
+}
+
+impl BuildInfo {
+    /// Returns the build details embedded at compile time.
+    #[must_use]
+    pub fn current() -> Self {
+        Self::new(
+            option_env!("INFS_GIT_COMMIT"),
+            option_env!("INFS_GIT_COMMIT_DATE"),
+            option_env!("INFS_BUILD_TIMESTAMP"),
+            option_env!("INFS_BUILD_PROFILE"),
+            &SUPPORTED_TOOLCHAINS,
+        )
+    }
+
+    /// Builds the details from the raw build script values.
+    ///
+    /// Missing values are reported as "unknown".
+    fn new(
+        commit: Option<&str>,
+        commit_date: Option<&str>,
+        build_timestamp: Option<&str>,
+        profile: Option<&str>,
+        toolchains: &ToolchainRange,
+    ) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commit: or_unknown(commit).to_string(),
+            commit_date: or_unknown(commit_date).to_string(),
+            build_date: format_build_timestamp(build_timestamp),
+            profile: or_unknown(profile).to_string(),
+            platform: platform_string(),
+            min_toolchain: toolchains.minimum.to_string(),
+            max_toolchain: toolchains.maximum.to_string(),
+        }
+    }
 }
 
 /// Executes the version command.
@@ -20,10 +92,15 @@ pub struct VersionArgs {
 /// Prints the version string derived from the package version
 /// defined in Cargo.toml at compile time. In verbose mode,
 /// prints additional build and platform information.
-#[allow(clippy::unnecessary_wraps)]
+///
+/// # Errors
+///
+/// Returns an error if the JSON output cannot be serialized.
 pub fn execute(args: &VersionArgs) -> Result<()> {
-    if args.verbose {
-        print_verbose_version();
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&BuildInfo::current())?);
+    } else if args.verbose {
+        print_verbose_version(&BuildInfo::current());
     } else {
         println!("infs {}", env!("CARGO_PKG_VERSION"));
     }
@@ -31,18 +108,42 @@ pub fn execute(args: &VersionArgs) -> Result<()> {
 }
 
 /// Prints detailed version information.
-fn print_verbose_version() {
-    println!("infs {}", env!("CARGO_PKG_VERSION"));
+fn print_verbose_version(info: &BuildInfo) {
+    println!("infs {}", info.version);
     println!();
     println!("Build Information:");
-    println!("  Version:  {}", env!("CARGO_PKG_VERSION"));
-    println!("  Commit:   {}", git_commit());
-    println!("  Platform: {}", platform_string());
+    println!("  Version:     {}", info.version);
+    println!("  Commit:      {}", info.commit);
+    println!("  Commit date: {}", info.commit_date);
+    println!("  Built:       {}", info.build_date);
+    println!("  Profile:     {}", info.profile);
+    println!("  Platform:    {}", info.platform);
+    println!(
+        "  Toolchains:  {} to {}",
+        info.min_toolchain, info.max_toolchain
+    );
+}
+
+/// Returns the git revision from environment or a fallback.
+pub fn git_commit() -> &'static str {
+    or_unknown(option_env!("INFS_GIT_COMMIT"))
 }
 
-/// Returns the git commit hash from environment or a fallback.
-fn git_commit() -> &'static str {
-    option_env!("INFS_GIT_COMMIT").unwrap_or("unknown")
+/// Returns the build time as an ISO 8601 string, or "unknown".
+pub fn build_date() -> String {
+    format_build_timestamp(option_env!("INFS_BUILD_TIMESTAMP"))
+}
+
+/// Returns `value`, or "unknown" if the build script did not set it.
+fn or_unknown(value: Option<&str>) -> &str {
+    value.filter(|v| !v.is_empty()).unwrap_or(UNKNOWN)
+}
+
+/// Formats a build timestamp in seconds as ISO 8601, or "unknown".
+fn format_build_timestamp(timestamp: Option<&str>) -> String {
+    timestamp
+        .and_then(|value| value.parse().ok())
+        .map_or_else(|| UNKNOWN.to_string(), format_timestamp_iso8601)
 }
 
 /// Returns a human-readable platform string.
@@ -56,14 +157,20 @@ mod tests {
 
     #[test]
     fn execute_with_verbose_false_succeeds() {
-        let args = VersionArgs { verbose: false };
+        let args = VersionArgs {
+            verbose: false,
+            json: false,
+        };
         let result = execute(&args);
         assert!(result.is_ok());
     }
 
     #[test]
     fn execute_with_verbose_true_succeeds() {
-        let args = VersionArgs { verbose: true };
+        let args = VersionArgs {
+            verbose: true,
+            json: false,
+        };
         let result = execute(&args);
         assert!(result.is_ok());
     }
@@ -75,9 +182,60 @@ mod tests {
         assert!(platform.contains('-'));
     }
 
+    #[test]
+    fn build_date_is_iso8601_timestamp() {
+        let date = build_date();
+        assert_eq!(date.len(), "1970-01-01T00:00:00Z".len());
+        assert!(date.ends_with('Z'));
+    }
+
     #[test]
     fn git_commit_returns_value() {
         let commit = git_commit();
         assert!(!commit.is_empty());
     }
+
+    #[test]
+    fn json_has_build_and_toolchain_fields() {
+        let json = serde_json::to_value(BuildInfo::current()).unwrap();
+        for field in [
+            "version",
+            "commit",
+            "commit_date",
+            "build_date",
+            "profile",
+            "platform",
+            "min_toolchain",
+            "max_toolchain",
+        ] {
+            assert!(json[field].is_string(), "missing field {field}");
+        }
+        assert_eq!(json["min_toolchain"], SUPPORTED_TOOLCHAINS.minimum);
+        assert_eq!(json["max_toolchain"], SUPPORTED_TOOLCHAINS.maximum);
+    }
+
+    #[test]
+    fn missing_build_values_are_unknown() {
+        let info = BuildInfo::new(None, None, None, Some(""), &SUPPORTED_TOOLCHAINS);
+        assert_eq!(info.commit, "unknown");
+        assert_eq!(info.commit_date, "unknown");
+        assert_eq!(info.build_date, "unknown");
+        assert_eq!(info.profile, "unknown");
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn build_values_are_reported_as_set() {
+        let info = BuildInfo::new(
+            Some("v0.1.0-2-gabc1234-dirty"),
+            Some("2025-01-01T12:00:00+00:00"),
+            Some("0"),
+            Some("release"),
+            &SUPPORTED_TOOLCHAINS,
+        );
+        assert_eq!(info.commit, "v0.1.0-2-gabc1234-dirty");
+        assert_eq!(info.commit_date, "2025-01-01T12:00:00+00:00");
+        assert_eq!(info.build_date, "1970-01-01T00:00:00Z");
+        assert_eq!(info.profile, "release");
+    }
 }
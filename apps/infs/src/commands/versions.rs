@@ -9,6 +9,8 @@
 //! infs versions           # List all available versions
 //! infs versions --stable  # List only stable versions
 //! infs versions --json    # Output in JSON format
+//! infs versions --notes   # Show the first lines of each version's release notes
+//! infs versions --refresh # Ignore the cached release manifest
 //! ```
 //!
 //! ## Output Format
@@ -22,16 +24,24 @@
 //!
 //!   * = available for current platform (linux)
 //! ```
+//!
+//! JSON output includes a `notes` excerpt for versions with release notes.
+//! `infs changelog <version>` prints the full notes.
+//!
+//! When the manifest is read from the cache, a line on stderr says how old
+//! it is; `--refresh` fetches it from the distribution server instead.
 
 use anyhow::Result;
 use clap::Args;
 use serde::Serialize;
 
 use crate::toolchain::Platform;
-use crate::toolchain::manifest::{fetch_manifest, sorted_versions};
+use crate::toolchain::manifest::{load_manifest, sorted_versions};
+use crate::toolchain::release_notes::EXCERPT_LINES;
 
 /// Arguments for the versions command.
 #[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct VersionsArgs {
     /// Show only stable versions.
     #[clap(long, short = 's')]
@@ -40,6 +50,14 @@ pub struct VersionsArgs {
     /// Show versions in JSON format.
     #[clap(long, short = 'j')]
     pub json: bool,
+
+    /// Show the first lines of each version's release notes.
+    #[clap(long)]
+    pub notes: bool,
+
+    /// Fetch the release manifest even if a cached copy exists.
+    #[clap(long)]
+    pub refresh: bool,
 }
 
 /// Version information for JSON output.
@@ -49,6 +67,8 @@ struct VersionInfo {
     stable: bool,
     platforms: Vec<String>,
     available_for_current: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
 }
 
 /// Executes the versions command.
@@ -56,7 +76,8 @@ struct VersionInfo {
 /// # Process
 ///
 /// 1. Detect the current platform
-/// 2. Fetch the release manifest from the distribution server
+/// 2. Fetch the release manifest from the distribution server, or the
+///    cache unless `--refresh` is set
 /// 3. Sort versions by semver (newest first)
 /// 4. Filter by stability if --stable flag is set
 /// 5. Output in text or JSON format
@@ -68,12 +89,15 @@ struct VersionInfo {
 /// - Manifest fetch fails (network error, parsing error, etc.)
 pub async fn execute(args: &VersionsArgs) -> Result<()> {
     let platform = Platform::detect()?;
-    let manifest = fetch_manifest().await?;
+    let (manifest, source) = load_manifest(args.refresh).await?;
+    if let Some(notice) = source.staleness_notice() {
+        eprintln!("{notice}");
+    }
 
     if args.json {
         output_json(&manifest, args.stable, platform)?;
     } else {
-        output_text(&manifest, args.stable, platform);
+        output_text(&manifest, args.stable, args.notes, platform);
     }
 
     Ok(())
@@ -99,6 +123,7 @@ fn output_json(
                 .map(String::from)
                 .collect(),
             available_for_current: v.has_platform(platform),
+            notes: v.notes_excerpt(EXCERPT_LINES),
         })
         .collect();
 
@@ -111,6 +136,7 @@ fn output_json(
 fn output_text(
     manifest: &crate::toolchain::manifest::Manifest,
     stable_only: bool,
+    show_notes: bool,
     platform: Platform,
 ) {
     let versions = sorted_versions(manifest);
@@ -160,6 +186,11 @@ fn output_text(
             "  {} {} {}{}",
             version.version, stability, platform_list, available_marker
         );
+        if show_notes && let Some(notes) = version.notes_excerpt(EXCERPT_LINES) {
+            for line in notes.lines() {
+                println!("      {line}");
+            }
+        }
     }
 
     if has_current_platform {
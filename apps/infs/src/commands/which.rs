@@ -0,0 +1,357 @@
+//! Which command for the infs CLI.
+//!
+//! Shows where the toolchain binaries resolve to, to answer questions like
+//! "which infc am I running?".
+//!
+//! ## Usage
+//!
+//! ```bash
+//! infs which          # Human-readable report
+//! infs which --json   # Output in JSON format
+//! ```
+//!
+//! ## Output Format
+//!
+//! ```text
+//! Default toolchain: 0.2.0
+//!
+//! infc
+//!   toolchain: ~/.inference/toolchains/0.2.0/infc (exists)
+//!   symlink:   ~/.inference/bin/infc -> ~/.inference/toolchains/0.2.0/infc
+//! ...
+//!
+//! Resolved infc: ~/.inference/toolchains/0.2.0/infc (via managed toolchain)
+//! ```
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+use crate::toolchain::resolver::resolve_infc;
+use crate::toolchain::{Platform, ToolchainPaths};
+
+/// Arguments for the which command.
+#[derive(Args)]
+pub struct WhichArgs {
+    /// Show the report in JSON format.
+    #[clap(long, short = 'j')]
+    pub json: bool,
+}
+
+/// Resolution details for a single managed binary.
+#[derive(Debug, Clone, Serialize)]
+struct BinaryReport {
+    name: String,
+    toolchain_path: Option<String>,
+    toolchain_exists: bool,
+    symlink: String,
+    symlink_target: Option<String>,
+    symlink_valid: bool,
+}
+
+/// Where `find_infc` resolves the compiler.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ResolvedInfc {
+    pub(crate) path: String,
+    pub(crate) source: String,
+}
+
+/// Full report for the which command.
+#[derive(Debug, Clone, Serialize)]
+struct WhichReport {
+    default_version: Option<String>,
+    binaries: Vec<BinaryReport>,
+    infc: Option<ResolvedInfc>,
+    infc_error: Option<String>,
+}
+
+/// Executes the which command.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Toolchain paths cannot be determined
+/// - Platform detection fails
+/// - The default version file cannot be read
+/// - JSON serialization fails
+pub fn execute(args: &WhichArgs) -> Result<()> {
+    let paths = ToolchainPaths::new()?;
+    let report = collect_report(&paths)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_text(&report));
+    }
+
+    Ok(())
+}
+
+/// Gathers resolution details for all managed binaries.
+fn collect_report(paths: &ToolchainPaths) -> Result<WhichReport> {
+    // symlink_targets() lists no binaries on unsupported platforms.
+    Platform::detect()?;
+    let default_version = paths.get_default_version()?;
+
+    let binaries = paths
+        .symlink_targets()
+        .into_iter()
+        .map(|(binary, symlink_target)| {
+            let toolchain_path = default_version
+                .as_deref()
+                .map(|version| paths.binary_path(version, &binary));
+            let symlink = paths.symlink_path(&binary);
+
+            BinaryReport {
+                name: binary,
+                toolchain_exists: toolchain_path.as_deref().is_some_and(Path::exists),
+                toolchain_path: toolchain_path.map(|p| p.display().to_string()),
+                symlink_valid: symlink_target.is_some() && symlink.exists(),
+                symlink: symlink.display().to_string(),
+                symlink_target: symlink_target.map(|p| p.display().to_string()),
+            }
+        })
+        .collect();
+
+    let (infc, infc_error) = resolve_infc_report();
+
+    Ok(WhichReport {
+        default_version,
+        binaries,
+        infc,
+        infc_error,
+    })
+}
+
+/// Resolves `infc` as `build` and `run` would.
+///
+/// Returns the resolved binary, or the first line of the resolution error.
+pub(crate) fn resolve_infc_report() -> (Option<ResolvedInfc>, Option<String>) {
+    match resolve_infc() {
+        Ok((path, source)) => (
+            Some(ResolvedInfc {
+                path: path.display().to_string(),
+                source: source.as_str().to_string(),
+            }),
+            None,
+        ),
+        Err(e) => (None, e.to_string().lines().next().map(String::from)),
+    }
+}
+
+/// Formats the report for human-readable output.
+fn format_text(report: &WhichReport) -> String {
+    let mut out = String::new();
+
+    match &report.default_version {
+        Some(version) => {
+            let _ = writeln!(out, "Default toolchain: {version}");
+        }
+        None => {
+            let _ = writeln!(out, "Default toolchain: (none)");
+        }
+    }
+
+    for binary in &report.binaries {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}", binary.name);
+
+        match &binary.toolchain_path {
+            Some(path) => {
+                let state = if binary.toolchain_exists {
+                    "exists"
+                } else {
+                    "missing"
+                };
+                let _ = writeln!(out, "  toolchain: {path} ({state})");
+            }
+            None => {
+                let _ = writeln!(out, "  toolchain: (no default toolchain)");
+            }
+        }
+
+        match &binary.symlink_target {
+            Some(target) if binary.symlink_valid => {
+                let _ = writeln!(out, "  symlink:   {} -> {target}", binary.symlink);
+            }
+            Some(target) => {
+                let _ = writeln!(out, "  symlink:   {} -> {target} (broken)", binary.symlink);
+            }
+            None => {
+                let _ = writeln!(out, "  symlink:   {} (missing)", binary.symlink);
+            }
+        }
+    }
+
+    let _ = writeln!(out);
+    match (&report.infc, &report.infc_error) {
+        (Some(infc), _) => {
+            let _ = writeln!(out, "Resolved infc: {} (via {})", infc.path, infc.source);
+        }
+        (None, Some(error)) => {
+            let _ = writeln!(out, "Resolved infc: {error}");
+        }
+        (None, None) => {
+            let _ = writeln!(out, "Resolved infc: not found");
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::env;
+
+    /// Creates a fake toolchain with all managed binaries and makes it the default.
+    fn install_default_toolchain(paths: &ToolchainPaths, version: &str) {
+        let bin_dir = paths.toolchain_bin_dir(version);
+        std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+        let ext = env::consts::EXE_SUFFIX;
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            std::fs::write(bin_dir.join(format!("{name}{ext}")), "").expect("Should write binary");
+        }
+        paths
+            .set_default_version(version)
+            .expect("Should set default");
+    }
+
+    #[test]
+    fn format_text_without_default_toolchain() {
+        let report = WhichReport {
+            default_version: None,
+            binaries: vec![BinaryReport {
+                name: "infc".to_string(),
+                toolchain_path: None,
+                toolchain_exists: false,
+                symlink: "/home/.inference/bin/infc".to_string(),
+                symlink_target: None,
+                symlink_valid: false,
+            }],
+            infc: None,
+            infc_error: Some("infc compiler not found.".to_string()),
+        };
+
+        let text = format_text(&report);
+        assert!(text.contains("Default toolchain: (none)"));
+        assert!(text.contains("/home/.inference/bin/infc (missing)"));
+        assert!(text.contains("Resolved infc: infc compiler not found."));
+    }
+
+    #[test]
+    fn format_text_marks_broken_symlink() {
+        let report = WhichReport {
+            default_version: Some("0.1.0".to_string()),
+            binaries: vec![BinaryReport {
+                name: "inf-llc".to_string(),
+                toolchain_path: Some("/tc/0.1.0/bin/inf-llc".to_string()),
+                toolchain_exists: true,
+                symlink: "/bin/inf-llc".to_string(),
+                symlink_target: Some("/gone/inf-llc".to_string()),
+                symlink_valid: false,
+            }],
+            infc: None,
+            infc_error: None,
+        };
+
+        let text = format_text(&report);
+        assert!(text.contains("/tc/0.1.0/bin/inf-llc (exists)"));
+        assert!(text.contains("/bin/inf-llc -> /gone/inf-llc (broken)"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn report_reflects_infc_resolution_order() {
+        let root = temp_test_dir("which_resolution");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_default_toolchain(&paths, "0.1.0");
+        paths
+            .update_symlinks("0.1.0")
+            .expect("Should create symlinks");
+
+        let override_infc = root.join("custom-infc");
+        std::fs::write(&override_infc, "").expect("Should write override");
+
+        let original_path = env::var("PATH").unwrap_or_default();
+
+        // SAFETY: This test runs serially and restores the env vars at the end.
+        unsafe {
+            env::set_var("INFERENCE_HOME", &root);
+            env::set_var("PATH", "");
+            env::remove_var("INFC_PATH");
+        }
+        let managed = collect_report(&paths).expect("Should collect report");
+
+        // SAFETY: See above.
+        unsafe {
+            env::set_var("INFC_PATH", &override_infc);
+        }
+        let overridden = collect_report(&paths).expect("Should collect report");
+
+        // SAFETY: Cleanup - restoring previous state.
+        unsafe {
+            env::set_var("PATH", original_path);
+            env::remove_var("INFC_PATH");
+            env::remove_var("INFERENCE_HOME");
+        }
+
+        assert_eq!(managed.default_version.as_deref(), Some("0.1.0"));
+        assert!(managed.binaries.iter().all(|b| b.toolchain_exists));
+        assert!(managed.binaries.iter().all(|b| b.symlink_valid));
+        assert!(format_text(&managed).contains("(via managed toolchain)"));
+        let infc = managed.infc.expect("Should resolve infc");
+        assert_eq!(infc.source, "managed toolchain");
+        assert!(infc.path.starts_with(&root.display().to_string()));
+
+        let infc = overridden.infc.expect("Should resolve infc");
+        assert_eq!(infc.source, "INFC_PATH");
+        assert_eq!(infc.path, override_infc.display().to_string());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial_test::serial]
+    fn report_prefers_system_path_over_managed_toolchain() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_test_dir("which_system_path");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_default_toolchain(&paths, "0.1.0");
+
+        let system_dir = root.join("system");
+        std::fs::create_dir_all(&system_dir).expect("Should create system dir");
+        let system_infc = system_dir.join("infc");
+        std::fs::write(&system_infc, "#!/bin/sh\n").expect("Should write infc");
+        std::fs::set_permissions(&system_infc, std::fs::Permissions::from_mode(0o755))
+            .expect("Should set permissions");
+
+        let original_path = env::var("PATH").unwrap_or_default();
+
+        // SAFETY: This test runs serially and restores the env vars at the end.
+        unsafe {
+            env::set_var("INFERENCE_HOME", &root);
+            env::set_var("PATH", &system_dir);
+            env::remove_var("INFC_PATH");
+        }
+        let report = collect_report(&paths).expect("Should collect report");
+
+        // SAFETY: Cleanup - restoring previous state.
+        unsafe {
+            env::set_var("PATH", original_path);
+            env::remove_var("INFERENCE_HOME");
+        }
+
+        let infc = report.infc.expect("Should resolve infc");
+        assert_eq!(infc.source, "PATH");
+        assert_eq!(infc.path, system_infc.display().to_string());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
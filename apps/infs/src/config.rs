@@ -0,0 +1,136 @@
+//! User configuration for the infs CLI.
+//!
+//! Settings are read from `config.toml` in the infs home directory
+//! (`~/.inference/config.toml` by default). The file is optional; a missing
+//! file or a missing key falls back to the defaults below.
+//!
+//! ## Example
+//!
+//! ```toml
+//! [security]
+//! # Fail installation when a release artifact cannot be verified.
+//! require_signatures = true
+//! # Verify signatures with this minisign public key. There is no built-in
+//! # key yet, so signatures are only checked when this is set.
+//! public_key = "RWS...base64 minisign public key..."
+//!
+//! [tui]
+//! # Hide the "update available" banner on the TUI main screen.
+//! update_banner = false
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::toolchain::ToolchainPaths;
+
+/// Contents of `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InfsConfig {
+    /// Settings for verifying downloaded toolchains.
+    pub security: SecurityConfig,
+    /// Settings for the interactive TUI.
+    pub tui: TuiConfig,
+}
+
+/// The `[security]` section of `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityConfig {
+    /// Refuse to install artifacts that have no signature.
+    ///
+    /// When `false`, an unsigned artifact is installed after its checksum
+    /// matches, with a warning.
+    pub require_signatures: bool,
+    /// Minisign public key to verify signatures with, overriding the
+    /// built-in release key (currently unset).
+    pub public_key: Option<String>,
+}
+
+/// The `[tui]` section of `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    /// Check for new releases in the background and show a banner.
+    pub update_banner: bool,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            update_banner: true,
+        }
+    }
+}
+
+impl InfsConfig {
+    /// Parses configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid TOML or contains unknown keys.
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content).context("Failed to parse infs configuration")
+    }
+
+    /// Loads the configuration for `paths`, using defaults if the file is absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(paths: &ToolchainPaths) -> Result<Self> {
+        let path = paths.config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = InfsConfig::parse("").expect("Should parse empty config");
+        assert_eq!(config, InfsConfig::default());
+        assert!(!config.security.require_signatures);
+        assert!(config.security.public_key.is_none());
+        assert!(config.tui.update_banner);
+    }
+
+    #[test]
+    fn update_banner_can_be_disabled() {
+        let config =
+            InfsConfig::parse("[tui]\nupdate_banner = false\n").expect("Should parse config");
+        assert!(!config.tui.update_banner);
+        assert_eq!(config.security, SecurityConfig::default());
+    }
+
+    #[test]
+    fn security_section_is_parsed() {
+        let config =
+            InfsConfig::parse("[security]\nrequire_signatures = true\npublic_key = \"RWQkey\"\n")
+                .expect("Should parse config");
+        assert!(config.security.require_signatures);
+        assert_eq!(config.security.public_key.as_deref(), Some("RWQkey"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(InfsConfig::parse("[security]\nrequire_signature = true\n").is_err());
+    }
+
+    #[test]
+    fn missing_file_uses_defaults() {
+        let root = std::env::temp_dir().join(format!("infs_test_config_{}", rand::random::<u64>()));
+        let paths = ToolchainPaths::with_root(root);
+        let config = InfsConfig::load(&paths).expect("Should load defaults");
+        assert_eq!(config, InfsConfig::default());
+    }
+}
@@ -0,0 +1,153 @@
+//! Confirmation prompts for destructive commands.
+//!
+//! Commands that delete something the user cannot get back by rebuilding,
+//! such as `uninstall` or `clean --all`, ask first when run in a terminal:
+//!
+//! ```text
+//! Uninstall toolchain 0.1.0? [y/N]
+//! ```
+//!
+//! The global `--yes` (`-y`) flag answers yes to every prompt, which also
+//! skips the version choice of `install` and the quit confirmation of the
+//! TUI. Without a terminal there is nobody to ask, so commands go ahead as
+//! if `--yes` were given.
+//!
+//! Commands take a [`Confirmer`] so tests can check whether a prompt would
+//! have been shown.
+
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+static ASSUME_YES: OnceLock<bool> = OnceLock::new();
+
+/// Sets whether prompts are answered with yes for this process.
+///
+/// Called by `main` after parsing arguments. The value can only be set once;
+/// until then prompts are shown.
+pub fn set_assume_yes(yes: bool) {
+    let _ = ASSUME_YES.set(yes);
+}
+
+/// Returns whether `--yes` was given.
+#[must_use]
+pub fn assume_yes() -> bool {
+    ASSUME_YES.get().copied().unwrap_or(false)
+}
+
+/// Asks the user to confirm an action.
+pub trait Confirmer {
+    /// Asks `question`, returning whether the user agreed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the answer cannot be read.
+    fn confirm(&mut self, question: &str) -> Result<bool>;
+}
+
+/// Asks on the terminal, defaulting to no.
+///
+/// Agrees without asking when stdin or stdout is not a terminal.
+#[derive(Debug, Default)]
+pub struct TerminalConfirmer;
+
+impl Confirmer for TerminalConfirmer {
+    fn confirm(&mut self, question: &str) -> Result<bool> {
+        if !(std::io::stdin().is_terminal() && std::io::stdout().is_terminal()) {
+            return Ok(true);
+        }
+        print!("{question} [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+            println!();
+            return Ok(false);
+        }
+        Ok(is_yes(&answer))
+    }
+}
+
+/// Returns true for `y` or `yes`, in any case.
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Asks `question` through `confirmer` unless `assume_yes` is set.
+///
+/// # Errors
+///
+/// Returns an error if the confirmer fails.
+pub fn confirm(confirmer: &mut dyn Confirmer, assume_yes: bool, question: &str) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+    confirmer.confirm(question)
+}
+
+/// Confirmers for tests.
+#[cfg(test)]
+pub mod testing {
+    use super::Confirmer;
+    use anyhow::{Result, bail};
+
+    /// Fails if asked anything.
+    pub struct NeverAsked;
+
+    impl Confirmer for NeverAsked {
+        fn confirm(&mut self, question: &str) -> Result<bool> {
+            bail!("unexpected prompt: {question}")
+        }
+    }
+
+    /// Gives a fixed answer and records the questions asked.
+    pub struct Answer {
+        pub agree: bool,
+        pub asked: Vec<String>,
+    }
+
+    impl Answer {
+        pub fn new(agree: bool) -> Self {
+            Self {
+                agree,
+                asked: Vec::new(),
+            }
+        }
+    }
+
+    impl Confirmer for Answer {
+        fn confirm(&mut self, question: &str) -> Result<bool> {
+            self.asked.push(question.to_string());
+            Ok(self.agree)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{Answer, NeverAsked};
+    use super::*;
+
+    #[test]
+    fn assume_yes_skips_the_prompt() {
+        assert!(confirm(&mut NeverAsked, true, "Delete?").unwrap());
+        assert!(confirm(&mut NeverAsked, false, "Delete?").is_err());
+    }
+
+    #[test]
+    fn confirm_returns_the_answer() {
+        let mut answer = Answer::new(false);
+        assert!(!confirm(&mut answer, false, "Delete?").unwrap());
+        assert_eq!(answer.asked, ["Delete?"]);
+    }
+
+    #[test]
+    fn is_yes_accepts_y_and_yes_only() {
+        for answer in ["y\n", "Y", " yes ", "YES\r\n"] {
+            assert!(is_yes(answer), "{answer:?}");
+        }
+        for answer in ["", "\n", "n", "no", "yep", "1"] {
+            assert!(!is_yes(answer), "{answer:?}");
+        }
+    }
+}
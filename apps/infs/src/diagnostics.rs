@@ -0,0 +1,398 @@
+//! Source-annotated diagnostic rendering.
+//!
+//! Compiler errors reach infs as a file path, a source span, a severity and
+//! a message. This module renders them the way rustc does, with the
+//! offending source line and a caret underline:
+//!
+//! ```text
+//! error: use of undeclared variable `x`
+//!  --> src/main.inf:2:12
+//!   |
+//! 2 |     return x;
+//!   |            ^
+//! ```
+//!
+//! Spans covering several lines are drawn with a connecting bar on the left.
+//! Colors follow the global `--color` setting (see [`crate::output`]).
+//!
+//! `infs build` and `infs run` ask `infc` for JSON diagnostics
+//! (`--message-format json`) when the installed toolchain supports it, and
+//! render each one here. Toolchains without the flag keep printing their
+//! plain messages, which are passed through unchanged.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::output::{self, Stream, Style};
+
+/// `infc` option selecting the diagnostic format.
+pub const MESSAGE_FORMAT_FLAG: &str = "--message-format";
+
+/// Number of lines shown at each end of a long multi-line span.
+const SPAN_CONTEXT_LINES: u32 = 2;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Self::Error => Style::Error,
+            Self::Warning => Style::Warning,
+        }
+    }
+}
+
+/// A region of a source file.
+///
+/// Lines and columns are 1-based, matching `Location` in the compiler;
+/// `end_column` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Span {
+    #[serde(rename = "line")]
+    pub start_line: u32,
+    #[serde(rename = "column")]
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+/// A message about a span of a source file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable warning code such as `unused_import`, if any.
+    #[serde(default)]
+    pub code: Option<String>,
+    pub message: String,
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// Parses one line of `infc --message-format json` output.
+    ///
+    /// Returns `None` for lines that are not diagnostics.
+    #[must_use]
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.trim_start().starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    /// Renders the diagnostic against `source`, the contents of [`Self::path`].
+    ///
+    /// Without the source, or if the span lies outside it, only the header
+    /// and the `-->` location line are rendered.
+    #[must_use]
+    pub fn render(&self, source: Option<&str>, colors: bool) -> String {
+        let paint = |style: Style, text: &str| output::apply(style, text, colors);
+        let severity_style = self.severity.style();
+        let span = self.span;
+
+        let label = match &self.code {
+            Some(code) => format!("{}[{code}]", self.severity.label()),
+            None => self.severity.label().to_string(),
+        };
+        let mut out = format!(
+            "{}{}\n",
+            paint(severity_style, &label),
+            paint(Style::Emphasis, &format!(": {}", self.message))
+        );
+
+        let lines: Vec<&str> = source.map(|s| s.lines().collect()).unwrap_or_default();
+        let shown = shown_lines(span, lines.len());
+        let width = shown
+            .iter()
+            .flatten()
+            .max()
+            .map_or(0, |line| line.to_string().len());
+        let gutter = " ".repeat(width);
+
+        let _ = writeln!(
+            out,
+            "{gutter}{} {}:{}:{}",
+            paint(Style::Gutter, "-->"),
+            self.path.display(),
+            span.start_line,
+            span.start_column
+        );
+        if shown.is_empty() {
+            return out;
+        }
+
+        let bar = paint(Style::Gutter, "|");
+        let _ = writeln!(out, "{gutter} {bar}");
+        let source_line = |out: &mut String, number: u32, marker: &str| {
+            let text = lines[(number - 1) as usize];
+            let number = paint(Style::Gutter, &format!("{number:>width$}"));
+            let _ = writeln!(out, "{number} {bar} {marker}{text}");
+        };
+
+        if span.start_line == span.end_line {
+            source_line(&mut out, span.start_line, "");
+            let carets = span.end_column.saturating_sub(span.start_column).max(1);
+            let _ = writeln!(
+                out,
+                "{gutter} {bar} {}{}",
+                " ".repeat(span.start_column.saturating_sub(1) as usize),
+                paint(severity_style, &"^".repeat(carets as usize))
+            );
+            return out;
+        }
+
+        let span_bar = paint(severity_style, "|");
+        for (index, line) in shown.iter().enumerate() {
+            match line {
+                Some(number) if *number == span.start_line => {
+                    source_line(&mut out, *number, "  ");
+                    let start = format!("{}^", "_".repeat(span.start_column.max(1) as usize));
+                    let _ = writeln!(out, "{gutter} {bar}  {}", paint(severity_style, &start));
+                }
+                Some(number) => {
+                    source_line(&mut out, *number, &format!("{span_bar} "));
+                }
+                None => {
+                    let _ = writeln!(out, "{}", paint(Style::Gutter, "..."));
+                }
+            }
+            if index + 1 == shown.len() {
+                let last_column = span.end_column.saturating_sub(1).max(1);
+                let end = format!("{}^", "_".repeat(last_column as usize));
+                let _ = writeln!(
+                    out,
+                    "{gutter} {bar} {span_bar}{}",
+                    paint(severity_style, &end)
+                );
+            }
+        }
+        out
+    }
+
+    /// Prints the diagnostic to stderr, reading the source from [`Self::path`].
+    pub fn emit(&self) {
+        let source = std::fs::read_to_string(&self.path).ok();
+        eprint!(
+            "{}",
+            self.render(source.as_deref(), output::colors_enabled(Stream::Stderr))
+        );
+    }
+}
+
+/// Returns the line numbers to print for `span`, with `None` marking elided lines.
+///
+/// Returns an empty list if the span is not within a file of `line_count` lines.
+fn shown_lines(span: Span, line_count: usize) -> Vec<Option<u32>> {
+    let in_file = |line: u32| line >= 1 && (line as usize) <= line_count;
+    if span.end_line < span.start_line || !in_file(span.start_line) || !in_file(span.end_line) {
+        return Vec::new();
+    }
+    let total = span.end_line - span.start_line + 1;
+    if total <= 2 * SPAN_CONTEXT_LINES + 1 {
+        return (span.start_line..=span.end_line).map(Some).collect();
+    }
+    let mut lines: Vec<Option<u32>> = (span.start_line..span.start_line + SPAN_CONTEXT_LINES)
+        .map(Some)
+        .collect();
+    lines.push(None);
+    lines.extend((span.end_line + 1 - SPAN_CONTEXT_LINES..=span.end_line).map(Some));
+    lines
+}
+
+/// Returns whether the `infc` at `infc_path` can print JSON diagnostics.
+#[must_use]
+pub fn infc_supports_json(infc_path: &Path) -> bool {
+    Command::new(infc_path)
+        .arg("--help")
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(MESSAGE_FORMAT_FLAG))
+}
+
+/// Runs an `infc` command, rendering the diagnostics it reports.
+///
+/// If the toolchain supports JSON diagnostics, they are requested and each
+/// one is rendered with [`Diagnostic::emit`]; other stderr lines are passed
+/// through. Otherwise stderr is inherited unchanged.
+///
+/// # Errors
+///
+/// Returns an error if `infc` cannot be started.
+pub fn run_infc(cmd: &mut Command, infc_path: &Path) -> Result<ExitStatus> {
+    cmd.stdin(Stdio::inherit()).stdout(Stdio::inherit());
+    if !infc_supports_json(infc_path) {
+        return cmd
+            .stderr(Stdio::inherit())
+            .status()
+            .with_context(|| format!("Failed to execute infc at {}", infc_path.display()));
+    }
+
+    let mut child = cmd
+        .arg(MESSAGE_FORMAT_FLAG)
+        .arg("json")
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines() {
+            let line = line.context("Failed to read infc output")?;
+            match Diagnostic::from_json_line(&line) {
+                Some(diagnostic) => diagnostic.emit(),
+                None => eprintln!("{line}"),
+            }
+        }
+    }
+
+    child.wait().context("Failed to wait for infc")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn f() -> i32 {\n    let y: i32 = 1;\n    return x;\n}\n";
+
+    fn diagnostic(start: (u32, u32), end: (u32, u32)) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: None,
+            message: "use of undeclared variable `x`".to_string(),
+            path: PathBuf::from("src/main.inf"),
+            span: Span {
+                start_line: start.0,
+                start_column: start.1,
+                end_line: end.0,
+                end_column: end.1,
+            },
+        }
+    }
+
+    #[test]
+    fn single_line_span_without_color() {
+        let rendered = diagnostic((3, 12), (3, 13)).render(Some(SOURCE), false);
+        assert_eq!(
+            rendered,
+            "error: use of undeclared variable `x`\n \
+             --> src/main.inf:3:12\n  \
+             |\n\
+             3 |     return x;\n  \
+             |            ^\n"
+        );
+    }
+
+    #[test]
+    fn single_line_span_with_color() {
+        let rendered = diagnostic((3, 5), (3, 14)).render(Some(SOURCE), true);
+        assert_eq!(
+            rendered,
+            "\x1b[1;31merror\x1b[0m\x1b[1m: use of undeclared variable `x`\x1b[0m\n \
+             \x1b[1;34m-->\x1b[0m src/main.inf:3:5\n  \
+             \x1b[1;34m|\x1b[0m\n\
+             \x1b[1;34m3\x1b[0m \x1b[1;34m|\x1b[0m     return x;\n  \
+             \x1b[1;34m|\x1b[0m     \x1b[1;31m^^^^^^^^^\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_span_without_color() {
+        let rendered = diagnostic((1, 15), (4, 2)).render(Some(SOURCE), false);
+        assert_eq!(
+            rendered,
+            "error: use of undeclared variable `x`\n \
+             --> src/main.inf:1:15\n  \
+             |\n\
+             1 |   fn f() -> i32 {\n  \
+             |  _______________^\n\
+             2 | |     let y: i32 = 1;\n\
+             3 | |     return x;\n\
+             4 | | }\n  \
+             | |_^\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_span_with_color() {
+        let rendered = diagnostic((3, 5), (4, 2)).render(Some(SOURCE), true);
+        let bar = "\x1b[1;34m|\x1b[0m";
+        let span_bar = "\x1b[1;31m|\x1b[0m";
+        assert_eq!(
+            rendered,
+            format!(
+                "\x1b[1;31merror\x1b[0m\x1b[1m: use of undeclared variable `x`\x1b[0m\n \
+                 \x1b[1;34m-->\x1b[0m src/main.inf:3:5\n  \
+                 {bar}\n\
+                 \x1b[1;34m3\x1b[0m {bar}       return x;\n  \
+                 {bar}  \x1b[1;31m_____^\x1b[0m\n\
+                 \x1b[1;34m4\x1b[0m {bar} {span_bar} }}\n  \
+                 {bar} {span_bar}\x1b[1;31m_^\x1b[0m\n"
+            )
+        );
+    }
+
+    #[test]
+    fn long_multi_line_span_elides_middle_lines() {
+        let source = (1..=12)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let rendered = diagnostic((2, 1), (11, 8)).render(Some(&source), false);
+        assert_eq!(
+            rendered,
+            "error: use of undeclared variable `x`\n  \
+             --> src/main.inf:2:1\n   \
+             |\n 2 |   line 2\n   \
+             |  _^\n 3 | | line 3\n\
+             ...\n\
+             10 | | line 10\n\
+             11 | | line 11\n   \
+             | |_______^\n"
+        );
+    }
+
+    #[test]
+    fn missing_source_renders_location_only() {
+        let with_warning_code = Diagnostic {
+            severity: Severity::Warning,
+            code: Some("unused_import".to_string()),
+            ..diagnostic((40, 1), (40, 4))
+        };
+        assert_eq!(
+            with_warning_code.render(Some(SOURCE), false),
+            "warning[unused_import]: use of undeclared variable `x`\n--> src/main.inf:40:1\n"
+        );
+        assert_eq!(
+            diagnostic((3, 12), (3, 13)).render(None, false),
+            "error: use of undeclared variable `x`\n--> src/main.inf:3:12\n"
+        );
+    }
+
+    #[test]
+    fn parses_infc_json_lines() {
+        let line = r#"{"code":null,"column":12,"end_column":13,"end_line":3,"line":3,"message":"use of undeclared variable `x`","path":"src/main.inf","severity":"error"}"#;
+        assert_eq!(
+            Diagnostic::from_json_line(line),
+            Some(diagnostic((3, 12), (3, 13)))
+        );
+        assert_eq!(Diagnostic::from_json_line("Parsed: src/main.inf"), None);
+        assert_eq!(Diagnostic::from_json_line("{not json"), None);
+    }
+}
@@ -4,10 +4,59 @@
 //! that can occur during CLI operations. While the current implementation primarily
 //! uses `anyhow::Result` for error handling, these typed errors enable more precise
 //! error handling and better error messages in specific scenarios.
+//!
+//! ## Exit Codes
+//!
+//! Each variant maps to a process exit code (see [`InfsError::exit_code`])
+//! so scripts can branch on the kind of failure:
+//!
+//! | Code | Meaning | Variants |
+//! |------|---------|----------|
+//! | 0 | Success | |
+//! | 1 | General failure | `IoError`, `ManifestError`, `InstallError`, untyped errors |
+//! | 2 | Invalid arguments | `InvalidArguments` (clap usage errors also exit with 2) |
+//! | 3 | File not found | `FileNotFound` |
+//! | 4 | Compilation failed | `ParseError`, `TypeCheckError`, `AnalysisError`, `CodegenError` |
+//! | 10 | Network failure | `NetworkError`, `DownloadError` |
+//! | 11 | Checksum or signature mismatch | `ChecksumMismatch`, `SignatureMismatch` |
+//! | 12 | Version not in the release manifest | `VersionNotFound` |
+//! | 13 | Toolchain version not installed | `ToolchainNotFound` |
+//! | 14 | Required tool not found | `ToolNotFound` |
+//! | any | Exit code of a failed subprocess | `ProcessExitCode` |
+//!
+//! Subprocess exit codes are passed through unchanged, so a failing
+//! `wasmtime` or `infc` can produce any of the codes above.
 
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Exit code for failures without a more specific code.
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code for invalid command line arguments.
+pub const EXIT_INVALID_ARGUMENTS: i32 = 2;
+
+/// Exit code when an input file does not exist.
+pub const EXIT_FILE_NOT_FOUND: i32 = 3;
+
+/// Exit code when compiling Inference source fails.
+pub const EXIT_COMPILATION_FAILED: i32 = 4;
+
+/// Exit code for network and download failures.
+pub const EXIT_NETWORK: i32 = 10;
+
+/// Exit code when a downloaded file does not match its checksum.
+pub const EXIT_CHECKSUM_MISMATCH: i32 = 11;
+
+/// Exit code when a version is not in the release manifest.
+pub const EXIT_VERSION_NOT_FOUND: i32 = 12;
+
+/// Exit code when a toolchain version is not installed.
+pub const EXIT_TOOLCHAIN_NOT_FOUND: i32 = 13;
+
+/// Exit code when a required tool (infc, wasmtime, ...) is not found.
+pub const EXIT_TOOL_NOT_FOUND: i32 = 14;
+
 /// Consolidated error type for infs CLI operations.
 ///
 /// This enum captures all error variants that can occur during compilation
@@ -68,6 +117,13 @@ pub enum InfsError {
         message: String,
     },
 
+    /// Network request failed (e.g., fetching the release manifest).
+    #[error("network error: {message}")]
+    NetworkError {
+        /// Description of the network error.
+        message: String,
+    },
+
     /// Network error during download.
     #[error("download error: {message}")]
     DownloadError {
@@ -87,6 +143,13 @@ pub enum InfsError {
         actual: String,
     },
 
+    /// Signature verification failed or a required signature is missing.
+    #[error("signature verification failed: {message}")]
+    SignatureMismatch {
+        /// Description of the signature error.
+        message: String,
+    },
+
     /// Manifest parsing or fetching failed.
     #[error("manifest error: {message}")]
     ManifestError {
@@ -94,6 +157,13 @@ pub enum InfsError {
         message: String,
     },
 
+    /// Requested version is not in the release manifest.
+    #[error("version not found: {version}")]
+    VersionNotFound {
+        /// The version or version requirement that was not found.
+        version: String,
+    },
+
     /// Toolchain not found.
     #[error("toolchain not found: {version}")]
     ToolchainNotFound {
@@ -101,6 +171,13 @@ pub enum InfsError {
         version: String,
     },
 
+    /// Required tool is not installed or not in PATH.
+    #[error("tool not found: {tool}")]
+    ToolNotFound {
+        /// The name of the missing tool.
+        tool: String,
+    },
+
     /// Installation failed.
     #[error("installation failed: {message}")]
     InstallError {
@@ -177,6 +254,14 @@ impl InfsError {
         }
     }
 
+    /// Creates a new `NetworkError`.
+    #[must_use]
+    pub fn network_error(message: impl Into<String>) -> Self {
+        Self::NetworkError {
+            message: message.into(),
+        }
+    }
+
     /// Creates a new `DownloadError`.
     #[must_use]
     pub fn download_error(message: impl Into<String>) -> Self {
@@ -207,6 +292,14 @@ impl InfsError {
         }
     }
 
+    /// Creates a new `SignatureMismatch` error.
+    #[must_use]
+    pub fn signature_mismatch(message: impl Into<String>) -> Self {
+        Self::SignatureMismatch {
+            message: message.into(),
+        }
+    }
+
     /// Creates a new `ManifestError`.
     #[must_use]
     pub fn manifest_error(message: impl Into<String>) -> Self {
@@ -215,6 +308,14 @@ impl InfsError {
         }
     }
 
+    /// Creates a new `VersionNotFound` error.
+    #[must_use]
+    pub fn version_not_found(version: impl Into<String>) -> Self {
+        Self::VersionNotFound {
+            version: version.into(),
+        }
+    }
+
     /// Creates a new `ToolchainNotFound` error.
     #[must_use]
     pub fn toolchain_not_found(version: impl Into<String>) -> Self {
@@ -223,6 +324,12 @@ impl InfsError {
         }
     }
 
+    /// Creates a new `ToolNotFound` error.
+    #[must_use]
+    pub fn tool_not_found(tool: impl Into<String>) -> Self {
+        Self::ToolNotFound { tool: tool.into() }
+    }
+
     /// Creates a new `InstallError`.
     #[must_use]
     pub fn install_error(message: impl Into<String>) -> Self {
@@ -236,6 +343,43 @@ impl InfsError {
     pub const fn process_exit_code(code: i32) -> Self {
         Self::ProcessExitCode { code }
     }
+
+    /// Returns the process exit code for this error.
+    ///
+    /// See the module documentation for the full table.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::InvalidArguments { .. } => EXIT_INVALID_ARGUMENTS,
+            Self::FileNotFound { .. } => EXIT_FILE_NOT_FOUND,
+            Self::ParseError { .. }
+            | Self::TypeCheckError { .. }
+            | Self::AnalysisError { .. }
+            | Self::CodegenError { .. } => EXIT_COMPILATION_FAILED,
+            Self::NetworkError { .. } | Self::DownloadError { .. } => EXIT_NETWORK,
+            Self::ChecksumMismatch { .. } | Self::SignatureMismatch { .. } => {
+                EXIT_CHECKSUM_MISMATCH
+            }
+            Self::VersionNotFound { .. } => EXIT_VERSION_NOT_FOUND,
+            Self::ToolchainNotFound { .. } => EXIT_TOOLCHAIN_NOT_FOUND,
+            Self::ToolNotFound { .. } => EXIT_TOOL_NOT_FOUND,
+            Self::ProcessExitCode { code } => *code,
+            Self::IoError { .. } | Self::ManifestError { .. } | Self::InstallError { .. } => {
+                EXIT_FAILURE
+            }
+        }
+    }
+
+    /// Finds the first `InfsError` in an error chain.
+    ///
+    /// Typed errors are usually wrapped with a user-facing message via
+    /// `anyhow::Context`, so the `InfsError` may sit below the top level.
+    #[must_use]
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        error
+            .downcast_ref::<Self>()
+            .or_else(|| error.chain().find_map(|cause| cause.downcast_ref::<Self>()))
+    }
 }
 
 #[cfg(test)]
@@ -298,4 +442,73 @@ mod tests {
         let err = InfsError::process_exit_code(42);
         assert_eq!(err.to_string(), "process exited with code 42");
     }
+
+    #[test]
+    fn exit_codes_are_distinct_per_failure_kind() {
+        let cases = [
+            (InfsError::invalid_arguments("bad"), 2),
+            (InfsError::file_not_found(PathBuf::from("a.inf")), 3),
+            (InfsError::parse_error("bad"), 4),
+            (InfsError::type_check_error("bad"), 4),
+            (InfsError::analysis_error("bad"), 4),
+            (InfsError::codegen_error("bad"), 4),
+            (InfsError::network_error("timeout"), 10),
+            (InfsError::download_error("timeout"), 10),
+            (InfsError::checksum_mismatch("abc", "def"), 11),
+            (InfsError::signature_mismatch("bad"), 11),
+            (InfsError::version_not_found("^9"), 12),
+            (InfsError::toolchain_not_found("0.1.0"), 13),
+            (InfsError::tool_not_found("wasmtime"), 14),
+            (
+                InfsError::io_error("read", std::io::Error::other("boom")),
+                1,
+            ),
+            (InfsError::manifest_error("bad"), 1),
+            (InfsError::install_error("bad"), 1),
+            (InfsError::process_exit_code(42), 42),
+        ];
+
+        for (err, expected) in cases {
+            assert_eq!(err.exit_code(), expected, "Wrong exit code for {err}");
+        }
+    }
+
+    #[test]
+    fn new_variants_display_details() {
+        assert_eq!(
+            InfsError::network_error("timeout").to_string(),
+            "network error: timeout"
+        );
+        assert_eq!(
+            InfsError::version_not_found("^0.9").to_string(),
+            "version not found: ^0.9"
+        );
+        assert_eq!(
+            InfsError::tool_not_found("wasmtime").to_string(),
+            "tool not found: wasmtime"
+        );
+    }
+
+    #[test]
+    fn find_locates_error_below_context() {
+        use anyhow::Context;
+
+        let err = Err::<(), _>(InfsError::tool_not_found("infc"))
+            .context("infc compiler not found")
+            .context("Failed to build")
+            .expect_err("Should be an error");
+        assert_eq!(err.to_string(), "Failed to build");
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(EXIT_TOOL_NOT_FOUND)
+        );
+
+        let err = anyhow::anyhow!("boom").context(InfsError::download_error("failed"));
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(EXIT_NETWORK)
+        );
+
+        assert!(InfsError::find(&anyhow::anyhow!("untyped")).is_none());
+    }
 }
@@ -0,0 +1,242 @@
+//! Leveled logging for the infs CLI.
+//!
+//! Informational output that commands always printed stays at the
+//! [`Level::Info`] default. Details that help debug network and install
+//! problems (URLs, HTTP status codes, checksums, staging paths, retries) are
+//! logged at [`Level::Debug`] and [`Level::Trace`] and only shown on request:
+//!
+//! ```bash
+//! infs -v install 0.1.0             # Debug messages
+//! infs -vv install 0.1.0            # Debug and trace messages
+//! INFS_LOG=debug infs install 0.1.0 # Same as -v
+//! INFS_LOG=warn infs install 0.1.0  # Only warnings and errors
+//! ```
+//!
+//! `-v` goes before the subcommand because `build -v` and `version -v`
+//! already have their own meaning. When both `-v` and `INFS_LOG` are given,
+//! the more verbose level wins.
+//!
+//! Info messages go to stdout as before; debug and trace messages go to
+//! stderr with a `debug:` or `trace:` label, so they never mix with output
+//! meant for scripts.
+//!
+//! Use the [`info!`], [`debug!`] and [`trace!`] macros rather than calling
+//! [`log`] directly.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::output::{self, Stream, Style};
+
+/// Environment variable selecting the log level.
+pub const LOG_ENV: &str = "INFS_LOG";
+
+static LEVEL: OnceLock<Level> = OnceLock::new();
+
+/// Severity of a log message, from least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The level used when neither `-v` nor `INFS_LOG` is given.
+    pub const DEFAULT: Level = Level::Info;
+
+    /// Parses a level name such as `debug`, in any case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warn),
+            "info" => Some(Self::Info),
+            "debug" => Some(Self::Debug),
+            "trace" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+
+    /// Returns the level selected by the number of `-v` flags.
+    #[must_use]
+    pub fn from_verbosity(verbose: u8) -> Self {
+        match verbose {
+            0 => Self::DEFAULT,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+
+    /// Returns the label printed before messages at this level.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error:",
+            Self::Warn => "warning:",
+            Self::Info => "info:",
+            Self::Debug => "debug:",
+            Self::Trace => "trace:",
+        }
+    }
+}
+
+/// Resolves the log level from the `-v` count and the `INFS_LOG` value.
+///
+/// An unrecognized `INFS_LOG` value is ignored.
+#[must_use]
+pub fn resolve(verbose: u8, env: Option<&str>) -> Level {
+    let from_env = env.and_then(Level::parse).unwrap_or(Level::DEFAULT);
+    if verbose == 0 {
+        from_env
+    } else {
+        from_env.max(Level::from_verbosity(verbose))
+    }
+}
+
+/// Sets the log level for this process from the `-v` count and `INFS_LOG`.
+///
+/// Called by `main` after parsing arguments. The level can only be set
+/// once; until then [`Level::DEFAULT`] is used.
+pub fn init(verbose: u8) {
+    let env = std::env::var(LOG_ENV).ok();
+    if let Some(value) = &env
+        && Level::parse(value).is_none()
+    {
+        output::warn(format_args!(
+            "ignoring unknown {LOG_ENV} value '{value}' (expected error, warn, info, debug or trace)"
+        ));
+    }
+    let _ = LEVEL.set(resolve(verbose, env.as_deref()));
+}
+
+/// Returns the log level of this process.
+#[must_use]
+pub fn level() -> Level {
+    LEVEL.get().copied().unwrap_or(Level::DEFAULT)
+}
+
+/// Returns whether messages at `level` are shown.
+#[must_use]
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+/// Destination of log messages.
+pub trait Sink {
+    /// Writes a message that passed the level check.
+    fn write(&mut self, level: Level, message: fmt::Arguments<'_>);
+}
+
+/// Writes info messages to stdout and everything else to stderr.
+pub struct TerminalSink;
+
+impl Sink for TerminalSink {
+    fn write(&mut self, level: Level, message: fmt::Arguments<'_>) {
+        match level {
+            Level::Info => println!("{message}"),
+            Level::Error => eprintln!(
+                "{} {message}",
+                output::paint(Stream::Stderr, Style::Error, level.label())
+            ),
+            Level::Warn => eprintln!(
+                "{} {message}",
+                output::paint(Stream::Stderr, Style::Warning, level.label())
+            ),
+            Level::Debug | Level::Trace => eprintln!(
+                "{} {message}",
+                output::paint(Stream::Stderr, Style::Gutter, level.label())
+            ),
+        }
+    }
+}
+
+/// Writes `message` to `sink` if `level` is enabled at `max`.
+pub fn log_to(sink: &mut dyn Sink, max: Level, level: Level, message: fmt::Arguments<'_>) {
+    if level <= max {
+        sink.write(level, message);
+    }
+}
+
+/// Writes `message` to the terminal if `level` is enabled.
+pub fn log(level: Level, message: fmt::Arguments<'_>) {
+    log_to(&mut TerminalSink, self::level(), level, message);
+}
+
+/// Logs an informational message, shown by default.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// Logs a debug message, shown with `-v` or `INFS_LOG=debug`.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+/// Logs a trace message, shown with `-vv` or `INFS_LOG=trace`.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Trace, format_args!($($arg)*))
+    };
+}
+
+pub(crate) use {debug, info, trace};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records messages instead of printing them.
+    #[derive(Default)]
+    struct Capture(Vec<(Level, String)>);
+
+    impl Sink for Capture {
+        fn write(&mut self, level: Level, message: fmt::Arguments<'_>) {
+            self.0.push((level, message.to_string()));
+        }
+    }
+
+    fn emit_all(max: Level) -> Vec<(Level, String)> {
+        let mut sink = Capture::default();
+        log_to(&mut sink, max, Level::Info, format_args!("installing"));
+        log_to(&mut sink, max, Level::Debug, format_args!("GET {}", "url"));
+        log_to(&mut sink, max, Level::Trace, format_args!("chunk"));
+        sink.0
+    }
+
+    #[test]
+    fn default_level_suppresses_debug_messages() {
+        let messages = emit_all(resolve(0, None));
+        assert_eq!(messages, [(Level::Info, String::from("installing"))]);
+    }
+
+    #[test]
+    fn double_verbose_emits_debug_and_trace_messages() {
+        let messages = emit_all(resolve(2, None));
+        assert_eq!(
+            messages,
+            [
+                (Level::Info, String::from("installing")),
+                (Level::Debug, String::from("GET url")),
+                (Level::Trace, String::from("chunk")),
+            ]
+        );
+        assert_eq!(emit_all(resolve(1, None)).len(), 2);
+    }
+
+    #[test]
+    fn env_sets_level_and_flags_only_raise_it() {
+        assert_eq!(resolve(0, Some("debug")), Level::Debug);
+        assert_eq!(resolve(0, Some("WARN")), Level::Warn);
+        assert_eq!(resolve(1, Some("warn")), Level::Debug);
+        assert_eq!(resolve(1, Some("trace")), Level::Trace);
+        assert_eq!(resolve(0, Some("loud")), Level::DEFAULT);
+        assert!(emit_all(resolve(0, Some("warn"))).is_empty());
+    }
+}
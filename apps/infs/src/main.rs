@@ -10,14 +10,26 @@
 //!
 //! - `new` - Create a new Inference project
 //! - `init` - Initialize an existing directory as an Inference project
+//! - `scaffold upgrade` - Apply template changes to an existing project
+//! - `clean` - Remove build artifacts
+//! - `fmt` - Format WebAssembly text files
 //! - `build` - Compile Inference source files
 //! - `run` - Build and execute WASM with wasmtime
 //! - `version` - Display version information
 //! - `install` - Install toolchain versions
 //! - `uninstall` - Remove toolchain versions
 //! - `list` - List installed toolchains
+//! - `changelog` - Show the release notes of a toolchain version
+//! - `manifest` - Show the artifact URL for a version and platform
 //! - `default` - Set default toolchain version
 //! - `doctor` - Check installation health
+//! - `repair` - Repair an installed toolchain
+//! - `which` - Show resolved toolchain binary paths
+//! - `toolchain run` - Run a binary from a specific toolchain version
+//! - `bug-report` - Collect environment details for bug reports
+//! - `env` - Print a summary of the infs environment
+//! - `info` - Inspect a WASM module or generated Rocq file
+//! - `docs` - Show the bundled documentation
 //! - `self update` - Update infs itself
 //!
 //! ## Usage Modes
@@ -32,6 +44,12 @@
 //! When run with `--headless` but no subcommand, `infs` displays help information
 //! instead of launching the TUI.
 //!
+//! ## Colors
+//!
+//! The global `--color=auto|always|never` flag controls colored output. In
+//! `auto` mode (the default) colors are used when the output stream is a
+//! terminal and `NO_COLOR` is not set. `--color=never` also disables the TUI.
+//!
 //! ## Examples
 //!
 //! Create a new project:
@@ -49,23 +67,39 @@
 //! infs install
 //! ```
 //!
+//! Build with a specific installed toolchain without changing the default:
+//! ```bash
+//! infs +0.1.0 build example.inf --codegen -o
+//! ```
+//!
 //! Display version:
 //! ```bash
 //! infs version
 //! ```
 
+mod backtrace;
 mod commands;
+mod config;
+mod confirm;
+mod diagnostics;
 mod errors;
+mod logging;
+mod output;
 mod project;
+#[cfg(test)]
+mod test_support;
 mod toolchain;
 mod tui;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use commands::{
-    build, default, doctor, init, install, list, new, run, self_cmd, uninstall, version, versions,
+    bug_report, build, cache, changelog, clean, default, docs, doctor, env, fmt, info, init,
+    install, list, manifest, new, repair, run, scaffold_cmd, self_cmd, toolchain_cmd, uninstall,
+    version, versions, which,
 };
 use errors::InfsError;
+use output::{ColorMode, Stream, Style};
 
 /// Inference unified CLI toolchain.
 ///
@@ -82,15 +116,19 @@ use errors::InfsError;
     after_help = "\
 COMPILER RESOLUTION:
     The infc compiler is located using the following priority order:
-    1. INFC_PATH environment variable (explicit override)
-    2. System PATH (via 'which infc')
-    3. Managed toolchain (~/.inference/toolchains/VERSION/bin/infc)
+    1. Toolchain selected with a leading +VERSION (e.g. 'infs +0.1.0 build ...')
+    2. INFC_PATH environment variable (explicit override)
+    3. System PATH (via 'which infc')
+    4. Managed toolchain (~/.inference/toolchains/VERSION/bin/infc)
 
 ENVIRONMENT VARIABLES:
     INFS_NO_TUI             Disable interactive TUI
+    NO_COLOR                Disable colors when --color=auto
     INFC_PATH               Explicit path to infc binary
     INFERENCE_HOME          Toolchain directory (default: ~/.inference)
-    INFS_DIST_SERVER        Distribution server URL (default: https://inference-lang.org)"
+    INFS_DIST_SERVER        Distribution server URL (default: https://inference-lang.org)
+    INFS_FORCE_LIBC         Override Linux C library detection (glibc or musl)
+    INFS_LOG                Log level: error, warn, info (default), debug, trace"
 )]
 pub struct Cli {
     /// Run in headless mode without TUI.
@@ -100,6 +138,49 @@ pub struct Cli {
     #[clap(long = "headless", global = true, action = clap::ArgAction::SetTrue)]
     pub headless: bool,
 
+    /// When to use colored output.
+    ///
+    /// `auto` uses colors when writing to a terminal and `NO_COLOR` is
+    /// unset. `never` also disables the interactive TUI.
+    #[clap(
+        long = "color",
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorMode::Auto
+    )]
+    pub color: ColorMode,
+
+    /// Answer yes to confirmation prompts.
+    ///
+    /// Lets destructive commands such as `uninstall` and `clean --all` run
+    /// without asking, e.g. in scripts.
+    #[clap(short = 'y', long = "yes", global = true, action = clap::ArgAction::SetTrue)]
+    pub yes: bool,
+
+    /// Seconds to wait for another infs process changing the toolchain.
+    ///
+    /// Installs, uninstalls, and default switches wait for each other. By
+    /// default they wait indefinitely; with this option they fail after
+    /// the given number of seconds instead.
+    #[clap(long = "lock-timeout", global = true, value_name = "SECONDS")]
+    pub lock_timeout: Option<u64>,
+
+    /// Fail right away when the release server rate-limits requests.
+    ///
+    /// By default, rate-limited requests are retried after the wait the
+    /// server asks for, if it is at most `INFS_RATE_LIMIT_WAIT_SECS`
+    /// (default 60).
+    #[clap(long = "no-wait", global = true, action = clap::ArgAction::SetTrue)]
+    pub no_wait: bool,
+
+    /// Show debug output; repeat for trace output (`-vv`).
+    ///
+    /// Goes before the subcommand, e.g. `infs -v install`, since `build -v`
+    /// and `version -v` have their own meaning. See also `INFS_LOG`.
+    #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
     /// The subcommand to execute.
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -121,6 +202,24 @@ pub enum Commands {
     /// directory without creating a new parent directory.
     Init(init::InitArgs),
 
+    /// Manage the files generated from the project template.
+    ///
+    /// `scaffold upgrade` applies template changes to files that were not
+    /// edited since `new` or `init` created them.
+    Scaffold(scaffold_cmd::ScaffoldArgs),
+
+    /// Remove build artifacts.
+    ///
+    /// Deletes the project's out/ directory, and with --all the
+    /// verification output directory from Inference.toml.
+    Clean(clean::CleanArgs),
+
+    /// Format WebAssembly text files.
+    ///
+    /// Runs wat-fmt over .wat files in the project or at the given path.
+    /// Use --check to verify formatting or --in-place to rewrite files.
+    Fmt(fmt::FmtArgs),
+
     /// Compile Inference source files.
     ///
     /// The build command runs one or more compilation phases over a single
@@ -163,6 +262,18 @@ pub enum Commands {
     /// with their stability status and platform availability.
     Versions(versions::VersionsArgs),
 
+    /// Show the release notes of a toolchain version.
+    ///
+    /// Prints the notes from the release manifest as plain text. Defaults
+    /// to the latest stable version.
+    Changelog(changelog::ChangelogArgs),
+
+    /// Show the artifact that would be installed.
+    ///
+    /// Resolves a version and platform against the release manifest and
+    /// prints the download URL, size, and checksum without downloading.
+    Manifest(manifest::ManifestArgs),
+
     /// Set the default toolchain version.
     ///
     /// Changes the default toolchain used for compilation.
@@ -172,7 +283,57 @@ pub enum Commands {
     ///
     /// Verifies that all required components are installed and configured
     /// correctly. Reports any issues with suggested remediation steps.
-    Doctor,
+    /// With `--fix`, repairs symlinks, executable permissions and PATH first.
+    Doctor(doctor::DoctorArgs),
+
+    /// Repair an installed toolchain.
+    ///
+    /// Recreates symlinks, re-applies executable permissions, and rewrites
+    /// missing metadata without re-downloading the toolchain.
+    Repair(repair::RepairArgs),
+
+    /// Show where toolchain binaries resolve to.
+    ///
+    /// Prints the default toolchain path and bin symlink for infc, inf-llc,
+    /// and rust-lld, and which infc the build and run commands would use.
+    Which(which::WhichArgs),
+
+    /// Show or clear cached data.
+    ///
+    /// `cache clear` removes the cached release manifest, and with --all
+    /// downloaded toolchain archives. `cache path` prints the cache directory.
+    Cache(cache::CacheArgs),
+
+    /// Work with a specific toolchain version.
+    ///
+    /// Runs binaries from an installed toolchain without changing the
+    /// default toolchain.
+    Toolchain(toolchain_cmd::ToolchainArgs),
+
+    /// Collect environment details for a bug report.
+    ///
+    /// Prints versions, platform, installed toolchains, external tools, and
+    /// the project manifest as Markdown. Secrets are never included.
+    BugReport(bug_report::BugReportArgs),
+
+    /// Print a summary of the infs environment.
+    ///
+    /// Shows the infs version, platform, infs home directory, toolchains,
+    /// the resolved infc, and whether coqc and wasmtime are available.
+    Env(env::EnvArgs),
+
+    /// Inspect a WASM module or generated Rocq file.
+    ///
+    /// For .wasm files, prints section sizes, imports, exports, memory and
+    /// table limits, and custom sections. For .v files, lists theorem and
+    /// lemma names and the file's SHA-256 hash.
+    Info(info::InfoArgs),
+
+    /// Show the documentation bundled with infs.
+    ///
+    /// Prints a documentation page, or opens it in a browser with --open,
+    /// without network access. Requires a build with `bundled-docs`.
+    Docs(docs::DocsArgs),
 
     /// Manage the infs binary itself.
     ///
@@ -193,21 +354,42 @@ async fn main() {
 ///
 /// For `ProcessExitCode` errors, returns the embedded exit code without
 /// printing an error message (the subprocess already printed its output).
-/// For all other errors, prints the error and returns exit code 1.
+/// For all other errors, prints the error and returns the exit code of the
+/// first [`InfsError`] in the chain, or 1 for untyped errors.
 fn handle_error(e: &anyhow::Error) -> i32 {
-    if let Some(InfsError::ProcessExitCode { code }) = e.downcast_ref::<InfsError>() {
+    let infs_error = InfsError::find(e);
+    if let Some(InfsError::ProcessExitCode { code }) = infs_error {
         return *code;
     }
-    eprintln!("Error: {e:?}");
-    1
+    eprintln!(
+        "{} {e:?}",
+        output::paint(Stream::Stderr, Style::Error, "Error:")
+    );
+    infs_error.map_or(errors::EXIT_FAILURE, InfsError::exit_code)
 }
 
 async fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let (toolchain_version, args) =
+        toolchain_cmd::take_version_prefix(std::env::args_os().collect());
+    if let Some(version) = &toolchain_version {
+        toolchain::resolver::set_toolchain_override(version);
+    }
+    let cli = Cli::parse_from(args);
+    output::set_color_mode(cli.color);
+    confirm::set_assume_yes(cli.yes);
+    toolchain::lock::set_timeout(cli.lock_timeout.map(std::time::Duration::from_secs));
+    toolchain::http::set_no_wait(cli.no_wait);
+    // The TUI owns the terminal, so log messages on stderr would corrupt it.
+    if cli.command.is_some() {
+        logging::init(cli.verbose);
+    }
 
     match cli.command {
         Some(Commands::New(args)) => new::execute(&args),
         Some(Commands::Init(args)) => init::execute(&args),
+        Some(Commands::Scaffold(args)) => scaffold_cmd::execute(&args),
+        Some(Commands::Clean(args)) => clean::execute(&args),
+        Some(Commands::Fmt(args)) => fmt::execute(&args),
         Some(Commands::Build(args)) => build::execute(&args),
         Some(Commands::Run(args)) => run::execute(&args),
         Some(Commands::Version(args)) => version::execute(&args),
@@ -215,11 +397,21 @@ async fn run() -> Result<()> {
         Some(Commands::Uninstall(args)) => uninstall::execute(&args).await,
         Some(Commands::List) => list::execute().await,
         Some(Commands::Versions(args)) => versions::execute(&args).await,
+        Some(Commands::Changelog(args)) => changelog::execute(&args).await,
+        Some(Commands::Manifest(args)) => manifest::execute(&args).await,
         Some(Commands::Default(args)) => default::execute(&args).await,
-        Some(Commands::Doctor) => doctor::execute().await,
+        Some(Commands::Doctor(args)) => doctor::execute(&args).await,
+        Some(Commands::Repair(args)) => repair::execute(&args),
+        Some(Commands::Which(args)) => which::execute(&args),
+        Some(Commands::Cache(args)) => cache::execute(&args),
+        Some(Commands::Toolchain(args)) => toolchain_cmd::execute(&args),
+        Some(Commands::BugReport(args)) => bug_report::execute(&args),
+        Some(Commands::Env(args)) => env::execute(&args),
+        Some(Commands::Info(args)) => info::execute(&args),
+        Some(Commands::Docs(args)) => docs::execute(&args),
         Some(Commands::SelfCmd(args)) => self_cmd::execute(&args).await,
         None => {
-            if cli.headless || !tui::should_use_tui() {
+            if cli.headless || !tui::should_use_tui(cli.color) {
                 println!("infs: Inference unified CLI toolchain");
                 println!();
                 println!("Run 'infs --help' for usage information.");
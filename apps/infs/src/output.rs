@@ -0,0 +1,218 @@
+//! Styled terminal output for the infs CLI.
+//!
+//! Commands use this module for colored labels such as `Warning:` and
+//! `Error:` so that the global `--color` flag is honoured in one place.
+//!
+//! ## Color Resolution
+//!
+//! | `--color` | `NO_COLOR` set | Stream is a TTY | Colors |
+//! |-----------|----------------|-----------------|--------|
+//! | `always`  | any            | any             | yes    |
+//! | `never`   | any            | any             | no     |
+//! | `auto`    | yes            | any             | no     |
+//! | `auto`    | no             | yes             | yes    |
+//! | `auto`    | no             | no              | no     |
+//!
+//! `NO_COLOR` counts as set when it has a non-empty value, following
+//! <https://no-color.org>. TTY detection is done per stream, so
+//! `infs build x.inf 2>log` keeps colors on stdout only.
+
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+/// Environment variable that disables colors in `auto` mode.
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+static COLOR_MODE: OnceLock<ColorMode> = OnceLock::new();
+
+/// When to use colors, as selected by the global `--color` flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Use colors when the output stream is a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    /// Always use colors.
+    Always,
+    /// Never use colors.
+    Never,
+}
+
+impl ColorMode {
+    /// Decides whether colors are enabled for a stream.
+    ///
+    /// An explicit `always` or `never` takes priority over `NO_COLOR`.
+    #[must_use]
+    pub fn colors_enabled(self, no_color: bool, is_tty: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => !no_color && is_tty,
+        }
+    }
+}
+
+/// Output stream a styled message is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn is_terminal(self) -> bool {
+        match self {
+            Self::Stdout => std::io::stdout().is_terminal(),
+            Self::Stderr => std::io::stderr().is_terminal(),
+        }
+    }
+}
+
+/// Semantic style of a message fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Success,
+    Warning,
+    Error,
+    /// Bold text, used for diagnostic messages.
+    Emphasis,
+    /// Line numbers and margins in source snippets.
+    Gutter,
+}
+
+impl Style {
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Self::Success => "1;32",
+            Self::Warning => "1;33",
+            Self::Error => "1;31",
+            Self::Emphasis => "1",
+            Self::Gutter => "1;34",
+        }
+    }
+}
+
+/// Sets the color mode for this process.
+///
+/// Called by `main` after parsing arguments. The mode can only be set once;
+/// until then `auto` is assumed.
+pub fn set_color_mode(mode: ColorMode) {
+    let _ = COLOR_MODE.set(mode);
+}
+
+/// Returns the color mode selected for this process.
+#[must_use]
+pub fn color_mode() -> ColorMode {
+    COLOR_MODE.get().copied().unwrap_or_default()
+}
+
+/// Returns whether `NO_COLOR` is set to a non-empty value.
+fn no_color_env() -> bool {
+    std::env::var_os(NO_COLOR_ENV).is_some_and(|value| !value.is_empty())
+}
+
+/// Returns whether colors are enabled for `stream`.
+#[must_use]
+pub fn colors_enabled(stream: Stream) -> bool {
+    color_mode().colors_enabled(no_color_env(), stream.is_terminal())
+}
+
+/// Wraps `text` in the ANSI escape codes for `style` when `enabled`.
+#[must_use]
+pub fn apply(style: Style, text: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b[{}m{text}\x1b[0m", style.ansi_code())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Styles `text` for `stream`, or returns it unchanged if colors are disabled.
+#[must_use]
+pub fn paint(stream: Stream, style: Style, text: &str) -> String {
+    apply(style, text, colors_enabled(stream))
+}
+
+/// Prints `Warning: <message>` to stderr.
+pub fn warn(message: impl Display) {
+    eprintln!(
+        "{} {message}",
+        paint(Stream::Stderr, Style::Warning, "Warning:")
+    );
+}
+
+/// Prints a success message to stdout.
+pub fn success(message: impl Display) {
+    println!(
+        "{}",
+        paint(Stream::Stdout, Style::Success, &message.to_string())
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn always_enables_colors_regardless_of_environment() {
+        for no_color in [false, true] {
+            for is_tty in [false, true] {
+                assert!(ColorMode::Always.colors_enabled(no_color, is_tty));
+            }
+        }
+    }
+
+    #[test]
+    fn never_disables_colors_regardless_of_environment() {
+        for no_color in [false, true] {
+            for is_tty in [false, true] {
+                assert!(!ColorMode::Never.colors_enabled(no_color, is_tty));
+            }
+        }
+    }
+
+    #[test]
+    fn auto_requires_tty_and_no_color_unset() {
+        assert!(ColorMode::Auto.colors_enabled(false, true));
+        assert!(!ColorMode::Auto.colors_enabled(false, false));
+        assert!(!ColorMode::Auto.colors_enabled(true, true));
+        assert!(!ColorMode::Auto.colors_enabled(true, false));
+    }
+
+    #[test]
+    fn default_mode_is_auto() {
+        assert_eq!(ColorMode::default(), ColorMode::Auto);
+    }
+
+    #[test]
+    fn color_mode_parses_flag_values() {
+        assert_eq!(
+            ColorMode::from_str("always", false).ok(),
+            Some(ColorMode::Always)
+        );
+        assert_eq!(
+            ColorMode::from_str("never", false).ok(),
+            Some(ColorMode::Never)
+        );
+        assert_eq!(
+            ColorMode::from_str("auto", false).ok(),
+            Some(ColorMode::Auto)
+        );
+        assert!(ColorMode::from_str("sometimes", false).is_err());
+    }
+
+    #[test]
+    fn apply_wraps_text_only_when_enabled() {
+        assert_eq!(apply(Style::Error, "Error:", false), "Error:");
+        assert_eq!(
+            apply(Style::Error, "Error:", true),
+            "\x1b[1;31mError:\x1b[0m"
+        );
+        assert_eq!(
+            apply(Style::Warning, "Warning:", true),
+            "\x1b[1;33mWarning:\x1b[0m"
+        );
+    }
+}
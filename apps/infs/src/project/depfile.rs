@@ -0,0 +1,263 @@
+//! Dependency files for `infs build --emit-deps`.
+//!
+//! A dependency file lists, in Makefile syntax, the files a build read, so
+//! Make, Ninja (`depfile`), or Bazel rules wrapping `infs build` know when
+//! to run it again:
+//!
+//! ```text
+//! out/main.wasm: Inference.toml src/main.inf
+//! ```
+//!
+//! Paths are relative to the project root, the directory of
+//! `Inference.toml` (or the current directory outside a project), use `/`
+//! as separator, and are escaped for Make.
+//!
+//! ## Inputs
+//!
+//! [`build_inputs`] lists the files a build reads: the source file and the
+//! project manifest, which selects the target, optimization level, and
+//! features. `infc` compiles a single source file; `use` directives do not
+//! load other files yet, and projects have neither path dependencies nor
+//! pre-build hooks. Once any of these exists, the files it reads must be
+//! added here.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::manifest::InferenceToml;
+use super::target::Target;
+
+/// Directory `infc` writes its outputs to, relative to the working directory.
+const OUT_DIR: &str = "out";
+
+/// Returns the files read by a build of `source`, as absolute paths.
+///
+/// # Errors
+///
+/// Returns an error if the current directory cannot be determined.
+pub fn build_inputs(source: &Path) -> Result<Vec<PathBuf>> {
+    let mut inputs: Vec<PathBuf> = InferenceToml::find(source).into_iter().collect();
+    inputs.push(std::path::absolute(source).context("Failed to resolve the source path")?);
+    Ok(inputs)
+}
+
+/// Returns the output files `infc` writes for `source` in `out/` under the
+/// current directory: the WASM binary with `wasm`, the Rocq file with `rocq`.
+///
+/// # Errors
+///
+/// Returns an error if the current directory cannot be determined.
+pub fn build_outputs(
+    source: &Path,
+    target: Target,
+    wasm: bool,
+    rocq: bool,
+) -> Result<Vec<PathBuf>> {
+    let out_dir = std::path::absolute(OUT_DIR).context("Failed to resolve the output directory")?;
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("module");
+    let mut outputs = Vec::new();
+    if wasm {
+        outputs.push(out_dir.join(target.wasm_file_name(stem)));
+    }
+    if rocq {
+        let rocq_stem = if target == Target::default() {
+            stem.to_string()
+        } else {
+            format!("{stem}.{target}")
+        };
+        outputs.push(out_dir.join(format!("{rocq_stem}.v")));
+    }
+    Ok(outputs)
+}
+
+/// Returns the project root of `source`: the directory of its manifest, or
+/// the current directory if it is not in a project.
+///
+/// # Errors
+///
+/// Returns an error if the current directory cannot be determined.
+pub fn project_root(source: &Path) -> Result<PathBuf> {
+    match InferenceToml::find(source).and_then(|manifest| manifest.parent().map(Path::to_path_buf))
+    {
+        Some(root) => Ok(root),
+        None => std::env::current_dir().context("Failed to determine the current directory"),
+    }
+}
+
+/// Renders a Makefile rule making `targets` depend on `inputs`, with paths
+/// relative to `root`.
+#[must_use]
+pub fn render(targets: &[PathBuf], inputs: &[PathBuf], root: &Path) -> String {
+    let escape_all = |paths: &[PathBuf]| {
+        paths
+            .iter()
+            .map(|path| escape(&relative_to(path, root)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    format!("{}: {}\n", escape_all(targets), escape_all(inputs))
+}
+
+/// Writes the dependency file for `source` to `depfile`.
+///
+/// # Errors
+///
+/// Returns an error if the paths cannot be resolved or the file cannot be
+/// written.
+pub fn write(depfile: &Path, source: &Path, outputs: &[PathBuf]) -> Result<()> {
+    let contents = render(outputs, &build_inputs(source)?, &project_root(source)?);
+    if let Some(parent) = depfile.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(depfile, contents)
+        .with_context(|| format!("Failed to write dependency file {}", depfile.display()))
+}
+
+/// Returns `path` relative to `base` with `/` separators, using `..` for
+/// paths outside `base`.
+///
+/// Both paths must be absolute. A path on another Windows drive than `base`
+/// is returned in full.
+#[must_use]
+pub fn relative_to(path: &Path, base: &Path) -> String {
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    if path.first() != base.first() {
+        return path
+            .iter()
+            .collect::<PathBuf>()
+            .to_string_lossy()
+            .replace('\\', "/");
+    }
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![String::from(".."); base.len() - common];
+    parts.extend(
+        path[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    if parts.is_empty() {
+        String::from(".")
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Escapes `path` for a Makefile rule: spaces, `#`, and `\` are escaped
+/// with a backslash and `$` is doubled, as Make and Ninja expect.
+#[must_use]
+pub fn escape(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' | '#' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '$' => out.push_str("$$"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = temp_test_dir(&format!("depfile_{name}"));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn inputs_of_multi_file_project_are_manifest_and_source() {
+        let root = temp_project("inputs");
+        std::fs::write(root.join("Inference.toml"), "").unwrap();
+        std::fs::write(root.join("src/main.inf"), "use util;\n").unwrap();
+        std::fs::write(
+            root.join("src/util.inf"),
+            "pub fn one() -> i32 { return 1; }\n",
+        )
+        .unwrap();
+        let source = root.join("src/main.inf");
+
+        // util.inf is not read: `use` does not load files yet.
+        assert_eq!(
+            build_inputs(&source).unwrap(),
+            [root.join("Inference.toml"), source.clone()]
+        );
+        assert_eq!(project_root(&source).unwrap(), root);
+
+        let outputs = [root.join("out/main.wasm"), root.join("out/main.v")];
+        assert_eq!(
+            render(&outputs, &build_inputs(&source).unwrap(), &root),
+            "out/main.wasm out/main.v: Inference.toml src/main.inf\n"
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn write_creates_parent_directory() {
+        let root = temp_project("write");
+        std::fs::write(root.join("Inference.toml"), "").unwrap();
+        let source = root.join("src/main.inf");
+        std::fs::write(&source, "").unwrap();
+        let depfile = root.join("build/deps/main.d");
+
+        write(&depfile, &source, &[root.join("out/main.wasm")]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&depfile).unwrap(),
+            "out/main.wasm: Inference.toml src/main.inf\n"
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn outputs_follow_infc_naming() {
+        let out = std::path::absolute(OUT_DIR).unwrap();
+        let source = Path::new("src/main.inf");
+        assert_eq!(
+            build_outputs(source, Target::Wasm32, true, true).unwrap(),
+            [out.join("main.wasm"), out.join("main.v")]
+        );
+        assert_eq!(
+            build_outputs(source, Target::Wasm64, true, false).unwrap(),
+            [out.join("main.wasm64.wasm")]
+        );
+        assert!(
+            build_outputs(source, Target::Wasm32, false, false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn relative_paths_walk_up_out_of_root() {
+        let root = Path::new("/work/app");
+        assert_eq!(
+            relative_to(Path::new("/work/app/src/main.inf"), root),
+            "src/main.inf"
+        );
+        assert_eq!(
+            relative_to(Path::new("/work/lib/src/lib.inf"), root),
+            "../lib/src/lib.inf"
+        );
+        assert_eq!(relative_to(root, root), ".");
+    }
+
+    #[test]
+    fn escape_handles_make_special_characters() {
+        assert_eq!(escape("src/my file.inf"), "src/my\\ file.inf");
+        assert_eq!(escape("a#b$c"), "a\\#b$$c");
+        assert_eq!(escape("plain/path.inf"), "plain/path.inf");
+    }
+}
@@ -0,0 +1,478 @@
+//! Feature selection.
+//!
+//! `[features]` in `Inference.toml` declares optional features and the
+//! features each one implies. The `default` entry lists the features enabled
+//! unless `--no-default-features` is given:
+//!
+//! ```toml
+//! [features]
+//! default = ["logging"]
+//! logging = []
+//! runtime-checks = ["logging"]
+//! ```
+//!
+//! `build` and `run` enable the default features plus those passed with
+//! `--features a,b`, then every feature those imply. Every declared feature
+//! is forwarded to `infc`, as `-D feature=NAME` if enabled and
+//! `-D feature=NAME=false` otherwise, which the program sees as the
+//! predefined constant `FEATURE_NAME`. Defining disabled features too keeps
+//! code that checks them compiling.
+//!
+//! Toolchains whose `infc` has no `--define` option can only build without
+//! features. Unlike the optimization level, features change what the program
+//! does, so selecting any with such a toolchain is an error rather than being
+//! silently dropped. Toolchains that predate disabled features only get the
+//! enabled ones.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use super::manifest::InferenceToml;
+use crate::errors::InfsError;
+
+/// Name of the `[features]` entry listing the default features.
+pub const DEFAULT_FEATURE: &str = "default";
+
+/// The `[features]` table: feature name to the features it implies.
+pub type FeatureTable = BTreeMap<String, Vec<String>>;
+
+/// Features of a build: every declared feature and the enabled ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Features {
+    /// Every feature declared in `[features]`, except `default`.
+    pub declared: BTreeSet<String>,
+    /// The enabled features, a subset of `declared`.
+    pub enabled: BTreeSet<String>,
+}
+
+/// Returns the name of the constant `infc` defines for feature `name`.
+#[must_use]
+pub fn constant_name(name: &str) -> String {
+    format!("FEATURE_{}", name.to_uppercase().replace('-', "_"))
+}
+
+/// Returns true if `name` can name a feature: letters, digits, `_` and `-`,
+/// starting with a letter or `_`.
+fn is_valid_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Checks that a `[features]` table is usable.
+///
+/// # Errors
+///
+/// Returns an error if a feature name is invalid, two names map to the same
+/// constant (e.g. `a-b` and `a_b`), a feature or `default` refers to an
+/// undeclared feature, or features imply each other in a cycle.
+pub fn validate_features(table: &FeatureTable) -> Result<()> {
+    let mut constants: BTreeMap<String, &str> = BTreeMap::new();
+    for (name, implied) in table {
+        if name != DEFAULT_FEATURE && !is_valid_name(name) {
+            bail!(
+                "invalid feature name '{name}'; use letters, digits, '_' and '-', starting with a letter or '_'"
+            );
+        }
+        if name != DEFAULT_FEATURE
+            && let Some(other) = constants.insert(constant_name(name), name)
+        {
+            bail!(
+                "features '{other}' and '{name}' both define the constant {}",
+                constant_name(name)
+            );
+        }
+        for dependency in implied {
+            if dependency == DEFAULT_FEATURE || !table.contains_key(dependency) {
+                bail!("feature '{name}' refers to undeclared feature '{dependency}'");
+            }
+        }
+    }
+    let mut finished = BTreeSet::new();
+    for name in table.keys() {
+        find_cycle(table, name, &mut Vec::new(), &mut finished)?;
+    }
+    Ok(())
+}
+
+/// Walks the features implied by `name` depth-first, failing on a feature
+/// that is already on the current `path`.
+fn find_cycle<'a>(
+    table: &'a FeatureTable,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    finished: &mut BTreeSet<&'a str>,
+) -> Result<()> {
+    if finished.contains(name) {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|entry| *entry == name) {
+        let cycle = path[start..]
+            .iter()
+            .chain(std::iter::once(&name))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        bail!("features imply each other in a cycle: {cycle}");
+    }
+    path.push(name);
+    for dependency in table.get(name).into_iter().flatten() {
+        find_cycle(table, dependency, path, finished)?;
+    }
+    path.pop();
+    finished.insert(name);
+    Ok(())
+}
+
+/// Picks the features of a build: the default features unless
+/// `no_default_features` is set, the `requested` ones, and everything they
+/// imply.
+///
+/// `table` must have passed [`validate_features`].
+///
+/// # Errors
+///
+/// Returns [`InfsError::InvalidArguments`] if a requested feature is not
+/// declared.
+pub fn select_features(
+    requested: &[String],
+    no_default_features: bool,
+    table: &FeatureTable,
+) -> Result<BTreeSet<String>> {
+    let mut pending: Vec<&str> = Vec::new();
+    for name in requested {
+        if name == DEFAULT_FEATURE || !table.contains_key(name) {
+            let declared = table
+                .keys()
+                .filter(|name| *name != DEFAULT_FEATURE)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            let declared = if declared.is_empty() {
+                String::from("the project declares no features")
+            } else {
+                format!("declared features: {}", declared.join(", "))
+            };
+            return Err(InfsError::invalid_arguments(format!(
+                "unknown feature `{name}`; {declared}"
+            ))
+            .into());
+        }
+        pending.push(name);
+    }
+    if !no_default_features {
+        pending.extend(
+            table
+                .get(DEFAULT_FEATURE)
+                .into_iter()
+                .flatten()
+                .map(String::as_str),
+        );
+    }
+
+    let mut selected = BTreeSet::new();
+    while let Some(name) = pending.pop() {
+        if selected.insert(name.to_string()) {
+            pending.extend(table.get(name).into_iter().flatten().map(String::as_str));
+        }
+    }
+    Ok(selected)
+}
+
+/// Resolves the features for compiling `source` from the flags and the
+/// `[features]` table of its project.
+///
+/// Sources outside a project have no features, so only an empty `--features`
+/// list is accepted for them.
+///
+/// # Errors
+///
+/// Returns an error if the project manifest exists but cannot be parsed, or
+/// a requested feature is not declared.
+pub fn resolve_features(
+    requested: &[String],
+    no_default_features: bool,
+    source: &Path,
+) -> Result<Features> {
+    let manifest = match InferenceToml::find(source) {
+        Some(path) => Some(InferenceToml::from_file(&path)?),
+        None => None,
+    };
+    let table = manifest
+        .map(|manifest| manifest.features)
+        .unwrap_or_default();
+    let enabled = select_features(requested, no_default_features, &table)?;
+    let declared = table
+        .into_keys()
+        .filter(|name| name != DEFAULT_FEATURE)
+        .collect();
+    Ok(Features { declared, enabled })
+}
+
+/// Returns whether an `infc` accepts `--define`, judging by its help text.
+#[must_use]
+pub fn supports_defines(infc_help: &str) -> bool {
+    infc_help.contains("--define")
+}
+
+/// Returns whether an `infc` accepts disabled features
+/// (`-D feature=NAME=false`), judging by its help text.
+#[must_use]
+pub fn supports_disabled_features(infc_help: &str) -> bool {
+    infc_help.contains("feature=NAME=false")
+}
+
+/// Returns the `infc` arguments defining `features`: every declared feature
+/// with its state if `with_disabled` is set, otherwise the enabled ones.
+#[must_use]
+pub fn feature_args(features: &Features, with_disabled: bool) -> Vec<String> {
+    features
+        .declared
+        .iter()
+        .filter_map(|feature| {
+            if features.enabled.contains(feature) {
+                Some(format!("feature={feature}"))
+            } else {
+                with_disabled.then(|| format!("feature={feature}=false"))
+            }
+        })
+        .flat_map(|define| [String::from("-D"), define])
+        .collect()
+}
+
+/// Adds a `-D` define for each of `features` to `cmd`.
+///
+/// Nothing is added, and `infc` is not asked for its options, when no
+/// features are declared. With a toolchain that predates disabled features,
+/// only the enabled ones are added.
+///
+/// # Errors
+///
+/// Returns an error if `infc --help` cannot be run, or
+/// [`InfsError::InvalidArguments`] if features are enabled and the `infc` at
+/// `infc_path` does not support them.
+pub fn add_feature_defines(cmd: &mut Command, infc_path: &Path, features: &Features) -> Result<()> {
+    if features.declared.is_empty() {
+        return Ok(());
+    }
+    let output = Command::new(infc_path)
+        .arg("--help")
+        .output()
+        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+    let help = String::from_utf8_lossy(&output.stdout);
+    if !supports_defines(&help) {
+        if features.enabled.is_empty() {
+            return Ok(());
+        }
+        let names = features
+            .enabled
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(InfsError::invalid_arguments(format!(
+            "features ({names}) are not supported by the installed toolchain ({}); \
+             build with --no-default-features or install a newer toolchain",
+            infc_path.display()
+        ))
+        .into());
+    }
+    cmd.args(feature_args(features, supports_disabled_features(&help)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(&str, &[&str])]) -> FeatureTable {
+        entries
+            .iter()
+            .map(|(name, implied)| {
+                (
+                    (*name).to_string(),
+                    implied.iter().map(|name| (*name).to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn names(features: &BTreeSet<String>) -> Vec<&str> {
+        features.iter().map(String::as_str).collect()
+    }
+
+    fn sample() -> FeatureTable {
+        table(&[
+            ("default", &["logging"]),
+            ("logging", &[]),
+            ("runtime-checks", &["logging", "bounds"]),
+            ("bounds", &[]),
+            ("tracing", &[]),
+        ])
+    }
+
+    #[test]
+    fn defaults_are_enabled_unless_disabled() {
+        let table = sample();
+        assert_eq!(
+            names(&select_features(&[], false, &table).unwrap()),
+            ["logging"]
+        );
+        assert!(select_features(&[], true, &table).unwrap().is_empty());
+        assert!(
+            select_features(&[], false, &FeatureTable::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn implied_features_are_enabled_transitively() {
+        let table = table(&[("a", &["b"]), ("b", &["c"]), ("c", &[]), ("d", &[])]);
+        assert_eq!(
+            names(&select_features(&["a".to_string()], true, &table).unwrap()),
+            ["a", "b", "c"]
+        );
+        assert_eq!(
+            names(&select_features(&["runtime-checks".to_string()], true, &sample()).unwrap()),
+            ["bounds", "logging", "runtime-checks"]
+        );
+    }
+
+    #[test]
+    fn unknown_requested_feature_is_rejected() {
+        let err = select_features(&["fast".to_string()], false, &sample()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("unknown feature `fast`"), "{message}");
+        assert!(
+            message.contains("declared features: bounds, logging, runtime-checks, tracing"),
+            "{message}"
+        );
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(crate::errors::EXIT_INVALID_ARGUMENTS)
+        );
+
+        let err = select_features(&["default".to_string()], false, &sample()).unwrap_err();
+        assert!(err.to_string().contains("unknown feature `default`"));
+
+        let err = select_features(&["x".to_string()], false, &FeatureTable::new()).unwrap_err();
+        assert!(err.to_string().contains("the project declares no features"));
+    }
+
+    #[test]
+    fn validate_accepts_well_formed_table() {
+        assert!(validate_features(&sample()).is_ok());
+        assert!(validate_features(&FeatureTable::new()).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_undeclared_features() {
+        let message = validate_features(&table(&[("a", &["b"])]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(message, "feature 'a' refers to undeclared feature 'b'");
+
+        let message = validate_features(&table(&[("default", &["a"])]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "feature 'default' refers to undeclared feature 'a'"
+        );
+
+        assert!(validate_features(&table(&[("default", &[]), ("a", &["default"])])).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_cycles() {
+        let message = validate_features(&table(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "features imply each other in a cycle: a -> b -> c -> a"
+        );
+
+        let message = validate_features(&table(&[("a", &["a"])]))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(message, "features imply each other in a cycle: a -> a");
+
+        assert!(
+            validate_features(&table(&[("a", &["b", "c"]), ("b", &["c"]), ("c", &[])])).is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_names() {
+        for name in ["1st", "has space", "dot.ted", ""] {
+            let message = validate_features(&table(&[(name, &[])]))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                message.contains("invalid feature name"),
+                "{name}: {message}"
+            );
+        }
+    }
+
+    #[test]
+    fn features_become_defines() {
+        let features = Features {
+            declared: sample()
+                .into_keys()
+                .filter(|n| n != DEFAULT_FEATURE)
+                .collect(),
+            enabled: select_features(&["runtime-checks".to_string()], true, &sample()).unwrap(),
+        };
+        assert_eq!(
+            feature_args(&features, true),
+            [
+                "-D",
+                "feature=bounds",
+                "-D",
+                "feature=logging",
+                "-D",
+                "feature=runtime-checks",
+                "-D",
+                "feature=tracing=false"
+            ]
+        );
+        assert_eq!(
+            feature_args(&features, false),
+            [
+                "-D",
+                "feature=bounds",
+                "-D",
+                "feature=logging",
+                "-D",
+                "feature=runtime-checks"
+            ]
+        );
+        assert!(supports_defines(
+            "  -D, --define <KEY=VALUE>  Define a compile-time value"
+        ));
+        assert!(!supports_defines(
+            "      --target <TARGET>  WebAssembly target"
+        ));
+        assert!(supports_disabled_features(
+            "`feature=NAME=false` defines it as `false`"
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_names_with_the_same_constant() {
+        for (a, b) in [("a-b", "a_b"), ("Foo", "foo")] {
+            let message = validate_features(&table(&[(a, &[]), (b, &[])]))
+                .unwrap_err()
+                .to_string();
+            assert!(message.contains("both define the constant"), "{message}");
+        }
+    }
+}
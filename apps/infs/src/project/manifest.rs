@@ -11,6 +11,7 @@
 //! [package]
 //! name = "myproject"
 //! version = "0.1.0"
+//! edition = "2024"
 //! infc_version = "0.1.0"
 //!
 //! [dependencies]
@@ -20,8 +21,18 @@
 //! target = "wasm32"
 //! optimize = "release"
 //!
+//! [features]
+//! default = ["logging"]
+//! logging = []
+//! runtime-checks = ["logging"]
+//!
 //! [verification]
 //! output-dir = "proofs/"
+//!
+//! [template]
+//! name = "default"
+//! git = true
+//! ci = "github"
 //! ```
 //!
 //! ## Reserved Names
@@ -32,9 +43,23 @@
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use super::features::{FeatureTable, validate_features};
+use super::optimize::Optimize;
+use super::scaffold::CiProvider;
+use super::target::Target;
+
+/// File name of the project manifest.
+pub const MANIFEST_FILE: &str = "Inference.toml";
+
+/// Language editions a project can declare in `[package] edition`.
+pub const EDITIONS: &[&str] = &["2024"];
+
+/// Edition of new projects and of manifests that do not declare one.
+pub const DEFAULT_EDITION: &str = "2024";
+
 /// Reserved words that cannot be used as project names.
 ///
 /// Includes Inference language keywords and problematic directory names.
@@ -95,9 +120,20 @@ pub struct InferenceToml {
     #[serde(default, skip_serializing_if = "BuildConfig::is_default")]
     pub build: BuildConfig,
 
+    /// Optional features and the features each one implies.
+    ///
+    /// The `default` entry lists the features enabled by default. See
+    /// [`super::features`].
+    #[serde(default, skip_serializing_if = "FeatureTable::is_empty")]
+    pub features: FeatureTable,
+
     /// Verification configuration for Rocq output.
     #[serde(default, skip_serializing_if = "VerificationConfig::is_default")]
     pub verification: VerificationConfig,
+
+    /// Project template the project was scaffolded from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateConfig>,
 }
 
 /// Package metadata in the manifest.
@@ -109,6 +145,10 @@ pub struct Package {
     /// The project version (semver format).
     pub version: String,
 
+    /// The language edition, one of [`EDITIONS`].
+    #[serde(default = "default_edition")]
+    pub edition: String,
+
     /// The infc compiler version used to create this project.
     #[serde(default = "default_infc_version")]
     pub infc_version: String,
@@ -147,11 +187,11 @@ impl Dependencies {
 /// Build configuration section.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct BuildConfig {
-    /// Target platform for compilation.
+    /// Target for compilation (`wasm32` or `wasm64`).
     #[serde(default = "default_target")]
-    pub target: String,
+    pub target: Target,
 
-    /// Optimization level.
+    /// Optimization level (`debug` or `release`).
     #[serde(default = "default_optimize")]
     pub optimize: String,
 }
@@ -197,6 +237,23 @@ impl VerificationConfig {
     }
 }
 
+/// Project template recorded by `infs new` and `infs init`.
+///
+/// `infs scaffold upgrade` re-renders the template from these choices.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TemplateConfig {
+    /// Template name.
+    pub name: String,
+
+    /// Whether git files (`.gitignore`, `.gitkeep`) are part of the project.
+    #[serde(default)]
+    pub git: bool,
+
+    /// CI provider whose configuration files are part of the project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<CiProvider>,
+}
+
 /// Gets the infc version to use for new projects.
 ///
 /// Tries to detect the installed infc version first by running `infc --version`.
@@ -255,8 +312,12 @@ fn default_infc_version() -> String {
     detect_infc_version()
 }
 
-fn default_target() -> String {
-    String::from("wasm32")
+fn default_edition() -> String {
+    String::from(DEFAULT_EDITION)
+}
+
+fn default_target() -> Target {
+    Target::default()
 }
 
 fn default_optimize() -> String {
@@ -277,6 +338,7 @@ impl InferenceToml {
             package: Package {
                 name: name.into(),
                 version: String::from("0.1.0"),
+                edition: default_edition(),
                 infc_version: default_infc_version(),
                 description: None,
                 authors: None,
@@ -284,8 +346,73 @@ impl InferenceToml {
             },
             dependencies: Dependencies::default(),
             build: BuildConfig::default(),
+            features: FeatureTable::new(),
             verification: VerificationConfig::default(),
+            template: None,
+        }
+    }
+
+    /// Parses and validates manifest content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not a valid manifest or
+    /// [`InferenceToml::validate`] fails.
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(content).context("Failed to parse Inference.toml")?;
+        manifest.validate().context("Invalid Inference.toml")?;
+        Ok(manifest)
+    }
+
+    /// Reads and parses a manifest file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid manifest,
+    /// for example because `[build] target` names an unsupported target or
+    /// [`InferenceToml::validate`] fails.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Failed to load {}", path.display()))
+    }
+
+    /// Checks values that parse but are not meaningful.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `[package] edition` is not one of [`EDITIONS`],
+    /// `[build] optimize` is not a known level, or `[features]` fails
+    /// [`validate_features`].
+    pub fn validate(&self) -> Result<()> {
+        validate_edition(&self.package.edition)?;
+        if Optimize::parse(&self.build.optimize).is_none() {
+            let levels = Optimize::ALL
+                .iter()
+                .map(|level| level.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!(
+                "unknown [build] optimize value '{}'; expected one of: {levels}",
+                self.build.optimize
+            );
         }
+        validate_features(&self.features).context("invalid [features]")?;
+        Ok(())
+    }
+
+    /// Finds the manifest of the project containing `source`.
+    ///
+    /// Searches the directory of `source` and its ancestors for
+    /// `Inference.toml` and returns the first one found.
+    #[must_use]
+    pub fn find(source: &Path) -> Option<PathBuf> {
+        let source = std::path::absolute(source).ok()?;
+        source
+            .parent()?
+            .ancestors()
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .find(|path| path.is_file())
     }
 
     /// Serializes the manifest to TOML format.
@@ -309,6 +436,113 @@ impl InferenceToml {
     }
 }
 
+/// Checks that `edition` is one of [`EDITIONS`].
+///
+/// # Errors
+///
+/// Returns an error naming the known editions if `edition` is not one of them.
+pub fn validate_edition(edition: &str) -> Result<()> {
+    if !EDITIONS.contains(&edition) {
+        bail!(
+            "unknown [package] edition value '{edition}'; expected one of: {}",
+            EDITIONS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Checks that `license` looks like an SPDX license expression, such as
+/// `MIT`, `Apache-2.0 OR MIT`, or `(MIT AND BSD-3-Clause)`.
+///
+/// Only the shape is checked: identifiers made of letters, digits, `.`,
+/// and `-` (optionally ending in `+`), joined by `AND`, `OR`, or `WITH`,
+/// with balanced parentheses. Identifiers are not looked up in the SPDX
+/// license list.
+///
+/// # Errors
+///
+/// Returns an error if `license` is not such an expression.
+pub fn validate_license(license: &str) -> Result<()> {
+    let spaced = license.replace('(', " ( ").replace(')', " ) ");
+    let mut expect_license = true;
+    let mut depth = 0usize;
+    let mut valid = true;
+    for token in spaced.split_whitespace() {
+        valid = match token {
+            "(" if expect_license => {
+                depth += 1;
+                true
+            }
+            ")" if !expect_license && depth > 0 => {
+                depth -= 1;
+                true
+            }
+            "AND" | "OR" | "WITH" if !expect_license => {
+                expect_license = true;
+                true
+            }
+            "(" | ")" | "AND" | "OR" | "WITH" => false,
+            id if expect_license => {
+                expect_license = false;
+                let id = id.strip_suffix('+').unwrap_or(id);
+                !id.is_empty()
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+            }
+            _ => false,
+        };
+        if !valid {
+            break;
+        }
+    }
+    if !valid || expect_license || depth != 0 {
+        bail!(
+            "invalid license '{license}'; expected an SPDX license expression \
+             such as 'MIT' or 'Apache-2.0 OR MIT'"
+        );
+    }
+    Ok(())
+}
+
+/// Optional `[package]` metadata given when a project is created.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageMetadata {
+    /// Project description.
+    pub description: Option<String>,
+    /// Project authors; empty leaves `authors` unset.
+    pub authors: Vec<String>,
+    /// License, as an SPDX license expression.
+    pub license: Option<String>,
+}
+
+impl PackageMetadata {
+    /// Checks the license with [`validate_license`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the license is not an SPDX license expression.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(license) = &self.license {
+            validate_license(license)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the fields of `package` that this metadata provides.
+    pub fn apply_to(&self, package: &mut Package) {
+        if self.description.is_some() {
+            package.description.clone_from(&self.description);
+        }
+        if !self.authors.is_empty() {
+            package.authors = Some(self.authors.clone());
+        }
+        if self.license.is_some() {
+            package.license.clone_from(&self.license);
+        }
+    }
+}
+
 /// Validates a project name for use in Inference projects.
 ///
 /// # Rules
@@ -353,6 +587,7 @@ pub fn validate_project_name(name: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
     use semver::Version;
 
     #[test]
@@ -360,6 +595,7 @@ mod tests {
         let manifest = InferenceToml::new("myproject");
         assert_eq!(manifest.package.name, "myproject");
         assert_eq!(manifest.package.version, "0.1.0");
+        assert_eq!(manifest.package.edition, DEFAULT_EDITION);
         // infc_version should be a valid semver (either detected or fallback)
         assert!(
             Version::parse(&manifest.package.infc_version).is_ok(),
@@ -368,6 +604,7 @@ mod tests {
         assert!(manifest.package.description.is_none());
         assert!(manifest.dependencies.is_empty());
         assert!(manifest.build.is_default());
+        assert!(manifest.features.is_empty());
         assert!(manifest.verification.is_default());
     }
 
@@ -397,12 +634,159 @@ mod tests {
         assert!(config.is_default());
 
         let config = BuildConfig {
-            target: String::from("wasm64"),
+            target: Target::Wasm64,
             optimize: String::from("debug"),
         };
         assert!(!config.is_default());
     }
 
+    #[test]
+    fn test_build_target_rejects_unknown_target() {
+        let manifest = "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n\n[build]\ntarget = \"wasm128\"\n";
+        let err = toml::from_str::<InferenceToml>(manifest).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("wasm128"), "{message}");
+        assert!(message.contains("wasm32"), "{message}");
+        assert!(message.contains("wasm64"), "{message}");
+    }
+
+    #[test]
+    fn test_validate_accepts_known_optimize_levels() {
+        let mut manifest = InferenceToml::new("demo");
+        for level in ["debug", "release"] {
+            manifest.build.optimize = level.to_string();
+            assert!(manifest.validate().is_ok(), "{level}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_optimize_level() {
+        let mut manifest = InferenceToml::new("demo");
+        manifest.build.optimize = String::from("fast");
+        let message = manifest.validate().unwrap_err().to_string();
+        assert!(message.contains("'fast'"), "{message}");
+        assert!(message.contains("debug, release"), "{message}");
+    }
+
+    #[test]
+    fn test_edition_defaults_when_omitted() {
+        let manifest =
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n";
+        let parsed: InferenceToml = toml::from_str(manifest).unwrap();
+        assert_eq!(parsed.package.edition, DEFAULT_EDITION);
+        assert!(parsed.validate().is_ok());
+        assert!(parsed.to_toml().unwrap().contains("edition = \"2024\""));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_edition() {
+        let mut manifest = InferenceToml::new("demo");
+        manifest.package.edition = String::from("2015");
+        let message = manifest.validate().unwrap_err().to_string();
+        assert!(message.contains("edition value '2015'"), "{message}");
+        assert!(message.contains("2024"), "{message}");
+        assert!(validate_edition(DEFAULT_EDITION).is_ok());
+    }
+
+    #[test]
+    fn test_validate_license_accepts_spdx_expressions() {
+        for license in [
+            "MIT",
+            "Apache-2.0 OR MIT",
+            "GPL-2.0+",
+            "GPL-3.0-or-later WITH Classpath-exception-2.0",
+            "(MIT OR Apache-2.0) AND BSD-3-Clause",
+            "LicenseRef-Proprietary",
+        ] {
+            assert!(validate_license(license).is_ok(), "{license}");
+        }
+        for license in [
+            "",
+            "MIT OR",
+            "MIT Apache-2.0",
+            "(MIT",
+            "MIT)",
+            "AND MIT",
+            "MIT/Apache-2.0",
+            "All rights reserved",
+        ] {
+            let message = validate_license(license).unwrap_err().to_string();
+            assert!(message.contains("SPDX license expression"), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_package_metadata_sets_only_given_fields() {
+        let mut package = InferenceToml::new("demo").package;
+        package.description = Some(String::from("kept"));
+        PackageMetadata {
+            description: None,
+            authors: vec![String::from("Ada"), String::from("Grace")],
+            license: Some(String::from("MIT")),
+        }
+        .apply_to(&mut package);
+        assert_eq!(package.description.as_deref(), Some("kept"));
+        assert_eq!(
+            package.authors,
+            Some(vec![String::from("Ada"), String::from("Grace")])
+        );
+        assert_eq!(package.license.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_from_file_validates_manifest() {
+        let root = temp_test_dir("manifest_validate");
+        let path = root.join(MANIFEST_FILE);
+        std::fs::write(
+            &path,
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n\n[build]\noptimize = \"O2\"\n",
+        )
+        .unwrap();
+
+        let err = InferenceToml::from_file(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown [build] optimize value 'O2'"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_features_round_trip_and_validate() {
+        let manifest = "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n\n[features]\ndefault = [\"logging\"]\nlogging = []\nruntime-checks = [\"logging\"]\n";
+        let parsed: InferenceToml = toml::from_str(manifest).unwrap();
+        assert!(parsed.validate().is_ok());
+        assert_eq!(parsed.features["runtime-checks"], ["logging"]);
+        let reparsed: InferenceToml = toml::from_str(&parsed.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed, parsed);
+
+        let mut cyclic = parsed;
+        cyclic.features.insert(
+            String::from("logging"),
+            vec![String::from("runtime-checks")],
+        );
+        let message = format!("{:#}", cyclic.validate().unwrap_err());
+        assert!(message.starts_with("invalid [features]: "), "{message}");
+        assert!(message.contains("cycle"), "{message}");
+    }
+
+    #[test]
+    fn test_find_searches_parent_directories() {
+        let root = temp_test_dir("manifest_find");
+        let src = root.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        InferenceToml::new("demo")
+            .write_to_file(&root.join(MANIFEST_FILE))
+            .unwrap();
+
+        assert_eq!(
+            InferenceToml::find(&src.join("main.inf")),
+            Some(root.join(MANIFEST_FILE))
+        );
+        let loaded = InferenceToml::from_file(&root.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(loaded.build.target, Target::Wasm32);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
     #[test]
     fn test_verification_config_is_default() {
         let config = VerificationConfig::default();
@@ -5,21 +5,35 @@
 //!
 //! ## Modules
 //!
+//! - [`depfile`] - Dependency files for build systems
+//! - [`features`] - Feature selection
 //! - [`manifest`] - Inference.toml parsing and validation
+//! - [`optimize`] - Optimization level selection
 //! - [`scaffold`] - Project creation and initialization
+//! - [`target`] - Compilation target selection
+//! - [`template`] - Template variables and user templates
+//! - [`upgrade`] - Template upgrades for existing projects
 //!
 //! ## Key Types
 //!
 //! - [`InferenceToml`] - The manifest file structure
 //! - [`ProjectConfig`] - Loaded and validated project configuration
 
+pub mod depfile;
+pub mod features;
 pub mod manifest;
+pub mod optimize;
 pub mod scaffold;
+pub mod target;
+pub mod template;
+pub mod upgrade;
 
 #[allow(unused_imports)]
 pub use manifest::validate_project_name;
 #[allow(unused_imports)]
 pub use manifest::{Dependencies, Package};
 #[allow(unused_imports)]
+pub use scaffold::create_project;
+#[allow(unused_imports)]
 pub use scaffold::create_project_default;
-pub use scaffold::{create_project, init_project};
+pub use scaffold::{CiProvider, Vcs, init_project};
@@ -0,0 +1,173 @@
+//! Optimization level selection.
+//!
+//! The level comes from the `--release` or `--debug` flag of `build` and
+//! `run`, falling back to `[build] optimize` in the project's
+//! `Inference.toml`, and finally to `debug`. It is forwarded to `infc` as
+//! `--opt-level 0` (debug) or `--opt-level 3` (release).
+//!
+//! Toolchains whose `infc` has no `--opt-level` option always optimize fully.
+//! For them the flag is left out rather than failing the build, since the
+//! level does not change program behavior.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::manifest::InferenceToml;
+
+/// Optimization level of a build.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Optimize {
+    /// No optimizations, for fast builds and readable output.
+    #[default]
+    Debug,
+    /// Full optimizations.
+    Release,
+}
+
+impl Optimize {
+    /// All levels, in the order they are listed in messages.
+    pub const ALL: [Optimize; 2] = [Optimize::Debug, Optimize::Release];
+
+    /// Parses a `[build] optimize` value.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.as_str() == value)
+    }
+
+    /// Returns the level name used in manifests.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Release => "release",
+        }
+    }
+
+    /// Returns the value passed to `infc --opt-level`.
+    #[must_use]
+    pub fn infc_opt_level(self) -> &'static str {
+        match self {
+            Self::Debug => "0",
+            Self::Release => "3",
+        }
+    }
+
+    /// Returns the level selected by the `--release` and `--debug` flags,
+    /// if either is set.
+    #[must_use]
+    pub fn from_flags(release: bool, debug: bool) -> Option<Self> {
+        if release {
+            Some(Self::Release)
+        } else if debug {
+            Some(Self::Debug)
+        } else {
+            None
+        }
+    }
+}
+
+/// Picks the optimization level for a build: the flag wins over the
+/// manifest, which wins over the default.
+#[must_use]
+pub fn select_optimize(flag: Option<Optimize>, manifest: Option<&InferenceToml>) -> Optimize {
+    flag.or_else(|| manifest.and_then(|manifest| Optimize::parse(&manifest.build.optimize)))
+        .unwrap_or_default()
+}
+
+/// Resolves the optimization level for compiling `source`.
+///
+/// The manifest is only read when neither flag was given.
+///
+/// # Errors
+///
+/// Returns an error if the project manifest exists but cannot be parsed,
+/// including when `[build] optimize` is not a known level.
+pub fn resolve_optimize(flag: Option<Optimize>, source: &Path) -> Result<Optimize> {
+    if flag.is_some() {
+        return Ok(select_optimize(flag, None));
+    }
+    let manifest = match InferenceToml::find(source) {
+        Some(path) => Some(InferenceToml::from_file(&path)?),
+        None => None,
+    };
+    Ok(select_optimize(None, manifest.as_ref()))
+}
+
+/// Returns whether an `infc` accepts `--opt-level`, judging by its help text.
+#[must_use]
+pub fn supports_opt_level(infc_help: &str) -> bool {
+    infc_help.contains("--opt-level")
+}
+
+/// Adds `--opt-level` for `optimize` to `cmd` if the `infc` at `infc_path`
+/// accepts it.
+///
+/// # Errors
+///
+/// Returns an error if `infc --help` cannot be run.
+pub fn add_opt_level(cmd: &mut Command, infc_path: &Path, optimize: Optimize) -> Result<()> {
+    let output = Command::new(infc_path)
+        .arg("--help")
+        .output()
+        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+    if supports_opt_level(&String::from_utf8_lossy(&output.stdout)) {
+        cmd.arg("--opt-level").arg(optimize.infc_opt_level());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_with_optimize(optimize: &str) -> InferenceToml {
+        let mut manifest = InferenceToml::new("demo");
+        manifest.build.optimize = optimize.to_string();
+        manifest
+    }
+
+    #[test]
+    fn parse_accepts_known_levels_only() {
+        assert_eq!(Optimize::parse("debug"), Some(Optimize::Debug));
+        assert_eq!(Optimize::parse("release"), Some(Optimize::Release));
+        assert_eq!(Optimize::parse("Release"), None);
+        assert_eq!(Optimize::parse("fast"), None);
+    }
+
+    #[test]
+    fn flag_takes_precedence_over_manifest() {
+        let manifest = manifest_with_optimize("release");
+        assert_eq!(
+            select_optimize(Some(Optimize::Debug), Some(&manifest)),
+            Optimize::Debug
+        );
+        assert_eq!(select_optimize(None, Some(&manifest)), Optimize::Release);
+        assert_eq!(select_optimize(None, None), Optimize::Debug);
+    }
+
+    #[test]
+    fn release_flag_wins_over_debug_flag() {
+        assert_eq!(Optimize::from_flags(true, false), Some(Optimize::Release));
+        assert_eq!(Optimize::from_flags(false, true), Some(Optimize::Debug));
+        assert_eq!(Optimize::from_flags(true, true), Some(Optimize::Release));
+        assert_eq!(Optimize::from_flags(false, false), None);
+    }
+
+    #[test]
+    fn levels_map_to_infc_opt_levels() {
+        assert_eq!(Optimize::Debug.infc_opt_level(), "0");
+        assert_eq!(Optimize::Release.infc_opt_level(), "3");
+    }
+
+    #[test]
+    fn opt_level_support_is_read_from_help() {
+        assert!(supports_opt_level(
+            "  --opt-level <LEVEL>  LLVM optimization level"
+        ));
+        assert!(!supports_opt_level(
+            "  --target <TARGET>  WebAssembly target"
+        ));
+    }
+}
@@ -12,25 +12,113 @@
 //!
 //! Use [`init_project`] to initialize the current directory as an
 //! Inference project without creating a new directory.
-
-use crate::project::manifest::{InferenceToml, detect_infc_version, validate_project_name};
+//!
+//! ## Version Control and CI
+//!
+//! New projects can be initialized with git, jujutsu (`jj`), or no version
+//! control (see [`Vcs`]), and can optionally include CI configuration for a
+//! [`CiProvider`]. A missing VCS binary only produces a warning.
+//!
+//! ## Templates
+//!
+//! The git and CI files form the project template. Its choices are recorded
+//! in the `[template]` section of `Inference.toml`, and the hashes of the
+//! generated files in `.inference/scaffold.lock`, so that
+//! [`upgrade`](super::upgrade) can bring the files up to date when the
+//! template changes.
+//!
+//! The `[package]` section of the generated `Inference.toml` is serialized
+//! from a [`Package`], so metadata such as descriptions is quoted correctly.
+//! The commented examples around it are rendered from a template string with
+//! a [`TemplateContext`], which provides `{{project_name}}`, `{{edition}}`,
+//! `{{year}}`, and `{{author}}`. [`create_project_with_template`] adds the
+//! files of a user template from `~/.inference/templates/` on top of the
+//! standard ones.
+//!
+//! ## Source Files
+//!
+//! Generated `.inf` files go through [`format_inf_source`] before they are
+//! written, so templates do not have to be spaced by hand.
+
+use crate::project::manifest::{
+    DEFAULT_EDITION, InferenceToml, Package, PackageMetadata, TemplateConfig, validate_edition,
+    validate_project_name,
+};
+use crate::project::template::{ProjectTemplate, TemplateContext, git_author};
+use crate::project::upgrade::ScaffoldLock;
 use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Name of the project template written by [`create_project`] and [`init_project`].
+pub const TEMPLATE_NAME: &str = "default";
+
+/// Revision of the template files.
+///
+/// Bump this whenever [`template_files`] changes so `infs scaffold upgrade`
+/// reports the new revision.
+pub const TEMPLATE_VERSION: u32 = 1;
+
+/// Version control system to initialize in a new project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Vcs {
+    /// Initialize a git repository.
+    #[default]
+    Git,
+    /// Initialize a jujutsu repository colocated with git.
+    #[value(name = "jj")]
+    Jujutsu,
+    /// Do not initialize version control.
+    None,
+}
+
+impl Vcs {
+    /// Returns `true` if `.gitignore` and `.gitkeep` files should be written.
+    ///
+    /// Jujutsu repositories are colocated with git and honor `.gitignore`.
+    #[must_use]
+    pub fn writes_git_files(self) -> bool {
+        matches!(self, Self::Git | Self::Jujutsu)
+    }
+}
+
+/// CI provider whose configuration files are added to a new project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CiProvider {
+    /// GitHub Actions.
+    Github,
+}
+
+impl CiProvider {
+    /// Returns the provider name used on the command line and in `Inference.toml`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Github => "github",
+        }
+    }
+}
+
 /// Creates a new Inference project with the given name.
 ///
 /// This function:
 /// 1. Validates the project name
 /// 2. Creates the project directory
 /// 3. Generates all project files
-/// 4. Optionally initializes a git repository
+/// 4. Writes the template files (git and CI files) and `.inference/scaffold.lock`
+/// 5. Optionally initializes a version control repository
 ///
 /// # Arguments
 ///
 /// * `name` - The project name (used for directory and manifest)
 /// * `parent_path` - Optional parent directory (defaults to current directory)
-/// * `init_git` - Whether to initialize a git repository
+/// * `vcs` - Version control system to initialize; with git, the author in
+///   the git configuration is recorded in `[package] authors`
+/// * `ci` - Optional CI provider whose configuration files are added
 ///
 /// # Returns
 ///
@@ -42,8 +130,64 @@ use std::process::Command;
 /// - The project name is invalid
 /// - The target directory already exists
 /// - File creation fails
-pub fn create_project(name: &str, parent_path: Option<&Path>, init_git: bool) -> Result<PathBuf> {
+pub fn create_project(
+    name: &str,
+    parent_path: Option<&Path>,
+    vcs: Vcs,
+    ci: Option<CiProvider>,
+) -> Result<PathBuf> {
+    create_project_with_template(
+        name,
+        parent_path,
+        vcs,
+        ci,
+        DEFAULT_EDITION,
+        &PackageMetadata::default(),
+        &ProjectTemplate::Builtin,
+    )
+}
+
+/// Creates a new Inference project for `edition` from `project_template`.
+///
+/// Works like [`create_project`], recording `metadata` in the `[package]`
+/// section, then writes the files of a user template over the generated
+/// ones. When authors are given, they are also the `{{author}}` of the
+/// template. Without authors, a project with a git repository gets the
+/// author from the git configuration, if one is set. The files are rendered before anything is
+/// written, so a broken template leaves no project directory behind.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project name is invalid
+/// - The edition is not one of [`EDITIONS`](crate::project::manifest::EDITIONS)
+/// - The license is not an SPDX license expression
+/// - The target directory already exists
+/// - A template file cannot be read or uses an unknown variable
+/// - File creation fails
+pub fn create_project_with_template(
+    name: &str,
+    parent_path: Option<&Path>,
+    vcs: Vcs,
+    ci: Option<CiProvider>,
+    edition: &str,
+    metadata: &PackageMetadata,
+    project_template: &ProjectTemplate,
+) -> Result<PathBuf> {
     validate_project_name(name)?;
+    validate_edition(edition)?;
+    metadata.validate()?;
+    let mut context = TemplateContext::for_project(name).with("edition", edition);
+    if !metadata.authors.is_empty() {
+        context = context.with("author", metadata.authors.join(", "));
+    }
+    let mut package = InferenceToml::new(name).package;
+    package.edition = edition.to_string();
+    metadata.apply_to(&mut package);
+    if package.authors.is_none() && vcs == Vcs::Git {
+        package.authors = git_author().map(|author| vec![author]);
+    }
+    let extra_files = project_template.files(&context)?;
 
     let parent = parent_path.unwrap_or_else(|| Path::new("."));
     let project_path = parent.join(name);
@@ -62,11 +206,27 @@ pub fn create_project(name: &str, parent_path: Option<&Path>, init_git: bool) ->
         )
     })?;
 
-    write_project_files(&project_path, name)?;
+    let template = TemplateConfig {
+        name: project_template.name().to_string(),
+        git: vcs.writes_git_files(),
+        ci,
+    };
+    write_project_files(&project_path, &context, &package, &template)?;
+
+    let files = template_files(&template);
+    for (relative_path, content) in &files {
+        write_template_file(&project_path, relative_path, content)?;
+    }
+    ScaffoldLock::from_files(&files).save(&project_path)?;
 
-    if init_git {
-        write_git_files(&project_path)?;
-        init_git_repository(&project_path);
+    for (relative_path, content) in &extra_files {
+        write_template_file(&project_path, relative_path, content)?;
+    }
+
+    match vcs {
+        Vcs::Git => init_git_repository(&project_path),
+        Vcs::Jujutsu => init_jj_repository(&project_path),
+        Vcs::None => {}
     }
 
     Ok(project_path)
@@ -74,13 +234,14 @@ pub fn create_project(name: &str, parent_path: Option<&Path>, init_git: bool) ->
 
 /// Creates a new Inference project using the default structure.
 ///
-/// This is a convenience function that calls [`create_project`].
+/// This is a convenience function that calls [`create_project`] without
+/// CI files.
 ///
 /// # Arguments
 ///
 /// * `name` - The project name (used for directory and manifest)
 /// * `parent_path` - Optional parent directory (defaults to current directory)
-/// * `init_git` - Whether to initialize a git repository
+/// * `vcs` - Version control system to initialize
 ///
 /// # Returns
 ///
@@ -90,12 +251,8 @@ pub fn create_project(name: &str, parent_path: Option<&Path>, init_git: bool) ->
 ///
 /// Returns an error if project creation fails.
 #[allow(dead_code)]
-pub fn create_project_default(
-    name: &str,
-    parent_path: Option<&Path>,
-    init_git: bool,
-) -> Result<PathBuf> {
-    create_project(name, parent_path, init_git)
+pub fn create_project_default(name: &str, parent_path: Option<&Path>, vcs: Vcs) -> Result<PathBuf> {
+    create_project(name, parent_path, vcs, None)
 }
 
 /// Initializes an existing directory as an Inference project.
@@ -133,7 +290,14 @@ pub fn init_project(path: Option<&Path>, name: Option<&str>, create_src: bool) -
         );
     }
 
-    let manifest = InferenceToml::new(&project_name);
+    // If git is initialized, the git files are part of the template.
+    let template = TemplateConfig {
+        name: TEMPLATE_NAME.to_string(),
+        git: project_path.join(".git").exists(),
+        ci: None,
+    };
+    let mut manifest = InferenceToml::new(&project_name);
+    manifest.template = Some(template.clone());
     manifest.write_to_file(&manifest_path)?;
 
     if create_src {
@@ -143,48 +307,37 @@ pub fn init_project(path: Option<&Path>, name: Option<&str>, create_src: bool) -
 
         let main_path = src_dir.join("main.inf");
         if !main_path.exists() {
-            std::fs::write(&main_path, main_inf_content())
-                .with_context(|| format!("Failed to write main.inf: {}", main_path.display()))?;
+            write_inf_file(&main_path, &main_inf_content())?;
         }
     }
 
-    // If git is initialized, create git-related files (without overwriting existing ones)
-    if project_path.join(".git").exists() {
-        write_git_files_if_missing(project_path)?;
-    }
-
-    Ok(())
-}
-
-/// Writes git-related files only if they don't already exist.
-fn write_git_files_if_missing(project_path: &Path) -> Result<()> {
-    let tests_gitkeep = project_path.join("tests").join(".gitkeep");
-    if !tests_gitkeep.exists() {
-        std::fs::create_dir_all(project_path.join("tests"))
-            .with_context(|| "Failed to create tests directory")?;
-        std::fs::write(&tests_gitkeep, "").with_context(|| "Failed to write tests/.gitkeep")?;
-    }
-
-    let proofs_gitkeep = project_path.join("proofs").join(".gitkeep");
-    if !proofs_gitkeep.exists() {
-        std::fs::create_dir_all(project_path.join("proofs"))
-            .with_context(|| "Failed to create proofs directory")?;
-        std::fs::write(&proofs_gitkeep, "").with_context(|| "Failed to write proofs/.gitkeep")?;
-    }
-
-    let gitignore_path = project_path.join(".gitignore");
-    if !gitignore_path.exists() {
-        std::fs::write(&gitignore_path, gitignore_content())
-            .with_context(|| "Failed to write .gitignore")?;
+    // Template files are created without overwriting existing ones; only the
+    // files written here are recorded in the lock.
+    let mut written = Vec::new();
+    for (relative_path, content) in template_files(&template) {
+        if !project_path.join(relative_path).exists() {
+            write_template_file(project_path, relative_path, &content)?;
+            written.push((relative_path, content));
+        }
     }
+    ScaffoldLock::from_files(&written).save(project_path)?;
 
     Ok(())
 }
 
 /// Writes core project files to the project directory.
-fn write_project_files(project_path: &Path, project_name: &str) -> Result<()> {
+fn write_project_files(
+    project_path: &Path,
+    context: &TemplateContext,
+    package: &Package,
+    template: &TemplateConfig,
+) -> Result<()> {
     let manifest_path = project_path.join("Inference.toml");
-    std::fs::write(&manifest_path, manifest_content(project_name)).with_context(|| {
+    std::fs::write(
+        &manifest_path,
+        manifest_content(context, package, template)?,
+    )
+    .with_context(|| {
         format!(
             "Failed to write Inference.toml: {}",
             manifest_path.display()
@@ -195,9 +348,7 @@ fn write_project_files(project_path: &Path, project_name: &str) -> Result<()> {
     std::fs::create_dir_all(&src_dir)
         .with_context(|| format!("Failed to create src directory: {}", src_dir.display()))?;
 
-    let main_path = src_dir.join("main.inf");
-    std::fs::write(&main_path, main_inf_content())
-        .with_context(|| format!("Failed to write main.inf: {}", main_path.display()))?;
+    write_inf_file(&src_dir.join("main.inf"), &main_inf_content())?;
 
     let tests_dir = project_path.join("tests");
     std::fs::create_dir_all(&tests_dir)
@@ -214,35 +365,162 @@ fn write_project_files(project_path: &Path, project_name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Writes git-related files (.gitignore, .gitkeep).
-fn write_git_files(project_path: &Path) -> Result<()> {
-    std::fs::write(project_path.join("tests").join(".gitkeep"), "")
-        .with_context(|| "Failed to write tests/.gitkeep")?;
-    std::fs::write(project_path.join("proofs").join(".gitkeep"), "")
-        .with_context(|| "Failed to write proofs/.gitkeep")?;
+/// Writes a generated `.inf` file after normalizing it with [`format_inf_source`].
+fn write_inf_file(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, format_inf_source(content))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Normalizes the layout of a generated `.inf` source file.
+///
+/// - Trailing whitespace is removed and line endings become `\n`.
+/// - Single-line top-level `use` directives are sorted, deduplicated, and
+///   grouped after the leading comment block, set off by blank lines.
+/// - Runs of blank lines collapse into one, and a top-level item always
+///   starts with a blank line after the closing brace of the previous one.
+/// - There are no leading blank lines and the file ends with one newline.
+///
+/// Only whitespace and the order of `use` lines change, so the result
+/// compiles exactly like the input.
+#[must_use]
+pub fn format_inf_source(source: &str) -> String {
+    let is_use = |line: &str| line.starts_with("use ") && line.ends_with(';');
+    let lines: Vec<&str> = source.lines().map(str::trim_end).collect();
+
+    let mut imports: Vec<&str> = lines.iter().copied().filter(|line| is_use(line)).collect();
+    imports.sort_unstable();
+    imports.dedup();
+
+    let header_len = lines
+        .iter()
+        .take_while(|line| line.is_empty() || line.starts_with("//"))
+        .count();
+    let mut ordered: Vec<&str> = lines[..header_len].to_vec();
+    if !imports.is_empty() {
+        ordered.push("");
+        ordered.extend(imports);
+        ordered.push("");
+    }
+    ordered.extend(lines[header_len..].iter().filter(|line| !is_use(line)));
+
+    let mut formatted = String::new();
+    let mut previous: Option<&str> = None;
+    let mut blank = false;
+    for line in ordered {
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        if previous.is_some_and(|previous| blank || previous == "}") {
+            formatted.push('\n');
+        }
+        formatted.push_str(line);
+        formatted.push('\n');
+        previous = Some(line);
+        blank = false;
+    }
+    formatted
+}
+
+/// Writes a template file, creating its parent directories.
+pub(crate) fn write_template_file(
+    project_path: &Path,
+    relative_path: impl AsRef<Path>,
+    content: impl AsRef<[u8]>,
+) -> Result<()> {
+    let path = project_path.join(relative_path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
 
-    let gitignore_path = project_path.join(".gitignore");
-    std::fs::write(&gitignore_path, gitignore_content())
-        .with_context(|| format!("Failed to write .gitignore: {}", gitignore_path.display()))?;
+/// Returns the files rendered from a template as
+/// `(path relative to the project root, content)` pairs.
+///
+/// `Inference.toml` and `src/` are not part of the template: they are
+/// written once and belong to the user afterwards.
+#[must_use]
+pub fn template_files(template: &TemplateConfig) -> Vec<(&'static str, String)> {
+    let mut files = Vec::new();
+    if template.git {
+        files.extend(git_files());
+    }
+    if let Some(ci) = template.ci {
+        files.extend(ci_files(ci));
+    }
+    files
+}
 
-    Ok(())
+/// Returns the `[template]` section of `Inference.toml` for `template`.
+#[must_use]
+pub fn template_section(template: &TemplateConfig) -> String {
+    let mut section = format!(
+        "[template]\nname = \"{}\"\ngit = {}\n",
+        template.name, template.git
+    );
+    if let Some(ci) = template.ci {
+        let _ = writeln!(section, "ci = \"{}\"", ci.as_str());
+    }
+    section
 }
 
-/// Generates the content for `Inference.toml`.
-fn manifest_content(project_name: &str) -> String {
-    let infc_version = detect_infc_version();
-    format!(
-        r#"[package]
-name = "{project_name}"
-version = "0.1.0"
-infc_version = "{infc_version}"
-
-# Optional fields:
-# description = "A brief description of the project"
-# authors = ["Your Name <you@example.com>"]
-# license = "MIT"
-
-# [dependencies]
+/// Returns the git-related files (`.gitignore`, `.gitkeep`).
+fn git_files() -> Vec<(&'static str, String)> {
+    vec![
+        ("tests/.gitkeep", String::new()),
+        ("proofs/.gitkeep", String::new()),
+        (".gitignore", gitignore_content()),
+    ]
+}
+
+/// Returns the CI configuration files for a provider as
+/// `(path relative to the project root, content)` pairs.
+fn ci_files(ci: CiProvider) -> Vec<(&'static str, String)> {
+    match ci {
+        CiProvider::Github => vec![(
+            ".github/workflows/verify.yml",
+            github_verify_workflow_content(),
+        )],
+    }
+}
+
+/// Generates the content for `.github/workflows/verify.yml`.
+fn github_verify_workflow_content() -> String {
+    String::from(
+        r#"name: verify
+
+on:
+  push:
+  pull_request:
+
+jobs:
+  verify:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+
+      - name: Install infs
+        run: |
+          curl -fsSL https://github.com/Inferara/inference/releases/latest/download/infs-linux-x64.tar.gz \
+            | tar -xz -C "$RUNNER_TEMP"
+          echo "$RUNNER_TEMP" >> "$GITHUB_PATH"
+
+      - name: Install toolchain
+        run: |
+          infs install
+          echo "$HOME/.inference/bin" >> "$GITHUB_PATH"
+
+      - name: Build and generate Rocq translation
+        run: infs build src/main.inf --codegen -o -v
+"#,
+    )
+}
+
+/// Template for `Inference.toml`, rendered by [`manifest_content`].
+const MANIFEST_TEMPLATE: &str = r#"{{package_section}}
+{{optional_fields}}# [dependencies]
 # Future: package dependencies
 # std = "0.1"
 
@@ -252,8 +530,49 @@ infc_version = "{infc_version}"
 
 # [verification]
 # output-dir = "proofs/"
-"#
-    )
+
+{{template_section}}"#;
+
+/// Generates the content for `Inference.toml`.
+///
+/// The `[package]` section is serialized from `package`, followed by
+/// commented examples of the optional fields it leaves unset. `context`
+/// provides the variables of the examples; the `[template]` section is
+/// added to it.
+fn manifest_content(
+    context: &TemplateContext,
+    package: &Package,
+    template: &TemplateConfig,
+) -> Result<String> {
+    #[derive(Serialize)]
+    struct PackageSection<'a> {
+        package: &'a Package,
+    }
+
+    let package_section =
+        toml::to_string(&PackageSection { package }).context("Failed to serialize [package]")?;
+    let examples: Vec<&str> = [
+        (
+            package.description.is_none(),
+            "# description = \"A brief description of the project\"",
+        ),
+        (package.authors.is_none(), "# authors = [\"{{author}}\"]"),
+        (package.license.is_none(), "# license = \"MIT\""),
+    ]
+    .into_iter()
+    .filter_map(|(unset, example)| unset.then_some(example))
+    .collect();
+    let optional_fields = if examples.is_empty() {
+        String::new()
+    } else {
+        context.render(&format!("# Optional fields:\n{}\n\n", examples.join("\n")))?
+    };
+    context
+        .clone()
+        .with("package_section", package_section)
+        .with("optional_fields", optional_fields)
+        .with("template_section", template_section(template))
+        .render(MANIFEST_TEMPLATE)
 }
 
 /// Generates the content for `src/main.inf`.
@@ -294,8 +613,20 @@ Thumbs.db
 /// This function logs a warning if git initialization fails rather than
 /// returning an error, as git is optional.
 fn init_git_repository(project_path: &Path) {
-    let result = Command::new("git")
-        .args(["init"])
+    run_vcs_init(project_path, "git", &["init"]);
+}
+
+/// Initializes a jujutsu repository colocated with git in the project directory.
+///
+/// Like [`init_git_repository`], failures only produce a warning.
+fn init_jj_repository(project_path: &Path) {
+    run_vcs_init(project_path, "jj", &["git", "init", "--colocate"]);
+}
+
+/// Runs a VCS initialization command, logging a warning on failure.
+fn run_vcs_init(project_path: &Path, program: &str, args: &[&str]) {
+    let result = Command::new(program)
+        .args(args)
         .current_dir(project_path)
         .output();
 
@@ -306,16 +637,19 @@ fn init_git_repository(project_path: &Path) {
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr);
             eprintln!(
-                "Warning: git init failed: {}. Project created without git repository.",
+                "Warning: {program} {} failed: {}. Project created without {program} repository.",
+                args.join(" "),
                 stderr.trim()
             );
         }
         Err(e) => {
             if e.kind() == std::io::ErrorKind::NotFound {
-                eprintln!("Warning: git not found. Project created without git repository.");
+                eprintln!(
+                    "Warning: {program} not found. Project created without {program} repository."
+                );
             } else {
                 eprintln!(
-                    "Warning: Failed to run git: {e}. Project created without git repository."
+                    "Warning: Failed to run {program}: {e}. Project created without {program} repository."
                 );
             }
         }
@@ -338,6 +672,7 @@ fn infer_project_name(path: &Path) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::project::template::DirectoryTemplate;
     use std::fs;
 
     fn temp_dir() -> PathBuf {
@@ -353,7 +688,7 @@ mod tests {
     #[test]
     fn test_create_project_success() {
         let parent = temp_dir();
-        let result = create_project("my_project", Some(&parent), false);
+        let result = create_project("my_project", Some(&parent), Vcs::None, None);
 
         assert!(result.is_ok());
         let project_path = result.unwrap();
@@ -373,7 +708,7 @@ mod tests {
     #[test]
     fn test_create_project_with_git_creates_gitignore() {
         let parent = temp_dir();
-        let result = create_project("git_enabled_project", Some(&parent), true);
+        let result = create_project("git_enabled_project", Some(&parent), Vcs::Git, None);
 
         assert!(result.is_ok());
         let project_path = result.unwrap();
@@ -390,7 +725,7 @@ mod tests {
     #[test]
     fn test_create_project_default() {
         let parent = temp_dir();
-        let result = create_project_default("my_project_default", Some(&parent), false);
+        let result = create_project_default("my_project_default", Some(&parent), Vcs::None);
 
         assert!(result.is_ok());
         let project_path = result.unwrap();
@@ -403,7 +738,7 @@ mod tests {
     #[test]
     fn test_create_project_invalid_name() {
         let parent = temp_dir();
-        let result = create_project("fn", Some(&parent), false);
+        let result = create_project("fn", Some(&parent), Vcs::None, None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("reserved"));
@@ -417,7 +752,7 @@ mod tests {
         let existing = parent.join("existing");
         fs::create_dir_all(&existing).unwrap();
 
-        let result = create_project("existing", Some(&parent), false);
+        let result = create_project("existing", Some(&parent), Vcs::None, None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("already exists"));
@@ -428,7 +763,7 @@ mod tests {
     #[test]
     fn test_create_project_with_git() {
         let parent = temp_dir();
-        let result = create_project("git_project", Some(&parent), true);
+        let result = create_project("git_project", Some(&parent), Vcs::Git, None);
 
         assert!(result.is_ok());
         let project_path = result.unwrap();
@@ -440,6 +775,208 @@ mod tests {
         cleanup(&parent);
     }
 
+    #[test]
+    fn test_create_project_with_jj_writes_git_files() {
+        let parent = temp_dir();
+        let result = create_project("jj_project", Some(&parent), Vcs::Jujutsu, None);
+
+        // jj may or may not be installed; a missing binary only warns
+        let project_path = result.unwrap();
+        assert!(project_path.join("Inference.toml").exists());
+        assert!(project_path.join(".gitignore").exists());
+        assert!(project_path.join("tests").join(".gitkeep").exists());
+
+        cleanup(&parent);
+    }
+
+    #[test]
+    fn test_create_project_from_directory_template() {
+        let parent = temp_dir();
+        let template_dir = parent.join("templates").join("lib");
+        fs::create_dir_all(template_dir.join("src")).unwrap();
+        fs::write(
+            template_dir.join("src").join("main.inf"),
+            "// {{project_name}} library\n",
+        )
+        .unwrap();
+        fs::write(template_dir.join("README.md"), "# {{project_name}}\n").unwrap();
+        let template = ProjectTemplate::Directory(DirectoryTemplate::load(&template_dir).unwrap());
+
+        let project_path = create_project_with_template(
+            "from_lib",
+            Some(&parent),
+            Vcs::None,
+            None,
+            DEFAULT_EDITION,
+            &PackageMetadata::default(),
+            &template,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(project_path.join("src").join("main.inf")).unwrap(),
+            "// from_lib library\n"
+        );
+        assert_eq!(
+            fs::read_to_string(project_path.join("README.md")).unwrap(),
+            "# from_lib\n"
+        );
+        let manifest = InferenceToml::from_file(&project_path.join("Inference.toml")).unwrap();
+        assert_eq!(manifest.template.map(|t| t.name), Some(String::from("lib")));
+
+        fs::write(template_dir.join("README.md"), "{{license}}").unwrap();
+        let template = ProjectTemplate::Directory(DirectoryTemplate::load(&template_dir).unwrap());
+        let result = create_project_with_template(
+            "broken",
+            Some(&parent),
+            Vcs::None,
+            None,
+            DEFAULT_EDITION,
+            &PackageMetadata::default(),
+            &template,
+        );
+        assert!(result.is_err());
+        assert!(!parent.join("broken").exists());
+
+        cleanup(&parent);
+    }
+
+    #[test]
+    fn test_create_project_with_edition() {
+        let parent = temp_dir();
+        let project_path = create_project_with_template(
+            "edition_project",
+            Some(&parent),
+            Vcs::None,
+            None,
+            "2024",
+            &PackageMetadata::default(),
+            &ProjectTemplate::Builtin,
+        )
+        .unwrap();
+        let manifest = InferenceToml::from_file(&project_path.join("Inference.toml")).unwrap();
+        assert_eq!(manifest.package.edition, "2024");
+
+        let result = create_project_with_template(
+            "old_edition",
+            Some(&parent),
+            Vcs::None,
+            None,
+            "2015",
+            &PackageMetadata::default(),
+            &ProjectTemplate::Builtin,
+        );
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("edition value '2015'"), "{message}");
+        assert!(!parent.join("old_edition").exists());
+
+        cleanup(&parent);
+    }
+
+    #[test]
+    fn test_create_project_with_metadata() {
+        let parent = temp_dir();
+        let metadata = PackageMetadata {
+            description: Some(String::from("Proofs about \"sorting\"")),
+            authors: vec![String::from("Ada <ada@example.com>"), String::from("Grace")],
+            license: Some(String::from("Apache-2.0 OR MIT")),
+        };
+        let project_path = create_project_with_template(
+            "metadata_project",
+            Some(&parent),
+            Vcs::None,
+            None,
+            DEFAULT_EDITION,
+            &metadata,
+            &ProjectTemplate::Builtin,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(project_path.join("Inference.toml")).unwrap();
+        assert!(
+            content.contains("license = \"Apache-2.0 OR MIT\""),
+            "{content}"
+        );
+        assert!(!content.contains("# Optional fields"), "{content}");
+        let manifest = InferenceToml::parse(&content).unwrap();
+        assert_eq!(manifest.package.description, metadata.description);
+        assert_eq!(manifest.package.authors, Some(metadata.authors.clone()));
+        assert_eq!(manifest.package.license, metadata.license);
+        assert_eq!(
+            InferenceToml::parse(&manifest.to_toml().unwrap()).unwrap(),
+            manifest
+        );
+
+        let result = create_project_with_template(
+            "bad_license",
+            Some(&parent),
+            Vcs::None,
+            None,
+            DEFAULT_EDITION,
+            &PackageMetadata {
+                license: Some(String::from("MIT/Apache")),
+                ..PackageMetadata::default()
+            },
+            &ProjectTemplate::Builtin,
+        );
+        assert!(result.is_err());
+        assert!(!parent.join("bad_license").exists());
+
+        cleanup(&parent);
+    }
+
+    #[test]
+    fn test_create_project_flag_matrix() {
+        let workflow = Path::new(".github").join("workflows").join("verify.yml");
+        let cases = [
+            (Vcs::None, None, false, false),
+            (Vcs::None, Some(CiProvider::Github), false, true),
+            (Vcs::Git, None, true, false),
+            (Vcs::Git, Some(CiProvider::Github), true, true),
+            (Vcs::Jujutsu, Some(CiProvider::Github), true, true),
+        ];
+
+        for (index, (vcs, ci, expect_gitignore, expect_workflow)) in cases.into_iter().enumerate() {
+            let parent = temp_dir();
+            let name = format!("matrix_{index}");
+            let project_path = create_project(&name, Some(&parent), vcs, ci).unwrap();
+
+            assert_eq!(
+                project_path.join(".gitignore").exists(),
+                expect_gitignore,
+                "{vcs:?} {ci:?}"
+            );
+            assert_eq!(
+                project_path.join(&workflow).exists(),
+                expect_workflow,
+                "{vcs:?} {ci:?}"
+            );
+            if vcs == Vcs::None {
+                assert!(!project_path.join(".git").exists());
+                assert!(!project_path.join(".jj").exists());
+            }
+
+            cleanup(&parent);
+        }
+    }
+
+    #[test]
+    fn test_github_workflow_installs_and_builds() {
+        let files = ci_files(CiProvider::Github);
+        assert_eq!(files.len(), 1);
+        let (path, content) = &files[0];
+        assert_eq!(*path, ".github/workflows/verify.yml");
+        assert!(content.contains("infs install"));
+        assert!(content.contains("infs build src/main.inf"));
+    }
+
+    #[test]
+    fn test_vcs_writes_git_files() {
+        assert!(Vcs::Git.writes_git_files());
+        assert!(Vcs::Jujutsu.writes_git_files());
+        assert!(!Vcs::None.writes_git_files());
+    }
+
     #[test]
     fn test_init_project_success() {
         let dir = temp_dir();
@@ -506,10 +1043,39 @@ mod tests {
 
     #[test]
     fn test_manifest_contains_project_name() {
-        let content = manifest_content("my_awesome_project");
-        assert!(content.contains("my_awesome_project"));
+        let template = TemplateConfig {
+            name: TEMPLATE_NAME.to_string(),
+            git: true,
+            ci: Some(CiProvider::Github),
+        };
+        let variables = TemplateContext::new()
+            .with("project_name", "my_awesome_project")
+            .with("edition", DEFAULT_EDITION)
+            .with("author", "Ada <ada@example.com>");
+        let package = InferenceToml::new("my_awesome_project").package;
+        let content = manifest_content(&variables, &package, &template).unwrap();
+        assert!(content.contains("name = \"my_awesome_project\""));
+        assert!(content.contains("# authors = [\"Ada <ada@example.com>\"]"));
         assert!(content.contains("version = \"0.1.0\""));
+        assert!(content.contains("edition = \"2024\""));
         assert!(content.contains("infc_version = \""));
+
+        let manifest: InferenceToml = toml::from_str(&content).unwrap();
+        assert_eq!(manifest.template, Some(template));
+    }
+
+    #[test]
+    fn test_format_inf_source_normalizes_spacing_and_imports() {
+        let source = "\r\n// Header comment\r\nuse b;\n\n\nuse a;  \nfn first() {\n    return;   \n}\nfn second() {\n\n\n    return;\n}\nuse a;\n\n\n";
+        let expected = "// Header comment\n\nuse a;\nuse b;\n\nfn first() {\n    return;\n}\n\nfn second() {\n\n    return;\n}\n";
+        assert_eq!(format_inf_source(source), expected);
+        assert_eq!(format_inf_source(expected), expected);
+    }
+
+    #[test]
+    fn test_format_inf_source_keeps_template_unchanged() {
+        assert_eq!(format_inf_source(&main_inf_content()), main_inf_content());
+        assert_eq!(format_inf_source(""), "");
     }
 
     #[test]
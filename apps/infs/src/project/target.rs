@@ -0,0 +1,192 @@
+//! Compilation target selection.
+//!
+//! The target comes from the `--target` flag of `build` and `run`, falling
+//! back to `[build] target` in the project's `Inference.toml`, and finally to
+//! `wasm32`. The selected target is forwarded to `infc`, which passes it on to
+//! code generation.
+//!
+//! `--target` was added to `infc` together with `wasm64` support, so older
+//! toolchains can only build `wasm32`. Before forwarding a non-default target,
+//! [`ensure_infc_supports`] checks the help text of the installed `infc`.
+
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::manifest::InferenceToml;
+use crate::errors::InfsError;
+
+/// WebAssembly target to compile for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// 32-bit linear memory.
+    #[default]
+    Wasm32,
+    /// 64-bit linear memory (memory64).
+    Wasm64,
+}
+
+impl Target {
+    /// Returns the target name used on the command line and in manifests.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wasm32 => "wasm32",
+            Self::Wasm64 => "wasm64",
+        }
+    }
+
+    /// Returns the file name `infc` writes the WASM binary to.
+    ///
+    /// Non-default targets include the target name, e.g. `main.wasm64.wasm`.
+    #[must_use]
+    pub fn wasm_file_name(self, stem: &str) -> String {
+        if self == Self::default() {
+            format!("{stem}.wasm")
+        } else {
+            format!("{stem}.{self}.wasm")
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Picks the target for a build: the flag wins over the manifest, which wins
+/// over the default.
+#[must_use]
+pub fn select_target(flag: Option<Target>, manifest: Option<&InferenceToml>) -> Target {
+    flag.or_else(|| manifest.map(|manifest| manifest.build.target))
+        .unwrap_or_default()
+}
+
+/// Resolves the target for compiling `source`.
+///
+/// The manifest is only read when no `--target` flag was given.
+///
+/// # Errors
+///
+/// Returns an error if the project manifest exists but cannot be parsed,
+/// including when it names an unknown target.
+pub fn resolve_target(flag: Option<Target>, source: &Path) -> Result<Target> {
+    if flag.is_some() {
+        return Ok(select_target(flag, None));
+    }
+    let manifest = match InferenceToml::find(source) {
+        Some(path) => Some(InferenceToml::from_file(&path)?),
+        None => None,
+    };
+    Ok(select_target(None, manifest.as_ref()))
+}
+
+/// Returns the targets an `infc` binary can build, judging by its help text.
+#[must_use]
+pub fn supported_targets(infc_help: &str) -> Vec<Target> {
+    if infc_help.contains("--target") {
+        Target::value_variants().to_vec()
+    } else {
+        vec![Target::default()]
+    }
+}
+
+/// Checks that `target` is in `supported`.
+///
+/// # Errors
+///
+/// Returns [`InfsError::InvalidArguments`] naming the supported targets.
+pub fn check_supported(target: Target, supported: &[Target], infc_path: &Path) -> Result<()> {
+    if supported.contains(&target) {
+        return Ok(());
+    }
+    let names = supported
+        .iter()
+        .map(|target| target.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(InfsError::invalid_arguments(format!(
+        "target `{target}` is not supported by the installed toolchain ({}); supported targets: {names}",
+        infc_path.display()
+    ))
+    .into())
+}
+
+/// Checks that the `infc` at `infc_path` can build `target`.
+///
+/// The default target is always supported and needs no check.
+///
+/// # Errors
+///
+/// Returns an error if `infc` cannot be run or does not support `target`.
+pub fn ensure_infc_supports(infc_path: &Path, target: Target) -> Result<()> {
+    if target == Target::default() {
+        return Ok(());
+    }
+    let output = Command::new(infc_path)
+        .arg("--help")
+        .output()
+        .with_context(|| format!("Failed to execute infc at {}", infc_path.display()))?;
+    let help = String::from_utf8_lossy(&output.stdout);
+    check_supported(target, &supported_targets(&help), infc_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_with_target(target: Target) -> InferenceToml {
+        let mut manifest = InferenceToml::new("demo");
+        manifest.build.target = target;
+        manifest
+    }
+
+    #[test]
+    fn flag_takes_precedence_over_manifest() {
+        let manifest = manifest_with_target(Target::Wasm64);
+        assert_eq!(
+            select_target(Some(Target::Wasm32), Some(&manifest)),
+            Target::Wasm32
+        );
+        assert_eq!(select_target(None, Some(&manifest)), Target::Wasm64);
+        assert_eq!(select_target(None, None), Target::Wasm32);
+    }
+
+    #[test]
+    fn wasm_file_name_includes_non_default_target() {
+        assert_eq!(Target::Wasm32.wasm_file_name("main"), "main.wasm");
+        assert_eq!(Target::Wasm64.wasm_file_name("main"), "main.wasm64.wasm");
+    }
+
+    #[test]
+    fn toolchain_without_target_flag_only_supports_default() {
+        assert_eq!(
+            supported_targets("Usage: infc [OPTIONS] <PATH>\n  --out-dir <DIR>"),
+            vec![Target::Wasm32]
+        );
+        assert_eq!(
+            supported_targets("  --target <TARGET>  WebAssembly target"),
+            vec![Target::Wasm32, Target::Wasm64]
+        );
+    }
+
+    #[test]
+    fn unsupported_target_error_lists_supported_targets() {
+        let err =
+            check_supported(Target::Wasm64, &[Target::Wasm32], Path::new("/tc/infc")).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("target `wasm64` is not supported"));
+        assert!(message.contains("/tc/infc"));
+        assert!(message.contains("supported targets: wasm32"));
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(crate::errors::EXIT_INVALID_ARGUMENTS)
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Placeholder substitution for project templates.
+//!
+//! Template strings reference variables as `{{name}}`; whitespace inside the
+//! braces is ignored. A [`TemplateContext`] holds the values and renders the
+//! strings:
+//!
+//! ```ignore
+//! let context = TemplateContext::new().with("project_name", "demo");
+//! assert_eq!(context.render("name = \"{{ project_name }}\"")?, "name = \"demo\"");
+//! ```
+//!
+//! [`TemplateContext::for_project`] provides the variables available to the
+//! project templates:
+//!
+//! | Variable | Value |
+//! |----------|-------|
+//! | `project_name` | Name of the project |
+//! | `year` | Current year |
+//! | `author` | `git config user.name` and `user.email`, or a placeholder |
+//!
+//! Referencing a variable that is not in the context is an error, so a typo
+//! in a template is caught when it is rendered instead of ending up in the
+//! generated project.
+//!
+//! ## User Templates
+//!
+//! Besides the built-in `default` template, `infs new --template <name>`
+//! accepts a [`DirectoryTemplate`]: a directory under
+//! `~/.inference/templates/<name>/` whose files are copied into the new
+//! project after the standard files, replacing them where paths overlap.
+//! Text files go through [`TemplateContext::render`]; other files are copied
+//! unchanged. A user template named like a built-in one is ignored.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use crate::errors::InfsError;
+use crate::project::manifest::MANIFEST_FILE;
+use crate::project::scaffold::TEMPLATE_NAME;
+use crate::toolchain::paths::format_timestamp_iso8601;
+
+/// Author used when git does not provide one.
+const DEFAULT_AUTHOR: &str = "Your Name <you@example.com>";
+
+/// Values available to a template, keyed by variable name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateContext {
+    vars: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    /// Creates an empty context.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the context for a new project named `project_name`.
+    #[must_use]
+    pub fn for_project(project_name: &str) -> Self {
+        Self::new()
+            .with("project_name", project_name)
+            .with("year", current_year())
+            .with("author", git_author().as_deref().unwrap_or(DEFAULT_AUTHOR))
+    }
+
+    /// Returns the context with `name` set to `value`.
+    #[must_use]
+    pub fn with(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Sets `name` to `value`, replacing any previous value.
+    pub fn insert(&mut self, name: &str, value: impl Into<String>) {
+        self.vars.insert(name.to_string(), value.into());
+    }
+
+    /// Returns the value of `name`, if set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Replaces every `{{name}}` in `template` with the value of `name`.
+    ///
+    /// Substituted values are not rendered again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `template` references a variable that is not in
+    /// the context, or contains a `{{` without a matching `}}`.
+    pub fn render(&self, template: &str) -> Result<String> {
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                bail!("Unclosed '{{{{' in template");
+            };
+            let name = after[..end].trim();
+            let Some(value) = self.get(name) else {
+                bail!("Unknown template variable '{name}'");
+            };
+            rendered.push_str(value);
+            rest = &after[end + 2..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+}
+
+/// A template that `infs new` can create a project from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectTemplate {
+    /// The built-in template.
+    Builtin,
+    /// A template read from the user's templates directory.
+    Directory(DirectoryTemplate),
+}
+
+impl ProjectTemplate {
+    /// Returns the template name recorded in `Inference.toml`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Builtin => TEMPLATE_NAME,
+            Self::Directory(template) => template.name(),
+        }
+    }
+
+    /// Renders the files this template adds on top of the standard project
+    /// files, as `(path relative to the project root, content)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a template file cannot be read or references an
+    /// unknown variable.
+    pub fn files(&self, context: &TemplateContext) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        match self {
+            Self::Builtin => Ok(Vec::new()),
+            Self::Directory(template) => template.files(context),
+        }
+    }
+}
+
+/// A project template stored as a directory tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryTemplate {
+    name: String,
+    root: PathBuf,
+}
+
+impl DirectoryTemplate {
+    /// Loads the template in `root`, named after the directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` is not a directory or contains an
+    /// `Inference.toml`, which is always generated by infs.
+    pub fn load(root: &Path) -> Result<Self> {
+        if !root.is_dir() {
+            bail!("Template directory not found: {}", root.display());
+        }
+        if root.join(MANIFEST_FILE).exists() {
+            bail!(
+                "Template {} must not contain {MANIFEST_FILE}; it is generated by infs",
+                root.display()
+            );
+        }
+        let name = root
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("Invalid template directory: {}", root.display()))?;
+        Ok(Self {
+            name: name.to_string(),
+            root: root.to_path_buf(),
+        })
+    }
+
+    /// Returns the template name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reads every file of the template, rendering text files with
+    /// `context`. Paths are relative to the template root, in sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or a text file references
+    /// an unknown variable.
+    pub fn files(&self, context: &TemplateContext) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = Vec::new();
+        for relative in list_files(&self.root, Path::new(""))? {
+            let path = self.root.join(&relative);
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let data = match String::from_utf8(bytes) {
+                Ok(text) => context
+                    .render(&text)
+                    .with_context(|| format!("Failed to render {}", path.display()))?
+                    .into_bytes(),
+                Err(binary) => binary.into_bytes(),
+            };
+            files.push((relative, data));
+        }
+        Ok(files)
+    }
+}
+
+/// Returns the files under `dir` relative to the template root, sorted.
+///
+/// Symlinks and other special files are skipped.
+fn list_files(root: &Path, dir: &Path) -> Result<Vec<PathBuf>> {
+    let full = root.join(dir);
+    let mut entries: Vec<_> = std::fs::read_dir(&full)
+        .with_context(|| format!("Failed to read {}", full.display()))?
+        .collect::<std::io::Result<_>>()
+        .with_context(|| format!("Failed to read {}", full.display()))?;
+    entries.sort_by_key(std::fs::DirEntry::file_name);
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let relative = dir.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        if file_type.is_dir() {
+            files.extend(list_files(root, &relative)?);
+        } else if file_type.is_file() {
+            files.push(relative);
+        }
+    }
+    Ok(files)
+}
+
+/// Returns the names of all templates: the built-in one first, then the
+/// user templates in `user_dir` in alphabetical order.
+///
+/// A missing `user_dir` means there are no user templates.
+#[must_use]
+pub fn available_templates(user_dir: &Path) -> Vec<String> {
+    let mut user: Vec<String> = std::fs::read_dir(user_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| name != TEMPLATE_NAME)
+        .collect();
+    user.sort();
+
+    let mut names = vec![TEMPLATE_NAME.to_string()];
+    names.extend(user);
+    names
+}
+
+/// Finds the template called `name`.
+///
+/// Built-in templates take precedence over user templates in `user_dir`
+/// with the same name.
+///
+/// # Errors
+///
+/// Returns an error if there is no template called `name` or the user
+/// template cannot be loaded.
+pub fn get_template(name: &str, user_dir: &Path) -> Result<ProjectTemplate> {
+    if name == TEMPLATE_NAME {
+        return Ok(ProjectTemplate::Builtin);
+    }
+    let root = user_dir.join(name);
+    if !name.contains(['/', '\\']) && root.is_dir() {
+        return Ok(ProjectTemplate::Directory(DirectoryTemplate::load(&root)?));
+    }
+    Err(InfsError::invalid_arguments(format!(
+        "unknown template '{name}' (available: {})",
+        available_templates(user_dir).join(", ")
+    ))
+    .into())
+}
+
+/// Returns the current year, e.g. `2025`.
+fn current_year() -> String {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format_timestamp_iso8601(timestamp)[..4].to_string()
+}
+
+/// Returns `Name <email>` from the git configuration, if a name is set.
+///
+/// Returns `None` if git is not installed or `user.name` is not set.
+pub fn git_author() -> Option<String> {
+    let config = |key: &str| {
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .output()
+            .ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+    let name = config("user.name")?;
+    Some(format_author(&name, config("user.email").as_deref()))
+}
+
+/// Formats an author as `Name <email>`, or `Name` without an email.
+fn format_author(name: &str, email: Option<&str>) -> String {
+    match email {
+        Some(email) => format!("{name} <{email}>"),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    #[test]
+    fn format_author_adds_email_when_known() {
+        assert_eq!(
+            format_author("Ada Lovelace", Some("ada@example.com")),
+            "Ada Lovelace <ada@example.com>"
+        );
+        assert_eq!(format_author("Ada Lovelace", None), "Ada Lovelace");
+    }
+
+    #[test]
+    fn render_substitutes_provided_variables() {
+        let context = TemplateContext::new()
+            .with("project_name", "demo")
+            .with("year", "2025");
+        let rendered = context
+            .render("// {{project_name}} (c) {{ year }}\nname = \"{{project_name}}\"\n")
+            .unwrap();
+        assert_eq!(rendered, "// demo (c) 2025\nname = \"demo\"\n");
+        assert_eq!(
+            context.render("no placeholders").unwrap(),
+            "no placeholders"
+        );
+    }
+
+    #[test]
+    fn render_rejects_missing_variables() {
+        let context = TemplateContext::new().with("project_name", "demo");
+        let error = context
+            .render("{{project_name}} by {{author}}")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Unknown template variable 'author'");
+        let error = context.render("{{project_name").unwrap_err();
+        assert!(error.to_string().contains("Unclosed"));
+    }
+
+    #[test]
+    fn render_does_not_expand_substituted_values() {
+        let context = TemplateContext::new().with("author", "{{year}}");
+        assert_eq!(context.render("{{author}}").unwrap(), "{{year}}");
+    }
+
+    fn temp_templates(name: &str) -> PathBuf {
+        let dir = temp_test_dir(&format!("templates_{name}"));
+        std::fs::create_dir_all(dir.join("lib").join("src")).unwrap();
+        std::fs::write(
+            dir.join("lib").join("src").join("main.inf"),
+            "// {{project_name}} by {{author}}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("lib").join("logo.bin"), [0xff, 0xfe, b'{', b'{']).unwrap();
+        std::fs::create_dir_all(dir.join(TEMPLATE_NAME)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn directory_template_renders_text_and_copies_binary_files() {
+        let dir = temp_templates("render");
+        let ProjectTemplate::Directory(template) = get_template("lib", &dir).unwrap() else {
+            panic!("expected a directory template");
+        };
+        let context = TemplateContext::new()
+            .with("project_name", "demo")
+            .with("author", "Ada");
+
+        let files = template.files(&context).unwrap();
+        assert_eq!(
+            files,
+            [
+                (PathBuf::from("logo.bin"), vec![0xff, 0xfe, b'{', b'{']),
+                (
+                    Path::new("src").join("main.inf"),
+                    b"// demo by Ada\n".to_vec()
+                ),
+            ]
+        );
+
+        let error = template.files(&TemplateContext::new()).unwrap_err();
+        assert!(format!("{error:#}").contains("Unknown template variable 'project_name'"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn builtin_templates_take_precedence_over_user_templates() {
+        let dir = temp_templates("discover");
+
+        assert_eq!(available_templates(&dir), [TEMPLATE_NAME, "lib"]);
+        assert_eq!(
+            get_template(TEMPLATE_NAME, &dir).unwrap(),
+            ProjectTemplate::Builtin
+        );
+        let error = get_template("missing", &dir).unwrap_err();
+        assert!(error.to_string().contains("available: default, lib"));
+        assert_eq!(available_templates(&dir.join("missing")), [TEMPLATE_NAME]);
+
+        std::fs::write(dir.join("lib").join(MANIFEST_FILE), "").unwrap();
+        assert!(get_template("lib", &dir).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn project_context_has_standard_variables() {
+        let context = TemplateContext::for_project("demo");
+        assert_eq!(context.get("project_name"), Some("demo"));
+        assert_eq!(context.get("year").map(str::len), Some(4));
+        assert!(context.get("author").is_some_and(|a| !a.is_empty()));
+    }
+}
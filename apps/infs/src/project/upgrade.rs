@@ -0,0 +1,523 @@
+//! Template upgrades for existing projects.
+//!
+//! `infs new` and `infs init` record the template choices in the
+//! `[template]` section of `Inference.toml` and the SHA-256 hash of every
+//! file they generated in `.inference/scaffold.lock`. [`upgrade_project`]
+//! re-renders the template and compares each file with the one on disk:
+//!
+//! | On disk | Action |
+//! |---------|--------|
+//! | Identical to the new template | Left alone |
+//! | Unchanged since it was generated (hash matches the lock) | Replaced |
+//! | Missing and never generated | Created |
+//! | Edited or deleted, template unchanged since it was generated | Left alone, not reported |
+//! | Edited, deleted, or not recorded in the lock | Conflict, left alone |
+//!
+//! Files under `src/` are never written. Projects created before templates
+//! were recorded get a `[template]` section inferred from the files present.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+
+use super::manifest::{InferenceToml, MANIFEST_FILE, TemplateConfig};
+use super::scaffold::{
+    CiProvider, TEMPLATE_NAME, TEMPLATE_VERSION, template_files, template_section,
+    write_template_file,
+};
+
+/// Path of the scaffold lock, relative to the project root.
+pub const SCAFFOLD_LOCK_FILE: &str = ".inference/scaffold.lock";
+
+/// Top-level directories that an upgrade never writes to.
+const PROTECTED_DIRS: &[&str] = &["src"];
+
+/// Contents of `.inference/scaffold.lock`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScaffoldLock {
+    /// [`TEMPLATE_VERSION`] the files were last generated from.
+    pub template_version: u32,
+    /// SHA-256 of each generated file as written, keyed by relative path.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl ScaffoldLock {
+    /// Creates a lock recording `files` at the current template version.
+    #[must_use]
+    pub fn from_files(files: &[(&str, String)]) -> Self {
+        Self {
+            template_version: TEMPLATE_VERSION,
+            files: files
+                .iter()
+                .map(|(path, content)| ((*path).to_string(), content_hash(content)))
+                .collect(),
+        }
+    }
+
+    /// Reads the lock of the project at `project_path`.
+    ///
+    /// Returns `None` if the project has no lock.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock exists but cannot be read or parsed.
+    pub fn load(project_path: &Path) -> Result<Option<Self>> {
+        let path = project_path.join(SCAFFOLD_LOCK_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lock = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(lock))
+    }
+
+    /// Writes the lock into the project at `project_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock cannot be serialized or written.
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        let body = toml::to_string_pretty(self).context("Failed to serialize scaffold lock")?;
+        let content = format!(
+            "# Generated by infs. Records the files created from the project template\n\
+             # so `infs scaffold upgrade` can tell which ones were edited. Commit this file.\n\n\
+             {body}"
+        );
+        write_template_file(project_path, SCAFFOLD_LOCK_FILE, &content)
+    }
+}
+
+/// Why a template file was left unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictReason {
+    /// The file was edited after it was generated.
+    Modified,
+    /// The file was generated and later deleted.
+    Deleted,
+    /// The file exists but was not generated by infs.
+    Untracked,
+}
+
+impl ConflictReason {
+    /// Returns a short description for the conflict list.
+    #[must_use]
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Modified => "modified locally",
+            Self::Deleted => "deleted locally",
+            Self::Untracked => "not created by infs",
+        }
+    }
+}
+
+/// What an upgrade did with a template file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    /// The file did not exist and was created.
+    Created,
+    /// The file was unmodified and was replaced with the new template.
+    Updated,
+    /// The file already matches the template.
+    UpToDate,
+    /// The file differs from the template and was left unchanged.
+    Conflict(ConflictReason),
+}
+
+/// Outcome of an upgrade for one template file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the project root.
+    pub path: String,
+    /// What was done with the file.
+    pub action: FileAction,
+    /// Unified diff from the file on disk to the template, for conflicts.
+    pub diff: Option<String>,
+}
+
+/// Result of [`upgrade_project`].
+#[derive(Debug, Clone)]
+pub struct UpgradeReport {
+    /// Template version recorded in the lock before the upgrade, if any.
+    pub from_version: Option<u32>,
+    /// Whether the `[template]` section was inferred and added to `Inference.toml`.
+    pub inferred_template: bool,
+    /// Per-file outcome, in template order.
+    pub changes: Vec<FileChange>,
+}
+
+impl UpgradeReport {
+    /// Returns the changes that are conflicts.
+    pub fn conflicts(&self) -> impl Iterator<Item = &FileChange> {
+        self.changes
+            .iter()
+            .filter(|change| matches!(change.action, FileAction::Conflict(_)))
+    }
+
+    /// Counts changes with the given action.
+    #[must_use]
+    pub fn count(&self, action: FileAction) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+}
+
+/// Re-renders the template of the project at `project_path` and applies
+/// the changes to files the user has not modified.
+///
+/// The lock is rewritten with the hashes of the files now on disk that
+/// match the template.
+///
+/// # Errors
+///
+/// Returns an error if `Inference.toml` is missing or invalid, or if a file
+/// cannot be read or written.
+pub fn upgrade_project(project_path: &Path) -> Result<UpgradeReport> {
+    let manifest_path = project_path.join(MANIFEST_FILE);
+    let manifest = InferenceToml::from_file(&manifest_path)?;
+
+    let (template, inferred_template) = match manifest.template {
+        Some(template) => (template, false),
+        None => (infer_template(project_path), true),
+    };
+
+    let lock = ScaffoldLock::load(project_path)?;
+    let from_version = lock.as_ref().map(|lock| lock.template_version);
+    let mut lock = lock.unwrap_or_default();
+
+    let changes = apply_template(project_path, &mut lock, &template_files(&template))?;
+    lock.template_version = TEMPLATE_VERSION;
+    lock.save(project_path)?;
+
+    if inferred_template {
+        let mut content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+        content.push_str(&template_section(&template));
+        std::fs::write(&manifest_path, content)
+            .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    }
+
+    Ok(UpgradeReport {
+        from_version,
+        inferred_template,
+        changes,
+    })
+}
+
+/// Guesses the template of a project that does not record one.
+fn infer_template(project_path: &Path) -> TemplateConfig {
+    let git = project_path.join(".git").exists() || project_path.join(".gitignore").exists();
+    let ci = project_path
+        .join(".github/workflows/verify.yml")
+        .exists()
+        .then_some(CiProvider::Github);
+    TemplateConfig {
+        name: TEMPLATE_NAME.to_string(),
+        git,
+        ci,
+    }
+}
+
+/// Compares each rendered file with the one on disk and writes the files
+/// that are safe to replace, updating `lock` for every file that now
+/// matches the template.
+fn apply_template(
+    project_path: &Path,
+    lock: &mut ScaffoldLock,
+    files: &[(&str, String)],
+) -> Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+
+    for (relative_path, content) in files {
+        if is_protected(relative_path) {
+            continue;
+        }
+        let path = project_path.join(relative_path);
+        let recorded = lock.files.get(*relative_path).cloned();
+        let current = if path.exists() {
+            Some(
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            )
+        } else {
+            None
+        };
+
+        // The user's edit or deletion stands while the template is unchanged.
+        let template_unchanged = recorded.as_deref() == Some(content_hash(content).as_str());
+        if template_unchanged && current.as_ref() != Some(content) {
+            continue;
+        }
+
+        let action = match (&current, &recorded) {
+            (Some(current), _) if current == content => FileAction::UpToDate,
+            (Some(current), Some(recorded)) if content_hash(current) == *recorded => {
+                FileAction::Updated
+            }
+            (Some(_), Some(_)) => FileAction::Conflict(ConflictReason::Modified),
+            (Some(_), None) => FileAction::Conflict(ConflictReason::Untracked),
+            (None, Some(_)) => FileAction::Conflict(ConflictReason::Deleted),
+            (None, None) => FileAction::Created,
+        };
+
+        let diff = match action {
+            FileAction::Conflict(_) => Some(unified_diff(
+                relative_path,
+                current.as_deref().unwrap_or_default(),
+                content,
+            )),
+            FileAction::Created | FileAction::Updated => {
+                write_template_file(project_path, relative_path, content)?;
+                None
+            }
+            FileAction::UpToDate => None,
+        };
+        if !matches!(action, FileAction::Conflict(_)) {
+            lock.files
+                .insert((*relative_path).to_string(), content_hash(content));
+        }
+
+        changes.push(FileChange {
+            path: (*relative_path).to_string(),
+            action,
+            diff,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Returns `true` if `relative_path` is inside a directory upgrades never touch.
+fn is_protected(relative_path: &str) -> bool {
+    relative_path
+        .split('/')
+        .next()
+        .is_some_and(|first| PROTECTED_DIRS.contains(&first))
+}
+
+/// Returns the hex SHA-256 of `content`.
+fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Returns a unified diff from `old` to `new` for `path`.
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_project() -> PathBuf {
+        let dir = temp_test_dir("upgrade");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("src/main.inf"),
+            "pub fn main() -> i32 { return 0; }\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    /// Scaffolds `old` files into a project and records them in the lock.
+    fn scaffold(project: &Path, old: &[(&str, String)]) -> ScaffoldLock {
+        for (path, content) in old {
+            write_template_file(project, path, content).unwrap();
+        }
+        let lock = ScaffoldLock::from_files(old);
+        lock.save(project).unwrap();
+        lock
+    }
+
+    #[test]
+    fn upgrade_updates_unmodified_files_and_reports_edited_ones() {
+        let project = temp_project();
+        let old = vec![
+            (".gitignore", "/out/\n".to_string()),
+            ("ci.yml", "steps: [build]\n".to_string()),
+        ];
+        let mut lock = scaffold(&project, &old);
+        fs::write(project.join(".gitignore"), "/out/\n/my-notes/\n").unwrap();
+
+        let new = vec![
+            (".gitignore", "/out/\n/target/\n".to_string()),
+            ("ci.yml", "steps: [build, verify]\n".to_string()),
+        ];
+        let changes = apply_template(&project, &mut lock, &new).unwrap();
+
+        assert_eq!(changes[0].path, ".gitignore");
+        assert_eq!(
+            changes[0].action,
+            FileAction::Conflict(ConflictReason::Modified)
+        );
+        assert_eq!(
+            fs::read_to_string(project.join(".gitignore")).unwrap(),
+            "/out/\n/my-notes/\n"
+        );
+        let diff = changes[0].diff.as_deref().unwrap();
+        assert!(diff.contains("--- a/.gitignore"));
+        assert!(diff.contains("-/my-notes/"));
+        assert!(diff.contains("+/target/"));
+
+        assert_eq!(changes[1].action, FileAction::Updated);
+        assert_eq!(
+            fs::read_to_string(project.join("ci.yml")).unwrap(),
+            "steps: [build, verify]\n"
+        );
+        assert_eq!(lock.files["ci.yml"], content_hash(&new[1].1));
+        assert_eq!(lock.files[".gitignore"], content_hash(&old[0].1));
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn upgrade_creates_new_files_and_respects_deletions() {
+        let project = temp_project();
+        let mut lock = scaffold(&project, &[("tests/.gitkeep", String::new())]);
+        fs::remove_file(project.join("tests/.gitkeep")).unwrap();
+        fs::write(project.join("extra.yml"), "mine\n").unwrap();
+
+        let new = vec![
+            ("tests/.gitkeep", "# keep\n".to_string()),
+            ("proofs/.gitkeep", String::new()),
+            ("extra.yml", "template\n".to_string()),
+        ];
+        let changes = apply_template(&project, &mut lock, &new).unwrap();
+        let actions = changes.iter().map(|c| c.action).collect::<Vec<_>>();
+
+        assert_eq!(
+            actions,
+            vec![
+                FileAction::Conflict(ConflictReason::Deleted),
+                FileAction::Created,
+                FileAction::Conflict(ConflictReason::Untracked),
+            ]
+        );
+        assert!(!project.join("tests/.gitkeep").exists());
+        assert!(project.join("proofs/.gitkeep").exists());
+        assert_eq!(
+            fs::read_to_string(project.join("extra.yml")).unwrap(),
+            "mine\n"
+        );
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn upgrade_ignores_edits_and_deletions_when_template_is_unchanged() {
+        let project = temp_project();
+        let files = vec![
+            (".gitignore", "/out/\n".to_string()),
+            ("tests/.gitkeep", String::new()),
+        ];
+        let mut lock = scaffold(&project, &files);
+        fs::write(project.join(".gitignore"), "/out/\n/my-notes/\n").unwrap();
+        fs::remove_file(project.join("tests/.gitkeep")).unwrap();
+
+        let changes = apply_template(&project, &mut lock, &files).unwrap();
+
+        assert!(changes.is_empty());
+        assert_eq!(
+            fs::read_to_string(project.join(".gitignore")).unwrap(),
+            "/out/\n/my-notes/\n"
+        );
+        assert!(!project.join("tests/.gitkeep").exists());
+        assert_eq!(lock, ScaffoldLock::from_files(&files));
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn upgrade_never_writes_to_src() {
+        let project = temp_project();
+        let mut lock = ScaffoldLock::default();
+        let new = vec![("src/main.inf", "replaced\n".to_string())];
+
+        let changes = apply_template(&project, &mut lock, &new).unwrap();
+
+        assert!(changes.is_empty());
+        assert!(
+            fs::read_to_string(project.join("src/main.inf"))
+                .unwrap()
+                .contains("fn main")
+        );
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn upgrade_of_fresh_project_changes_nothing() {
+        let parent = temp_project();
+        let project = crate::project::create_project(
+            "fresh",
+            Some(&parent),
+            crate::project::Vcs::None,
+            Some(CiProvider::Github),
+        )
+        .unwrap();
+
+        let report = upgrade_project(&project).unwrap();
+
+        assert_eq!(report.from_version, Some(TEMPLATE_VERSION));
+        assert!(!report.inferred_template);
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.count(FileAction::UpToDate), 1);
+        assert_eq!(report.conflicts().count(), 0);
+
+        fs::remove_dir_all(&parent).ok();
+    }
+
+    #[test]
+    fn upgrade_infers_template_for_old_projects() {
+        let project = temp_project();
+        let manifest = InferenceToml::new("legacy");
+        fs::write(project.join(MANIFEST_FILE), manifest.to_toml().unwrap()).unwrap();
+        fs::write(project.join(".gitignore"), "/out/\n").unwrap();
+
+        let report = upgrade_project(&project).unwrap();
+
+        assert!(report.inferred_template);
+        assert_eq!(report.from_version, None);
+        assert_eq!(
+            report
+                .changes
+                .iter()
+                .map(|c| (c.path.as_str(), c.action))
+                .collect::<Vec<_>>(),
+            vec![
+                ("tests/.gitkeep", FileAction::Created),
+                ("proofs/.gitkeep", FileAction::Created),
+                (
+                    ".gitignore",
+                    FileAction::Conflict(ConflictReason::Untracked)
+                ),
+            ]
+        );
+        let manifest = InferenceToml::from_file(&project.join(MANIFEST_FILE)).unwrap();
+        let template = manifest.template.unwrap();
+        assert!(template.git);
+        assert_eq!(template.ci, None);
+        let lock = ScaffoldLock::load(&project).unwrap().unwrap();
+        assert_eq!(lock.template_version, TEMPLATE_VERSION);
+        assert!(lock.files.contains_key("tests/.gitkeep"));
+        assert!(!lock.files.contains_key(".gitignore"));
+
+        fs::remove_dir_all(&project).ok();
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers shared by the unit tests.
+
+use std::path::PathBuf;
+
+/// Creates a fresh directory under the system temp directory, named after
+/// `name` with a random suffix so tests running in parallel do not collide.
+pub fn temp_test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("infs_test_{}_{}", name, rand::random::<u64>()));
+    std::fs::create_dir_all(&dir).expect("Should create temp dir");
+    dir
+}
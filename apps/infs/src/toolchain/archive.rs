@@ -2,12 +2,19 @@
 //!
 //! This module provides functionality for extracting ZIP and tar.gz archives
 //! used during toolchain and self-update installations.
+//!
+//! Extraction progress can be observed with an [`ExtractProgressCallback`],
+//! which is invoked once per archive entry.
 
 use anyhow::{Context, Result};
 use flate2::read::GzDecoder;
 use std::path::{Path, PathBuf};
 use tar::Archive;
 
+/// Callback receiving `(entries_processed, total_entries)` after each
+/// archive entry is handled.
+pub type ExtractProgressCallback<'a> = &'a dyn Fn(u64, u64);
+
 /// Extracts a ZIP archive to the destination directory.
 ///
 /// Creates the destination directory if it does not exist.
@@ -29,6 +36,23 @@ use tar::Archive;
 /// extract_zip(Path::new("archive.zip"), Path::new("output_dir"))?;
 /// ```
 pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    extract_zip_with_progress(archive_path, dest_dir, None)
+}
+
+/// Extracts a ZIP archive, reporting progress once per entry.
+///
+/// The total is the number of entries in the ZIP central directory. If the
+/// ZIP wraps a single tar.gz archive, the nested extraction reports its own
+/// entries afterwards.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`extract_zip`].
+pub fn extract_zip_with_progress(
+    archive_path: &Path,
+    dest_dir: &Path,
+    progress: Option<ExtractProgressCallback>,
+) -> Result<()> {
     let file = std::fs::File::open(archive_path)
         .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
 
@@ -37,10 +61,18 @@ pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
 
     std::fs::create_dir_all(dest_dir)
         .with_context(|| format!("Failed to create directory: {}", dest_dir.display()))?;
+    let dest_root = canonical_dest_dir(dest_dir)?;
 
     let strip_prefix = find_common_root_folder(&mut archive);
+    let total = archive.len() as u64;
+    let report = |processed: u64| {
+        if let Some(progress) = progress {
+            progress(processed, total);
+        }
+    };
 
     for i in 0..archive.len() {
+        let processed = i as u64 + 1;
         let mut entry = archive
             .by_index(i)
             .with_context(|| format!("Failed to read archive entry {i}"))?;
@@ -50,21 +82,14 @@ pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
             .with_context(|| format!("Invalid entry path in archive: entry {i}"))?;
 
         // Security: defense-in-depth check (enclosed_name already filters these)
-        // Reject paths with parent directory references or absolute paths
-        if entry_path.is_absolute()
-            || entry_path
-                .components()
-                .any(|c| matches!(c, std::path::Component::ParentDir))
-        {
-            anyhow::bail!(
-                "Refusing to extract path with parent directory or absolute reference: {}",
-                entry_path.display()
-            );
-        }
+        reject_unsafe_path(&entry_path)?;
 
         let relative_path = if let Some(ref prefix) = strip_prefix {
             match entry_path.strip_prefix(prefix) {
-                Ok(p) if p.as_os_str().is_empty() => continue,
+                Ok(p) if p.as_os_str().is_empty() => {
+                    report(processed);
+                    continue;
+                }
                 Ok(p) => p.to_path_buf(),
                 Err(_) => entry_path.clone(),
             }
@@ -73,6 +98,7 @@ pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
         };
 
         let output_path = dest_dir.join(&relative_path);
+        ensure_within_dest(&dest_root, &output_path)?;
 
         if entry.is_dir() {
             std::fs::create_dir_all(&output_path).with_context(|| {
@@ -90,10 +116,12 @@ pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
             std::io::copy(&mut entry, &mut outfile)
                 .with_context(|| format!("Failed to extract: {}", output_path.display()))?;
         }
+
+        report(processed);
     }
 
     // After extraction, check for nested tar.gz archive
-    extract_nested_tar_gz_if_present(dest_dir)?;
+    extract_nested_tar_gz_if_present(dest_dir, progress)?;
 
     Ok(())
 }
@@ -103,7 +131,10 @@ pub fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
 /// This handles GitHub releases that wrap tar.gz archives in ZIP files.
 /// If `dest_dir` contains only a `.tar.gz` file (plus optional `.sha256`),
 /// extracts the tar.gz and removes the archive files.
-fn extract_nested_tar_gz_if_present(dest_dir: &Path) -> Result<()> {
+fn extract_nested_tar_gz_if_present(
+    dest_dir: &Path,
+    progress: Option<ExtractProgressCallback>,
+) -> Result<()> {
     let entries: Vec<_> = std::fs::read_dir(dest_dir)
         .with_context(|| format!("Failed to read directory: {}", dest_dir.display()))?
         .filter_map(Result::ok)
@@ -143,7 +174,7 @@ fn extract_nested_tar_gz_if_present(dest_dir: &Path) -> Result<()> {
     }
 
     // Extract the nested tar.gz
-    extract_tar_gz(&tar_gz_path, dest_dir)?;
+    extract_tar_gz_with_progress(&tar_gz_path, dest_dir, progress)?;
 
     // Clean up the archive files
     std::fs::remove_file(&tar_gz_path).ok();
@@ -175,20 +206,55 @@ fn extract_nested_tar_gz_if_present(dest_dir: &Path) -> Result<()> {
 /// extract_archive(Path::new("archive.zip"), Path::new("output_dir"))?;
 /// ```
 pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<()> {
-    let path_str = archive_path.to_string_lossy();
-    if path_str.ends_with(".tar.gz") || path_str.ends_with(".tgz") {
+    if is_tar_gz(archive_path) {
         extract_tar_gz(archive_path, dest_dir)
     } else {
         extract_zip(archive_path, dest_dir)
     }
 }
 
+/// Extracts an archive (ZIP or tar.gz), reporting progress once per entry.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`extract_archive`].
+pub fn extract_archive_with_progress(
+    archive_path: &Path,
+    dest_dir: &Path,
+    progress: Option<ExtractProgressCallback>,
+) -> Result<()> {
+    if is_tar_gz(archive_path) {
+        extract_tar_gz_with_progress(archive_path, dest_dir, progress)
+    } else {
+        extract_zip_with_progress(archive_path, dest_dir, progress)
+    }
+}
+
+/// Returns true if the archive path has a `.tar.gz` or `.tgz` extension.
+fn is_tar_gz(archive_path: &Path) -> bool {
+    let path_str = archive_path.to_string_lossy();
+    path_str.ends_with(".tar.gz") || path_str.ends_with(".tgz")
+}
+
+/// Returns true if extracting the archive restores file modes.
+///
+/// tar archives carry Unix mode bits, which [`extract_tar_gz`] applies on
+/// Unix. ZIP archives do not, so [`set_executable_permissions`] must be run
+/// after extracting them.
+pub fn preserves_permissions(archive_path: &Path) -> bool {
+    cfg!(unix) && is_tar_gz(archive_path)
+}
+
 /// Extracts a tar.gz archive to the destination directory.
 ///
 /// Creates the destination directory if it does not exist.
 /// If all archive entries share a common root folder, it is automatically
 /// stripped during extraction (e.g., `toolchain-0.2.0/bin/infc` becomes `bin/infc`).
 ///
+/// On Unix, the permission bits stored in each entry are applied to the
+/// extracted file, so executables keep their `+x` bit. setuid, setgid, and
+/// sticky bits are not restored.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -204,15 +270,41 @@ pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<()> {
 /// extract_tar_gz(Path::new("archive.tar.gz"), Path::new("output_dir"))?;
 /// ```
 pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    extract_tar_gz_with_progress(archive_path, dest_dir, None)
+}
+
+/// Extracts a tar.gz archive, reporting progress once per entry.
+///
+/// tar has no index, so the total comes from the scan that detects the
+/// common root folder before extraction starts.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`extract_tar_gz`].
+pub fn extract_tar_gz_with_progress(
+    archive_path: &Path,
+    dest_dir: &Path,
+    progress: Option<ExtractProgressCallback>,
+) -> Result<()> {
     std::fs::create_dir_all(dest_dir)
         .with_context(|| format!("Failed to create directory: {}", dest_dir.display()))?;
-
-    let strip_prefix = find_common_root_folder_tar(archive_path)?;
+    let dest_root = canonical_dest_dir(dest_dir)?;
+
+    let (strip_prefix, total) = scan_tar_gz(archive_path)?;
+    let mut processed = 0;
+    let mut report = || {
+        processed += 1;
+        if let Some(progress) = progress {
+            progress(processed, total);
+        }
+    };
 
     let file = std::fs::File::open(archive_path)
         .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
     let decoder = GzDecoder::new(file);
     let mut archive = Archive::new(decoder);
+    // Apply each entry's 0o777 mode bits, without setuid/setgid/sticky.
+    archive.set_preserve_permissions(false);
 
     for entry in archive
         .entries()
@@ -226,22 +318,14 @@ pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
             .with_context(|| "Failed to get entry path")?
             .into_owned();
 
-        // Security: reject paths with parent directory references or absolute paths
-        // to prevent path traversal attacks (e.g., "../../../etc/passwd")
-        if entry_path.is_absolute()
-            || entry_path
-                .components()
-                .any(|c| matches!(c, std::path::Component::ParentDir))
-        {
-            anyhow::bail!(
-                "Refusing to extract path with parent directory or absolute reference: {}",
-                entry_path.display()
-            );
-        }
+        reject_unsafe_path(&entry_path)?;
 
         let relative_path = if let Some(ref prefix) = strip_prefix {
             match entry_path.strip_prefix(prefix) {
-                Ok(p) if p.as_os_str().is_empty() => continue,
+                Ok(p) if p.as_os_str().is_empty() => {
+                    report();
+                    continue;
+                }
                 Ok(p) => p.to_path_buf(),
                 Err(_) => entry_path.clone(),
             }
@@ -250,8 +334,28 @@ pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
         };
 
         let output_path = dest_dir.join(&relative_path);
+        ensure_within_dest(&dest_root, &output_path)?;
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let link = entry
+                .link_name()
+                .with_context(|| "Failed to get link target")?
+                .unwrap_or_default()
+                .into_owned();
+            // Symlinks resolve relative to their own directory; hard links
+            // are unpacked as given, so they must stay inside the entry's
+            // directory as well.
+            if link_escapes(&relative_path, &link) {
+                anyhow::bail!(
+                    "Refusing to extract link pointing outside the destination directory: {} -> {}",
+                    entry_path.display(),
+                    link.display()
+                );
+            }
+        }
 
-        if entry.header().entry_type().is_dir() {
+        if entry_type.is_dir() {
             std::fs::create_dir_all(&output_path).with_context(|| {
                 format!("Failed to create directory: {}", output_path.display())
             })?;
@@ -265,20 +369,98 @@ pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
                 .unpack(&output_path)
                 .with_context(|| format!("Failed to extract: {}", output_path.display()))?;
         }
+
+        report();
     }
 
     Ok(())
 }
 
-/// Finds a common root folder shared by all tar.gz archive entries.
+/// Rejects archive entry paths with parent directory references or absolute
+/// paths, preventing path traversal attacks (e.g., `../../../etc/passwd`).
 ///
-/// Returns `Some(prefix)` if all entries start with the same folder name
-/// AND there are nested entries (paths with more than one component).
-/// Otherwise returns `None`.
+/// # Errors
+///
+/// Returns an error naming the entry if its path is unsafe.
+pub(crate) fn reject_unsafe_path(entry_path: &Path) -> Result<()> {
+    if entry_path.is_absolute()
+        || entry_path
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        anyhow::bail!(
+            "Refusing to extract path with parent directory or absolute reference: {}",
+            entry_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Canonicalizes the destination directory for containment checks.
+pub(crate) fn canonical_dest_dir(dest_dir: &Path) -> Result<PathBuf> {
+    dest_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve directory: {}", dest_dir.display()))
+}
+
+/// Ensures an output path resolves inside the canonical destination directory.
+///
+/// The deepest existing ancestor of `output_path` is canonicalized, so
+/// symlinks created by earlier archive entries cannot redirect writes
+/// outside the destination.
+///
+/// # Errors
+///
+/// Returns an error if the path would resolve outside `dest_root`.
+pub(crate) fn ensure_within_dest(dest_root: &Path, output_path: &Path) -> Result<()> {
+    let resolved = output_path
+        .ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok());
+
+    match resolved {
+        Some(resolved) if resolved.starts_with(dest_root) => Ok(()),
+        _ => anyhow::bail!(
+            "Refusing to extract path outside the destination directory: {}",
+            output_path.display()
+        ),
+    }
+}
+
+/// Returns true if a link target would leave the destination directory.
+///
+/// `relative_path` is the link entry's path inside the destination; the
+/// target is resolved lexically from the entry's parent directory.
+fn link_escapes(relative_path: &Path, link: &Path) -> bool {
+    let mut depth = relative_path
+        .parent()
+        .map_or(0, |parent| parent.components().count());
+
+    for component in link.components() {
+        match component {
+            std::path::Component::Normal(_) => depth += 1,
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if depth == 0 {
+                    return true;
+                }
+                depth -= 1;
+            }
+            std::path::Component::RootDir | std::path::Component::Prefix(_) => return true,
+        }
+    }
+
+    false
+}
+
+/// Scans a tar.gz archive for its common root folder and entry count.
+///
+/// The root folder is `Some(prefix)` if all entries start with the same
+/// folder name AND there are nested entries (paths with more than one
+/// component). Otherwise it is `None`.
 ///
 /// This prevents flat files at the archive root from being incorrectly
 /// treated as "common root folders" and stripped away.
-fn find_common_root_folder_tar(archive_path: &Path) -> Result<Option<PathBuf>> {
+fn scan_tar_gz(archive_path: &Path) -> Result<(Option<PathBuf>, u64)> {
     let file = std::fs::File::open(archive_path)
         .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
 
@@ -286,7 +468,9 @@ fn find_common_root_folder_tar(archive_path: &Path) -> Result<Option<PathBuf>> {
     let mut archive = Archive::new(decoder);
 
     let mut common_root: Option<PathBuf> = None;
+    let mut roots_differ = false;
     let mut has_nested_entries = false;
+    let mut entry_count = 0;
 
     for entry in archive
         .entries()
@@ -294,6 +478,11 @@ fn find_common_root_folder_tar(archive_path: &Path) -> Result<Option<PathBuf>> {
     {
         let entry = entry
             .with_context(|| format!("Failed to read tar entry: {}", archive_path.display()))?;
+        entry_count += 1;
+
+        if roots_differ {
+            continue;
+        }
 
         let path = entry.path().with_context(|| "Failed to get entry path")?;
 
@@ -309,17 +498,17 @@ fn find_common_root_folder_tar(archive_path: &Path) -> Result<Option<PathBuf>> {
 
         match &common_root {
             None => common_root = Some(root),
-            Some(existing) if existing != &root => return Ok(None),
+            Some(existing) if existing != &root => roots_differ = true,
             Some(_) => {}
         }
     }
 
     // Only strip common root if there are nested entries
     // (root is actually a containing folder, not just a flat file)
-    if has_nested_entries {
-        Ok(common_root)
+    if has_nested_entries && !roots_differ {
+        Ok((common_root, entry_count))
     } else {
-        Ok(None)
+        Ok((None, entry_count))
     }
 }
 
@@ -440,19 +629,12 @@ pub fn set_executable_permissions(_dir: &Path) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
     use flate2::Compression;
     use flate2::write::GzEncoder;
     use std::io::Write;
     use tar::Builder;
 
-    /// Creates a temporary test directory with a unique name.
-    fn temp_test_dir(name: &str) -> PathBuf {
-        let dir =
-            std::env::temp_dir().join(format!("infs_test_{}_{}", name, rand::random::<u64>()));
-        std::fs::create_dir_all(&dir).expect("Should create temp dir");
-        dir
-    }
-
     /// Creates a tar.gz archive with files nested under a root folder.
     fn create_tar_gz_with_root(archive_path: &Path, root_name: &str) {
         let file = std::fs::File::create(archive_path).expect("Should create file");
@@ -1001,10 +1183,228 @@ mod tests {
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
 
-    // Note: The `tar` crate itself prevents creating archives with `..` paths,
-    // so we cannot easily test path traversal rejection. The security check in
-    // extract_tar_gz provides defense-in-depth for archives from untrusted sources.
-    // ZIP's `enclosed_name()` method also provides similar protection.
+    /// Appends a tar entry with a raw name, bypassing the `..` validation
+    /// performed by `Builder::append_data`.
+    fn append_raw_tar_entry<W: Write>(
+        builder: &mut Builder<W>,
+        name: &str,
+        entry_type: tar::EntryType,
+        link: Option<&str>,
+        data: &[u8],
+    ) {
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+        if let Some(link) = link {
+            header.as_old_mut().linkname[..link.len()].copy_from_slice(link.as_bytes());
+        }
+        header.set_entry_type(entry_type);
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, data).expect("Should append entry");
+    }
+
+    #[test]
+    fn extract_tar_gz_rejects_parent_dir_entry() {
+        let temp_dir = temp_test_dir("tar_gz_traversal");
+        let archive_path = temp_dir.join("evil.tar.gz");
+        let dest_dir = temp_dir.join("nested").join("output");
+
+        {
+            let file = std::fs::File::create(&archive_path).expect("Should create file");
+            let encoder = GzEncoder::new(file, Compression::default());
+            let mut builder = Builder::new(encoder);
+            append_raw_tar_entry(
+                &mut builder,
+                "../../evil",
+                tar::EntryType::Regular,
+                None,
+                b"pwned",
+            );
+            builder.into_inner().expect("Should finish encoder");
+        }
+
+        let err = extract_tar_gz(&archive_path, &dest_dir).expect_err("Should reject entry");
+        assert!(err.to_string().contains("Refusing to extract"));
+        assert!(!temp_dir.join("evil").exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_tar_gz_rejects_symlink_escaping_destination() {
+        let temp_dir = temp_test_dir("tar_gz_symlink_escape");
+        let archive_path = temp_dir.join("evil.tar.gz");
+        let dest_dir = temp_dir.join("output");
+
+        {
+            let file = std::fs::File::create(&archive_path).expect("Should create file");
+            let encoder = GzEncoder::new(file, Compression::default());
+            let mut builder = Builder::new(encoder);
+            append_raw_tar_entry(
+                &mut builder,
+                "bin/escape",
+                tar::EntryType::Symlink,
+                Some("../.."),
+                b"",
+            );
+            builder.into_inner().expect("Should finish encoder");
+        }
+
+        let err = extract_tar_gz(&archive_path, &dest_dir).expect_err("Should reject link");
+        assert!(err.to_string().contains("outside the destination"));
+        assert!(!dest_dir.join("bin").join("escape").exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_tar_gz_rejects_write_through_existing_symlink() {
+        let temp_dir = temp_test_dir("tar_gz_symlink_write");
+        let archive_path = temp_dir.join("evil.tar.gz");
+        let dest_dir = temp_dir.join("output");
+        let outside_dir = temp_dir.join("outside");
+        std::fs::create_dir_all(&dest_dir).expect("Should create dest dir");
+        std::fs::create_dir_all(&outside_dir).expect("Should create outside dir");
+        std::os::unix::fs::symlink(&outside_dir, dest_dir.join("lib"))
+            .expect("Should create symlink");
+
+        {
+            let file = std::fs::File::create(&archive_path).expect("Should create file");
+            let encoder = GzEncoder::new(file, Compression::default());
+            let mut builder = Builder::new(encoder);
+            append_raw_tar_entry(
+                &mut builder,
+                "bin/infc",
+                tar::EntryType::Regular,
+                None,
+                b"binary",
+            );
+            append_raw_tar_entry(
+                &mut builder,
+                "lib/evil.so",
+                tar::EntryType::Regular,
+                None,
+                b"pwned",
+            );
+            builder.into_inner().expect("Should finish encoder");
+        }
+
+        let err = extract_tar_gz(&archive_path, &dest_dir).expect_err("Should reject entry");
+        assert!(err.to_string().contains("outside the destination"));
+        assert!(!outside_dir.join("evil.so").exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn extract_zip_rejects_parent_dir_entry() {
+        let temp_dir = temp_test_dir("zip_traversal");
+        let archive_path = temp_dir.join("evil.zip");
+        let dest_dir = temp_dir.join("nested").join("output");
+
+        {
+            let file = std::fs::File::create(&archive_path).expect("Should create file");
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default();
+            zip.start_file("../../evil", options)
+                .expect("Should start file");
+            zip.write_all(b"pwned").expect("Should write");
+            zip.finish().expect("Should finish");
+        }
+
+        assert!(extract_zip(&archive_path, &dest_dir).is_err());
+        assert!(!temp_dir.join("evil").exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn extract_zip_reports_progress_once_per_entry() {
+        let temp_dir = temp_test_dir("zip_progress");
+        let archive_path = temp_dir.join("test.zip");
+        let dest_dir = temp_dir.join("output");
+
+        {
+            let file = std::fs::File::create(&archive_path).expect("Should create file");
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default();
+            zip.add_directory("bin/", options)
+                .expect("Should add directory");
+            zip.start_file("bin/infc", options)
+                .expect("Should start file");
+            zip.write_all(b"binary content").expect("Should write");
+            zip.start_file("README.md", options)
+                .expect("Should start file");
+            zip.write_all(b"readme").expect("Should write");
+            zip.finish().expect("Should finish");
+        }
+
+        let events = std::cell::RefCell::new(Vec::new());
+        let progress = |processed, total| events.borrow_mut().push((processed, total));
+        extract_zip_with_progress(&archive_path, &dest_dir, Some(&progress))
+            .expect("Should extract");
+
+        assert_eq!(events.into_inner(), vec![(1, 3), (2, 3), (3, 3)]);
+        assert!(dest_dir.join("bin").join("infc").exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn extract_tar_gz_reports_progress_once_per_entry() {
+        let temp_dir = temp_test_dir("tar_gz_progress");
+        let archive_path = temp_dir.join("test.tar.gz");
+        let dest_dir = temp_dir.join("output");
+        create_tar_gz_like_ci_infc_toolchain(&archive_path);
+
+        let events = std::cell::RefCell::new(Vec::new());
+        let progress = |processed, total| events.borrow_mut().push((processed, total));
+        extract_archive_with_progress(&archive_path, &dest_dir, Some(&progress))
+            .expect("Should extract");
+
+        // Two directory entries and three files
+        let expected: Vec<(u64, u64)> = (1..=5).map(|i| (i, 5)).collect();
+        assert_eq!(events.into_inner(), expected);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn scan_tar_gz_counts_entries_with_different_roots() {
+        let temp_dir = temp_test_dir("tar_gz_scan");
+        let archive_path = temp_dir.join("test.tar.gz");
+        create_tar_gz_without_root(&archive_path);
+
+        let (root, count) = scan_tar_gz(&archive_path).expect("Should scan");
+        assert_eq!(root, None);
+        assert_eq!(count, 2);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn link_escapes_detects_targets_outside_destination() {
+        assert!(!link_escapes(
+            Path::new("lib/libLLVM.so"),
+            Path::new("libLLVM.so.18")
+        ));
+        assert!(!link_escapes(
+            Path::new("bin/infc"),
+            Path::new("../lib/infc")
+        ));
+        assert!(link_escapes(
+            Path::new("bin/infc"),
+            Path::new("../../etc/passwd")
+        ));
+        assert!(link_escapes(Path::new("infc"), Path::new("../infc")));
+        assert!(link_escapes(
+            Path::new("bin/infc"),
+            Path::new("/usr/bin/infc")
+        ));
+    }
 
     #[test]
     fn extract_tar_gz_empty_archive() {
@@ -1066,6 +1466,37 @@ mod tests {
         use super::*;
         use std::os::unix::fs::PermissionsExt;
 
+        #[test]
+        fn extract_tar_gz_applies_entry_modes() {
+            let temp_dir = temp_test_dir("tar_gz_modes");
+            let archive_path = temp_dir.join("test.tar.gz");
+            let dest_dir = temp_dir.join("output");
+            create_tar_gz_with_root(&archive_path, "toolchain");
+
+            extract_tar_gz(&archive_path, &dest_dir).expect("Should extract");
+
+            let infc_mode = std::fs::metadata(dest_dir.join("bin").join("infc"))
+                .expect("Should get metadata")
+                .permissions()
+                .mode();
+            let lib_mode = std::fs::metadata(dest_dir.join("lib").join("libLLVM.so"))
+                .expect("Should get metadata")
+                .permissions()
+                .mode();
+
+            assert_eq!(infc_mode & 0o777, 0o755, "infc should be executable");
+            assert_eq!(lib_mode & 0o777, 0o644, "library should keep its mode");
+
+            let _ = std::fs::remove_dir_all(&temp_dir);
+        }
+
+        #[test]
+        fn preserves_permissions_only_for_tar_gz() {
+            assert!(preserves_permissions(Path::new("infc-linux-x64.tar.gz")));
+            assert!(preserves_permissions(Path::new("infc-linux-x64.tgz")));
+            assert!(!preserves_permissions(Path::new("infc-windows-x64.zip")));
+        }
+
         #[test]
         fn set_executable_permissions_sets_755_on_bin_files() {
             let temp_dir = temp_test_dir("exec_perm_bin");
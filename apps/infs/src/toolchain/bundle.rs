@@ -0,0 +1,559 @@
+//! Toolchain bundles for machines without network access.
+//!
+//! `infs toolchain export` packs an installed toolchain into a single
+//! zstd-compressed tar archive, which `infs toolchain import` installs on
+//! another machine of the same platform. A bundle contains:
+//!
+//! ```text
+//! bundle.json            # Bundle manifest: version, platform, file hashes
+//! toolchain/infc         # The toolchain directory, without .metadata.json
+//! toolchain/bin/inf-llc
+//! toolchain/bin/rust-lld
+//! ```
+//!
+//! The manifest lists every file with its SHA256 in the style of the release
+//! manifest, and is written first so an import learns the version before
+//! extracting anything:
+//!
+//! ```json
+//! {
+//!   "format": 1,
+//!   "version": "0.1.0",
+//!   "platform": "linux-x64",
+//!   "signature": "verified",
+//!   "files": [
+//!     { "path": "infc", "sha256": "abc123..." }
+//!   ]
+//! }
+//! ```
+//!
+//! Imports are extracted with the same path traversal checks as release
+//! archives, and a file that is not listed, does not match its hash, or is
+//! missing rejects the whole bundle.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use tar::{Archive, Builder, EntryType, Header};
+
+use crate::errors::InfsError;
+use crate::toolchain::archive::{
+    ExtractProgressCallback, canonical_dest_dir, ensure_within_dest, reject_unsafe_path,
+};
+use crate::toolchain::signature::SignatureStatus;
+use crate::toolchain::verify::{compute_sha256, is_valid_sha256, verify_checksum};
+use crate::toolchain::{Platform, ToolchainPaths};
+
+/// File extension of toolchain bundles.
+pub const BUNDLE_EXTENSION: &str = "tar.zst";
+
+/// Name of the manifest entry at the start of a bundle.
+const MANIFEST_ENTRY: &str = "bundle.json";
+
+/// Directory holding the toolchain files inside a bundle.
+const TOOLCHAIN_DIR: &str = "toolchain";
+
+/// Bundle layout version written by this version of infs.
+const BUNDLE_FORMAT: u32 = 1;
+
+/// zstd compression level; the library default.
+const COMPRESSION_LEVEL: i32 = 0;
+
+/// Manifest describing the contents of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle layout version.
+    pub format: u32,
+    /// Toolchain version the bundle installs.
+    pub version: String,
+    /// Platform the toolchain binaries were built for (e.g. `linux-x64`).
+    pub platform: String,
+    /// Outcome of the signature check when the toolchain was installed on
+    /// the exporting machine.
+    #[serde(default)]
+    pub signature: SignatureStatus,
+    /// Every file of the toolchain with its checksum.
+    pub files: Vec<BundleFile>,
+}
+
+/// A file of the toolchain directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to the toolchain directory, with `/` separators.
+    pub path: String,
+    /// SHA256 of the file contents as a lowercase hex string.
+    pub sha256: String,
+}
+
+/// Returns the default file name for a bundle of `version`.
+#[must_use]
+pub fn default_bundle_name(version: &str, platform: Platform) -> String {
+    format!("inference-toolchain-{version}-{platform}.{BUNDLE_EXTENSION}")
+}
+
+/// Packs the installed toolchain `version` into a bundle at `output`.
+///
+/// # Errors
+///
+/// Returns an error if the version is not installed, contains anything but
+/// regular files and directories, or the bundle cannot be written. A
+/// partially written bundle is removed.
+pub fn export_bundle(
+    paths: &ToolchainPaths,
+    version: &str,
+    platform: Platform,
+    output: &Path,
+) -> Result<BundleManifest> {
+    if !paths.is_version_installed(version) {
+        return Err(InfsError::toolchain_not_found(version)).with_context(|| {
+            format!(
+                "Toolchain version {version} is not installed.\n\
+                 Run 'infs list' to see installed versions."
+            )
+        });
+    }
+
+    let toolchain_dir = paths.toolchain_dir(version);
+    let metadata_path = paths.metadata_path(version);
+    let mut relative_paths = Vec::new();
+    collect_files(&toolchain_dir, Path::new(""), &mut relative_paths)?;
+    relative_paths.retain(|relative| toolchain_dir.join(relative) != metadata_path);
+    relative_paths.sort();
+
+    let files = relative_paths
+        .iter()
+        .map(|relative| {
+            Ok(BundleFile {
+                path: bundle_path(relative),
+                sha256: compute_sha256(&toolchain_dir.join(relative))?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        version: version.to_string(),
+        platform: platform.to_string(),
+        signature: paths
+            .read_metadata(version)
+            .map(|metadata| metadata.signature)
+            .unwrap_or_default(),
+        files,
+    };
+
+    let written = write_bundle(&manifest, &toolchain_dir, &relative_paths, output);
+    if let Err(e) = written {
+        std::fs::remove_file(output).ok();
+        return Err(e.context(format!("Failed to write bundle {}", output.display())));
+    }
+    Ok(manifest)
+}
+
+/// Appends the paths of all regular files below `dir` to `files`, relative
+/// to the toolchain directory.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let full = root.join(dir);
+    let entries = std::fs::read_dir(&full)
+        .with_context(|| format!("Failed to read directory {}", full.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read {}", full.display()))?;
+        let relative = dir.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .with_context(|| format!("Failed to inspect {}", entry.path().display()))?;
+        if file_type.is_dir() {
+            collect_files(root, &relative, files)?;
+        } else if file_type.is_file() {
+            files.push(relative);
+        } else {
+            bail!(
+                "Cannot export {}: bundles can only contain regular files",
+                entry.path().display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Formats a relative path with `/` separators on every platform.
+fn bundle_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Writes the manifest followed by the toolchain files to `output`.
+fn write_bundle(
+    manifest: &BundleManifest,
+    toolchain_dir: &Path,
+    relative_paths: &[PathBuf],
+    output: &Path,
+) -> Result<()> {
+    let file = File::create(output).context("Failed to create file")?;
+    let encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL)?;
+    let mut builder = Builder::new(encoder);
+    builder.follow_symlinks(false);
+
+    let json = serde_json::to_vec_pretty(manifest).context("Failed to serialize manifest")?;
+    let mut header = Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_ENTRY, json.as_slice())?;
+
+    for (relative, file) in relative_paths.iter().zip(&manifest.files) {
+        builder
+            .append_path_with_name(
+                toolchain_dir.join(relative),
+                format!("{TOOLCHAIN_DIR}/{}", file.path),
+            )
+            .with_context(|| format!("Failed to add {}", file.path))?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Reads and validates the manifest at the start of a bundle.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a bundle or the manifest is invalid.
+pub fn read_manifest(bundle: &Path) -> Result<BundleManifest> {
+    let mut archive = open_bundle(bundle)?;
+    let mut entries = archive
+        .entries()
+        .with_context(|| format!("Failed to read bundle {}", bundle.display()))?;
+    let Some(entry) = entries.next() else {
+        bail!("{} is empty, not a toolchain bundle", bundle.display());
+    };
+    let entry = entry.with_context(|| format!("Failed to read bundle {}", bundle.display()))?;
+    parse_manifest(entry, bundle)
+}
+
+/// Opens a bundle for reading.
+fn open_bundle(bundle: &Path) -> Result<Archive<zstd::Decoder<'static, std::io::BufReader<File>>>> {
+    let file = File::open(bundle)
+        .with_context(|| format!("Failed to open bundle {}", bundle.display()))?;
+    let decoder = zstd::Decoder::new(file)
+        .with_context(|| format!("Failed to read bundle {}", bundle.display()))?;
+    Ok(Archive::new(decoder))
+}
+
+/// Parses the manifest from the first entry of `bundle`.
+fn parse_manifest(
+    entry: tar::Entry<'_, impl std::io::Read>,
+    bundle: &Path,
+) -> Result<BundleManifest> {
+    let is_manifest = entry
+        .path()
+        .is_ok_and(|path| path.as_os_str() == MANIFEST_ENTRY);
+    if !is_manifest {
+        bail!(
+            "{} is not a toolchain bundle: it does not start with {MANIFEST_ENTRY}",
+            bundle.display()
+        );
+    }
+
+    let manifest: BundleManifest = serde_json::from_reader(entry)
+        .with_context(|| format!("Invalid {MANIFEST_ENTRY} in {}", bundle.display()))?;
+    if manifest.format != BUNDLE_FORMAT {
+        bail!(
+            "{} uses bundle format {}, but this version of infs reads format {BUNDLE_FORMAT}.\n\
+             Export the toolchain with a matching version of infs.",
+            bundle.display(),
+            manifest.format
+        );
+    }
+    let version = manifest.version.as_str();
+    if version.is_empty() || version.contains(['/', '\\']) || version.starts_with('.') {
+        bail!(
+            "Invalid toolchain version '{version}' in {}",
+            bundle.display()
+        );
+    }
+    for file in &manifest.files {
+        reject_unsafe_path(Path::new(&file.path))?;
+        if !is_valid_sha256(&file.sha256) {
+            bail!("Invalid checksum for {} in {}", file.path, bundle.display());
+        }
+    }
+    Ok(manifest)
+}
+
+/// Extracts the toolchain files of `bundle` into `dest` and checks each one
+/// against the bundle manifest.
+///
+/// # Errors
+///
+/// Returns an error if the bundle is invalid, an entry would be written
+/// outside `dest`, or a file is unlisted, corrupted, or missing. `dest` may
+/// be left partially extracted.
+pub fn unpack_bundle(
+    bundle: &Path,
+    dest: &Path,
+    progress: Option<ExtractProgressCallback>,
+) -> Result<BundleManifest> {
+    std::fs::create_dir_all(dest)
+        .with_context(|| format!("Failed to create directory: {}", dest.display()))?;
+    let dest_root = canonical_dest_dir(dest)?;
+
+    let mut archive = open_bundle(bundle)?;
+    // Apply each entry's 0o777 mode bits, without setuid/setgid/sticky.
+    archive.set_preserve_permissions(false);
+    let mut entries = archive
+        .entries()
+        .with_context(|| format!("Failed to read bundle {}", bundle.display()))?;
+
+    let Some(first) = entries.next() else {
+        bail!("{} is empty, not a toolchain bundle", bundle.display());
+    };
+    let manifest = parse_manifest(
+        first.with_context(|| format!("Failed to read bundle {}", bundle.display()))?,
+        bundle,
+    )?;
+    let mut expected: HashMap<&str, &str> = manifest
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.sha256.as_str()))
+        .collect();
+    let total = expected.len() as u64;
+    let mut processed = 0;
+
+    for entry in entries {
+        let mut entry =
+            entry.with_context(|| format!("Failed to read bundle {}", bundle.display()))?;
+        let entry_path = entry
+            .path()
+            .with_context(|| "Failed to get entry path")?
+            .into_owned();
+        reject_unsafe_path(&entry_path)?;
+        let Ok(relative) = entry_path.strip_prefix(TOOLCHAIN_DIR) else {
+            bail!(
+                "Unexpected entry {} in bundle {}",
+                entry_path.display(),
+                bundle.display()
+            );
+        };
+        let relative = relative.to_path_buf();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let output_path = dest.join(&relative);
+        ensure_within_dest(&dest_root, &output_path)?;
+
+        match entry.header().entry_type() {
+            EntryType::Directory => {
+                std::fs::create_dir_all(&output_path).with_context(|| {
+                    format!("Failed to create directory: {}", output_path.display())
+                })?;
+            }
+            EntryType::Regular => {
+                let path = bundle_path(&relative);
+                let Some(sha256) = expected.remove(path.as_str()) else {
+                    bail!(
+                        "{path} in bundle {} is not listed in {MANIFEST_ENTRY}",
+                        bundle.display()
+                    );
+                };
+                if let Some(parent) = output_path.parent() {
+                    std::fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create directory: {}", parent.display())
+                    })?;
+                }
+                entry
+                    .unpack(&output_path)
+                    .with_context(|| format!("Failed to extract: {}", output_path.display()))?;
+                verify_checksum(&output_path, sha256).with_context(|| {
+                    format!("{path} in bundle {} is corrupted", bundle.display())
+                })?;
+
+                processed += 1;
+                if let Some(progress) = progress {
+                    progress(processed, total);
+                }
+            }
+            _ => bail!(
+                "Refusing to extract {} from bundle {}: only regular files are allowed",
+                entry_path.display(),
+                bundle.display()
+            ),
+        }
+    }
+
+    if let Some(missing) = expected.keys().min() {
+        bail!(
+            "{missing} is listed in {MANIFEST_ENTRY} but missing from bundle {}",
+            bundle.display()
+        );
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn platform() -> Platform {
+        Platform::detect().expect("Should detect platform")
+    }
+
+    /// Installs a fake toolchain with `infc` at the root and a tool in `bin`.
+    fn install_fake(paths: &ToolchainPaths, version: &str) {
+        let bin = paths.toolchain_bin_dir(version);
+        std::fs::create_dir_all(&bin).expect("Should create bin");
+        std::fs::write(paths.toolchain_dir(version).join("infc"), "infc").expect("Should write");
+        std::fs::write(bin.join("inf-llc"), "inf-llc").expect("Should write");
+        paths
+            .write_metadata(version, &crate::toolchain::paths::ToolchainMetadata::now())
+            .expect("Should write metadata");
+    }
+
+    /// Writes a bundle with the given manifest and `(name, contents)` entries.
+    fn write_raw_bundle(path: &Path, manifest: &BundleManifest, entries: &[(&str, &[u8])]) {
+        let file = File::create(path).expect("Should create bundle");
+        let encoder = zstd::Encoder::new(file, 0).expect("Should create encoder");
+        let mut builder = Builder::new(encoder);
+        let json = serde_json::to_vec(manifest).expect("Should serialize");
+        let mut all = vec![(MANIFEST_ENTRY, json.as_slice())];
+        all.extend_from_slice(entries);
+        for (name, contents) in all {
+            let mut header = Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents)
+                .expect("Should append");
+        }
+        builder
+            .into_inner()
+            .expect("Should finish tar")
+            .finish()
+            .expect("Should finish zstd");
+    }
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    fn manifest_for(files: &[(&str, &[u8])]) -> BundleManifest {
+        BundleManifest {
+            format: BUNDLE_FORMAT,
+            version: String::from("0.1.0"),
+            platform: platform().to_string(),
+            signature: SignatureStatus::Unknown,
+            files: files
+                .iter()
+                .map(|(path, contents)| BundleFile {
+                    path: (*path).to_string(),
+                    sha256: sha256_hex(contents),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn export_lists_files_without_metadata() {
+        let root = temp_test_dir("bundle_export");
+        let paths = ToolchainPaths::with_root(root.join("home"));
+        install_fake(&paths, "0.1.0");
+        let output = root.join("bundle.tar.zst");
+
+        let manifest = export_bundle(&paths, "0.1.0", platform(), &output).expect("Should export");
+
+        let listed: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(listed, ["bin/inf-llc", "infc"]);
+        assert_eq!(manifest.files[1].sha256, sha256_hex(b"infc"));
+        assert_eq!(read_manifest(&output).expect("Should read"), manifest);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn export_requires_installed_version() {
+        let root = temp_test_dir("bundle_export_missing");
+        let paths = ToolchainPaths::with_root(root.clone());
+        let output = root.join("bundle.tar.zst");
+
+        let err = export_bundle(&paths, "0.9.0", platform(), &output).expect_err("Should fail");
+        assert!(err.to_string().contains("0.9.0 is not installed"));
+        assert!(!output.exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn unpack_rejects_tampered_file() {
+        let root = temp_test_dir("bundle_tampered");
+        let bundle = root.join("bundle.tar.zst");
+        let manifest = manifest_for(&[("infc", b"original")]);
+        write_raw_bundle(&bundle, &manifest, &[("toolchain/infc", b"tampered")]);
+
+        let err = unpack_bundle(&bundle, &root.join("out"), None).expect_err("Should fail");
+        assert!(format!("{err:#}").contains("infc in bundle"), "{err:#}");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn unpack_rejects_unlisted_and_missing_files() {
+        let root = temp_test_dir("bundle_unlisted");
+        let bundle = root.join("bundle.tar.zst");
+
+        let manifest = manifest_for(&[("infc", b"infc")]);
+        write_raw_bundle(
+            &bundle,
+            &manifest,
+            &[("toolchain/infc", b"infc"), ("toolchain/extra", b"x")],
+        );
+        let err = unpack_bundle(&bundle, &root.join("a"), None).expect_err("Should fail");
+        assert!(err.to_string().contains("extra"), "{err}");
+
+        write_raw_bundle(&bundle, &manifest, &[]);
+        let err = unpack_bundle(&bundle, &root.join("b"), None).expect_err("Should fail");
+        assert!(err.to_string().contains("missing from bundle"), "{err}");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn manifest_with_unsafe_paths_is_rejected() {
+        let root = temp_test_dir("bundle_traversal");
+        let bundle = root.join("bundle.tar.zst");
+
+        let manifest = manifest_for(&[("../escape", b"x")]);
+        write_raw_bundle(&bundle, &manifest, &[]);
+        let err = read_manifest(&bundle).expect_err("Should fail");
+        assert!(err.to_string().contains("Refusing"), "{err}");
+
+        let mut manifest = manifest_for(&[]);
+        manifest.version = String::from("../0.1.0");
+        write_raw_bundle(&bundle, &manifest, &[]);
+        let err = read_manifest(&bundle).expect_err("Should fail");
+        assert!(
+            err.to_string().contains("Invalid toolchain version"),
+            "{err}"
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn read_manifest_rejects_other_archives() {
+        let root = temp_test_dir("bundle_not_a_bundle");
+        let bundle = root.join("bundle.tar.zst");
+        std::fs::write(&bundle, b"not zstd").expect("Should write");
+
+        assert!(read_manifest(&bundle).is_err());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
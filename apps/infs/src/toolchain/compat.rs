@@ -0,0 +1,496 @@
+//! External tool compatibility checks.
+//!
+//! Generated Rocq (`.v`) files require a minimum `coqc` version, and older
+//! `wasmtime` releases reject WebAssembly features emitted by our codegen.
+//! This module embeds a compatibility table keyed by toolchain version and
+//! compares it against the versions reported by the installed tools.
+//!
+//! The checks are used by `infs doctor` and, lazily, by commands that
+//! invoke external tools (such as `infs run`).
+//!
+//! It also records [`SUPPORTED_TOOLCHAINS`], the range of toolchain versions
+//! this build of infs works with. `infs install` and `infs toolchain import`
+//! warn when they install a version outside it.
+
+use std::process::Command;
+
+use semver::Version;
+
+use super::ToolchainPaths;
+use super::doctor::DoctorCheck;
+use crate::output;
+
+/// External tools whose versions affect generated artifacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalTool {
+    /// Rocq (Coq) compiler, used to check generated `.v` files.
+    Coqc,
+    /// WebAssembly runtime, used by `infs run`.
+    Wasmtime,
+}
+
+impl ExternalTool {
+    /// Returns the executable name of the tool.
+    #[must_use]
+    pub fn binary_name(self) -> &'static str {
+        match self {
+            ExternalTool::Coqc => "coqc",
+            ExternalTool::Wasmtime => "wasmtime",
+        }
+    }
+
+    /// Returns a short hint describing how to install or upgrade the tool.
+    #[must_use]
+    pub fn install_hint(self) -> &'static str {
+        match self {
+            ExternalTool::Coqc => "Install Rocq from https://rocq-prover.org/install",
+            ExternalTool::Wasmtime => "Install wasmtime from https://wasmtime.dev/",
+        }
+    }
+}
+
+/// Version requirements for a single external tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolRequirement {
+    /// Oldest version that works with the toolchain.
+    pub minimum: &'static str,
+    /// Newest version the toolchain has been tested with.
+    pub known_good: &'static str,
+}
+
+/// A row of the compatibility table.
+///
+/// Each row applies to toolchain versions from `since` up to, but not
+/// including, the `since` of the next row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompatEntry {
+    /// First toolchain version this row applies to.
+    pub since: &'static str,
+    /// Requirements for `coqc`.
+    pub coqc: ToolRequirement,
+    /// Requirements for `wasmtime`.
+    pub wasmtime: ToolRequirement,
+}
+
+impl CompatEntry {
+    /// Returns the requirement for the given tool.
+    #[must_use]
+    pub fn requirement(&self, tool: ExternalTool) -> ToolRequirement {
+        match tool {
+            ExternalTool::Coqc => self.coqc,
+            ExternalTool::Wasmtime => self.wasmtime,
+        }
+    }
+}
+
+/// Compatibility table, sorted by ascending `since` version.
+pub const COMPATIBILITY_MATRIX: &[CompatEntry] = &[CompatEntry {
+    since: "0.0.0",
+    coqc: ToolRequirement {
+        minimum: "8.18.0",
+        known_good: "9.0.0",
+    },
+    // `known_good` follows the wasmtime version the end-to-end tests run
+    // against (`tests/Cargo.toml`).
+    wasmtime: ToolRequirement {
+        minimum: "14.0.0",
+        known_good: "40.0.0",
+    },
+}];
+
+/// Range of toolchain versions a build of infs supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolchainRange {
+    /// Oldest supported toolchain version.
+    pub minimum: &'static str,
+    /// Newest toolchain version this build has been tested with.
+    pub maximum: &'static str,
+}
+
+impl ToolchainRange {
+    /// Returns whether `version` lies within the range, bounds included.
+    ///
+    /// Pre-release toolchains are compared as their release. Returns `None`
+    /// if `version` is not a valid semantic version.
+    #[must_use]
+    pub fn contains(&self, version: &str) -> Option<bool> {
+        let version = Version::parse(version.trim_start_matches('v')).ok()?;
+        let release = Version::new(version.major, version.minor, version.patch);
+        let minimum = Version::parse(self.minimum).ok()?;
+        let maximum = Version::parse(self.maximum).ok()?;
+        Some(minimum <= release && release <= maximum)
+    }
+}
+
+/// Toolchain versions supported by this build of infs.
+pub const SUPPORTED_TOOLCHAINS: ToolchainRange = ToolchainRange {
+    minimum: "0.1.0",
+    maximum: "0.4.0",
+};
+
+/// Returns a warning if toolchain `version` is outside `range`.
+///
+/// Versions that cannot be parsed are not reported.
+#[must_use]
+pub fn toolchain_range_warning(range: &ToolchainRange, version: &str) -> Option<String> {
+    if range.contains(version)? {
+        return None;
+    }
+    Some(format!(
+        "toolchain {version} is outside the range supported by infs {} ({} to {}); \
+         some commands may not work as expected",
+        env!("CARGO_PKG_VERSION"),
+        range.minimum,
+        range.maximum
+    ))
+}
+
+/// Finds the table row that applies to `toolchain_version`.
+///
+/// When the toolchain version is unknown or unparseable, the newest row is
+/// used. Returns `None` only if the table is empty or the version predates
+/// every row.
+#[must_use]
+pub fn entry_for<'a>(
+    matrix: &'a [CompatEntry],
+    toolchain_version: Option<&str>,
+) -> Option<&'a CompatEntry> {
+    let Some(version) = toolchain_version.and_then(|v| Version::parse(v).ok()) else {
+        return matrix.last();
+    };
+
+    matrix.iter().rev().find(|entry| {
+        Version::parse(entry.since).is_ok_and(|since| {
+            // Pre-release toolchains share the row of their release.
+            let release = Version::new(version.major, version.minor, version.patch);
+            since <= release
+        })
+    })
+}
+
+/// Extracts a version number from `--version` output.
+///
+/// Returns the first whitespace-separated token that looks like a dotted
+/// version number. A leading `v` and trailing distro-specific suffixes such
+/// as `+dfsg`, `~ubuntu` or `-1` are ignored, and a missing patch component
+/// is treated as zero.
+#[must_use]
+pub fn parse_tool_version(output: &str) -> Option<Version> {
+    output.split_whitespace().find_map(parse_version_token)
+}
+
+fn parse_version_token(token: &str) -> Option<Version> {
+    let token = token.trim_start_matches(['(', 'v']);
+    let end = token
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(token.len());
+    let numeric = token[..end].trim_end_matches('.');
+
+    let parts: Vec<&str> = numeric.split('.').collect();
+    if parts.len() < 2 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+
+    let mut numbers = parts.iter().take(3).map(|p| p.parse::<u64>().ok());
+    let major = numbers.next()??;
+    let minor = numbers.next()??;
+    let patch = numbers.next().unwrap_or(Some(0))?;
+    Some(Version::new(major, minor, patch))
+}
+
+/// Compares `--version` output against a requirement.
+///
+/// Returns an error when the found version is below the minimum, a warning
+/// when it is newer than the known-good version or cannot be parsed, and
+/// Ok otherwise.
+#[must_use]
+pub fn check_version_output(
+    tool: ExternalTool,
+    output: &str,
+    requirement: ToolRequirement,
+) -> DoctorCheck {
+    let name = tool.binary_name();
+    let minimum = Version::parse(requirement.minimum).expect("minimum must be valid semver");
+    let known_good =
+        Version::parse(requirement.known_good).expect("known_good must be valid semver");
+
+    let Some(found) = parse_tool_version(output) else {
+        let first_line = output.lines().next().unwrap_or_default().trim();
+        return DoctorCheck::warning(
+            name,
+            format!("Cannot parse version from '{first_line}' (requires >= {minimum})"),
+        );
+    };
+
+    if found < minimum {
+        DoctorCheck::error(
+            name,
+            format!(
+                "Found {found}, requires >= {minimum}. {}",
+                tool.install_hint()
+            ),
+        )
+    } else if found > known_good {
+        DoctorCheck::warning(
+            name,
+            format!("Found {found}, newer than known-good {known_good}; not yet tested"),
+        )
+    } else {
+        DoctorCheck::ok(name, format!("Found {found} (requires >= {minimum})"))
+    }
+}
+
+/// Runs `<tool> --version` and returns its combined output.
+#[must_use]
+pub fn tool_version_output(tool: ExternalTool) -> Option<String> {
+    let output = Command::new(tool.binary_name())
+        .arg("--version")
+        .output()
+        .ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+/// Returns the first non-empty line of `<tool> --version`.
+///
+/// Returns `None` if the tool cannot be run or prints nothing.
+#[must_use]
+pub fn tool_version_line(tool: ExternalTool) -> Option<String> {
+    tool_version_output(tool)?
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+/// Returns the default toolchain version, if one is set.
+fn default_toolchain_version() -> Option<String> {
+    ToolchainPaths::new().ok()?.get_default_version().ok()?
+}
+
+/// Checks an installed tool against the compatibility table.
+///
+/// A missing tool is reported as a warning since it is only needed by
+/// some commands.
+#[must_use]
+pub fn check_tool(tool: ExternalTool) -> DoctorCheck {
+    let name = tool.binary_name();
+
+    if which::which(name).is_err() {
+        return DoctorCheck::warning(name, format!("Not found in PATH. {}", tool.install_hint()));
+    }
+
+    let toolchain_version = default_toolchain_version();
+    let Some(entry) = entry_for(COMPATIBILITY_MATRIX, toolchain_version.as_deref()) else {
+        return DoctorCheck::warning(name, "No compatibility data for this toolchain");
+    };
+
+    match tool_version_output(tool) {
+        Some(output) => check_version_output(tool, &output, entry.requirement(tool)),
+        None => DoctorCheck::warning(name, format!("Cannot run '{name} --version'")),
+    }
+}
+
+/// Checks if the installed coqc is compatible with the default toolchain.
+#[must_use]
+pub fn check_coqc() -> DoctorCheck {
+    check_tool(ExternalTool::Coqc)
+}
+
+/// Checks if the installed wasmtime is compatible with the default toolchain.
+#[must_use]
+pub fn check_wasmtime() -> DoctorCheck {
+    check_tool(ExternalTool::Wasmtime)
+}
+
+/// Prints a one-line warning if the installed tool is incompatible.
+///
+/// Intended for commands that are about to invoke the tool; the command
+/// proceeds regardless of the result.
+pub fn warn_if_incompatible(tool: ExternalTool) {
+    let check = check_tool(tool);
+    if check.status != super::doctor::DoctorCheckStatus::Ok {
+        output::warn(format_args!("{}: {}", check.name, check.message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolchain::doctor::DoctorCheckStatus;
+
+    const REQUIREMENT: ToolRequirement = ToolRequirement {
+        minimum: "8.18.0",
+        known_good: "9.0.0",
+    };
+
+    #[test]
+    fn toolchain_range_includes_bounds_and_prereleases() {
+        let range = ToolchainRange {
+            minimum: "0.1.0",
+            maximum: "0.4.0",
+        };
+        assert_eq!(range.contains("0.1.0"), Some(true));
+        assert_eq!(range.contains("0.4.0"), Some(true));
+        assert_eq!(range.contains("0.3.0-alpha"), Some(true));
+        assert_eq!(range.contains("v0.2.4"), Some(true));
+        assert_eq!(range.contains("0.0.9"), Some(false));
+        assert_eq!(range.contains("0.4.1"), Some(false));
+        assert_eq!(range.contains("latest"), None);
+    }
+
+    #[test]
+    fn toolchain_range_warning_only_outside_range() {
+        let range = ToolchainRange {
+            minimum: "0.1.0",
+            maximum: "0.4.0",
+        };
+        assert!(toolchain_range_warning(&range, "0.2.0").is_none());
+        assert!(toolchain_range_warning(&range, "nightly").is_none());
+        let warning = toolchain_range_warning(&range, "1.0.0").unwrap();
+        assert!(warning.contains("toolchain 1.0.0"));
+        assert!(warning.contains("0.1.0 to 0.4.0"));
+    }
+
+    #[test]
+    fn parse_coq_version_strings() {
+        let cases = [
+            (
+                "The Coq Proof Assistant, version 8.18.0 compiled with OCaml 4.14.1",
+                Version::new(8, 18, 0),
+            ),
+            (
+                "The Coq Proof Assistant, version 8.12.0 (July 2020)\ncompiled on Jul 27 2020 17:52:39 with OCaml 4.10.0",
+                Version::new(8, 12, 0),
+            ),
+            (
+                "The Coq Proof Assistant, version 8.16.1+dfsg compiled with OCaml 4.13.1",
+                Version::new(8, 16, 1),
+            ),
+            (
+                "The Rocq Prover, version 9.0.0\ncompiled with OCaml 4.14.2",
+                Version::new(9, 0, 0),
+            ),
+            (
+                "The Coq Proof Assistant, version 8.20+rc1\ncompiled with OCaml 5.2.0",
+                Version::new(8, 20, 0),
+            ),
+        ];
+
+        for (output, expected) in cases {
+            assert_eq!(parse_tool_version(output), Some(expected), "{output}");
+        }
+    }
+
+    #[test]
+    fn parse_wasmtime_version_strings() {
+        let cases = [
+            ("wasmtime-cli 14.0.4", Version::new(14, 0, 4)),
+            (
+                "wasmtime 25.0.1 (b4fd7d8a4 2024-09-20)",
+                Version::new(25, 0, 1),
+            ),
+            ("wasmtime-cli 0.40.1\n", Version::new(0, 40, 1)),
+            ("wasmtime v19.0.2~ubuntu1", Version::new(19, 0, 2)),
+        ];
+
+        for (output, expected) in cases {
+            assert_eq!(parse_tool_version(output), Some(expected), "{output}");
+        }
+    }
+
+    #[test]
+    fn parse_rejects_output_without_version() {
+        assert_eq!(parse_tool_version(""), None);
+        assert_eq!(parse_tool_version("command not found"), None);
+        assert_eq!(parse_tool_version("built on 2024-09-20 at 17:52:39"), None);
+    }
+
+    #[test]
+    fn check_version_below_minimum_is_error() {
+        let check = check_version_output(
+            ExternalTool::Coqc,
+            "The Coq Proof Assistant, version 8.12.0 (July 2020)",
+            REQUIREMENT,
+        );
+        assert_eq!(check.status, DoctorCheckStatus::Error);
+        assert!(check.message.contains("Found 8.12.0"));
+        assert!(check.message.contains(">= 8.18.0"));
+        assert!(check.message.contains("rocq-prover.org"));
+    }
+
+    #[test]
+    fn check_version_within_range_is_ok() {
+        let check = check_version_output(
+            ExternalTool::Coqc,
+            "The Coq Proof Assistant, version 8.18.0 compiled with OCaml 4.14.1",
+            REQUIREMENT,
+        );
+        assert_eq!(check.status, DoctorCheckStatus::Ok);
+    }
+
+    #[test]
+    fn check_version_newer_than_known_good_is_warning() {
+        let check = check_version_output(
+            ExternalTool::Coqc,
+            "The Rocq Prover, version 9.1.0",
+            REQUIREMENT,
+        );
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(check.message.contains("9.1.0"));
+    }
+
+    #[test]
+    fn check_unparseable_version_is_warning() {
+        let check = check_version_output(ExternalTool::Wasmtime, "wasmtime dev", REQUIREMENT);
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(check.message.contains("wasmtime dev"));
+    }
+
+    #[test]
+    fn entry_for_picks_row_by_toolchain_version() {
+        let matrix = [
+            CompatEntry {
+                since: "0.0.0",
+                coqc: REQUIREMENT,
+                wasmtime: REQUIREMENT,
+            },
+            CompatEntry {
+                since: "0.3.0",
+                coqc: ToolRequirement {
+                    minimum: "9.0.0",
+                    known_good: "9.0.0",
+                },
+                wasmtime: REQUIREMENT,
+            },
+        ];
+
+        assert_eq!(entry_for(&matrix, Some("0.2.5")).unwrap().since, "0.0.0");
+        assert_eq!(entry_for(&matrix, Some("0.3.0")).unwrap().since, "0.3.0");
+        assert_eq!(
+            entry_for(&matrix, Some("0.3.0-alpha")).unwrap().since,
+            "0.3.0"
+        );
+        assert_eq!(entry_for(&matrix, None).unwrap().since, "0.3.0");
+        assert_eq!(entry_for(&matrix, Some("garbage")).unwrap().since, "0.3.0");
+    }
+
+    #[test]
+    fn compatibility_matrix_is_valid() {
+        let mut previous: Option<Version> = None;
+        for entry in COMPATIBILITY_MATRIX {
+            let since = Version::parse(entry.since).expect("since must be valid semver");
+            if let Some(prev) = &previous {
+                assert!(since > *prev, "matrix must be sorted by since");
+            }
+            for tool in [ExternalTool::Coqc, ExternalTool::Wasmtime] {
+                let req = entry.requirement(tool);
+                let minimum = Version::parse(req.minimum).expect("minimum must be semver");
+                let known_good = Version::parse(req.known_good).expect("known_good must be semver");
+                assert!(minimum <= known_good);
+            }
+            previous = Some(since);
+        }
+    }
+}
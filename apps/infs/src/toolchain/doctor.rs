@@ -6,13 +6,25 @@
 //! ## Checks Performed
 //!
 //! - `infs` binary availability in PATH
-//! - Platform detection
+//! - Toolchain bin directory on PATH
+//! - Platform detection (including the C library on Linux)
 //! - Toolchain directory existence
 //! - Default toolchain configuration
 //! - `inf-llc` binary presence
 //! - `rust-lld` binary presence
+//! - Broken symlinks in the bin directory
+//! - Executable permissions of the default toolchain binaries (Unix only)
 //! - `libLLVM` shared library (Linux only)
+//! - `coqc` and `wasmtime` versions against the compatibility table
+//! - Leftover `~/.infs` root, several `infc` on PATH, and repeated PATH
+//!   blocks in shell profiles (Unix only)
 
+use std::ffi::OsStr;
+use std::path::Path;
+
+use super::compat::{check_coqc, check_wasmtime};
+use super::installations::run_checks as run_installation_checks;
+use super::shell::path_remedy;
 use super::{Platform, ToolchainPaths};
 
 /// Generates a message for when no default toolchain is set.
@@ -100,30 +112,46 @@ impl DoctorCheck {
 /// Runs all doctor checks and returns the results.
 ///
 /// This function aggregates all health checks into a single vector.
-/// On Linux, it additionally includes the `libLLVM` check.
+/// On Linux, it additionally includes the `libLLVM` check. The checks for
+/// legacy and duplicate installations from [`installations`](super::installations)
+/// come last.
 #[cfg(not(target_os = "linux"))]
 pub fn run_all_checks() -> Vec<DoctorCheck> {
-    vec![
+    let mut checks = vec![
         check_infs_binary(),
+        check_path_contains_bin(),
         check_platform(),
         check_toolchain_directory(),
         check_default_toolchain(),
         check_inf_llc(),
         check_rust_lld(),
-    ]
+        check_symlinks(),
+    ];
+    #[cfg(unix)]
+    checks.push(check_executable_permissions());
+    checks.extend([check_coqc(), check_wasmtime()]);
+    checks.extend(run_installation_checks());
+    checks
 }
 
 #[cfg(target_os = "linux")]
 pub fn run_all_checks() -> Vec<DoctorCheck> {
-    vec![
+    let mut checks = vec![
         check_infs_binary(),
+        check_path_contains_bin(),
         check_platform(),
         check_toolchain_directory(),
         check_default_toolchain(),
         check_inf_llc(),
         check_rust_lld(),
+        check_symlinks(),
+        check_executable_permissions(),
         check_libllvm(),
-    ]
+        check_coqc(),
+        check_wasmtime(),
+    ];
+    checks.extend(run_installation_checks());
+    checks
 }
 
 /// Checks if the infs binary is accessible in PATH.
@@ -149,11 +177,62 @@ pub fn check_infs_binary() -> DoctorCheck {
     }
 }
 
+/// Checks if the toolchain bin directory is listed in `PATH`.
+///
+/// The bin directory holds the default toolchain's symlinks, so without it
+/// `infc` and friends only work through `infs`.
+#[must_use]
+pub fn check_path_contains_bin() -> DoctorCheck {
+    match ToolchainPaths::new() {
+        Ok(paths) => check_path_contains_bin_in(&paths),
+        Err(e) => DoctorCheck::error("PATH", format!("Cannot determine path: {e}")),
+    }
+}
+
+/// Checks that the bin directory of `paths` is listed in `PATH`.
+#[must_use]
+pub fn check_path_contains_bin_in(paths: &ToolchainPaths) -> DoctorCheck {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    if path_contains_dir(&path_var, &paths.bin) {
+        DoctorCheck::ok("PATH", format!("Contains {}", paths.bin.display()))
+    } else {
+        DoctorCheck::warning(
+            "PATH",
+            format!(
+                "{} is not in PATH. {}",
+                paths.bin.display(),
+                path_remedy(&paths.bin)
+            ),
+        )
+    }
+}
+
+/// Returns `true` if `dir` is one of the entries of a `PATH`-style value.
+///
+/// Entries are split with the platform path separator. Trailing separators
+/// are ignored, and on Windows the comparison is case-insensitive.
+#[must_use]
+pub fn path_contains_dir(path_var: &OsStr, dir: &Path) -> bool {
+    std::env::split_paths(path_var).any(|entry| {
+        if cfg!(windows) {
+            entry
+                .to_string_lossy()
+                .trim_end_matches(['\\', '/'])
+                .eq_ignore_ascii_case(dir.to_string_lossy().trim_end_matches(['\\', '/']))
+        } else {
+            entry == dir
+        }
+    })
+}
+
 /// Checks platform detection.
 #[must_use]
 pub fn check_platform() -> DoctorCheck {
     match Platform::detect() {
-        Ok(platform) => DoctorCheck::ok("Platform", format!("Detected {platform}")),
+        Ok(platform) => match platform.libc() {
+            Some(libc) => DoctorCheck::ok("Platform", format!("Detected {platform} ({libc})")),
+            None => DoctorCheck::ok("Platform", format!("Detected {platform}")),
+        },
         Err(e) => DoctorCheck::error("Platform", format!("Detection failed: {e}")),
     }
 }
@@ -261,6 +340,96 @@ pub fn check_binary(name: &str, binary_name: &str) -> DoctorCheck {
     }
 }
 
+/// Checks that the symlinks in the bin directory point to existing binaries.
+#[must_use]
+pub fn check_symlinks() -> DoctorCheck {
+    match ToolchainPaths::new() {
+        Ok(paths) => check_symlinks_in(&paths),
+        Err(e) => DoctorCheck::error("Symlinks", format!("Cannot determine path: {e}")),
+    }
+}
+
+/// Checks the symlinks in the bin directory of `paths`, naming what each
+/// broken one points to.
+#[must_use]
+pub fn check_symlinks_in(paths: &ToolchainPaths) -> DoctorCheck {
+    let broken = paths.validate_symlinks();
+    let targets = paths.symlink_targets();
+    let describe = |binary: &String| match targets.iter().find(|(name, _)| name == binary) {
+        Some((_, Some(target))) => format!("{binary} -> {}", target.display()),
+        _ => binary.clone(),
+    };
+    if broken.is_empty() {
+        DoctorCheck::ok(
+            "Symlinks",
+            format!("No broken links in {}", paths.bin.display()),
+        )
+    } else {
+        DoctorCheck::warning(
+            "Symlinks",
+            format!(
+                "Broken links in {}: {}. Run 'infs doctor --fix' to recreate them.",
+                paths.bin.display(),
+                broken.iter().map(describe).collect::<Vec<_>>().join(", ")
+            ),
+        )
+    }
+}
+
+/// Returns the managed binaries of toolchain `version` that exist but have
+/// no executable bit set.
+#[cfg(unix)]
+#[must_use]
+pub fn non_executable_binaries(paths: &ToolchainPaths, version: &str) -> Vec<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    ToolchainPaths::MANAGED_BINARIES
+        .iter()
+        .filter(|name| {
+            std::fs::metadata(paths.binary_path(version, name))
+                .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 == 0)
+        })
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Checks that the binaries of the default toolchain are executable (Unix
+/// only).
+#[cfg(unix)]
+#[must_use]
+pub fn check_executable_permissions() -> DoctorCheck {
+    match ToolchainPaths::new() {
+        Ok(paths) => check_executable_permissions_in(&paths),
+        Err(e) => DoctorCheck::error(
+            "Executable permissions",
+            format!("Cannot determine path: {e}"),
+        ),
+    }
+}
+
+/// Checks that the binaries of the default toolchain of `paths` are
+/// executable (Unix only).
+#[cfg(unix)]
+#[must_use]
+pub fn check_executable_permissions_in(paths: &ToolchainPaths) -> DoctorCheck {
+    const NAME: &str = "Executable permissions";
+    let Ok(Some(version)) = paths.get_default_version() else {
+        return DoctorCheck::ok(NAME, "No default toolchain to check");
+    };
+    let missing = non_executable_binaries(paths, &version);
+    if missing.is_empty() {
+        DoctorCheck::ok(NAME, format!("Toolchain {version} binaries are executable"))
+    } else {
+        DoctorCheck::warning(
+            NAME,
+            format!(
+                "Not executable in toolchain {version}: {}. Run 'infs doctor --fix' to restore the permissions.",
+                missing.join(", ")
+            ),
+        )
+    }
+}
+
 /// Checks if libLLVM is available (Linux only).
 #[cfg(target_os = "linux")]
 #[must_use]
@@ -343,12 +512,43 @@ mod tests {
     #[test]
     fn run_all_checks_returns_expected_count() {
         let checks = run_all_checks();
-        // Base checks: infs, platform, toolchain dir, default toolchain, inf-llc, rust-lld
-        #[cfg(not(target_os = "linux"))]
-        assert_eq!(checks.len(), 6);
+        // Base checks: infs, PATH, platform, toolchain dir, default toolchain, inf-llc,
+        // rust-lld, symlinks, coqc, wasmtime, legacy root, infc on PATH
+        #[cfg(windows)]
+        assert_eq!(checks.len(), 12);
+        // Executable permissions and shell profiles are also checked on Unix
+        #[cfg(all(unix, not(target_os = "linux")))]
+        assert_eq!(checks.len(), 14);
         // On Linux, libLLVM is also checked
         #[cfg(target_os = "linux")]
-        assert_eq!(checks.len(), 7);
+        assert_eq!(checks.len(), 15);
+    }
+
+    #[test]
+    fn path_contains_dir_splits_on_platform_separator() {
+        let root = std::env::temp_dir().join("infs_test_path");
+        let bin = root.join("bin");
+        let other = root.join("other");
+
+        let path_var =
+            std::env::join_paths([other.clone(), bin.clone()]).expect("Should join paths");
+        assert!(path_contains_dir(&path_var, &bin));
+        assert!(path_contains_dir(&path_var, &other));
+        assert!(!path_contains_dir(&path_var, &root));
+
+        let without_bin = std::env::join_paths([other]).expect("Should join paths");
+        assert!(!path_contains_dir(&without_bin, &bin));
+        assert!(!path_contains_dir(OsStr::new(""), &bin));
+    }
+
+    #[test]
+    fn path_contains_dir_ignores_trailing_separator() {
+        let bin = std::env::temp_dir().join("infs_test_path").join("bin");
+        let mut entry = bin.clone().into_os_string();
+        entry.push(std::path::MAIN_SEPARATOR_STR);
+
+        let path_var = std::env::join_paths([entry]).expect("Should join paths");
+        assert!(path_contains_dir(&path_var, &bin));
     }
 
     #[test]
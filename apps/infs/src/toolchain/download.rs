@@ -8,7 +8,11 @@
 //! - Streaming downloads with progress callbacks
 //! - Automatic retry with exponential backoff (3 attempts)
 //! - Downloads to temporary file, then renames on success
-//! - Configurable timeout per request
+//! - Rejects responses that are not archives, such as HTML error pages served
+//!   with `200 OK` by a login redirect (see [`sniff_archive`]), without
+//!   retrying
+//! - SHA256 computed while downloading, returned in [`DownloadOutcome`]
+//! - Request timeout configurable via `INFS_DOWNLOAD_TIMEOUT_SECS`
 //!
 //! ## TUI Integration
 //!
@@ -16,15 +20,20 @@
 //! progress via a callback instead of printing to stdout.
 
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, bail};
 use futures_util::StreamExt;
 use rand::Rng;
+use sha2::{Digest, Sha256};
 use tokio::io::AsyncWriteExt;
 
+use super::http::download_timeout;
+use crate::errors::InfsError;
+use crate::logging::{debug, info, trace};
+
 /// Progress event emitted during downloads.
 ///
 /// Used by [`download_file_with_callback`] to report progress to TUI or other consumers.
@@ -55,21 +64,94 @@ pub enum ProgressEvent {
     },
 }
 
+/// Result of a completed download.
+///
+/// The digest is computed from the chunks as they arrive, so callers can
+/// check it against the expected checksum without reading the file again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadOutcome {
+    /// Number of bytes downloaded.
+    pub bytes: u64,
+    /// SHA256 of the downloaded content as a lowercase hex string.
+    pub sha256: String,
+}
+
 /// Callback type for receiving progress updates during downloads.
 ///
 /// The callback is invoked on each progress event. It is wrapped in `Arc`
 /// to allow sharing across async boundaries.
 pub type ProgressCallback = Arc<dyn Fn(ProgressEvent) + Send + Sync>;
 
+/// Archive format recognized from the first bytes of a download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// gzip stream, as in `.tar.gz` artifacts.
+    Gzip,
+    /// ZIP archive.
+    Zip,
+}
+
+/// Number of leading bytes [`sniff_archive`] needs.
+const MAGIC_LEN: usize = 4;
+
+/// Returns the archive format `head` starts with, or `None` if it is not a
+/// gzip stream or ZIP archive.
+#[must_use]
+pub fn sniff_archive(head: &[u8]) -> Option<ArchiveKind> {
+    match head {
+        [0x1f, 0x8b, ..] => Some(ArchiveKind::Gzip),
+        [b'P', b'K', 0x03, 0x04, ..] | [b'P', b'K', 0x05, 0x06, ..] => Some(ArchiveKind::Zip),
+        _ => None,
+    }
+}
+
+/// A response that is not an archive. The server would answer the same
+/// again, so downloads fail with it at once instead of retrying.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct NotAnArchive(String);
+
+/// Fails if the response to `url` declares an HTML page, which servers
+/// return for login redirects and error pages, sometimes with `200 OK`.
+fn check_content_type(response: &reqwest::Response, url: &str) -> Result<()> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    if mime.eq_ignore_ascii_case("text/html") {
+        return Err(NotAnArchive(format!(
+            "{url} returned an HTML page instead of an archive (Content-Type: {content_type}); \
+             the server may require authentication or the URL may be wrong"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Fails if the downloaded content starting with `head` is not an archive.
+fn check_archive_magic(head: &[u8], url: &str) -> Result<()> {
+    if sniff_archive(head).is_none() {
+        return Err(NotAnArchive(format!("{url} did not return a gzip or ZIP archive")).into());
+    }
+    Ok(())
+}
+
+/// Returns the file `dest` is downloaded to before being renamed.
+///
+/// The name includes the process id, as downloads run without the toolchain
+/// lock and two processes may fetch the same archive at once.
+fn temp_path(dest: &Path) -> PathBuf {
+    dest.with_extension(format!("{}.tmp", std::process::id()))
+}
+
 /// Maximum number of download retry attempts.
 const MAX_RETRIES: u32 = 3;
 
 /// Base delay between retries in milliseconds.
 const BASE_RETRY_DELAY_MS: u64 = 1000;
 
-/// Request timeout in seconds.
-const REQUEST_TIMEOUT_SECS: u64 = 300;
-
 /// Downloads a file from the given URL to the specified path with progress display.
 ///
 /// The download uses streaming to avoid loading the entire file into memory.
@@ -81,14 +163,18 @@ const REQUEST_TIMEOUT_SECS: u64 = 300;
 /// * `url` - The URL to download from
 /// * `dest` - The destination file path
 ///
+/// # Returns
+///
+/// The size and SHA256 of the downloaded file.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The network request fails after all retries
 /// - The destination file cannot be created
 /// - Writing to the file fails
-pub async fn download_file(url: &str, dest: &Path) -> Result<()> {
-    let temp_path = dest.with_extension("tmp");
+pub async fn download_file(url: &str, dest: &Path) -> Result<DownloadOutcome> {
+    let temp_path = temp_path(dest);
 
     if let Some(parent) = dest.parent() {
         tokio::fs::create_dir_all(parent)
@@ -101,16 +187,17 @@ pub async fn download_file(url: &str, dest: &Path) -> Result<()> {
     for attempt in 0..MAX_RETRIES {
         if attempt > 0 {
             let delay = calculate_retry_delay(attempt);
-            println!(
+            info!(
                 "Retrying download (attempt {}/{})...",
                 attempt + 1,
                 MAX_RETRIES
             );
+            debug!("Waiting {delay} ms before retrying");
             tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
         }
 
         match download_with_progress(url, &temp_path).await {
-            Ok(()) => {
+            Ok(outcome) => {
                 tokio::fs::rename(&temp_path, dest).await.with_context(|| {
                     format!(
                         "Failed to rename {} to {}",
@@ -118,47 +205,97 @@ pub async fn download_file(url: &str, dest: &Path) -> Result<()> {
                         dest.display()
                     )
                 })?;
-                return Ok(());
+                return Ok(outcome);
             }
             Err(e) => {
-                last_error = Some(e);
+                debug!("Download attempt {} failed: {e:#}", attempt + 1);
                 let _ = tokio::fs::remove_file(&temp_path).await;
+                let retryable = e.downcast_ref::<NotAnArchive>().is_none();
+                last_error = Some(e);
+                if !retryable {
+                    break;
+                }
             }
         }
     }
 
-    Err(last_error
-        .unwrap_or_else(|| anyhow::anyhow!("Download failed after {MAX_RETRIES} attempts")))
+    let error = last_error
+        .unwrap_or_else(|| anyhow::anyhow!("Download failed after {MAX_RETRIES} attempts"));
+    Err(error.context(InfsError::download_error(format!(
+        "Failed to download {url}"
+    ))))
+}
+
+/// Returns the size of a remote file without downloading it.
+///
+/// Sends a `HEAD` request and reads the `Content-Length` header. Returns
+/// `None` if the server does not report a length.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the server returns a
+/// non-success status code.
+pub async fn fetch_content_length(url: &str) -> Result<Option<u64>> {
+    let client = reqwest::Client::builder()
+        .timeout(download_timeout())
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let response = client
+        .head(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to connect to {url}"))?;
+
+    if !response.status().is_success() {
+        bail!("HTTP error {}: {url}", response.status());
+    }
+
+    Ok(response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok()))
 }
 
 /// Minimum interval between progress updates in milliseconds.
 const CLI_PROGRESS_INTERVAL_MS: u128 = 250;
 
 /// Downloads a file with simple text-based progress display.
-async fn download_with_progress(url: &str, dest: &Path) -> Result<()> {
+async fn download_with_progress(url: &str, dest: &Path) -> Result<DownloadOutcome> {
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .timeout(download_timeout())
         .build()
         .context("Failed to create HTTP client")?;
 
+    debug!("GET {url}");
     let response = client
         .get(url)
         .send()
         .await
         .with_context(|| format!("Failed to connect to {url}"))?;
+    debug!(
+        "{url}: HTTP {}, content length {:?}",
+        response.status(),
+        response.content_length()
+    );
 
     if !response.status().is_success() {
         bail!("HTTP error {}: {url}", response.status());
     }
+    check_content_type(&response, url)?;
 
     let total_size = response.content_length().unwrap_or(0);
+    debug!("Writing to {}", dest.display());
 
     let mut file = tokio::fs::File::create(dest)
         .await
         .with_context(|| format!("Failed to create file: {}", dest.display()))?;
 
     let mut stream = response.bytes_stream();
+    let mut hasher = Sha256::new();
     let mut downloaded: u64 = 0;
+    let mut head = Vec::with_capacity(MAGIC_LEN);
     let start_time = Instant::now();
     let mut last_update = Instant::now();
 
@@ -167,7 +304,13 @@ async fn download_with_progress(url: &str, dest: &Path) -> Result<()> {
         file.write_all(&chunk)
             .await
             .with_context(|| format!("Failed to write to {}", dest.display()))?;
+        hasher.update(&chunk);
+        if head.len() < MAGIC_LEN {
+            let take = (MAGIC_LEN - head.len()).min(chunk.len());
+            head.extend_from_slice(&chunk[..take]);
+        }
         downloaded += chunk.len() as u64;
+        trace!("Received {} bytes ({downloaded} total)", chunk.len());
 
         let now = Instant::now();
         if now.duration_since(last_update).as_millis() >= CLI_PROGRESS_INTERVAL_MS {
@@ -179,38 +322,58 @@ async fn download_with_progress(url: &str, dest: &Path) -> Result<()> {
     file.flush()
         .await
         .with_context(|| format!("Failed to flush {}", dest.display()))?;
+    check_archive_magic(&head, url)?;
 
     print_progress(downloaded, total_size, start_time.elapsed().as_secs_f64());
     println!();
 
-    Ok(())
+    Ok(finish(hasher, downloaded))
+}
+
+/// Builds the outcome of a download from its hasher and byte count.
+fn finish(hasher: Sha256, bytes: u64) -> DownloadOutcome {
+    let sha256 = hex::encode(hasher.finalize());
+    debug!("Downloaded {bytes} bytes, SHA256 {sha256}");
+    DownloadOutcome { bytes, sha256 }
 }
 
 /// Prints a simple text-based progress line.
+fn print_progress(downloaded: u64, total: u64, elapsed_secs: f64) {
+    let speed = if elapsed_secs > 0.0 {
+        #[allow(clippy::cast_precision_loss)]
+        let downloaded = downloaded as f64;
+        downloaded / elapsed_secs
+    } else {
+        0.0
+    };
+    print!("\r{}", format_progress(downloaded, total, speed));
+    let _ = std::io::stdout().flush();
+}
+
+/// Formats a download progress line, e.g. `1.00 MB/2.00 MB (50%) 512.00 KB/s`.
+///
+/// `speed` is in bytes per second. The line ends with padding so it fully
+/// overwrites a longer previous line when printed after `\r`.
+#[must_use]
 #[allow(clippy::cast_precision_loss)]
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_sign_loss)]
-fn print_progress(downloaded: u64, total: u64, elapsed_secs: f64) {
+pub fn format_progress(downloaded: u64, total: u64, speed: f64) -> String {
     let percent = if total > 0 {
         (downloaded as f64 / total as f64 * 100.0) as u8
     } else {
         0
     };
-    let speed = if elapsed_secs > 0.0 {
-        downloaded as f64 / elapsed_secs
-    } else {
-        0.0
-    };
     let speed_str = format_speed(speed);
     let downloaded_str = format_bytes(downloaded);
     let total_str = format_bytes(total);
 
-    print!("\r{downloaded_str}/{total_str} ({percent}%) {speed_str}     ");
-    let _ = std::io::stdout().flush();
+    format!("{downloaded_str}/{total_str} ({percent}%) {speed_str}     ")
 }
 
 /// Formats bytes into a human-readable string (KB, MB, GB).
-fn format_bytes(bytes: u64) -> String {
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
     const GB: f64 = MB * 1024.0;
@@ -230,7 +393,8 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 /// Formats speed (bytes/sec) into a human-readable string.
-fn format_speed(speed: f64) -> String {
+#[must_use]
+pub fn format_speed(speed: f64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
 
@@ -243,6 +407,31 @@ fn format_speed(speed: f64) -> String {
     }
 }
 
+/// Estimates the time left to download `total` bytes at `speed` bytes per
+/// second.
+///
+/// Returns `None` if the total or the speed is unknown.
+#[must_use]
+pub fn estimate_remaining(downloaded: u64, total: u64, speed: u64) -> Option<Duration> {
+    if total == 0 || speed == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(
+        total.saturating_sub(downloaded).div_ceil(speed),
+    ))
+}
+
+/// Formats a duration for progress output, e.g. `45s`, `3m 05s`, or `1h 02m`.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, _) => format!("{h}h {m:02}m"),
+    }
+}
+
 /// Calculates the retry delay with exponential backoff and jitter.
 ///
 /// The delay doubles with each attempt (1s, 2s, 4s) with +/- 25% jitter.
@@ -269,6 +458,10 @@ const PROGRESS_CALLBACK_INTERVAL_MS: u128 = 100;
 /// * `dest` - The destination file path
 /// * `callback` - Progress callback that receives [`ProgressEvent`]s
 ///
+/// # Returns
+///
+/// The size and SHA256 of the downloaded file.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -279,8 +472,8 @@ pub async fn download_file_with_callback(
     url: &str,
     dest: &Path,
     callback: ProgressCallback,
-) -> Result<()> {
-    let temp_path = dest.with_extension("tmp");
+) -> Result<DownloadOutcome> {
+    let temp_path = temp_path(dest);
 
     if let Some(parent) = dest.parent() {
         tokio::fs::create_dir_all(parent)
@@ -293,11 +486,15 @@ pub async fn download_file_with_callback(
     for attempt in 0..MAX_RETRIES {
         if attempt > 0 {
             let delay = calculate_retry_delay(attempt);
+            debug!(
+                "Retrying download (attempt {}/{MAX_RETRIES}) in {delay} ms",
+                attempt + 1
+            );
             tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
         }
 
         match download_with_callback(url, &temp_path, callback.clone()).await {
-            Ok(()) => {
+            Ok(outcome) => {
                 tokio::fs::rename(&temp_path, dest).await.with_context(|| {
                     format!(
                         "Failed to rename {} to {}",
@@ -306,11 +503,16 @@ pub async fn download_file_with_callback(
                     )
                 })?;
                 callback(ProgressEvent::Completed);
-                return Ok(());
+                return Ok(outcome);
             }
             Err(e) => {
+                debug!("Download attempt {} failed: {e:#}", attempt + 1);
                 let _ = tokio::fs::remove_file(&temp_path).await;
+                let retryable = e.downcast_ref::<NotAnArchive>().is_none();
                 last_error = Some(e);
+                if !retryable {
+                    break;
+                }
             }
         }
     }
@@ -324,23 +526,35 @@ pub async fn download_file_with_callback(
 }
 
 /// Downloads a file with callback-based progress reporting.
-async fn download_with_callback(url: &str, dest: &Path, callback: ProgressCallback) -> Result<()> {
+async fn download_with_callback(
+    url: &str,
+    dest: &Path,
+    callback: ProgressCallback,
+) -> Result<DownloadOutcome> {
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .timeout(download_timeout())
         .build()
         .context("Failed to create HTTP client")?;
 
+    debug!("GET {url}");
     let response = client
         .get(url)
         .send()
         .await
         .with_context(|| format!("Failed to connect to {url}"))?;
+    debug!(
+        "{url}: HTTP {}, content length {:?}",
+        response.status(),
+        response.content_length()
+    );
 
     if !response.status().is_success() {
         bail!("HTTP error {}: {url}", response.status());
     }
+    check_content_type(&response, url)?;
 
     let total_size = response.content_length().unwrap_or(0);
+    debug!("Writing to {}", dest.display());
 
     callback(ProgressEvent::Started {
         url: url.to_string(),
@@ -352,7 +566,9 @@ async fn download_with_callback(url: &str, dest: &Path, callback: ProgressCallba
         .with_context(|| format!("Failed to create file: {}", dest.display()))?;
 
     let mut stream = response.bytes_stream();
+    let mut hasher = Sha256::new();
     let mut downloaded: u64 = 0;
+    let mut head = Vec::with_capacity(MAGIC_LEN);
     let start_time = Instant::now();
     let mut last_callback_time = Instant::now();
 
@@ -361,8 +577,14 @@ async fn download_with_callback(url: &str, dest: &Path, callback: ProgressCallba
         file.write_all(&chunk)
             .await
             .with_context(|| format!("Failed to write to {}", dest.display()))?;
+        hasher.update(&chunk);
+        if head.len() < MAGIC_LEN {
+            let take = (MAGIC_LEN - head.len()).min(chunk.len());
+            head.extend_from_slice(&chunk[..take]);
+        }
 
         downloaded += chunk.len() as u64;
+        trace!("Received {} bytes ({downloaded} total)", chunk.len());
 
         let now = Instant::now();
         let elapsed_since_callback = now.duration_since(last_callback_time).as_millis();
@@ -388,6 +610,7 @@ async fn download_with_callback(url: &str, dest: &Path, callback: ProgressCallba
     file.flush()
         .await
         .with_context(|| format!("Failed to flush {}", dest.display()))?;
+    check_archive_magic(&head, url)?;
 
     let elapsed_secs = start_time.elapsed().as_secs_f64();
     #[allow(
@@ -405,12 +628,141 @@ async fn download_with_callback(url: &str, dest: &Path, callback: ProgressCallba
         speed: final_speed,
     });
 
-    Ok(())
+    Ok(finish(hasher, downloaded))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves `body` for every request on a local port until the test
+    /// process exits. Returns the URL to download.
+    fn serve(body: Vec<u8>) -> String {
+        serve_as(body, "application/gzip").0
+    }
+
+    /// Like [`serve`], with `content_type` as the `Content-Type` header.
+    /// Also returns the number of requests served so far.
+    fn serve_as(body: Vec<u8>, content_type: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!(
+            "http://{}/archive.tar.gz",
+            listener.local_addr().expect("Should have address")
+        );
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                served.fetch_add(1, Ordering::SeqCst);
+                let mut reader = BufReader::new(stream.try_clone().expect("Should clone"));
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).ok();
+                stream.write_all(&body).ok();
+            }
+        });
+        (url, requests)
+    }
+
+    /// Returns a body large enough to arrive in several chunks, starting
+    /// with the gzip magic bytes.
+    fn large_body() -> Vec<u8> {
+        let mut body = vec![0x1f, 0x8b];
+        body.extend((0..300_000u32).map(|i| (i % 251) as u8));
+        body
+    }
+
+    fn temp_dest(name: &str) -> std::path::PathBuf {
+        temp_test_dir(&format!("download_{name}")).join("archive.tar.gz")
+    }
+
+    #[tokio::test]
+    async fn download_with_callback_returns_digest_of_content() {
+        let body = large_body();
+        let url = serve(body.clone());
+        let dest = temp_dest("callback");
+        let callback: ProgressCallback = Arc::new(|_| {});
+
+        let outcome = download_file_with_callback(&url, &dest, callback)
+            .await
+            .expect("Should download");
+
+        assert_eq!(outcome.bytes, body.len() as u64);
+        assert_eq!(outcome.sha256, hex::encode(Sha256::digest(&body)));
+        assert_eq!(std::fs::read(&dest).expect("Should read"), body);
+        assert_eq!(
+            outcome.sha256,
+            crate::toolchain::verify::compute_sha256(&dest).expect("Should hash")
+        );
+        std::fs::remove_dir_all(dest.parent().expect("Should have parent")).ok();
+    }
+
+    #[tokio::test]
+    async fn download_with_progress_returns_digest_of_content() {
+        let body = large_body();
+        let url = serve(body.clone());
+        let dest = temp_dest("progress");
+
+        let outcome = download_file(&url, &dest).await.expect("Should download");
+
+        assert_eq!(outcome.bytes, body.len() as u64);
+        assert_eq!(outcome.sha256, hex::encode(Sha256::digest(&body)));
+        std::fs::remove_dir_all(dest.parent().expect("Should have parent")).ok();
+    }
+
+    #[test]
+    fn sniff_archive_recognizes_gzip_and_zip() {
+        assert_eq!(
+            sniff_archive(&[0x1f, 0x8b, 0x08, 0x00]),
+            Some(ArchiveKind::Gzip)
+        );
+        assert_eq!(sniff_archive(b"PK\x03\x04rest"), Some(ArchiveKind::Zip));
+        assert_eq!(sniff_archive(b"PK\x05\x06"), Some(ArchiveKind::Zip));
+        assert_eq!(sniff_archive(b"<!DOCTYPE html><html>"), None);
+        assert_eq!(sniff_archive(b"PK"), None);
+        assert_eq!(sniff_archive(b""), None);
+    }
+
+    #[tokio::test]
+    async fn html_responses_are_rejected_without_retry() {
+        let callback: ProgressCallback = Arc::new(|_| {});
+        let page = b"<!DOCTYPE html><html><body>Sign in</body></html>".to_vec();
+        let dest = temp_dest("html");
+
+        let (url, requests) = serve_as(page.clone(), "text/html; charset=utf-8");
+        let error = download_file_with_callback(&url, &dest, callback.clone())
+            .await
+            .expect_err("Should reject HTML");
+        assert!(error.to_string().contains("HTML page"), "{error}");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert!(!dest.exists());
+        assert!(!temp_path(&dest).exists());
+
+        // Without the header, the content itself gives the page away.
+        let (url, requests) = serve_as(page, "application/octet-stream");
+        let error = download_file(&url, &dest)
+            .await
+            .expect_err("Should reject non-archive");
+        assert!(
+            format!("{error:#}").contains("did not return a gzip or ZIP archive"),
+            "{error:#}"
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert!(!dest.exists());
+        assert!(!temp_path(&dest).exists());
+        std::fs::remove_dir_all(dest.parent().expect("Should have parent")).ok();
+    }
 
     #[test]
     fn retry_delay_increases_exponentially() {
@@ -432,6 +784,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_progress_shows_bytes_percent_and_speed() {
+        assert_eq!(
+            format_progress(1024 * 1024, 2 * 1024 * 1024, 512.0 * 1024.0).trim_end(),
+            "1.00 MB/2.00 MB (50%) 512.00 KB/s"
+        );
+        assert_eq!(
+            format_progress(10, 0, 0.0).trim_end(),
+            "10 B/0 B (0%) 0 B/s"
+        );
+    }
+
+    #[test]
+    fn estimate_remaining_and_format_duration() {
+        assert_eq!(
+            estimate_remaining(80, 200, 12),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(estimate_remaining(200, 200, 12), Some(Duration::ZERO));
+        assert_eq!(estimate_remaining(80, 0, 12), None);
+        assert_eq!(estimate_remaining(80, 200, 0), None);
+
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_duration(Duration::from_mins(62)), "1h 02m");
+    }
+
     #[test]
     fn progress_event_started_contains_url_and_total() {
         let event = ProgressEvent::Started {
@@ -0,0 +1,531 @@
+//! Automatic fixes applied by `infs doctor --fix`.
+//!
+//! Problems with a local remedy are repaired in place:
+//!
+//! - Executable permissions of the default toolchain binaries are re-applied
+//!   (Unix only)
+//! - Broken or missing symlinks in the bin directory are recreated for the
+//!   default toolchain, or removed when no default toolchain is set
+//! - The bin directory is added to `PATH` in the shell profile
+//!
+//! Problems that need a download or a decision, such as a binary missing
+//! from the toolchain or an unknown shell, are reported as not fixable with
+//! the manual remedy. Unlike `infs repair`, one unfixable problem does not
+//! stop the others from being fixed.
+//!
+//! The TUI fixes one check at a time through [`FixAction`], which also
+//! describes the change before it is made and re-runs the check afterwards.
+
+use std::ffi::OsStr;
+
+use super::doctor::{
+    DoctorCheck, check_path_contains_bin_in, check_symlinks_in, path_contains_dir,
+};
+use super::paths::CopiedLinks;
+use super::shell::{ConfigureResult, configure_path, format_result_message, path_remedy};
+use super::{Platform, ToolchainPaths};
+
+/// Result of trying to fix one problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixOutcome {
+    /// The problem was repaired.
+    Fixed {
+        /// Name of the fixed check.
+        name: String,
+        /// What was changed.
+        change: String,
+    },
+    /// The problem could not be repaired automatically.
+    NotFixable {
+        /// Name of the affected check.
+        name: String,
+        /// Why, and what to do instead.
+        reason: String,
+    },
+}
+
+impl FixOutcome {
+    fn fixed(name: impl Into<String>, change: impl Into<String>) -> Self {
+        Self::Fixed {
+            name: name.into(),
+            change: change.into(),
+        }
+    }
+
+    fn not_fixable(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::NotFixable {
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// The fix for one doctor check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixAction {
+    /// Re-apply executable permissions, see [`fix_executable_permissions`].
+    ExecutablePermissions,
+    /// Recreate or remove symlinks, see [`fix_symlinks`].
+    Symlinks,
+    /// Add the bin directory to the shell profile, see [`fix_path`].
+    Path,
+}
+
+impl FixAction {
+    /// Returns the fix for the doctor check named `check`, if it has one.
+    #[must_use]
+    pub fn for_check(check: &str) -> Option<Self> {
+        match check {
+            "Executable permissions" => Some(Self::ExecutablePermissions),
+            "Symlinks" => Some(Self::Symlinks),
+            "PATH" => Some(Self::Path),
+            _ => None,
+        }
+    }
+
+    /// Describes what applying the fix to `paths` would change, one change
+    /// per line.
+    #[must_use]
+    pub fn describe(self, paths: &ToolchainPaths) -> Vec<String> {
+        let default = paths.get_default_version().ok().flatten();
+        match (self, default) {
+            #[cfg(unix)]
+            (Self::ExecutablePermissions, Some(version)) => {
+                let missing = super::doctor::non_executable_binaries(paths, &version);
+                vec![format!(
+                    "Make {} executable in toolchain {version}",
+                    missing.join(", ")
+                )]
+            }
+            (Self::ExecutablePermissions, _) => Vec::new(),
+            (Self::Symlinks, Some(version)) => vec![format!(
+                "Recreate missing or broken links in {} to toolchain {version}",
+                paths.bin.display()
+            )],
+            (Self::Symlinks, None) => vec![format!(
+                "Remove broken links {} from {}",
+                paths.validate_symlinks().join(", "),
+                paths.bin.display()
+            )],
+            (Self::Path, _) => vec![format!(
+                "Add {} to PATH in the shell profile",
+                paths.bin.display()
+            )],
+        }
+    }
+
+    /// Applies the fix to the installation at `paths`.
+    ///
+    /// Returns nothing when there is nothing to fix.
+    #[must_use]
+    pub fn apply(self, paths: &ToolchainPaths) -> Vec<FixOutcome> {
+        match self {
+            Self::ExecutablePermissions => fix_executable_permissions(paths),
+            Self::Symlinks => fix_symlinks(paths),
+            Self::Path => {
+                let path_var = std::env::var_os("PATH").unwrap_or_default();
+                fix_path(paths, &path_var).into_iter().collect()
+            }
+        }
+    }
+
+    /// Runs the doctor check this action fixes against `paths`.
+    ///
+    /// The `PATH` check looks at the environment of this process, so it
+    /// keeps failing after the shell profile is fixed until a new shell is
+    /// started.
+    #[must_use]
+    pub fn check(self, paths: &ToolchainPaths) -> DoctorCheck {
+        match self {
+            #[cfg(unix)]
+            Self::ExecutablePermissions => super::doctor::check_executable_permissions_in(paths),
+            #[cfg(not(unix))]
+            Self::ExecutablePermissions => {
+                DoctorCheck::ok("Executable permissions", "Not checked on this platform")
+            }
+            Self::Symlinks => check_symlinks_in(paths),
+            Self::Path => check_path_contains_bin_in(paths),
+        }
+    }
+}
+
+/// Applies every fix to the installation at `paths`, checking `PATH`
+/// against the current environment.
+///
+/// Returns nothing when there is nothing to fix.
+#[must_use]
+pub fn apply_fixes(paths: &ToolchainPaths) -> Vec<FixOutcome> {
+    let mut outcomes = fix_executable_permissions(paths);
+    outcomes.extend(fix_symlinks(paths));
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    outcomes.extend(fix_path(paths, &path_var));
+    outcomes
+}
+
+/// Recreates broken or missing symlinks in the bin directory.
+///
+/// Copies of another toolchain's binaries (see [`CopiedLinks`]) are
+/// replaced too. Links are recreated for the default toolchain one binary
+/// at a time, so a binary missing from the toolchain is reported without
+/// blocking the others. Without a default toolchain, broken links are
+/// removed.
+#[must_use]
+pub fn fix_symlinks(paths: &ToolchainPaths) -> Vec<FixOutcome> {
+    const NAME: &str = "Symlinks";
+    let ext = match Platform::detect() {
+        Ok(platform) => platform.executable_extension(),
+        Err(e) => return vec![FixOutcome::not_fixable(NAME, format!("{e}"))],
+    };
+
+    let version = match paths.get_default_version() {
+        Ok(Some(version)) => version,
+        Ok(None) => return remove_broken_symlinks(paths),
+        Err(e) => {
+            return vec![FixOutcome::not_fixable(
+                NAME,
+                format!("Cannot read default version: {e}"),
+            )];
+        }
+    };
+    if !paths.is_version_installed(&version) {
+        return vec![FixOutcome::not_fixable(
+            NAME,
+            format!(
+                "Default toolchain {version} is not installed. Run 'infs install {version}' to install it."
+            ),
+        )];
+    }
+
+    let copies = CopiedLinks::load(&paths.copied_links_path());
+    let mut outcomes = Vec::new();
+    let mut relinked = Vec::new();
+    for name in ToolchainPaths::MANAGED_BINARIES {
+        let binary = format!("{name}{ext}");
+        if paths.symlink_path(&binary).exists() && !copies.is_stale(&binary, Some(&version)) {
+            continue;
+        }
+        if !paths.binary_path(&version, &binary).exists() {
+            outcomes.push(FixOutcome::not_fixable(
+                name,
+                format!(
+                    "{binary} is missing from toolchain {version} and cannot be repaired locally. \
+                     Run 'infs uninstall {version}' and 'infs install {version}' to reinstall it."
+                ),
+            ));
+            continue;
+        }
+        let linked = std::fs::create_dir_all(&paths.bin)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| paths.create_symlink(&version, &binary));
+        match linked {
+            Ok(()) => relinked.push(binary),
+            Err(e) => outcomes.push(FixOutcome::not_fixable(NAME, format!("{e:#}"))),
+        }
+    }
+    if !relinked.is_empty() {
+        outcomes.insert(
+            0,
+            FixOutcome::fixed(
+                NAME,
+                format!(
+                    "Linked {} to toolchain {version} in {}",
+                    relinked.join(", "),
+                    paths.bin.display()
+                ),
+            ),
+        );
+    }
+    outcomes
+}
+
+/// Removes broken symlinks when there is no default toolchain to point
+/// them at.
+fn remove_broken_symlinks(paths: &ToolchainPaths) -> Vec<FixOutcome> {
+    const NAME: &str = "Symlinks";
+    let broken = paths.validate_symlinks();
+    if broken.is_empty() {
+        return Vec::new();
+    }
+    for binary in &broken {
+        if let Err(e) = paths.remove_symlink(binary) {
+            return vec![FixOutcome::not_fixable(NAME, format!("{e:#}"))];
+        }
+    }
+    vec![FixOutcome::fixed(
+        NAME,
+        format!(
+            "Removed broken links {} (no default toolchain is set)",
+            broken.join(", ")
+        ),
+    )]
+}
+
+/// Re-applies executable permissions to the default toolchain binaries.
+#[cfg(unix)]
+#[must_use]
+pub fn fix_executable_permissions(paths: &ToolchainPaths) -> Vec<FixOutcome> {
+    const NAME: &str = "Executable permissions";
+    let Ok(Some(version)) = paths.get_default_version() else {
+        return Vec::new();
+    };
+    let missing = super::doctor::non_executable_binaries(paths, &version);
+    if missing.is_empty() {
+        return Vec::new();
+    }
+    match super::set_executable_permissions(&paths.toolchain_dir(&version)) {
+        Ok(()) => vec![FixOutcome::fixed(
+            NAME,
+            format!(
+                "Made {} executable in toolchain {version}",
+                missing.join(", ")
+            ),
+        )],
+        Err(e) => vec![FixOutcome::not_fixable(NAME, format!("{e:#}"))],
+    }
+}
+
+/// Re-applies executable permissions (nothing to do on Windows).
+#[cfg(not(unix))]
+#[must_use]
+pub fn fix_executable_permissions(_paths: &ToolchainPaths) -> Vec<FixOutcome> {
+    Vec::new()
+}
+
+/// Adds the bin directory to `PATH` in the shell profile if `path_var`
+/// does not contain it.
+#[must_use]
+pub fn fix_path(paths: &ToolchainPaths, path_var: &OsStr) -> Option<FixOutcome> {
+    const NAME: &str = "PATH";
+    if path_contains_dir(path_var, &paths.bin) {
+        return None;
+    }
+    Some(match configure_path(&paths.bin) {
+        Ok(result @ ConfigureResult::Added { .. }) => {
+            FixOutcome::fixed(NAME, format_result_message(&result, &paths.bin))
+        }
+        Ok(ConfigureResult::AlreadyConfigured { .. }) => {
+            FixOutcome::not_fixable(NAME, path_remedy(&paths.bin))
+        }
+        #[cfg(unix)]
+        Ok(result) => FixOutcome::not_fixable(NAME, format_result_message(&result, &paths.bin)),
+        Err(e) => FixOutcome::not_fixable(NAME, format!("Cannot update the shell profile: {e:#}")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use crate::toolchain::doctor::DoctorCheckStatus;
+
+    /// Creates an installed default toolchain with all managed binaries.
+    fn install_default_toolchain(paths: &ToolchainPaths, version: &str) {
+        let bin_dir = paths.toolchain_bin_dir(version);
+        std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            std::fs::write(bin_dir.join(name), "#!/bin/sh\necho ok\n").expect("Should write");
+        }
+        paths
+            .set_default_version(version)
+            .expect("Should set default");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_symlinks_repairs_broken_links_and_reports_missing_binary() {
+        let root = temp_test_dir("fix_symlinks");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_default_toolchain(&paths, "0.1.0");
+        std::fs::remove_file(paths.binary_path("0.1.0", "rust-lld")).expect("Should remove");
+
+        std::fs::create_dir_all(&paths.bin).expect("Should create bin dir");
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            std::os::unix::fs::symlink(root.join("gone").join(name), paths.symlink_path(name))
+                .expect("Should create broken symlink");
+        }
+        assert_eq!(paths.validate_symlinks().len(), 3);
+
+        let outcomes = fix_symlinks(&paths);
+        assert_eq!(outcomes.len(), 2, "{outcomes:?}");
+        assert!(
+            matches!(&outcomes[0], FixOutcome::Fixed { change, .. } if change.starts_with("Linked infc, inf-llc to toolchain 0.1.0")),
+            "{outcomes:?}"
+        );
+        assert!(
+            matches!(&outcomes[1], FixOutcome::NotFixable { name, reason } if name == "rust-lld" && reason.contains("infs install 0.1.0")),
+            "{outcomes:?}"
+        );
+        for name in ["infc", "inf-llc"] {
+            let target = std::fs::read_link(paths.symlink_path(name)).expect("Should be symlink");
+            assert_eq!(target, paths.binary_path("0.1.0", name));
+        }
+        assert_eq!(paths.validate_symlinks(), ["rust-lld"]);
+
+        // The fixed links stay fixed; only the missing binary is reported again
+        let outcomes = fix_symlinks(&paths);
+        assert!(
+            matches!(outcomes.as_slice(), [FixOutcome::NotFixable { name, .. }] if name == "rust-lld"),
+            "{outcomes:?}"
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_symlinks_recopies_copy_of_previous_default() {
+        use crate::toolchain::paths::LinkKind;
+
+        let root = temp_test_dir("fix_stale_copy");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_default_toolchain(&paths, "0.1.0");
+        install_default_toolchain(&paths, "0.2.0");
+        paths.update_symlinks("0.2.0").expect("Should link");
+
+        // infc was copied from 0.1.0, as on Windows without symlinks
+        let infc = paths.symlink_path("infc");
+        std::fs::remove_file(&infc).expect("Should remove link");
+        std::fs::copy(paths.binary_path("0.1.0", "infc"), &infc).expect("Should copy");
+        let mut copies = CopiedLinks::default();
+        copies.record("infc", "0.1.0", LinkKind::Copy);
+        copies
+            .save(&paths.copied_links_path())
+            .expect("Should save");
+        assert_eq!(paths.validate_symlinks(), ["infc"]);
+
+        let outcomes = fix_symlinks(&paths);
+        assert!(
+            matches!(outcomes.as_slice(), [FixOutcome::Fixed { change, .. }] if change.starts_with("Linked infc to toolchain 0.2.0")),
+            "{outcomes:?}"
+        );
+        assert_eq!(
+            std::fs::read_link(&infc).expect("Should be symlink"),
+            paths.binary_path("0.2.0", "infc")
+        );
+        assert!(!paths.copied_links_path().exists());
+        assert!(paths.validate_symlinks().is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn fix_actions_match_their_checks() {
+        let root = temp_test_dir("fix_actions");
+        let paths = ToolchainPaths::with_root(root.clone());
+        for action in [
+            FixAction::ExecutablePermissions,
+            FixAction::Symlinks,
+            FixAction::Path,
+        ] {
+            assert_eq!(
+                FixAction::for_check(&action.check(&paths).name),
+                Some(action)
+            );
+        }
+        assert_eq!(FixAction::for_check("Platform"), None);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_action_describes_and_fixes_symlinks() {
+        let root = temp_test_dir("fix_action_symlinks");
+        let paths = ToolchainPaths::with_root(root.clone());
+        std::fs::create_dir_all(&paths.bin).expect("Should create bin dir");
+        std::os::unix::fs::symlink(root.join("gone"), paths.symlink_path("infc"))
+            .expect("Should create broken symlink");
+
+        let action = FixAction::Symlinks;
+        assert_eq!(
+            action.describe(&paths),
+            [format!(
+                "Remove broken links infc from {}",
+                paths.bin.display()
+            )]
+        );
+        assert_ne!(action.check(&paths).status, DoctorCheckStatus::Ok);
+        assert!(matches!(
+            action.apply(&paths).as_slice(),
+            [FixOutcome::Fixed { .. }]
+        ));
+        assert_eq!(action.check(&paths).status, DoctorCheckStatus::Ok);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_symlinks_removes_broken_links_without_default() {
+        let root = temp_test_dir("fix_symlinks_no_default");
+        let paths = ToolchainPaths::with_root(root.clone());
+        std::fs::create_dir_all(&paths.bin).expect("Should create bin dir");
+        std::os::unix::fs::symlink(root.join("gone"), paths.symlink_path("infc"))
+            .expect("Should create broken symlink");
+
+        let outcomes = fix_symlinks(&paths);
+        assert!(
+            matches!(outcomes.as_slice(), [FixOutcome::Fixed { change, .. }] if change.contains("infc")),
+            "{outcomes:?}"
+        );
+        assert!(paths.symlink_path("infc").symlink_metadata().is_err());
+        assert!(fix_symlinks(&paths).is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn fix_symlinks_reports_uninstalled_default() {
+        let root = temp_test_dir("fix_symlinks_uninstalled");
+        let paths = ToolchainPaths::with_root(root.clone());
+        paths
+            .set_default_version("0.3.0")
+            .expect("Should set default");
+
+        let outcomes = fix_symlinks(&paths);
+        assert!(
+            matches!(outcomes.as_slice(), [FixOutcome::NotFixable { reason, .. }] if reason.contains("infs install 0.3.0")),
+            "{outcomes:?}"
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_executable_permissions_restores_exec_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_test_dir("fix_permissions");
+        let paths = ToolchainPaths::with_root(root.clone());
+        install_default_toolchain(&paths, "0.1.0");
+        for name in ToolchainPaths::MANAGED_BINARIES {
+            let mode = if name == "inf-llc" { 0o644 } else { 0o755 };
+            std::fs::set_permissions(
+                paths.binary_path("0.1.0", name),
+                std::fs::Permissions::from_mode(mode),
+            )
+            .expect("Should set permissions");
+        }
+
+        let outcomes = fix_executable_permissions(&paths);
+        assert_eq!(
+            outcomes,
+            [FixOutcome::fixed(
+                "Executable permissions",
+                "Made inf-llc executable in toolchain 0.1.0"
+            )]
+        );
+        assert!(crate::toolchain::doctor::non_executable_binaries(&paths, "0.1.0").is_empty());
+        assert!(fix_executable_permissions(&paths).is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn fix_path_does_nothing_when_bin_is_on_path() {
+        let paths = ToolchainPaths::with_root(std::env::temp_dir().join("infs_test_fix_path"));
+        let path_var = std::env::join_paths([paths.bin.clone()]).expect("Should join paths");
+        assert!(fix_path(&paths, &path_var).is_none());
+    }
+}
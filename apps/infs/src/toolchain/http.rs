@@ -0,0 +1,475 @@
+//! HTTP client settings shared by manifest fetching and downloads.
+//!
+//! Request timeouts default to 30 seconds for release metadata and 300
+//! seconds for artifact downloads. Both can be raised on slow links:
+//!
+//! - `INFS_HTTP_TIMEOUT_SECS` - timeout for fetching the release manifest
+//! - `INFS_DOWNLOAD_TIMEOUT_SECS` - timeout for downloading artifacts
+//!
+//! Values are whole seconds and are clamped to
+//! [`MIN_TIMEOUT_SECS`]..=[`MAX_TIMEOUT_SECS`]. Invalid values are ignored
+//! with a warning.
+//!
+//! ## Rate limits
+//!
+//! Release metadata requests go through [`send_with_backoff`]. When the
+//! server answers `429`, or `403` with `Retry-After` or
+//! `X-RateLimit-Remaining: 0`, it waits as long as the server asks and
+//! retries, up to [`MAX_RATE_LIMIT_RETRIES`] times. Waits longer than
+//! `INFS_RATE_LIMIT_WAIT_SECS` (default 60) are not attempted, and the
+//! global `--no-wait` flag turns retrying off.
+
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+use crate::errors::InfsError;
+use crate::logging::debug;
+use crate::output;
+
+/// Environment variable overriding the release metadata request timeout.
+pub const HTTP_TIMEOUT_ENV: &str = "INFS_HTTP_TIMEOUT_SECS";
+
+/// Environment variable overriding the artifact download timeout.
+pub const DOWNLOAD_TIMEOUT_ENV: &str = "INFS_DOWNLOAD_TIMEOUT_SECS";
+
+/// Environment variable overriding the longest rate-limit wait.
+pub const RATE_LIMIT_WAIT_ENV: &str = "INFS_RATE_LIMIT_WAIT_SECS";
+
+/// Default timeout for release metadata requests, in seconds.
+const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+
+/// Default timeout for artifact downloads, in seconds.
+const DEFAULT_DOWNLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// Default longest wait for a rate limit to reset, in seconds.
+const DEFAULT_RATE_LIMIT_WAIT_SECS: u64 = 60;
+
+/// Number of times a rate-limited request is retried.
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Header with the number of seconds to wait after a rate-limited request.
+const RETRY_AFTER_HEADER: &str = "retry-after";
+
+/// Header with the number of requests left before the rate limit applies.
+const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// Header with the Unix time at which the rate limit resets.
+const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// Whether `--no-wait` was given.
+static NO_WAIT: OnceLock<bool> = OnceLock::new();
+
+/// Smallest accepted timeout, in seconds.
+pub const MIN_TIMEOUT_SECS: u64 = 5;
+
+/// Largest accepted timeout, in seconds.
+pub const MAX_TIMEOUT_SECS: u64 = 3600;
+
+/// Returns the timeout for release metadata requests.
+#[must_use]
+pub fn http_timeout() -> Duration {
+    timeout_from_env(HTTP_TIMEOUT_ENV, DEFAULT_HTTP_TIMEOUT_SECS)
+}
+
+/// Returns the timeout for artifact downloads.
+#[must_use]
+pub fn download_timeout() -> Duration {
+    timeout_from_env(DOWNLOAD_TIMEOUT_ENV, DEFAULT_DOWNLOAD_TIMEOUT_SECS)
+}
+
+/// Reads a timeout from `name`, falling back to `default_secs`.
+///
+/// An unset or empty variable uses the default silently; an unparsable one
+/// prints a warning first.
+fn timeout_from_env(name: &str, default_secs: u64) -> Duration {
+    let secs = match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => parse_timeout_secs(&value).unwrap_or_else(|| {
+            output::warn(format!(
+                "ignoring {name}={value}: expected a number of seconds"
+            ));
+            default_secs
+        }),
+        _ => default_secs,
+    };
+    Duration::from_secs(secs)
+}
+
+/// Parses a timeout in whole seconds and clamps it to the accepted range.
+///
+/// Returns `None` if `value` is not a non-negative integer.
+fn parse_timeout_secs(value: &str) -> Option<u64> {
+    let secs: u64 = value.trim().parse().ok()?;
+    Some(secs.clamp(MIN_TIMEOUT_SECS, MAX_TIMEOUT_SECS))
+}
+
+/// Turns off waiting for rate limits in this process.
+///
+/// Called by `main` after parsing `--no-wait`. The value can only be set
+/// once.
+pub fn set_no_wait(no_wait: bool) {
+    let _ = NO_WAIT.set(no_wait);
+}
+
+/// Returns the longest wait for a rate limit before retrying, or `None` if
+/// rate-limited requests are not retried.
+///
+/// `--no-wait` and `INFS_RATE_LIMIT_WAIT_SECS=0` turn retrying off. Values
+/// above [`MAX_TIMEOUT_SECS`] are clamped; invalid ones are ignored with a
+/// warning.
+#[must_use]
+pub fn rate_limit_wait_cap() -> Option<Duration> {
+    if NO_WAIT.get().copied().unwrap_or(false) {
+        return None;
+    }
+    let secs = match std::env::var(RATE_LIMIT_WAIT_ENV) {
+        Ok(value) if !value.trim().is_empty() => {
+            if let Ok(secs) = value.trim().parse::<u64>() {
+                secs.min(MAX_TIMEOUT_SECS)
+            } else {
+                output::warn(format!(
+                    "ignoring {RATE_LIMIT_WAIT_ENV}={value}: expected a number of seconds"
+                ));
+                DEFAULT_RATE_LIMIT_WAIT_SECS
+            }
+        }
+        _ => DEFAULT_RATE_LIMIT_WAIT_SECS,
+    };
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Returns whether a response with `status` and `headers` signals a rate
+/// limit.
+///
+/// `429` always does. `403` only does with `Retry-After` or
+/// `X-RateLimit-Remaining: 0`; otherwise access is simply denied.
+#[must_use]
+pub fn is_rate_limited(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap) -> bool {
+    match status.as_u16() {
+        429 => true,
+        403 => {
+            headers.contains_key(RETRY_AFTER_HEADER)
+                || headers
+                    .get(RATE_LIMIT_REMAINING_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.trim() == "0")
+        }
+        _ => false,
+    }
+}
+
+/// Reads how long to wait before retrying a rate-limited request.
+///
+/// Uses `Retry-After` when it holds a number of seconds, and otherwise the
+/// `X-RateLimit-Reset` Unix timestamp relative to `now`. A reset time in the
+/// past means no wait. Returns `None` if neither header is usable.
+#[must_use]
+pub fn retry_after(headers: &reqwest::header::HeaderMap, now: SystemTime) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+
+    if let Some(secs) = header(RETRY_AFTER_HEADER) {
+        return Some(Duration::from_secs(secs));
+    }
+    let reset = header(RATE_LIMIT_RESET_HEADER)?;
+    let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now)))
+}
+
+/// Returns the message for a request to `url` that stayed rate limited,
+/// with the wait reported by the server and when the limit resets.
+#[must_use]
+pub fn rate_limit_message(url: &str, wait: Option<Duration>, now: SystemTime) -> String {
+    let Some(wait) = wait else {
+        return format!("Rate limited by {url}; try again later");
+    };
+    let reset = (now + wait)
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format!(
+        "Rate limited by {url}; retry in {} seconds (limit resets at {})",
+        wait.as_secs(),
+        crate::toolchain::paths::format_timestamp_iso8601(reset)
+    )
+}
+
+/// Sends the request built by `request`, retrying while it is rate limited.
+///
+/// `what` names the fetched resource in errors, e.g. "manifest". Each wait
+/// is announced once on stderr. The last response is returned as is, so
+/// callers still handle a persisting rate limit themselves, e.g. with
+/// [`rate_limit_message`].
+///
+/// # Errors
+///
+/// Returns a network error if a request cannot be sent.
+pub async fn send_with_backoff(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    what: &str,
+    url: &str,
+) -> Result<reqwest::Response> {
+    send_with_retries(request, what, url, rate_limit_wait_cap(), output::warn).await
+}
+
+/// Implements [`send_with_backoff`] with the longest wait `cap` and
+/// `notify` receiving the progress message before each wait.
+async fn send_with_retries(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    what: &str,
+    url: &str,
+    cap: Option<Duration>,
+    notify: impl Fn(String),
+) -> Result<reqwest::Response> {
+    let mut retries = 0;
+    loop {
+        debug!("GET {url}");
+        let response = request().send().await.map_err(|e| {
+            anyhow::Error::new(e).context(InfsError::network_error(format!(
+                "Failed to fetch {what} from {url}"
+            )))
+        })?;
+        debug!("{url}: HTTP {}", response.status());
+
+        if retries == MAX_RATE_LIMIT_RETRIES
+            || !is_rate_limited(response.status(), response.headers())
+        {
+            return Ok(response);
+        }
+        let Some(cap) = cap else {
+            return Ok(response);
+        };
+        let Some(wait) = retry_after(response.headers(), SystemTime::now()) else {
+            return Ok(response);
+        };
+        if wait > cap {
+            debug!(
+                "Rate limited for {} s, longer than {} s",
+                wait.as_secs(),
+                cap.as_secs()
+            );
+            return Ok(response);
+        }
+
+        retries += 1;
+        notify(format!(
+            "rate limited by {url}, retrying in {}s\u{2026} ({retries}/{MAX_RATE_LIMIT_RETRIES})",
+            wait.as_secs()
+        ));
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers the requests on a local port with `responses` in turn, each a
+    /// status line and extra header lines, then with `200 OK`. Returns the
+    /// URL and the number of requests served.
+    fn serve_script(responses: Vec<&'static str>) -> (String, Arc<Mutex<usize>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!(
+            "http://{}/releases.json",
+            listener.local_addr().expect("Should have address")
+        );
+        let count = Arc::new(Mutex::new(0));
+        let served = count.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().expect("Should clone"));
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let mut served = served.lock().expect("Should lock");
+                let head = responses.get(*served).copied().unwrap_or("200 OK");
+                *served += 1;
+                let response =
+                    format!("HTTP/1.1 {head}\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]");
+                stream.write_all(response.as_bytes()).ok();
+            }
+        });
+        (url, count)
+    }
+
+    /// Sends a GET to `url` with `cap`, returning the final status and the
+    /// progress messages.
+    async fn get(url: &str, cap: Option<Duration>) -> (u16, Vec<String>) {
+        let client = reqwest::Client::new();
+        let notices = Mutex::new(Vec::new());
+        let response = send_with_retries(
+            || client.get(url),
+            "manifest",
+            url,
+            cap,
+            |message| {
+                notices.lock().expect("Should lock").push(message);
+            },
+        )
+        .await
+        .expect("Should send");
+        (
+            response.status().as_u16(),
+            notices.into_inner().expect("Should lock"),
+        )
+    }
+
+    #[tokio::test]
+    async fn rate_limited_request_is_retried_after_wait() {
+        let (url, served) = serve_script(vec!["403 Forbidden\r\nRetry-After: 0"]);
+
+        let (status, notices) = get(&url, Some(Duration::from_mins(1))).await;
+
+        assert_eq!(status, 200);
+        assert_eq!(*served.lock().unwrap(), 2);
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].contains("retrying in 0s"), "{notices:?}");
+    }
+
+    #[tokio::test]
+    async fn persisting_rate_limit_gives_up_after_retries() {
+        let limited = "429 Too Many Requests\r\nRetry-After: 0";
+        let (url, served) = serve_script(vec![limited; 5]);
+
+        let (status, notices) = get(&url, Some(Duration::from_mins(1))).await;
+
+        assert_eq!(status, 429);
+        assert_eq!(*served.lock().unwrap(), 1 + MAX_RATE_LIMIT_RETRIES as usize);
+        assert_eq!(notices.len(), MAX_RATE_LIMIT_RETRIES as usize);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_not_retried_without_cap_or_beyond_it() {
+        let (url, served) = serve_script(vec!["429 Too Many Requests\r\nRetry-After: 0"]);
+        let (status, notices) = get(&url, None).await;
+        assert_eq!(status, 429);
+        assert_eq!(*served.lock().unwrap(), 1);
+        assert!(notices.is_empty());
+
+        let (url, served) = serve_script(vec!["429 Too Many Requests\r\nRetry-After: 120"]);
+        let (status, notices) = get(&url, Some(Duration::from_mins(1))).await;
+        assert_eq!(status, 429);
+        assert_eq!(*served.lock().unwrap(), 1);
+        assert!(notices.is_empty());
+    }
+
+    #[test]
+    fn forbidden_is_a_rate_limit_only_with_rate_limit_headers() {
+        let forbidden = reqwest::StatusCode::FORBIDDEN;
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert!(!is_rate_limited(forbidden, &headers));
+        assert!(is_rate_limited(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            &headers
+        ));
+
+        headers.insert("X-RateLimit-Remaining", "12".parse().unwrap());
+        assert!(!is_rate_limited(forbidden, &headers));
+        headers.insert("X-RateLimit-Remaining", "0".parse().unwrap());
+        assert!(is_rate_limited(forbidden, &headers));
+    }
+
+    #[test]
+    fn rate_limit_message_includes_reset_time() {
+        let now = UNIX_EPOCH + Duration::from_hours(24);
+        let message = rate_limit_message("https://example.com", Some(Duration::from_mins(1)), now);
+        assert!(message.contains("retry in 60 seconds"), "{message}");
+        assert!(
+            message.contains("resets at 1970-01-02T00:01:00Z"),
+            "{message}"
+        );
+
+        let message = rate_limit_message("https://example.com", None, now);
+        assert!(message.contains("try again later"), "{message}");
+    }
+
+    #[test]
+    fn retry_after_parses_seconds() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("Retry-After", "60".parse().unwrap());
+        assert_eq!(
+            retry_after(&headers, SystemTime::now()),
+            Some(Duration::from_mins(1))
+        );
+    }
+
+    #[test]
+    fn retry_after_falls_back_to_rate_limit_reset() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("X-RateLimit-Reset", "1700000045".parse().unwrap());
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(45)));
+
+        headers.insert("X-RateLimit-Reset", "1699999990".parse().unwrap());
+        assert_eq!(retry_after(&headers, now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_ignores_unusable_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers, SystemTime::now()), None);
+
+        headers.insert(
+            "Retry-After",
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers, SystemTime::now()), None);
+    }
+
+    #[test]
+    fn parse_timeout_secs_accepts_and_clamps_values() {
+        assert_eq!(parse_timeout_secs("120"), Some(120));
+        assert_eq!(parse_timeout_secs(" 90\n"), Some(90));
+        assert_eq!(parse_timeout_secs("0"), Some(MIN_TIMEOUT_SECS));
+        assert_eq!(parse_timeout_secs("1"), Some(MIN_TIMEOUT_SECS));
+        assert_eq!(parse_timeout_secs("86400"), Some(MAX_TIMEOUT_SECS));
+    }
+
+    #[test]
+    fn parse_timeout_secs_rejects_invalid_values() {
+        assert_eq!(parse_timeout_secs(""), None);
+        assert_eq!(parse_timeout_secs("-5"), None);
+        assert_eq!(parse_timeout_secs("1.5"), None);
+        assert_eq!(parse_timeout_secs("30s"), None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn timeouts_come_from_separate_variables() {
+        // SAFETY: This test runs serially and restores the env vars at the end.
+        unsafe {
+            std::env::set_var(HTTP_TIMEOUT_ENV, "90");
+            std::env::remove_var(DOWNLOAD_TIMEOUT_ENV);
+        }
+        let http = http_timeout();
+        let download = download_timeout();
+
+        // SAFETY: See above.
+        unsafe {
+            std::env::set_var(HTTP_TIMEOUT_ENV, "not-a-number");
+            std::env::set_var(DOWNLOAD_TIMEOUT_ENV, "99999");
+        }
+        let invalid_http = http_timeout();
+        let clamped_download = download_timeout();
+
+        // SAFETY: Cleanup - restoring previous state.
+        unsafe {
+            std::env::remove_var(HTTP_TIMEOUT_ENV);
+            std::env::remove_var(DOWNLOAD_TIMEOUT_ENV);
+        }
+
+        assert_eq!(http, Duration::from_secs(90));
+        assert_eq!(download, Duration::from_secs(DEFAULT_DOWNLOAD_TIMEOUT_SECS));
+        assert_eq!(invalid_http, Duration::from_secs(DEFAULT_HTTP_TIMEOUT_SECS));
+        assert_eq!(clamped_download, Duration::from_secs(MAX_TIMEOUT_SECS));
+    }
+}
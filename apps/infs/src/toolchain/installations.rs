@@ -0,0 +1,324 @@
+//! Detection of duplicate and legacy installations.
+//!
+//! Toolchains used to live in `~/.infs` (or `INFS_HOME`) before the root
+//! moved to `~/.inference` (or `INFERENCE_HOME`). Machines that used both
+//! can end up with two roots, several `infc` binaries on `PATH`, or more
+//! than one PATH block in a shell profile, so which toolchain runs depends
+//! on the shell. The checks here report each of these with a remedy; they
+//! are part of [`run_all_checks`](super::doctor::run_all_checks).
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use super::doctor::DoctorCheck;
+use super::{Platform, ToolchainPaths};
+
+/// Environment variable that selected the legacy root.
+pub const LEGACY_HOME_ENV: &str = "INFS_HOME";
+
+/// Directory name of the legacy root in the home directory.
+pub const LEGACY_ROOT_DIR: &str = ".infs";
+
+/// Returns the legacy root: `INFS_HOME` if set, otherwise `~/.infs`.
+#[must_use]
+pub fn legacy_root() -> Option<PathBuf> {
+    match std::env::var_os(LEGACY_HOME_ENV) {
+        Some(home) if !home.is_empty() => Some(PathBuf::from(home)),
+        _ => dirs::home_dir().map(|home| home.join(LEGACY_ROOT_DIR)),
+    }
+}
+
+/// Returns the versions installed in `legacy` but not in `paths`, sorted.
+///
+/// These would be lost if the legacy root were removed.
+#[must_use]
+pub fn unique_legacy_versions(legacy: &Path, paths: &ToolchainPaths) -> Vec<String> {
+    ToolchainPaths::with_root(legacy.to_path_buf())
+        .list_installed_versions()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|version| !paths.is_version_installed(version))
+        .collect()
+}
+
+/// Checks for a toolchain root at the legacy location `legacy`.
+#[must_use]
+pub fn check_legacy_root(legacy: &Path, paths: &ToolchainPaths) -> DoctorCheck {
+    const NAME: &str = "Legacy installation";
+    if !legacy.is_dir() || legacy == paths.root {
+        return DoctorCheck::ok(NAME, format!("No toolchains in {}", legacy.display()));
+    }
+    let unique = unique_legacy_versions(legacy, paths);
+    if unique.is_empty() {
+        DoctorCheck::warning(
+            NAME,
+            format!(
+                "Found old toolchain root {}. Every version in it is also in {}; remove {} to avoid confusion.",
+                legacy.display(),
+                paths.root.display(),
+                legacy.display()
+            ),
+        )
+    } else {
+        DoctorCheck::warning(
+            NAME,
+            format!(
+                "Found old toolchain root {} with versions missing from {}: {}. Run 'infs install <version>' for the ones you need, then remove {}.",
+                legacy.display(),
+                paths.root.display(),
+                unique.join(", "),
+                legacy.display()
+            ),
+        )
+    }
+}
+
+/// Returns every `binary` reachable through a `PATH`-style value, in
+/// lookup order.
+///
+/// Entries resolving to the same file (for example through a symlinked
+/// directory) are listed once.
+#[must_use]
+pub fn binaries_on_path(path_var: &OsStr, binary: &str) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = Vec::new();
+    let mut resolved: Vec<PathBuf> = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        let candidate = dir.join(binary);
+        if !candidate.is_file() {
+            continue;
+        }
+        let real = candidate
+            .canonicalize()
+            .unwrap_or_else(|_| candidate.clone());
+        if !resolved.contains(&real) {
+            resolved.push(real);
+            found.push(candidate);
+        }
+    }
+    found
+}
+
+/// Checks that at most one `infc` is reachable through `path_var`.
+#[must_use]
+pub fn check_single_infc(path_var: &OsStr, infc: &str) -> DoctorCheck {
+    const NAME: &str = "infc on PATH";
+    let found = binaries_on_path(path_var, infc);
+    match found.as_slice() {
+        [] => DoctorCheck::ok(NAME, "No infc on PATH"),
+        [single] => DoctorCheck::ok(NAME, format!("Only {}", single.display())),
+        [first, ..] => {
+            let list: Vec<String> = found
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            DoctorCheck::warning(
+                NAME,
+                format!(
+                    "Found {} copies: {}. Shells use {}; remove the other directories from PATH.",
+                    found.len(),
+                    list.join(", "),
+                    first.display()
+                ),
+            )
+        }
+    }
+}
+
+/// Returns the shell profile files that `infs` may have edited.
+#[cfg(unix)]
+#[must_use]
+pub fn profile_files(home: &Path) -> Vec<PathBuf> {
+    use super::shell::Shell;
+
+    [Shell::Bash, Shell::Zsh, Shell::Fish]
+        .into_iter()
+        .flat_map(|shell| shell.profile_candidates(home))
+        .collect()
+}
+
+/// Checks that no profile in `profiles` has more than one Inference PATH
+/// block.
+#[cfg(unix)]
+#[must_use]
+pub fn check_profile_blocks(profiles: &[PathBuf]) -> DoctorCheck {
+    use super::shell::INFERENCE_MARKER;
+
+    const NAME: &str = "Shell profiles";
+    let duplicated: Vec<String> = profiles
+        .iter()
+        .filter_map(|profile| {
+            let content = std::fs::read_to_string(profile).ok()?;
+            let blocks = content.matches(INFERENCE_MARKER).count();
+            (blocks > 1).then(|| format!("{} ({blocks} blocks)", profile.display()))
+        })
+        .collect();
+    if duplicated.is_empty() {
+        DoctorCheck::ok(NAME, "At most one Inference PATH block per profile")
+    } else {
+        DoctorCheck::warning(
+            NAME,
+            format!(
+                "Multiple '{INFERENCE_MARKER}' PATH blocks in {}. Keep the one for the current toolchain root and delete the others.",
+                duplicated.join(", ")
+            ),
+        )
+    }
+}
+
+/// Runs the installation checks against the current environment.
+#[must_use]
+pub fn run_checks() -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+    match (ToolchainPaths::new(), legacy_root()) {
+        (Ok(paths), Some(legacy)) => checks.push(check_legacy_root(&legacy, &paths)),
+        _ => checks.push(DoctorCheck::warning(
+            "Legacy installation",
+            "Cannot determine the toolchain directories",
+        )),
+    }
+
+    let ext = Platform::detect().map_or("", |platform| platform.executable_extension());
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    checks.push(check_single_infc(&path_var, &format!("infc{ext}")));
+
+    #[cfg(unix)]
+    {
+        let profiles = dirs::home_dir()
+            .map(|home| profile_files(&home))
+            .unwrap_or_default();
+        checks.push(check_profile_blocks(&profiles));
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use crate::toolchain::doctor::DoctorCheckStatus;
+
+    fn install(root: &Path, version: &str) {
+        std::fs::create_dir_all(root.join("toolchains").join(version).join("bin")).unwrap();
+    }
+
+    #[test]
+    fn legacy_root_is_reported_with_unique_versions() {
+        let dir = temp_test_dir("installations_legacy");
+        let paths = ToolchainPaths::with_root(dir.join(".inference"));
+        let legacy = dir.join(".infs");
+        install(&paths.root, "0.2.0");
+        install(&legacy, "0.1.0");
+        install(&legacy, "0.2.0");
+
+        assert_eq!(unique_legacy_versions(&legacy, &paths), ["0.1.0"]);
+        let check = check_legacy_root(&legacy, &paths);
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(check.message.contains("missing from"), "{}", check.message);
+        assert!(check.message.contains("0.1.0"), "{}", check.message);
+
+        install(&paths.root, "0.1.0");
+        assert!(unique_legacy_versions(&legacy, &paths).is_empty());
+        let check = check_legacy_root(&legacy, &paths);
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(check.message.contains("is also in"), "{}", check.message);
+
+        std::fs::remove_dir_all(&legacy).unwrap();
+        assert_eq!(
+            check_legacy_root(&legacy, &paths).status,
+            DoctorCheckStatus::Ok
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_root_equal_to_current_root_is_ignored() {
+        let dir = temp_test_dir("installations_same_root");
+        let paths = ToolchainPaths::with_root(dir.clone());
+        install(&dir, "0.1.0");
+        assert_eq!(
+            check_legacy_root(&dir, &paths).status,
+            DoctorCheckStatus::Ok
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn multiple_infc_on_path_are_reported() {
+        let dir = temp_test_dir("installations_path");
+        let first = dir.join("first");
+        let second = dir.join("second");
+        let empty = dir.join("empty");
+        for bin in [&first, &second, &empty] {
+            std::fs::create_dir_all(bin).unwrap();
+        }
+        std::fs::write(first.join("infc"), "").unwrap();
+        std::fs::write(second.join("infc"), "").unwrap();
+
+        let single = std::env::join_paths([&empty, &first]).unwrap();
+        assert_eq!(binaries_on_path(&single, "infc"), [first.join("infc")]);
+        assert_eq!(
+            check_single_infc(&single, "infc").status,
+            DoctorCheckStatus::Ok
+        );
+
+        let both = std::env::join_paths([&second, &empty, &first, &second]).unwrap();
+        assert_eq!(
+            binaries_on_path(&both, "infc"),
+            [second.join("infc"), first.join("infc")]
+        );
+        let check = check_single_infc(&both, "infc");
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(
+            check.message.starts_with("Found 2 copies"),
+            "{}",
+            check.message
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_path_entries_count_once() {
+        let dir = temp_test_dir("installations_symlink");
+        let bin = dir.join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("infc"), "").unwrap();
+        std::os::unix::fs::symlink(&bin, dir.join("alias")).unwrap();
+
+        let path_var = std::env::join_paths([bin.clone(), dir.join("alias")]).unwrap();
+        assert_eq!(binaries_on_path(&path_var, "infc"), [bin.join("infc")]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn profiles_with_several_path_blocks_are_reported() {
+        use crate::toolchain::shell::INFERENCE_MARKER;
+
+        let home = temp_test_dir("installations_profiles");
+        let block =
+            |root: &str| format!("\n{INFERENCE_MARKER}\nexport PATH=\"{root}/bin:$PATH\"\n");
+        std::fs::write(home.join(".bashrc"), block("/home/u/.inference")).unwrap();
+        let profiles = profile_files(&home);
+        assert!(profiles.contains(&home.join(".zshrc")));
+        assert_eq!(
+            check_profile_blocks(&profiles).status,
+            DoctorCheckStatus::Ok
+        );
+
+        std::fs::write(
+            home.join(".zshrc"),
+            block("/home/u/.infs") + &block("/home/u/.inference"),
+        )
+        .unwrap();
+        let check = check_profile_blocks(&profiles);
+        assert_eq!(check.status, DoctorCheckStatus::Warning);
+        assert!(
+            check.message.contains(".zshrc (2 blocks)"),
+            "{}",
+            check.message
+        );
+        assert!(!check.message.contains(".bashrc"), "{}", check.message);
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
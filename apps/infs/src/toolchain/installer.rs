@@ -0,0 +1,1381 @@
+//! Toolchain installation pipeline.
+//!
+//! [`Installer`] performs every step of installing a toolchain: resolving
+//! the version from the release manifest, downloading the archive, verifying
+//! its checksum and signature, extracting it, and registering it (metadata,
+//! default version, symlinks). Progress is reported through an
+//! [`InstallCallback`], so the CLI `install` command and the TUI share one
+//! implementation and differ only in how they present it.
+//!
+//! ## Staged Extraction
+//!
+//! Archives are extracted into `downloads/<version>.partial` and moved into
+//! `toolchains/<version>` only once extraction and permission fixes have
+//! succeeded. A failed or interrupted installation therefore never leaves a
+//! directory that looks like an installed toolchain.
+//!
+//! ## Resuming
+//!
+//! [`InstallOptions::resume_from`] restarts a failed installation at a later
+//! [`InstallPhase`]. A downloaded archive still on disk is reused instead of
+//! being downloaded again, and an extracted toolchain is only linked.
+//!
+//! A fresh download is hashed as it streams in, and its signature is fetched
+//! at the same time, so verifying it does not read the archive again. Only a
+//! reused archive is hashed from disk.
+//!
+//! Every entry point holds the [`ToolchainLock`] while it changes the
+//! toolchain directory, so concurrent `infs` processes install one at a
+//! time. [`Installer::install`] takes it only once the archive is
+//! downloaded and verified, so a slow download does not block other
+//! processes; the lock is awaited on a blocking thread.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+
+use crate::config::InfsConfig;
+use crate::errors::InfsError;
+use crate::logging::debug;
+use crate::toolchain::bundle::{read_manifest, unpack_bundle};
+use crate::toolchain::lock::{self, ToolchainLock};
+use crate::toolchain::manifest::{
+    Manifest, fallback_warning, fetch_manifest, libc_mismatch_warning, resolve_version,
+};
+use crate::toolchain::paths::{InstallSource, ToolchainMetadata};
+use crate::toolchain::signature::{
+    SIGNATURE_EXTENSION, SignatureStatus, check_signature, fetch_signature, no_key_warning,
+    public_key, unsigned_warning,
+};
+use crate::toolchain::verify::verify_digest;
+use crate::toolchain::{
+    Platform, ProgressCallback, ProgressEvent, ToolchainPaths, download_file_with_callback,
+    extract_archive_with_progress, preserves_permissions, set_executable_permissions,
+    verify_checksum,
+};
+
+/// Suffix of the staging directory an archive is extracted into.
+const STAGING_SUFFIX: &str = ".partial";
+
+/// A step of the toolchain installation pipeline.
+///
+/// Variants are declared in execution order, so phases can be compared to
+/// decide which steps to skip when resuming a failed installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InstallPhase {
+    /// Resolving the artifact from the release manifest.
+    FetchingManifest,
+    /// Downloading the toolchain archive.
+    Downloading,
+    /// Verifying the archive checksum and signature.
+    Verifying,
+    /// Extracting the archive into the toolchains directory.
+    Extracting,
+    /// Setting the default version and updating symlinks.
+    Linking,
+}
+
+impl InstallPhase {
+    /// All phases in execution order.
+    pub const ALL: [InstallPhase; 5] = [
+        InstallPhase::FetchingManifest,
+        InstallPhase::Downloading,
+        InstallPhase::Verifying,
+        InstallPhase::Extracting,
+        InstallPhase::Linking,
+    ];
+
+    /// Returns a short human-readable label for the phase.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            InstallPhase::FetchingManifest => "Manifest",
+            InstallPhase::Downloading => "Download",
+            InstallPhase::Verifying => "Verify",
+            InstallPhase::Extracting => "Extract",
+            InstallPhase::Linking => "Link",
+        }
+    }
+
+    /// Returns whether a failure in this phase can be retried.
+    ///
+    /// A checksum mismatch means the artifact itself is bad, so retrying
+    /// verification against the same file cannot succeed.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        !matches!(self, InstallPhase::Verifying)
+    }
+}
+
+/// Progress reported by [`Installer`].
+#[derive(Debug, Clone)]
+pub enum InstallEvent {
+    /// A new phase of the pipeline has begun.
+    PhaseEntered(InstallPhase),
+    /// A step has started (e.g., "Verifying checksum").
+    StepStarted(String),
+    /// A step has finished.
+    StepCompleted(String),
+    /// Something the user should know about that does not stop installation.
+    Warning(String),
+    /// Progress of the archive download.
+    Download(ProgressEvent),
+    /// Number of archive entries extracted so far.
+    ExtractProgress {
+        /// Entries processed so far.
+        processed: u64,
+        /// Total number of entries in the archive.
+        total: u64,
+    },
+}
+
+/// Callback receiving [`InstallEvent`]s.
+///
+/// Wrapped in `Arc` so the download progress can be forwarded to it from
+/// async code.
+pub type InstallCallback = Arc<dyn Fn(InstallEvent) + Send + Sync>;
+
+/// Settings that change how a toolchain is installed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstallOptions {
+    /// Consider prerelease versions when resolving "latest" or a range.
+    pub include_prerelease: bool,
+    /// Make the toolchain the default even if another default is set.
+    ///
+    /// The first installed toolchain, and one installed while no default is
+    /// set, always becomes the default.
+    pub make_default: bool,
+    /// Phase to restart from after a failed attempt.
+    pub resume_from: Option<InstallPhase>,
+}
+
+/// A toolchain to install from the release manifest.
+#[derive(Debug, Clone, Default)]
+pub struct InstallRequest {
+    /// Version or semver range to install. `None` or "latest" selects the
+    /// latest stable version.
+    pub version: Option<String>,
+    pub options: InstallOptions,
+}
+
+/// Outcome of a successful installation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledToolchain {
+    /// The installed version.
+    pub version: String,
+    /// Whether the version was already installed, so nothing was extracted.
+    pub already_installed: bool,
+    /// Whether this is the only installed toolchain.
+    pub first_install: bool,
+    /// The default version before installation.
+    pub previous_default: Option<String>,
+    /// Whether the toolchain was made the default (and symlinked).
+    pub made_default: bool,
+}
+
+/// Installs toolchains into a toolchain directory.
+pub struct Installer {
+    paths: ToolchainPaths,
+    platform: Platform,
+    config: InfsConfig,
+    manifest: Option<Manifest>,
+}
+
+impl Installer {
+    /// Creates an installer for the current platform and the default
+    /// toolchain directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform is unsupported, the toolchain
+    /// directory cannot be determined, or `config.toml` is invalid.
+    pub fn new() -> Result<Self> {
+        let platform = Platform::detect().context("Failed to detect platform")?;
+        let paths = ToolchainPaths::new().context("Failed to initialize toolchain paths")?;
+        Self::with_paths(paths, platform)
+    }
+
+    /// Creates an installer for `platform` that installs into `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `config.toml` under `paths` is invalid.
+    pub fn with_paths(paths: ToolchainPaths, platform: Platform) -> Result<Self> {
+        let config = InfsConfig::load(&paths)?;
+        Ok(Self {
+            paths,
+            platform,
+            config,
+            manifest: None,
+        })
+    }
+
+    /// Resolves versions against `manifest` instead of fetching it.
+    #[must_use]
+    pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Returns the toolchain directory this installer writes to.
+    #[must_use]
+    pub fn paths(&self) -> &ToolchainPaths {
+        &self.paths
+    }
+
+    /// Installs a toolchain from the release manifest.
+    ///
+    /// # Process
+    ///
+    /// 1. Resolve the version and the artifact for the platform
+    /// 2. Download the archive with progress reporting, and its signature
+    /// 3. Verify the SHA256 checksum and the minisign signature, if published
+    /// 4. Extract into a staging directory and move it into place
+    /// 5. Set as default if it's the first installation
+    ///
+    /// An already installed version is not downloaded again, but becomes the
+    /// default if no default is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be fetched, no artifact
+    /// matches, or any later step fails. A corrupt archive is removed so the
+    /// next attempt downloads it afresh. A signature that cannot be fetched
+    /// is a download error and keeps the archive.
+    pub async fn install(
+        &self,
+        request: InstallRequest,
+        progress: InstallCallback,
+    ) -> Result<InstalledToolchain> {
+        let options = request.options;
+        let resume_from = options
+            .resume_from
+            .unwrap_or(InstallPhase::FetchingManifest);
+
+        progress(InstallEvent::PhaseEntered(InstallPhase::FetchingManifest));
+        self.paths
+            .ensure_directories()
+            .context("Failed to create toolchain directories")?;
+        let step = String::from("Fetching release manifest");
+        progress(InstallEvent::StepStarted(step.clone()));
+        let manifest = match &self.manifest {
+            Some(manifest) => manifest.clone(),
+            None => fetch_manifest()
+                .await
+                .context("Failed to download release manifest")?,
+        };
+        let entry = resolve_version(
+            &manifest,
+            request.version.as_deref(),
+            options.include_prerelease,
+        )?;
+        let (artifact, fallback) = entry
+            .find_artifact_with_fallback(self.platform)
+            .with_context(|| {
+                format!(
+                    "No artifact found for platform {} in version {}",
+                    self.platform, entry.version
+                )
+            })?;
+        let version = entry.version.clone();
+        debug!(
+            "Resolved {} to {version}: {}",
+            request.version.as_deref().unwrap_or("latest"),
+            artifact.url
+        );
+        progress(InstallEvent::StepCompleted(step));
+
+        if fallback {
+            progress(InstallEvent::Warning(fallback_warning(
+                self.platform,
+                artifact,
+            )));
+        }
+        if let Some(warning) = libc_mismatch_warning(self.platform, artifact) {
+            progress(InstallEvent::Warning(warning));
+        }
+
+        let installed = self.paths.is_version_installed(&version);
+        if installed && resume_from < InstallPhase::Linking {
+            let _lock = self.lock_async(&progress).await?;
+            return self.already_installed(version);
+        }
+
+        let archive_path = self.paths.download_path(artifact.filename());
+        if !installed {
+            let reuse = resume_from > InstallPhase::Downloading && archive_path.exists();
+            let (digest, signature) = self
+                .download(&artifact.url, &version, &archive_path, reuse, &progress)
+                .await?;
+
+            progress(InstallEvent::PhaseEntered(InstallPhase::Verifying));
+            let verified = self.verify_download(
+                &archive_path,
+                &artifact.url,
+                &artifact.sha256,
+                digest.as_deref(),
+                signature.as_deref(),
+                &progress,
+            );
+            let signature = match verified {
+                Ok(signature) => signature,
+                Err(e) => {
+                    std::fs::remove_file(&archive_path).ok();
+                    return Err(e);
+                }
+            };
+
+            let _lock = self.lock_async(&progress).await?;
+            // Another process may have installed it while this one downloaded.
+            if self.paths.is_version_installed(&version) {
+                std::fs::remove_file(&archive_path).ok();
+                return self.already_installed(version);
+            }
+            let metadata = ToolchainMetadata::now().with_signature(signature);
+            self.extract(&archive_path, &version, &metadata, &progress)?;
+            let installed = self.link(&version, options, &progress)?;
+            std::fs::remove_file(&archive_path).ok();
+            return Ok(installed);
+        }
+
+        let _lock = self.lock_async(&progress).await?;
+        let installed = self.link(&version, options, &progress)?;
+        std::fs::remove_file(&archive_path).ok();
+        Ok(installed)
+    }
+
+    /// Downloads the archive at `url` to `archive_path`, or reuses it if
+    /// `reuse` is set, and fetches its signature.
+    ///
+    /// Returns the SHA256 of a fresh download, which is computed while it
+    /// streams in (a reused archive has to be read again to verify it), and
+    /// the signature, if any.
+    async fn download(
+        &self,
+        url: &str,
+        version: &str,
+        archive_path: &Path,
+        reuse: bool,
+        progress: &InstallCallback,
+    ) -> Result<(Option<String>, Option<String>)> {
+        let (digest, signature) = if reuse {
+            debug!("Reusing downloaded archive {}", archive_path.display());
+            (None, self.fetch_signature(url).await)
+        } else {
+            progress(InstallEvent::PhaseEntered(InstallPhase::Downloading));
+            let step = format!("Downloading toolchain v{version}");
+            progress(InstallEvent::StepStarted(step.clone()));
+            let forward = progress.clone();
+            let callback: ProgressCallback =
+                Arc::new(move |event| forward(InstallEvent::Download(event)));
+            let (outcome, signature) = tokio::join!(
+                download_file_with_callback(url, archive_path, callback),
+                self.fetch_signature(url)
+            );
+            let outcome = outcome.context("Failed to download toolchain archive")?;
+            progress(InstallEvent::StepCompleted(step));
+            (Some(outcome.sha256), signature)
+        };
+        // Failing to fetch the signature says nothing about the archive, so
+        // it is kept and the failure reported before verifying.
+        let signature = signature.map_err(|e| {
+            e.context(InfsError::download_error(
+                "Failed to download toolchain signature",
+            ))
+        })?;
+        Ok((digest, signature))
+    }
+
+    /// Installs a toolchain from an archive already on disk as `version`.
+    ///
+    /// The archive is checked against `checksum`, if given, and against a
+    /// `.minisig` file next to it, if present. The archive itself is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is missing, the checksum or signature
+    /// does not match, or extraction fails.
+    pub fn install_archive(
+        &self,
+        archive_path: &Path,
+        version: &str,
+        checksum: Option<&str>,
+        options: InstallOptions,
+        progress: &InstallCallback,
+    ) -> Result<InstalledToolchain> {
+        if !archive_path.is_file() {
+            bail!("Archive not found: {}", archive_path.display());
+        }
+        self.paths
+            .ensure_directories()
+            .context("Failed to create toolchain directories")?;
+        let _lock = self.lock(progress)?;
+        if self.paths.is_version_installed(version) {
+            return self.already_installed(version.to_string());
+        }
+
+        progress(InstallEvent::PhaseEntered(InstallPhase::Verifying));
+        if let Some(expected) = checksum {
+            let step = String::from("Verifying checksum");
+            progress(InstallEvent::StepStarted(step.clone()));
+            verify_checksum(archive_path, expected)?;
+            progress(InstallEvent::StepCompleted(step));
+        }
+
+        let mut signature_path = archive_path.as_os_str().to_owned();
+        signature_path.push(format!(".{SIGNATURE_EXTENSION}"));
+        let signature_path = Path::new(&signature_path);
+        let signature = if signature_path.is_file() {
+            Some(
+                std::fs::read_to_string(signature_path)
+                    .with_context(|| format!("Failed to read {}", signature_path.display()))?,
+            )
+        } else {
+            None
+        };
+        let status = self.check_signature(
+            archive_path,
+            signature.as_deref(),
+            &archive_path.display().to_string(),
+            progress,
+        )?;
+
+        let metadata = ToolchainMetadata::now()
+            .with_signature(status)
+            .with_source(InstallSource::Archive);
+        self.extract(archive_path, version, &metadata, progress)?;
+        self.link(version, options, progress)
+    }
+
+    /// Installs a toolchain from a bundle made by `infs toolchain export`.
+    ///
+    /// Every file is checked against the hashes in the bundle manifest before
+    /// the toolchain is moved into place, and the metadata records it as
+    /// [`InstallSource::Imported`]. An installed version with the same number
+    /// is replaced only if `force` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle is missing or invalid, was exported on
+    /// another platform, the version is installed and `force` is not set, or
+    /// extraction fails.
+    pub fn import_bundle(
+        &self,
+        bundle_path: &Path,
+        force: bool,
+        options: InstallOptions,
+        progress: &InstallCallback,
+    ) -> Result<InstalledToolchain> {
+        if !bundle_path.is_file() {
+            bail!("Bundle not found: {}", bundle_path.display());
+        }
+        self.paths
+            .ensure_directories()
+            .context("Failed to create toolchain directories")?;
+        let _lock = self.lock(progress)?;
+
+        progress(InstallEvent::PhaseEntered(InstallPhase::Verifying));
+        let step = String::from("Reading bundle manifest");
+        progress(InstallEvent::StepStarted(step.clone()));
+        let manifest = read_manifest(bundle_path)?;
+        let version = manifest.version;
+        if manifest.platform != self.platform.as_str() {
+            bail!(
+                "{} contains toolchain {version} for {}, but this machine is {}.",
+                bundle_path.display(),
+                manifest.platform,
+                self.platform
+            );
+        }
+        let replace = self.paths.is_version_installed(&version);
+        if replace && !force {
+            return Err(InfsError::invalid_arguments(format!(
+                "toolchain {version} is already installed; use --force to replace it"
+            ))
+            .into());
+        }
+        progress(InstallEvent::StepCompleted(step));
+
+        progress(InstallEvent::PhaseEntered(InstallPhase::Extracting));
+        let step = String::from("Extracting and verifying bundle");
+        progress(InstallEvent::StepStarted(step.clone()));
+        let staging = self.staging_dir(&version)?;
+        let report =
+            |processed, total| progress(InstallEvent::ExtractProgress { processed, total });
+        let unpacked = unpack_bundle(bundle_path, &staging, Some(&report))
+            .context("Failed to import toolchain bundle");
+        let manifest = match unpacked {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                std::fs::remove_dir_all(&staging).ok();
+                return Err(e);
+            }
+        };
+
+        if replace {
+            let toolchain_dir = self.paths.toolchain_dir(&version);
+            std::fs::remove_dir_all(&toolchain_dir)
+                .with_context(|| format!("Failed to remove {}", toolchain_dir.display()))?;
+        }
+        let metadata = ToolchainMetadata::now()
+            .with_signature(manifest.signature)
+            .with_source(InstallSource::Imported);
+        self.install_staged(&staging, &version, &metadata)?;
+        progress(InstallEvent::StepCompleted(step));
+
+        self.link(&version, options, progress)
+    }
+
+    /// Verifies a downloaded archive's checksum and published signature.
+    ///
+    /// `digest` is the SHA256 computed during the download; without it the
+    /// archive is hashed from disk. `signature` is the published signature,
+    /// if any.
+    fn verify_download(
+        &self,
+        archive_path: &Path,
+        url: &str,
+        sha256: &str,
+        digest: Option<&str>,
+        signature: Option<&str>,
+        progress: &InstallCallback,
+    ) -> Result<SignatureStatus> {
+        let step = String::from("Verifying checksum");
+        progress(InstallEvent::StepStarted(step.clone()));
+        match digest {
+            Some(digest) => verify_digest(archive_path, digest, sha256),
+            None => verify_checksum(archive_path, sha256),
+        }
+        .context("Checksum verification failed - download may be corrupted")?;
+        progress(InstallEvent::StepCompleted(step));
+
+        self.check_signature(archive_path, signature, url, progress)
+    }
+
+    /// Downloads the signature for an artifact, or returns `None` without a
+    /// request if no public key is configured to check it against.
+    async fn fetch_signature(&self, url: &str) -> Result<Option<String>> {
+        if public_key(&self.config.security).is_none() {
+            return Ok(None);
+        }
+        fetch_signature(url).await
+    }
+
+    /// Checks `archive_path` against `signature`, warning if it is unsigned.
+    ///
+    /// `source` names the archive in the warning.
+    fn check_signature(
+        &self,
+        archive_path: &Path,
+        signature: Option<&str>,
+        source: &str,
+        progress: &InstallCallback,
+    ) -> Result<SignatureStatus> {
+        let step = String::from("Verifying signature");
+        progress(InstallEvent::StepStarted(step.clone()));
+        let status = check_signature(archive_path, signature, &self.config.security)?;
+        debug!("Signature of {source}: {status:?}");
+        if status == SignatureStatus::Unsigned {
+            let warning = if public_key(&self.config.security).is_some() {
+                unsigned_warning(source)
+            } else {
+                no_key_warning(source)
+            };
+            progress(InstallEvent::Warning(warning));
+        }
+        progress(InstallEvent::StepCompleted(step));
+        Ok(status)
+    }
+
+    /// Extracts the archive into a staging directory, fixes permissions, and
+    /// moves it into place as `version` with its metadata.
+    fn extract(
+        &self,
+        archive_path: &Path,
+        version: &str,
+        metadata: &ToolchainMetadata,
+        progress: &InstallCallback,
+    ) -> Result<()> {
+        progress(InstallEvent::PhaseEntered(InstallPhase::Extracting));
+        let step = String::from("Extracting archive");
+        progress(InstallEvent::StepStarted(step.clone()));
+
+        let staging = self.staging_dir(version)?;
+        debug!(
+            "Extracting {} into {}",
+            archive_path.display(),
+            staging.display()
+        );
+
+        let report =
+            |processed, total| progress(InstallEvent::ExtractProgress { processed, total });
+        let staged = extract_archive_with_progress(archive_path, &staging, Some(&report))
+            .context("Failed to extract toolchain archive")
+            .and_then(|()| {
+                if preserves_permissions(archive_path) {
+                    Ok(())
+                } else {
+                    set_executable_permissions(&staging)
+                        .context("Failed to set executable permissions")
+                }
+            });
+        if let Err(e) = staged {
+            std::fs::remove_dir_all(&staging).ok();
+            return Err(e);
+        }
+
+        self.install_staged(&staging, version, metadata)?;
+        progress(InstallEvent::StepCompleted(step));
+        Ok(())
+    }
+
+    /// Returns an empty staging directory path for `version`, removing what
+    /// an earlier attempt left behind.
+    fn staging_dir(&self, version: &str) -> Result<std::path::PathBuf> {
+        let staging = self
+            .paths
+            .download_path(&format!("{version}{STAGING_SUFFIX}"));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)
+                .context("Failed to remove partially extracted toolchain")?;
+        }
+        Ok(staging)
+    }
+
+    /// Moves a staged toolchain into place as `version` and writes its
+    /// metadata.
+    fn install_staged(
+        &self,
+        staging: &Path,
+        version: &str,
+        metadata: &ToolchainMetadata,
+    ) -> Result<()> {
+        let toolchain_dir = self.paths.toolchain_dir(version);
+        debug!(
+            "Moving {} to {}",
+            staging.display(),
+            toolchain_dir.display()
+        );
+        std::fs::rename(staging, &toolchain_dir).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                staging.display(),
+                toolchain_dir.display()
+            )
+        })?;
+
+        if let Err(e) = self.paths.write_metadata(version, metadata) {
+            std::fs::remove_dir_all(&toolchain_dir).ok();
+            return Err(e.context("Failed to write toolchain metadata"));
+        }
+        Ok(())
+    }
+
+    /// Makes `version` the default and updates symlinks when it is the first
+    /// toolchain, no default is set, or [`InstallOptions::make_default`] is
+    /// set.
+    fn link(
+        &self,
+        version: &str,
+        options: InstallOptions,
+        progress: &InstallCallback,
+    ) -> Result<InstalledToolchain> {
+        progress(InstallEvent::PhaseEntered(InstallPhase::Linking));
+        let step = String::from("Configuring toolchain");
+        progress(InstallEvent::StepStarted(step.clone()));
+
+        let installed_versions = self
+            .paths
+            .list_installed_versions()
+            .context("Failed to list installed versions")?;
+        let first_install = installed_versions.len() == 1 && installed_versions[0] == version;
+        let previous_default = self
+            .paths
+            .get_default_version()
+            .context("Failed to get default version")?;
+
+        let made_default = first_install || previous_default.is_none() || options.make_default;
+        if made_default {
+            self.set_default(version)?;
+        }
+
+        progress(InstallEvent::StepCompleted(step));
+        Ok(InstalledToolchain {
+            version: version.to_string(),
+            already_installed: false,
+            first_install,
+            previous_default,
+            made_default,
+        })
+    }
+
+    /// Takes the toolchain lock, reporting waits as warnings.
+    fn lock(&self, progress: &InstallCallback) -> Result<ToolchainLock> {
+        ToolchainLock::acquire_with(&self.paths.lock_file(), lock::timeout(), |event| {
+            progress(InstallEvent::Warning(event.to_string()));
+        })
+    }
+
+    /// Takes the toolchain lock like [`Self::lock`], waiting on a blocking
+    /// thread so the async runtime keeps running.
+    async fn lock_async(&self, progress: &InstallCallback) -> Result<ToolchainLock> {
+        let path = self.paths.lock_file();
+        let progress = progress.clone();
+        tokio::task::spawn_blocking(move || {
+            ToolchainLock::acquire_with(&path, lock::timeout(), |event| {
+                progress(InstallEvent::Warning(event.to_string()));
+            })
+        })
+        .await
+        .context("Failed to wait for the toolchain lock")?
+    }
+
+    /// Reports an installed `version`, making it the default if no default
+    /// is set.
+    ///
+    /// A missing default (e.g., the default file was removed by hand) would
+    /// otherwise leave the toolchain unusable, so this is a recovery path.
+    fn already_installed(&self, version: String) -> Result<InstalledToolchain> {
+        let previous_default = self.paths.get_default_version()?;
+        let made_default = previous_default.is_none();
+        if made_default {
+            self.set_default(&version)?;
+        }
+        Ok(InstalledToolchain {
+            version,
+            already_installed: true,
+            first_install: false,
+            previous_default,
+            made_default,
+        })
+    }
+
+    fn set_default(&self, version: &str) -> Result<()> {
+        self.paths
+            .set_default_version(version)
+            .context("Failed to set default version")?;
+        self.paths
+            .update_symlinks(version)
+            .context("Failed to update symlinks")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    /// Returns a toolchain tar.gz laid out like CI artifacts.
+    fn toolchain_tar_gz() -> Vec<u8> {
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+
+        let ext = std::env::consts::EXE_SUFFIX;
+        for entry in [
+            format!("infc{ext}"),
+            format!("bin/inf-llc{ext}"),
+            format!("bin/rust-lld{ext}"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(14);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, entry, b"binary content".as_slice())
+                .expect("Should append file");
+        }
+
+        builder
+            .into_inner()
+            .expect("Should finish")
+            .finish()
+            .expect("Should compress")
+    }
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    /// Serves `files` (path -> body) over HTTP on a local port until the
+    /// test process exits. Unknown paths get `404 Not Found`.
+    ///
+    /// Returns the server's base URL and a log of requested paths.
+    fn serve(files: Vec<(&'static str, Vec<u8>)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        serve_responses(
+            files
+                .into_iter()
+                .map(|(path, body)| (path, "200 OK", body))
+                .collect(),
+        )
+    }
+
+    /// Like [`serve`], with the status line of each response.
+    fn serve_responses(
+        files: Vec<(&'static str, &'static str, Vec<u8>)>,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let base = format!(
+            "http://{}",
+            listener.local_addr().expect("Should have address")
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().expect("Should clone"));
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).ok();
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                    header.clear();
+                }
+
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                log.lock().expect("Should lock").push(path.clone());
+                let (status, body) = files
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .map_or(("404 Not Found", b"".as_slice()), |(_, status, body)| {
+                        (*status, body.as_slice())
+                    });
+                let head = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).ok();
+                stream.write_all(body).ok();
+            }
+        });
+
+        (base, requests)
+    }
+
+    /// Returns a manifest listing `version` with a Linux artifact at `base`.
+    fn manifest(base: &str, version: &str, sha256: &str) -> Manifest {
+        serde_json::from_value(serde_json::json!([{
+            "version": version,
+            "stable": true,
+            "files": [{
+                "url": format!("{base}/{version}/infc-linux-x64.tar.gz"),
+                "sha256": sha256,
+            }]
+        }]))
+        .expect("Should build manifest")
+    }
+
+    /// Returns a callback collecting events and the collected events.
+    fn recorder() -> (InstallCallback, Arc<Mutex<Vec<InstallEvent>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let callback: InstallCallback =
+            Arc::new(move |event| sink.lock().expect("Should lock").push(event));
+        (callback, events)
+    }
+
+    fn request(version: &str) -> InstallRequest {
+        InstallRequest {
+            version: Some(version.to_string()),
+            options: InstallOptions::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn install_downloads_verifies_and_registers_toolchain() {
+        let archive = toolchain_tar_gz();
+        let sha256 = sha256_hex(&archive);
+        let (base, requests) = serve(vec![("/0.1.0/infc-linux-x64.tar.gz", archive)]);
+        let home = temp_test_dir("installer_e2e");
+        let paths = ToolchainPaths::with_root(home.clone());
+        let installer = Installer::with_paths(paths, Platform::LinuxX64)
+            .expect("Should create installer")
+            .with_manifest(manifest(&base, "0.1.0", &sha256));
+        let (callback, events) = recorder();
+
+        let toolchain = installer
+            .install(request("0.1.0"), callback)
+            .await
+            .expect("Should install");
+
+        assert_eq!(
+            toolchain,
+            InstalledToolchain {
+                version: String::from("0.1.0"),
+                already_installed: false,
+                first_install: true,
+                previous_default: None,
+                made_default: true,
+            }
+        );
+        let paths = installer.paths();
+        let infc = format!("infc{}", std::env::consts::EXE_SUFFIX);
+        assert!(paths.binary_path("0.1.0", &infc).exists());
+        assert_eq!(
+            paths.read_metadata("0.1.0").map(|m| m.signature),
+            Some(SignatureStatus::Unsigned)
+        );
+        assert_eq!(
+            paths.get_default_version().expect("Should read default"),
+            Some(String::from("0.1.0"))
+        );
+        assert!(!paths.download_path("infc-linux-x64.tar.gz").exists());
+        assert!(!paths.download_path("0.1.0.partial").exists());
+        // Without a public key no signature is fetched.
+        assert_eq!(
+            *requests.lock().expect("Should lock"),
+            ["/0.1.0/infc-linux-x64.tar.gz"]
+        );
+
+        let phases: Vec<InstallPhase> = events
+            .lock()
+            .expect("Should lock")
+            .iter()
+            .filter_map(|event| match event {
+                InstallEvent::PhaseEntered(phase) => Some(*phase),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(phases, InstallPhase::ALL);
+
+        let (callback, _) = recorder();
+        let again = installer
+            .install(request("0.1.0"), callback)
+            .await
+            .expect("Should report installed version");
+        assert!(again.already_installed);
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[tokio::test]
+    async fn install_downloads_while_another_process_holds_the_lock() {
+        let archive = toolchain_tar_gz();
+        let sha256 = sha256_hex(&archive);
+        let (base, requests) = serve(vec![("/0.1.0/infc-linux-x64.tar.gz", archive)]);
+        let home = temp_test_dir("installer_locked");
+        let paths = ToolchainPaths::with_root(home.clone());
+        paths
+            .ensure_directories()
+            .expect("Should create directories");
+        let held = ToolchainLock::acquire_with(&paths.lock_file(), None, |_| {})
+            .expect("Should take the lock");
+        let installer = Installer::with_paths(paths, Platform::LinuxX64)
+            .expect("Should create installer")
+            .with_manifest(manifest(&base, "0.1.0", &sha256));
+        let (callback, _) = recorder();
+
+        let install = tokio::spawn(async move {
+            let result = installer.install(request("0.1.0"), callback).await;
+            (installer, result)
+        });
+        let started = std::time::Instant::now();
+        while requests.lock().expect("Should lock").is_empty() {
+            assert!(
+                started.elapsed() < std::time::Duration::from_secs(10),
+                "The archive should be downloaded without the lock"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!install.is_finished());
+        drop(held);
+
+        let (installer, result) = install.await.expect("Should join");
+        result.expect("Should install once the lock is released");
+        assert!(installer.paths().is_version_installed("0.1.0"));
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[tokio::test]
+    async fn install_rejects_corrupt_download() {
+        let (base, _) = serve(vec![(
+            "/0.1.0/infc-linux-x64.tar.gz",
+            b"\x1f\x8bnot a toolchain".to_vec(),
+        )]);
+        let home = temp_test_dir("installer_corrupt");
+        let installer =
+            Installer::with_paths(ToolchainPaths::with_root(home.clone()), Platform::LinuxX64)
+                .expect("Should create installer")
+                .with_manifest(manifest(&base, "0.1.0", &"0".repeat(64)));
+        let (callback, _) = recorder();
+
+        let err = installer
+            .install(request("0.1.0"), callback)
+            .await
+            .expect_err("Checksum mismatch should fail");
+
+        assert!(format!("{err:#}").contains("Checksum verification failed"));
+        let paths = installer.paths();
+        assert!(!paths.is_version_installed("0.1.0"));
+        assert!(!paths.download_path("infc-linux-x64.tar.gz").exists());
+        assert_eq!(paths.get_default_version().expect("Should read"), None);
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[tokio::test]
+    async fn install_keeps_archive_when_signature_cannot_be_fetched() {
+        let archive = toolchain_tar_gz();
+        let sha256 = sha256_hex(&archive);
+        let (base, _) = serve_responses(vec![
+            ("/0.1.0/infc-linux-x64.tar.gz", "200 OK", archive),
+            (
+                "/0.1.0/infc-linux-x64.tar.gz.minisig",
+                "503 Service Unavailable",
+                Vec::new(),
+            ),
+        ]);
+        let home = temp_test_dir("installer_signature_fetch");
+        let paths = ToolchainPaths::with_root(home.clone());
+        paths
+            .ensure_directories()
+            .expect("Should create directories");
+        std::fs::write(
+            paths.config_path(),
+            "[security]\npublic_key = \"RWQBAgMEBQYHCAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4f\"\n",
+        )
+        .expect("Should write config");
+        let installer = Installer::with_paths(paths, Platform::LinuxX64)
+            .expect("Should create installer")
+            .with_manifest(manifest(&base, "0.1.0", &sha256));
+        let (callback, events) = recorder();
+
+        let err = installer
+            .install(request("0.1.0"), callback)
+            .await
+            .expect_err("Signature fetch failure should fail");
+
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(crate::errors::EXIT_NETWORK)
+        );
+        assert!(
+            !events
+                .lock()
+                .expect("Should lock")
+                .iter()
+                .any(|event| matches!(event, InstallEvent::PhaseEntered(InstallPhase::Verifying)))
+        );
+        assert!(
+            installer
+                .paths()
+                .download_path("infc-linux-x64.tar.gz")
+                .exists()
+        );
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[tokio::test]
+    async fn install_resumes_with_downloaded_archive() {
+        let archive = toolchain_tar_gz();
+        let sha256 = sha256_hex(&archive);
+        let (base, requests) = serve(vec![]);
+        let home = temp_test_dir("installer_resume");
+        let paths = ToolchainPaths::with_root(home.clone());
+        paths
+            .ensure_directories()
+            .expect("Should create directories");
+        std::fs::write(paths.download_path("infc-linux-x64.tar.gz"), &archive)
+            .expect("Should write archive");
+        std::fs::create_dir_all(paths.download_path("0.1.0.partial").join("bin"))
+            .expect("Should create stale staging directory");
+        let installer = Installer::with_paths(paths, Platform::LinuxX64)
+            .expect("Should create installer")
+            .with_manifest(manifest(&base, "0.1.0", &sha256));
+        let (callback, _) = recorder();
+
+        let mut request = request("0.1.0");
+        request.options.resume_from = Some(InstallPhase::Verifying);
+        installer
+            .install(request, callback)
+            .await
+            .expect("Should install from the downloaded archive");
+
+        assert!(installer.paths().is_version_installed("0.1.0"));
+        assert!(requests.lock().expect("Should lock").is_empty());
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn install_archive_keeps_existing_default() {
+        let home = temp_test_dir("installer_archive");
+        let archive_path = home.join("toolchain.tar.gz");
+        std::fs::write(&archive_path, toolchain_tar_gz()).expect("Should write archive");
+        let installer = Installer::with_paths(
+            ToolchainPaths::with_root(home.join("home")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        let (callback, _) = recorder();
+
+        let first = installer
+            .install_archive(
+                &archive_path,
+                "0.1.0",
+                None,
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect("Should install first toolchain");
+        assert!(first.first_install && first.made_default);
+
+        let second = installer
+            .install_archive(
+                &archive_path,
+                "0.2.0",
+                None,
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect("Should install second toolchain");
+        assert!(!second.first_install && !second.made_default);
+        assert_eq!(second.previous_default.as_deref(), Some("0.1.0"));
+
+        let options = InstallOptions {
+            make_default: true,
+            ..InstallOptions::default()
+        };
+        let third = installer
+            .install_archive(&archive_path, "0.3.0", None, options, &callback)
+            .expect("Should install third toolchain");
+        assert!(third.made_default);
+        assert_eq!(
+            installer
+                .paths()
+                .get_default_version()
+                .expect("Should read"),
+            Some(String::from("0.3.0"))
+        );
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn install_archive_verifies_checksum() {
+        let home = temp_test_dir("installer_archive_checksum");
+        let archive_path = home.join("toolchain.tar.gz");
+        let archive = toolchain_tar_gz();
+        std::fs::write(&archive_path, &archive).expect("Should write archive");
+        let installer = Installer::with_paths(
+            ToolchainPaths::with_root(home.join("home")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        let (callback, _) = recorder();
+
+        let result = installer.install_archive(
+            &archive_path,
+            "0.1.0",
+            Some("deadbeef"),
+            InstallOptions::default(),
+            &callback,
+        );
+
+        assert!(result.is_err());
+        assert!(!installer.paths().is_version_installed("0.1.0"));
+
+        installer
+            .install_archive(
+                &archive_path,
+                "0.1.0",
+                Some(&sha256_hex(&archive)),
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect("Should install with matching checksum");
+        assert!(installer.paths().is_version_installed("0.1.0"));
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn install_archive_enforces_required_signatures() {
+        let home = temp_test_dir("installer_archive_signature");
+        let archive_path = home.join("toolchain.tar.gz");
+        std::fs::write(&archive_path, toolchain_tar_gz()).expect("Should write archive");
+        let paths = ToolchainPaths::with_root(home.join("home"));
+        paths
+            .ensure_directories()
+            .expect("Should create directories");
+        std::fs::write(
+            paths.config_path(),
+            "[security]\nrequire_signatures = true\npublic_key = \"RWQBAgMEBQYHCAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4f\"\n",
+        )
+        .expect("Should write config");
+        let (callback, _) = recorder();
+
+        let installer = Installer::with_paths(paths.clone(), Platform::LinuxX64)
+            .expect("Should create installer");
+        let err = installer
+            .install_archive(
+                &archive_path,
+                "0.1.0",
+                None,
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect_err("Unsigned archive should be rejected");
+
+        assert!(err.to_string().contains("has no signature"));
+        assert!(!paths.is_version_installed("0.1.0"));
+        assert!(!paths.download_path("0.1.0.partial").exists());
+
+        std::fs::remove_file(paths.config_path()).expect("Should remove config");
+        let installer =
+            Installer::with_paths(paths, Platform::LinuxX64).expect("Should create installer");
+        installer
+            .install_archive(
+                &archive_path,
+                "0.1.0",
+                None,
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect("Should install unsigned archive by default");
+        assert_eq!(
+            installer
+                .paths()
+                .read_metadata("0.1.0")
+                .map(|m| m.signature),
+            Some(SignatureStatus::Unsigned)
+        );
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn install_archive_missing_file_fails() {
+        let home = temp_test_dir("installer_archive_missing");
+        let installer = Installer::with_paths(
+            ToolchainPaths::with_root(home.join("home")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        let (callback, _) = recorder();
+
+        let result = installer.install_archive(
+            &home.join("nope.tar.gz"),
+            "0.1.0",
+            None,
+            InstallOptions::default(),
+            &callback,
+        );
+
+        assert!(result.is_err());
+        assert!(!installer.paths().is_version_installed("0.1.0"));
+
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    #[test]
+    fn import_bundle_registers_exported_toolchain() {
+        let root = temp_test_dir("installer_import");
+        let archive_path = root.join("toolchain.tar.gz");
+        std::fs::write(&archive_path, toolchain_tar_gz()).expect("Should write archive");
+        let (callback, _) = recorder();
+        let source = Installer::with_paths(
+            ToolchainPaths::with_root(root.join("online")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        source
+            .install_archive(
+                &archive_path,
+                "0.1.0",
+                None,
+                InstallOptions::default(),
+                &callback,
+            )
+            .expect("Should install");
+        let bundle = root.join("bundle.tar.zst");
+        crate::toolchain::bundle::export_bundle(
+            source.paths(),
+            "0.1.0",
+            Platform::LinuxX64,
+            &bundle,
+        )
+        .expect("Should export");
+
+        let target = Installer::with_paths(
+            ToolchainPaths::with_root(root.join("offline")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        let imported = target
+            .import_bundle(&bundle, false, InstallOptions::default(), &callback)
+            .expect("Should import");
+        assert!(imported.first_install && imported.made_default);
+        let metadata = target
+            .paths()
+            .read_metadata("0.1.0")
+            .expect("Should have metadata");
+        assert_eq!(metadata.source, InstallSource::Imported);
+        assert_eq!(metadata.signature, SignatureStatus::Unsigned);
+        let ext = std::env::consts::EXE_SUFFIX;
+        assert_eq!(
+            std::fs::read(
+                target
+                    .paths()
+                    .binary_path("0.1.0", &format!("inf-llc{ext}"))
+            )
+            .expect("Should read imported binary"),
+            b"binary content"
+        );
+
+        let err = target
+            .import_bundle(&bundle, false, InstallOptions::default(), &callback)
+            .expect_err("Should refuse to replace");
+        assert!(err.to_string().contains("--force"), "{err}");
+        target
+            .import_bundle(&bundle, true, InstallOptions::default(), &callback)
+            .expect("Should replace with --force");
+        assert!(!target.paths().download_path("0.1.0.partial").exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn import_bundle_rejects_other_platforms() {
+        let root = temp_test_dir("installer_import_platform");
+        let paths = ToolchainPaths::with_root(root.join("online"));
+        std::fs::create_dir_all(paths.toolchain_bin_dir("0.1.0")).expect("Should create bin");
+        std::fs::write(paths.toolchain_dir("0.1.0").join("infc"), "infc").expect("Should write");
+        let bundle = root.join("bundle.tar.zst");
+        crate::toolchain::bundle::export_bundle(&paths, "0.1.0", Platform::MacosArm64, &bundle)
+            .expect("Should export");
+        let (callback, _) = recorder();
+
+        let target = Installer::with_paths(
+            ToolchainPaths::with_root(root.join("offline")),
+            Platform::LinuxX64,
+        )
+        .expect("Should create installer");
+        let err = target
+            .import_bundle(&bundle, false, InstallOptions::default(), &callback)
+            .expect_err("Should reject");
+
+        assert!(
+            err.to_string().contains("this machine is linux-x64"),
+            "{err}"
+        );
+        assert!(!target.paths().is_version_installed("0.1.0"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,354 @@
+//! Cross-process lock for toolchain changes.
+//!
+//! Installing, uninstalling, repairing, and switching toolchains rewrite the
+//! `default` file, the symlinks in `bin/`, and the toolchain directories.
+//! Two `infs` processes doing that at once (for example CI jobs sharing a
+//! home directory, or an install started while the TUI sets the default) can
+//! leave the toolchain directory half-updated. Every such operation holds a
+//! [`ToolchainLock`] on `~/.inference/.lock` while it runs; commands that
+//! only read, such as `list` and `which`, never take it.
+//!
+//! The lock is an advisory file lock, so the operating system releases it
+//! when the holding process exits, even if it crashes. The holder writes its
+//! pid into the file and clears it on release. A waiting process shows the
+//! pid, and a pid left behind by a process that is no longer running means
+//! that process crashed while changing the toolchain, which is reported as
+//! [`LockEvent::Stale`].
+//!
+//! By default a process waits as long as it takes; the global
+//! `--lock-timeout <SECONDS>` option gives up after that many seconds.
+
+use std::fmt;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+
+use crate::output;
+use crate::toolchain::ToolchainPaths;
+
+/// How often a waiting process retries the lock.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Sets how long [`ToolchainLock::acquire`] waits for the lock in this
+/// process. `None` waits indefinitely.
+///
+/// Called by `main` after parsing `--lock-timeout`. The value can only be
+/// set once.
+pub fn set_timeout(timeout: Option<Duration>) {
+    let _ = TIMEOUT.set(timeout);
+}
+
+/// Returns the timeout set with [`set_timeout`], or `None` to wait
+/// indefinitely.
+#[must_use]
+pub fn timeout() -> Option<Duration> {
+    TIMEOUT.get().copied().flatten()
+}
+
+/// Something worth telling the user while taking the lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockEvent {
+    /// Another process holds the lock; `pid` is its process id, if known.
+    Waiting {
+        /// Process id of the holder.
+        pid: Option<u32>,
+    },
+    /// The previous holder exited without releasing the lock.
+    Stale {
+        /// Process id of the crashed holder.
+        pid: u32,
+    },
+}
+
+impl fmt::Display for LockEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Waiting { pid: Some(pid) } => write!(
+                f,
+                "another infs process is modifying the toolchain (pid {pid}); waiting..."
+            ),
+            Self::Waiting { pid: None } => {
+                write!(
+                    f,
+                    "another infs process is modifying the toolchain; waiting..."
+                )
+            }
+            Self::Stale { pid } => write!(
+                f,
+                "infs process {pid} exited while modifying the toolchain; run 'infs doctor' if toolchains misbehave"
+            ),
+        }
+    }
+}
+
+/// Exclusive lock on the toolchain directory, released when dropped.
+#[derive(Debug)]
+pub struct ToolchainLock {
+    file: File,
+}
+
+impl ToolchainLock {
+    /// Takes the lock of `paths`, waiting up to the `--lock-timeout`.
+    ///
+    /// [`LockEvent`]s are printed as warnings on stderr.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened, or another
+    /// process still holds the lock when the timeout expires.
+    pub fn acquire(paths: &ToolchainPaths) -> Result<Self> {
+        Self::acquire_with(&paths.lock_file(), timeout(), output::warn)
+    }
+
+    /// Takes the lock at `path`, waiting up to `timeout` (`None` waits
+    /// indefinitely).
+    ///
+    /// `on_event` is called once when the lock is busy, and when the
+    /// previous holder crashed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened, or another
+    /// process still holds the lock when `timeout` expires.
+    pub fn acquire_with(
+        path: &Path,
+        timeout: Option<Duration>,
+        mut on_event: impl FnMut(LockEvent),
+    ) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        let started = Instant::now();
+        let mut waiting = false;
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    let pid = read_holder(path).filter(|&pid| is_process_alive(pid));
+                    if timeout.is_some_and(|limit| started.elapsed() >= limit) {
+                        let holder = pid.map(|pid| format!(" (pid {pid})")).unwrap_or_default();
+                        bail!(
+                            "another infs process{holder} is still modifying the toolchain; \
+                             gave up after {}s (see --lock-timeout)",
+                            started.elapsed().as_secs()
+                        );
+                    }
+                    if !waiting {
+                        on_event(LockEvent::Waiting { pid });
+                        waiting = true;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+                }
+            }
+        }
+
+        let mut previous = String::new();
+        file.read_to_string(&mut previous).ok();
+        if let Ok(pid) = previous.trim().parse::<u32>()
+            && pid != std::process::id()
+            && !is_process_alive(pid)
+        {
+            on_event(LockEvent::Stale { pid });
+        }
+        file.set_len(0)
+            .and_then(|()| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| writeln!(file, "{}", std::process::id()))
+            .with_context(|| format!("Failed to write lock file {}", path.display()))?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for ToolchainLock {
+    fn drop(&mut self) {
+        // An empty file tells the next holder this one finished cleanly.
+        self.file.set_len(0).ok();
+        self.file.unlock().ok();
+    }
+}
+
+/// Reads the pid of the process holding the lock at `path`.
+///
+/// Returns `None` if the file cannot be read (Windows does not allow
+/// reading a locked file) or does not hold a pid yet.
+fn read_holder(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Returns whether a process with `pid` is running.
+#[cfg(target_os = "linux")]
+fn is_process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// Returns whether a process with `pid` is running.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Returns whether a process with `pid` is running.
+#[cfg(windows)]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH", "/FO", "CSV"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{pid}\"")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::sync::{Arc, Mutex};
+
+    fn temp_lock(name: &str) -> std::path::PathBuf {
+        temp_test_dir(&format!("lock_{name}")).join(".lock")
+    }
+
+    /// Returns the pid of a process that has already exited.
+    fn exited_pid() -> u32 {
+        let mut child = std::process::Command::new(std::env::current_exe().unwrap())
+            .arg("--list")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        pid
+    }
+
+    #[test]
+    fn contending_threads_are_serialized() {
+        let path = temp_lock("contend");
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let waited = Arc::new(Mutex::new(Vec::new()));
+
+        let handles: Vec<_> = (0..2)
+            .map(|worker| {
+                let (path, log, waited) = (path.clone(), log.clone(), waited.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..5 {
+                        let _lock = ToolchainLock::acquire_with(&path, None, |event| {
+                            waited.lock().unwrap().push(event);
+                        })
+                        .unwrap();
+                        log.lock().unwrap().push((worker, "start"));
+                        std::thread::sleep(Duration::from_millis(20));
+                        log.lock().unwrap().push((worker, "end"));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 20);
+        for pair in log.chunks(2) {
+            assert_eq!(pair[0].0, pair[1].0, "interleaved mutation: {log:?}");
+            assert_eq!((pair[0].1, pair[1].1), ("start", "end"));
+        }
+        for event in waited.lock().unwrap().iter() {
+            // The holder may be between truncating and unlocking the file.
+            assert!(
+                matches!(event, LockEvent::Waiting { pid: None })
+                    || *event
+                        == LockEvent::Waiting {
+                            pid: Some(std::process::id())
+                        },
+                "{event:?}"
+            );
+        }
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn times_out_while_held() {
+        let path = temp_lock("timeout");
+        let held = ToolchainLock::acquire_with(&path, None, |_| {}).unwrap();
+        let mut events = Vec::new();
+        let error = ToolchainLock::acquire_with(&path, Some(Duration::ZERO), |event| {
+            events.push(event);
+        })
+        .unwrap_err();
+        let message = error.to_string();
+        assert!(
+            message.contains("still modifying the toolchain"),
+            "{message}"
+        );
+        assert!(
+            message.contains(&format!("pid {}", std::process::id())),
+            "{message}"
+        );
+        assert!(events.is_empty());
+        drop(held);
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn stale_pid_of_crashed_process_is_reported() {
+        let path = temp_lock("stale");
+        let pid = exited_pid();
+        std::fs::write(&path, format!("{pid}\n")).unwrap();
+
+        let mut events = Vec::new();
+        let lock = ToolchainLock::acquire_with(&path, Some(Duration::ZERO), |event| {
+            events.push(event);
+        })
+        .unwrap();
+        assert_eq!(events, [LockEvent::Stale { pid }]);
+        assert_eq!(read_holder(&path), Some(std::process::id()));
+
+        drop(lock);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        let mut events = Vec::new();
+        let _lock = ToolchainLock::acquire_with(&path, Some(Duration::ZERO), |event| {
+            events.push(event);
+        })
+        .unwrap();
+        assert!(events.is_empty());
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn liveness_of_current_and_exited_process() {
+        assert!(is_process_alive(std::process::id()));
+        assert!(!is_process_alive(exited_pid()));
+    }
+
+    #[test]
+    fn events_have_friendly_messages() {
+        assert_eq!(
+            LockEvent::Waiting { pid: Some(1234) }.to_string(),
+            "another infs process is modifying the toolchain (pid 1234); waiting..."
+        );
+        assert!(
+            LockEvent::Stale { pid: 7 }
+                .to_string()
+                .contains("infs doctor")
+        );
+    }
+}
@@ -13,6 +13,7 @@
 //!   {
 //!     "version": "0.2.0",
 //!     "stable": true,
+//!     "notes": "## What's Changed\n* ...",
 //!     "files": [
 //!       {
 //!         "url": "https://github.com/Inferara/inference/releases/download/v0.1.0-alpha/infc-linux-x64.tar.gz",
@@ -23,19 +24,30 @@
 //! ]
 //! ```
 //!
+//! `notes` holds the Markdown release notes and may be omitted.
+//!
 //! ## Data Source
 //!
 //! Release information is fetched from a static `releases.json` file hosted on
 //! the distribution server (default: `https://inference-lang.org`). The server
 //! can be overridden via the `INFS_DIST_SERVER` environment variable for testing
-//! or using a mirror.
+//! or using a mirror. The request timeout can be raised with
+//! `INFS_HTTP_TIMEOUT_SECS` (see [`super::http`]).
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use super::Platform;
+use super::http::{
+    http_timeout, is_rate_limited, rate_limit_message, retry_after, send_with_backoff,
+};
+use super::platform::Libc;
+use crate::errors::InfsError;
+use crate::logging::debug;
 
 /// Environment variable to override the distribution server URL.
 pub const DIST_SERVER_ENV: &str = "INFS_DIST_SERVER";
@@ -46,21 +58,21 @@ const DEFAULT_DIST_SERVER: &str = "https://inference-lang.org";
 /// Path to releases manifest on server.
 const RELEASES_PATH: &str = "/releases.json";
 
-/// Request timeout in seconds.
-const REQUEST_TIMEOUT_SECS: u64 = 30;
-
 /// User-Agent header for HTTP requests.
-const USER_AGENT: &str = "infs-toolchain-manager";
+pub(crate) const USER_AGENT: &str = "infs-toolchain-manager";
 
 /// Platform-specific file entry in the manifest.
 ///
 /// Each OS has exactly one supported architecture:
 /// - Linux: x64 only
 /// - Windows: x64 only
-/// - macOS: arm64 only
+/// - macOS: arm64 only (`arm64` or `apple-silicon` in file names), with x64
+///   artifacts accepted as a Rosetta 2 fallback
 ///
-/// The `filename`, `os`, and `tool` values are derived from the URL path.
+/// The `filename`, `os`, `arch`, and `tool` values are derived from the URL path.
 /// URL format: `https://.../tool-os-arch.tar.gz` (e.g., `infc-linux-x64.tar.gz`).
+/// Linux artifacts linked against musl carry a `-musl` suffix
+/// (e.g., `infc-linux-x64-musl.tar.gz`).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FileEntry {
     /// Download URL for the artifact.
@@ -93,6 +105,52 @@ impl FileEntry {
     pub fn os(&self) -> &str {
         self.filename().split('-').nth(1).unwrap_or("")
     }
+
+    /// Determines the platform from the filename's OS segment.
+    ///
+    /// Example: `"infc-linux-x64.tar.gz"` -> `Some(Platform::LinuxX64)`
+    #[must_use]
+    pub fn platform(&self) -> Option<Platform> {
+        Platform::from_filename(self.filename())
+    }
+
+    /// Extracts the architecture from filename (everything after the OS
+    /// segment, without the archive extension and `-musl` suffix).
+    ///
+    /// Example: `"infc-linux-x64-musl.tar.gz"` -> `"x64"`,
+    /// `"infc-macos-apple-silicon.tar.gz"` -> `"apple-silicon"`
+    #[must_use]
+    pub fn arch(&self) -> &str {
+        let arch = self.stem().splitn(3, '-').nth(2).unwrap_or("");
+        arch.strip_suffix("-musl").unwrap_or(arch)
+    }
+
+    /// Extracts the C library from filename (`musl` suffix after the arch).
+    ///
+    /// Example: `"infc-linux-x64-musl.tar.gz"` -> `Libc::Musl`,
+    /// `"infc-linux-x64.tar.gz"` -> `Libc::Glibc`
+    #[must_use]
+    pub fn libc(&self) -> Libc {
+        if self
+            .stem()
+            .split('-')
+            .skip(3)
+            .any(|segment| segment == "musl")
+        {
+            Libc::Musl
+        } else {
+            Libc::Glibc
+        }
+    }
+
+    /// Returns the filename without its archive extension.
+    fn stem(&self) -> &str {
+        let filename = self.filename();
+        filename
+            .strip_suffix(".tar.gz")
+            .or_else(|| filename.strip_suffix(".zip"))
+            .unwrap_or(filename)
+    }
 }
 
 /// Version entry in the manifest.
@@ -102,11 +160,43 @@ pub struct VersionEntry {
     pub version: String,
     /// Whether this is a stable release.
     pub stable: bool,
+    /// Release notes in Markdown, if the release has any.
+    ///
+    /// See [`super::release_notes`] for plain-text rendering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
     /// Platform-specific files for this version.
     pub files: Vec<FileEntry>,
 }
 
 impl VersionEntry {
+    /// Orders two entries by version.
+    ///
+    /// Versions are compared as semver. A version that is not valid semver
+    /// sorts below every valid one, and two invalid versions compare as
+    /// strings.
+    #[must_use]
+    pub fn cmp_version(&self, other: &Self) -> std::cmp::Ordering {
+        let ours = semver::Version::parse(&self.version).ok();
+        let theirs = semver::Version::parse(&other.version).ok();
+        match (ours, theirs) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (None, None) => self.version.cmp(&other.version),
+        }
+    }
+
+    /// Returns the first `max_lines` lines of the release notes as plain
+    /// text, or `None` if the version has no notes.
+    #[must_use]
+    pub fn notes_excerpt(&self, max_lines: usize) -> Option<String> {
+        self.notes
+            .as_deref()
+            .map(|notes| super::release_notes::excerpt(notes, max_lines))
+            .filter(|excerpt| !excerpt.is_empty())
+    }
+
     /// Checks if this version has artifacts for the specified platform.
     ///
     /// # Arguments
@@ -118,8 +208,7 @@ impl VersionEntry {
     /// `true` if any artifact exists for the platform's OS, `false` otherwise.
     #[must_use]
     pub fn has_platform(&self, platform: Platform) -> bool {
-        let os = platform.os();
-        self.files.iter().any(|f| f.os() == os)
+        self.files.iter().any(|f| f.platform() == Some(platform))
     }
 
     /// Returns sorted list of available platforms for this version.
@@ -146,10 +235,10 @@ impl VersionEntry {
         platforms
     }
 
-    /// Finds the artifact for a specific platform and tool.
+    /// Finds the native artifact for a specific platform and tool.
     ///
-    /// Each OS has exactly one supported architecture, so matching is done
-    /// by OS and tool name only.
+    /// An artifact matches when its OS and architecture segments are those
+    /// of `platform` and its tool name is `tool`.
     ///
     /// # Arguments
     ///
@@ -161,8 +250,34 @@ impl VersionEntry {
     /// The file entry, or `None` if no matching artifact exists.
     #[must_use = "returns artifact info without side effects"]
     pub fn find_artifact(&self, platform: Platform, tool: &str) -> Option<&FileEntry> {
-        let os = platform.os();
-        self.files.iter().find(|f| f.os() == os && f.tool() == tool)
+        self.find_artifact_for_libc(platform, tool, platform.libc())
+    }
+
+    /// Finds the artifact for a platform and tool, preferring a C library.
+    ///
+    /// When `libc` is `Some`, an artifact linked against it is preferred and
+    /// a glibc artifact is used as the fallback. When `libc` is `None`
+    /// (non-Linux platforms), the glibc (unsuffixed) artifact is returned.
+    ///
+    /// # Returns
+    ///
+    /// The file entry, or `None` if no matching artifact exists.
+    #[must_use = "returns artifact info without side effects"]
+    pub fn find_artifact_for_libc(
+        &self,
+        platform: Platform,
+        tool: &str,
+        libc: Option<Libc>,
+    ) -> Option<&FileEntry> {
+        let wanted = libc.unwrap_or(Libc::Glibc);
+        let archs = platform.info().archs;
+        let mut candidates = self.files.iter().filter(|f| {
+            f.platform() == Some(platform) && f.tool() == tool && archs.contains(&f.arch())
+        });
+        candidates
+            .clone()
+            .find(|f| f.libc() == wanted)
+            .or_else(|| candidates.find(|f| f.libc() == Libc::Glibc))
     }
 
     /// Finds the infc artifact for a specific platform.
@@ -181,6 +296,34 @@ impl VersionEntry {
         self.find_artifact(platform, "infc")
     }
 
+    /// Finds the infc artifact for a platform, falling back to one that runs
+    /// under emulation.
+    ///
+    /// The native artifact from [`Self::find_infc_artifact`] is preferred.
+    /// Without one, an artifact for one of the platform's fallback
+    /// architectures is returned (an x64 build under Rosetta 2 on Apple
+    /// Silicon).
+    ///
+    /// # Returns
+    ///
+    /// The file entry and whether it is a non-native fallback, or `None` if
+    /// neither exists.
+    #[must_use = "returns artifact info without side effects"]
+    pub fn find_artifact_with_fallback(&self, platform: Platform) -> Option<(&FileEntry, bool)> {
+        if let Some(artifact) = self.find_infc_artifact(platform) {
+            return Some((artifact, false));
+        }
+        let fallback_archs = platform.info().fallback_archs;
+        self.files
+            .iter()
+            .find(|f| {
+                f.platform() == Some(platform)
+                    && f.tool() == "infc"
+                    && fallback_archs.contains(&f.arch())
+            })
+            .map(|f| (f, true))
+    }
+
     /// Finds the infs CLI artifact for a specific platform.
     ///
     /// # Arguments
@@ -199,6 +342,37 @@ impl VersionEntry {
 /// Release manifest - array of version entries.
 pub type Manifest = Vec<VersionEntry>;
 
+/// Returns a warning if `artifact` is linked against a different C library
+/// than the running system.
+///
+/// This happens when no musl artifact exists and the glibc build was
+/// selected as a fallback on a musl system.
+#[must_use]
+pub fn libc_mismatch_warning(platform: Platform, artifact: &FileEntry) -> Option<String> {
+    let system = platform.libc()?;
+    let artifact_libc = artifact.libc();
+    (system != artifact_libc).then(|| {
+        format!(
+            "No {system} build of {} is available for {platform}; using the {artifact_libc} \
+             build {}, which may not run on this system.",
+            artifact.tool(),
+            artifact.filename()
+        )
+    })
+}
+
+/// Returns the warning printed when a non-native artifact was selected.
+#[must_use]
+pub fn fallback_warning(platform: Platform, artifact: &FileEntry) -> String {
+    format!(
+        "No native {platform} build of {} is available; using the {} build {}, \
+         which runs under emulation (Rosetta 2 on macOS).",
+        artifact.tool(),
+        artifact.arch(),
+        artifact.filename()
+    )
+}
+
 /// Finds the latest stable version in the manifest.
 ///
 /// Stable versions are sorted by semver and the highest one is returned.
@@ -212,16 +386,10 @@ pub type Manifest = Vec<VersionEntry>;
 /// The latest stable version entry, or `None` if no stable versions exist.
 #[must_use = "returns version info without side effects"]
 pub fn latest_stable(manifest: &Manifest) -> Option<&VersionEntry> {
-    manifest.iter().filter(|v| v.stable).max_by(|a, b| {
-        let a_ver = semver::Version::parse(&a.version).ok();
-        let b_ver = semver::Version::parse(&b.version).ok();
-        match (a_ver, b_ver) {
-            (Some(a), Some(b)) => a.cmp(&b),
-            (Some(_), None) => std::cmp::Ordering::Greater,
-            (None, Some(_)) => std::cmp::Ordering::Less,
-            (None, None) => a.version.cmp(&b.version),
-        }
-    })
+    manifest
+        .iter()
+        .filter(|v| v.stable)
+        .max_by(|a, b| a.cmp_version(b))
 }
 
 /// Finds the latest version in the manifest regardless of stability.
@@ -239,16 +407,7 @@ pub fn latest_stable(manifest: &Manifest) -> Option<&VersionEntry> {
 /// The latest version entry, or `None` if the manifest is empty.
 #[must_use = "returns version info without side effects"]
 pub fn latest_version(manifest: &Manifest) -> Option<&VersionEntry> {
-    manifest.iter().max_by(|a, b| {
-        let a_ver = semver::Version::parse(&a.version).ok();
-        let b_ver = semver::Version::parse(&b.version).ok();
-        match (a_ver, b_ver) {
-            (Some(a), Some(b)) => a.cmp(&b),
-            (Some(_), None) => std::cmp::Ordering::Greater,
-            (None, Some(_)) => std::cmp::Ordering::Less,
-            (None, None) => a.version.cmp(&b.version),
-        }
-    })
+    manifest.iter().max_by(|a, b| a.cmp_version(b))
 }
 
 /// Finds a specific version in the manifest.
@@ -266,6 +425,189 @@ pub fn find_version<'a>(manifest: &'a Manifest, version: &str) -> Option<&'a Ver
     manifest.iter().find(|v| v.version == version)
 }
 
+/// Returns the spellings a release version may be listed under.
+///
+/// Manifests may write versions with or without a leading `v`, so both
+/// `0.1.0` and `v0.1.0` yield `["0.1.0", "v0.1.0"]`.
+#[must_use = "returns candidates without side effects"]
+pub fn version_candidates(version: &str) -> Vec<String> {
+    let plain = version.trim().trim_start_matches('v');
+    vec![plain.to_string(), format!("v{plain}")]
+}
+
+/// Finds a specific version in the manifest, with or without a leading `v`.
+///
+/// Unlike [`find_version`], which matches the manifest entry exactly, this
+/// tries every spelling from [`version_candidates`].
+///
+/// # Errors
+///
+/// Returns [`InfsError::VersionNotFound`] listing the spellings that were tried.
+pub fn find_version_by_number<'a>(
+    manifest: &'a Manifest,
+    version: &str,
+) -> Result<&'a VersionEntry> {
+    let candidates = version_candidates(version);
+    candidates
+        .iter()
+        .find_map(|candidate| find_version(manifest, candidate))
+        .ok_or_else(|| InfsError::version_not_found(version))
+        .with_context(|| {
+            format!(
+                "Version {version} not found in manifest (tried {})",
+                candidates.join(", ")
+            )
+        })
+}
+
+/// Returns whether a version entry is a prerelease.
+///
+/// Entries marked unstable in the manifest and versions with a semver
+/// prerelease suffix (e.g., `0.3.0-alpha`) both count as prereleases.
+#[must_use]
+pub fn is_prerelease(entry: &VersionEntry) -> bool {
+    !entry.stable
+        || semver::Version::parse(&entry.version).is_ok_and(|version| !version.pre.is_empty())
+}
+
+/// Finds the highest version in the manifest matching a semver requirement.
+///
+/// Prereleases are skipped unless `include_prerelease` is set. When they
+/// are included, a prerelease matches if its release version does (so
+/// `0.3.0-alpha` matches `^0.3`).
+///
+/// # Arguments
+///
+/// * `manifest` - The manifest to search
+/// * `req` - The version requirement (e.g., `^0.2` or `>=0.2, <0.3`)
+/// * `include_prerelease` - Whether prerelease versions may be selected
+///
+/// # Returns
+///
+/// The highest matching version entry, or `None` if nothing matches.
+#[must_use = "returns version info without side effects"]
+pub fn find_matching_version<'a>(
+    manifest: &'a Manifest,
+    req: &semver::VersionReq,
+    include_prerelease: bool,
+) -> Option<&'a VersionEntry> {
+    matching_versions(manifest, req, include_prerelease)
+        .into_iter()
+        .next()
+}
+
+/// Returns all versions matching a semver requirement, highest first.
+///
+/// Matching follows [`find_matching_version`].
+#[must_use = "returns version info without side effects"]
+pub fn matching_versions<'a>(
+    manifest: &'a Manifest,
+    req: &semver::VersionReq,
+    include_prerelease: bool,
+) -> Vec<&'a VersionEntry> {
+    let mut matches: Vec<(semver::Version, &VersionEntry)> = manifest
+        .iter()
+        .filter(|entry| include_prerelease || !is_prerelease(entry))
+        .filter_map(|entry| {
+            let version = semver::Version::parse(&entry.version).ok()?;
+            let release = semver::Version::new(version.major, version.minor, version.patch);
+            let matches = req.matches(&version) || (include_prerelease && req.matches(&release));
+            matches.then_some((version, entry))
+        })
+        .collect();
+    matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Returns the versions to offer when a version argument is ambiguous.
+///
+/// A range is ambiguous when it matches several versions, or when, without
+/// `include_prerelease`, only prereleases match it. The result is highest
+/// first, and empty for `latest`, exact versions, and unambiguous ranges,
+/// which [`resolve_version`] handles on its own.
+#[must_use = "returns version info without side effects"]
+pub fn version_choices<'a>(
+    manifest: &'a Manifest,
+    version: &str,
+    include_prerelease: bool,
+) -> Vec<&'a VersionEntry> {
+    let version = version.trim();
+    if version == "latest"
+        || find_version(manifest, version).is_some()
+        || semver::Version::parse(version.trim_start_matches('v')).is_ok()
+    {
+        return Vec::new();
+    }
+    let Ok(req) = semver::VersionReq::parse(version) else {
+        return Vec::new();
+    };
+    let matches = matching_versions(manifest, &req, include_prerelease);
+    if matches.len() > 1 {
+        return matches;
+    }
+    if matches.is_empty() && !include_prerelease {
+        return matching_versions(manifest, &req, true);
+    }
+    Vec::new()
+}
+
+/// Resolves a version argument to a manifest entry.
+///
+/// `None` or `"latest"` selects the latest stable version (falling back to
+/// the latest version when there is no stable release), or the latest
+/// version of any kind when `include_prerelease` is set. A full version
+/// string must match a manifest entry exactly. Anything else is parsed as a
+/// semver requirement and resolved with [`find_matching_version`].
+///
+/// # Errors
+///
+/// Returns an error if the manifest is empty, the version is not found, or
+/// no version satisfies the requirement.
+pub fn resolve_version<'a>(
+    manifest: &'a Manifest,
+    version: Option<&str>,
+    include_prerelease: bool,
+) -> Result<&'a VersionEntry> {
+    let version = match version.map(str::trim) {
+        None | Some("latest") => {
+            let latest = if include_prerelease {
+                latest_version(manifest)
+            } else {
+                latest_stable(manifest).or_else(|| latest_version(manifest))
+            };
+            return latest.context("No version found in manifest");
+        }
+        Some(v) => v,
+    };
+
+    if let Some(entry) = find_version(manifest, version) {
+        return Ok(entry);
+    }
+
+    // A full version that is not in the manifest is not treated as a range,
+    // so "0.1.0" never silently resolves to "0.1.5".
+    if semver::Version::parse(version.trim_start_matches('v')).is_ok() {
+        return find_version_by_number(manifest, version);
+    }
+    let Ok(req) = semver::VersionReq::parse(version) else {
+        return Err(InfsError::version_not_found(version))
+            .with_context(|| format!("Version {version} not found in manifest"));
+    };
+
+    find_matching_version(manifest, &req, include_prerelease)
+        .ok_or_else(|| InfsError::version_not_found(version))
+        .with_context(|| {
+            if include_prerelease {
+                format!("No version matching '{version}' found in manifest")
+            } else {
+                format!(
+                    "No stable version matching '{version}' found in manifest \
+                 (use --pre to include prereleases)"
+                )
+            }
+        })
+}
+
 /// Returns all available version strings from the manifest.
 ///
 /// # Arguments
@@ -296,49 +638,113 @@ pub fn available_versions(manifest: &Manifest) -> Vec<&str> {
 #[must_use = "returns sorted version list without side effects"]
 pub fn sorted_versions(manifest: &Manifest) -> Vec<&VersionEntry> {
     let mut versions: Vec<&VersionEntry> = manifest.iter().collect();
-    versions.sort_by(|a, b| {
-        let a_ver = semver::Version::parse(&a.version).ok();
-        let b_ver = semver::Version::parse(&b.version).ok();
-        match (a_ver, b_ver) {
-            // Both valid semver: sort descending (newest first)
-            (Some(a), Some(b)) => b.cmp(&a),
-            // a is valid, b is invalid: a comes first (Less)
-            (Some(_), None) => std::cmp::Ordering::Less,
-            // a is invalid, b is valid: b comes first (Greater)
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            // Both invalid: sort descending by string
-            (None, None) => b.version.cmp(&a.version),
-        }
-    });
+    versions.sort_by(|a, b| b.cmp_version(a));
     versions
 }
 
+/// Version of the manifest cache format.
+///
+/// Bump this when [`VersionEntry`] gains fields, so caches written by older
+/// releases (which dropped those fields) are refreshed. Version 2 added
+/// release notes.
+pub const CACHE_SCHEMA_VERSION: u32 = 2;
+
 /// Cached manifest with timestamp.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CachedManifest {
+    /// Cache format version; 0 for caches written before it was recorded.
+    #[serde(default)]
+    schema_version: u32,
     manifest: Manifest,
     timestamp: u64,
+    /// SHA-256 of the manifest, see [`manifest_checksum`]; `None` for
+    /// caches written before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
 }
 
-/// Returns the path to the manifest cache file.
-fn cache_path() -> Result<PathBuf> {
-    let root = if let Ok(home) = std::env::var(super::paths::INFERENCE_HOME_ENV) {
-        PathBuf::from(home)
-    } else {
-        #[cfg(windows)]
-        {
-            dirs::data_dir()
-                .context("Cannot determine AppData directory")?
-                .join("inference")
-        }
-        #[cfg(not(windows))]
-        {
-            dirs::home_dir()
-                .context("Cannot determine home directory")?
-                .join(".inference")
+impl CachedManifest {
+    /// Returns whether the cached manifest matches its recorded checksum.
+    ///
+    /// Caches without a checksum cannot be checked and are accepted.
+    fn is_intact(&self) -> bool {
+        self.checksum
+            .as_ref()
+            .is_none_or(|checksum| *checksum == manifest_checksum(&self.manifest))
+    }
+
+    /// Returns how long before the Unix time `now` the cache was written.
+    ///
+    /// A timestamp in the future (e.g., after a clock change) counts as zero.
+    #[must_use]
+    pub fn age(&self, now: u64) -> Duration {
+        Duration::from_secs(now.saturating_sub(self.timestamp))
+    }
+}
+
+/// Where a manifest returned by [`load_manifest`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestSource {
+    /// Fetched from the distribution server.
+    Network,
+    /// Read from the local cache.
+    Cache {
+        /// Time since the cache was written.
+        age: Duration,
+    },
+}
+
+impl ManifestSource {
+    /// Returns a notice saying how old a cached manifest is, or `None` if
+    /// the manifest was just fetched.
+    ///
+    /// Commands print it so that users know a version published since may
+    /// be missing.
+    #[must_use]
+    pub fn staleness_notice(self) -> Option<String> {
+        match self {
+            Self::Network => None,
+            Self::Cache { age } => Some(format!(
+                "manifest cached {} (use --refresh for latest)",
+                format_age(age)
+            )),
         }
+    }
+}
+
+/// Formats `age` as a rounded-down relative time, e.g. `5 minutes ago`.
+#[must_use]
+pub fn format_age(age: Duration) -> String {
+    let minutes = age.as_secs() / 60;
+    let (count, unit) = match minutes {
+        0 => return String::from("less than a minute ago"),
+        1..60 => (minutes, "minute"),
+        60..2880 => (minutes / 60, "hour"),
+        _ => (minutes / 1440, "day"),
     };
-    Ok(root.join("cache").join("manifest.json"))
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} ago")
+}
+
+/// Returns the hex SHA-256 of the manifest's compact JSON form.
+///
+/// The digest is taken over the parsed manifest rather than the file, so
+/// reformatting the cache does not invalidate it but any changed value does.
+fn manifest_checksum(manifest: &Manifest) -> String {
+    let json = serde_json::to_vec(manifest).unwrap_or_default();
+    hex::encode(Sha256::digest(&json))
+}
+
+/// Name of the manifest cache file in [`ToolchainPaths::cache_dir`].
+///
+/// [`ToolchainPaths::cache_dir`]: super::paths::ToolchainPaths::cache_dir
+pub const MANIFEST_CACHE_FILE: &str = "manifest.json";
+
+/// Returns the path to the manifest cache file.
+fn cache_path() -> Result<PathBuf> {
+    Ok(super::paths::ToolchainPaths::new()?
+        .cache_dir()
+        .join(MANIFEST_CACHE_FILE))
 }
 
 /// Returns the current Unix timestamp.
@@ -348,28 +754,50 @@ fn current_timestamp() -> u64 {
         .map_or(0, |d| d.as_secs())
 }
 
-/// Attempts to load the manifest from cache if valid.
+/// Attempts to load the cached manifest.
+///
+/// See [`read_cache`].
+fn load_from_cache() -> Option<CachedManifest> {
+    read_cache(&cache_path().ok()?)
+}
+
+/// Reads the cached manifest at `cache_file`.
 ///
-/// If the cache file exists but cannot be parsed (e.g., old format),
-/// it will be deleted to allow a fresh fetch.
-fn load_from_cache() -> Option<Manifest> {
-    let cache_file = cache_path().ok()?;
-    let content = std::fs::read_to_string(&cache_file).ok()?;
+/// If the cache file exists but cannot be parsed (e.g., old format), or
+/// its manifest does not match the recorded checksum (e.g., a partial
+/// write), it will be deleted to allow a fresh fetch. A cache with an
+/// older [`CACHE_SCHEMA_VERSION`] is returned as is; the caller decides
+/// whether to refresh it.
+fn read_cache(cache_file: &Path) -> Option<CachedManifest> {
+    let content = std::fs::read_to_string(cache_file).ok()?;
 
     let Ok(cached) = serde_json::from_str::<CachedManifest>(&content) else {
         // Old format or corrupted cache - delete it
-        let _ = std::fs::remove_file(&cache_file);
+        let _ = std::fs::remove_file(cache_file);
         return None;
     };
-    Some(cached.manifest)
+    if !cached.is_intact() {
+        debug!("Discarding release manifest cache with a mismatched checksum");
+        let _ = std::fs::remove_file(cache_file);
+        return None;
+    }
+    Some(cached)
 }
 
 /// Saves the manifest to cache.
 fn save_to_cache(manifest: &Manifest) {
-    let Ok(cache_file) = cache_path() else {
-        return;
-    };
+    if let Ok(cache_file) = cache_path() {
+        write_cache(&cache_file, manifest);
+    }
+}
 
+/// Writes `manifest` and its checksum to `cache_file`.
+///
+/// The file is replaced atomically (see [`replace_file`]), so a crash or a
+/// concurrent `infs` process never leaves a truncated cache behind; when
+/// two processes write at once, the last rename wins. Failures are ignored;
+/// the manifest is simply fetched again next time.
+fn write_cache(cache_file: &Path, manifest: &Manifest) {
     if let Some(parent) = cache_file.parent()
         && std::fs::create_dir_all(parent).is_err()
     {
@@ -377,15 +805,47 @@ fn save_to_cache(manifest: &Manifest) {
     }
 
     let cached = CachedManifest {
+        schema_version: CACHE_SCHEMA_VERSION,
         manifest: manifest.clone(),
         timestamp: current_timestamp(),
+        checksum: Some(manifest_checksum(manifest)),
     };
 
     let Ok(content) = serde_json::to_string_pretty(&cached) else {
         return;
     };
 
-    let _ = std::fs::write(cache_file, content);
+    let _ = replace_file(cache_file, |file| file.write_all(content.as_bytes()));
+}
+
+/// Replaces `path` with the content written by `write`.
+///
+/// The content goes to a temporary file in the same directory, which is
+/// renamed over `path` only once `write` succeeds. Readers see either the
+/// old file or the new one, never a partial write. On failure the
+/// temporary file is removed and `path` is left untouched.
+fn replace_file(
+    path: &Path,
+    write: impl FnOnce(&mut std::fs::File) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    // Unique per process and call, so concurrent writers never share a file
+    let temp_path = path.with_file_name(format!(
+        ".{file_name}.{}.{:x}.tmp",
+        std::process::id(),
+        rand::random::<u32>()
+    ));
+
+    let result = std::fs::File::create(&temp_path).and_then(|mut file| {
+        write(&mut file)?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&temp_path, path)
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
 }
 
 /// Fetches the release manifest, using a local cache with 15-minute TTL.
@@ -394,19 +854,54 @@ fn save_to_cache(manifest: &Manifest) {
 /// returns the cached manifest without making a network request. On cache miss or
 /// expiry, fetches from the static manifest URL and updates the cache.
 ///
+/// A cache written with an older [`CACHE_SCHEMA_VERSION`] is refreshed too,
+/// but is still used if the network is unavailable.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The manifest URL cannot be fetched (and no valid cache exists)
 /// - The response cannot be parsed as JSON
 pub async fn fetch_manifest() -> Result<Manifest> {
-    if let Some(manifest) = load_from_cache() {
-        return Ok(manifest);
+    load_manifest(false).await.map(|(manifest, _)| manifest)
+}
+
+/// Fetches the release manifest like [`fetch_manifest`], and reports
+/// whether it came from the cache.
+///
+/// With `refresh`, the cache is bypassed and only used if the network is
+/// unavailable.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The manifest URL cannot be fetched (and no valid cache exists)
+/// - The response cannot be parsed as JSON
+pub async fn load_manifest(refresh: bool) -> Result<(Manifest, ManifestSource)> {
+    let cached = load_from_cache();
+    if !refresh
+        && let Some(cached) = &cached
+        && cached.schema_version == CACHE_SCHEMA_VERSION
+    {
+        debug!("Using cached release manifest");
+        let age = cached.age(current_timestamp());
+        return Ok((cached.manifest.clone(), ManifestSource::Cache { age }));
     }
 
-    let manifest = fetch_manifest_from_network().await?;
-    save_to_cache(&manifest);
-    Ok(manifest)
+    match fetch_manifest_from_network().await {
+        Ok(manifest) => {
+            save_to_cache(&manifest);
+            Ok((manifest, ManifestSource::Network))
+        }
+        Err(e) => {
+            let Some(cached) = cached else {
+                return Err(e);
+            };
+            debug!("Falling back to cached release manifest: {e:#}");
+            let age = cached.age(current_timestamp());
+            Ok((cached.manifest, ManifestSource::Cache { age }))
+        }
+    }
 }
 
 /// Returns the URL to the releases manifest.
@@ -424,12 +919,21 @@ fn releases_url() -> String {
 }
 
 /// Handles HTTP errors with user-friendly messages.
-fn handle_http_error(status: reqwest::StatusCode, url: &str) -> anyhow::Error {
-    match status.as_u16() {
-        404 => anyhow::anyhow!("Release manifest not found at {url}"),
-        code if code >= 500 => anyhow::anyhow!("Server error ({code}): {url}"),
-        code => anyhow::anyhow!("HTTP error {code}: {url}"),
-    }
+///
+/// For rate-limit responses, `retry_after` is the wait reported by the
+/// server (see [`retry_after`]) and is included in the message.
+fn handle_http_error(
+    status: reqwest::StatusCode,
+    url: &str,
+    retry_after: Option<Duration>,
+) -> anyhow::Error {
+    let message = match status.as_u16() {
+        404 => format!("Release manifest not found at {url}"),
+        403 | 429 => rate_limit_message(url, retry_after, SystemTime::now()),
+        code if code >= 500 => format!("Server error ({code}): {url}"),
+        code => format!("HTTP error {code}: {url}"),
+    };
+    InfsError::network_error(message).into()
 }
 
 /// Fetches the release manifest directly from the distribution server, bypassing cache.
@@ -441,25 +945,26 @@ fn handle_http_error(status: reqwest::StatusCode, url: &str) -> anyhow::Error {
 ///
 /// Returns an error if:
 /// - The HTTP request fails
-/// - The server returns a non-success status code
+/// - The server returns a non-success status code (rate-limited requests
+///   are retried first, see [`send_with_backoff`])
 /// - The response cannot be parsed as JSON
 async fn fetch_manifest_from_network() -> Result<Manifest> {
     let url = releases_url();
 
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .timeout(http_timeout())
         .user_agent(USER_AGENT)
         .build()
         .context("Failed to create HTTP client")?;
 
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .with_context(|| format!("Failed to fetch manifest from {url}"))?;
+    let response = send_with_backoff(|| client.get(&url), "manifest", &url).await?;
 
-    if !response.status().is_success() {
-        return Err(handle_http_error(response.status(), &url));
+    let status = response.status();
+    if !status.is_success() {
+        let wait = is_rate_limited(status, response.headers())
+            .then(|| retry_after(response.headers(), SystemTime::now()))
+            .flatten();
+        return Err(handle_http_error(status, &url, wait));
     }
 
     let text = response
@@ -475,44 +980,46 @@ async fn fetch_manifest_from_network() -> Result<Manifest> {
 
 /// Fetches the release manifest and finds the artifact for a specific version and platform.
 ///
-/// If `version` is `None` or "latest", returns the latest stable version's artifact.
+/// The version is resolved with [`resolve_version`]: `None` or "latest"
+/// selects the latest stable version, and semver requirements such as `^0.2`
+/// select the highest matching version. Prereleases are only considered
+/// when `include_prerelease` is set.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The manifest cannot be fetched
-/// - The specified version is not found
+/// - The specified version is not found or no version matches the requirement
 /// - No artifact exists for the current platform
+///
+/// # Returns
+///
+/// The resolved version, the infc artifact, and whether the artifact is a
+/// non-native fallback (see [`VersionEntry::find_artifact_with_fallback`]).
 pub async fn fetch_artifact(
     version: Option<&str>,
     platform: Platform,
-) -> Result<(String, FileEntry)> {
+    include_prerelease: bool,
+) -> Result<(String, FileEntry, bool)> {
     let manifest = fetch_manifest().await?;
+    let version_entry = resolve_version(&manifest, version, include_prerelease)?;
 
-    let version_entry = match version {
-        None | Some("latest") => latest_stable(&manifest)
-            .or_else(|| latest_version(&manifest))
-            .context("No version found in manifest")?,
-        Some(v) => find_version(&manifest, v)
-            .with_context(|| format!("Version {v} not found in manifest"))?,
-    };
-
-    let artifact = version_entry
-        .find_infc_artifact(platform)
+    let (artifact, fallback) = version_entry
+        .find_artifact_with_fallback(platform)
         .with_context(|| {
             format!(
                 "No artifact found for platform {} in version {}",
                 platform, version_entry.version
             )
-        })?
-        .clone();
+        })?;
 
-    Ok((version_entry.version.clone(), artifact))
+    Ok((version_entry.version.clone(), artifact.clone(), fallback))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
 
     fn sample_manifest_json() -> &'static str {
         r#"[
@@ -715,16 +1222,19 @@ mod tests {
             VersionEntry {
                 version: "0.1.0".to_string(),
                 stable: true,
+                notes: None,
                 files: vec![],
             },
             VersionEntry {
                 version: "invalid".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
             VersionEntry {
                 version: "0.2.0".to_string(),
                 stable: true,
+                notes: None,
                 files: vec![],
             },
         ];
@@ -749,15 +1259,129 @@ mod tests {
             serde_json::from_str(sample_manifest_json()).expect("Should parse manifest");
 
         let cached = CachedManifest {
+            schema_version: CACHE_SCHEMA_VERSION,
             manifest: manifest.clone(),
             timestamp: 1_000_000,
+            checksum: Some(manifest_checksum(&manifest)),
         };
 
         let json = serde_json::to_string(&cached).expect("Should serialize");
         let deserialized: CachedManifest = serde_json::from_str(&json).expect("Should deserialize");
 
+        assert_eq!(deserialized.schema_version, CACHE_SCHEMA_VERSION);
         assert_eq!(deserialized.timestamp, 1_000_000);
-        assert_eq!(deserialized.manifest.len(), manifest.len());
+        assert_eq!(deserialized.manifest, manifest);
+    }
+
+    #[test]
+    fn tampered_manifest_cache_is_discarded() {
+        let dir = temp_test_dir("manifest");
+        let cache_file = dir.join(MANIFEST_CACHE_FILE);
+        let manifest: Manifest =
+            serde_json::from_str(sample_manifest_json()).expect("Should parse manifest");
+
+        write_cache(&cache_file, &manifest);
+        let cached = read_cache(&cache_file).expect("Intact cache should load");
+        assert_eq!(cached.manifest, manifest);
+
+        // Still valid JSON, but a download URL points elsewhere
+        let content = std::fs::read_to_string(&cache_file).unwrap();
+        let tampered = content.replace("https://example.com/0.2.0/", "https://evil.example/");
+        assert_ne!(content, tampered);
+        std::fs::write(&cache_file, tampered).unwrap();
+
+        assert!(read_cache(&cache_file).is_none());
+        assert!(!cache_file.exists(), "tampered cache should be removed");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn interrupted_cache_write_keeps_previous_cache() {
+        let dir = temp_test_dir("manifest");
+        let cache_file = dir.join(MANIFEST_CACHE_FILE);
+        let manifest: Manifest =
+            serde_json::from_str(sample_manifest_json()).expect("Should parse manifest");
+        write_cache(&cache_file, &manifest);
+
+        // A writer that fails halfway, as a crash or a full disk would
+        let result = replace_file(&cache_file, |file| {
+            file.write_all(b"[{\"version\": \"9.9.9\", ")?;
+            Err(std::io::Error::other("disk full"))
+        });
+        assert!(result.is_err());
+
+        let cached = read_cache(&cache_file).expect("Previous cache should remain valid");
+        assert_eq!(cached.manifest, manifest);
+        let entries: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(entries.len(), 1, "temporary file left behind: {entries:?}");
+
+        let mut updated = manifest.clone();
+        updated.truncate(1);
+        write_cache(&cache_file, &updated);
+        assert_eq!(read_cache(&cache_file).unwrap().manifest, updated);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn staleness_notice_reports_age_of_stored_timestamp() {
+        let cached: CachedManifest =
+            serde_json::from_str(r#"{"schema_version": 2, "manifest": [], "timestamp": 1000000}"#)
+                .unwrap();
+
+        let source = ManifestSource::Cache {
+            age: cached.age(1_000_000 + 5 * 60 + 59),
+        };
+        assert_eq!(
+            source.staleness_notice().as_deref(),
+            Some("manifest cached 5 minutes ago (use --refresh for latest)")
+        );
+        assert_eq!(cached.age(999_000), Duration::ZERO);
+        assert_eq!(ManifestSource::Network.staleness_notice(), None);
+    }
+
+    #[test]
+    fn format_age_picks_largest_unit() {
+        assert_eq!(
+            format_age(Duration::from_secs(59)),
+            "less than a minute ago"
+        );
+        assert_eq!(format_age(Duration::from_mins(1)), "1 minute ago");
+        assert_eq!(format_age(Duration::from_mins(59)), "59 minutes ago");
+        assert_eq!(format_age(Duration::from_hours(1)), "1 hour ago");
+        assert_eq!(format_age(Duration::from_hours(47)), "47 hours ago");
+        assert_eq!(format_age(Duration::from_hours(48)), "2 days ago");
+    }
+
+    #[test]
+    fn cached_manifest_without_checksum_is_accepted() {
+        let json = r#"{"schema_version": 2, "manifest": [], "timestamp": 0}"#;
+        let cached: CachedManifest = serde_json::from_str(json).expect("Should deserialize");
+        assert_eq!(cached.checksum, None);
+        assert!(cached.is_intact());
+    }
+
+    #[test]
+    fn cached_manifest_without_schema_version_is_version_zero() {
+        let json =
+            r#"{"manifest": [{"version": "0.1.0", "stable": true, "files": []}], "timestamp": 0}"#;
+        let cached: CachedManifest = serde_json::from_str(json).expect("Should deserialize");
+        assert_eq!(cached.schema_version, 0);
+        assert_eq!(cached.manifest[0].notes, None);
+    }
+
+    #[test]
+    fn version_entry_notes_are_optional() {
+        let json = r#"{"version": "0.2.0", "stable": true, "notes": "* Fixes", "files": []}"#;
+        let entry: VersionEntry = serde_json::from_str(json).expect("Should parse entry");
+        assert_eq!(entry.notes.as_deref(), Some("* Fixes"));
+
+        let without = VersionEntry {
+            notes: None,
+            ..entry
+        };
+        let json = serde_json::to_string(&without).expect("Should serialize");
+        assert!(!json.contains("notes"), "{json}");
     }
 
     #[test]
@@ -914,7 +1538,7 @@ mod tests {
 
     #[test]
     fn handle_http_error_404() {
-        let error = handle_http_error(reqwest::StatusCode::NOT_FOUND, "https://example.com");
+        let error = handle_http_error(reqwest::StatusCode::NOT_FOUND, "https://example.com", None);
         assert!(error.to_string().contains("not found"));
     }
 
@@ -923,16 +1547,34 @@ mod tests {
         let error = handle_http_error(
             reqwest::StatusCode::INTERNAL_SERVER_ERROR,
             "https://example.com",
+            None,
         );
         assert!(error.to_string().contains("500"));
     }
 
     #[test]
     fn handle_http_error_generic() {
-        let error = handle_http_error(reqwest::StatusCode::BAD_REQUEST, "https://example.com");
+        let error = handle_http_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            "https://example.com",
+            None,
+        );
         assert!(error.to_string().contains("400"));
     }
 
+    #[test]
+    fn handle_http_error_rate_limit_includes_wait() {
+        let error = handle_http_error(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            "https://example.com",
+            Some(Duration::from_mins(1)),
+        );
+        assert!(error.to_string().contains("retry in 60 seconds"));
+
+        let error = handle_http_error(reqwest::StatusCode::FORBIDDEN, "https://example.com", None);
+        assert!(error.to_string().contains("try again later"));
+    }
+
     #[test]
     fn latest_stable_returns_none_for_empty_manifest() {
         let manifest: Manifest = vec![];
@@ -945,11 +1587,13 @@ mod tests {
             VersionEntry {
                 version: "0.1.0-alpha".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
             VersionEntry {
                 version: "0.2.0-beta".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
         ];
@@ -979,11 +1623,13 @@ mod tests {
             VersionEntry {
                 version: "0.1.0-alpha".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
             VersionEntry {
                 version: "0.2.0-beta".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
         ];
@@ -998,11 +1644,13 @@ mod tests {
             VersionEntry {
                 version: "0.1.0-alpha".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
             VersionEntry {
                 version: "0.2.0-beta".to_string(),
                 stable: false,
+                notes: None,
                 files: vec![],
             },
         ];
@@ -1026,11 +1674,198 @@ mod tests {
         assert!(entry.stable);
     }
 
+    fn range_manifest() -> Manifest {
+        ["0.1.0", "0.1.5", "0.2.0", "0.2.4", "0.3.0-alpha", "0.10.0"]
+            .iter()
+            .map(|version| VersionEntry {
+                version: (*version).to_string(),
+                stable: !version.contains('-'),
+                notes: None,
+                files: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cmp_version_orders_by_semver_then_string() {
+        use std::cmp::Ordering;
+
+        let entry = |version: &str| VersionEntry {
+            version: version.to_string(),
+            stable: true,
+            notes: None,
+            files: vec![],
+        };
+
+        assert_eq!(
+            entry("0.10.0").cmp_version(&entry("0.9.0")),
+            Ordering::Greater
+        );
+        assert_eq!(
+            entry("0.3.0-alpha").cmp_version(&entry("0.3.0")),
+            Ordering::Less
+        );
+        assert_eq!(
+            entry("nightly").cmp_version(&entry("0.1.0")),
+            Ordering::Less
+        );
+        assert_eq!(
+            entry("beta").cmp_version(&entry("alpha")),
+            Ordering::Greater
+        );
+        assert_eq!(entry("0.2.0").cmp_version(&entry("0.2.0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn find_matching_version_resolves_sample_manifest() {
+        let manifest: Manifest =
+            serde_json::from_str(sample_manifest_json()).expect("Should parse manifest");
+        let resolve = |req: &str, pre: bool| {
+            let req = semver::VersionReq::parse(req).expect("Should parse requirement");
+            find_matching_version(&manifest, &req, pre).map(|entry| entry.version.as_str())
+        };
+
+        assert_eq!(resolve("^0.1", false), Some("0.1.0"));
+        assert_eq!(resolve("*", false), Some("0.2.0"));
+        assert_eq!(resolve("*", true), Some("0.3.0-alpha"));
+        assert_eq!(resolve(">=0.4, <1", true), None);
+    }
+
+    #[test]
+    fn resolve_version_caret_range_picks_highest_match() {
+        let manifest = range_manifest();
+        let entry = resolve_version(&manifest, Some("^0.1"), false).expect("Should resolve");
+        assert_eq!(entry.version, "0.1.5");
+    }
+
+    #[test]
+    fn resolve_version_compound_range_picks_highest_match() {
+        let manifest = range_manifest();
+        let entry = resolve_version(&manifest, Some(">=0.2, <0.3"), false).expect("Should resolve");
+        assert_eq!(entry.version, "0.2.4");
+    }
+
+    #[test]
+    fn resolve_version_prefers_exact_match() {
+        let manifest = range_manifest();
+        let entry = resolve_version(&manifest, Some("0.1.0"), false).expect("Should resolve");
+        assert_eq!(entry.version, "0.1.0");
+
+        let err = resolve_version(&manifest, Some("0.1.1"), false).expect_err("Should fail");
+        assert!(err.to_string().contains("Version 0.1.1 not found"));
+    }
+
+    #[test]
+    fn version_candidates_cover_both_tag_forms() {
+        assert_eq!(version_candidates("0.1.0"), vec!["0.1.0", "v0.1.0"]);
+        assert_eq!(version_candidates("v0.1.0"), vec!["0.1.0", "v0.1.0"]);
+        assert_eq!(
+            version_candidates(" 0.3.0-alpha "),
+            vec!["0.3.0-alpha", "v0.3.0-alpha"]
+        );
+    }
+
+    #[test]
+    fn find_version_by_number_accepts_either_prefix() {
+        let mut manifest = range_manifest();
+        manifest.push(VersionEntry {
+            version: "v0.4.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![],
+        });
+
+        let entry = find_version_by_number(&manifest, "v0.1.0").expect("Should find 0.1.0");
+        assert_eq!(entry.version, "0.1.0");
+        let entry = find_version_by_number(&manifest, "0.4.0").expect("Should find v0.4.0");
+        assert_eq!(entry.version, "v0.4.0");
+        let entry = resolve_version(&manifest, Some("0.4.0"), false).expect("Should resolve");
+        assert_eq!(entry.version, "v0.4.0");
+
+        let err = find_version_by_number(&manifest, "0.9.0").expect_err("Should fail");
+        assert!(
+            err.to_string()
+                .contains("Version 0.9.0 not found in manifest (tried 0.9.0, v0.9.0)")
+        );
+        assert_eq!(
+            InfsError::find(&err).map(InfsError::exit_code),
+            Some(crate::errors::EXIT_VERSION_NOT_FOUND)
+        );
+    }
+
+    #[test]
+    fn resolve_version_excludes_prereleases_unless_requested() {
+        let manifest = range_manifest();
+
+        let err = resolve_version(&manifest, Some("^0.3"), false).expect_err("Should fail");
+        assert!(err.to_string().contains("--pre"));
+
+        let entry = resolve_version(&manifest, Some("^0.3"), true).expect("Should resolve");
+        assert_eq!(entry.version, "0.3.0-alpha");
+    }
+
+    #[test]
+    fn resolve_version_latest_respects_prerelease_flag() {
+        let mut manifest = range_manifest();
+
+        // A newer stable release wins over an older prerelease.
+        let latest = resolve_version(&manifest, None, true).expect("Should resolve");
+        assert_eq!(latest.version, "0.10.0");
+
+        manifest.push(VersionEntry {
+            version: "0.11.0-rc.1".to_string(),
+            stable: false,
+            notes: None,
+            files: vec![],
+        });
+
+        let stable = resolve_version(&manifest, None, false).expect("Should resolve");
+        assert_eq!(stable.version, "0.10.0");
+        let latest = resolve_version(&manifest, Some("latest"), true).expect("Should resolve");
+        assert_eq!(latest.version, "0.11.0-rc.1");
+    }
+
+    #[test]
+    fn resolve_version_rejects_unparseable_and_unmatched() {
+        let manifest = range_manifest();
+
+        let err =
+            resolve_version(&manifest, Some("not-a-version"), false).expect_err("Should fail");
+        assert!(err.to_string().contains("Version not-a-version not found"));
+
+        let err = resolve_version(&manifest, Some("^1"), true).expect_err("Should fail");
+        assert!(err.to_string().contains("No version matching '^1'"));
+    }
+
+    #[test]
+    fn version_choices_lists_ambiguous_matches_highest_first() {
+        let manifest = range_manifest();
+        let versions = |version: &str, pre: bool| -> Vec<String> {
+            version_choices(&manifest, version, pre)
+                .into_iter()
+                .map(|entry| entry.version.clone())
+                .collect()
+        };
+
+        assert_eq!(versions("0.1", false), ["0.1.5", "0.1.0"]);
+        assert_eq!(versions("^0.2", true), ["0.2.4", "0.2.0"]);
+        // Only a prerelease matches, so it is offered rather than rejected.
+        assert_eq!(versions("^0.3", false), ["0.3.0-alpha"]);
+
+        assert!(versions("^0.3", true).is_empty());
+        assert!(versions("^0.10", false).is_empty());
+        assert!(versions("0.1.0", false).is_empty());
+        assert!(versions("latest", true).is_empty());
+        assert!(versions("^1", true).is_empty());
+        assert!(versions("not-a-version", false).is_empty());
+    }
+
     #[test]
     fn find_artifact_with_tool_parameter() {
         let entry = VersionEntry {
             version: "0.1.0".to_string(),
             stable: true,
+            notes: None,
             files: vec![
                 FileEntry {
                     url: "https://example.com/infc-linux-x64.tar.gz".to_string(),
@@ -1201,4 +2036,178 @@ mod tests {
         // Whitespace is preserved (not trimmed)
         assert_eq!(entry.filename(), "infc-linux-x64.tar.gz ");
     }
+
+    fn file(url: &str) -> FileEntry {
+        FileEntry {
+            url: url.to_string(),
+            sha256: "a".repeat(64),
+        }
+    }
+
+    #[test]
+    fn file_entry_libc_parses_musl_suffix() {
+        let musl = file("https://example.com/infc-linux-x64-musl.tar.gz");
+        assert_eq!(musl.tool(), "infc");
+        assert_eq!(musl.os(), "linux");
+        assert_eq!(musl.libc(), Libc::Musl);
+
+        let glibc = file("https://example.com/infc-linux-x64.tar.gz");
+        assert_eq!(glibc.libc(), Libc::Glibc);
+
+        let zip = file("https://example.com/infc-windows-x64.zip");
+        assert_eq!(zip.libc(), Libc::Glibc);
+    }
+
+    #[test]
+    fn file_entry_libc_ignores_musl_outside_suffix() {
+        let entry = file("https://example.com/musl-linux-x64.tar.gz");
+        assert_eq!(entry.libc(), Libc::Glibc);
+    }
+
+    #[test]
+    fn find_artifact_for_libc_prefers_matching_libc() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![
+                file("https://example.com/infc-linux-x64.tar.gz"),
+                file("https://example.com/infc-linux-x64-musl.tar.gz"),
+                file("https://example.com/infc-macos-arm64.tar.gz"),
+            ],
+        };
+
+        let musl = entry
+            .find_artifact_for_libc(Platform::LinuxX64, "infc", Some(Libc::Musl))
+            .expect("Should find musl artifact");
+        assert_eq!(musl.filename(), "infc-linux-x64-musl.tar.gz");
+
+        let glibc = entry
+            .find_artifact_for_libc(Platform::LinuxX64, "infc", Some(Libc::Glibc))
+            .expect("Should find glibc artifact");
+        assert_eq!(glibc.filename(), "infc-linux-x64.tar.gz");
+
+        let macos = entry
+            .find_artifact_for_libc(Platform::MacosArm64, "infc", None)
+            .expect("Should find macOS artifact");
+        assert_eq!(macos.filename(), "infc-macos-arm64.tar.gz");
+    }
+
+    #[test]
+    fn find_artifact_for_libc_falls_back_to_glibc() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![file("https://example.com/infc-linux-x64.tar.gz")],
+        };
+
+        let artifact = entry
+            .find_artifact_for_libc(Platform::LinuxX64, "infc", Some(Libc::Musl))
+            .expect("Should fall back to glibc artifact");
+        assert_eq!(artifact.libc(), Libc::Glibc);
+    }
+
+    #[test]
+    fn find_artifact_for_libc_does_not_pick_musl_for_glibc() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![file("https://example.com/infc-linux-x64-musl.tar.gz")],
+        };
+
+        assert!(
+            entry
+                .find_artifact_for_libc(Platform::LinuxX64, "infc", Some(Libc::Glibc))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn libc_mismatch_warning_only_for_linux() {
+        let artifact = file("https://example.com/infc-macos-arm64.tar.gz");
+        assert!(libc_mismatch_warning(Platform::MacosArm64, &artifact).is_none());
+    }
+
+    #[test]
+    fn arch_is_parsed_from_filename() {
+        assert_eq!(file("https://x/infc-linux-x64.tar.gz").arch(), "x64");
+        assert_eq!(file("https://x/infc-linux-x64-musl.tar.gz").arch(), "x64");
+        assert_eq!(
+            file("https://x/infs-macos-apple-silicon.tar.gz").arch(),
+            "apple-silicon"
+        );
+        assert_eq!(file("https://x/infc-windows-x64.zip").arch(), "x64");
+        assert_eq!(file("https://x/infc.tar.gz").arch(), "");
+    }
+
+    #[test]
+    fn find_artifact_with_fallback_prefers_native_artifact() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![
+                file("https://example.com/infc-macos-x64.tar.gz"),
+                file("https://example.com/infc-macos-apple-silicon.tar.gz"),
+            ],
+        };
+
+        let (artifact, fallback) = entry
+            .find_artifact_with_fallback(Platform::MacosArm64)
+            .expect("Should find native artifact");
+        assert_eq!(artifact.filename(), "infc-macos-apple-silicon.tar.gz");
+        assert!(!fallback);
+    }
+
+    #[test]
+    fn find_artifact_with_fallback_uses_x64_under_rosetta() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![
+                file("https://example.com/infc-linux-x64.tar.gz"),
+                file("https://example.com/infc-macos-x64.tar.gz"),
+            ],
+        };
+
+        assert!(entry.find_artifact(Platform::MacosArm64, "infc").is_none());
+        let (artifact, fallback) = entry
+            .find_artifact_with_fallback(Platform::MacosArm64)
+            .expect("Should fall back to x64 artifact");
+        assert_eq!(artifact.filename(), "infc-macos-x64.tar.gz");
+        assert!(fallback);
+        assert!(fallback_warning(Platform::MacosArm64, artifact).contains("Rosetta 2"));
+    }
+
+    #[test]
+    fn find_artifact_with_fallback_returns_none_without_artifact() {
+        let entry = VersionEntry {
+            version: "0.1.0".to_string(),
+            stable: true,
+            notes: None,
+            files: vec![
+                file("https://example.com/infc-linux-arm64.tar.gz"),
+                file("https://example.com/infs-macos-x64.tar.gz"),
+            ],
+        };
+
+        assert!(
+            entry
+                .find_artifact_with_fallback(Platform::LinuxX64)
+                .is_none()
+        );
+        assert!(
+            entry
+                .find_artifact_with_fallback(Platform::MacosArm64)
+                .is_none()
+        );
+        assert!(
+            entry
+                .find_artifact_with_fallback(Platform::WindowsX64)
+                .is_none()
+        );
+    }
 }
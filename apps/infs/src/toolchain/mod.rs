@@ -8,24 +8,45 @@
 //! - [`platform`] - OS and architecture detection
 //! - [`paths`] - Toolchain directory path management
 //! - [`manifest`] - Release manifest fetching and parsing
+//! - [`release_notes`] - Plain-text rendering of release notes
 //! - [`download`] - HTTP download with progress tracking
+//! - [`http`] - HTTP client timeouts
 //! - [`verify`] - SHA256 checksum verification
+//! - [`signature`] - Minisign signature verification
 //! - [`archive`] - ZIP and tar.gz archive extraction utilities
+//! - [`bundle`] - Toolchain export and import for offline machines
+//! - [`installer`] - Installation pipeline shared by the CLI and TUI
+//! - [`lock`] - Cross-process lock around toolchain changes
 //! - [`doctor`] - Toolchain health checks
+//! - [`fix`] - Automatic fixes for doctor checks
 //! - [`conflict`] - PATH conflict detection
+//! - [`installations`] - Legacy and duplicate installation detection
+//! - [`compat`] - External tool version compatibility
 
 pub mod archive;
+pub mod bundle;
+pub mod compat;
 pub mod conflict;
 pub mod doctor;
 pub mod download;
+pub mod fix;
+pub mod http;
+pub mod installations;
+pub mod installer;
+pub mod lock;
 pub mod manifest;
 pub mod paths;
 pub mod platform;
+pub mod release_notes;
 pub mod resolver;
 pub mod shell;
+pub mod signature;
 pub mod verify;
 
-pub use archive::{extract_archive, set_executable_permissions};
+pub use archive::{
+    extract_archive, extract_archive_with_progress, preserves_permissions,
+    set_executable_permissions,
+};
 pub use download::{ProgressCallback, ProgressEvent, download_file, download_file_with_callback};
 pub use manifest::{fetch_artifact, fetch_manifest, latest_stable, latest_version};
 pub use paths::ToolchainPaths;
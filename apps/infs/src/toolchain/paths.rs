@@ -20,7 +20,10 @@
 //!   bin/                      # Symlinks to default toolchain binaries
 //!   downloads/                # Download cache
 //!   cache/                    # Cached data (manifest, etc.)
+//!   logs/                     # Output of commands run from the TUI
 //!   default                   # File containing default version string
+//!   config.toml               # User configuration (optional)
+//!   copied-links.json         # Entries of bin/ that are not symlinks (Windows)
 //! ```
 //!
 //! Note: Binaries are searched first in the `bin/` subdirectory, then at the
@@ -31,6 +34,8 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use super::signature::SignatureStatus;
+
 /// Environment variable to override the default toolchain root directory.
 pub const INFERENCE_HOME_ENV: &str = "INFERENCE_HOME";
 
@@ -40,9 +45,15 @@ const METADATA_FILE: &str = ".metadata.json";
 /// Metadata file for the infs CLI itself.
 const INFS_METADATA_FILE: &str = "infs.json";
 
+/// User configuration file for infs.
+const CONFIG_FILE: &str = "config.toml";
+
 /// Current schema version for infs metadata.
 const INFS_METADATA_SCHEMA_VERSION: u32 = 1;
 
+/// File recording the entries of `bin/` that are not symlinks.
+const COPIED_LINKS_FILE: &str = "copied-links.json";
+
 /// Metadata about a toolchain installation.
 ///
 /// This is stored in each toolchain version directory as `.metadata.json`.
@@ -50,6 +61,43 @@ const INFS_METADATA_SCHEMA_VERSION: u32 = 1;
 pub struct ToolchainMetadata {
     /// ISO 8601 timestamp of when the toolchain was installed.
     pub installed_at: String,
+    /// Outcome of the signature check on the installed archive.
+    ///
+    /// Metadata written before signatures were checked reads as
+    /// [`SignatureStatus::Unknown`].
+    #[serde(default)]
+    pub signature: SignatureStatus,
+    /// Where the toolchain was installed from.
+    ///
+    /// Metadata written before the source was recorded reads as
+    /// [`InstallSource::Download`].
+    #[serde(default)]
+    pub source: InstallSource,
+}
+
+/// Where an installed toolchain came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InstallSource {
+    /// Downloaded from the distribution server.
+    #[default]
+    Download,
+    /// Installed from an archive on disk with `infs install --from`.
+    Archive,
+    /// Imported from a bundle made by `infs toolchain export`.
+    Imported,
+}
+
+impl InstallSource {
+    /// Returns a short label for `infs list`, or `None` for downloads.
+    #[must_use]
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Self::Download => None,
+            Self::Archive => Some("local archive"),
+            Self::Imported => Some("imported"),
+        }
+    }
 }
 
 /// Metadata about the infs CLI itself (not toolchains).
@@ -152,7 +200,8 @@ impl DateTime {
 }
 
 /// Formats a Unix timestamp as an ISO 8601 datetime string (YYYY-MM-DDTHH:MM:SSZ).
-fn format_timestamp_iso8601(timestamp: u64) -> String {
+#[must_use]
+pub fn format_timestamp_iso8601(timestamp: u64) -> String {
     let dt = DateTime::from_timestamp(timestamp);
     format!(
         "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
@@ -172,9 +221,25 @@ impl ToolchainMetadata {
 
         Self {
             installed_at: format_timestamp(timestamp),
+            signature: SignatureStatus::default(),
+            source: InstallSource::default(),
         }
     }
 
+    /// Records the outcome of the signature check.
+    #[must_use = "returns updated metadata without side effects"]
+    pub fn with_signature(mut self, signature: SignatureStatus) -> Self {
+        self.signature = signature;
+        self
+    }
+
+    /// Records where the toolchain was installed from.
+    #[must_use = "returns updated metadata without side effects"]
+    pub fn with_source(mut self, source: InstallSource) -> Self {
+        self.source = source;
+        self
+    }
+
     /// Returns a human-readable relative time string (e.g., "2 days ago").
     #[must_use = "returns formatted time without side effects"]
     pub fn installed_ago(&self) -> String {
@@ -253,6 +318,91 @@ fn parse_and_format_relative_time(date_str: &str) -> String {
     }
 }
 
+/// How an entry of the bin directory refers to its toolchain binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    /// A symbolic link, which always resolves to the toolchain binary.
+    Symlink,
+    /// A hard link, which keeps the old binary alive after an uninstall.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    HardLink,
+    /// A copy of the binary.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    Copy,
+}
+
+/// Entries of the bin directory that are hard links or copies, with the
+/// toolchain version each was made from.
+///
+/// On Windows, creating a symlink can fail without Developer Mode, and
+/// [`create_link`] falls back to a hard link or a copy. Unlike a symlink,
+/// such an entry still looks valid after its toolchain is uninstalled or
+/// the default changes, so the version is recorded to detect that.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CopiedLinks {
+    versions: std::collections::BTreeMap<String, String>,
+}
+
+impl CopiedLinks {
+    /// Reads the records at `path`; a missing or unreadable file means none.
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the records to `path`, or removes the file if there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or removed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if self.versions.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize links")?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Records how `binary` was linked to toolchain `version`.
+    ///
+    /// Symlinks need no record, so linking one forgets an earlier copy.
+    pub fn record(&mut self, binary: &str, version: &str, kind: LinkKind) {
+        if kind == LinkKind::Symlink {
+            self.forget(binary);
+        } else {
+            self.versions
+                .insert(binary.to_string(), version.to_string());
+        }
+    }
+
+    /// Forgets the record of `binary`, e.g. after removing it.
+    pub fn forget(&mut self, binary: &str) {
+        self.versions.remove(binary);
+    }
+
+    /// Returns the version `binary` was copied from, if it is a copy.
+    #[must_use]
+    pub fn version(&self, binary: &str) -> Option<&str> {
+        self.versions.get(binary).map(String::as_str)
+    }
+
+    /// Returns whether `binary` is a copy of a toolchain other than
+    /// `default_version`, which must be copied again.
+    #[must_use]
+    pub fn is_stale(&self, binary: &str, default_version: Option<&str>) -> bool {
+        self.version(binary)
+            .is_some_and(|version| Some(version) != default_version)
+    }
+}
+
 /// Manages paths for toolchain installations.
 ///
 /// This struct provides access to all toolchain-related directories and files,
@@ -337,12 +487,48 @@ impl ToolchainPaths {
         self.root.join("default")
     }
 
+    /// Returns the path to the lock file held while toolchains are changed.
+    #[must_use = "returns the path without side effects"]
+    pub fn lock_file(&self) -> PathBuf {
+        self.root.join(".lock")
+    }
+
     /// Returns the path to the infs metadata file.
     #[must_use = "returns the path without side effects"]
     pub fn infs_metadata_path(&self) -> PathBuf {
         self.root.join(INFS_METADATA_FILE)
     }
 
+    /// Returns the path to the user configuration file.
+    #[must_use = "returns the path without side effects"]
+    pub fn config_path(&self) -> PathBuf {
+        self.root.join(CONFIG_FILE)
+    }
+
+    /// Returns the directory holding cached data such as the release manifest.
+    #[must_use = "returns the path without side effects"]
+    pub fn cache_dir(&self) -> PathBuf {
+        self.root.join("cache")
+    }
+
+    /// Returns the path to the record of bin entries that are not symlinks.
+    #[must_use = "returns the path without side effects"]
+    pub fn copied_links_path(&self) -> PathBuf {
+        self.root.join(COPIED_LINKS_FILE)
+    }
+
+    /// Returns the directory holding user project templates.
+    #[must_use = "returns the path without side effects"]
+    pub fn templates_dir(&self) -> PathBuf {
+        self.root.join("templates")
+    }
+
+    /// Returns the directory holding the output of commands run from the TUI.
+    #[must_use = "returns the path without side effects"]
+    pub fn logs_dir(&self) -> PathBuf {
+        self.root.join("logs")
+    }
+
     /// Writes infs metadata to the metadata file.
     ///
     /// # Errors
@@ -506,7 +692,8 @@ impl ToolchainPaths {
 
     /// Creates a symlink from the global bin directory to a toolchain binary.
     ///
-    /// On Windows, this creates a hard link or copies the file if symlinks are not supported.
+    /// On Windows, this creates a hard link or copies the file if symlinks are
+    /// not supported, and records that in [`CopiedLinks`].
     ///
     /// # Errors
     ///
@@ -522,13 +709,18 @@ impl ToolchainPaths {
             );
         }
 
-        if target.exists() {
+        // Use symlink_metadata so broken symlinks are replaced as well.
+        if target.symlink_metadata().is_ok() {
             std::fs::remove_file(&target).with_context(|| {
                 format!("Failed to remove existing symlink: {}", target.display())
             })?;
         }
 
-        create_link(&source, &target)
+        let kind = create_link(&source, &target)?;
+        let record = self.copied_links_path();
+        let mut copies = CopiedLinks::load(&record);
+        copies.record(binary_name, version, kind);
+        copies.save(&record)
     }
 
     /// Removes a symlink from the global bin directory.
@@ -544,6 +736,12 @@ impl ToolchainPaths {
             std::fs::remove_file(&target)
                 .with_context(|| format!("Failed to remove symlink: {}", target.display()))?;
         }
+        let record = self.copied_links_path();
+        let mut copies = CopiedLinks::load(&record);
+        if copies.version(binary_name).is_some() {
+            copies.forget(binary_name);
+            copies.save(&record)?;
+        }
         Ok(())
     }
 
@@ -620,27 +818,79 @@ impl ToolchainPaths {
 
     /// Checks if symlinks in the bin directory are valid (point to existing binaries).
     ///
-    /// Returns a list of binary names that have broken symlinks.
+    /// Returns a list of binary names that have broken symlinks. Copies (see
+    /// [`CopiedLinks`]) of a toolchain other than the default count as broken.
     #[must_use = "returns list of broken symlinks without side effects"]
     pub fn validate_symlinks(&self) -> Vec<String> {
         let Ok(platform) = crate::toolchain::Platform::detect() else {
             return Vec::new();
         };
         let ext = platform.executable_extension();
+        let copies = CopiedLinks::load(&self.copied_links_path());
+        let default_version = self.get_default_version().ok().flatten();
 
         let mut broken = Vec::new();
         for name in Self::MANAGED_BINARIES {
             let binary = format!("{name}{ext}");
-            let symlink_path = self.symlink_path(&binary);
-
-            // Check if symlink exists (as a symlink, even if broken) but target does not
-            if symlink_path.symlink_metadata().is_ok() && !symlink_path.exists() {
+            if self.is_link_broken(&binary, &copies, default_version.as_deref()) {
                 broken.push(binary);
             }
         }
         broken
     }
 
+    /// Returns whether the bin entry `binary` exists but does not provide the
+    /// binary of `default_version`.
+    fn is_link_broken(
+        &self,
+        binary: &str,
+        copies: &CopiedLinks,
+        default_version: Option<&str>,
+    ) -> bool {
+        let symlink_path = self.symlink_path(binary);
+        // Check if symlink exists (as a symlink, even if broken) but target does not
+        symlink_path.symlink_metadata().is_ok()
+            && (!symlink_path.exists() || copies.is_stale(binary, default_version))
+    }
+
+    /// Returns what the bin entry of each managed binary refers to, for
+    /// diagnostics.
+    ///
+    /// A symlink reports its target, even if that no longer exists. A hard
+    /// link or copy (see [`CopiedLinks`]) reports the toolchain binary it was
+    /// made from, or the entry itself if that is unknown. A missing entry
+    /// reports `None`.
+    #[must_use = "returns link targets without side effects"]
+    pub fn symlink_targets(&self) -> Vec<(String, Option<PathBuf>)> {
+        let Ok(platform) = crate::toolchain::Platform::detect() else {
+            return Vec::new();
+        };
+        let ext = platform.executable_extension();
+        let copies = CopiedLinks::load(&self.copied_links_path());
+
+        Self::MANAGED_BINARIES
+            .iter()
+            .map(|name| {
+                let binary = format!("{name}{ext}");
+                let target = self.link_target(&binary, &copies);
+                (binary, target)
+            })
+            .collect()
+    }
+
+    /// Returns what the bin entry `binary` refers to; see [`Self::symlink_targets`].
+    fn link_target(&self, binary: &str, copies: &CopiedLinks) -> Option<PathBuf> {
+        let entry = self.symlink_path(binary);
+        let metadata = entry.symlink_metadata().ok()?;
+        if metadata.file_type().is_symlink() {
+            return std::fs::read_link(&entry).ok();
+        }
+        match copies.version(binary) {
+            Some(version) => Some(self.binary_path(version, binary)),
+            None => Some(entry),
+        }
+    }
+
     /// Repairs broken symlinks by updating them to point to the default version,
     /// or removing them if no valid default exists.
     ///
@@ -668,8 +918,13 @@ impl ToolchainPaths {
     }
 }
 
-/// Creates a symbolic link (Unix) or hard link (Windows) from source to target.
-fn create_link(source: &Path, target: &Path) -> Result<()> {
+/// Creates a symbolic link from source to target, and returns what was
+/// created.
+///
+/// On Windows, a symlink is tried first; the standard library asks for an
+/// unprivileged symlink, which Developer Mode allows. Without it, a hard
+/// link is created, or as a last resort (e.g. across volumes) a copy.
+fn create_link(source: &Path, target: &Path) -> Result<LinkKind> {
     #[cfg(unix)]
     {
         std::os::unix::fs::symlink(source, target).with_context(|| {
@@ -679,28 +934,37 @@ fn create_link(source: &Path, target: &Path) -> Result<()> {
                 target.display()
             )
         })?;
+        Ok(LinkKind::Symlink)
     }
 
     #[cfg(windows)]
     {
+        use crate::logging::debug;
+
         std::os::windows::fs::symlink_file(source, target)
-            .or_else(|_| std::fs::hard_link(source, target))
-            .or_else(|_| std::fs::copy(source, target).map(|_| ()))
+            .map(|()| LinkKind::Symlink)
+            .or_else(|e| {
+                debug!("Cannot symlink {}: {e}", target.display());
+                std::fs::hard_link(source, target).map(|()| LinkKind::HardLink)
+            })
+            .or_else(|e| {
+                debug!("Cannot hard link {}: {e}", target.display());
+                std::fs::copy(source, target).map(|_| LinkKind::Copy)
+            })
             .with_context(|| {
                 format!(
                     "Failed to create link from {} to {}",
                     source.display(),
                     target.display()
                 )
-            })?;
+            })
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
     use std::env;
 
     #[test]
@@ -734,6 +998,14 @@ mod tests {
         assert_eq!(paths.default_file(), temp_dir.join("default"));
     }
 
+    #[test]
+    fn lock_file_path_is_correct() {
+        let temp_dir = env::temp_dir().join("infs_test_lock_file");
+        let paths = ToolchainPaths::with_root(temp_dir.clone());
+
+        assert_eq!(paths.lock_file(), temp_dir.join(".lock"));
+    }
+
     #[test]
     fn download_path_constructs_correctly() {
         let temp_dir = env::temp_dir().join("infs_test_download");
@@ -850,6 +1122,71 @@ mod tests {
         assert_eq!(ts, "1970-01-02T01:01:01Z");
     }
 
+    #[test]
+    fn copied_links_track_copies_and_their_version() {
+        let mut copies = CopiedLinks::default();
+        copies.record("infc.exe", "0.1.0", LinkKind::Copy);
+        copies.record("inf-llc.exe", "0.1.0", LinkKind::HardLink);
+        copies.record("rust-lld.exe", "0.1.0", LinkKind::Symlink);
+
+        assert_eq!(copies.version("infc.exe"), Some("0.1.0"));
+        assert_eq!(copies.version("rust-lld.exe"), None);
+        assert!(!copies.is_stale("infc.exe", Some("0.1.0")));
+        assert!(copies.is_stale("infc.exe", Some("0.2.0")));
+        assert!(copies.is_stale("inf-llc.exe", None));
+        assert!(!copies.is_stale("rust-lld.exe", Some("0.2.0")));
+
+        // Relinking with a symlink forgets the copy
+        copies.record("infc.exe", "0.2.0", LinkKind::Symlink);
+        assert!(!copies.is_stale("infc.exe", Some("0.2.0")));
+        assert_eq!(copies.version("infc.exe"), None);
+    }
+
+    #[test]
+    fn copied_links_round_trip_and_remove_empty_file() {
+        let temp_dir = temp_test_dir("copied_links");
+        let path = temp_dir.join(COPIED_LINKS_FILE);
+
+        assert_eq!(CopiedLinks::load(&path), CopiedLinks::default());
+        let mut copies = CopiedLinks::default();
+        copies.record("infc.exe", "0.1.0", LinkKind::Copy);
+        copies.save(&path).unwrap();
+        assert_eq!(CopiedLinks::load(&path), copies);
+
+        copies.forget("infc.exe");
+        copies.save(&path).unwrap();
+        assert!(!path.exists());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(CopiedLinks::load(&path), CopiedLinks::default());
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn symlink_targets_report_link_targets() {
+        let temp_dir = temp_test_dir("symlink_targets");
+        let paths = ToolchainPaths::with_root(temp_dir.clone());
+        let ext = crate::toolchain::Platform::detect()
+            .unwrap()
+            .executable_extension();
+        let infc = format!("infc{ext}");
+        let source = paths.binary_path("0.1.0", &infc);
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(&paths.bin).unwrap();
+        std::fs::write(&source, "binary").unwrap();
+
+        paths.create_symlink("0.1.0", &infc).unwrap();
+
+        let targets = paths.symlink_targets();
+        assert_eq!(targets.len(), ToolchainPaths::MANAGED_BINARIES.len());
+        assert_eq!(targets[0], (infc, Some(source)));
+        assert_eq!(targets[1], (format!("inf-llc{ext}"), None));
+        assert_eq!(targets[2], (format!("rust-lld{ext}"), None));
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
+
     #[test]
     fn ensure_infs_metadata_creates_file_if_missing() {
         let temp_dir = env::temp_dir().join("infs_test_ensure_meta");
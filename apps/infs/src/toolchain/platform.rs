@@ -8,9 +8,126 @@
 //! - Linux `x86_64` (`linux-x64`)
 //! - macOS ARM64 (`macos-arm64`)
 //! - Windows `x86_64` (`windows-x64`)
+//!
+//! The identifier, OS, and architecture strings of each platform live in a
+//! single table, [`PLATFORMS`], which drives parsing, display, and matching
+//! artifact file names in the release manifest. Supporting a new platform means adding a
+//! [`Platform`] variant and one row to that table.
+//!
+//! ## C Library Detection
+//!
+//! On Linux, the C library (glibc or musl) is detected at runtime so that
+//! Alpine-based systems receive musl-linked artifacts. Detection checks for a
+//! musl dynamic loader in `/lib`, then parses `ldd --version` output. The
+//! `INFS_FORCE_LIBC` environment variable (`glibc` or `musl`) overrides it.
+//! The result is detected once per process, see [`Libc::current`].
 
 use anyhow::{Result, bail};
 use std::fmt;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Environment variable to override C library detection.
+pub const FORCE_LIBC_ENV: &str = "INFS_FORCE_LIBC";
+
+/// C library a Linux system is built on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Libc {
+    /// GNU C library (most distributions).
+    Glibc,
+    /// musl C library (Alpine and other minimal images).
+    Musl,
+}
+
+impl Libc {
+    /// Detects the C library of the running system.
+    ///
+    /// Honors [`FORCE_LIBC_ENV`] first, then looks for a musl loader, then
+    /// parses `ldd --version`. Defaults to glibc when nothing is conclusive.
+    #[must_use]
+    pub fn detect() -> Self {
+        if let Some(libc) = std::env::var(FORCE_LIBC_ENV)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+        {
+            return libc;
+        }
+
+        if has_musl_loader() {
+            return Self::Musl;
+        }
+
+        ldd_version_output()
+            .and_then(|output| parse_ldd_output(&output))
+            .unwrap_or(Self::Glibc)
+    }
+
+    /// Returns the C library of the running system, running [`Self::detect`]
+    /// on the first call only.
+    #[must_use]
+    pub fn current() -> Self {
+        static CURRENT: OnceLock<Libc> = OnceLock::new();
+        *CURRENT.get_or_init(Self::detect)
+    }
+
+    /// Parses a C library name (`glibc`, `gnu`, or `musl`, case-insensitive).
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "glibc" | "gnu" => Some(Self::Glibc),
+            "musl" => Some(Self::Musl),
+            _ => None,
+        }
+    }
+
+    /// Returns the C library name.
+    #[must_use = "returns the libc string without side effects"]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Glibc => "glibc",
+            Self::Musl => "musl",
+        }
+    }
+}
+
+impl fmt::Display for Libc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Returns `true` if a musl dynamic loader (`/lib/ld-musl-*`) exists.
+fn has_musl_loader() -> bool {
+    std::fs::read_dir("/lib").is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-"))
+    })
+}
+
+/// Runs `ldd --version` and returns its combined output.
+///
+/// musl's `ldd` prints its banner to stderr and exits non-zero, so both
+/// streams are collected regardless of the exit status.
+fn ldd_version_output() -> Option<String> {
+    let output = Command::new("ldd").arg("--version").output().ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+/// Determines the C library from `ldd --version` output.
+#[must_use]
+pub fn parse_ldd_output(output: &str) -> Option<Libc> {
+    let lower = output.to_ascii_lowercase();
+    if lower.contains("musl") {
+        Some(Libc::Musl)
+    } else if lower.contains("glibc") || lower.contains("gnu libc") {
+        Some(Libc::Glibc)
+    } else {
+        None
+    }
+}
 
 /// Represents a supported platform for toolchain binaries.
 ///
@@ -27,7 +144,64 @@ pub enum Platform {
     WindowsX64,
 }
 
+/// Strings identifying a [`Platform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformInfo {
+    /// The platform described by this row.
+    pub platform: Platform,
+    /// Identifier used on the command line and in messages (e.g., `linux-x64`).
+    pub id: &'static str,
+    /// OS segment of artifact file names (e.g., `linux` in `infc-linux-x64.tar.gz`).
+    pub os: &'static str,
+    /// Architecture segments of native artifact file names (e.g., `x64`).
+    pub archs: &'static [&'static str],
+    /// Architecture segments of artifacts that run under emulation, used when
+    /// no native artifact exists (x64 builds under Rosetta 2 on Apple Silicon).
+    pub fallback_archs: &'static [&'static str],
+}
+
+/// Supported platforms, in [`Platform`] declaration order.
+pub const PLATFORMS: &[PlatformInfo] = &[
+    PlatformInfo {
+        platform: Platform::LinuxX64,
+        id: "linux-x64",
+        os: "linux",
+        archs: &["x64"],
+        fallback_archs: &[],
+    },
+    PlatformInfo {
+        platform: Platform::MacosArm64,
+        id: "macos-arm64",
+        os: "macos",
+        archs: &["arm64", "apple-silicon"],
+        fallback_archs: &["x64"],
+    },
+    PlatformInfo {
+        platform: Platform::WindowsX64,
+        id: "windows-x64",
+        os: "windows",
+        archs: &["x64"],
+        fallback_archs: &[],
+    },
+];
+
 impl Platform {
+    /// Returns the identifier strings for this platform.
+    #[must_use]
+    pub fn info(self) -> &'static PlatformInfo {
+        &PLATFORMS[self as usize]
+    }
+
+    /// Returns the supported platform identifiers as a comma-separated list.
+    #[must_use]
+    pub fn supported_ids() -> String {
+        PLATFORMS
+            .iter()
+            .map(|info| info.id)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// Detects the current platform based on compile-time configuration.
     ///
     /// # Errors
@@ -61,24 +235,48 @@ impl Platform {
         #[allow(unreachable_code)]
         {
             bail!(
-                "Unsupported platform: {} on {}. \
-                 Supported platforms are: linux-x64, macos-arm64, windows-x64",
+                "Unsupported platform: {} on {}. Supported platforms are: {}",
                 std::env::consts::OS,
-                std::env::consts::ARCH
+                std::env::consts::ARCH,
+                Self::supported_ids()
             );
         }
     }
 
+    /// Parses a platform identifier (`linux-x64`, `macos-arm64`, `windows-x64`).
+    ///
+    /// The bare OS name (`linux`, `macos`, `windows`) is accepted as well,
+    /// since each OS has exactly one supported architecture.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        PLATFORMS
+            .iter()
+            .find(|info| info.id == value || info.os == value)
+            .map(|info| info.platform)
+    }
+
+    /// Determines the platform of a release artifact from its file name.
+    ///
+    /// File names follow `{tool}-{os}-{arch}.{ext}`; the OS segment selects
+    /// the platform, since each OS has exactly one supported architecture.
+    ///
+    /// Example: `"infc-linux-x64-musl.tar.gz"` -> `Some(Platform::LinuxX64)`
+    #[must_use]
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let os = filename.split('-').nth(1)?;
+        PLATFORMS
+            .iter()
+            .find(|info| info.os == os)
+            .map(|info| info.platform)
+    }
+
     /// Returns the platform identifier string used in manifest URLs and file names.
     ///
     /// These strings match the naming convention used in the release manifest.
     #[must_use = "returns the platform string without side effects"]
     pub fn as_str(self) -> &'static str {
-        match self {
-            Self::LinuxX64 => "linux-x64",
-            Self::MacosArm64 => "macos-arm64",
-            Self::WindowsX64 => "windows-x64",
-        }
+        self.info().id
     }
 
     /// Returns the executable file extension for this platform.
@@ -108,10 +306,17 @@ impl Platform {
     /// One of: `"linux"`, `"macos"`, `"windows"`
     #[must_use = "returns the OS string without side effects"]
     pub fn os(self) -> &'static str {
+        self.info().os
+    }
+
+    /// Returns the C library for this platform.
+    ///
+    /// Returns [`Libc::current`] on Linux and `None` elsewhere.
+    #[must_use]
+    pub fn libc(self) -> Option<Libc> {
         match self {
-            Self::LinuxX64 => "linux",
-            Self::MacosArm64 => "macos",
-            Self::WindowsX64 => "windows",
+            Self::LinuxX64 => Some(Libc::current()),
+            Self::MacosArm64 | Self::WindowsX64 => None,
         }
     }
 }
@@ -133,6 +338,67 @@ mod tests {
         assert_eq!(Platform::WindowsX64.as_str(), "windows-x64");
     }
 
+    #[test]
+    fn platform_table_matches_declaration_order() {
+        for (index, info) in PLATFORMS.iter().enumerate() {
+            assert_eq!(info.platform as usize, index, "row for {}", info.id);
+            assert_eq!(info.platform.info(), info);
+        }
+    }
+
+    #[test]
+    fn every_platform_round_trips_through_strings_and_file_names() {
+        for platform in PLATFORMS.iter().map(|info| info.platform) {
+            assert_eq!(Platform::parse(platform.as_str()), Some(platform));
+            assert_eq!(Platform::parse(&platform.to_string()), Some(platform));
+
+            for ext in ["tar.gz", "zip"] {
+                let filename = format!("infc-{}.{ext}", platform.as_str());
+                assert_eq!(Platform::from_filename(&filename), Some(platform));
+            }
+        }
+    }
+
+    #[test]
+    fn from_filename_rejects_unknown_platforms() {
+        assert_eq!(
+            Platform::from_filename("infc-linux-x64-musl.tar.gz"),
+            Some(Platform::LinuxX64)
+        );
+        assert_eq!(Platform::from_filename("infc-freebsd-x64.tar.gz"), None);
+        assert_eq!(Platform::from_filename("infc.tar.gz"), None);
+        assert_eq!(Platform::from_filename(""), None);
+    }
+
+    #[test]
+    fn every_platform_id_ends_with_a_native_arch() {
+        for info in PLATFORMS {
+            assert_eq!(info.id, format!("{}-{}", info.os, info.archs[0]));
+            assert!(
+                info.fallback_archs
+                    .iter()
+                    .all(|arch| !info.archs.contains(arch)),
+                "fallback arch of {} is also native",
+                info.id
+            );
+        }
+    }
+
+    #[test]
+    fn platform_parse_accepts_identifiers_and_os_names() {
+        for platform in [
+            Platform::LinuxX64,
+            Platform::MacosArm64,
+            Platform::WindowsX64,
+        ] {
+            assert_eq!(Platform::parse(platform.as_str()), Some(platform));
+            assert_eq!(Platform::parse(platform.os()), Some(platform));
+        }
+        assert_eq!(Platform::parse(" Linux-X64 "), Some(Platform::LinuxX64));
+        assert_eq!(Platform::parse("linux-arm64"), None);
+        assert_eq!(Platform::parse(""), None);
+    }
+
     #[test]
     fn platform_display_matches_as_str() {
         assert_eq!(format!("{}", Platform::LinuxX64), "linux-x64");
@@ -173,4 +439,48 @@ mod tests {
         assert_eq!(Platform::MacosArm64.os(), "macos");
         assert_eq!(Platform::WindowsX64.os(), "windows");
     }
+
+    #[test]
+    fn libc_only_reported_for_linux() {
+        assert!(Platform::LinuxX64.libc().is_some());
+        assert_eq!(Platform::LinuxX64.libc(), Some(Libc::current()));
+        assert!(Platform::MacosArm64.libc().is_none());
+        assert!(Platform::WindowsX64.libc().is_none());
+    }
+
+    #[test]
+    fn libc_parse_accepts_known_names() {
+        assert_eq!(Libc::parse("glibc"), Some(Libc::Glibc));
+        assert_eq!(Libc::parse("GNU"), Some(Libc::Glibc));
+        assert_eq!(Libc::parse(" musl\n"), Some(Libc::Musl));
+        assert_eq!(Libc::parse("uclibc"), None);
+    }
+
+    #[test]
+    fn parse_ldd_output_detects_glibc() {
+        let output = "ldd (Ubuntu GLIBC 2.35-0ubuntu3.8) 2.35\n\
+                      Copyright (C) 2022 Free Software Foundation, Inc.";
+        assert_eq!(parse_ldd_output(output), Some(Libc::Glibc));
+
+        let output = "ldd (GNU libc) 2.39";
+        assert_eq!(parse_ldd_output(output), Some(Libc::Glibc));
+    }
+
+    #[test]
+    fn parse_ldd_output_detects_musl() {
+        let output = "musl libc (x86_64)\nVersion 1.2.4\nDynamic Program Loader";
+        assert_eq!(parse_ldd_output(output), Some(Libc::Musl));
+    }
+
+    #[test]
+    fn parse_ldd_output_unknown_returns_none() {
+        assert_eq!(parse_ldd_output(""), None);
+        assert_eq!(parse_ldd_output("ldd: command not found"), None);
+    }
+
+    #[test]
+    fn libc_display_matches_as_str() {
+        assert_eq!(Libc::Glibc.to_string(), "glibc");
+        assert_eq!(Libc::Musl.to_string(), "musl");
+    }
 }
@@ -0,0 +1,170 @@
+//! Plain-text rendering of release notes.
+//!
+//! Release notes in the manifest are Markdown. The terminal and the TUI show
+//! them as plain text: [`strip_markdown`] removes the markup, and
+//! [`excerpt`] keeps the first few lines for list views. Both are pure
+//! functions, so the same notes always render the same way.
+
+/// Number of lines shown by `infs versions --notes` and the TUI picker.
+pub const EXCERPT_LINES: usize = 3;
+
+/// Converts Markdown to plain text.
+///
+/// Headings, emphasis, inline code, block quotes, and code fence lines are
+/// reduced to their text, links and images to their label, and list items
+/// to `- item`. Runs of blank lines collapse into one, and leading and
+/// trailing blank lines are removed.
+#[must_use]
+pub fn strip_markdown(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            continue;
+        }
+        let text = strip_line(trimmed);
+        if text.is_empty() && lines.last().is_none_or(String::is_empty) {
+            continue;
+        }
+        lines.push(text);
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Returns the first `max_lines` non-empty lines of the stripped notes.
+///
+/// A line `...` is appended when lines were left out.
+#[must_use]
+pub fn excerpt(markdown: &str, max_lines: usize) -> String {
+    let stripped = strip_markdown(markdown);
+    let mut lines = stripped.lines().filter(|line| !line.is_empty());
+    let mut kept: Vec<&str> = lines.by_ref().take(max_lines).collect();
+    if lines.next().is_some() {
+        kept.push("...");
+    }
+    kept.join("\n")
+}
+
+/// Strips the block and inline markup of a single trimmed line.
+fn strip_line(line: &str) -> String {
+    let mut line = line;
+    while let Some(rest) = line.strip_prefix('>') {
+        line = rest.trim_start();
+    }
+    let heading = line.trim_start_matches('#');
+    if heading.len() < line.len() && (heading.is_empty() || heading.starts_with(' ')) {
+        line = heading.trim_start();
+    }
+    let inline = strip_inline(line);
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return format!("- {}", strip_inline(item));
+        }
+    }
+    if is_rule(line) {
+        return String::new();
+    }
+    inline
+}
+
+/// Returns true for horizontal rules such as `---` or `***`.
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|marker| compact.chars().all(|c| c.to_string() == *marker))
+}
+
+/// Removes emphasis and code markers and reduces links to their label.
+fn strip_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some((label, after)) = link(rest) {
+            out.push_str(&strip_inline(label));
+            rest = after;
+            continue;
+        }
+        if matches!(c, '*' | '_' | '`') {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Parses `[label](url)` or `![label](url)` at the start of `text`.
+///
+/// Returns the label and the text after the link.
+fn link(text: &str) -> Option<(&str, &str)> {
+    let text = text.strip_prefix('!').unwrap_or(text);
+    let rest = text.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let after_label = &rest[label_end + 2..];
+    let url_end = after_label.find(')')?;
+    Some((&rest[..label_end], &after_label[url_end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &str = "\
+## What's Changed
+
+* Add **structural** diffs by @dev in [#42](https://example.com/42)
+- Fix `infc` crash on empty files
+
+> Note: _requires_ a reinstall.
+
+---
+
+```
+infs install 0.3.0
+```
+
+
+![logo](https://example.com/logo.png)
+";
+
+    #[test]
+    fn strip_markdown_removes_markup() {
+        assert_eq!(
+            strip_markdown(NOTES),
+            "What's Changed\n\
+             \n\
+             - Add structural diffs by @dev in #42\n\
+             - Fix infc crash on empty files\n\
+             \n\
+             Note: requires a reinstall.\n\
+             \n\
+             infs install 0.3.0\n\
+             \n\
+             logo"
+        );
+    }
+
+    #[test]
+    fn strip_markdown_keeps_plain_text() {
+        assert_eq!(strip_markdown("Bug fixes only."), "Bug fixes only.");
+        assert_eq!(strip_markdown("#hashtag, 3 # 4"), "#hashtag, 3 # 4");
+        assert_eq!(strip_markdown("[not a link] (x)"), "[not a link] (x)");
+        assert_eq!(strip_markdown("\n\n"), "");
+    }
+
+    #[test]
+    fn excerpt_keeps_first_lines() {
+        assert_eq!(
+            excerpt(NOTES, 2),
+            "What's Changed\n- Add structural diffs by @dev in #42\n..."
+        );
+        assert_eq!(excerpt("One\n\nTwo", 3), "One\nTwo");
+        assert_eq!(excerpt("", EXCERPT_LINES), "");
+    }
+}
@@ -3,13 +3,15 @@
 //! This module provides functionality for locating the `infc` compiler binary
 //! across different installation contexts. The search order prioritizes:
 //!
-//! 1. Explicit override via `INFC_PATH` environment variable
-//! 2. System PATH via `which::which("infc")`
-//! 3. Managed toolchain at `~/.inference/toolchains/VERSION/bin/infc`
+//! 1. Toolchain selected with the `+version` command-line prefix
+//! 2. Explicit override via `INFC_PATH` environment variable
+//! 3. System PATH via `which::which("infc")`
+//! 4. Managed toolchain at `~/.inference/toolchains/VERSION/bin/infc`
 //!
 //! ## Environment Variables
 //!
-//! - `INFC_PATH`: Explicit path to the infc binary (highest priority)
+//! - `INFC_PATH`: Explicit path to the infc binary (highest priority).
+//!   Must point to an existing file; an empty value is ignored.
 //!
 //! ## Example
 //!
@@ -22,27 +24,69 @@
 
 use anyhow::{Context, Result, bail};
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
+use crate::errors::InfsError;
 use crate::toolchain::paths::ToolchainPaths;
 use crate::toolchain::platform::Platform;
 
 /// Environment variable for explicit infc binary path override.
 const INFC_PATH_ENV: &str = "INFC_PATH";
 
+/// Toolchain version selected for this process with the `+version` prefix.
+static TOOLCHAIN_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Selects the toolchain version used to resolve `infc` for this process.
+///
+/// Called by `main` for `infs +VERSION ...`. The selection takes priority
+/// over every other source and can only be set once.
+pub fn set_toolchain_override(version: &str) {
+    let _ = TOOLCHAIN_OVERRIDE.set(version.to_string());
+}
+
+/// Where a resolved `infc` binary was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfcSource {
+    /// The toolchain selected with the `+version` prefix.
+    ToolchainOverride,
+    /// The `INFC_PATH` environment variable.
+    EnvOverride,
+    /// The system PATH.
+    SystemPath,
+    /// The default managed toolchain.
+    ManagedToolchain,
+}
+
+impl InfcSource {
+    /// Returns a short description of the source.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ToolchainOverride => "+version override",
+            Self::EnvOverride => "INFC_PATH",
+            Self::SystemPath => "PATH",
+            Self::ManagedToolchain => "managed toolchain",
+        }
+    }
+}
+
 /// Locates the `infc` compiler binary.
 ///
 /// Searches for the infc binary in the following priority order:
 ///
-/// 1. **`INFC_PATH` environment variable** - Explicit override for testing
+/// 1. **`+version` prefix** - The toolchain selected on the command line
+///    (see [`set_toolchain_override`])
+/// 2. **`INFC_PATH` environment variable** - Explicit override for testing
 ///    or custom installations
-/// 2. **System PATH** - Uses `which::which("infc")` to find infc in PATH
-/// 3. **Managed toolchain** - Looks in `~/.inference/toolchains/VERSION/bin/infc`
+/// 3. **System PATH** - Uses `which::which("infc")` to find infc in PATH
+/// 4. **Managed toolchain** - Looks in `~/.inference/toolchains/VERSION/bin/infc`
 ///    using the default toolchain version if set
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - `INFC_PATH` is set but the path does not exist
+/// - A `+version` toolchain is selected but not installed or lacks infc
+/// - `INFC_PATH` is set but the path does not exist or is a directory
 /// - No infc binary could be found in any location
 ///
 /// The error message provides helpful guidance on how to install infc.
@@ -56,11 +100,38 @@ const INFC_PATH_ENV: &str = "INFC_PATH";
 ///     .status()?;
 /// ```
 pub fn find_infc() -> Result<PathBuf> {
-    // Priority 1: INFC_PATH environment variable
-    if let Ok(path) = std::env::var(INFC_PATH_ENV) {
+    resolve_infc().map(|(path, _)| path)
+}
+
+/// Locates the `infc` compiler binary and reports where it was found.
+///
+/// Uses the same priority order as [`find_infc`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`find_infc`].
+pub fn resolve_infc() -> Result<(PathBuf, InfcSource)> {
+    // Priority 1: toolchain selected with the +version prefix
+    if let Some(version) = TOOLCHAIN_OVERRIDE.get() {
+        let paths = ToolchainPaths::new()?;
+        let path = toolchain_infc(&paths, version)?;
+        return Ok((path, InfcSource::ToolchainOverride));
+    }
+
+    // Priority 2: INFC_PATH environment variable (an empty value is ignored)
+    if let Ok(path) = std::env::var(INFC_PATH_ENV)
+        && !path.is_empty()
+    {
         let path = PathBuf::from(path);
-        if path.exists() {
-            return Ok(path);
+        if path.is_file() {
+            return Ok((path, InfcSource::EnvOverride));
+        }
+        if path.is_dir() {
+            bail!(
+                "INFC_PATH environment variable set to '{}', but it is a directory. \
+                 Point it at the infc binary instead.",
+                path.display()
+            );
         }
         bail!(
             "INFC_PATH environment variable set to '{}', but file does not exist",
@@ -68,12 +139,12 @@ pub fn find_infc() -> Result<PathBuf> {
         );
     }
 
-    // Priority 2: System PATH
+    // Priority 3: System PATH
     if let Ok(path) = which::which("infc") {
-        return Ok(path);
+        return Ok((path, InfcSource::SystemPath));
     }
 
-    // Priority 3: Managed toolchain
+    // Priority 4: Managed toolchain
     if let Ok(paths) = ToolchainPaths::new()
         && let Ok(Some(version)) = paths.get_default_version()
     {
@@ -81,28 +152,86 @@ pub fn find_infc() -> Result<PathBuf> {
             Platform::detect().context("Failed to detect platform while searching for infc")?;
         let ext = platform.executable_extension();
         let infc_name = format!("infc{ext}");
-        let infc_path = paths.toolchain_bin_dir(&version).join(&infc_name);
+        let infc_path = paths.binary_path(&version, &infc_name);
 
         if infc_path.exists() {
-            return Ok(infc_path);
+            return Ok((infc_path, InfcSource::ManagedToolchain));
         }
     }
 
-    bail!(
+    Err(InfsError::tool_not_found("infc")).context(
         "infc compiler not found.\n\n\
         The infc compiler is required to build Inference programs.\n\n\
         To install:\n  \
         - Run: infs install latest\n  \
         - Or download from: https://github.com/Inferara/inference/releases\n  \
-        - Or set INFC_PATH environment variable to the infc binary path"
-    );
+        - Or set INFC_PATH environment variable to the infc binary path",
+    )
+}
+
+/// Returns the `infc` binary of an installed toolchain version.
+///
+/// # Errors
+///
+/// Returns an error if the version is not installed or has no infc binary.
+fn toolchain_infc(paths: &ToolchainPaths, version: &str) -> Result<PathBuf> {
+    if !paths.is_version_installed(version) {
+        return Err(InfsError::toolchain_not_found(version)).with_context(|| {
+            format!(
+                "Toolchain version {version} selected with '+{version}' is not installed.\n\
+                 Run 'infs install {version}' to install it."
+            )
+        });
+    }
+
+    let ext = Platform::detect()
+        .context("Failed to detect platform while searching for infc")?
+        .executable_extension();
+    let infc_path = paths.binary_path(version, &format!("infc{ext}"));
+    if !infc_path.is_file() {
+        return Err(InfsError::tool_not_found("infc")).with_context(|| {
+            format!(
+                "Toolchain version {version} has no infc binary at {}",
+                infc_path.display()
+            )
+        });
+    }
+
+    Ok(infc_path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
     use std::env;
 
+    #[test]
+    fn toolchain_infc_resolves_installed_version() {
+        let root = env::temp_dir().join(format!(
+            "infs_test_toolchain_infc_{}",
+            rand::random::<u64>()
+        ));
+        let paths = ToolchainPaths::with_root(root.clone());
+
+        let err = toolchain_infc(&paths, "0.1.0").expect_err("Should fail");
+        assert!(err.to_string().contains("infs install 0.1.0"));
+
+        let bin_dir = paths.toolchain_bin_dir("0.1.0");
+        std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+        let err = toolchain_infc(&paths, "0.1.0").expect_err("Should fail");
+        assert!(err.to_string().contains("no infc binary"));
+
+        let infc = bin_dir.join(format!("infc{}", env::consts::EXE_SUFFIX));
+        std::fs::write(&infc, "").expect("Should write infc");
+        assert_eq!(
+            toolchain_infc(&paths, "0.1.0").expect("Should resolve"),
+            infc
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
     #[test]
     #[serial_test::serial]
     fn infc_path_env_nonexistent_returns_error() {
@@ -157,4 +286,94 @@ mod tests {
             "Error should contain installation instructions: {err}"
         );
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn infc_path_env_existing_file_takes_precedence() {
+        let dir = temp_test_dir("resolver_override");
+        let infc = dir.join("infc");
+        std::fs::write(&infc, "").expect("Should write infc");
+
+        // SAFETY: This test runs in isolation and we restore the env var at the end.
+        unsafe {
+            env::set_var(INFC_PATH_ENV, &infc);
+        }
+
+        let result = resolve_infc();
+
+        // SAFETY: Cleanup - restoring previous state
+        unsafe {
+            env::remove_var(INFC_PATH_ENV);
+        }
+
+        let (path, source) = result.expect("Should resolve override");
+        assert_eq!(path, infc);
+        assert_eq!(source, InfcSource::EnvOverride);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn infc_path_env_directory_returns_error() {
+        let dir = temp_test_dir("resolver_override_dir");
+
+        // SAFETY: This test runs in isolation and we restore the env var at the end.
+        unsafe {
+            env::set_var(INFC_PATH_ENV, &dir);
+        }
+
+        let result = find_infc();
+
+        // SAFETY: Cleanup - restoring previous state
+        unsafe {
+            env::remove_var(INFC_PATH_ENV);
+        }
+
+        let err = result
+            .expect_err("Directory override should fail")
+            .to_string();
+        assert!(err.contains("INFC_PATH"));
+        assert!(err.contains("directory"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn falls_back_to_managed_toolchain_without_override() {
+        let root = temp_test_dir("resolver_fallback");
+        let paths = ToolchainPaths::with_root(root.clone());
+        let bin_dir = paths.toolchain_bin_dir("0.1.0");
+        std::fs::create_dir_all(&bin_dir).expect("Should create bin dir");
+        let infc = bin_dir.join(format!("infc{}", env::consts::EXE_SUFFIX));
+        std::fs::write(&infc, "").expect("Should write infc");
+        paths
+            .set_default_version("0.1.0")
+            .expect("Should set default");
+
+        let original_path = env::var("PATH").unwrap_or_default();
+
+        // SAFETY: This test runs in isolation and we restore the env vars at the end.
+        unsafe {
+            env::set_var(INFC_PATH_ENV, "");
+            env::set_var("PATH", "");
+            env::set_var("INFERENCE_HOME", &root);
+        }
+
+        let result = resolve_infc();
+
+        // SAFETY: Cleanup - restoring previous state
+        unsafe {
+            env::remove_var(INFC_PATH_ENV);
+            env::set_var("PATH", original_path);
+            env::remove_var("INFERENCE_HOME");
+        }
+
+        let (path, source) = result.expect("Should fall back to managed toolchain");
+        assert_eq!(path, infc);
+        assert_eq!(source, InfcSource::ManagedToolchain);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
 }
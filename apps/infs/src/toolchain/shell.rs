@@ -29,7 +29,7 @@ use std::path::{Path, PathBuf};
 
 /// Marker comment used to identify inference PATH configuration.
 #[cfg(unix)]
-const INFERENCE_MARKER: &str = "# Inference toolchain";
+pub(crate) const INFERENCE_MARKER: &str = "# Inference toolchain";
 
 /// Represents supported shell types.
 #[cfg(unix)]
@@ -319,13 +319,67 @@ pub fn format_result_message(result: &ConfigureResult, bin_path: &Path) -> Strin
     }
 }
 
+/// Describes how to put `bin_path` on PATH in the way `configure_path` would.
+///
+/// If the detected shell profile already contains the inference PATH entry,
+/// the profile only needs to be reloaded. Otherwise the message names the
+/// profile and the exact line `configure_path` appends to it.
+#[cfg(unix)]
+#[must_use]
+pub fn path_remedy(bin_path: &Path) -> String {
+    let profile = Shell::detect().and_then(|shell| {
+        let home_dir = dirs::home_dir()?;
+        let candidates = shell.profile_candidates(&home_dir);
+        let profile = find_existing_profile(&candidates).or_else(|| candidates.first().cloned())?;
+        Some((shell, profile))
+    });
+
+    let Some((shell, profile)) = profile else {
+        return format!(
+            "Add to your PATH:\n  {}",
+            format_manual_path_instruction(bin_path)
+        );
+    };
+
+    if is_path_configured(&profile).unwrap_or(false) {
+        return format!(
+            "PATH is configured in {} but not active in this shell. Run '{}' or restart your terminal.",
+            profile.display(),
+            shell.source_command(&profile)
+        );
+    }
+
+    let config = shell.path_config(bin_path);
+    let line = config.trim().lines().last().unwrap_or_default();
+    format!("Add to {}:\n  {line}", profile.display())
+}
+
+/// Describes how to put `bin_path` on PATH in the way `configure_path` would.
+#[cfg(windows)]
+#[must_use]
+pub fn path_remedy(bin_path: &Path) -> String {
+    format!(
+        "Add {} to the user Path in HKCU\\Environment, then restart your terminal.",
+        bin_path.display()
+    )
+}
+
 /// Returns the manual PATH configuration instruction appropriate for the platform.
 #[must_use]
 #[cfg(unix)]
-fn format_manual_path_instruction(bin_path: &Path) -> String {
+pub fn format_manual_path_instruction(bin_path: &Path) -> String {
     format!("export PATH=\"{}:$PATH\"", bin_path.display())
 }
 
+/// Returns the manual PATH configuration instruction appropriate for the platform.
+///
+/// On Windows this is the directory to append to the user `Path` variable.
+#[must_use]
+#[cfg(windows)]
+pub fn format_manual_path_instruction(bin_path: &Path) -> String {
+    bin_path.display().to_string()
+}
+
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
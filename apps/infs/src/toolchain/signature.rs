@@ -0,0 +1,353 @@
+//! Minisign signature verification for release artifacts.
+//!
+//! Each release artifact may be published with a detached minisign
+//! signature at `{artifact.url}.minisig`. When present, the signature is
+//! checked against `security.public_key` from `config.toml` before the
+//! archive is extracted. Inferara releases are not signed yet, so there is
+//! no built-in key ([`RELEASE_PUBLIC_KEY`]): without a configured key,
+//! signatures are neither fetched nor checked.
+//!
+//! A missing signature or key is not an error by default: the archive is
+//! still protected by its manifest checksum, and infs prints a warning.
+//! Setting `security.require_signatures = true` turns either into an
+//! installation failure.
+
+use std::io::Read;
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+use super::http::{
+    http_timeout, is_rate_limited, rate_limit_message, retry_after, send_with_backoff,
+};
+use super::manifest::USER_AGENT;
+use crate::config::SecurityConfig;
+use crate::errors::InfsError;
+
+/// Minisign public key used to sign Inferara toolchain releases.
+///
+/// `None` until releases are signed; until then signatures are only checked
+/// against `security.public_key`.
+pub const RELEASE_PUBLIC_KEY: Option<&str> = None;
+
+/// Extension appended to an artifact URL to locate its signature.
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+/// Outcome of the signature check for an installed toolchain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureStatus {
+    /// The archive signature was verified.
+    Verified,
+    /// No signature was published; only the checksum was verified.
+    Unsigned,
+    /// Installed before signatures were checked.
+    #[default]
+    Unknown,
+}
+
+impl SignatureStatus {
+    /// Returns a short label for `infs list`, or `None` if nothing was recorded.
+    #[must_use]
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Self::Verified => Some("signed"),
+            Self::Unsigned => Some("unsigned"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// Returns the key signatures are checked against: `security.public_key`,
+/// or the built-in release key, if any.
+#[must_use]
+pub fn public_key(security: &SecurityConfig) -> Option<&str> {
+    security.public_key.as_deref().or(RELEASE_PUBLIC_KEY)
+}
+
+/// Returns the URL of the detached signature for an artifact.
+#[must_use]
+pub fn signature_url(artifact_url: &str) -> String {
+    format!("{artifact_url}.{SIGNATURE_EXTENSION}")
+}
+
+/// Returns the warning printed when an artifact has no signature.
+#[must_use]
+pub fn unsigned_warning(artifact: &str) -> String {
+    format!("no signature found for {artifact}; installing without signature verification")
+}
+
+/// Returns the warning printed when no public key is configured.
+#[must_use]
+pub fn no_key_warning(artifact: &str) -> String {
+    format!(
+        "no public key configured in security.public_key; installing {artifact} without signature verification"
+    )
+}
+
+/// Downloads the signature for an artifact.
+///
+/// Returns `None` if the server answers `404 Not Found`. Rate-limited
+/// requests are retried first, see [`send_with_backoff`].
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the server answers with any
+/// other unsuccessful status.
+pub async fn fetch_signature(artifact_url: &str) -> Result<Option<String>> {
+    let url = signature_url(artifact_url);
+    let client = reqwest::Client::builder()
+        .timeout(http_timeout())
+        .user_agent(USER_AGENT)
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let response = send_with_backoff(|| client.get(&url), "signature", &url).await?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if is_rate_limited(status, response.headers()) {
+        let wait = retry_after(response.headers(), SystemTime::now());
+        return Err(
+            InfsError::network_error(rate_limit_message(&url, wait, SystemTime::now())).into(),
+        );
+    }
+    if !status.is_success() {
+        return Err(InfsError::network_error(format!(
+            "Failed to fetch signature from {url}: HTTP {status}"
+        ))
+        .into());
+    }
+
+    let text = response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read signature from {url}"))?;
+    Ok(Some(text))
+}
+
+/// Checks an archive against its signature according to `security`.
+///
+/// With a signature and a public key (see [`public_key`]), the archive must
+/// verify against the key. Without either, the archive is accepted as
+/// [`SignatureStatus::Unsigned`] unless signatures are required.
+///
+/// # Errors
+///
+/// Returns [`InfsError::SignatureMismatch`] if the signature is invalid, was
+/// made with a different key, or the signature or key is missing while
+/// signatures are required.
+pub fn check_signature(
+    archive_path: &Path,
+    signature: Option<&str>,
+    security: &SecurityConfig,
+) -> Result<SignatureStatus> {
+    let Some(public_key) = public_key(security) else {
+        if security.require_signatures {
+            return Err(InfsError::signature_mismatch(format!(
+                "cannot verify {}: security.require_signatures is enabled but security.public_key is not set",
+                archive_path.display()
+            ))
+            .into());
+        }
+        return Ok(SignatureStatus::Unsigned);
+    };
+    let Some(signature) = signature else {
+        if security.require_signatures {
+            return Err(InfsError::signature_mismatch(format!(
+                "{} has no signature and security.require_signatures is enabled",
+                archive_path.display()
+            ))
+            .into());
+        }
+        return Ok(SignatureStatus::Unsigned);
+    };
+
+    verify_file(archive_path, signature, public_key)?;
+    Ok(SignatureStatus::Verified)
+}
+
+/// Verifies a file against a minisign signature.
+///
+/// `public_key` is either the bare base64 key or the contents of a
+/// minisign `.pub` file.
+///
+/// # Errors
+///
+/// Returns an error if the key or signature cannot be decoded, the file
+/// cannot be read, or the signature does not match.
+pub fn verify_file(file_path: &Path, signature: &str, public_key: &str) -> Result<()> {
+    let key_line = public_key
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or_default();
+    let public_key = PublicKey::from_base64(key_line)
+        .map_err(|e| InfsError::signature_mismatch(format!("invalid public key: {e}")))?;
+    let signature = Signature::decode(signature)
+        .map_err(|e| InfsError::signature_mismatch(format!("invalid signature file: {e}")))?;
+
+    let mismatch = |e: minisign_verify::Error| {
+        anyhow::Error::new(InfsError::signature_mismatch(e.to_string())).context(format!(
+            "Signature verification failed for {}\n\
+             \n\
+             The download may be corrupted or tampered with.",
+            file_path.display()
+        ))
+    };
+
+    let mut verifier = public_key.verify_stream(&signature).map_err(mismatch)?;
+    let mut file = std::fs::File::open(file_path).with_context(|| {
+        format!(
+            "Failed to open file for signature check: {}",
+            file_path.display()
+        )
+    })?;
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let bytes_read = file.read(&mut buffer).with_context(|| {
+            format!(
+                "Failed to read file for signature check: {}",
+                file_path.display()
+            )
+        })?;
+
+        if bytes_read == 0 {
+            break;
+        }
+
+        verifier.update(&buffer[..bytes_read]);
+    }
+
+    verifier.finalize().map_err(mismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::EXIT_CHECKSUM_MISMATCH;
+
+    /// Public key of a throwaway test keypair.
+    const TEST_PUBLIC_KEY: &str = "RWSyWVd1MPYgWp5diRTe42FPUU5FQUwwkY8qHkS7WaYrWmv9qTPw9ywZ";
+
+    /// Public key of another keypair.
+    const OTHER_PUBLIC_KEY: &str = "RWQBAgMEBQYHCAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4f";
+
+    /// Content signed by [`TEST_SIGNATURE`].
+    const SIGNED_CONTENT: &[u8] = b"inference toolchain archive\n";
+
+    /// Signature of [`SIGNED_CONTENT`] made with the test secret key.
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUSyWVd1MPYgWm/5RPtcNkmBOGpENWVuu3xjyoAdWDQvVQln+bNUsHRuMn0sGm4hauLG53f3zV9vZFoUOKzNMxzwWWm2TDpQ4g4=
+trusted comment: timestamp:1760000000\tfile:infc-linux-x64.tar.gz\thashed
+lQgWVlG0Xc9udmnBrSl1MhXGI3CRumy1/qUuoQfT19CP/pXNgogTO6TsWLH7iXFbdJYuoPlnk7NfwLjbiAkYCA==
+";
+
+    fn archive_with(content: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "infs_test_signature_{}.tar.gz",
+            rand::random::<u64>()
+        ));
+        std::fs::write(&path, content).expect("Should write archive");
+        path
+    }
+
+    fn test_security(require_signatures: bool) -> SecurityConfig {
+        SecurityConfig {
+            require_signatures,
+            public_key: Some(TEST_PUBLIC_KEY.to_string()),
+        }
+    }
+
+    fn exit_code(err: &anyhow::Error) -> Option<i32> {
+        InfsError::find(err).map(InfsError::exit_code)
+    }
+
+    #[test]
+    fn valid_signature_is_verified() {
+        let archive = archive_with(SIGNED_CONTENT);
+        let status = check_signature(&archive, Some(TEST_SIGNATURE), &test_security(true));
+        std::fs::remove_file(&archive).ok();
+
+        assert_eq!(status.expect("Should verify"), SignatureStatus::Verified);
+    }
+
+    #[test]
+    fn tampered_archive_is_rejected() {
+        let archive = archive_with(b"inference toolchain archive with a backdoor\n");
+        let err = check_signature(&archive, Some(TEST_SIGNATURE), &test_security(false))
+            .expect_err("Tampered archive should fail");
+        std::fs::remove_file(&archive).ok();
+
+        assert!(err.to_string().contains("Signature verification failed"));
+        assert_eq!(exit_code(&err), Some(EXIT_CHECKSUM_MISMATCH));
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        let archive = archive_with(SIGNED_CONTENT);
+        let err = verify_file(&archive, TEST_SIGNATURE, OTHER_PUBLIC_KEY)
+            .expect_err("Foreign key should fail");
+        std::fs::remove_file(&archive).ok();
+
+        assert!(format!("{err:#}").contains("different key"));
+    }
+
+    #[test]
+    fn missing_signature_depends_on_policy() {
+        let archive = archive_with(SIGNED_CONTENT);
+        let lenient = check_signature(&archive, None, &test_security(false));
+        let strict = check_signature(&archive, None, &test_security(true));
+        std::fs::remove_file(&archive).ok();
+
+        assert_eq!(lenient.expect("Should accept"), SignatureStatus::Unsigned);
+        let err = strict.expect_err("Should require a signature");
+        assert!(err.to_string().contains("security.require_signatures"));
+        assert_eq!(exit_code(&err), Some(EXIT_CHECKSUM_MISMATCH));
+    }
+
+    #[test]
+    fn missing_public_key_depends_on_policy() {
+        let archive = archive_with(SIGNED_CONTENT);
+        let mut security = test_security(false);
+        security.public_key = None;
+        let lenient = check_signature(&archive, Some(TEST_SIGNATURE), &security);
+        security.require_signatures = true;
+        let strict = check_signature(&archive, Some(TEST_SIGNATURE), &security);
+        std::fs::remove_file(&archive).ok();
+
+        assert_eq!(RELEASE_PUBLIC_KEY, None);
+        assert_eq!(lenient.expect("Should accept"), SignatureStatus::Unsigned);
+        let err = strict.expect_err("Should require a key");
+        assert!(err.to_string().contains("security.public_key is not set"));
+        assert_eq!(exit_code(&err), Some(EXIT_CHECKSUM_MISMATCH));
+    }
+
+    #[test]
+    fn public_key_file_contents_are_accepted() {
+        let archive = archive_with(SIGNED_CONTENT);
+        let key_file = format!("untrusted comment: minisign public key\n{TEST_PUBLIC_KEY}\n");
+        let result = verify_file(&archive, TEST_SIGNATURE, &key_file);
+        std::fs::remove_file(&archive).ok();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn metadata_without_signature_reads_as_unknown() {
+        let metadata: crate::toolchain::paths::ToolchainMetadata =
+            serde_json::from_str(r#"{"installed_at":"2025-01-01"}"#)
+                .expect("Should parse old metadata");
+        assert_eq!(metadata.signature, SignatureStatus::Unknown);
+        assert_eq!(
+            signature_url("https://x/a.tar.gz"),
+            "https://x/a.tar.gz.minisig"
+        );
+    }
+}
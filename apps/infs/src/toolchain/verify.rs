@@ -6,9 +6,12 @@
 use std::io::Read;
 use std::path::Path;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
 
+use crate::errors::InfsError;
+use crate::logging::debug;
+
 /// Verifies that a file matches the expected SHA256 checksum.
 ///
 /// # Arguments
@@ -29,18 +32,39 @@ use sha2::{Digest, Sha256};
 /// ```
 pub fn verify_checksum(file_path: &Path, expected: &str) -> Result<()> {
     let computed = compute_sha256(file_path)?;
+    verify_digest(file_path, &computed, expected)
+}
+
+/// Checks an already computed SHA256 digest of `file_path` against the
+/// expected checksum.
+///
+/// Used when the digest was computed while downloading, so the file does
+/// not have to be read again.
+///
+/// # Errors
+///
+/// Returns an error if `computed` does not match `expected`.
+pub fn verify_digest(file_path: &Path, computed: &str, expected: &str) -> Result<()> {
+    let computed = computed.to_lowercase();
+    debug!(
+        "SHA256 of {}: {computed} (expected {})",
+        file_path.display(),
+        expected.to_lowercase()
+    );
 
     if computed != expected.to_lowercase() {
-        bail!(
-            "Checksum verification failed for {}\n\
-             \n\
-             Expected: {expected}\n\
-             Got:      {computed}\n\
-             \n\
-             The download may be corrupted or tampered with.\n\
-             Please try downloading again.",
-            file_path.display()
-        );
+        return Err(InfsError::checksum_mismatch(expected, &computed)).with_context(|| {
+            format!(
+                "Checksum verification failed for {}\n\
+                 \n\
+                 Expected: {expected}\n\
+                 Got:      {computed}\n\
+                 \n\
+                 The download may be corrupted or tampered with.\n\
+                 Please try downloading again.",
+                file_path.display()
+            )
+        });
     }
 
     Ok(())
@@ -82,6 +106,14 @@ pub fn compute_sha256(file_path: &Path) -> Result<String> {
     Ok(hex::encode(hash))
 }
 
+/// Returns whether a string is a well-formed SHA256 hex digest.
+///
+/// Accepts 64 hexadecimal characters in either case.
+#[must_use]
+pub fn is_valid_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,9 +201,34 @@ mod tests {
         std::fs::remove_file(&test_file).ok();
     }
 
+    #[test]
+    fn verify_digest_compares_without_reading_file() {
+        let path = Path::new("/nonexistent/archive.tar.gz");
+        let digest = "a948904f2f0f479b8f8197694b30184b0d2ed1c1cd2a1ec0fb85d299a192a447";
+
+        assert!(verify_digest(path, digest, &digest.to_uppercase()).is_ok());
+        let error = verify_digest(path, digest, &"0".repeat(64)).unwrap_err();
+        assert!(error.to_string().contains("archive.tar.gz"));
+    }
+
     #[test]
     fn compute_sha256_fails_for_nonexistent_file() {
         let result = compute_sha256(Path::new("/nonexistent/file/path"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn is_valid_sha256_checks_length_and_hex_digits() {
+        assert!(is_valid_sha256(
+            "a948904f2f0f479b8f8197694b30184b0d2ed1c1cd2a1ec0fb85d299a192a447"
+        ));
+        assert!(is_valid_sha256(
+            "A948904F2F0F479B8F8197694B30184B0D2ED1C1CD2A1EC0FB85D299A192A447"
+        ));
+        assert!(!is_valid_sha256(""));
+        assert!(!is_valid_sha256("abc123"));
+        assert!(!is_valid_sha256(
+            "g948904f2f0f479b8f8197694b30184b0d2ed1c1cd2a1ec0fb85d299a192a447"
+        ));
+    }
 }
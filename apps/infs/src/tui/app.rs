@@ -14,6 +14,8 @@
 //! - **Toolchains**: List of installed toolchain versions
 //! - **Doctor**: Health check results
 //! - **Progress**: Download/operation progress display
+//! - **Logs**: Saved outputs of the last commands run from the TUI
+//! - **`LogView`**: One saved output, with scrolling and `/` search
 //!
 //! ## Features
 //!
@@ -21,30 +23,56 @@
 //! - Tab completion for commands
 //! - Cursor movement with Left/Right arrows
 //! - Toolchain operations (Enter to set as default)
-
-use std::sync::mpsc::Receiver;
-use std::time::Duration;
+//! - Quit confirmation while a background installation is running
+//! - Retrying a failed installation from the failed phase (r on the progress screen)
+//! - Background release check with an update-available banner on the main
+//!   screen (U to install the new toolchain, x to dismiss)
+//! - Output of build/run commands saved and viewable on the Logs screen
+//! - File picker for `build`, `run`, and `verify` given without a file, with
+//!   fuzzy filtering and the last choice per command preselected
+//! - Automatic fixes on the doctor screen (f for the selected check, F for
+//!   all), confirmed first and run in the background
+
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::Frame;
 
-use super::install_task;
+use super::command_log::LogStore;
+use super::log_viewer::{LogViewerState, PAGE_LINES};
 use super::menu::Menu;
 use super::state::{
-    DoctorState, InstallProgress, ProgressItem, ProgressState, Screen, ToolchainInfo,
-    ToolchainsState, VersionSelectInfo, VersionSelectState,
+    DoctorState, FixProgress, FixRequest, InstallPhase, InstallProgress, LogsState, ProgressItem,
+    ProgressState, ProgressUnit, Screen, SessionState, ToolchainInfo, ToolchainsState,
+    UpdateBannerState, UpdateInfo, VersionSelectInfo, VersionSelectState,
 };
 use super::terminal::TerminalGuard;
 use super::theme::Theme;
-use super::views::{doctor_view, main_view, progress_view, toolchain_view, version_select_view};
+use super::views::{
+    doctor_view, log_viewer_view, logs_view, main_view, progress_view, toolchain_view,
+    version_select_view,
+};
 use super::widgets::command_history::CommandHistory;
+use super::widgets::confirm_dialog;
+use super::widgets::file_picker::{self, FilePickerState, PickerAction};
+use super::{fix_task, install_task};
+use crate::config::InfsConfig;
 use crate::toolchain::ToolchainPaths;
 use crate::toolchain::doctor::run_all_checks;
+use crate::toolchain::lock::ToolchainLock;
 
 /// Event polling timeout in milliseconds.
 const POLL_TIMEOUT_MS: u64 = 100;
 
+/// Interval between background checks for new releases.
+const UPDATE_CHECK_INTERVAL: Duration = Duration::from_mins(30);
+
+/// Prompt shown when quitting while a background task is in flight.
+const CONFIRM_QUIT_MESSAGE: &str = "A download is in progress, quit anyway?";
+
 /// Known commands for tab completion.
 const KNOWN_COMMANDS: &[&str] = &[
     "build",
@@ -57,9 +85,14 @@ const KNOWN_COMMANDS: &[&str] = &[
     "version",
     "quit",
     "toolchains",
+    "logs",
     "exit",
 ];
 
+/// Commands that run on a source file, chosen with the file picker when
+/// none is given.
+const TARGET_COMMANDS: &[&str] = &["build", "run", "verify"];
+
 /// Input mode for the TUI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum InputMode {
@@ -84,6 +117,10 @@ pub struct App {
     status_message: String,
     /// Whether the application should quit.
     should_quit: bool,
+    /// Whether the quit confirmation dialog is shown.
+    confirm_quit: bool,
+    /// Whether `--yes` was given, quitting without the confirmation dialog.
+    assume_yes: bool,
     /// Theme colors.
     theme: Theme,
     /// Menu state.
@@ -94,20 +131,41 @@ pub struct App {
     doctor_state: DoctorState,
     /// Progress view state.
     progress_state: ProgressState,
+    /// Logs view state.
+    logs_state: LogsState,
+    /// Log being viewed on the log view screen.
+    log_viewer: Option<LogViewerState>,
     /// Command history.
     command_history: CommandHistory,
-    /// Command to execute after TUI exits (for commands requiring terminal access).
-    pending_command: Option<String>,
+    /// Command to execute after TUI exits (for commands requiring terminal access),
+    /// as arguments to infs.
+    pending_command: Option<Vec<String>>,
+    /// File picker shown over the current screen, if open.
+    file_picker: Option<FilePickerState>,
+    /// Directory the file picker searches.
+    project_root: PathBuf,
+    /// State kept across TUI restarts.
+    session: SessionState,
     /// Override for executable path (used in tests).
     exe_path_override: Option<std::path::PathBuf>,
     /// Receiver for installation progress messages from background task.
     install_receiver: Option<Receiver<InstallProgress>>,
+    /// Version requested by the current installation, kept for retries.
+    install_version: Option<String>,
     /// Screen to return to after progress view is dismissed.
     previous_screen: Option<Screen>,
     /// Version select view state.
     version_select_state: VersionSelectState,
     /// Receiver for version loading results from background task.
     version_load_receiver: Option<Receiver<Result<Vec<VersionSelectInfo>, String>>>,
+    /// Update-available banner state.
+    update_banner: UpdateBannerState,
+    /// Receiver for the result of the background release check.
+    update_receiver: Option<Receiver<UpdateInfo>>,
+    /// When the last background release check was started.
+    last_update_check: Option<Instant>,
+    /// Receiver for progress of the doctor fixes running in the background.
+    fix_receiver: Option<Receiver<FixProgress>>,
 }
 
 impl Default for App {
@@ -119,18 +177,30 @@ impl Default for App {
             cursor_pos: 0,
             status_message: String::from("Press ':' to enter a command, 'q' to quit"),
             should_quit: false,
+            confirm_quit: false,
+            assume_yes: crate::confirm::assume_yes(),
             theme: Theme::detect(),
             menu: Menu::new(),
             toolchains_state: ToolchainsState::new(),
             doctor_state: DoctorState::new(),
             progress_state: ProgressState::default(),
+            logs_state: LogsState::new(),
+            log_viewer: None,
             command_history: CommandHistory::new(),
             pending_command: None,
+            file_picker: None,
+            project_root: PathBuf::from("."),
+            session: SessionState::default(),
             exe_path_override: None,
             install_receiver: None,
+            install_version: None,
             previous_screen: None,
             version_select_state: VersionSelectState::new(),
             version_load_receiver: None,
+            update_banner: UpdateBannerState::default(),
+            update_receiver: None,
+            last_update_check: None,
+            fix_receiver: None,
         }
     }
 }
@@ -151,10 +221,46 @@ impl App {
         self.exe_path_override = Some(path);
     }
 
+    /// Returns `true` while a background task (such as an installation) is running.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.install_receiver.is_some() || self.fix_receiver.is_some()
+    }
+
+    /// Requests the application to quit.
+    ///
+    /// Quits immediately when idle. While a background task is in flight,
+    /// shows the confirmation dialog instead.
+    fn request_quit(&mut self) {
+        if self.assume_yes {
+            self.confirm_quit_accept();
+        } else if self.is_busy() {
+            self.confirm_quit = true;
+            self.status_message = String::from(CONFIRM_QUIT_MESSAGE);
+        } else {
+            self.should_quit = true;
+        }
+    }
+
     /// Handles a key event based on current screen and input mode.
     fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
-            self.should_quit = true;
+            // A second Ctrl+C while the dialog is open forces the quit
+            if self.confirm_quit {
+                self.confirm_quit_accept();
+            } else {
+                self.request_quit();
+            }
+            return;
+        }
+
+        if self.confirm_quit {
+            self.handle_confirm_quit_key(code);
+            return;
+        }
+
+        if self.file_picker.is_some() {
+            self.handle_file_picker_key(code);
             return;
         }
 
@@ -164,6 +270,49 @@ impl App {
         }
     }
 
+    /// Handles key events while the quit confirmation dialog is shown.
+    fn handle_confirm_quit_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                self.confirm_quit_accept();
+            }
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+                self.confirm_quit = false;
+                self.pending_command = None;
+                self.status_message = String::from("Quit cancelled");
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles key events while the file picker is shown.
+    fn handle_file_picker_key(&mut self, code: KeyCode) {
+        let Some(picker) = &mut self.file_picker else {
+            return;
+        };
+        match picker.handle_key(code) {
+            PickerAction::None => {}
+            PickerAction::Cancel => {
+                self.file_picker = None;
+                self.status_message = String::from("Command cancelled");
+            }
+            PickerAction::Pick(target) => {
+                let command = picker.command.clone();
+                self.file_picker = None;
+                self.run_on_target(&command, target);
+            }
+        }
+    }
+
+    /// Confirms quitting, cancelling any in-flight installation.
+    fn confirm_quit_accept(&mut self) {
+        self.confirm_quit = false;
+        if self.is_busy() {
+            self.cancel_installation();
+        }
+        self.should_quit = true;
+    }
+
     /// Handles a key event in normal mode.
     fn handle_normal_key(&mut self, code: KeyCode) {
         match self.screen {
@@ -172,6 +321,8 @@ impl App {
             Screen::Doctor => self.handle_doctor_key(code),
             Screen::Progress => self.handle_progress_key(code),
             Screen::VersionSelect => self.handle_version_select_key(code),
+            Screen::Logs => self.handle_logs_key(code),
+            Screen::LogView => self.handle_log_view_key(code),
         }
     }
 
@@ -179,7 +330,7 @@ impl App {
     fn handle_main_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('q') => {
-                self.should_quit = true;
+                self.request_quit();
             }
             KeyCode::Char(':') => {
                 self.input_mode = InputMode::Command;
@@ -198,13 +349,16 @@ impl App {
             KeyCode::Enter => {
                 self.activate_menu_item();
             }
+            KeyCode::Char('u' | 'U') if self.update_banner.is_visible() => {
+                self.accept_update();
+            }
+            KeyCode::Char('x') if self.update_banner.is_visible() => {
+                self.update_banner.dismiss();
+            }
             KeyCode::Char(c) => {
-                if let Some(item) = Menu::find_by_key(c) {
-                    if item.quits {
-                        self.should_quit = true;
-                    } else if let Some(screen) = item.screen {
-                        self.navigate_to(screen);
-                    }
+                if let Some(idx) = Menu::index_of_key(c) {
+                    self.menu.select(idx);
+                    self.activate_menu_item();
                 }
             }
             _ => {}
@@ -247,11 +401,7 @@ impl App {
 
     /// Sets the currently selected toolchain as the default.
     fn set_selected_toolchain_as_default(&mut self) {
-        let Some(toolchain) = self
-            .toolchains_state
-            .toolchains
-            .get(self.toolchains_state.selected)
-        else {
+        let Some(toolchain) = self.toolchains_state.toolchains.selected_item() else {
             return;
         };
 
@@ -262,7 +412,10 @@ impl App {
 
         let version = toolchain.version.clone();
 
+        // The UI thread must not block, so a busy lock fails right away.
         let result = ToolchainPaths::new().and_then(|paths| {
+            let _lock =
+                ToolchainLock::acquire_with(&paths.lock_file(), Some(Duration::ZERO), |_| {})?;
             paths.set_default_version(&version)?;
             paths.update_symlinks(&version)?;
             Ok(())
@@ -283,6 +436,11 @@ impl App {
 
     /// Handles key events on the doctor screen.
     fn handle_doctor_key(&mut self, code: KeyCode) {
+        if self.doctor_state.fix_request.is_some() {
+            self.handle_fix_confirm_key(code);
+            return;
+        }
+
         match code {
             KeyCode::Esc => {
                 self.screen = Screen::Main;
@@ -294,23 +452,199 @@ impl App {
             KeyCode::Down | KeyCode::Char('j') => {
                 self.doctor_state.select_next();
             }
-            KeyCode::Char('r') => {
+            KeyCode::Char('r') if !self.doctor_state.is_fixing() => {
                 self.load_doctor_data();
             }
+            KeyCode::Char('f') if !self.doctor_state.is_fixing() => {
+                let selected = self.doctor_state.selected;
+                if self.doctor_state.fix_action(selected).is_some() {
+                    self.request_fixes(vec![selected]);
+                } else if let Some(check) = self.doctor_state.checks.get(selected) {
+                    self.status_message = format!("No automatic fix for {}", check.name);
+                }
+            }
+            KeyCode::Char('F') if !self.doctor_state.is_fixing() => {
+                let indices = self.doctor_state.fixable_indices();
+                if indices.is_empty() {
+                    self.status_message = String::from("Nothing to fix");
+                } else {
+                    self.request_fixes(indices);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles key events while the doctor fix confirmation is shown.
+    fn handle_fix_confirm_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                self.start_doctor_fixes();
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                self.doctor_state.fix_request = None;
+                self.status_message = String::from("Fix cancelled");
+            }
+            _ => {}
+        }
+    }
+
+    /// Asks for confirmation before fixing the checks at `indices`.
+    ///
+    /// The dialog lists what each fix will change. With `--yes`, the fixes
+    /// start right away.
+    fn request_fixes(&mut self, indices: Vec<usize>) {
+        let paths = match ToolchainPaths::new() {
+            Ok(paths) => paths,
+            Err(e) => {
+                self.status_message = format!("Cannot fix: {e}");
+                return;
+            }
+        };
+        let changes = indices
+            .iter()
+            .filter_map(|&index| self.doctor_state.fix_action(index))
+            .flat_map(|action| action.describe(&paths))
+            .collect();
+        self.doctor_state.fix_request = Some(FixRequest { indices, changes });
+
+        if self.assume_yes {
+            self.start_doctor_fixes();
+        } else {
+            self.status_message = String::from("Apply the fixes? (y/n)");
+        }
+    }
+
+    /// Starts the confirmed doctor fixes in a background thread.
+    fn start_doctor_fixes(&mut self) {
+        use std::sync::mpsc;
+
+        let queue = self.doctor_state.start_fixes();
+        if queue.is_empty() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.fix_receiver = Some(rx);
+        std::thread::spawn(move || fix_task::run_fixes(queue, &tx));
+        self.status_message = String::from("Fixing...");
+    }
+
+    /// Polls the doctor fix channel and updates the checks in place.
+    ///
+    /// This method should be called in each iteration of the TUI event loop.
+    fn poll_fix_progress(&mut self) {
+        let Some(receiver) = self.fix_receiver.as_ref() else {
+            return;
+        };
+
+        let mut disconnected = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(progress) => self.doctor_state.apply_fix_progress(progress),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        if disconnected || !self.doctor_state.is_fixing() {
+            // A worker that died mid-queue leaves nothing more to wait for.
+            self.doctor_state.fixing = None;
+            self.fix_receiver = None;
+            self.status_message = String::from("Fixes finished. Press 'r' to re-run all checks");
+        }
+    }
+
+    /// Handles key events on the logs screen.
+    fn handle_logs_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.navigate_to(Screen::Main);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.logs_state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.logs_state.select_next();
+            }
+            KeyCode::Char('r') => {
+                self.load_logs_data();
+            }
+            KeyCode::Enter => {
+                self.open_selected_log();
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the selected saved run in the log viewer.
+    fn open_selected_log(&mut self) {
+        let (Some(store), Some(entry)) = (
+            self.logs_state.store.as_ref(),
+            self.logs_state.entries.selected_item(),
+        ) else {
+            return;
+        };
+        let title = format!("infs {} ({})", entry.command, entry.status());
+        self.log_viewer = Some(LogViewerState::open(title, &store.log_path(entry)));
+        self.navigate_to(Screen::LogView);
+    }
+
+    /// Handles key events on the log view screen.
+    fn handle_log_view_key(&mut self, code: KeyCode) {
+        let Some(viewer) = self.log_viewer.as_mut() else {
+            self.navigate_to(Screen::Logs);
+            return;
+        };
+        if viewer.search_input.is_some() {
+            match code {
+                KeyCode::Enter => viewer.submit_search(),
+                KeyCode::Esc => viewer.cancel_search(),
+                KeyCode::Backspace => viewer.pop_search_char(),
+                KeyCode::Char(c) => viewer.push_search_char(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.log_viewer = None;
+                self.navigate_to(Screen::Logs);
+            }
+            KeyCode::Up | KeyCode::Char('k') => viewer.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => viewer.scroll_down(1),
+            KeyCode::PageUp => viewer.scroll_up(PAGE_LINES),
+            KeyCode::PageDown | KeyCode::Char(' ') => viewer.scroll_down(PAGE_LINES),
+            KeyCode::Home | KeyCode::Char('g') => viewer.scroll_to_top(),
+            KeyCode::End | KeyCode::Char('G') => viewer.scroll_to_bottom(),
+            KeyCode::Char('/') => viewer.start_search(),
+            KeyCode::Char('n') => viewer.next_match(),
+            KeyCode::Char('N') => viewer.previous_match(),
             _ => {}
         }
     }
 
     /// Handles key events on the progress screen.
     fn handle_progress_key(&mut self, code: KeyCode) {
-        if code == KeyCode::Esc {
-            if self.progress_state.completed {
-                // Installation completed or failed - return to previous screen
-                self.return_from_progress();
-            } else {
-                // Installation in progress - cancel it
-                self.cancel_installation();
+        match code {
+            KeyCode::Esc => {
+                if self.progress_state.completed {
+                    // Installation completed or failed - return to previous screen
+                    self.return_from_progress();
+                } else {
+                    // Installation in progress - cancel it
+                    self.cancel_installation();
+                }
             }
+            KeyCode::Char('r') => {
+                if let Some(phase) = self.progress_state.task.retry_phase() {
+                    let version = self.install_version.clone();
+                    self.start_installation_from(version, Some(phase));
+                }
+            }
+            _ => {}
         }
     }
 
@@ -370,7 +704,7 @@ impl App {
     fn activate_menu_item(&mut self) {
         let item = self.menu.selected_item();
         if item.quits {
-            self.should_quit = true;
+            self.request_quit();
         } else if let Some(screen) = item.screen {
             self.navigate_to(screen);
         }
@@ -404,6 +738,15 @@ impl App {
                 }
                 self.status_message = String::from("Press Enter to install, Esc to go back");
             }
+            Screen::Logs => {
+                if !self.logs_state.loaded {
+                    self.load_logs_data();
+                }
+                self.status_message = String::from("Press Enter to view, Esc to go back");
+            }
+            Screen::LogView => {
+                self.status_message = String::from("Press '/' to search, Esc to go back");
+            }
         }
     }
 
@@ -603,6 +946,12 @@ impl App {
     fn execute_command(&mut self) {
         let command = self.command_input.trim().to_lowercase();
         let original_input = self.command_input.clone();
+        // The file argument of build, run, and verify keeps its case
+        let target = original_input
+            .trim()
+            .split_once(char::is_whitespace)
+            .filter(|(name, _)| TARGET_COMMANDS.contains(&name.to_lowercase().as_str()))
+            .map(|(name, target)| (name.to_lowercase(), PathBuf::from(target.trim())));
 
         self.command_input.clear();
         self.cursor_pos = 0;
@@ -617,9 +966,14 @@ impl App {
         self.command_history.push(original_input);
         self.command_history.reset_navigation();
 
+        if let Some((name, target)) = target {
+            self.run_on_target(&name, target);
+            return;
+        }
+
         match command.as_str() {
             "q" | "quit" | "exit" => {
-                self.should_quit = true;
+                self.request_quit();
             }
             "toolchains" | "t" => {
                 self.navigate_to(Screen::Toolchains);
@@ -627,10 +981,16 @@ impl App {
             "doctor" | "d" => {
                 self.navigate_to(Screen::Doctor);
             }
+            "logs" | "l" => {
+                self.navigate_to(Screen::Logs);
+            }
             // Commands that need terminal access - exit TUI and run
-            "build" | "new" | "install" | "run" | "verify" => {
-                self.pending_command = Some(command);
-                self.should_quit = true;
+            "build" | "run" | "verify" => {
+                self.open_file_picker(&command);
+            }
+            "new" | "install" => {
+                self.pending_command = Some(vec![command]);
+                self.request_quit();
             }
             // Quick commands - spawn subprocess and show output
             "help" => {
@@ -645,6 +1005,41 @@ impl App {
         }
     }
 
+    /// Opens the file picker for `command` over the `.inf` files of the project.
+    fn open_file_picker(&mut self, command: &str) {
+        let found = file_picker::find_source_files(
+            &self.project_root,
+            file_picker::MAX_DEPTH,
+            file_picker::MAX_ENTRIES,
+        );
+        if found.files.is_empty() {
+            self.status_message = format!(
+                "No .inf files found under {}; use ':{command} <file>'",
+                self.project_root.display()
+            );
+            return;
+        }
+        self.file_picker = Some(FilePickerState::new(
+            command,
+            found,
+            self.session.last_target(command),
+        ));
+        self.status_message = String::from("Type to filter, Enter to choose, Esc to cancel");
+    }
+
+    /// Exits the TUI to run `command` on `target`, a path relative to the
+    /// project directory, remembering the choice.
+    fn run_on_target(&mut self, command: &str, target: PathBuf) {
+        let path = self.project_root.join(&target);
+        let path = path.strip_prefix(".").unwrap_or(&path);
+        self.pending_command = Some(vec![
+            command.to_string(),
+            path.to_string_lossy().into_owned(),
+        ]);
+        self.session.remember_target(command, target);
+        self.request_quit();
+    }
+
     /// Runs a quick command via subprocess and displays output in status message.
     fn run_quick_command(&mut self, args: &[&str]) {
         let exe = self
@@ -704,7 +1099,7 @@ impl App {
             }
         };
 
-        self.toolchains_state.toolchains = versions
+        let toolchains = versions
             .into_iter()
             .map(|version| {
                 let is_default = default_version.as_ref() == Some(&version);
@@ -717,7 +1112,7 @@ impl App {
             })
             .collect();
 
-        self.toolchains_state.selected = 0;
+        self.toolchains_state.toolchains.set_items(toolchains);
         self.toolchains_state.loaded = true;
     }
 
@@ -728,6 +1123,21 @@ impl App {
         self.doctor_state.loaded = true;
     }
 
+    /// Loads the saved runs from the command log.
+    fn load_logs_data(&mut self) {
+        if self.logs_state.store.is_none() {
+            self.logs_state.store = LogStore::open().ok();
+        }
+        let entries = self
+            .logs_state
+            .store
+            .as_ref()
+            .map(LogStore::entries)
+            .unwrap_or_default();
+        self.logs_state.entries.set_items(entries);
+        self.logs_state.loaded = true;
+    }
+
     /// Loads version data from the release manifest in a background thread.
     ///
     /// Creates a channel for the result, spawns a thread with a tokio runtime
@@ -751,6 +1161,7 @@ impl App {
             let result = rt.block_on(async {
                 use crate::toolchain::Platform;
                 use crate::toolchain::manifest::{fetch_manifest, sorted_versions};
+                use crate::toolchain::release_notes::EXCERPT_LINES;
 
                 let platform =
                     Platform::detect().map_err(|e| format!("Platform detection failed: {e}"))?;
@@ -769,6 +1180,7 @@ impl App {
                             .map(String::from)
                             .collect(),
                         available_for_current: v.has_platform(platform),
+                        notes: v.notes_excerpt(EXCERPT_LINES),
                     })
                     .collect();
 
@@ -806,6 +1218,80 @@ impl App {
         }
     }
 
+    /// Acts on the update banner.
+    ///
+    /// A toolchain update starts installing the new version. An infs-only
+    /// update cannot be applied from the TUI, so the command is shown instead.
+    fn accept_update(&mut self) {
+        if let Some(version) = self.update_banner.toolchain_update() {
+            let version = version.to_string();
+            self.start_installation(Some(version));
+        } else if let Some(version) = &self.update_banner.update.infs {
+            self.status_message = format!("Run 'infs self update' to install infs {version}");
+        }
+    }
+
+    /// Starts a background release check if one is due.
+    ///
+    /// Checks run when the TUI starts and then every
+    /// [`UPDATE_CHECK_INTERVAL`], unless the banner is disabled or dismissed.
+    /// Failures are ignored: the banner simply does not appear.
+    fn check_for_updates(&mut self) {
+        use std::sync::mpsc;
+
+        if !self.update_banner.enabled
+            || self.update_banner.dismissed
+            || self.update_receiver.is_some()
+            || self
+                .last_update_check
+                .is_some_and(|at| at.elapsed() < UPDATE_CHECK_INTERVAL)
+        {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.update_receiver = Some(rx);
+        self.last_update_check = Some(Instant::now());
+
+        std::thread::spawn(move || {
+            let Ok(rt) = tokio::runtime::Runtime::new() else {
+                return;
+            };
+            let update = rt.block_on(async {
+                let manifest = crate::toolchain::fetch_manifest().await.ok()?;
+                let default_version = ToolchainPaths::new()
+                    .ok()
+                    .and_then(|paths| paths.get_default_version().ok().flatten());
+                Some(UpdateInfo::from_manifest(
+                    &manifest,
+                    default_version.as_deref(),
+                    env!("CARGO_PKG_VERSION"),
+                ))
+            });
+            if let Some(update) = update {
+                let _ = tx.send(update);
+            }
+        });
+    }
+
+    /// Polls the background release check and updates the banner.
+    fn poll_update_check(&mut self) {
+        let Some(receiver) = self.update_receiver.as_ref() else {
+            return;
+        };
+
+        match receiver.try_recv() {
+            Ok(update) => {
+                self.update_banner.apply(update);
+                self.update_receiver = None;
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.update_receiver = None;
+            }
+            Err(TryRecvError::Empty) => {}
+        }
+    }
+
     /// Starts a background installation task.
     ///
     /// Creates a channel for progress messages, sets up the progress state,
@@ -816,6 +1302,18 @@ impl App {
     ///
     /// * `version` - Optional version to install. If `None`, installs the latest version.
     fn start_installation(&mut self, version: Option<String>) {
+        self.start_installation_from(version, None);
+    }
+
+    /// Starts a background installation task, optionally resuming from a failed phase.
+    ///
+    /// When retrying from the progress screen, the screen to return to is kept
+    /// from the original attempt.
+    fn start_installation_from(
+        &mut self,
+        version: Option<String>,
+        resume_from: Option<InstallPhase>,
+    ) {
         use std::sync::mpsc;
 
         let (tx, rx) = mpsc::channel();
@@ -830,12 +1328,16 @@ impl App {
         self.progress_state.add_item(progress_item);
 
         // Remember current screen to return to
-        self.previous_screen = Some(self.screen);
+        if self.screen != Screen::Progress {
+            self.previous_screen = Some(self.screen);
+        }
+
+        self.install_version.clone_from(&version);
 
         // Spawn installation task on a separate thread with its own tokio runtime
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-            rt.block_on(install_task::run_installation(version, tx));
+            rt.block_on(install_task::run_installation(version, resume_from, tx));
         });
 
         // Navigate to progress screen
@@ -862,7 +1364,9 @@ impl App {
         // Process collected messages
         let mut clear_receiver = false;
         for msg in messages {
+            self.progress_state.task.apply(&msg);
             match msg {
+                InstallProgress::PhaseEntered { .. } => {}
                 InstallProgress::PhaseStarted { phase } => {
                     self.progress_state.set_status(format!("{phase}..."));
                     // Update progress item description to show current phase
@@ -881,10 +1385,16 @@ impl App {
                         item.update_with_speed(downloaded, speed);
                     }
                 }
+                InstallProgress::ExtractProgress { processed, total } => {
+                    self.update_extract_progress(processed, total);
+                }
                 InstallProgress::PhaseCompleted { phase } => {
                     self.progress_state.set_status(format!("{phase} - done"));
                 }
                 InstallProgress::Completed { version } => {
+                    if self.update_banner.update.toolchain.as_deref() == Some(version.as_str()) {
+                        self.update_banner.update.toolchain = None;
+                    }
                     self.progress_state.complete();
                     self.progress_state
                         .set_status(format!("Toolchain v{version} installed successfully"));
@@ -896,9 +1406,15 @@ impl App {
                         String::from("Installation complete! Press Esc to return.");
                     clear_receiver = true;
                 }
-                InstallProgress::Failed { error } => {
+                InstallProgress::Failed {
+                    error, retryable, ..
+                } => {
                     self.progress_state.set_error(&error);
-                    self.status_message = String::from("Installation failed. Press Esc to return.");
+                    self.status_message = if retryable {
+                        String::from("Installation failed. Press 'r' to retry, Esc to return.")
+                    } else {
+                        String::from("Installation failed. Press Esc to return.")
+                    };
                     clear_receiver = true;
                 }
             }
@@ -908,12 +1424,31 @@ impl App {
             self.install_receiver = None;
         }
     }
+
+    /// Updates the extraction progress item, adding it on the first update.
+    fn update_extract_progress(&mut self, processed: u64, total: u64) {
+        let items = &mut self.progress_state.items;
+        if !items.iter().any(|item| item.unit == ProgressUnit::Entries) {
+            items.push(ProgressItem::new("Extracting archive").with_unit(ProgressUnit::Entries));
+        }
+        if let Some(item) = items
+            .iter_mut()
+            .find(|item| item.unit == ProgressUnit::Entries)
+        {
+            item.total = total;
+            item.current = processed;
+            if processed >= total {
+                item.complete();
+            }
+        }
+    }
 }
 
 /// Runs the main TUI event loop.
 ///
-/// Returns `Ok(Some(command))` if the TUI exits with a pending command to execute,
-/// or `Ok(None)` if the TUI exits normally without a pending command.
+/// Returns `Ok(Some(args))` if the TUI exits with a pending command to execute,
+/// or `Ok(None)` if the TUI exits normally without a pending command. `session`
+/// carries state such as the last file picker choices from one run to the next.
 ///
 /// # Errors
 ///
@@ -921,13 +1456,25 @@ impl App {
 /// - Terminal setup fails
 /// - Drawing fails
 /// - Event polling fails
-pub fn run_app(guard: &mut TerminalGuard) -> Result<Option<String>> {
-    let mut app = App::default();
+pub fn run_app(
+    guard: &mut TerminalGuard,
+    session: &mut SessionState,
+) -> Result<Option<Vec<String>>> {
+    let mut app = App {
+        session: std::mem::take(session),
+        ..App::default()
+    };
+    app.update_banner.enabled = ToolchainPaths::new()
+        .and_then(|paths| InfsConfig::load(&paths))
+        .map_or(true, |config| config.tui.update_banner);
 
     loop {
         // Poll for async operations (non-blocking)
+        app.check_for_updates();
         app.poll_install_progress();
         app.poll_version_loading();
+        app.poll_update_check();
+        app.poll_fix_progress();
 
         guard
             .terminal
@@ -946,6 +1493,7 @@ pub fn run_app(guard: &mut TerminalGuard) -> Result<Option<String>> {
         }
     }
 
+    *session = app.session;
     Ok(app.pending_command)
 }
 
@@ -964,6 +1512,7 @@ fn render(app: &App, frame: &mut Frame) {
                 app.input_mode == InputMode::Command,
                 &app.status_message,
                 app.cursor_display_pos(),
+                app.update_banner.message().as_deref(),
             );
         }
         Screen::Toolchains => {
@@ -978,12 +1527,46 @@ fn render(app: &App, frame: &mut Frame) {
         Screen::VersionSelect => {
             version_select_view::render(frame, area, &app.theme, &app.version_select_state);
         }
+        Screen::Logs => {
+            logs_view::render(frame, area, &app.theme, &app.logs_state);
+        }
+        Screen::LogView => {
+            if let Some(viewer) = &app.log_viewer {
+                log_viewer_view::render(frame, area, &app.theme, viewer);
+            }
+        }
+    }
+
+    if let Some(picker) = &app.file_picker {
+        file_picker::render(frame, area, &app.theme, picker);
+    }
+
+    if app.screen == Screen::Doctor
+        && let Some(request) = &app.doctor_state.fix_request
+    {
+        let message = format!("Apply {} fix(es)?", request.indices.len());
+        confirm_dialog::render_with_details(
+            frame,
+            area,
+            &app.theme,
+            "Fix",
+            &message,
+            &request.changes,
+        );
+    }
+
+    if app.confirm_quit {
+        confirm_dialog::render(frame, area, &app.theme, "Quit", CONFIRM_QUIT_MESSAGE);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_test_dir;
+    use crate::toolchain::doctor::{DoctorCheck, DoctorCheckStatus};
+    use crate::tui::state::InstallTaskState;
+    use crate::tui::widgets::scroll_list::ScrollList;
 
     #[test]
     fn app_default_is_normal_mode() {
@@ -1014,6 +1597,102 @@ mod tests {
         assert!(app.should_quit);
     }
 
+    #[test]
+    fn quit_while_idle_exits_directly() {
+        let mut app = App::default();
+        assert!(!app.is_busy());
+
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert!(app.should_quit);
+        assert!(!app.confirm_quit);
+    }
+
+    #[test]
+    fn quit_while_busy_shows_confirmation() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (_tx, rx) = mpsc::channel::<InstallProgress>();
+        app.install_receiver = Some(rx);
+        assert!(app.is_busy());
+
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert!(!app.should_quit);
+        assert!(app.confirm_quit);
+        assert!(app.status_message.contains("in progress"));
+    }
+
+    #[test]
+    fn quit_while_busy_with_assume_yes_skips_confirmation() {
+        use std::sync::mpsc;
+
+        let mut app = App {
+            assume_yes: true,
+            ..App::default()
+        };
+        let (_tx, rx) = mpsc::channel::<InstallProgress>();
+        app.install_receiver = Some(rx);
+        app.progress_state = ProgressState::new("Test");
+
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert!(app.should_quit);
+        assert!(!app.confirm_quit);
+        assert!(!app.is_busy());
+    }
+
+    #[test]
+    fn confirm_quit_yes_cancels_and_quits() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (_tx, rx) = mpsc::channel::<InstallProgress>();
+        app.install_receiver = Some(rx);
+        app.progress_state = ProgressState::new("Test");
+
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+
+        assert!(app.should_quit);
+        assert!(!app.confirm_quit);
+        assert!(app.install_receiver.is_none());
+    }
+
+    #[test]
+    fn confirm_quit_no_dismisses_dialog() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (_tx, rx) = mpsc::channel::<InstallProgress>();
+        app.install_receiver = Some(rx);
+
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(!app.should_quit);
+        assert!(!app.confirm_quit);
+        assert!(app.is_busy());
+    }
+
+    #[test]
+    fn ctrl_c_twice_while_busy_forces_quit() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (_tx, rx) = mpsc::channel::<InstallProgress>();
+        app.install_receiver = Some(rx);
+        app.progress_state = ProgressState::new("Test");
+
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.confirm_quit);
+        assert!(!app.should_quit);
+
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.should_quit);
+    }
+
     #[test]
     fn normal_mode_colon_enters_command_mode() {
         let mut app = App::default();
@@ -1198,43 +1877,128 @@ mod tests {
     #[test]
     fn execute_terminal_command_sets_pending_and_quits() {
         let mut app = App {
-            command_input: String::from("build"),
-            cursor_pos: 5,
+            command_input: String::from("install"),
+            cursor_pos: 7,
             ..App::default()
         };
 
         app.execute_command();
 
         assert!(app.should_quit);
-        assert_eq!(app.pending_command, Some(String::from("build")));
+        assert_eq!(app.pending_command, Some(vec![String::from("install")]));
     }
 
     #[test]
-    fn execute_run_command_sets_pending_and_quits() {
+    fn execute_build_with_file_sets_pending_and_quits() {
         let mut app = App {
-            command_input: String::from("run"),
-            cursor_pos: 3,
+            command_input: String::from("Build src/Main.inf"),
+            cursor_pos: 18,
             ..App::default()
         };
 
         app.execute_command();
 
         assert!(app.should_quit);
-        assert_eq!(app.pending_command, Some(String::from("run")));
+        assert!(app.file_picker.is_none());
+        assert_eq!(
+            app.pending_command,
+            Some(vec![String::from("build"), String::from("src/Main.inf")])
+        );
+        assert_eq!(
+            app.session.last_target("build"),
+            Some(std::path::Path::new("src/Main.inf"))
+        );
+    }
+
+    /// Creates a project directory with two source files.
+    fn picker_project(name: &str) -> PathBuf {
+        let dir = temp_test_dir(&format!("app_picker_{name}"));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.inf"), "").unwrap();
+        std::fs::write(dir.join("src/main.inf"), "").unwrap();
+        dir
+    }
+
+    fn type_command(app: &mut App, command: &str) {
+        app.handle_key(KeyCode::Char(':'), KeyModifiers::NONE);
+        for c in command.chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
     }
 
     #[test]
-    fn execute_verify_command_sets_pending_and_quits() {
+    fn run_without_file_opens_picker_and_runs_choice() {
+        let root = picker_project("run");
         let mut app = App {
-            command_input: String::from("verify"),
-            cursor_pos: 6,
+            project_root: root.clone(),
             ..App::default()
         };
 
-        app.execute_command();
+        type_command(&mut app, "run");
+        assert!(!app.should_quit);
+        assert_eq!(app.file_picker.as_ref().map(|p| p.matches.len()), Some(2));
+
+        // Keys go to the picker, not the main screen shortcuts
+        for c in "main".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(app.screen, Screen::Main);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
 
+        assert!(app.file_picker.is_none());
         assert!(app.should_quit);
-        assert_eq!(app.pending_command, Some(String::from("verify")));
+        assert_eq!(
+            app.pending_command,
+            Some(vec![
+                String::from("run"),
+                root.join("src/main.inf").to_string_lossy().into_owned()
+            ])
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn picker_preselects_last_choice_per_command() {
+        let root = picker_project("last");
+        let mut session = SessionState::default();
+        session.remember_target("verify", PathBuf::from("src/main.inf"));
+        let mut app = App {
+            project_root: root.clone(),
+            session,
+            ..App::default()
+        };
+
+        type_command(&mut app, "verify");
+        let picker = app.file_picker.as_ref().expect("picker should be open");
+        assert_eq!(
+            picker.selected(),
+            Some(std::path::Path::new("src/main.inf"))
+        );
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.file_picker.is_none());
+        assert!(app.pending_command.is_none());
+
+        type_command(&mut app, "build");
+        let picker = app.file_picker.as_ref().expect("picker should be open");
+        assert_eq!(picker.selected(), Some(std::path::Path::new("src/lib.inf")));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn build_without_source_files_stays_on_main() {
+        let root = temp_test_dir("app_picker_empty");
+        let mut app = App {
+            project_root: root.clone(),
+            ..App::default()
+        };
+
+        type_command(&mut app, "build");
+        assert!(app.file_picker.is_none());
+        assert!(!app.should_quit);
+        assert!(app.status_message.starts_with("No .inf files found"));
+        std::fs::remove_dir_all(&root).unwrap();
     }
 
     #[test]
@@ -1331,6 +2095,34 @@ mod tests {
         assert_eq!(app.screen, Screen::Doctor);
     }
 
+    #[test]
+    fn shortcut_uppercase_activates_menu_item() {
+        let mut app = App::default();
+        app.handle_key(KeyCode::Char('D'), KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Doctor);
+        assert_eq!(app.menu.selected(), Menu::index_of_key('d').unwrap());
+    }
+
+    #[test]
+    fn shortcut_unknown_key_is_noop() {
+        let mut app = App::default();
+        app.handle_key(KeyCode::Char('z'), KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Main);
+        assert_eq!(app.menu.selected(), 0);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn shortcut_keys_ignored_in_command_mode() {
+        let mut app = App {
+            input_mode: InputMode::Command,
+            ..App::default()
+        };
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Main);
+        assert_eq!(app.command_input, "d");
+    }
+
     #[test]
     fn esc_from_toolchains_returns_to_main() {
         let mut app = App {
@@ -1351,6 +2143,154 @@ mod tests {
         assert_eq!(app.screen, Screen::Main);
     }
 
+    fn doctor_app(checks: Vec<DoctorCheck>) -> App {
+        App {
+            screen: Screen::Doctor,
+            assume_yes: false,
+            doctor_state: DoctorState {
+                checks,
+                loaded: true,
+                ..DoctorState::default()
+            },
+            ..App::default()
+        }
+    }
+
+    #[test]
+    fn fix_key_on_check_without_fix_shows_status() {
+        let mut app = doctor_app(vec![DoctorCheck::error("Platform", "Unsupported")]);
+        app.handle_key(KeyCode::Char('f'), KeyModifiers::NONE);
+        assert!(app.doctor_state.fix_request.is_none());
+        assert_eq!(app.status_message, "No automatic fix for Platform");
+    }
+
+    #[test]
+    fn fix_key_asks_for_confirmation_and_n_cancels() {
+        let mut app = doctor_app(vec![
+            DoctorCheck::ok("Platform", "linux-x64"),
+            DoctorCheck::error("Symlinks", "Broken links: infc"),
+        ]);
+        app.handle_key(KeyCode::Char('F'), KeyModifiers::NONE);
+        let request = app
+            .doctor_state
+            .fix_request
+            .as_ref()
+            .expect("fix requested");
+        assert_eq!(request.indices, [1]);
+        assert!(app.fix_receiver.is_none());
+
+        // Navigation is blocked while the dialog is open
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Doctor);
+        assert!(app.doctor_state.fix_request.is_none());
+        assert_eq!(app.status_message, "Fix cancelled");
+    }
+
+    #[test]
+    fn fix_all_with_nothing_to_fix_shows_status() {
+        let mut app = doctor_app(vec![DoctorCheck::ok("Platform", "linux-x64")]);
+        app.handle_key(KeyCode::Char('F'), KeyModifiers::NONE);
+        assert!(app.doctor_state.fix_request.is_none());
+        assert_eq!(app.status_message, "Nothing to fix");
+    }
+
+    #[test]
+    fn poll_fix_progress_finishes_queue() {
+        use crate::tui::state::FixReport;
+        use std::sync::mpsc;
+
+        let mut app = doctor_app(vec![DoctorCheck::error("Symlinks", "Broken links: infc")]);
+        app.doctor_state.fix_request = Some(FixRequest {
+            indices: vec![0],
+            changes: Vec::new(),
+        });
+        assert_eq!(app.doctor_state.start_fixes().len(), 1);
+        let (tx, rx) = mpsc::channel();
+        app.fix_receiver = Some(rx);
+        assert!(app.is_busy());
+
+        tx.send(FixProgress::Started { index: 0 }).unwrap();
+        tx.send(FixProgress::Finished {
+            index: 0,
+            result: Ok(FixReport {
+                check: DoctorCheck::ok("Symlinks", "All symlinks valid"),
+                outcomes: Vec::new(),
+            }),
+        })
+        .unwrap();
+        app.poll_fix_progress();
+
+        assert!(app.fix_receiver.is_none());
+        assert!(!app.doctor_state.is_fixing());
+        assert_eq!(app.doctor_state.checks[0].status, DoctorCheckStatus::Ok);
+        assert!(app.status_message.starts_with("Fixes finished"));
+    }
+
+    #[test]
+    fn logs_screen_opens_and_searches_saved_output() {
+        let dir = temp_test_dir("tui_logs");
+        let store = LogStore::new(dir.clone(), 5);
+        store
+            .record(
+                "build",
+                String::from("2026-01-01T00:00:00Z"),
+                Some(0),
+                "ok\n",
+            )
+            .unwrap();
+        store
+            .record(
+                "run",
+                String::from("2026-01-01T00:01:00Z"),
+                Some(1),
+                "start\nerror: trap\n",
+            )
+            .unwrap();
+
+        let mut app = App::default();
+        app.logs_state.store = Some(store);
+        app.handle_key(KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Logs);
+        assert_eq!(
+            app.logs_state
+                .entries
+                .selected_item()
+                .map(|e| e.command.as_str()),
+            Some("run")
+        );
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::LogView);
+        for code in [KeyCode::Char('/'), KeyCode::Char('t'), KeyCode::Char('r')] {
+            app.handle_key(code, KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        let viewer = app.log_viewer.as_ref().unwrap();
+        assert_eq!(viewer.lines(), ["start", "error: trap"]);
+        assert_eq!(viewer.scroll, 1);
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Logs);
+        assert!(app.log_viewer.is_none());
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.screen, Screen::Main);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn command_logs_navigates_to_logs() {
+        let mut app = App {
+            input_mode: InputMode::Command,
+            command_input: String::from("logs"),
+            cursor_pos: 4,
+            ..App::default()
+        };
+        app.logs_state.loaded = true;
+        app.execute_command();
+        assert_eq!(app.screen, Screen::Logs);
+    }
+
     #[test]
     fn menu_navigation_with_arrows() {
         let mut app = App::default();
@@ -1450,8 +2390,11 @@ mod tests {
 
     #[test]
     fn command_history_up_down() {
+        // No source files, so `build` does not open the file picker.
+        let root = temp_test_dir("app_history");
         let mut app = App {
             input_mode: InputMode::Command,
+            project_root: root.clone(),
             ..App::default()
         };
 
@@ -1478,6 +2421,7 @@ mod tests {
 
         app.handle_key(KeyCode::Down, KeyModifiers::NONE);
         assert_eq!(app.command_input, "doctor");
+        std::fs::remove_dir_all(&root).unwrap();
     }
 
     #[test]
@@ -1563,12 +2507,11 @@ mod tests {
         let mut app = App {
             screen: Screen::Toolchains,
             toolchains_state: ToolchainsState {
-                toolchains: vec![ToolchainInfo {
+                toolchains: ScrollList::new(vec![ToolchainInfo {
                     version: "0.1.0".to_string(),
                     is_default: true,
                     metadata: None,
-                }],
-                selected: 0,
+                }]),
                 loaded: true,
             },
             ..App::default()
@@ -1622,6 +2565,40 @@ mod tests {
         assert_eq!(item.speed_bytes_per_sec, Some(1024));
     }
 
+    #[test]
+    fn poll_install_progress_tracks_extraction_entries() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (tx, rx) = mpsc::channel();
+        app.install_receiver = Some(rx);
+        app.progress_state = ProgressState::new("Test");
+        app.progress_state.add_item(ProgressItem::new("Download"));
+
+        tx.send(InstallProgress::ExtractProgress {
+            processed: 1,
+            total: 4,
+        })
+        .expect("Should send");
+        app.poll_install_progress();
+
+        assert_eq!(app.progress_state.items.len(), 2);
+        let item = &app.progress_state.items[1];
+        assert_eq!(item.unit, ProgressUnit::Entries);
+        assert_eq!(item.format_progress(), "1 / 4 files");
+        assert!(!item.completed);
+
+        tx.send(InstallProgress::ExtractProgress {
+            processed: 4,
+            total: 4,
+        })
+        .expect("Should send");
+        app.poll_install_progress();
+
+        assert_eq!(app.progress_state.items.len(), 2);
+        assert!(app.progress_state.items[1].completed);
+    }
+
     #[test]
     fn poll_install_progress_handles_completion() {
         use std::sync::mpsc;
@@ -1656,7 +2633,9 @@ mod tests {
 
         // Send failure message
         tx.send(InstallProgress::Failed {
+            phase: InstallPhase::Downloading,
             error: String::from("Network error"),
+            retryable: true,
         })
         .expect("Should send");
 
@@ -1665,6 +2644,78 @@ mod tests {
         assert!(app.progress_state.completed);
         assert!(app.progress_state.error.is_some());
         assert!(app.install_receiver.is_none());
+        assert_eq!(
+            app.progress_state.task.retry_phase(),
+            Some(InstallPhase::Downloading)
+        );
+        assert!(app.status_message.contains("retry"));
+    }
+
+    #[test]
+    fn poll_install_progress_tracks_phase() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (tx, rx) = mpsc::channel();
+        app.install_receiver = Some(rx);
+        app.progress_state = ProgressState::new("Test");
+
+        tx.send(InstallProgress::PhaseEntered {
+            phase: InstallPhase::Extracting,
+        })
+        .expect("Should send");
+
+        app.poll_install_progress();
+
+        assert_eq!(
+            app.progress_state.task,
+            InstallTaskState::Running(InstallPhase::Extracting)
+        );
+    }
+
+    #[test]
+    fn retry_after_retryable_failure_restarts_installation() {
+        let mut app = App {
+            screen: Screen::Progress,
+            previous_screen: Some(Screen::Toolchains),
+            install_version: Some(String::from("0.1.0")),
+            progress_state: ProgressState::new("Test"),
+            ..App::default()
+        };
+        app.progress_state.set_error("Extraction failed");
+        app.progress_state.task = InstallTaskState::Failed {
+            phase: InstallPhase::Extracting,
+            error: String::from("Extraction failed"),
+            retryable: true,
+        };
+
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+
+        assert_eq!(app.screen, Screen::Progress);
+        assert_eq!(app.previous_screen, Some(Screen::Toolchains));
+        assert!(app.install_receiver.is_some());
+        assert!(!app.progress_state.completed);
+        assert_eq!(app.install_version.as_deref(), Some("0.1.0"));
+    }
+
+    #[test]
+    fn retry_after_checksum_failure_is_ignored() {
+        let mut app = App {
+            screen: Screen::Progress,
+            progress_state: ProgressState::new("Test"),
+            ..App::default()
+        };
+        app.progress_state.set_error("Checksum mismatch");
+        app.progress_state.task = InstallTaskState::Failed {
+            phase: InstallPhase::Verifying,
+            error: String::from("Checksum mismatch"),
+            retryable: false,
+        };
+
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+
+        assert!(app.install_receiver.is_none());
+        assert!(app.progress_state.completed);
     }
 
     #[test]
@@ -1724,12 +2775,14 @@ mod tests {
                         stable: true,
                         platforms: vec!["linux".to_string()],
                         available_for_current: true,
+                        notes: None,
                     },
                     VersionSelectInfo {
                         version: "0.1.0".to_string(),
                         stable: true,
                         platforms: vec!["linux".to_string()],
                         available_for_current: true,
+                        notes: None,
                     },
                 ],
                 selected: 0,
@@ -1765,6 +2818,7 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 }],
                 selected: 0,
                 loaded: true,
@@ -1791,6 +2845,7 @@ mod tests {
                     stable: true,
                     platforms: vec!["macos".to_string()],
                     available_for_current: false,
+                    notes: None,
                 }],
                 selected: 0,
                 loaded: true,
@@ -1821,6 +2876,7 @@ mod tests {
             stable: true,
             platforms: vec!["linux".to_string()],
             available_for_current: true,
+            notes: None,
         }];
 
         tx.send(Ok(versions.clone())).expect("Should send");
@@ -1852,4 +2908,102 @@ mod tests {
         assert!(app.version_select_state.error.is_some());
         assert!(app.version_load_receiver.is_none());
     }
+
+    fn app_with_update(toolchain: Option<&str>, infs: Option<&str>) -> App {
+        let mut app = App::default();
+        app.update_banner.apply(UpdateInfo {
+            toolchain: toolchain.map(String::from),
+            infs: infs.map(String::from),
+        });
+        app
+    }
+
+    #[test]
+    fn poll_update_check_shows_banner() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (tx, rx) = mpsc::channel();
+        app.update_receiver = Some(rx);
+
+        tx.send(UpdateInfo {
+            toolchain: Some("0.3.0".to_string()),
+            infs: None,
+        })
+        .expect("Should send");
+        app.poll_update_check();
+
+        assert!(app.update_receiver.is_none());
+        assert!(app.update_banner.is_visible());
+    }
+
+    #[test]
+    fn poll_update_check_ignores_failed_check() {
+        use std::sync::mpsc;
+
+        let mut app = App::default();
+        let (tx, rx) = mpsc::channel::<UpdateInfo>();
+        app.update_receiver = Some(rx);
+        drop(tx);
+
+        app.poll_update_check();
+
+        assert!(app.update_receiver.is_none());
+        assert!(!app.update_banner.is_visible());
+    }
+
+    #[test]
+    fn disabled_or_dismissed_banner_skips_update_check() {
+        let mut app = App::default();
+        app.update_banner.enabled = false;
+        app.check_for_updates();
+        assert!(app.update_receiver.is_none());
+
+        let mut app = App::default();
+        app.update_banner.dismiss();
+        app.check_for_updates();
+        assert!(app.update_receiver.is_none());
+    }
+
+    #[test]
+    fn x_dismisses_update_banner() {
+        let mut app = app_with_update(Some("0.3.0"), None);
+
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+
+        assert!(!app.update_banner.is_visible());
+        assert_eq!(app.screen, Screen::Main);
+    }
+
+    #[test]
+    fn u_installs_toolchain_update() {
+        let mut app = app_with_update(Some("0.3.0"), None);
+
+        app.handle_key(KeyCode::Char('U'), KeyModifiers::SHIFT);
+
+        assert_eq!(app.screen, Screen::Progress);
+        assert_eq!(app.install_version.as_deref(), Some("0.3.0"));
+        assert_eq!(app.previous_screen, Some(Screen::Main));
+        app.cancel_installation();
+    }
+
+    #[test]
+    fn u_for_infs_update_shows_command() {
+        let mut app = app_with_update(None, Some("0.5.0"));
+
+        app.handle_key(KeyCode::Char('u'), KeyModifiers::NONE);
+
+        assert_eq!(app.screen, Screen::Main);
+        assert!(app.status_message.contains("infs self update"));
+    }
+
+    #[test]
+    fn u_without_banner_is_noop() {
+        let mut app = App::default();
+
+        app.handle_key(KeyCode::Char('u'), KeyModifiers::NONE);
+
+        assert_eq!(app.screen, Screen::Main);
+        assert!(app.install_receiver.is_none());
+    }
 }
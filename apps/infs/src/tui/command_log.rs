@@ -0,0 +1,358 @@
+//! Saved output of commands run from the TUI.
+//!
+//! Commands that need the terminal (`build`, `run`, ...) run after the TUI
+//! has been suspended, and their output is gone once the TUI redraws. The
+//! output is copied to the terminal and captured at the same time, then saved
+//! under `~/.inference/logs/`:
+//!
+//! ```text
+//! ~/.inference/logs/
+//! ├── index.json   # Saved runs, oldest first
+//! ├── 1.log
+//! └── 2.log
+//! ```
+//!
+//! Only the last [`MAX_LOGS`] runs are kept; older logs are removed when a
+//! new one is saved. Color codes are stripped from saved output.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::toolchain::ToolchainPaths;
+use crate::toolchain::paths::format_timestamp_iso8601;
+
+/// Number of runs whose output is kept.
+pub const MAX_LOGS: usize = 20;
+
+/// File listing the saved runs.
+const INDEX_FILE: &str = "index.json";
+
+/// A saved run of a command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Sequence number of the run, also naming its log file.
+    pub id: u64,
+    /// Command line after `infs`, such as `build`.
+    pub command: String,
+    /// ISO 8601 time the command was started.
+    pub started_at: String,
+    /// Exit code, or `None` if the command was ended by a signal.
+    pub exit_code: Option<i32>,
+}
+
+impl LogEntry {
+    /// Returns the name of the file holding the output.
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        format!("{}.log", self.id)
+    }
+
+    /// Returns a short description of how the command ended.
+    #[must_use]
+    pub fn status(&self) -> String {
+        match self.exit_code {
+            Some(0) => String::from("ok"),
+            Some(code) => format!("exit {code}"),
+            None => String::from("killed"),
+        }
+    }
+
+    /// Returns true if the command exited successfully.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Directory of saved command logs.
+#[derive(Debug, Clone)]
+pub struct LogStore {
+    dir: PathBuf,
+    max_logs: usize,
+}
+
+impl LogStore {
+    /// Creates a store in `dir` keeping at most `max_logs` runs.
+    #[must_use]
+    pub fn new(dir: PathBuf, max_logs: usize) -> Self {
+        Self { dir, max_logs }
+    }
+
+    /// Opens the store under the infs home directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined.
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(ToolchainPaths::new()?.logs_dir(), MAX_LOGS))
+    }
+
+    /// Returns the path of the output file of `entry`.
+    #[must_use]
+    pub fn log_path(&self, entry: &LogEntry) -> PathBuf {
+        self.dir.join(entry.file_name())
+    }
+
+    /// Returns the saved runs, most recent first.
+    ///
+    /// A missing or unreadable index reads as no runs.
+    #[must_use]
+    pub fn entries(&self) -> Vec<LogEntry> {
+        let mut entries = self.read_index();
+        entries.reverse();
+        entries
+    }
+
+    /// Saves the output of a run and removes the oldest runs beyond the limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory, the log file, or the index cannot
+    /// be written.
+    pub fn record(
+        &self,
+        command: &str,
+        started_at: String,
+        exit_code: Option<i32>,
+        output: &str,
+    ) -> Result<LogEntry> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create directory: {}", self.dir.display()))?;
+
+        let mut entries = self.read_index();
+        let entry = LogEntry {
+            id: entries.last().map_or(1, |last| last.id + 1),
+            command: command.to_string(),
+            started_at,
+            exit_code,
+        };
+        let path = self.log_path(&entry);
+        std::fs::write(&path, strip_ansi(output))
+            .with_context(|| format!("Failed to write log: {}", path.display()))?;
+        entries.push(entry.clone());
+
+        for removed in rotate(&mut entries, self.max_logs) {
+            let _ = std::fs::remove_file(self.log_path(&removed));
+        }
+        let index =
+            serde_json::to_string_pretty(&entries).context("Failed to serialize log index")?;
+        let index_path = self.dir.join(INDEX_FILE);
+        std::fs::write(&index_path, index)
+            .with_context(|| format!("Failed to write log index: {}", index_path.display()))?;
+        Ok(entry)
+    }
+
+    /// Reads the index, oldest run first.
+    fn read_index(&self) -> Vec<LogEntry> {
+        std::fs::read_to_string(self.dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Drops the oldest entries so that at most `max_logs` remain, returning the
+/// dropped ones.
+fn rotate(entries: &mut Vec<LogEntry>, max_logs: usize) -> Vec<LogEntry> {
+    let excess = entries.len().saturating_sub(max_logs);
+    entries.drain(..excess).collect()
+}
+
+/// Removes ANSI escape sequences (colors, cursor movement) from `text`.
+#[must_use]
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        if chars.next_if_eq(&'[').is_some() {
+            // CSI sequence: parameters, then a final byte in `@`..=`~`
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            // Other escapes: intermediate bytes in ` `..=`/`, then a final byte
+            while chars.next_if(|c| (' '..='/').contains(c)).is_some() {}
+            chars.next();
+        }
+    }
+    out
+}
+
+/// Returns the current time as an ISO 8601 string.
+#[must_use]
+pub fn now() -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format_timestamp_iso8601(timestamp)
+}
+
+/// Runs `cmd`, copying its stdout and stderr to ours as it goes and
+/// capturing both, in the order they were written.
+///
+/// Stdin is inherited, so commands can still prompt.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be started or waited for.
+pub fn run_teed(cmd: &mut Command) -> Result<(ExitStatus, String)> {
+    let mut child = cmd
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let captured = Arc::new(Mutex::new(Vec::new()));
+
+    let stdout = child.stdout.take().map(|pipe| {
+        let captured = Arc::clone(&captured);
+        std::thread::spawn(move || tee(pipe, std::io::stdout(), &captured))
+    });
+    let stderr = child.stderr.take().map(|pipe| {
+        let captured = Arc::clone(&captured);
+        std::thread::spawn(move || tee(pipe, std::io::stderr(), &captured))
+    });
+    for handle in [stdout, stderr].into_iter().flatten() {
+        let _ = handle.join();
+    }
+    let status = child.wait()?;
+
+    let captured = captured
+        .lock()
+        .map(|bytes| bytes.clone())
+        .unwrap_or_default();
+    Ok((status, String::from_utf8_lossy(&captured).into_owned()))
+}
+
+/// Copies `source` to `sink` until end of file, appending everything read to
+/// `captured`.
+fn tee(mut source: impl Read, mut sink: impl Write, captured: &Mutex<Vec<u8>>) {
+    let mut buffer = [0u8; 4096];
+    loop {
+        match source.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                let _ = sink.write_all(&buffer[..read]);
+                let _ = sink.flush();
+                if let Ok(mut captured) = captured.lock() {
+                    captured.extend_from_slice(&buffer[..read]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+
+    fn temp_store(max_logs: usize) -> LogStore {
+        LogStore::new(temp_test_dir("logs"), max_logs)
+    }
+
+    fn entry(id: u64) -> LogEntry {
+        LogEntry {
+            id,
+            command: String::from("build"),
+            started_at: String::from("2026-01-01T00:00:00Z"),
+            exit_code: Some(0),
+        }
+    }
+
+    #[test]
+    fn rotate_drops_oldest_entries() {
+        let mut entries: Vec<LogEntry> = (1..=5).map(entry).collect();
+        let removed = rotate(&mut entries, 3);
+        assert_eq!(removed.iter().map(|e| e.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), [3, 4, 5]);
+        assert!(rotate(&mut entries, 3).is_empty());
+    }
+
+    #[test]
+    fn record_keeps_most_recent_runs() {
+        let store = temp_store(2);
+        assert!(store.entries().is_empty());
+
+        for (command, code) in [("build", 0), ("run", 1), ("new", 0)] {
+            store
+                .record(
+                    command,
+                    now(),
+                    Some(code),
+                    &format!("output of {command}\n"),
+                )
+                .unwrap();
+        }
+
+        let entries = store.entries();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| e.command.as_str())
+                .collect::<Vec<_>>(),
+            ["new", "run"]
+        );
+        assert_eq!(entries[0].id, 3);
+        assert_eq!(entries[1].status(), "exit 1");
+        assert_eq!(
+            std::fs::read_to_string(store.log_path(&entries[0])).unwrap(),
+            "output of new\n"
+        );
+        assert!(!store.dir.join("1.log").exists());
+
+        std::fs::remove_dir_all(&store.dir).ok();
+    }
+
+    #[test]
+    fn record_strips_colors() {
+        let store = temp_store(MAX_LOGS);
+        let entry = store
+            .record("build", now(), None, "\x1b[1;31merror\x1b[0m: failed\n")
+            .unwrap();
+        assert_eq!(entry.status(), "killed");
+        assert_eq!(
+            std::fs::read_to_string(store.log_path(&entry)).unwrap(),
+            "error: failed\n"
+        );
+        std::fs::remove_dir_all(&store.dir).ok();
+    }
+
+    #[test]
+    fn corrupt_index_reads_as_empty() {
+        let store = temp_store(MAX_LOGS);
+        std::fs::create_dir_all(&store.dir).unwrap();
+        std::fs::write(store.dir.join(INDEX_FILE), "not json").unwrap();
+        assert!(store.entries().is_empty());
+        assert_eq!(store.record("run", now(), Some(0), "").unwrap().id, 1);
+        std::fs::remove_dir_all(&store.dir).ok();
+    }
+
+    #[test]
+    fn strip_ansi_keeps_plain_text() {
+        assert_eq!(strip_ansi("plain text"), "plain text");
+        assert_eq!(strip_ansi("\x1b[2K\x1b[1Gdone"), "done");
+        assert_eq!(strip_ansi("a\x1b(Bb"), "ab");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_teed_captures_both_streams() {
+        let (status, output) =
+            run_teed(Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"])).unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert!(output.contains("out\n"), "{output}");
+        assert!(output.contains("err\n"), "{output}");
+    }
+}
@@ -0,0 +1,78 @@
+//! Background doctor fixes for TUI integration.
+//!
+//! Fixes queued from the doctor view run one after another on a separate
+//! thread, so the event loop keeps drawing while a fix touches the disk.
+//! Each fix reports [`FixProgress`] over a channel, including the re-run
+//! result of its check.
+
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use super::state::{FixProgress, FixReport};
+use crate::toolchain::ToolchainPaths;
+use crate::toolchain::fix::FixAction;
+use crate::toolchain::lock::ToolchainLock;
+
+/// Runs `queue` in order, sending progress for each fix to `tx`.
+///
+/// A failing fix does not stop the ones after it. Stops early if the
+/// receiver is gone.
+pub fn run_fixes(queue: Vec<(usize, FixAction)>, tx: &Sender<FixProgress>) {
+    let paths = ToolchainPaths::new().map_err(|e| format!("{e:#}"));
+    for (index, action) in queue {
+        if tx.send(FixProgress::Started { index }).is_err() {
+            return;
+        }
+        let result = paths
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|paths| run_fix(paths, action));
+        if tx.send(FixProgress::Finished { index, result }).is_err() {
+            return;
+        }
+    }
+}
+
+/// Applies `action` to `paths` and runs its check again.
+///
+/// The toolchain lock is taken without waiting, so a fix fails instead of
+/// hanging while another infs process changes the toolchain.
+fn run_fix(paths: &ToolchainPaths, action: FixAction) -> Result<FixReport, String> {
+    let _lock = ToolchainLock::acquire_with(&paths.lock_file(), Some(Duration::ZERO), |_| {})
+        .map_err(|e| format!("{e:#}"))?;
+    let outcomes = action.apply(paths);
+    Ok(FixReport {
+        check: action.check(paths),
+        outcomes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use std::sync::mpsc;
+
+    #[test]
+    fn run_fix_reports_outcomes_and_rechecks() {
+        let root = temp_test_dir("fix_task");
+        let paths = ToolchainPaths::with_root(root.clone());
+
+        let report = run_fix(&paths, FixAction::Symlinks).expect("Should run fix");
+        assert!(report.outcomes.is_empty(), "{:?}", report.outcomes);
+        assert_eq!(report.check.name, "Symlinks");
+
+        let _held = ToolchainLock::acquire_with(&paths.lock_file(), None, |_| {}).unwrap();
+        let error = run_fix(&paths, FixAction::Symlinks).unwrap_err();
+        assert!(error.contains("still modifying the toolchain"), "{error}");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn run_fixes_stops_when_receiver_is_gone() {
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        run_fixes(vec![(0, FixAction::Symlinks)], &tx);
+    }
+}
@@ -0,0 +1,79 @@
+//! Fuzzy matching for filter-as-you-type lists.
+//!
+//! A query matches a candidate when its characters appear in the candidate
+//! in order, ignoring case. [`score`] ranks matches so that consecutive
+//! characters and characters at the start of a word or path component rank
+//! higher, and [`filter`] applies it to a list.
+
+/// Bonus for a matched character directly after the previous match.
+const CONSECUTIVE_BONUS: u32 = 5;
+
+/// Bonus for a matched character at the start of a word or path component.
+const BOUNDARY_BONUS: u32 = 3;
+
+/// Returns the score of `candidate` for `query`, or `None` if it does not
+/// match. Higher scores are better; an empty query matches everything with
+/// score 0.
+#[must_use]
+pub fn score(query: &str, candidate: &str) -> Option<u32> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut total = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for c in candidate.chars() {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(std::iter::once(wanted));
+        if matched {
+            query.next();
+            total += 1;
+            if previous_matched {
+                total += CONSECUTIVE_BONUS;
+            }
+            if previous.is_none_or(|p| matches!(p, '/' | '\\' | '_' | '-' | '.' | ' ')) {
+                total += BOUNDARY_BONUS;
+            }
+        }
+        previous_matched = matched;
+        previous = Some(c);
+    }
+
+    query.peek().is_none().then_some(total)
+}
+
+/// Returns the indices of the `candidates` matching `query`, best first.
+///
+/// Candidates with equal scores keep their original order.
+#[must_use]
+pub fn filter<S: AsRef<str>>(query: &str, candidates: &[S]) -> Vec<usize> {
+    let mut scored: Vec<(u32, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, candidate)| score(query, candidate.as_ref()).map(|s| (s, idx)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, idx)| idx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_characters_in_order_ignoring_case() {
+        assert!(score("smi", "src/main.inf").is_some());
+        assert!(score("SRC", "src/main.inf").is_some());
+        assert!(score("ims", "src/main.inf").is_none());
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn filter_ranks_consecutive_and_boundary_matches_first() {
+        let candidates = ["src/domain.inf", "src/main.inf", "tests/other.inf"];
+        assert_eq!(filter("main", &candidates), [1, 0]);
+        assert_eq!(filter("", &candidates), [0, 1, 2]);
+        assert!(filter("zzz", &candidates).is_empty());
+    }
+}
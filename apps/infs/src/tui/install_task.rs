@@ -7,177 +7,111 @@
 //! blocking the main TUI event loop.
 
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 
-use super::state::InstallProgress;
-use crate::toolchain::paths::ToolchainMetadata;
-use crate::toolchain::{
-    Platform, ProgressCallback, ProgressEvent, ToolchainPaths, download_file_with_callback,
-    extract_archive, fetch_artifact, set_executable_permissions, verify_checksum,
+use super::state::{InstallPhase, InstallProgress};
+use crate::toolchain::ProgressEvent;
+use crate::toolchain::installer::{
+    InstallCallback, InstallEvent, InstallOptions, InstallRequest, Installer,
 };
 
 /// Runs the toolchain installation asynchronously, sending progress updates to the TUI.
 ///
-/// This function performs the same operations as the CLI install command but reports
-/// progress via the provided channel instead of printing to stdout.
+/// This function drives the same [`Installer`] as the CLI install command but
+/// reports progress via the provided channel instead of printing to stdout.
 ///
 /// # Arguments
 ///
 /// * `version` - Optional version to install. If `None`, installs the latest version.
+/// * `resume_from` - Phase to restart from after a failed attempt. Earlier phases
+///   whose results are still on disk (the downloaded archive, the extracted
+///   toolchain) are skipped. The manifest is always fetched to resolve the artifact.
 /// * `tx` - Channel sender for progress updates.
 ///
-/// # Process
-///
-/// 1. Detect the current platform
-/// 2. Fetch the release manifest
-/// 3. Find the artifact for the requested version and platform
-/// 4. Download the archive with progress reporting
-/// 5. Verify the SHA256 checksum
-/// 6. Extract to the toolchains directory
-/// 7. Set as default if it's the first installation
-pub async fn run_installation(version: Option<String>, tx: Sender<InstallProgress>) {
-    if let Err(e) = run_installation_inner(version, tx.clone()).await {
-        let _ = tx.send(InstallProgress::Failed {
-            error: e.to_string(),
-        });
-    }
-}
-
-/// Inner implementation that returns a Result for easier error handling.
-#[allow(clippy::too_many_lines)]
-async fn run_installation_inner(
+/// On failure, a [`InstallProgress::Failed`] message carries the failing
+/// [`InstallPhase`] and whether it can be retried.
+pub async fn run_installation(
     version: Option<String>,
+    resume_from: Option<InstallPhase>,
     tx: Sender<InstallProgress>,
-) -> Result<()> {
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: String::from("Detecting platform"),
-    });
-
-    let platform = Platform::detect().context("Failed to detect platform")?;
-    let paths = ToolchainPaths::new().context("Failed to initialize toolchain paths")?;
-
-    paths
-        .ensure_directories()
-        .context("Failed to create toolchain directories")?;
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: String::from("Detecting platform"),
-    });
-
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: String::from("Downloading release manifest"),
-    });
-
-    let version_arg = version.as_deref();
-    let (resolved_version, artifact) = fetch_artifact(version_arg, platform)
-        .await
-        .context("Failed to download release manifest")?;
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: String::from("Downloading release manifest"),
-    });
-
-    if paths.is_version_installed(&resolved_version) {
-        let _ = tx.send(InstallProgress::Completed {
-            version: resolved_version,
-        });
-        return Ok(());
-    }
-
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: format!("Downloading toolchain v{resolved_version}"),
-    });
-
-    let archive_filename = artifact.filename();
-    let archive_path = paths.download_path(archive_filename);
-
-    let tx_callback = tx.clone();
-    let callback: ProgressCallback = std::sync::Arc::new(move |event| {
-        match event {
-            ProgressEvent::Started { total, .. } => {
-                let _ = tx_callback.send(InstallProgress::DownloadStarted { total });
-            }
-            ProgressEvent::Progress { downloaded, speed } => {
-                let _ = tx_callback.send(InstallProgress::DownloadProgress { downloaded, speed });
-            }
-            ProgressEvent::Completed | ProgressEvent::Failed { .. } => {
-                // Handled at higher level
-            }
+) {
+    let current = Arc::new(Mutex::new(InstallPhase::FetchingManifest));
+    match install(version, resume_from, &current, &tx).await {
+        Ok(version) => {
+            let _ = tx.send(InstallProgress::Completed { version });
+        }
+        Err(e) => {
+            let phase = *current
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let _ = tx.send(InstallProgress::Failed {
+                phase,
+                error: e.to_string(),
+                retryable: phase.is_retryable(),
+            });
         }
-    });
-
-    download_file_with_callback(&artifact.url, &archive_path, callback)
-        .await
-        .context("Failed to download toolchain archive")?;
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: format!("Downloading toolchain v{resolved_version}"),
-    });
-
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: String::from("Verifying checksum"),
-    });
-
-    verify_checksum(&archive_path, &artifact.sha256)
-        .context("Checksum verification failed - download may be corrupted")?;
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: String::from("Verifying checksum"),
-    });
-
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: String::from("Extracting archive"),
-    });
-
-    let toolchain_dir = paths.toolchain_dir(&resolved_version);
-    extract_archive(&archive_path, &toolchain_dir)
-        .context("Failed to extract toolchain archive")?;
-
-    set_executable_permissions(&toolchain_dir).context("Failed to set executable permissions")?;
-
-    let metadata = ToolchainMetadata::now();
-    paths
-        .write_metadata(&resolved_version, &metadata)
-        .context("Failed to write toolchain metadata")?;
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: String::from("Extracting archive"),
-    });
-
-    let _ = tx.send(InstallProgress::PhaseStarted {
-        phase: String::from("Configuring toolchain"),
-    });
-
-    let installed_versions = paths
-        .list_installed_versions()
-        .context("Failed to list installed versions")?;
-    let is_first_install =
-        installed_versions.len() == 1 && installed_versions[0] == resolved_version;
-    let current_default = paths
-        .get_default_version()
-        .context("Failed to get default version")?;
-
-    if is_first_install || current_default.is_none() {
-        paths
-            .set_default_version(&resolved_version)
-            .context("Failed to set default version")?;
-        paths
-            .update_symlinks(&resolved_version)
-            .context("Failed to update symlinks")?;
     }
+}
 
-    std::fs::remove_file(&archive_path).ok();
-
-    let _ = tx.send(InstallProgress::PhaseCompleted {
-        phase: String::from("Configuring toolchain"),
-    });
-
-    let _ = tx.send(InstallProgress::Completed {
-        version: resolved_version,
-    });
+/// Installs the toolchain, forwarding installer events to `tx`.
+///
+/// `current` is kept up to date with the phase being executed so the caller
+/// can report which phase failed. Returns the installed version.
+async fn install(
+    version: Option<String>,
+    resume_from: Option<InstallPhase>,
+    current: &Arc<Mutex<InstallPhase>>,
+    tx: &Sender<InstallProgress>,
+) -> Result<String> {
+    let installer = Installer::new()?;
+    let request = InstallRequest {
+        version,
+        options: InstallOptions {
+            resume_from,
+            ..InstallOptions::default()
+        },
+    };
+
+    let tx = tx.clone();
+    let current = current.clone();
+    let callback: InstallCallback = Arc::new(move |event| forward(event, &current, &tx));
+    let toolchain = installer.install(request, callback).await?;
+    Ok(toolchain.version)
+}
 
-    Ok(())
+/// Translates an installer event into TUI progress messages.
+///
+/// Phase changes are also recorded in `current`.
+fn forward(event: InstallEvent, current: &Mutex<InstallPhase>, tx: &Sender<InstallProgress>) {
+    let message = match event {
+        InstallEvent::PhaseEntered(phase) => {
+            *current
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner) = phase;
+            InstallProgress::PhaseEntered { phase }
+        }
+        InstallEvent::StepStarted(phase) => InstallProgress::PhaseStarted { phase },
+        InstallEvent::StepCompleted(phase) => InstallProgress::PhaseCompleted { phase },
+        InstallEvent::Warning(warning) => InstallProgress::PhaseCompleted {
+            phase: format!("Warning: {warning}"),
+        },
+        InstallEvent::Download(ProgressEvent::Started { total, .. }) => {
+            InstallProgress::DownloadStarted { total }
+        }
+        InstallEvent::Download(ProgressEvent::Progress { downloaded, speed }) => {
+            InstallProgress::DownloadProgress { downloaded, speed }
+        }
+        InstallEvent::Download(ProgressEvent::Completed | ProgressEvent::Failed { .. }) => {
+            // Handled at higher level
+            return;
+        }
+        InstallEvent::ExtractProgress { processed, total } => {
+            InstallProgress::ExtractProgress { processed, total }
+        }
+    };
+    let _ = tx.send(message);
 }
 
 #[cfg(test)]
@@ -240,10 +174,12 @@ mod tests {
     #[test]
     fn install_progress_failed_contains_error() {
         let progress = InstallProgress::Failed {
+            phase: InstallPhase::Downloading,
             error: String::from("Network error"),
+            retryable: true,
         };
         match progress {
-            InstallProgress::Failed { error } => {
+            InstallProgress::Failed { error, .. } => {
                 assert_eq!(error, "Network error");
             }
             _ => panic!("Expected Failed variant"),
@@ -276,6 +212,64 @@ mod tests {
         assert!(debug_str.contains("test"));
     }
 
+    #[test]
+    fn forward_records_phase_and_notifies() {
+        let (tx, rx) = mpsc::channel();
+        let current = Mutex::new(InstallPhase::FetchingManifest);
+
+        forward(
+            InstallEvent::PhaseEntered(InstallPhase::Extracting),
+            &current,
+            &tx,
+        );
+
+        assert_eq!(*current.lock().unwrap(), InstallPhase::Extracting);
+        match rx.recv().expect("Should receive") {
+            InstallProgress::PhaseEntered { phase } => {
+                assert_eq!(phase, InstallPhase::Extracting);
+            }
+            other => panic!("Unexpected variant: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn forward_translates_progress_events() {
+        let (tx, rx) = mpsc::channel();
+        let current = Mutex::new(InstallPhase::FetchingManifest);
+
+        forward(
+            InstallEvent::Warning(String::from("unsigned")),
+            &current,
+            &tx,
+        );
+        forward(
+            InstallEvent::Download(ProgressEvent::Progress {
+                downloaded: 10,
+                speed: 5,
+            }),
+            &current,
+            &tx,
+        );
+        forward(
+            InstallEvent::Download(ProgressEvent::Completed),
+            &current,
+            &tx,
+        );
+
+        match rx.recv().expect("Should receive") {
+            InstallProgress::PhaseCompleted { phase } => assert_eq!(phase, "Warning: unsigned"),
+            other => panic!("Unexpected variant: {other:?}"),
+        }
+        match rx.recv().expect("Should receive") {
+            InstallProgress::DownloadProgress { downloaded, speed } => {
+                assert_eq!((downloaded, speed), (10, 5));
+            }
+            other => panic!("Unexpected variant: {other:?}"),
+        }
+        assert!(rx.try_recv().is_err());
+        assert_eq!(*current.lock().unwrap(), InstallPhase::FetchingManifest);
+    }
+
     #[test]
     fn channel_can_send_install_progress() {
         let (tx, rx) = mpsc::channel();
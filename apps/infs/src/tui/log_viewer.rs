@@ -0,0 +1,417 @@
+//! State of the log viewer screen.
+//!
+//! Logs can be large, so the file is read in chunks of [`CHUNK_LINES`] lines
+//! as the view moves down, keeping at least one chunk loaded past the top of
+//! the view. Searching reads further chunks only until a match is found.
+//!
+//! ## Keys
+//!
+//! - `/` starts a search; Enter runs it, Esc cancels
+//! - `n` / `N` jump to the next / previous match, wrapping around
+//! - Up/Down, PgUp/PgDn, `g`/`G` scroll
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Number of lines read from the log at a time.
+pub const CHUNK_LINES: usize = 500;
+
+/// Number of lines moved by `PgUp` and `PgDn`.
+pub const PAGE_LINES: usize = 20;
+
+/// Scrollable, searchable view of one log.
+pub struct LogViewerState {
+    /// Title shown above the log.
+    pub title: String,
+    /// Remaining content of the log; `None` once it has been read to the end.
+    reader: Option<Box<dyn BufRead>>,
+    /// Lines read so far.
+    lines: Vec<String>,
+    /// Index of the first visible line.
+    pub scroll: usize,
+    /// Search being typed after `/`, if any.
+    pub search_input: Option<String>,
+    /// Last submitted search.
+    pub query: Option<String>,
+    /// Result of the last search or an error, for the status line.
+    pub message: Option<String>,
+}
+
+impl fmt::Debug for LogViewerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogViewerState")
+            .field("title", &self.title)
+            .field("lines", &self.lines.len())
+            .field("complete", &self.is_complete())
+            .field("scroll", &self.scroll)
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LogViewerState {
+    /// Creates a viewer reading from `reader`, with the first chunk loaded.
+    #[must_use]
+    pub fn from_reader(title: impl Into<String>, reader: impl BufRead + 'static) -> Self {
+        let mut state = Self {
+            title: title.into(),
+            reader: Some(Box::new(reader)),
+            lines: Vec::new(),
+            scroll: 0,
+            search_input: None,
+            query: None,
+            message: None,
+        };
+        state.load_chunk();
+        state
+    }
+
+    /// Opens the log file at `path`.
+    ///
+    /// A file that cannot be opened shows as an empty log with an error
+    /// message.
+    #[must_use]
+    pub fn open(title: impl Into<String>, path: &Path) -> Self {
+        match File::open(path) {
+            Ok(file) => Self::from_reader(title, BufReader::new(file)),
+            Err(e) => {
+                let mut state = Self::from_reader(title, std::io::empty());
+                state.message = Some(format!("Cannot open {}: {e}", path.display()));
+                state
+            }
+        }
+    }
+
+    /// Returns the lines read so far.
+    #[must_use]
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns true once the whole log has been read.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.reader.is_none()
+    }
+
+    /// Reads up to [`CHUNK_LINES`] more lines. Returns false at the end of
+    /// the log.
+    fn load_chunk(&mut self) -> bool {
+        let Some(reader) = self.reader.as_mut() else {
+            return false;
+        };
+        let before = self.lines.len();
+        let mut buffer = Vec::new();
+        while self.lines.len() - before < CHUNK_LINES {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => {
+                    self.reader = None;
+                    break;
+                }
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buffer);
+                    self.lines
+                        .push(line.trim_end_matches(['\n', '\r']).to_string());
+                }
+            }
+        }
+        self.lines.len() > before
+    }
+
+    /// Reads chunks until at least `count` lines are loaded or the log ends.
+    fn ensure_lines(&mut self, count: usize) {
+        while self.lines.len() < count && self.load_chunk() {}
+    }
+
+    /// Reads the rest of the log.
+    fn load_all(&mut self) {
+        while self.load_chunk() {}
+    }
+
+    /// Keeps a chunk loaded past the top of the view.
+    fn prefetch(&mut self) {
+        self.ensure_lines(self.scroll + CHUNK_LINES);
+    }
+
+    /// Scrolls down by `count` lines, stopping at the last line.
+    pub fn scroll_down(&mut self, count: usize) {
+        self.ensure_lines(self.scroll + count + 1);
+        self.scroll = (self.scroll + count).min(self.lines.len().saturating_sub(1));
+        self.prefetch();
+    }
+
+    /// Scrolls up by `count` lines.
+    pub fn scroll_up(&mut self, count: usize) {
+        self.scroll = self.scroll.saturating_sub(count);
+    }
+
+    /// Scrolls to the first line.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Scrolls to the last line, reading the whole log.
+    pub fn scroll_to_bottom(&mut self) {
+        self.load_all();
+        self.scroll = self.lines.len().saturating_sub(1);
+    }
+
+    /// Starts typing a search.
+    pub fn start_search(&mut self) {
+        self.search_input = Some(String::new());
+        self.message = None;
+    }
+
+    /// Cancels the search being typed.
+    pub fn cancel_search(&mut self) {
+        self.search_input = None;
+    }
+
+    /// Appends a character to the search being typed.
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(input) = self.search_input.as_mut() {
+            input.push(c);
+        }
+    }
+
+    /// Removes the last character of the search being typed.
+    pub fn pop_search_char(&mut self) {
+        if let Some(input) = self.search_input.as_mut() {
+            input.pop();
+        }
+    }
+
+    /// Runs the typed search from the top of the view.
+    ///
+    /// An empty search repeats the previous one.
+    pub fn submit_search(&mut self) {
+        let Some(input) = self.search_input.take() else {
+            return;
+        };
+        if !input.is_empty() {
+            self.query = Some(input);
+        }
+        if self.query.is_some() {
+            self.jump(Direction::Forward, self.scroll);
+        }
+    }
+
+    /// Jumps to the next match after the top line.
+    pub fn next_match(&mut self) {
+        self.jump(Direction::Forward, self.scroll + 1);
+    }
+
+    /// Jumps to the previous match before the top line.
+    pub fn previous_match(&mut self) {
+        self.jump(Direction::Backward, self.scroll);
+    }
+
+    /// Scrolls to the first match of the query found in `direction` from line
+    /// `from`, wrapping around the log.
+    fn jump(&mut self, direction: Direction, from: usize) {
+        let Some(query) = self.query.clone() else {
+            self.message = Some(String::from("No previous search"));
+            return;
+        };
+        let needle = query.to_lowercase();
+        let found = match direction {
+            Direction::Forward => self
+                .find_forward(&needle, from)
+                .or_else(|| self.find_in(&needle, 0..from.min(self.lines.len()))),
+            Direction::Backward => self
+                .find_in(&needle, (0..from.min(self.lines.len())).rev())
+                .or_else(|| {
+                    self.load_all();
+                    self.find_in(&needle, (from..self.lines.len()).rev())
+                }),
+        };
+        match found {
+            Some(line) => {
+                self.scroll = line;
+                self.message = None;
+                self.prefetch();
+            }
+            None => self.message = Some(format!("Pattern not found: {query}")),
+        }
+    }
+
+    /// Finds the first line at or after `from` containing `needle`, reading
+    /// chunks as needed.
+    fn find_forward(&mut self, needle: &str, from: usize) -> Option<usize> {
+        let mut start = from;
+        loop {
+            if let Some(line) = self.find_in(needle, start..self.lines.len()) {
+                return Some(line);
+            }
+            start = start.max(self.lines.len());
+            if !self.load_chunk() {
+                return None;
+            }
+        }
+    }
+
+    /// Finds the first of `range` whose loaded line contains `needle`.
+    fn find_in(&self, needle: &str, mut range: impl Iterator<Item = usize>) -> Option<usize> {
+        range.find(|&index| {
+            self.lines
+                .get(index)
+                .is_some_and(|line| line.to_lowercase().contains(needle))
+        })
+    }
+
+    /// Returns true if `line` contains the current query.
+    #[must_use]
+    pub fn is_match(&self, line: &str) -> bool {
+        self.query
+            .as_deref()
+            .is_some_and(|query| line.to_lowercase().contains(&query.to_lowercase()))
+    }
+}
+
+/// Search direction.
+#[derive(Clone, Copy)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A log with `count` lines, where every 100th line is an error.
+    fn viewer(count: usize) -> LogViewerState {
+        let text: String = (0..count)
+            .map(|i| {
+                if i % 100 == 50 {
+                    format!("error: line {i}\n")
+                } else {
+                    format!("line {i}\n")
+                }
+            })
+            .collect();
+        LogViewerState::from_reader("build", Cursor::new(text))
+    }
+
+    fn search(state: &mut LogViewerState, query: &str) {
+        state.start_search();
+        for c in query.chars() {
+            state.push_search_char(c);
+        }
+        state.submit_search();
+    }
+
+    #[test]
+    fn large_logs_load_lazily() {
+        let mut state = viewer(CHUNK_LINES * 4);
+        assert_eq!(state.lines().len(), CHUNK_LINES);
+        assert!(!state.is_complete());
+
+        state.scroll_down(10);
+        assert_eq!(state.scroll, 10);
+        assert_eq!(state.lines().len(), CHUNK_LINES * 2);
+
+        state.scroll_to_bottom();
+        assert!(state.is_complete());
+        assert_eq!(state.lines().len(), CHUNK_LINES * 4);
+        assert_eq!(state.scroll, CHUNK_LINES * 4 - 1);
+    }
+
+    #[test]
+    fn scrolling_stays_within_log() {
+        let mut state = viewer(5);
+        assert!(state.is_complete());
+        state.scroll_down(PAGE_LINES);
+        assert_eq!(state.scroll, 4);
+        state.scroll_up(2);
+        assert_eq!(state.scroll, 2);
+        state.scroll_up(PAGE_LINES);
+        assert_eq!(state.scroll, 0);
+
+        let mut empty = viewer(0);
+        empty.scroll_down(1);
+        empty.scroll_to_bottom();
+        assert_eq!(empty.scroll, 0);
+    }
+
+    #[test]
+    fn search_jumps_between_matches() {
+        let mut state = viewer(300);
+        search(&mut state, "ERROR");
+        assert_eq!(state.scroll, 50);
+        assert!(state.message.is_none());
+
+        state.next_match();
+        assert_eq!(state.scroll, 150);
+        state.next_match();
+        assert_eq!(state.scroll, 250);
+        state.next_match();
+        assert_eq!(state.scroll, 50, "wraps to the first match");
+
+        state.previous_match();
+        assert_eq!(state.scroll, 250, "wraps to the last match");
+        state.previous_match();
+        assert_eq!(state.scroll, 150);
+    }
+
+    #[test]
+    fn search_reads_chunks_until_a_match() {
+        let text: String = (0..CHUNK_LINES * 3)
+            .map(|i| {
+                if i == CHUNK_LINES + 7 {
+                    String::from("needle\n")
+                } else {
+                    format!("line {i}\n")
+                }
+            })
+            .collect();
+        let mut state = LogViewerState::from_reader("run", Cursor::new(text));
+        search(&mut state, "needle");
+        assert_eq!(state.scroll, CHUNK_LINES + 7);
+        assert!(!state.is_complete());
+    }
+
+    #[test]
+    fn search_without_match_keeps_position() {
+        let mut state = viewer(300);
+        state.scroll_down(3);
+        search(&mut state, "missing");
+        assert_eq!(state.scroll, 3);
+        assert_eq!(state.message.as_deref(), Some("Pattern not found: missing"));
+
+        let mut fresh = viewer(10);
+        fresh.next_match();
+        assert_eq!(fresh.message.as_deref(), Some("No previous search"));
+    }
+
+    #[test]
+    fn search_input_can_be_edited_and_cancelled() {
+        let mut state = viewer(300);
+        state.start_search();
+        state.push_search_char('x');
+        state.pop_search_char();
+        for c in "line 2".chars() {
+            state.push_search_char(c);
+        }
+        assert_eq!(state.search_input.as_deref(), Some("line 2"));
+        state.cancel_search();
+        assert!(state.search_input.is_none());
+        assert!(state.query.is_none());
+
+        search(&mut state, "line 2");
+        assert_eq!(state.scroll, 2);
+        assert!(state.is_match("Line 20"));
+        assert!(!state.is_match("line 3"));
+    }
+
+    #[test]
+    fn open_missing_file_shows_error() {
+        let state = LogViewerState::open("build", Path::new("/nonexistent/infs/1.log"));
+        assert!(state.lines().is_empty());
+        assert!(state.message.unwrap().starts_with("Cannot open"));
+    }
+}
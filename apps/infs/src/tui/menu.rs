@@ -44,6 +44,7 @@ impl MenuItem {
 pub const MENU_ITEMS: &[MenuItem] = &[
     MenuItem::screen("Toolchains", 't', Screen::Toolchains),
     MenuItem::screen("Doctor", 'd', Screen::Doctor),
+    MenuItem::screen("Logs", 'l', Screen::Logs),
     MenuItem::quit("Quit", 'q'),
 ];
 
@@ -93,10 +94,25 @@ impl Menu {
         self.selected = (self.selected + 1) % MENU_ITEMS.len();
     }
 
-    /// Finds a menu item by its shortcut key.
+    /// Selects the item at `index`, ignoring out-of-range indices.
+    pub fn select(&mut self, index: usize) {
+        if index < MENU_ITEMS.len() {
+            self.selected = index;
+        }
+    }
+
+    /// Finds a menu item by its shortcut key (case-insensitive).
     #[must_use]
+    #[allow(dead_code)]
     pub fn find_by_key(key: char) -> Option<&'static MenuItem> {
-        MENU_ITEMS.iter().find(|item| item.key == key)
+        Self::index_of_key(key).map(|idx| &MENU_ITEMS[idx])
+    }
+
+    /// Returns the index of the menu item with the given shortcut key (case-insensitive).
+    #[must_use]
+    pub fn index_of_key(key: char) -> Option<usize> {
+        let key = key.to_ascii_lowercase();
+        MENU_ITEMS.iter().position(|item| item.key == key)
     }
 }
 
@@ -145,11 +161,38 @@ mod tests {
         assert!(item.is_some());
         assert_eq!(item.unwrap().screen, Some(Screen::Doctor));
 
+        let item = Menu::find_by_key('l');
+        assert_eq!(item.unwrap().screen, Some(Screen::Logs));
+
         let item = Menu::find_by_key('q');
         assert!(item.is_some());
         assert!(item.unwrap().quits);
     }
 
+    #[test]
+    fn find_by_key_is_case_insensitive() {
+        let item = Menu::find_by_key('T');
+        assert_eq!(item.map(|i| i.screen), Some(Some(Screen::Toolchains)));
+    }
+
+    #[test]
+    fn index_of_key_maps_to_menu_index() {
+        for (idx, item) in MENU_ITEMS.iter().enumerate() {
+            assert_eq!(Menu::index_of_key(item.key), Some(idx));
+            assert_eq!(Menu::index_of_key(item.key.to_ascii_uppercase()), Some(idx));
+        }
+        assert_eq!(Menu::index_of_key('z'), None);
+    }
+
+    #[test]
+    fn select_ignores_out_of_range() {
+        let mut menu = Menu::new();
+        menu.select(1);
+        assert_eq!(menu.selected(), 1);
+        menu.select(MENU_ITEMS.len());
+        assert_eq!(menu.selected(), 1);
+    }
+
     #[test]
     fn find_by_key_returns_none_for_unknown() {
         let item = Menu::find_by_key('z');
@@ -12,12 +12,17 @@
 //!
 //! The TUI will not launch in headless environments:
 //! - When `INFS_NO_TUI` environment variable is set (any value)
+//! - When `--color=never` is given
 //! - When stdout is not a terminal (piped or redirected)
 //!
 //! ## Modules
 //!
 //! - [`terminal`] - Terminal setup and cleanup with RAII guard
 //! - [`app`] - Main application state and event loop
+//! - [`command_log`] - Saved output of commands run from the TUI
+//! - [`fix_task`] - Background doctor fixes
+//! - [`fuzzy`] - Fuzzy matching for filter-as-you-type lists
+//! - [`log_viewer`] - Scrollable, searchable log view
 //! - [`state`] - Screen state machine and view states
 //! - [`theme`] - Color theme system
 //! - [`menu`] - Menu navigation
@@ -25,7 +30,11 @@
 //! - [`widgets`] - Reusable widget components
 
 pub mod app;
+pub mod command_log;
+pub mod fix_task;
+pub mod fuzzy;
 pub mod install_task;
+pub mod log_viewer;
 pub mod menu;
 pub mod state;
 pub mod terminal;
@@ -37,17 +46,21 @@ use std::io::IsTerminal;
 
 use anyhow::{Context, Result};
 
+use crate::output::{self, ColorMode, Stream};
 use crate::toolchain::ToolchainPaths;
+use command_log::LogStore;
+use state::SessionState;
 use terminal::TerminalGuard;
 
 /// Determines whether the TUI should be used based on environment.
 ///
 /// Returns `false` in headless environments:
 /// - `INFS_NO_TUI` environment variable (any value)
+/// - `--color=never`, since the TUI cannot render without colors
 /// - Non-TTY stdout (piped or redirected)
 #[must_use]
-pub fn should_use_tui() -> bool {
-    if std::env::var("INFS_NO_TUI").is_ok() {
+pub fn should_use_tui(color: ColorMode) -> bool {
+    if std::env::var("INFS_NO_TUI").is_ok() || color == ColorMode::Never {
         return false;
     }
     std::io::stdout().is_terminal()
@@ -75,10 +88,11 @@ pub fn run() -> Result<()> {
         let _ = paths.ensure_directories();
     }
 
+    let mut session = SessionState::default();
     loop {
         let pending_command = {
             let mut guard = TerminalGuard::new().context("failed to initialize terminal")?;
-            app::run_app(&mut guard).context("TUI application error")?
+            app::run_app(&mut guard, &mut session).context("TUI application error")?
             // Guard is dropped here, restoring terminal
         };
 
@@ -98,15 +112,34 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
-/// Executes a pending command after the TUI has exited.
-fn execute_pending_command(command: &str) -> Result<()> {
+/// Executes a pending command, given as arguments to infs, after the TUI has
+/// exited.
+///
+/// The output is shown as usual and also saved to the command log, where the
+/// Logs screen can show it after the TUI is back. Since the command writes to
+/// a pipe, the color choice is passed on explicitly.
+fn execute_pending_command(args: &[String]) -> Result<()> {
+    let command = args.join(" ");
     let exe = std::env::current_exe().context("failed to get current executable")?;
+    let color = if output::colors_enabled(Stream::Stdout) {
+        "--color=always"
+    } else {
+        "--color=never"
+    };
 
     println!();
-    let status = std::process::Command::new(&exe)
-        .arg(command)
-        .status()
-        .with_context(|| format!("failed to execute 'infs {command}'"))?;
+    let started_at = command_log::now();
+    let (status, captured) =
+        command_log::run_teed(std::process::Command::new(&exe).args(args).arg(color))
+            .with_context(|| format!("failed to execute 'infs {command}'"))?;
+
+    if let Err(e) = LogStore::open()
+        .and_then(|store| store.record(&command, started_at, status.code(), &captured))
+    {
+        output::warn(format!(
+            "could not save the output of 'infs {command}': {e:#}"
+        ));
+    }
 
     if !status.success() {
         // Log failure but don't exit - we'll return to TUI
@@ -140,7 +173,7 @@ mod tests {
     fn should_use_tui_returns_bool() {
         // This test verifies the function can be called and returns a boolean.
         // The actual return value depends on the environment.
-        let result = should_use_tui();
+        let result = should_use_tui(ColorMode::Auto);
         // In CI, this should be false (CI=true is typically set)
         // We can't assert a specific value since test environments vary
         let _ = result;
@@ -157,13 +190,13 @@ mod tests {
         unsafe {
             std::env::set_var("INFS_NO_TUI", "1");
         }
-        assert!(!should_use_tui());
+        assert!(!should_use_tui(ColorMode::Auto));
 
         // Empty string still disables TUI (env var is set)
         unsafe {
             std::env::set_var("INFS_NO_TUI", "");
         }
-        assert!(!should_use_tui());
+        assert!(!should_use_tui(ColorMode::Auto));
 
         // Restore original
         unsafe {
@@ -173,4 +206,9 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn should_use_tui_is_disabled_by_color_never() {
+        assert!(!should_use_tui(ColorMode::Never));
+    }
 }
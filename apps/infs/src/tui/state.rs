@@ -3,9 +3,20 @@
 //! This module defines the screen state machine and view-specific state
 //! for the infs TUI application.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::toolchain::download::{estimate_remaining, format_duration};
+use crate::toolchain::fix::{FixAction, FixOutcome};
+use crate::toolchain::manifest::{Manifest, latest_stable, latest_version};
 use crate::toolchain::paths::ToolchainMetadata;
 
+use super::command_log::{LogEntry, LogStore};
+use super::widgets::scroll_list::ScrollList;
+
 pub use crate::toolchain::doctor::{DoctorCheck, DoctorCheckStatus};
+pub use crate::toolchain::installer::InstallPhase;
 
 /// Active screen in the TUI application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -21,6 +32,107 @@ pub enum Screen {
     Progress,
     /// Version selection view for choosing a version to install.
     VersionSelect,
+    /// Saved outputs of commands run from the TUI.
+    Logs,
+    /// Contents of one saved output.
+    LogView,
+}
+
+/// State machine for a TUI toolchain installation.
+///
+/// Transitions are driven by [`InstallProgress`] messages via
+/// [`InstallTaskState::apply`]:
+/// `Idle -> Running(FetchingManifest) -> ... -> Running(Linking) -> Done`,
+/// with any running phase able to move to `Failed`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InstallTaskState {
+    /// No installation has started.
+    #[default]
+    Idle,
+    /// The installation is executing the given phase.
+    Running(InstallPhase),
+    /// The installation finished successfully.
+    Done,
+    /// The installation failed.
+    Failed {
+        /// The phase that failed.
+        phase: InstallPhase,
+        /// Error description.
+        error: String,
+        /// Whether the installation can be retried from `phase`.
+        retryable: bool,
+    },
+}
+
+impl InstallTaskState {
+    /// Advances the state machine in response to a progress message.
+    pub fn apply(&mut self, msg: &InstallProgress) {
+        match msg {
+            InstallProgress::PhaseEntered { phase } => {
+                *self = InstallTaskState::Running(*phase);
+            }
+            InstallProgress::Completed { .. } => {
+                *self = InstallTaskState::Done;
+            }
+            InstallProgress::Failed {
+                phase,
+                error,
+                retryable,
+            } => {
+                *self = InstallTaskState::Failed {
+                    phase: *phase,
+                    error: error.clone(),
+                    retryable: *retryable,
+                };
+            }
+            InstallProgress::PhaseStarted { .. }
+            | InstallProgress::DownloadStarted { .. }
+            | InstallProgress::DownloadProgress { .. }
+            | InstallProgress::ExtractProgress { .. }
+            | InstallProgress::PhaseCompleted { .. } => {}
+        }
+    }
+
+    /// Returns the phase to restart from, if the task failed and can be retried.
+    #[must_use]
+    pub fn retry_phase(&self) -> Option<InstallPhase> {
+        match self {
+            InstallTaskState::Failed {
+                phase,
+                retryable: true,
+                ..
+            } => Some(*phase),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the given phase has finished in the current state.
+    #[must_use]
+    pub fn is_phase_done(&self, phase: InstallPhase) -> bool {
+        match self {
+            InstallTaskState::Idle => false,
+            InstallTaskState::Running(current)
+            | InstallTaskState::Failed { phase: current, .. } => phase < *current,
+            InstallTaskState::Done => true,
+        }
+    }
+
+    /// Returns remediation advice for non-retryable failures.
+    #[must_use]
+    pub fn remediation(&self) -> Option<&'static str> {
+        match self {
+            InstallTaskState::Failed {
+                phase: InstallPhase::Verifying,
+                retryable: false,
+                ..
+            } => Some(
+                "The downloaded archive does not match the published checksum and was removed. \
+                 Check for a proxy or network issue and install again; if it persists, \
+                 report the release as corrupted.",
+            ),
+            _ => None,
+        }
+    }
 }
 
 /// Message sent from installation task to TUI for progress updates.
@@ -30,6 +142,11 @@ pub enum Screen {
 /// the progress display accordingly.
 #[derive(Debug, Clone)]
 pub enum InstallProgress {
+    /// The installation entered a new pipeline phase.
+    PhaseEntered {
+        /// The phase being entered.
+        phase: InstallPhase,
+    },
     /// A new phase of the installation has started.
     PhaseStarted {
         /// Description of the phase (e.g., "Fetching manifest", "Downloading").
@@ -47,6 +164,13 @@ pub enum InstallProgress {
         /// Current download speed in bytes per second.
         speed: u64,
     },
+    /// Archive extraction progress update.
+    ExtractProgress {
+        /// Archive entries processed so far.
+        processed: u64,
+        /// Total number of archive entries.
+        total: u64,
+    },
     /// A phase of the installation has completed.
     PhaseCompleted {
         /// Description of the completed phase.
@@ -59,8 +183,12 @@ pub enum InstallProgress {
     },
     /// Installation failed with an error.
     Failed {
+        /// The phase that failed.
+        phase: InstallPhase,
         /// Error description.
         error: String,
+        /// Whether the installation can be retried from `phase`.
+        retryable: bool,
     },
 }
 
@@ -78,10 +206,8 @@ pub struct ToolchainInfo {
 /// State for the toolchains view.
 #[derive(Debug, Clone, Default)]
 pub struct ToolchainsState {
-    /// List of installed toolchains.
-    pub toolchains: Vec<ToolchainInfo>,
-    /// Currently selected index.
-    pub selected: usize,
+    /// List of installed toolchains with selection and scroll state.
+    pub toolchains: ScrollList<ToolchainInfo>,
     /// Whether the data has been loaded.
     pub loaded: bool,
 }
@@ -95,19 +221,63 @@ impl ToolchainsState {
 
     /// Moves selection up.
     pub fn select_previous(&mut self) {
-        if !self.toolchains.is_empty() {
-            self.selected = self.selected.saturating_sub(1);
-        }
+        self.toolchains.select_previous();
     }
 
     /// Moves selection down.
     pub fn select_next(&mut self) {
-        if !self.toolchains.is_empty() {
-            self.selected = (self.selected + 1).min(self.toolchains.len() - 1);
-        }
+        self.toolchains.select_next();
     }
 }
 
+/// What a fix run from the doctor view did.
+#[derive(Debug, Clone)]
+pub struct FixReport {
+    /// The check, run again after the fix.
+    pub check: DoctorCheck,
+    /// What the fix changed or could not change.
+    pub outcomes: Vec<FixOutcome>,
+}
+
+/// Progress messages from the background fix task to the doctor view.
+#[derive(Debug, Clone)]
+pub enum FixProgress {
+    /// The fix of the check at `index` started.
+    Started {
+        /// Index of the check in [`DoctorState::checks`].
+        index: usize,
+    },
+    /// The fix of the check at `index` finished, or failed with an error.
+    Finished {
+        /// Index of the check in [`DoctorState::checks`].
+        index: usize,
+        /// What the fix did.
+        result: Result<FixReport, String>,
+    },
+}
+
+/// Fixes waiting for confirmation in the doctor view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixRequest {
+    /// Indices of the checks to fix, in order.
+    pub indices: Vec<usize>,
+    /// What the fixes will change, one change per line.
+    pub changes: Vec<String>,
+}
+
+/// Fixes being applied in the doctor view.
+#[derive(Debug, Clone)]
+pub struct FixQueueState {
+    /// Number of queued fixes.
+    pub total: usize,
+    /// Number of finished fixes.
+    pub completed: usize,
+    /// Index of the check being fixed.
+    pub current: Option<usize>,
+    /// When the queue started, for the spinner.
+    pub started: Instant,
+}
+
 /// State for the doctor view.
 #[derive(Debug, Clone, Default)]
 pub struct DoctorState {
@@ -117,6 +287,13 @@ pub struct DoctorState {
     pub selected: usize,
     /// Whether the data has been loaded.
     pub loaded: bool,
+    /// Result of the last fix of a check, by check index, shown below its
+    /// message.
+    pub details: HashMap<usize, String>,
+    /// Fixes waiting for confirmation.
+    pub fix_request: Option<FixRequest>,
+    /// Fixes being applied.
+    pub fixing: Option<FixQueueState>,
 }
 
 impl DoctorState {
@@ -166,6 +343,147 @@ impl DoctorState {
             .filter(|c| c.status == DoctorCheckStatus::Error)
             .count()
     }
+
+    /// Returns the fix for the check at `index`, if it did not pass and has
+    /// one.
+    #[must_use]
+    pub fn fix_action(&self, index: usize) -> Option<FixAction> {
+        self.checks
+            .get(index)
+            .filter(|check| check.status != DoctorCheckStatus::Ok)
+            .and_then(|check| FixAction::for_check(&check.name))
+    }
+
+    /// Returns the indices of all checks [`DoctorState::fix_action`] can fix.
+    #[must_use]
+    pub fn fixable_indices(&self) -> Vec<usize> {
+        (0..self.checks.len())
+            .filter(|&index| self.fix_action(index).is_some())
+            .collect()
+    }
+
+    /// Returns `true` while fixes are being applied.
+    #[must_use]
+    pub fn is_fixing(&self) -> bool {
+        self.fixing.is_some()
+    }
+
+    /// Takes the confirmed [`FixRequest`] and starts the queue.
+    ///
+    /// Returns the fixes to run, in order.
+    pub fn start_fixes(&mut self) -> Vec<(usize, FixAction)> {
+        let Some(request) = self.fix_request.take() else {
+            return Vec::new();
+        };
+        let queue: Vec<_> = request
+            .indices
+            .into_iter()
+            .filter_map(|index| self.fix_action(index).map(|action| (index, action)))
+            .collect();
+        if !queue.is_empty() {
+            self.fixing = Some(FixQueueState {
+                total: queue.len(),
+                completed: 0,
+                current: None,
+                started: Instant::now(),
+            });
+        }
+        queue
+    }
+
+    /// Applies a message from the fix task.
+    ///
+    /// A successful fix replaces the check with its re-run result. A fix
+    /// that fails, or reports a problem it could not fix, marks the check
+    /// failed. Either way the outcome is kept in [`DoctorState::details`].
+    pub fn apply_fix_progress(&mut self, progress: FixProgress) {
+        let Some(queue) = self.fixing.as_mut() else {
+            return;
+        };
+        let (index, result) = match progress {
+            FixProgress::Started { index } => {
+                queue.current = Some(index);
+                return;
+            }
+            FixProgress::Finished { index, result } => (index, result),
+        };
+        queue.completed += 1;
+        queue.current = None;
+        if queue.completed >= queue.total {
+            self.fixing = None;
+        }
+        let Some(check) = self.checks.get_mut(index) else {
+            return;
+        };
+
+        let (failed, detail) = match result {
+            Ok(report) => {
+                let failures: Vec<_> = report
+                    .outcomes
+                    .iter()
+                    .filter_map(|outcome| match outcome {
+                        FixOutcome::NotFixable { name, reason } => {
+                            Some(format!("{name}: {reason}"))
+                        }
+                        FixOutcome::Fixed { .. } => None,
+                    })
+                    .collect();
+                if failures.is_empty() {
+                    let changes: Vec<_> = report
+                        .outcomes
+                        .iter()
+                        .filter_map(|outcome| match outcome {
+                            FixOutcome::Fixed { change, .. } => Some(change.as_str()),
+                            FixOutcome::NotFixable { .. } => None,
+                        })
+                        .collect();
+                    *check = report.check;
+                    let detail = if changes.is_empty() {
+                        String::from("Nothing to fix")
+                    } else {
+                        changes.join("; ")
+                    };
+                    (false, detail)
+                } else {
+                    (true, failures.join("; "))
+                }
+            }
+            Err(error) => (true, error),
+        };
+        if failed {
+            check.status = DoctorCheckStatus::Error;
+        }
+        self.details.insert(index, detail);
+    }
+}
+
+/// State for the logs view.
+#[derive(Debug, Clone, Default)]
+pub struct LogsState {
+    /// Saved runs, most recent first.
+    pub entries: ScrollList<LogEntry>,
+    /// Store the runs were read from, if the home directory is known.
+    pub store: Option<LogStore>,
+    /// Whether the data has been loaded.
+    pub loaded: bool,
+}
+
+impl LogsState {
+    /// Creates a new empty logs state.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves selection up.
+    pub fn select_previous(&mut self) {
+        self.entries.select_previous();
+    }
+
+    /// Moves selection down.
+    pub fn select_next(&mut self) {
+        self.entries.select_next();
+    }
 }
 
 /// Information about an available version for installation.
@@ -179,6 +497,8 @@ pub struct VersionSelectInfo {
     pub platforms: Vec<String>,
     /// Whether this version is available for the current platform.
     pub available_for_current: bool,
+    /// First lines of the release notes as plain text, if any.
+    pub notes: Option<String>,
 }
 
 /// State for the version selection view.
@@ -233,15 +553,155 @@ impl VersionSelectState {
     }
 }
 
+/// Newer releases found by the background manifest refresh.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateInfo {
+    /// Newer toolchain than the default installed one, if any.
+    pub toolchain: Option<String>,
+    /// Newer infs release than the running binary, if any.
+    pub infs: Option<String>,
+}
+
+impl UpdateInfo {
+    /// Compares the latest release in `manifest` with what is installed.
+    ///
+    /// The latest stable release is preferred, as in `infs install` and
+    /// `infs self update`. No toolchain update is reported while no default
+    /// toolchain is set. Versions that are not valid semver are ignored.
+    #[must_use]
+    pub fn from_manifest(
+        manifest: &Manifest,
+        default_version: Option<&str>,
+        infs_version: &str,
+    ) -> Self {
+        let Some(latest) = latest_stable(manifest).or_else(|| latest_version(manifest)) else {
+            return Self::default();
+        };
+        let newer =
+            |current: &str| is_newer(&latest.version, current).then(|| latest.version.clone());
+
+        Self {
+            toolchain: default_version.and_then(newer),
+            infs: newer(infs_version),
+        }
+    }
+
+    /// Returns `true` if no update is available.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.toolchain.is_none() && self.infs.is_none()
+    }
+}
+
+/// Returns `true` if `candidate` is a newer semver version than `current`.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parse = |v: &str| semver::Version::parse(v.trim_start_matches('v')).ok();
+    matches!(
+        (parse(candidate), parse(current)),
+        (Some(candidate), Some(current)) if candidate > current
+    )
+}
+
+/// State of the update-available banner on the main screen.
+#[derive(Debug, Clone)]
+pub struct UpdateBannerState {
+    /// Whether the banner is enabled in the configuration.
+    pub enabled: bool,
+    /// Whether the user dismissed the banner for this session.
+    pub dismissed: bool,
+    /// Latest update check result.
+    pub update: UpdateInfo,
+}
+
+impl Default for UpdateBannerState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dismissed: false,
+            update: UpdateInfo::default(),
+        }
+    }
+}
+
+impl UpdateBannerState {
+    /// Records the result of an update check.
+    ///
+    /// A dismissed banner stays hidden for the rest of the session, even if
+    /// a later check finds a newer release.
+    pub fn apply(&mut self, update: UpdateInfo) {
+        self.update = update;
+    }
+
+    /// Hides the banner for the rest of the session.
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+
+    /// Returns `true` if the banner should be shown.
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.enabled && !self.dismissed && !self.update.is_empty()
+    }
+
+    /// Returns the toolchain version offered by the visible banner, if any.
+    #[must_use]
+    pub fn toolchain_update(&self) -> Option<&str> {
+        if self.is_visible() {
+            self.update.toolchain.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the one-line banner text, or `None` if the banner is hidden.
+    #[must_use]
+    pub fn message(&self) -> Option<String> {
+        if !self.is_visible() {
+            return None;
+        }
+        match (&self.update.toolchain, &self.update.infs) {
+            (Some(toolchain), _) => Some(format!(
+                "Toolchain {toolchain} available — press U to update, x to dismiss"
+            )),
+            (None, Some(infs)) => Some(format!(
+                "infs {infs} available — run 'infs self update', x to dismiss"
+            )),
+            (None, None) => None,
+        }
+    }
+}
+
+/// State kept while the TUI restarts around commands it runs in the terminal.
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    /// File last chosen in the file picker, per command.
+    last_targets: HashMap<String, PathBuf>,
+}
+
+impl SessionState {
+    /// Returns the file last chosen for `command`.
+    #[must_use]
+    pub fn last_target(&self, command: &str) -> Option<&Path> {
+        self.last_targets.get(command).map(PathBuf::as_path)
+    }
+
+    /// Remembers `target` as the file chosen for `command`.
+    pub fn remember_target(&mut self, command: &str, target: PathBuf) {
+        self.last_targets.insert(command.to_string(), target);
+    }
+}
+
 /// Progress information for a single download or operation.
 #[derive(Debug, Clone)]
 pub struct ProgressItem {
     /// Description of what is being downloaded/processed.
     pub description: String,
-    /// Current progress in bytes.
+    /// Current progress, measured in `unit`.
     pub current: u64,
-    /// Total size in bytes (0 if unknown).
+    /// Total amount of work, measured in `unit` (0 if unknown).
     pub total: u64,
+    /// Unit of `current` and `total`.
+    pub unit: ProgressUnit,
     /// Whether this item is completed.
     pub completed: bool,
     /// Current download speed in bytes per second.
@@ -258,12 +718,20 @@ impl ProgressItem {
             description: description.into(),
             current: 0,
             total: 0,
+            unit: ProgressUnit::Bytes,
             completed: false,
             speed_bytes_per_sec: None,
             started_at: None,
         }
     }
 
+    /// Sets the unit that progress is measured in.
+    #[must_use]
+    pub fn with_unit(mut self, unit: ProgressUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
     /// Marks the start of the download operation.
     pub fn start(&mut self) {
         self.started_at = Some(std::time::Instant::now());
@@ -301,6 +769,13 @@ impl ProgressItem {
     /// Formats the progress as a human-readable string.
     #[must_use]
     pub fn format_progress(&self) -> String {
+        if self.unit == ProgressUnit::Entries {
+            return if self.total == 0 {
+                format!("{} files", self.current)
+            } else {
+                format!("{} / {} files", self.current, self.total)
+            };
+        }
         if self.total == 0 {
             if self.completed {
                 "Done".to_string()
@@ -326,6 +801,31 @@ impl ProgressItem {
             _ => String::new(),
         }
     }
+
+    /// Formats the estimated time left, e.g. `ETA 10s`.
+    ///
+    /// Returns an empty string for completed items, items measured in
+    /// entries, and items without a total or a speed.
+    #[must_use]
+    pub fn format_eta(&self) -> String {
+        if self.completed || self.unit != ProgressUnit::Bytes {
+            return String::new();
+        }
+        self.speed_bytes_per_sec
+            .and_then(|speed| estimate_remaining(self.current, self.total, speed))
+            .map(|eta| format!("ETA {}", format_duration(eta)))
+            .unwrap_or_default()
+    }
+}
+
+/// Unit of a [`ProgressItem`]'s progress values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressUnit {
+    /// Bytes transferred, e.g. for downloads.
+    #[default]
+    Bytes,
+    /// Archive entries processed, e.g. for extraction.
+    Entries,
 }
 
 /// Formats bytes as a human-readable string.
@@ -365,6 +865,8 @@ pub struct ProgressState {
     pub completed: bool,
     /// Error message if the operation failed.
     pub error: Option<String>,
+    /// Installation state machine, if this progress tracks an installation.
+    pub task: InstallTaskState,
 }
 
 impl ProgressState {
@@ -377,6 +879,7 @@ impl ProgressState {
             status: String::new(),
             completed: false,
             error: None,
+            task: InstallTaskState::Idle,
         }
     }
 
@@ -427,22 +930,21 @@ mod tests {
     #[test]
     fn toolchains_state_select_previous_at_zero_stays_zero() {
         let mut state = ToolchainsState {
-            toolchains: vec![ToolchainInfo {
+            toolchains: ScrollList::new(vec![ToolchainInfo {
                 version: "0.1.0".to_string(),
                 is_default: true,
                 metadata: None,
-            }],
-            selected: 0,
+            }]),
             loaded: true,
         };
         state.select_previous();
-        assert_eq!(state.selected, 0);
+        assert_eq!(state.toolchains.selected(), 0);
     }
 
     #[test]
     fn toolchains_state_select_next_respects_bounds() {
         let mut state = ToolchainsState {
-            toolchains: vec![
+            toolchains: ScrollList::new(vec![
                 ToolchainInfo {
                     version: "0.1.0".to_string(),
                     is_default: true,
@@ -453,14 +955,13 @@ mod tests {
                     is_default: false,
                     metadata: None,
                 },
-            ],
-            selected: 0,
+            ]),
             loaded: true,
         };
         state.select_next();
-        assert_eq!(state.selected, 1);
+        assert_eq!(state.toolchains.selected(), 1);
         state.select_next();
-        assert_eq!(state.selected, 1);
+        assert_eq!(state.toolchains.selected(), 1);
     }
 
     #[test]
@@ -468,7 +969,7 @@ mod tests {
         let mut state = ToolchainsState::new();
         state.select_previous();
         state.select_next();
-        assert_eq!(state.selected, 0);
+        assert_eq!(state.toolchains.selected(), 0);
     }
 
     #[test]
@@ -482,6 +983,7 @@ mod tests {
             ],
             selected: 0,
             loaded: true,
+            ..DoctorState::default()
         };
         assert_eq!(state.ok_count(), 2);
         assert_eq!(state.warning_count(), 1);
@@ -497,6 +999,7 @@ mod tests {
             ],
             selected: 0,
             loaded: true,
+            ..DoctorState::default()
         };
         state.select_next();
         assert_eq!(state.selected, 1);
@@ -508,6 +1011,117 @@ mod tests {
         assert_eq!(state.selected, 0);
     }
 
+    fn fixable_doctor_state() -> DoctorState {
+        DoctorState {
+            checks: vec![
+                DoctorCheck::ok("Platform", "linux x64"),
+                DoctorCheck::warning("Symlinks", "Broken links: infc"),
+                DoctorCheck::error("inf-llc", "Not found"),
+                DoctorCheck::warning("Executable permissions", "Not executable: infc"),
+            ],
+            loaded: true,
+            ..DoctorState::default()
+        }
+    }
+
+    fn fix_request(state: &DoctorState) -> FixRequest {
+        FixRequest {
+            indices: state.fixable_indices(),
+            changes: vec![String::from("Recreate links")],
+        }
+    }
+
+    #[test]
+    fn doctor_state_finds_fixable_checks() {
+        let state = fixable_doctor_state();
+        assert_eq!(state.fix_action(0), None);
+        assert_eq!(state.fix_action(1), Some(FixAction::Symlinks));
+        assert_eq!(state.fix_action(2), None);
+        assert_eq!(state.fixable_indices(), [1, 3]);
+    }
+
+    #[test]
+    fn doctor_state_fix_success_updates_check_in_place() {
+        let mut state = fixable_doctor_state();
+        state.fix_request = Some(FixRequest {
+            indices: vec![1],
+            changes: vec![String::from("Recreate links")],
+        });
+        assert_eq!(state.start_fixes(), [(1, FixAction::Symlinks)]);
+        assert!(state.fix_request.is_none());
+        assert!(state.is_fixing());
+
+        state.apply_fix_progress(FixProgress::Started { index: 1 });
+        assert_eq!(state.fixing.as_ref().and_then(|q| q.current), Some(1));
+        state.apply_fix_progress(FixProgress::Finished {
+            index: 1,
+            result: Ok(FixReport {
+                check: DoctorCheck::ok("Symlinks", "No broken links"),
+                outcomes: vec![FixOutcome::Fixed {
+                    name: String::from("Symlinks"),
+                    change: String::from("Linked infc to toolchain 0.1.0"),
+                }],
+            }),
+        });
+
+        assert!(!state.is_fixing());
+        assert_eq!(state.checks[1].status, DoctorCheckStatus::Ok);
+        assert_eq!(state.checks[1].message, "No broken links");
+        assert_eq!(state.details[&1], "Linked infc to toolchain 0.1.0");
+        assert_eq!(state.warning_count(), 1);
+    }
+
+    #[test]
+    fn doctor_state_failure_mid_queue_marks_check_failed_and_continues() {
+        let mut state = fixable_doctor_state();
+        state.fix_request = Some(fix_request(&state));
+        let queue = state.start_fixes();
+        assert_eq!(
+            queue,
+            [
+                (1, FixAction::Symlinks),
+                (3, FixAction::ExecutablePermissions)
+            ]
+        );
+
+        state.apply_fix_progress(FixProgress::Started { index: 1 });
+        state.apply_fix_progress(FixProgress::Finished {
+            index: 1,
+            result: Ok(FixReport {
+                check: DoctorCheck::warning("Symlinks", "Broken links: infc"),
+                outcomes: vec![FixOutcome::NotFixable {
+                    name: String::from("infc"),
+                    reason: String::from("infc is missing from toolchain 0.1.0"),
+                }],
+            }),
+        });
+        let queue_state = state.fixing.as_ref().expect("Second fix is still queued");
+        assert_eq!((queue_state.completed, queue_state.total), (1, 2));
+        assert_eq!(state.checks[1].status, DoctorCheckStatus::Error);
+        assert_eq!(
+            state.details[&1],
+            "infc: infc is missing from toolchain 0.1.0"
+        );
+
+        state.apply_fix_progress(FixProgress::Started { index: 3 });
+        state.apply_fix_progress(FixProgress::Finished {
+            index: 3,
+            result: Err(String::from("Permission denied")),
+        });
+        assert!(!state.is_fixing());
+        assert_eq!(state.checks[3].status, DoctorCheckStatus::Error);
+        assert_eq!(state.checks[3].message, "Not executable: infc");
+        assert_eq!(state.details[&3], "Permission denied");
+        assert_eq!(state.error_count(), 3);
+    }
+
+    #[test]
+    fn doctor_state_start_without_request_does_nothing() {
+        let mut state = fixable_doctor_state();
+        assert!(state.start_fixes().is_empty());
+        assert!(!state.is_fixing());
+    }
+
     #[test]
     fn progress_item_percentage_no_total() {
         let item = ProgressItem::new("test");
@@ -518,6 +1132,15 @@ mod tests {
         assert!((item.percentage() - 1.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn progress_item_format_entries() {
+        let mut item = ProgressItem::new("Extracting").with_unit(ProgressUnit::Entries);
+        item.current = 3;
+        assert_eq!(item.format_progress(), "3 files");
+        item.total = 10;
+        assert_eq!(item.format_progress(), "3 / 10 files");
+    }
+
     #[test]
     fn progress_item_format_bytes() {
         assert_eq!(format_bytes(500), "500 B");
@@ -548,6 +1171,17 @@ mod tests {
         assert_eq!(state.error.as_deref(), Some("Something went wrong"));
     }
 
+    #[test]
+    fn progress_item_format_eta() {
+        let mut item = ProgressItem::new("Downloading");
+        assert_eq!(item.format_eta(), "");
+        item.total = 200;
+        item.update_with_speed(80, 12);
+        assert_eq!(item.format_eta(), "ETA 10s");
+        item.complete();
+        assert_eq!(item.format_eta(), "");
+    }
+
     #[test]
     fn progress_item_format_speed_none() {
         let item = ProgressItem::new("test");
@@ -661,16 +1295,104 @@ mod tests {
     #[test]
     fn install_progress_failed_contains_error() {
         let progress = InstallProgress::Failed {
+            phase: InstallPhase::Downloading,
             error: String::from("Network error"),
+            retryable: true,
         };
         match progress {
-            InstallProgress::Failed { error } => {
+            InstallProgress::Failed { error, .. } => {
                 assert_eq!(error, "Network error");
             }
             _ => panic!("Expected Failed variant"),
         }
     }
 
+    fn run_until_failure(failing: InstallPhase) -> InstallTaskState {
+        let mut state = InstallTaskState::default();
+        for phase in InstallPhase::ALL {
+            state.apply(&InstallProgress::PhaseEntered { phase });
+            assert_eq!(state, InstallTaskState::Running(phase));
+            if phase == failing {
+                state.apply(&InstallProgress::Failed {
+                    phase,
+                    error: format!("{} failed", phase.label()),
+                    retryable: phase.is_retryable(),
+                });
+                return state;
+            }
+        }
+        unreachable!("failing phase is always reached");
+    }
+
+    #[test]
+    fn install_task_state_default_is_idle() {
+        let state = InstallTaskState::default();
+        assert_eq!(state, InstallTaskState::Idle);
+        assert!(state.retry_phase().is_none());
+        assert!(!state.is_phase_done(InstallPhase::FetchingManifest));
+    }
+
+    #[test]
+    fn install_task_state_runs_to_done() {
+        let mut state = InstallTaskState::default();
+        for phase in InstallPhase::ALL {
+            state.apply(&InstallProgress::PhaseEntered { phase });
+        }
+        state.apply(&InstallProgress::Completed {
+            version: String::from("0.1.0"),
+        });
+        assert_eq!(state, InstallTaskState::Done);
+        assert!(InstallPhase::ALL.iter().all(|p| state.is_phase_done(*p)));
+    }
+
+    #[test]
+    fn install_task_state_ignores_detail_messages() {
+        let mut state = InstallTaskState::Running(InstallPhase::Downloading);
+        state.apply(&InstallProgress::DownloadProgress {
+            downloaded: 10,
+            speed: 5,
+        });
+        assert_eq!(state, InstallTaskState::Running(InstallPhase::Downloading));
+    }
+
+    #[test]
+    fn install_task_state_failure_in_each_phase() {
+        for failing in InstallPhase::ALL {
+            let state = run_until_failure(failing);
+            match &state {
+                InstallTaskState::Failed { phase, error, .. } => {
+                    assert_eq!(*phase, failing);
+                    assert!(error.contains(failing.label()));
+                }
+                other => panic!("Expected Failed state, got {other:?}"),
+            }
+            for phase in InstallPhase::ALL {
+                assert_eq!(state.is_phase_done(phase), phase < failing);
+            }
+        }
+    }
+
+    #[test]
+    fn install_task_state_retries_from_failed_phase() {
+        for failing in InstallPhase::ALL {
+            let state = run_until_failure(failing);
+            if failing == InstallPhase::Verifying {
+                assert!(state.retry_phase().is_none());
+                assert!(state.remediation().is_some());
+            } else {
+                assert_eq!(state.retry_phase(), Some(failing));
+                assert!(state.remediation().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn install_phases_are_ordered() {
+        let mut sorted = InstallPhase::ALL;
+        sorted.sort();
+        assert_eq!(sorted, InstallPhase::ALL);
+    }
+
     #[test]
     fn install_progress_is_clone() {
         let progress = InstallProgress::DownloadProgress {
@@ -716,12 +1438,14 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.2.0".to_string(),
                     stable: true,
                     platforms: vec!["linux".to_string(), "macos".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
             ],
             selected: 0,
@@ -753,12 +1477,14 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.2.0".to_string(),
                     stable: false,
                     platforms: vec!["macos".to_string()],
                     available_for_current: false,
+                    notes: None,
                 },
             ],
             selected: 1,
@@ -783,12 +1509,14 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.2.0".to_string(),
                     stable: false,
                     platforms: vec!["macos".to_string()],
                     available_for_current: false,
+                    notes: None,
                 },
             ],
             selected: 0,
@@ -811,4 +1539,89 @@ mod tests {
         state.select_next();
         assert_eq!(state.selected, 0);
     }
+
+    fn release(version: &str, stable: bool) -> crate::toolchain::manifest::VersionEntry {
+        crate::toolchain::manifest::VersionEntry {
+            version: version.to_string(),
+            stable,
+            notes: None,
+            files: vec![],
+        }
+    }
+
+    #[test]
+    fn update_info_reports_newer_stable_releases() {
+        let manifest = vec![
+            release("0.2.0", true),
+            release("0.3.0", true),
+            release("0.4.0-rc.1", false),
+        ];
+
+        let update = UpdateInfo::from_manifest(&manifest, Some("0.2.0"), "0.1.0");
+        assert_eq!(update.toolchain.as_deref(), Some("0.3.0"));
+        assert_eq!(update.infs.as_deref(), Some("0.3.0"));
+
+        let update = UpdateInfo::from_manifest(&manifest, Some("0.3.0"), "0.3.0");
+        assert!(update.is_empty());
+
+        // Newer installs and unparseable versions never trigger the banner.
+        let update = UpdateInfo::from_manifest(&manifest, Some("0.4.0"), "dev");
+        assert!(update.is_empty());
+    }
+
+    #[test]
+    fn update_info_needs_a_default_toolchain() {
+        let manifest = vec![release("0.3.0", true)];
+        let update = UpdateInfo::from_manifest(&manifest, None, "0.3.0");
+        assert!(update.is_empty());
+        assert!(UpdateInfo::from_manifest(&Vec::new(), Some("0.1.0"), "0.1.0").is_empty());
+    }
+
+    #[test]
+    fn update_banner_lifecycle() {
+        let mut banner = UpdateBannerState::default();
+        assert!(!banner.is_visible());
+        assert!(banner.message().is_none());
+
+        banner.apply(UpdateInfo {
+            toolchain: Some("0.3.0".to_string()),
+            infs: None,
+        });
+        assert!(banner.is_visible());
+        assert_eq!(banner.toolchain_update(), Some("0.3.0"));
+        let message = banner.message().expect("Should show banner");
+        assert!(message.contains("Toolchain 0.3.0 available"));
+        assert!(message.contains("press U to update"));
+
+        banner.dismiss();
+        assert!(!banner.is_visible());
+        assert!(banner.toolchain_update().is_none());
+
+        // A later refresh does not bring a dismissed banner back.
+        banner.apply(UpdateInfo {
+            toolchain: Some("0.4.0".to_string()),
+            infs: None,
+        });
+        assert!(banner.message().is_none());
+    }
+
+    #[test]
+    fn update_banner_for_infs_only_and_disabled() {
+        let update = UpdateInfo {
+            toolchain: None,
+            infs: Some("0.5.0".to_string()),
+        };
+        let mut banner = UpdateBannerState::default();
+        banner.apply(update.clone());
+        let message = banner.message().expect("Should show banner");
+        assert!(message.contains("infs 0.5.0 available"));
+        assert!(banner.toolchain_update().is_none());
+
+        let mut disabled = UpdateBannerState {
+            enabled: false,
+            ..UpdateBannerState::default()
+        };
+        disabled.apply(update);
+        assert!(!disabled.is_visible());
+    }
 }
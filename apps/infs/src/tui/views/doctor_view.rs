@@ -1,7 +1,9 @@
 //! Doctor view rendering for the TUI.
 //!
 //! This module contains the rendering logic for the doctor check results screen,
-//! showing the status of each health check.
+//! showing the status of each health check. While fixes run, the help bar
+//! shows a spinner with the fix count; the result of a fix is shown below
+//! the message of its check.
 
 use ratatui::{
     Frame,
@@ -11,9 +13,15 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::tui::state::{DoctorCheckStatus, DoctorState};
+use crate::tui::state::{DoctorCheckStatus, DoctorState, FixQueueState};
 use crate::tui::theme::Theme;
 
+/// Spinner frames shown while fixes run.
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// How long each spinner frame is shown, in milliseconds.
+const SPINNER_FRAME_MS: u128 = 100;
+
 /// Renders the doctor view.
 pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &DoctorState) {
     let chunks = Layout::vertical([
@@ -25,7 +33,10 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &DoctorState)
 
     render_summary(frame, chunks[0], theme, state);
     render_check_list(frame, chunks[1], theme, state);
-    render_help(frame, chunks[2], theme);
+    match &state.fixing {
+        Some(queue) => render_fix_progress(frame, chunks[2], theme, state, queue),
+        None => render_help(frame, chunks[2], theme),
+    }
 }
 
 /// Renders the summary bar showing counts.
@@ -110,6 +121,18 @@ fn render_check_list(frame: &mut Frame, area: Rect, theme: &Theme, state: &Docto
                 Span::styled(&check.message, message_style),
             ]));
 
+            if let Some(detail) = state.details.get(&idx) {
+                let detail_color = if check.status == DoctorCheckStatus::Error {
+                    theme.error
+                } else {
+                    theme.success
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("         "),
+                    Span::styled(detail, Style::default().fg(detail_color)),
+                ]));
+            }
+
             lines.push(Line::from(""));
         }
     }
@@ -135,6 +158,12 @@ fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
         Span::raw("  "),
         Span::styled("[r] ", Style::default().fg(theme.highlight)),
         Span::styled("Refresh", Style::default().fg(theme.muted)),
+        Span::raw("  "),
+        Span::styled("[f] ", Style::default().fg(theme.highlight)),
+        Span::styled("Fix", Style::default().fg(theme.muted)),
+        Span::raw("  "),
+        Span::styled("[F] ", Style::default().fg(theme.highlight)),
+        Span::styled("Fix all", Style::default().fg(theme.muted)),
     ]);
 
     let help = Paragraph::new(help_text).block(
@@ -146,6 +175,42 @@ fn render_help(frame: &mut Frame, area: Rect, theme: &Theme) {
     frame.render_widget(help, area);
 }
 
+/// Renders the spinner and fix count in place of the help text.
+fn render_fix_progress(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    state: &DoctorState,
+    queue: &FixQueueState,
+) {
+    let elapsed = queue.started.elapsed().as_millis() / SPINNER_FRAME_MS;
+    let spinner = SPINNER_FRAMES[usize::try_from(elapsed).unwrap_or(0) % SPINNER_FRAMES.len()];
+    let name = queue
+        .current
+        .and_then(|index| state.checks.get(index))
+        .map_or("", |check| check.name.as_str());
+
+    let progress_line = Line::from(vec![
+        Span::styled(format!("{spinner} "), Style::default().fg(theme.highlight)),
+        Span::styled(
+            format!(
+                "Fixing {}/{}: {name}",
+                (queue.completed + 1).min(queue.total),
+                queue.total
+            ),
+            Style::default().fg(theme.text),
+        ),
+    ]);
+
+    let progress = Paragraph::new(progress_line).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border)),
+    );
+
+    frame.render_widget(progress, area);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,6 +248,7 @@ mod tests {
             ],
             selected: 0,
             loaded: true,
+            ..DoctorState::default()
         };
 
         terminal
@@ -204,6 +270,7 @@ mod tests {
             ],
             selected: 1,
             loaded: true,
+            ..DoctorState::default()
         };
 
         terminal
@@ -225,7 +292,38 @@ mod tests {
             ],
             selected: 2,
             loaded: true,
+            ..DoctorState::default()
+        };
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Should render");
+    }
+
+    #[test]
+    fn render_fix_progress_and_details_does_not_panic() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let mut state = DoctorState {
+            checks: vec![
+                DoctorCheck::error("Symlinks", "Broken links: infc"),
+                DoctorCheck::warning("PATH", "Not in PATH"),
+            ],
+            loaded: true,
+            ..DoctorState::default()
         };
+        state.details.insert(
+            0,
+            String::from("infc: infc is missing from toolchain 0.1.0"),
+        );
+        state.fixing = Some(FixQueueState {
+            total: 2,
+            completed: 1,
+            current: Some(1),
+            started: std::time::Instant::now(),
+        });
 
         terminal
             .draw(|frame| {
@@ -0,0 +1,172 @@
+//! Log viewer rendering for the TUI.
+//!
+//! This module contains the rendering logic for the screen showing one saved
+//! command output, with lines matching the current search highlighted.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::tui::log_viewer::LogViewerState;
+use crate::tui::theme::Theme;
+
+/// Renders the log viewer.
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &LogViewerState) {
+    let chunks = Layout::vertical([
+        Constraint::Min(6),    // Log contents
+        Constraint::Length(3), // Search or help text
+    ])
+    .split(area);
+
+    render_contents(frame, chunks[0], theme, state);
+    render_footer(frame, chunks[1], theme, state);
+}
+
+/// Renders the visible part of the log.
+fn render_contents(frame: &mut Frame, area: Rect, theme: &Theme, state: &LogViewerState) {
+    let total = state.lines().len();
+    let position = if total == 0 {
+        String::from("empty")
+    } else {
+        let more = if state.is_complete() { "" } else { "+" };
+        format!("line {} of {total}{more}", state.scroll + 1)
+    };
+    let block = Block::default()
+        .title(format!(" {} ", state.title))
+        .title_bottom(Line::from(format!(" {position} ")).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border));
+
+    let height = usize::from(area.height.saturating_sub(2));
+    let lines: Vec<Line> = state
+        .lines()
+        .iter()
+        .skip(state.scroll)
+        .take(height)
+        .map(|line| {
+            if state.is_match(line) {
+                Line::styled(
+                    line.as_str(),
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Line::styled(line.as_str(), Style::default().fg(theme.text))
+            }
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Renders the search prompt, the last search result, or the key help.
+fn render_footer(frame: &mut Frame, area: Rect, theme: &Theme, state: &LogViewerState) {
+    let line = if let Some(input) = &state.search_input {
+        Line::from(vec![
+            Span::styled("/", Style::default().fg(theme.highlight)),
+            Span::styled(input.as_str(), Style::default().fg(theme.text)),
+        ])
+    } else if let Some(message) = &state.message {
+        Line::from(Span::styled(
+            message.as_str(),
+            Style::default().fg(theme.warning),
+        ))
+    } else {
+        Line::from(vec![
+            Span::styled("[Esc] ", Style::default().fg(theme.highlight)),
+            Span::styled("Back", Style::default().fg(theme.muted)),
+            Span::raw("  "),
+            Span::styled("[Up/Down/PgUp/PgDn] ", Style::default().fg(theme.highlight)),
+            Span::styled("Scroll", Style::default().fg(theme.muted)),
+            Span::raw("  "),
+            Span::styled("[g/G] ", Style::default().fg(theme.highlight)),
+            Span::styled("Top/Bottom", Style::default().fg(theme.muted)),
+            Span::raw("  "),
+            Span::styled("[/] ", Style::default().fg(theme.highlight)),
+            Span::styled("Search", Style::default().fg(theme.muted)),
+            Span::raw("  "),
+            Span::styled("[n/N] ", Style::default().fg(theme.highlight)),
+            Span::styled("Next/Prev", Style::default().fg(theme.muted)),
+        ])
+    };
+
+    let footer = Paragraph::new(line).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border)),
+    );
+
+    frame.render_widget(footer, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use std::io::Cursor;
+
+    fn create_test_terminal() -> Terminal<TestBackend> {
+        let backend = TestBackend::new(80, 24);
+        Terminal::new(backend).expect("Should create terminal")
+    }
+
+    fn screen_text(terminal: &Terminal<TestBackend>) -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    }
+
+    fn draw(state: &LogViewerState) -> String {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, state);
+            })
+            .expect("Should render");
+        screen_text(&terminal)
+    }
+
+    #[test]
+    fn render_empty_does_not_panic() {
+        let state = LogViewerState::from_reader("build", Cursor::new(""));
+        let text = draw(&state);
+        assert!(text.contains("empty"), "{text}");
+    }
+
+    #[test]
+    fn render_shows_lines_from_scroll_position() {
+        let content = (0..100)
+            .map(|i| format!("output line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut state = LogViewerState::from_reader("run", Cursor::new(content));
+        state.scroll_down(40);
+
+        let text = draw(&state);
+        assert!(text.contains("output line 40"), "{text}");
+        assert!(!text.contains("output line 39"), "{text}");
+        assert!(text.contains("line 41 of 100"), "{text}");
+    }
+
+    #[test]
+    fn render_shows_search_prompt_and_result() {
+        let mut state = LogViewerState::from_reader("build", Cursor::new("a\nb\n"));
+        state.start_search();
+        state.push_search_char('z');
+        assert!(draw(&state).contains("/z"));
+
+        state.submit_search();
+        assert!(draw(&state).contains("Pattern not found: z"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Logs view rendering for the TUI.
+//!
+//! This module contains the rendering logic for the logs screen, listing the
+//! saved outputs of commands run from the TUI, most recent first.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::tui::command_log::LogEntry;
+use crate::tui::state::LogsState;
+use crate::tui::theme::Theme;
+
+/// Renders the logs view.
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &LogsState) {
+    let chunks = Layout::vertical([
+        Constraint::Min(6),    // Log list
+        Constraint::Length(3), // Help text
+    ])
+    .split(area);
+
+    render_log_list(frame, chunks[0], theme, state);
+    render_help(frame, chunks[1], theme, state.entries.is_empty());
+}
+
+/// Renders the list of saved runs.
+fn render_log_list(frame: &mut Frame, area: Rect, theme: &Theme, state: &LogsState) {
+    let block = Block::default()
+        .title(" Recent Command Output ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border));
+
+    if state.entries.is_empty() {
+        let lines = vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "  No saved output yet. Run build or run with ':' to record one.",
+                Style::default().fg(theme.muted),
+            )]),
+        ];
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+        return;
+    }
+
+    state
+        .entries
+        .render(frame, area, block, |entry, is_selected| {
+            format_log_row(theme, entry, is_selected)
+        });
+}
+
+/// Formats a single saved run.
+fn format_log_row<'a>(theme: &Theme, entry: &'a LogEntry, is_selected: bool) -> Line<'a> {
+    let prefix = if is_selected { "> " } else { "  " };
+
+    let command_style = if is_selected {
+        Style::default()
+            .fg(theme.selected)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text)
+    };
+
+    let status_color = if entry.succeeded() {
+        theme.success
+    } else {
+        theme.error
+    };
+
+    Line::from(vec![
+        Span::styled(prefix, command_style),
+        Span::styled(format!("{:<10}", entry.command), command_style),
+        Span::styled(
+            format!("{:<10}", entry.status()),
+            Style::default().fg(status_color),
+        ),
+        Span::styled(&entry.started_at, Style::default().fg(theme.muted)),
+    ])
+}
+
+/// Renders the help text at the bottom.
+fn render_help(frame: &mut Frame, area: Rect, theme: &Theme, is_empty: bool) {
+    let mut spans = vec![
+        Span::styled("[Esc] ", Style::default().fg(theme.highlight)),
+        Span::styled("Back", Style::default().fg(theme.muted)),
+        Span::raw("  "),
+        Span::styled("[r] ", Style::default().fg(theme.highlight)),
+        Span::styled("Refresh", Style::default().fg(theme.muted)),
+    ];
+    if !is_empty {
+        spans.extend([
+            Span::raw("  "),
+            Span::styled("[Up/Down] ", Style::default().fg(theme.highlight)),
+            Span::styled("Navigate", Style::default().fg(theme.muted)),
+            Span::raw("  "),
+            Span::styled("[Enter] ", Style::default().fg(theme.highlight)),
+            Span::styled("View", Style::default().fg(theme.muted)),
+        ]);
+    }
+
+    let help = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border)),
+    );
+
+    frame.render_widget(help, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::widgets::scroll_list::ScrollList;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn create_test_terminal() -> Terminal<TestBackend> {
+        let backend = TestBackend::new(80, 24);
+        Terminal::new(backend).expect("Should create terminal")
+    }
+
+    fn entry(id: u64, command: &str, exit_code: Option<i32>) -> LogEntry {
+        LogEntry {
+            id,
+            command: command.to_string(),
+            started_at: String::from("2026-01-01T12:00:00Z"),
+            exit_code,
+        }
+    }
+
+    fn screen_text(terminal: &Terminal<TestBackend>) -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    }
+
+    #[test]
+    fn render_empty_does_not_panic() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let state = LogsState::default();
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Should render");
+        assert!(screen_text(&terminal).contains("No saved output yet"));
+    }
+
+    #[test]
+    fn render_with_entries_shows_status() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let mut state = LogsState {
+            entries: ScrollList::new(vec![entry(2, "run", Some(1)), entry(1, "build", Some(0))]),
+            store: None,
+            loaded: true,
+        };
+        state.select_next();
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Should render");
+        let text = screen_text(&terminal);
+        assert!(text.contains("exit 1"), "{text}");
+        assert!(text.contains("> build"), "{text}");
+    }
+}
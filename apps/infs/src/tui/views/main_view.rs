@@ -1,7 +1,8 @@
 //! Main view rendering for the TUI.
 //!
 //! This module contains the rendering logic for the main menu screen,
-//! including the logo, menu items, command input, and status line.
+//! including the update banner, logo, menu items, command input, and
+//! status line.
 
 use ratatui::{
     Frame,
@@ -25,7 +26,17 @@ pub fn render(
     is_command_mode: bool,
     status_message: &str,
     cursor_pos: usize,
+    banner: Option<&str>,
 ) {
+    let area = match banner {
+        Some(message) => {
+            let rows = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(area);
+            render_banner(frame, rows[0], theme, message);
+            rows[1]
+        }
+        None => area,
+    };
+
     let chunks = Layout::vertical([
         Constraint::Length(8), // Logo and version
         Constraint::Min(6),    // Menu
@@ -47,6 +58,16 @@ pub fn render(
     render_status(frame, chunks[3], theme, status_message);
 }
 
+/// Renders the one-line update-available banner.
+fn render_banner(frame: &mut Frame, area: Rect, theme: &Theme, message: &str) {
+    let banner = Paragraph::new(format!(" {message}")).style(
+        Style::default()
+            .fg(theme.warning)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(banner, area);
+}
+
 /// Renders the header with colorful "I" logo and version/directory info.
 fn render_header(frame: &mut Frame, area: Rect, theme: &Theme) {
     // Split header into logo (left) and info (right)
@@ -239,7 +260,7 @@ mod tests {
 
         terminal
             .draw(|frame| {
-                render(frame, frame.area(), &theme, &menu, "", false, "", 0);
+                render(frame, frame.area(), &theme, &menu, "", false, "", 0, None);
             })
             .expect("Should render");
     }
@@ -261,6 +282,7 @@ mod tests {
                     true,
                     "Ready",
                     5,
+                    None,
                 );
             })
             .expect("Should render");
@@ -284,6 +306,7 @@ mod tests {
                     false,
                     "Status message",
                     0,
+                    None,
                 );
             })
             .expect("Should render");
@@ -307,8 +330,38 @@ mod tests {
                     true,
                     "",
                     long_command.len(),
+                    None,
                 );
             })
             .expect("Should render");
     }
+
+    #[test]
+    fn render_with_update_banner_shows_message() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let menu = Menu::new();
+
+        terminal
+            .draw(|frame| {
+                render(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    &menu,
+                    "",
+                    false,
+                    "",
+                    0,
+                    Some("Toolchain 0.3.0 available"),
+                );
+            })
+            .expect("Should render");
+
+        let buffer = terminal.backend().buffer();
+        let first_row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, 0)].symbol())
+            .collect();
+        assert!(first_row.contains("Toolchain 0.3.0 available"));
+    }
 }
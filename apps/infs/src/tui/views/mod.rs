@@ -9,8 +9,12 @@
 //! - [`doctor_view`] - Doctor check results
 //! - [`progress_view`] - Download/operation progress display
 //! - [`version_select_view`] - Version selection for installation
+//! - [`logs_view`] - Saved outputs of commands run from the TUI
+//! - [`log_viewer_view`] - Contents of one saved output
 
 pub mod doctor_view;
+pub mod log_viewer_view;
+pub mod logs_view;
 pub mod main_view;
 pub mod progress_view;
 pub mod toolchain_view;
@@ -1,17 +1,18 @@
 //! Progress view rendering for the TUI.
 //!
 //! This module contains the rendering logic for the progress screen,
-//! showing download progress and operation status.
+//! showing download progress and operation status. For installations, it
+//! also shows the pipeline phases and how to recover from a failure.
 
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph},
+    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
 };
 
-use crate::tui::state::ProgressState;
+use crate::tui::state::{InstallPhase, InstallTaskState, ProgressState};
 use crate::tui::theme::Theme;
 
 /// Renders the progress view.
@@ -106,7 +107,11 @@ fn render_items(frame: &mut Frame, area: Rect, theme: &Theme, state: &ProgressSt
             let speed_text = if item.completed {
                 Span::raw("")
             } else {
-                let speed = item.format_speed();
+                let speed = [item.format_speed(), item.format_eta()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
                 if speed.is_empty() {
                     Span::raw("")
                 } else {
@@ -124,7 +129,13 @@ fn render_items(frame: &mut Frame, area: Rect, theme: &Theme, state: &ProgressSt
         }
     }
 
-    let items_widget = Paragraph::new(lines).block(
+    if state.task != InstallTaskState::Idle {
+        lines.push(Line::from(""));
+        lines.push(phases_line(theme, &state.task));
+        lines.extend(recovery_lines(theme, &state.task));
+    }
+
+    let items_widget = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
         Block::default()
             .title(" Progress ")
             .borders(Borders::ALL)
@@ -134,6 +145,60 @@ fn render_items(frame: &mut Frame, area: Rect, theme: &Theme, state: &ProgressSt
     frame.render_widget(items_widget, area);
 }
 
+/// Builds a line listing the installation phases with their status.
+fn phases_line<'a>(theme: &Theme, task: &InstallTaskState) -> Line<'a> {
+    let mut spans = vec![Span::raw("  ")];
+
+    for (idx, phase) in InstallPhase::ALL.into_iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(" > ", Style::default().fg(theme.muted)));
+        }
+
+        let style = match task {
+            InstallTaskState::Failed { phase: failed, .. } if *failed == phase => Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+            InstallTaskState::Running(current) if *current == phase => Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+            _ if task.is_phase_done(phase) => Style::default().fg(theme.success),
+            _ => Style::default().fg(theme.muted),
+        };
+        spans.push(Span::styled(phase.label(), style));
+    }
+
+    Line::from(spans)
+}
+
+/// Builds the retry hint or remediation text for a failed installation.
+fn recovery_lines<'a>(theme: &Theme, task: &InstallTaskState) -> Vec<Line<'a>> {
+    if let Some(phase) = task.retry_phase() {
+        return vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("[r] ", Style::default().fg(theme.highlight)),
+                Span::styled(
+                    format!("Retry from {}", phase.label()),
+                    Style::default().fg(theme.text),
+                ),
+            ]),
+        ];
+    }
+
+    task.remediation()
+        .map(|text| {
+            vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(text, Style::default().fg(theme.warning)),
+                ]),
+            ]
+        })
+        .unwrap_or_default()
+}
+
 /// Renders the footer with status message and help text.
 fn render_footer(frame: &mut Frame, area: Rect, theme: &Theme, state: &ProgressState) {
     let status_text = if let Some(ref error) = state.error {
@@ -205,6 +270,51 @@ mod tests {
             .expect("Failed to draw");
     }
 
+    #[test]
+    fn render_install_phases_does_not_panic() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let mut state = ProgressState::new("Installing");
+        state.task = InstallTaskState::Running(InstallPhase::Verifying);
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Failed to draw");
+    }
+
+    #[test]
+    fn recovery_lines_offer_retry_for_retryable_failure() {
+        let theme = Theme::dark();
+        let task = InstallTaskState::Failed {
+            phase: InstallPhase::Downloading,
+            error: String::from("timeout"),
+            retryable: true,
+        };
+        let text: String = recovery_lines(&theme, &task)
+            .iter()
+            .flat_map(|line| line.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        assert!(text.contains("Retry from Download"));
+    }
+
+    #[test]
+    fn recovery_lines_show_remediation_for_checksum_failure() {
+        let theme = Theme::dark();
+        let task = InstallTaskState::Failed {
+            phase: InstallPhase::Verifying,
+            error: String::from("mismatch"),
+            retryable: false,
+        };
+        let text: String = recovery_lines(&theme, &task)
+            .iter()
+            .flat_map(|line| line.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        assert!(text.contains("checksum"));
+        assert!(!text.contains("Retry"));
+    }
+
     #[test]
     fn render_error_does_not_panic() {
         let mut terminal = create_test_terminal();
@@ -11,7 +11,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::tui::state::ToolchainsState;
+use crate::tui::state::{ToolchainInfo, ToolchainsState};
 use crate::tui::theme::Theme;
 
 /// Renders the toolchains view.
@@ -28,17 +28,34 @@ pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &ToolchainsSt
 
 /// Renders the toolchain list.
 fn render_toolchain_list(frame: &mut Frame, area: Rect, theme: &Theme, state: &ToolchainsState) {
-    let mut lines = Vec::new();
+    let block = Block::default()
+        .title(" Installed Toolchains ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border));
 
     if state.toolchains.is_empty() {
-        lines.push(Line::from(""));
-        lines.push(Line::from(vec![Span::styled(
+        render_empty(frame, area, theme, block);
+        return;
+    }
+
+    state
+        .toolchains
+        .render(frame, area, block, |toolchain, is_selected| {
+            format_toolchain_row(theme, toolchain, is_selected)
+        });
+}
+
+/// Renders the placeholder shown when no toolchains are installed.
+fn render_empty(frame: &mut Frame, area: Rect, theme: &Theme, block: Block) {
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
             "  No toolchains installed.",
             Style::default().fg(theme.muted),
-        )]));
-        lines.push(Line::from(""));
+        )]),
+        Line::from(""),
         // Show selectable "Install" option
-        lines.push(Line::from(vec![
+        Line::from(vec![
             Span::styled(
                 "> ",
                 Style::default()
@@ -55,48 +72,44 @@ fn render_toolchain_list(frame: &mut Frame, area: Rect, theme: &Theme, state: &T
                 "Install latest toolchain",
                 Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-        ]));
-    } else {
-        for (idx, toolchain) in state.toolchains.iter().enumerate() {
-            let is_selected = idx == state.selected;
+        ]),
+    ];
 
-            let prefix = if is_selected { "> " } else { "  " };
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
 
-            let version_style = if is_selected {
-                Style::default()
-                    .fg(theme.selected)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(theme.text)
-            };
-
-            let default_indicator = if toolchain.is_default {
-                Span::styled(" (default)", Style::default().fg(theme.success))
-            } else {
-                Span::raw("")
-            };
-
-            let installed_ago = toolchain.metadata.as_ref().map_or_else(String::new, |m| {
-                format!(" - installed {}", m.installed_ago())
-            });
-
-            lines.push(Line::from(vec![
-                Span::styled(prefix, version_style),
-                Span::styled(&toolchain.version, version_style),
-                default_indicator,
-                Span::styled(installed_ago, Style::default().fg(theme.muted)),
-            ]));
-        }
-    }
+/// Formats a single toolchain row.
+fn format_toolchain_row<'a>(
+    theme: &Theme,
+    toolchain: &'a ToolchainInfo,
+    is_selected: bool,
+) -> Line<'a> {
+    let prefix = if is_selected { "> " } else { "  " };
 
-    let list_widget = Paragraph::new(lines).block(
-        Block::default()
-            .title(" Installed Toolchains ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.border)),
-    );
+    let version_style = if is_selected {
+        Style::default()
+            .fg(theme.selected)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text)
+    };
+
+    let default_indicator = if toolchain.is_default {
+        Span::styled(" (default)", Style::default().fg(theme.success))
+    } else {
+        Span::raw("")
+    };
+
+    let installed_ago = toolchain.metadata.as_ref().map_or_else(String::new, |m| {
+        format!(" - installed {}", m.installed_ago())
+    });
 
-    frame.render_widget(list_widget, area);
+    Line::from(vec![
+        Span::styled(prefix, version_style),
+        Span::styled(&toolchain.version, version_style),
+        default_indicator,
+        Span::styled(installed_ago, Style::default().fg(theme.muted)),
+    ])
 }
 
 /// Renders the help text at the bottom.
@@ -137,7 +150,7 @@ fn render_help(frame: &mut Frame, area: Rect, theme: &Theme, is_empty: bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tui::state::ToolchainInfo;
+    use crate::tui::widgets::scroll_list::ScrollList;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
 
@@ -164,7 +177,7 @@ mod tests {
         let mut terminal = create_test_terminal();
         let theme = Theme::dark();
         let state = ToolchainsState {
-            toolchains: vec![
+            toolchains: ScrollList::new(vec![
                 ToolchainInfo {
                     version: "0.2.0".to_string(),
                     is_default: true,
@@ -175,8 +188,7 @@ mod tests {
                     is_default: false,
                     metadata: None,
                 },
-            ],
-            selected: 0,
+            ]),
             loaded: true,
         };
 
@@ -191,8 +203,8 @@ mod tests {
     fn render_with_selection_does_not_panic() {
         let mut terminal = create_test_terminal();
         let theme = Theme::dark();
-        let state = ToolchainsState {
-            toolchains: vec![
+        let mut state = ToolchainsState {
+            toolchains: ScrollList::new(vec![
                 ToolchainInfo {
                     version: "0.2.0".to_string(),
                     is_default: true,
@@ -203,16 +215,42 @@ mod tests {
                     is_default: false,
                     metadata: None,
                 },
-            ],
-            selected: 1,
+            ]),
+            loaded: true,
+        };
+        state.toolchains.select(1);
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Should render");
+    }
+
+    #[test]
+    fn render_scrolls_to_selection() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let mut state = ToolchainsState {
+            toolchains: ScrollList::new(
+                (0..40)
+                    .map(|i| ToolchainInfo {
+                        version: format!("0.{i}.0"),
+                        is_default: i == 0,
+                        metadata: None,
+                    })
+                    .collect(),
+            ),
             loaded: true,
         };
+        state.toolchains.select(39);
 
         terminal
             .draw(|frame| {
                 render(frame, frame.area(), &theme, &state);
             })
             .expect("Should render");
+        assert!(state.toolchains.offset() > 0);
     }
 
     #[test]
@@ -220,12 +258,11 @@ mod tests {
         let mut terminal = create_test_terminal();
         let theme = Theme::dark();
         let state = ToolchainsState {
-            toolchains: vec![ToolchainInfo {
+            toolchains: ScrollList::new(vec![ToolchainInfo {
                 version: "0.1.0".to_string(),
                 is_default: true,
                 metadata: None,
-            }],
-            selected: 0,
+            }]),
             loaded: true,
         };
 
@@ -1,7 +1,8 @@
 //! Version select view rendering for the TUI.
 //!
 //! This module contains the rendering logic for the version selection screen,
-//! showing available versions with their stability and platform availability.
+//! showing available versions with their stability and platform availability,
+//! and the first lines of the selected version's release notes.
 
 use ratatui::{
     Frame,
@@ -11,19 +12,26 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::toolchain::release_notes::EXCERPT_LINES;
 use crate::tui::state::VersionSelectState;
 use crate::tui::theme::Theme;
 
+/// Height of the release notes pane: the excerpt, its `...` line, and borders.
+#[allow(clippy::cast_possible_truncation)]
+const NOTES_HEIGHT: u16 = EXCERPT_LINES as u16 + 3;
+
 /// Renders the version select view.
 pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &VersionSelectState) {
     let chunks = Layout::vertical([
-        Constraint::Min(6),    // Version list
-        Constraint::Length(3), // Help text
+        Constraint::Min(6),               // Version list
+        Constraint::Length(NOTES_HEIGHT), // Release notes
+        Constraint::Length(3),            // Help text
     ])
     .split(area);
 
     render_version_list(frame, chunks[0], theme, state);
-    render_help(frame, chunks[1], theme, state);
+    render_notes(frame, chunks[1], theme, state);
+    render_help(frame, chunks[2], theme, state);
 }
 
 /// Renders the version list.
@@ -111,6 +119,40 @@ fn render_version_list(frame: &mut Frame, area: Rect, theme: &Theme, state: &Ver
     frame.render_widget(list_widget, area);
 }
 
+/// Renders the release notes of the selected version.
+fn render_notes(frame: &mut Frame, area: Rect, theme: &Theme, state: &VersionSelectState) {
+    let mut lines = Vec::new();
+    if let Some(version) = state.selected_version() {
+        match &version.notes {
+            Some(notes) => {
+                for line in notes.lines() {
+                    lines.push(Line::from(vec![Span::styled(
+                        format!("  {line}"),
+                        Style::default().fg(theme.text),
+                    )]));
+                }
+            }
+            None => lines.push(Line::from(vec![Span::styled(
+                "  No release notes.",
+                Style::default().fg(theme.muted),
+            )])),
+        }
+    }
+
+    let title = state.selected_version().map_or_else(
+        || " Release Notes ".to_string(),
+        |v| format!(" Release Notes ({}) ", v.version),
+    );
+    let notes_widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border)),
+    );
+
+    frame.render_widget(notes_widget, area);
+}
+
 /// Renders the help text at the bottom.
 fn render_help(frame: &mut Frame, area: Rect, theme: &Theme, state: &VersionSelectState) {
     let help_text = if state.loading || state.error.is_some() || state.versions.is_empty() {
@@ -212,18 +254,21 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string(), "macos".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.1.0".to_string(),
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.3.0-alpha".to_string(),
                     stable: false,
                     platforms: vec!["macos".to_string()],
                     available_for_current: false,
+                    notes: None,
                 },
             ],
             selected: 0,
@@ -240,6 +285,39 @@ mod tests {
             .expect("Should render");
     }
 
+    #[test]
+    fn render_shows_selected_release_notes() {
+        let mut terminal = create_test_terminal();
+        let theme = Theme::dark();
+        let state = VersionSelectState {
+            versions: vec![VersionSelectInfo {
+                version: "0.2.0".to_string(),
+                stable: true,
+                platforms: vec!["linux".to_string()],
+                available_for_current: true,
+                notes: Some("Faster builds\n- New fmt command".to_string()),
+            }],
+            loaded: true,
+            current_os: "linux".to_string(),
+            ..Default::default()
+        };
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, &state);
+            })
+            .expect("Should render");
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(content.contains("Release Notes (0.2.0)"));
+        assert!(content.contains("- New fmt command"));
+    }
+
     #[test]
     fn render_with_selection_does_not_panic() {
         let mut terminal = create_test_terminal();
@@ -251,12 +329,14 @@ mod tests {
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
                 VersionSelectInfo {
                     version: "0.1.0".to_string(),
                     stable: true,
                     platforms: vec!["linux".to_string()],
                     available_for_current: true,
+                    notes: None,
                 },
             ],
             selected: 1,
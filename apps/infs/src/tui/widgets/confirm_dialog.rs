@@ -0,0 +1,170 @@
+//! Modal confirmation dialog widget.
+//!
+//! This module renders a small centered dialog over the current screen,
+//! asking the user to confirm or dismiss an action with `y`/`n`.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::theme::Theme;
+
+/// Dialog height including borders.
+const DIALOG_HEIGHT: u16 = 6;
+
+/// Preferred dialog width including borders.
+const DIALOG_WIDTH: u16 = 50;
+
+/// Renders a confirmation dialog centered within `area`.
+///
+/// The area under the dialog is cleared first so the underlying screen
+/// does not bleed through.
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, title: &str, message: &str) {
+    render_with_details(frame, area, theme, title, message, &[]);
+}
+
+/// Renders a confirmation dialog that lists `details` below the message,
+/// such as the changes an action will make.
+///
+/// The dialog grows to fit the details, up to the size of `area`.
+pub fn render_with_details(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    message: &str,
+    details: &[String],
+) {
+    let widest = details
+        .iter()
+        .map(|detail| detail.chars().count() + 4)
+        .max()
+        .unwrap_or(0);
+    let width = u16::try_from(widest).unwrap_or(u16::MAX).max(DIALOG_WIDTH);
+    let height = DIALOG_HEIGHT
+        .saturating_add(u16::try_from(details.len()).unwrap_or(u16::MAX))
+        .saturating_add(u16::from(!details.is_empty()));
+    let dialog_area = centered_rect(area, width, height);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            message,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if !details.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(details.iter().map(|detail| {
+            Line::from(Span::styled(
+                detail.as_str(),
+                Style::default().fg(theme.muted),
+            ))
+        }));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y] ", Style::default().fg(theme.highlight)),
+            Span::styled("Yes   ", Style::default().fg(theme.text)),
+            Span::styled("[n] ", Style::default().fg(theme.highlight)),
+            Span::styled("No", Style::default().fg(theme.text)),
+        ]),
+    ]);
+
+    let dialog = Paragraph::new(lines).alignment(Alignment::Center).block(
+        Block::default()
+            .title(format!(" {title} "))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning)),
+    );
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(dialog, dialog_area);
+}
+
+/// Returns a rectangle of at most `width` x `height` centered within `area`.
+pub(crate) fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    let vertical = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .split(vertical[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn render_confirm_dialog_does_not_panic() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).expect("Should create terminal");
+        let theme = Theme::dark();
+
+        terminal
+            .draw(|frame| {
+                render(frame, frame.area(), &theme, "Quit", "Quit anyway?");
+            })
+            .expect("Should render");
+    }
+
+    #[test]
+    fn render_with_details_does_not_panic() {
+        let backend = TestBackend::new(40, 8);
+        let mut terminal = Terminal::new(backend).expect("Should create terminal");
+        let theme = Theme::dark();
+        let details = vec![
+            String::from("Recreate missing or broken links in /home/user/.inference/bin"),
+            String::from("Make infc executable in toolchain 0.1.0"),
+        ];
+
+        terminal
+            .draw(|frame| {
+                render_with_details(
+                    frame,
+                    frame.area(),
+                    &theme,
+                    "Fix",
+                    "Apply 2 fixes?",
+                    &details,
+                );
+            })
+            .expect("Should render");
+    }
+
+    #[test]
+    fn centered_rect_fits_small_area() {
+        let area = Rect::new(0, 0, 20, 4);
+        let rect = centered_rect(area, DIALOG_WIDTH, DIALOG_HEIGHT);
+        assert!(rect.width <= area.width);
+        assert!(rect.height <= area.height);
+    }
+
+    #[test]
+    fn centered_rect_is_centered() {
+        let area = Rect::new(0, 0, 80, 24);
+        let rect = centered_rect(area, 50, 6);
+        assert_eq!(rect.width, 50);
+        assert_eq!(rect.height, 6);
+        assert_eq!(rect.x, 15);
+        assert_eq!(rect.y, 9);
+    }
+}
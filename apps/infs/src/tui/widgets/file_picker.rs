@@ -0,0 +1,378 @@
+//! Modal file picker widget.
+//!
+//! This module lets the user choose the `.inf` file a command such as
+//! `build` or `run` operates on. [`find_source_files`] lists the candidates
+//! under the project directory, honoring `.gitignore` and skipping hidden
+//! directories, and gives up after [`MAX_DEPTH`] levels or [`MAX_ENTRIES`]
+//! entries so that huge trees do not freeze the TUI. [`FilePickerState`]
+//! filters them as the user types, using the [`fuzzy`] scorer, and
+//! [`render`] draws the picker centered over the current screen.
+//!
+//! [`fuzzy`]: crate::tui::fuzzy
+
+use std::path::{Path, PathBuf};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::tui::fuzzy;
+use crate::tui::theme::Theme;
+use crate::tui::widgets::confirm_dialog::centered_rect;
+use crate::tui::widgets::scroll_list::ScrollList;
+
+/// Deepest directory level searched below the project directory.
+pub const MAX_DEPTH: usize = 8;
+
+/// Number of directory entries visited before the search stops.
+pub const MAX_ENTRIES: usize = 5_000;
+
+/// Extension of Inference source files.
+const SOURCE_EXTENSION: &str = "inf";
+
+/// Picker height including borders.
+const PICKER_HEIGHT: u16 = 16;
+
+/// Preferred picker width including borders.
+const PICKER_WIDTH: u16 = 70;
+
+/// Source files found under a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFiles {
+    /// Paths relative to the searched directory, sorted.
+    pub files: Vec<PathBuf>,
+    /// Whether the search stopped at [`MAX_ENTRIES`] before visiting everything.
+    pub truncated: bool,
+}
+
+/// Lists the `.inf` files under `root`, relative to it.
+///
+/// Files and directories excluded by `.gitignore` (also outside a git
+/// repository) and hidden ones are skipped. Unreadable entries are ignored.
+#[must_use]
+pub fn find_source_files(root: &Path, max_depth: usize, max_entries: usize) -> SourceFiles {
+    let walker = ignore::WalkBuilder::new(root)
+        .max_depth(Some(max_depth))
+        .require_git(false)
+        .build();
+
+    let mut found = SourceFiles::default();
+    for (visited, entry) in walker.flatten().enumerate() {
+        if visited >= max_entries {
+            found.truncated = true;
+            break;
+        }
+        let path = entry.path();
+        if entry.file_type().is_some_and(|t| t.is_file())
+            && path.extension().is_some_and(|ext| ext == SOURCE_EXTENSION)
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            found.files.push(relative.to_path_buf());
+        }
+    }
+    found.files.sort();
+    found
+}
+
+/// What the caller should do after a key press in the picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickerAction {
+    /// Keep the picker open.
+    None,
+    /// Close the picker without choosing a file.
+    Cancel,
+    /// Close the picker and run the command on this file.
+    Pick(PathBuf),
+}
+
+/// State of an open file picker.
+#[derive(Debug, Clone)]
+pub struct FilePickerState {
+    /// Command the chosen file is passed to, e.g. `build`.
+    pub command: String,
+    /// All candidate files.
+    files: Vec<PathBuf>,
+    /// Display form of `files`, used for matching.
+    labels: Vec<String>,
+    /// Whether the candidates were cut short by the search limits.
+    pub truncated: bool,
+    /// Filter typed so far.
+    pub query: String,
+    /// Indices into `files` of the candidates matching `query`, best first.
+    pub matches: ScrollList<usize>,
+}
+
+impl FilePickerState {
+    /// Creates a picker for `command` over `found`, selecting `last` (the
+    /// file previously chosen for the command) if it is still present.
+    #[must_use]
+    pub fn new(command: impl Into<String>, found: SourceFiles, last: Option<&Path>) -> Self {
+        let labels = found
+            .files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        let mut picker = Self {
+            command: command.into(),
+            files: found.files,
+            labels,
+            truncated: found.truncated,
+            query: String::new(),
+            matches: ScrollList::default(),
+        };
+        picker.refilter();
+        if let Some(file) = last.and_then(|last| picker.files.iter().position(|f| f == last))
+            && let Some(index) = picker.matches.items().iter().position(|&m| m == file)
+        {
+            picker.matches.select(index);
+        }
+        picker
+    }
+
+    /// Returns the display label of the file at `index`.
+    #[must_use]
+    pub fn label(&self, index: usize) -> &str {
+        &self.labels[index]
+    }
+
+    /// Returns the selected file, if any file matches the query.
+    #[must_use]
+    pub fn selected(&self) -> Option<&Path> {
+        self.matches
+            .selected_item()
+            .map(|&index| self.files[index].as_path())
+    }
+
+    /// Updates the picker for a key press.
+    pub fn handle_key(&mut self, code: KeyCode) -> PickerAction {
+        match code {
+            KeyCode::Esc => PickerAction::Cancel,
+            KeyCode::Enter => self.selected().map_or(PickerAction::None, |path| {
+                PickerAction::Pick(path.to_path_buf())
+            }),
+            KeyCode::Up => {
+                self.matches.select_previous();
+                PickerAction::None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.matches.select_next();
+                PickerAction::None
+            }
+            KeyCode::Backspace => {
+                if self.query.pop().is_some() {
+                    self.refilter();
+                }
+                PickerAction::None
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refilter();
+                PickerAction::None
+            }
+            _ => PickerAction::None,
+        }
+    }
+
+    /// Recomputes the matches for the current query, selecting the best one.
+    fn refilter(&mut self) {
+        self.matches
+            .set_items(fuzzy::filter(&self.query, &self.labels));
+    }
+}
+
+/// Renders the picker centered within `area`.
+pub fn render(frame: &mut Frame, area: Rect, theme: &Theme, state: &FilePickerState) {
+    let picker_area = centered_rect(area, PICKER_WIDTH, PICKER_HEIGHT);
+    frame.render_widget(Clear, picker_area);
+
+    let block = Block::default()
+        .title(format!(" {}: choose a file ", state.command))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight));
+    let inner = block.inner(picker_area);
+    frame.render_widget(block, picker_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Query
+        Constraint::Min(1),    // Matches
+        Constraint::Length(1), // Help text
+    ])
+    .split(inner);
+
+    let query = Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.highlight)),
+        Span::styled(&state.query, Style::default().fg(theme.text)),
+        Span::styled("_", Style::default().fg(theme.muted)),
+    ]);
+    frame.render_widget(Paragraph::new(query), chunks[0]);
+
+    if state.matches.is_empty() {
+        let message = Line::from(Span::styled(
+            "  No matching files",
+            Style::default().fg(theme.muted),
+        ));
+        frame.render_widget(Paragraph::new(message), chunks[1]);
+    } else {
+        state
+            .matches
+            .render(frame, chunks[1], Block::default(), |&index, is_selected| {
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.selected)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let prefix = if is_selected { "> " } else { "  " };
+                Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(state.label(index).to_string(), style),
+                ])
+            });
+    }
+
+    let mut help = vec![
+        Span::styled("[Enter] ", Style::default().fg(theme.highlight)),
+        Span::styled("Choose", Style::default().fg(theme.muted)),
+        Span::raw("  "),
+        Span::styled("[Esc] ", Style::default().fg(theme.highlight)),
+        Span::styled("Cancel", Style::default().fg(theme.muted)),
+    ];
+    if state.truncated {
+        help.extend([
+            Span::raw("  "),
+            Span::styled("search stopped early", Style::default().fg(theme.warning)),
+        ]);
+    }
+    frame.render_widget(Paragraph::new(Line::from(help)), chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_test_dir;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = temp_test_dir(&format!("picker_{name}"));
+        for sub in ["src/nested", "out", ".hidden", "vendor"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        for file in [
+            "src/main.inf",
+            "src/nested/lib.inf",
+            "src/notes.txt",
+            "out/generated.inf",
+            ".hidden/secret.inf",
+            "vendor/dep.inf",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        std::fs::write(dir.join(".gitignore"), "out/\n").unwrap();
+        dir
+    }
+
+    fn picker(files: &[&str], last: Option<&str>) -> FilePickerState {
+        let found = SourceFiles {
+            files: files.iter().map(PathBuf::from).collect(),
+            truncated: false,
+        };
+        FilePickerState::new("build", found, last.map(Path::new))
+    }
+
+    #[test]
+    fn finds_source_files_honoring_gitignore() {
+        let dir = temp_project("find");
+        let found = find_source_files(&dir, MAX_DEPTH, MAX_ENTRIES);
+        assert_eq!(
+            found.files,
+            [
+                PathBuf::from("src/main.inf"),
+                PathBuf::from("src/nested/lib.inf"),
+                PathBuf::from("vendor/dep.inf"),
+            ]
+        );
+        assert!(!found.truncated);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn search_limits_cap_depth_and_entries() {
+        let dir = temp_project("limits");
+        let shallow = find_source_files(&dir, 2, MAX_ENTRIES);
+        assert!(!shallow.files.contains(&PathBuf::from("src/nested/lib.inf")));
+
+        let capped = find_source_files(&dir, MAX_DEPTH, 2);
+        assert!(capped.truncated);
+        assert!(capped.files.len() < 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn typing_filters_and_backspace_restores() {
+        let mut state = picker(
+            &["src/lib.inf", "src/main.inf", "tests/main_test.inf"],
+            None,
+        );
+        assert_eq!(state.matches.len(), 3);
+
+        for c in "main".chars() {
+            assert_eq!(state.handle_key(KeyCode::Char(c)), PickerAction::None);
+        }
+        assert_eq!(state.matches.len(), 2);
+        assert_eq!(state.selected(), Some(Path::new("src/main.inf")));
+
+        state.handle_key(KeyCode::Char('z'));
+        assert!(state.matches.is_empty());
+        assert_eq!(state.handle_key(KeyCode::Enter), PickerAction::None);
+
+        for _ in 0..5 {
+            state.handle_key(KeyCode::Backspace);
+        }
+        assert_eq!(state.query, "");
+        assert_eq!(state.matches.len(), 3);
+    }
+
+    #[test]
+    fn enter_picks_selection_and_esc_cancels() {
+        let mut state = picker(&["a.inf", "b.inf"], None);
+        state.handle_key(KeyCode::Down);
+        assert_eq!(
+            state.handle_key(KeyCode::Enter),
+            PickerAction::Pick(PathBuf::from("b.inf"))
+        );
+        assert_eq!(state.handle_key(KeyCode::Esc), PickerAction::Cancel);
+    }
+
+    #[test]
+    fn last_choice_is_preselected() {
+        let state = picker(&["a.inf", "b.inf", "c.inf"], Some("c.inf"));
+        assert_eq!(state.selected(), Some(Path::new("c.inf")));
+
+        let state = picker(&["a.inf"], Some("gone.inf"));
+        assert_eq!(state.selected(), Some(Path::new("a.inf")));
+    }
+
+    #[test]
+    fn render_file_picker_does_not_panic() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).expect("Should create terminal");
+        let theme = Theme::dark();
+        let mut state = picker(&["src/main.inf"], None);
+        state.truncated = true;
+
+        terminal
+            .draw(|frame| render(frame, frame.area(), &theme, &state))
+            .expect("Should render");
+        state.handle_key(KeyCode::Char('x'));
+        terminal
+            .draw(|frame| render(frame, frame.area(), &theme, &state))
+            .expect("Should render");
+    }
+}
@@ -4,5 +4,11 @@
 //!
 //! - [`logo`] - Styled logo rendering with theme support
 //! - [`input_field`] - Advanced input field with cursor support
+//! - [`confirm_dialog`] - Modal yes/no confirmation dialog
+//! - [`file_picker`] - Modal picker for the `.inf` file a command runs on
+//! - [`scroll_list`] - Scrollable list with selection
 
 pub mod command_history;
+pub mod confirm_dialog;
+pub mod file_picker;
+pub mod scroll_list;
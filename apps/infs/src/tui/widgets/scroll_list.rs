@@ -0,0 +1,300 @@
+//! Scrollable list widget with selection.
+//!
+//! This module provides [`ScrollList`], a generic list that owns its items,
+//! the selected index, and the scroll offset. Views supply a closure that
+//! formats each row, and the widget takes care of keeping the selected row
+//! visible within the available height.
+
+use std::cell::Cell;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+
+/// A list of items with a selection cursor and a scroll offset.
+///
+/// By default, moving past either end of the list clamps the selection.
+/// Use [`ScrollList::with_wrap`] to wrap around instead.
+#[derive(Debug, Clone)]
+pub struct ScrollList<T> {
+    /// Items in display order.
+    items: Vec<T>,
+    /// Currently selected index.
+    selected: usize,
+    /// Index of the first visible item.
+    ///
+    /// Stored in a `Cell` so rendering through a shared reference can
+    /// adjust it to keep the selection visible.
+    offset: Cell<usize>,
+    /// Whether selection wraps around at the list boundaries.
+    wrap: bool,
+}
+
+impl<T> Default for ScrollList<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+#[allow(dead_code)]
+impl<T> ScrollList<T> {
+    /// Creates a list with the first item selected.
+    #[must_use]
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            selected: 0,
+            offset: Cell::new(0),
+            wrap: false,
+        }
+    }
+
+    /// Sets whether selection wraps around at the list boundaries.
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Replaces the items, resetting selection and scroll offset.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.selected = 0;
+        self.offset.set(0);
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.set_items(Vec::new());
+    }
+
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the items in display order.
+    #[must_use]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns `true` if the list has no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the currently selected index.
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the currently selected item, if any.
+    #[must_use]
+    pub fn selected_item(&self) -> Option<&T> {
+        self.items.get(self.selected)
+    }
+
+    /// Returns the current scroll offset.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Selects the item at `index`, clamping to the last item.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    /// Moves selection up.
+    pub fn select_previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        if self.selected == 0 && self.wrap {
+            self.selected = self.items.len() - 1;
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    /// Moves selection down.
+    pub fn select_next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        if self.selected + 1 >= self.items.len() && self.wrap {
+            self.selected = 0;
+        } else {
+            self.selected = (self.selected + 1).min(self.items.len() - 1);
+        }
+    }
+
+    /// Returns the items visible in a viewport of `height` rows.
+    ///
+    /// Adjusts the scroll offset only as much as needed to keep the
+    /// selected item visible, and returns the offset of the first item
+    /// in the slice alongside the slice itself.
+    pub fn visible_slice(&self, height: usize) -> (usize, &[T]) {
+        if self.items.is_empty() || height == 0 {
+            self.offset.set(0);
+            return (0, &[]);
+        }
+
+        let max_offset = self.items.len().saturating_sub(height);
+        let mut offset = self.offset.get().min(max_offset);
+        if self.selected < offset {
+            offset = self.selected;
+        } else if self.selected >= offset + height {
+            offset = self.selected + 1 - height;
+        }
+        self.offset.set(offset);
+
+        let end = (offset + height).min(self.items.len());
+        (offset, &self.items[offset..end])
+    }
+
+    /// Renders the visible rows inside `block`.
+    ///
+    /// `format_row` receives each visible item and whether it is selected.
+    pub fn render<F>(&self, frame: &mut Frame, area: Rect, block: Block, format_row: F)
+    where
+        F: for<'a> Fn(&'a T, bool) -> Line<'a>,
+    {
+        let height = block.inner(area).height as usize;
+        let (offset, visible) = self.visible_slice(height);
+
+        let lines: Vec<Line> = visible
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| format_row(item, offset + idx == self.selected))
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Borders;
+
+    #[test]
+    fn select_clamps_at_boundaries_by_default() {
+        let mut list = ScrollList::new(vec![1, 2, 3]);
+        list.select_previous();
+        assert_eq!(list.selected(), 0);
+
+        list.select_next();
+        list.select_next();
+        list.select_next();
+        assert_eq!(list.selected(), 2);
+    }
+
+    #[test]
+    fn select_wraps_when_enabled() {
+        let mut list = ScrollList::new(vec![1, 2, 3]).with_wrap(true);
+        list.select_previous();
+        assert_eq!(list.selected(), 2);
+
+        list.select_next();
+        assert_eq!(list.selected(), 0);
+    }
+
+    #[test]
+    fn empty_navigation_is_safe() {
+        let mut list: ScrollList<u8> = ScrollList::default().with_wrap(true);
+        list.select_previous();
+        list.select_next();
+        assert_eq!(list.selected(), 0);
+        assert!(list.selected_item().is_none());
+        assert_eq!(list.visible_slice(5), (0, &[][..]));
+    }
+
+    #[test]
+    fn visible_slice_scrolls_down_to_selection() {
+        let mut list = ScrollList::new((0..10).collect());
+        list.select(4);
+        let (offset, visible) = list.visible_slice(3);
+        assert_eq!(offset, 2);
+        assert_eq!(visible, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn visible_slice_keeps_offset_while_selection_visible() {
+        let mut list = ScrollList::new((0..10).collect());
+        list.select(4);
+        list.visible_slice(3);
+
+        list.select_previous();
+        let (offset, _) = list.visible_slice(3);
+        assert_eq!(offset, 2);
+    }
+
+    #[test]
+    fn visible_slice_scrolls_up_to_selection() {
+        let mut list = ScrollList::new((0..10).collect());
+        list.select(9);
+        assert_eq!(list.visible_slice(3).0, 7);
+
+        list.select(1);
+        let (offset, visible) = list.visible_slice(3);
+        assert_eq!(offset, 1);
+        assert_eq!(visible, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn visible_slice_follows_wrap_to_start_and_end() {
+        let mut list = ScrollList::new((0..10).collect()).with_wrap(true);
+        list.select_previous();
+        assert_eq!(list.visible_slice(4), (6, &[6, 7, 8, 9][..]));
+
+        list.select_next();
+        assert_eq!(list.visible_slice(4), (0, &[0, 1, 2, 3][..]));
+    }
+
+    #[test]
+    fn visible_slice_shorter_list_than_height() {
+        let list = ScrollList::new(vec![1, 2]);
+        assert_eq!(list.visible_slice(5), (0, &[1, 2][..]));
+    }
+
+    #[test]
+    fn set_items_resets_selection_and_offset() {
+        let mut list = ScrollList::new((0..10).collect());
+        list.select(9);
+        list.visible_slice(3);
+
+        list.set_items(vec![1, 2]);
+        assert_eq!(list.selected(), 0);
+        assert_eq!(list.offset(), 0);
+    }
+
+    #[test]
+    fn render_does_not_panic() {
+        let backend = TestBackend::new(40, 5);
+        let mut terminal = Terminal::new(backend).expect("Should create terminal");
+        let mut list = ScrollList::new((0..10).map(|i| i.to_string()).collect());
+        list.select(8);
+
+        terminal
+            .draw(|frame| {
+                list.render(
+                    frame,
+                    frame.area(),
+                    Block::default().borders(Borders::ALL),
+                    |item, _| Line::from(item.as_str()),
+                );
+            })
+            .expect("Should render");
+        assert_eq!(list.offset(), 6);
+    }
+}
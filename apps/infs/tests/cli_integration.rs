@@ -142,7 +142,7 @@ fn path_without_tools() -> String {
 
 /// Verifies that the build command fails gracefully when the input file doesn't exist.
 ///
-/// **Expected behavior**: Exit with non-zero code and print "Path not found" to stderr.
+/// **Expected behavior**: Exit with code 3 (file not found) and print "Path not found" to stderr.
 #[test]
 fn build_fails_when_file_missing() {
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
@@ -150,7 +150,7 @@ fn build_fails_when_file_missing() {
         .arg("this-file-does-not-exist.inf")
         .arg("--parse");
 
-    cmd.assert().failure().stderr(
+    cmd.assert().failure().code(3).stderr(
         predicate::str::contains("Path not found").or(predicate::str::contains("path not found")),
     );
 }
@@ -319,6 +319,29 @@ fn version_command_shows_version() {
         .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")));
 }
 
+/// Verifies that `version --json` prints the build details as JSON.
+///
+/// **Expected behavior**: Exit with code 0 and print an object with the
+/// commit, commit date, profile, and supported toolchain range.
+#[test]
+fn version_json_shows_build_details() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.args(["version", "--json"]);
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    for field in [
+        "commit",
+        "commit_date",
+        "profile",
+        "min_toolchain",
+        "max_toolchain",
+    ] {
+        assert!(json[field].is_string(), "missing field {field}");
+    }
+}
+
 /// Verifies that the `--version` flag displays the correct version information.
 ///
 /// **Expected behavior**: Exit with code 0 and print the version string to stdout.
@@ -383,6 +406,69 @@ fn tui_detects_infs_no_tui_environment() {
         .stdout(predicate::str::contains("--help").or(predicate::str::contains("build")));
 }
 
+// =============================================================================
+// Color Output Tests
+// =============================================================================
+
+/// Verifies that `--color=always` styles the error label even when stderr is not a TTY.
+///
+/// **Expected behavior**: The `Error:` label is wrapped in ANSI escape codes,
+/// even with `NO_COLOR` set, because the explicit flag takes priority.
+#[test]
+fn color_always_styles_error_label() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("NO_COLOR", "1")
+        .arg("--color=always")
+        .arg("build")
+        .arg("this-file-does-not-exist.inf")
+        .arg("--parse");
+
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("\x1b[1;31mError:\x1b[0m"));
+}
+
+/// Verifies that `--color=never` and `NO_COLOR` produce plain output.
+///
+/// **Expected behavior**: stderr starts with a plain `Error:` label and contains
+/// no ANSI escape codes.
+#[test]
+fn color_never_and_no_color_produce_plain_output() {
+    let mut never = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    never
+        .args(["build", "this-file-does-not-exist.inf", "--parse"])
+        .arg("--color")
+        .arg("never");
+    never
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error:").and(predicate::str::contains("\x1b[").not()));
+
+    let mut no_color = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    no_color
+        .env("NO_COLOR", "1")
+        .args(["build", "this-file-does-not-exist.inf", "--parse"]);
+    no_color
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("Error:").and(predicate::str::contains("\x1b[").not()));
+}
+
+/// Verifies that `--color` rejects values other than auto, always, and never.
+///
+/// **Expected behavior**: Exit with code 2 and list the accepted values.
+#[test]
+fn color_rejects_unknown_value() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("--color=sometimes").arg("version");
+
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("auto, always, never"));
+}
+
 // =============================================================================
 // Byte-Identical Output Tests
 // =============================================================================
@@ -528,6 +614,37 @@ fn install_without_network_shows_error() {
         .stderr(predicate::str::contains("Error").or(predicate::str::contains("error")));
 }
 
+/// Verifies that `-vv` shows debug messages that are hidden by default.
+///
+/// **Test setup**: Points `INFS_DIST_SERVER` at a closed local port.
+///
+/// **Expected behavior**: Both runs fail; only the `-vv` run logs the
+/// manifest request on stderr.
+#[test]
+fn verbose_flag_shows_debug_messages() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let install = |verbose: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+        cmd.env("INFERENCE_HOME", temp.path())
+            .env("INFS_DIST_SERVER", "http://127.0.0.1:9")
+            .env_remove("INFS_LOG")
+            .args(verbose)
+            .args(["install", "0.0.0-nonexistent"]);
+        cmd
+    };
+
+    install(&[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("debug:").not());
+    install(&["-vv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "debug: GET http://127.0.0.1:9/releases.json",
+        ));
+}
+
 // -----------------------------------------------------------------------------
 // Uninstall Command Tests
 // -----------------------------------------------------------------------------
@@ -565,6 +682,41 @@ fn uninstall_nonexistent_shows_message() {
         .stderr(predicate::str::contains("not installed"));
 }
 
+/// Verifies that a toolchain change gives up when another process holds the
+/// toolchain lock past `--lock-timeout`, while read-only commands still run.
+///
+/// **Test setup**: The test process locks `.lock` in an isolated
+/// `INFERENCE_HOME` and records its pid there, as a running infs would.
+///
+/// **Expected behavior**: `uninstall` fails naming the holder's pid and the
+/// flag; `list` succeeds.
+#[test]
+fn uninstall_times_out_while_toolchain_is_locked() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let lock = std::fs::File::create(temp.path().join(".lock")).unwrap();
+    lock.lock().unwrap();
+    std::fs::write(
+        temp.path().join(".lock"),
+        format!("{}\n", std::process::id()),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFERENCE_HOME", temp.path())
+        .args(["--lock-timeout", "0", "uninstall", "0.1.0"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "(pid {})",
+            std::process::id()
+        )))
+        .stderr(predicate::str::contains("--lock-timeout"));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFERENCE_HOME", temp.path()).arg("list");
+    cmd.assert().success();
+}
+
 // -----------------------------------------------------------------------------
 // List Command Tests
 // -----------------------------------------------------------------------------
@@ -683,6 +835,144 @@ fn versions_json_flag_is_accepted() {
     cmd.assert().failure();
 }
 
+/// Writes a manifest cache with release notes for 0.1.0 and none for 0.2.0.
+fn write_cached_manifest_with_notes(home: &std::path::Path) {
+    std::fs::create_dir_all(home.join("cache")).unwrap();
+    std::fs::write(
+        home.join("cache").join("manifest.json"),
+        r###"{"schema_version": 2, "timestamp": 0, "manifest": [
+            {"version": "0.1.0", "stable": true, "files": [],
+             "notes": "## Highlights\n\n* **Faster** parsing\n* New [fmt](https://example.com) command\n* Fixes\n* More fixes"},
+            {"version": "0.2.0", "stable": true, "files": []}
+        ]}"###,
+    )
+    .unwrap();
+}
+
+/// Verifies that `infs versions --notes` prints release note excerpts and
+/// `infs changelog` prints the full notes, both from the manifest cache.
+///
+/// **Test setup**: Writes a cached manifest to an isolated `INFERENCE_HOME`.
+///
+/// **Expected behavior**: Markdown is stripped, the excerpt is cut after
+/// three lines, and versions without notes say so.
+#[test]
+fn versions_notes_and_changelog_show_release_notes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    write_cached_manifest_with_notes(temp.path());
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", temp.path())
+        .env("INFS_DIST_SERVER", "http://localhost:1")
+        .args(["versions", "--notes", "--headless"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "      Highlights\n      - Faster parsing\n      - New fmt command\n      ...\n",
+        ))
+        .stdout(predicate::str::contains("More fixes").not());
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", temp.path())
+        .env("INFS_DIST_SERVER", "http://localhost:1")
+        .args(["changelog", "0.1.0", "--headless"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Release notes for 0.1.0:"))
+        .stdout(predicate::str::contains("- Fixes\n- More fixes\n"));
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", temp.path())
+        .env("INFS_DIST_SERVER", "http://localhost:1")
+        .args(["changelog", "--headless"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No release notes for 0.2.0."));
+}
+
+/// Verifies that `infs versions` says how old a cached manifest is, and that
+/// `--refresh` bypasses the cache.
+///
+/// **Test setup**: Writes a cached manifest and points `INFS_DIST_SERVER` at
+/// a closed port.
+///
+/// **Expected behavior**: The cached versions are listed with the age of the
+/// cache on stderr; with `--refresh` the failed fetch falls back to the cache.
+#[test]
+fn versions_reports_age_of_cached_manifest() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    write_cached_manifest_with_notes(temp.path());
+
+    for args in [&["versions"][..], &["versions", "--refresh"]] {
+        Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+            .env("INFERENCE_HOME", temp.path())
+            .env("INFS_DIST_SERVER", "http://localhost:1")
+            .args(args)
+            .arg("--headless")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("0.2.0"))
+            .stderr(
+                predicate::str::is_match(
+                    r"manifest cached \d+ days ago \(use --refresh for latest\)",
+                )
+                .unwrap(),
+            );
+    }
+}
+
+/// Verifies that a manifest cache whose contents do not match its checksum
+/// is discarded and the manifest fetched again.
+///
+/// **Test setup**: Writes a cached manifest with a wrong checksum and points
+/// `INFS_DIST_SERVER` at a closed port.
+///
+/// **Expected behavior**: `infs versions` fails with a network error instead
+/// of listing the cached versions, and the cache file is removed.
+#[test]
+fn versions_refetches_when_manifest_cache_is_tampered() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cache = temp.path().join("cache").join("manifest.json");
+    std::fs::create_dir_all(cache.parent().unwrap()).unwrap();
+    std::fs::write(
+        &cache,
+        format!(
+            r#"{{"schema_version": 2, "timestamp": 0, "checksum": "{}",
+                "manifest": [{{"version": "0.1.0", "stable": true, "files": []}}]}}"#,
+            "0".repeat(64)
+        ),
+    )
+    .unwrap();
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", temp.path())
+        .env("INFS_DIST_SERVER", "http://localhost:1")
+        .args(["versions", "--headless"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("0.1.0").not());
+
+    assert!(!cache.exists());
+}
+
+/// Verifies that `infs docs` explains how to get documentation when infs is
+/// built without the `bundled-docs` feature.
+///
+/// **Expected behavior**: Exit with non-zero code and name the feature.
+#[cfg(not(feature = "bundled-docs"))]
+#[test]
+fn docs_without_bundled_docs_points_to_feature() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", temp.path())
+        .args(["docs", "--headless"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("bundled-docs"))
+        .stderr(predicate::str::contains("https://inference-lang.org"));
+}
+
 // -----------------------------------------------------------------------------
 // Default Command Tests
 // -----------------------------------------------------------------------------
@@ -806,6 +1096,297 @@ fn doctor_shows_checking_message() {
         .stdout(predicate::str::contains("Checking Inference toolchain"));
 }
 
+/// Verifies that `infs doctor --fix` repairs broken symlinks but keeps
+/// reporting a binary that is missing from the toolchain.
+///
+/// **Test setup**: Isolated `INFERENCE_HOME` with default toolchain 0.1.0
+/// lacking `rust-lld`, and broken links for every managed binary. The bin
+/// directory is on `PATH` so the shell profile is left alone.
+///
+/// **Expected behavior**: `infc` and `inf-llc` are relinked, `rust-lld` is
+/// reported as not fixable and still fails its check.
+#[cfg(unix)]
+#[test]
+fn doctor_fix_repairs_broken_symlinks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let toolchain_bin = temp.path().join("toolchains/0.1.0/bin");
+    std::fs::create_dir_all(&toolchain_bin).unwrap();
+    for name in ["infc", "inf-llc"] {
+        std::fs::write(toolchain_bin.join(name), "#!/bin/sh\n").unwrap();
+    }
+    std::fs::write(temp.path().join("default"), "0.1.0").unwrap();
+    let bin = temp.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    for name in ["infc", "inf-llc", "rust-lld"] {
+        std::os::unix::fs::symlink(temp.path().join("gone").join(name), bin.join(name)).unwrap();
+    }
+    let path_var = std::env::join_paths(std::iter::once(bin.clone()).chain(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    )))
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFERENCE_HOME", temp.path())
+        .env("PATH", &path_var)
+        .args(["doctor", "--fix"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[FIXED] Symlinks: Linked infc, inf-llc to toolchain 0.1.0",
+        ))
+        .stdout(predicate::str::contains(
+            "[WARN] rust-lld: rust-lld is missing from toolchain 0.1.0",
+        ))
+        .stdout(predicate::str::contains("[FAIL] rust-lld"));
+    for name in ["infc", "inf-llc"] {
+        assert_eq!(
+            std::fs::read_link(bin.join(name)).unwrap(),
+            toolchain_bin.join(name)
+        );
+    }
+    assert!(!bin.join("rust-lld").exists());
+}
+
+// -----------------------------------------------------------------------------
+// Toolchain Export/Import Command Tests
+// -----------------------------------------------------------------------------
+
+/// Verifies that `infs cache clear` removes the manifest cache and reports
+/// the bytes freed, and that `--all` also removes downloaded archives.
+#[test]
+fn cache_clear_removes_manifest_cache() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let manifest = temp.path().join("cache/manifest.json");
+    let archive = temp.path().join("downloads/infc.tar.gz");
+    std::fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+    std::fs::create_dir_all(archive.parent().unwrap()).unwrap();
+    std::fs::write(&manifest, "{}").unwrap();
+    std::fs::write(&archive, "archive").unwrap();
+
+    let cache = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+        cmd.env("INFERENCE_HOME", temp.path())
+            .arg("cache")
+            .args(args);
+        cmd
+    };
+    cache(&["path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            temp.path().join("cache").display().to_string(),
+        ));
+    cache(&["clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Freed 2 B"));
+    assert!(!manifest.exists());
+    assert!(archive.exists());
+
+    cache(&["clear", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Freed 7 B"));
+    assert!(!archive.exists());
+    cache(&["clear"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cache is already empty."));
+}
+
+/// Verifies that a toolchain exported from one `INFERENCE_HOME` can be
+/// imported into another.
+///
+/// **Test setup**: A fake toolchain 0.1.0 in one home, an empty second home.
+///
+/// **Expected behavior**: The import installs the same files, makes 0.1.0
+/// the default, is listed as imported, and a second import needs `--force`.
+#[test]
+fn toolchain_export_import_round_trip() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let online = temp.path().join("online");
+    let offline = temp.path().join("offline");
+    let ext = std::env::consts::EXE_SUFFIX;
+    let toolchain = online.join("toolchains/0.1.0");
+    std::fs::create_dir_all(toolchain.join("bin")).unwrap();
+    std::fs::write(toolchain.join(format!("infc{ext}")), "infc").unwrap();
+    for tool in ["inf-llc", "rust-lld"] {
+        std::fs::write(toolchain.join(format!("bin/{tool}{ext}")), tool).unwrap();
+    }
+    let bundle = temp.path().join("bundle.tar.zst");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", &online)
+        .args(["toolchain", "export", "0.1.0", "-o"])
+        .arg(&bundle)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exported toolchain 0.1.0 (3 files)",
+        ));
+
+    let import = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+        cmd.env("INFERENCE_HOME", &offline)
+            .env("HOME", temp.path())
+            .args(["toolchain", "import", "--no-modify-path"])
+            .arg(&bundle);
+        cmd
+    };
+    import().assert().success().stdout(predicate::str::contains(
+        "Toolchain 0.1.0 installed successfully.",
+    ));
+    assert_eq!(
+        std::fs::read(offline.join(format!("toolchains/0.1.0/bin/inf-llc{ext}"))).unwrap(),
+        b"inf-llc"
+    );
+    assert_eq!(
+        std::fs::read_to_string(offline.join("default"))
+            .unwrap()
+            .trim(),
+        "0.1.0"
+    );
+
+    Command::new(assert_cmd::cargo::cargo_bin!("infs"))
+        .env("INFERENCE_HOME", &offline)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("imported"));
+
+    import()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    import().arg("--force").assert().success();
+}
+
+// -----------------------------------------------------------------------------
+// Env Command Tests
+// -----------------------------------------------------------------------------
+
+/// Verifies that `infs env` prints the version, platform, and home directory.
+///
+/// **Test setup**: Uses an isolated `INFERENCE_HOME` directory.
+///
+/// **Expected behavior**: Exit with code 0 and list the key fields.
+#[test]
+fn env_shows_version_and_platform() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFERENCE_HOME", temp.path()).arg("env");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "infs_version:         {}",
+            env!("CARGO_PKG_VERSION")
+        )))
+        .stdout(predicate::str::contains("platform:"))
+        .stdout(predicate::str::contains(temp.path().display().to_string()))
+        .stdout(predicate::str::contains("installed_toolchains: (none)"))
+        .stdout(predicate::str::contains("wasmtime:"));
+}
+
+/// Verifies that `infs env --json` prints valid JSON with the key fields.
+///
+/// **Test setup**: Uses an isolated `INFERENCE_HOME` directory.
+///
+/// **Expected behavior**: Output parses as JSON and includes version and platform.
+#[test]
+fn env_json_is_valid() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    let output = cmd
+        .env("INFERENCE_HOME", temp.path())
+        .args(["env", "--json"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["infs_version"], env!("CARGO_PKG_VERSION"));
+    assert!(json.get("platform").is_some());
+    assert_eq!(json["installed_toolchains"], serde_json::json!([]));
+}
+
+// -----------------------------------------------------------------------------
+// Info Command Tests
+// -----------------------------------------------------------------------------
+
+/// Verifies that `infs info` lists the exports of a compiled module.
+///
+/// **Test setup**: Uses `trivial.wasm`, the codegen output for `trivial.inf`.
+///
+/// **Expected behavior**: Exit with code 0 and list the `hello_world` and
+/// `memory` exports and the `name` custom section.
+#[test]
+fn info_lists_wasm_exports() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("info").arg(fixture_file("trivial.wasm"));
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Kind:    wasm"))
+        .stdout(predicate::str::contains("hello_world  func 0"))
+        .stdout(predicate::str::contains("memory       memory 0"))
+        .stdout(predicate::str::contains("custom \"name\""));
+}
+
+/// Verifies that `infs info --json` reports exports of a module that uses
+/// non-deterministic instructions.
+///
+/// **Expected behavior**: Output parses as JSON with `kind` set to `wasm`
+/// and at least one exported function.
+#[test]
+fn info_json_handles_nondet_module() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    let output = cmd
+        .args(["info", "--json"])
+        .arg(fixture_file("nondet.wasm"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["kind"], "wasm");
+    let exports = json["exports"].as_array().unwrap();
+    assert!(exports.iter().any(|export| export["kind"] == "func"));
+}
+
+/// Verifies that `infs info` lists theorems in a Rocq file.
+///
+/// **Expected behavior**: Exit with code 0 and list both declarations and
+/// the file hash.
+#[test]
+fn info_lists_rocq_theorems() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("info").arg(fixture_file("theorems.v"));
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("SHA256:"))
+        .stdout(predicate::str::contains("Theorem add_comm (line 6)"))
+        .stdout(predicate::str::contains("Lemma add_zero_r (line 11)"));
+}
+
+/// Verifies that `infs info` rejects files it cannot inspect.
+///
+/// **Expected behavior**: Exit with code 2 and name the supported extensions.
+#[test]
+fn info_rejects_unsupported_file() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("info").arg(fixture_file("trivial.inf"));
+
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("expected a .wasm or .v file"));
+}
+
 // -----------------------------------------------------------------------------
 // Self Update Command Tests
 // -----------------------------------------------------------------------------
@@ -879,6 +1460,54 @@ fn install_uses_custom_dist_server() {
         .stderr(predicate::str::contains("invalid-test-server"));
 }
 
+/// Verifies that an ambiguous version range resolves without prompting
+/// when infs is not attached to a terminal.
+///
+/// **Test setup**: A cached manifest with 0.2.0 and 0.2.4, whose artifacts
+/// point at an unreachable server.
+///
+/// **Expected behavior**: `infs install 0.2` picks 0.2.4 without listing the
+/// choices, then fails to download.
+#[test]
+fn install_range_without_terminal_picks_highest_match() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let entry = |version: &str| {
+        let files: Vec<String> = ["linux-x64.tar.gz", "macos-arm64.tar.gz", "windows-x64.zip"]
+            .iter()
+            .map(|suffix| {
+                format!(
+                    r#"{{"url": "http://invalid-test-server.localhost/{version}/infc-{suffix}", "sha256": "{}"}}"#,
+                    "a".repeat(64)
+                )
+            })
+            .collect();
+        format!(
+            r#"{{"version": "{version}", "stable": true, "files": [{}]}}"#,
+            files.join(", ")
+        )
+    };
+    std::fs::create_dir_all(temp.path().join("cache")).unwrap();
+    std::fs::write(
+        temp.path().join("cache").join("manifest.json"),
+        format!(
+            r#"{{"schema_version": 2, "manifest": [{}, {}], "timestamp": 0}}"#,
+            entry("0.2.0"),
+            entry("0.2.4")
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFERENCE_HOME", temp.path())
+        .arg("install")
+        .arg("0.2");
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Downloading toolchain v0.2.4"))
+        .stdout(predicate::str::contains("Multiple versions match").not());
+}
+
 /// Verifies that `infs self` without a subcommand shows an error.
 ///
 /// **Expected behavior**: Exit with non-zero code when no subcommand is provided.
@@ -1056,27 +1685,109 @@ fn new_generates_valid_manifest() {
         manifest_content.contains("version = \"0.1.0\""),
         "Manifest should contain default version"
     );
+    assert!(
+        manifest_content.contains("edition = \"2024\""),
+        "Manifest should contain default edition"
+    );
 }
 
-/// Verifies that `infs new --no-git` skips git initialization.
+/// Verifies that `infs new --edition` records a known edition and rejects
+/// an unknown one.
 ///
-/// **Expected behavior**: Project is created successfully without .git directory.
+/// **Expected behavior**: The manifest contains the given edition; an unknown
+/// edition fails without creating the project directory.
 #[test]
-fn new_with_no_git_flag() {
+fn new_with_edition() {
     let temp = assert_fs::TempDir::new().unwrap();
 
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
     cmd.current_dir(temp.path())
-        .arg("new")
-        .arg("nogit_project")
-        .arg("--no-git");
-
+        .args(["new", "edition_project", "--no-git", "--edition", "2024"]);
     cmd.assert().success();
 
-    let project_dir = temp.child("nogit_project");
-    assert!(
-        project_dir.path().exists(),
-        "Project directory should exist"
+    let manifest =
+        std::fs::read_to_string(temp.child("edition_project").child("Inference.toml").path())
+            .unwrap();
+    assert!(manifest.contains("edition = \"2024\""));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["new", "old_edition", "--no-git", "--edition", "2015"]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "unknown [package] edition value '2015'",
+    ));
+    temp.child("old_edition").assert(predicate::path::missing());
+}
+
+/// Verifies that `infs new` records the metadata flags in `[package]` and
+/// rejects a malformed license.
+///
+/// **Expected behavior**: The manifest lists the description, every
+/// `--author`, and the license; `MIT/Apache-2.0` fails without creating the
+/// project directory.
+#[test]
+fn new_with_package_metadata() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path()).args([
+        "new",
+        "metadata_project",
+        "--no-git",
+        "--description",
+        "Verified sorting",
+        "--author",
+        "Ada <ada@example.com>",
+        "--author",
+        "Grace",
+        "--license",
+        "MIT",
+    ]);
+    cmd.assert().success();
+
+    let manifest = std::fs::read_to_string(
+        temp.child("metadata_project")
+            .child("Inference.toml")
+            .path(),
+    )
+    .unwrap();
+    assert!(manifest.contains("description = \"Verified sorting\""));
+    assert!(manifest.contains("authors = [\"Ada <ada@example.com>\", \"Grace\"]"));
+    assert!(manifest.contains("license = \"MIT\""));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path()).args([
+        "new",
+        "bad_license",
+        "--no-git",
+        "--license",
+        "MIT/Apache-2.0",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid license 'MIT/Apache-2.0'"));
+    temp.child("bad_license").assert(predicate::path::missing());
+}
+
+/// Verifies that `infs new --no-git` skips git initialization.
+///
+/// **Expected behavior**: Project is created successfully without .git directory.
+#[test]
+fn new_with_no_git_flag() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .arg("new")
+        .arg("nogit_project")
+        .arg("--no-git");
+
+    cmd.assert().success();
+
+    let project_dir = temp.child("nogit_project");
+    assert!(
+        project_dir.path().exists(),
+        "Project directory should exist"
     );
     assert!(
         !project_dir.child(".git").path().exists(),
@@ -1084,6 +1795,319 @@ fn new_with_no_git_flag() {
     );
 }
 
+/// Verifies that `infs new --vcs none --ci github` adds the workflow without a repository.
+///
+/// **Expected behavior**: Project contains `.github/workflows/verify.yml`, no `.git`
+/// directory and no `.gitignore`.
+#[test]
+fn new_with_vcs_none_and_github_ci() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .arg("new")
+        .arg("ci_project")
+        .arg("--vcs")
+        .arg("none")
+        .arg("--ci")
+        .arg("github");
+
+    cmd.assert().success();
+
+    let project_dir = temp.child("ci_project");
+    assert!(
+        project_dir
+            .child(".github/workflows/verify.yml")
+            .path()
+            .exists(),
+        "GitHub workflow should exist when --ci github is used"
+    );
+    assert!(!project_dir.child(".git").path().exists());
+    assert!(!project_dir.child(".gitignore").path().exists());
+}
+
+/// Verifies that `infs new --template` copies a user template from `INFERENCE_HOME`.
+///
+/// **Expected behavior**: The template's files are rendered into the project,
+/// and an unknown template name fails with the list of available templates.
+#[test]
+fn new_with_user_template() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = temp.child("home");
+    home.child("templates/lib/src/main.inf")
+        .write_str("// {{project_name}} library\n")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .env("INFERENCE_HOME", home.path())
+        .args(["new", "lib_project", "--vcs", "none", "--template", "lib"]);
+    cmd.assert().success();
+
+    temp.child("lib_project/src/main.inf")
+        .assert("// lib_project library\n");
+    temp.child("lib_project/Inference.toml")
+        .assert(predicate::str::contains("name = \"lib\""));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .env("INFERENCE_HOME", home.path())
+        .args(["new", "other_project", "--template", "missing"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("available: default, lib"));
+}
+
+/// Verifies that `--no-git` cannot be combined with an explicit `--vcs`.
+///
+/// **Expected behavior**: Exit with non-zero code and report the conflict.
+#[test]
+fn new_no_git_conflicts_with_vcs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .arg("new")
+        .arg("conflict_project")
+        .arg("--no-git")
+        .arg("--vcs")
+        .arg("git");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+// -----------------------------------------------------------------------------
+// Scaffold Command Tests
+// -----------------------------------------------------------------------------
+
+/// Verifies that `infs scaffold upgrade` reports an edited template file as a
+/// conflict once the template changes it, and leaves it unchanged.
+///
+/// **Test setup**: Creates a project with `--ci github`, edits the workflow,
+/// then makes the lock record an older template for it.
+///
+/// **Expected behavior**: Exit with code 0. While the template is unchanged
+/// the edit is not reported; afterwards the workflow is listed as modified
+/// with a patch for `--diff`. `src/main.inf` is unchanged.
+#[test]
+fn scaffold_upgrade_reports_edited_files() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["new", "upgrade_project", "--vcs", "none", "--ci", "github"])
+        .assert()
+        .success();
+
+    let project_dir = temp.child("upgrade_project");
+    project_dir
+        .child(".inference/scaffold.lock")
+        .assert(predicate::path::exists());
+    let main_inf = std::fs::read_to_string(project_dir.child("src/main.inf").path()).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .args(["scaffold", "upgrade"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project template is up to date."));
+
+    project_dir
+        .child(".github/workflows/verify.yml")
+        .write_str("name: my-own-workflow\n")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .args(["scaffold", "upgrade"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("conflict").not());
+
+    // Pretend the workflow was generated by an older template.
+    let lock_path = project_dir.child(".inference/scaffold.lock");
+    let lock = std::fs::read_to_string(lock_path.path()).unwrap();
+    let lock = lock
+        .lines()
+        .map(|line| {
+            if line.starts_with("\".github/workflows/verify.yml\"") {
+                format!("\".github/workflows/verify.yml\" = \"{}\"", "0".repeat(64))
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    lock_path.write_str(&lock).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .args(["scaffold", "upgrade", "--diff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "conflict  .github/workflows/verify.yml (modified locally)",
+        ))
+        .stdout(predicate::str::contains("-name: my-own-workflow"));
+
+    project_dir
+        .child(".github/workflows/verify.yml")
+        .assert("name: my-own-workflow\n");
+    project_dir.child("src/main.inf").assert(main_inf.as_str());
+}
+
+/// Verifies that `infs scaffold upgrade` fails outside an Inference project.
+///
+/// **Expected behavior**: Exit with code 3 (file not found).
+#[test]
+fn scaffold_upgrade_requires_project() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["scaffold", "upgrade"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("is not an Inference project"));
+}
+
+/// Verifies that `infs clean` removes `out/`, and the proofs directory only
+/// with `--all`.
+///
+/// **Expected behavior**: `clean` removes `out/` and keeps `proofs/`;
+/// `clean --all` removes `proofs/`; a second run has nothing to clean.
+#[test]
+fn clean_removes_build_artifacts() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["new", "clean_project", "--vcs", "none"])
+        .assert()
+        .success();
+
+    let project_dir = temp.child("clean_project");
+    project_dir
+        .child("out/main.wasm")
+        .write_str("wasm")
+        .unwrap();
+    project_dir
+        .child("proofs/main.v")
+        .write_str("proof")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .arg("clean")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed out"));
+    project_dir.child("out").assert(predicate::path::missing());
+    project_dir.child("proofs/main.v").assert("proof");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("clean")
+        .arg(project_dir.path())
+        .arg("--all")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed proofs/"));
+    project_dir
+        .child("proofs")
+        .assert(predicate::path::missing());
+    project_dir
+        .child("src/main.inf")
+        .assert(predicate::path::exists());
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .args(["clean", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to clean."));
+}
+
+/// Verifies that `infs clean --all` refuses an output directory outside the
+/// project.
+///
+/// **Test setup**: `[verification] output-dir` points at a sibling directory.
+///
+/// **Expected behavior**: Exit with code 2 and leave the sibling untouched.
+#[test]
+fn clean_refuses_directories_outside_project() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["new", "clean_project", "--vcs", "none"])
+        .assert()
+        .success();
+
+    let project_dir = temp.child("clean_project");
+    let manifest = std::fs::read_to_string(project_dir.child("Inference.toml").path()).unwrap();
+    project_dir
+        .child("Inference.toml")
+        .write_str(&format!(
+            "{manifest}\n[verification]\noutput-dir = \"../elsewhere\"\n"
+        ))
+        .unwrap();
+    temp.child("elsewhere/keep.v").write_str("keep").unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(project_dir.path())
+        .args(["clean", "--all"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "refusing to remove '../elsewhere'",
+        ));
+    temp.child("elsewhere/keep.v").assert("keep");
+}
+
+/// Verifies `infs fmt --check` and `--in-place` on a small `.wat` fixture.
+///
+/// **Expected behavior**: `--check` exits with code 1 and prints a diff for
+/// the unformatted file; after `--in-place` rewrites it, `--check` succeeds.
+#[test]
+fn fmt_check_and_in_place() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let wat = temp.child("out/main.wat");
+    wat.write_str("(module (func $main i32.const 1 drop))")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["fmt", "--check"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("+  (func $main"))
+        .stderr(predicate::str::contains("1 of 1 file(s) are not formatted"));
+    wat.assert("(module (func $main i32.const 1 drop))");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["fmt", "--in-place"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Formatted"));
+    let formatted = std::fs::read_to_string(wat.path()).unwrap();
+    assert!(
+        formatted.starts_with("(module\n  (func $main"),
+        "{formatted}"
+    );
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.current_dir(temp.path())
+        .args(["fmt", "--check"])
+        .assert()
+        .success();
+}
+
 // -----------------------------------------------------------------------------
 // Init Command Tests
 // -----------------------------------------------------------------------------
@@ -1382,13 +2406,13 @@ fn run_requires_path_argument() {
 
 /// Verifies that `infs run` fails when source file doesn't exist.
 ///
-/// **Expected behavior**: Exit with non-zero code and print "Path not found".
+/// **Expected behavior**: Exit with code 3 (file not found) and print "Path not found".
 #[test]
 fn run_fails_when_file_missing() {
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
     cmd.arg("run").arg("this-file-does-not-exist.inf");
 
-    cmd.assert().failure().stderr(
+    cmd.assert().failure().code(3).stderr(
         predicate::str::contains("Path not found").or(predicate::str::contains("path not found")),
     );
 }
@@ -1397,7 +2421,8 @@ fn run_fails_when_file_missing() {
 ///
 /// **Test setup**: Uses PATH override to ensure wasmtime is not found.
 ///
-/// **Expected behavior**: Exit with non-zero code and display installation instructions.
+/// **Expected behavior**: Exit with code 14 (tool not found) and display installation
+/// instructions.
 #[test]
 fn run_shows_wasmtime_not_found_message() {
     let temp = assert_fs::TempDir::new().unwrap();
@@ -1413,6 +2438,7 @@ fn run_shows_wasmtime_not_found_message() {
 
     cmd.assert()
         .failure()
+        .code(14)
         .stderr(predicate::str::contains("wasmtime not found"))
         .stderr(
             predicate::str::contains("wasmtime.dev")
@@ -1650,6 +2676,481 @@ fn build_with_invalid_infc_path_shows_error() {
     );
 }
 
+/// Writes a fake `infc` script into `dir` that records its arguments.
+///
+/// `infc --help` mentions `--target` only when `supports_target` is set,
+/// mimicking toolchains from before target selection was added. Other
+/// invocations append their arguments to `dir/infc-args.log`.
+#[cfg(unix)]
+fn fake_infc(dir: &std::path::Path, supports_target: bool) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let help = if supports_target {
+        "      --target <TARGET>  WebAssembly target"
+    } else {
+        "      --out-dir <DIR>  Directory for output files"
+    };
+    let log = dir.join("infc-args.log");
+    let script = format!(
+        "#!/bin/sh\nif [ \"$1\" = \"--help\" ]; then echo '{help}'; exit 0; fi\necho \"$@\" >> '{}'\n",
+        log.display()
+    );
+    let path = dir.join("infc");
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+/// Creates a project whose manifest selects `target`, returning the source path.
+fn project_with_target(dir: &std::path::Path, target: &str) -> std::path::PathBuf {
+    std::fs::write(
+        dir.join("Inference.toml"),
+        format!(
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ninfc_version = \"0.1.0\"\n\n[build]\ntarget = \"{target}\"\n"
+        ),
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    let source = dir.join("src").join("main.inf");
+    std::fs::write(&source, "pub fn main() -> i32 { return 0; }\n").unwrap();
+    source
+}
+
+/// Verifies that `--target` overrides `[build] target` from the manifest.
+///
+/// **Test setup**: A project targeting `wasm64` and a fake infc that records
+/// the arguments it receives.
+///
+/// **Expected behavior**: Without the flag infc gets `--target wasm64`; with
+/// `--target wasm32` no target is forwarded.
+#[cfg(unix)]
+#[test]
+fn build_target_flag_overrides_manifest() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let infc = fake_infc(temp.path(), true);
+    let source = project_with_target(temp.path(), "wasm64");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--codegen");
+    cmd.assert().success();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--codegen")
+        .arg("--target")
+        .arg("wasm32");
+    cmd.assert().success();
+
+    let log = std::fs::read_to_string(temp.path().join("infc-args.log")).unwrap();
+    let calls: Vec<&str> = log.lines().collect();
+    assert_eq!(calls.len(), 2, "{log}");
+    assert!(calls[0].ends_with("--codegen --target wasm64"), "{log}");
+    assert!(!calls[1].contains("--target"), "{log}");
+}
+
+/// Verifies that a target the installed infc doesn't support is rejected.
+///
+/// **Test setup**: A fake infc whose help text has no `--target` option.
+///
+/// **Expected behavior**: Exit with code 2 before compiling, listing the
+/// supported targets.
+#[cfg(unix)]
+#[test]
+fn build_rejects_target_unsupported_by_toolchain() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let infc = fake_infc(temp.path(), false);
+    let source = project_with_target(temp.path(), "wasm32");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--codegen")
+        .arg("--target")
+        .arg("wasm64");
+
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "target `wasm64` is not supported by the installed toolchain",
+        ))
+        .stderr(predicate::str::contains("supported targets: wasm32"));
+    assert!(!temp.path().join("infc-args.log").exists());
+}
+
+/// Verifies that `--emit-deps` writes a Makefile dependency file.
+///
+/// **Test setup**: A project with two source files, one with a space in its
+/// name, built from the project root with a fake infc.
+///
+/// **Expected behavior**: The file lists both outputs depending on the
+/// manifest and the compiled source, relative to the root with spaces
+/// escaped. The other source file is not read by the compiler and is not
+/// listed.
+#[cfg(unix)]
+#[test]
+fn build_emit_deps_writes_makefile_dependencies() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let infc = fake_infc(temp.path(), true);
+    project_with_target(temp.path(), "wasm32");
+    let source = temp.path().join("src").join("my app.inf");
+    std::fs::write(&source, "pub fn main() -> i32 { return 0; }\n").unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .current_dir(temp.path())
+        .arg("build")
+        .arg("src/my app.inf")
+        .arg("--codegen")
+        .arg("-o")
+        .arg("-v")
+        .arg("--emit-deps")
+        .arg("deps/app.d");
+    cmd.assert().success();
+
+    let deps = std::fs::read_to_string(temp.path().join("deps").join("app.d")).unwrap();
+    assert_eq!(
+        deps,
+        "out/my\\ app.wasm out/my\\ app.v: Inference.toml src/my\\ app.inf\n"
+    );
+}
+
+/// Verifies that `--emit-deps` requires an output to describe.
+///
+/// **Expected behavior**: Without `-o` or `-v` the build fails before
+/// compiling.
+#[test]
+fn build_emit_deps_requires_output() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("build")
+        .arg(&source)
+        .arg("--codegen")
+        .arg("--emit-deps")
+        .arg(temp.path().join("main.d"));
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--emit-deps requires an output"));
+    assert!(!temp.path().join("main.d").exists());
+}
+
+/// Verifies that `--release`/`--debug` override `[build] optimize`.
+///
+/// **Test setup**: A project with `optimize = "release"` and a fake infc that
+/// advertises `--opt-level` and records its arguments.
+///
+/// **Expected behavior**: The manifest level is forwarded as `--opt-level 3`,
+/// `--debug` forwards `--opt-level 0`, and both flags together are rejected.
+#[cfg(unix)]
+#[test]
+fn build_optimize_flag_overrides_manifest() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+    let manifest = temp.path().join("Inference.toml");
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(&manifest, format!("{content}optimize = \"release\"\n")).unwrap();
+    let log = temp.path().join("infc-args.log");
+    let infc = temp.path().join("infc");
+    let script = format!(
+        "#!/bin/sh\nif [ \"$1\" = \"--help\" ]; then echo '      --opt-level <LEVEL>'; exit 0; fi\necho \"$@\" >> '{}'\n",
+        log.display()
+    );
+    std::fs::write(&infc, script).unwrap();
+    std::fs::set_permissions(&infc, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    for flags in [&[][..], &["--debug"][..]] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+        cmd.env("INFC_PATH", &infc)
+            .arg("build")
+            .arg(&source)
+            .arg("--codegen")
+            .args(flags);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--codegen")
+        .arg("--release")
+        .arg("--debug");
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    let logged = std::fs::read_to_string(&log).unwrap();
+    let calls: Vec<&str> = logged.lines().collect();
+    assert_eq!(calls.len(), 2, "{logged}");
+    assert!(calls[0].ends_with("--codegen --opt-level 3"), "{logged}");
+    assert!(calls[1].ends_with("--codegen --opt-level 0"), "{logged}");
+}
+
+/// Verifies that features selected in the manifest and with flags reach infc.
+///
+/// **Test setup**: A project declaring features, with `logging` enabled by
+/// default, and a fake infc that advertises `--define` and records its
+/// arguments.
+///
+/// **Expected behavior**: Every declared feature is forwarded, enabled ones
+/// as `-D feature=NAME` and the rest as `-D feature=NAME=false`. The
+/// defaults are enabled, `--features` adds features and those they imply,
+/// `--no-default-features` drops the defaults, and unknown features are
+/// rejected before compiling.
+#[cfg(unix)]
+#[test]
+fn build_forwards_selected_features() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+    let manifest = temp.path().join("Inference.toml");
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(
+        &manifest,
+        format!(
+            "{content}\n[features]\ndefault = [\"logging\"]\nlogging = []\nbounds = []\nruntime-checks = [\"bounds\"]\n"
+        ),
+    )
+    .unwrap();
+    let log = temp.path().join("infc-args.log");
+    let infc = temp.path().join("infc");
+    let script = format!(
+        "#!/bin/sh\nif [ \"$1\" = \"--help\" ]; then echo '  -D, --define <KEY=VALUE>  feature=NAME=false disables it'; exit 0; fi\necho \"$@\" >> '{}'\n",
+        log.display()
+    );
+    std::fs::write(&infc, script).unwrap();
+    std::fs::set_permissions(&infc, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    for flags in [
+        &[][..],
+        &["--features", "runtime-checks"][..],
+        &["--no-default-features"][..],
+    ] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+        cmd.env("INFC_PATH", &infc)
+            .arg("build")
+            .arg(&source)
+            .arg("--parse")
+            .args(flags);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--parse")
+        .arg("--features")
+        .arg("fast");
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "unknown feature `fast`; declared features: bounds, logging, runtime-checks",
+        ));
+
+    let logged = std::fs::read_to_string(&log).unwrap();
+    let calls: Vec<&str> = logged.lines().collect();
+    assert_eq!(calls.len(), 3, "{logged}");
+    assert!(
+        calls[0].ends_with(
+            "--parse -D feature=bounds=false -D feature=logging -D feature=runtime-checks=false"
+        ),
+        "{logged}"
+    );
+    assert!(
+        calls[1]
+            .ends_with("--parse -D feature=bounds -D feature=logging -D feature=runtime-checks"),
+        "{logged}"
+    );
+    assert!(
+        calls[2].ends_with(
+            "--parse -D feature=bounds=false -D feature=logging=false -D feature=runtime-checks=false"
+        ),
+        "{logged}"
+    );
+}
+
+/// Verifies that features are rejected when infc cannot receive them.
+///
+/// **Test setup**: A project with a default feature and a fake infc without
+/// `--define`.
+///
+/// **Expected behavior**: Exit with code 2 before compiling, unless the
+/// defaults are turned off with `--no-default-features`.
+#[cfg(unix)]
+#[test]
+fn build_rejects_features_unsupported_by_toolchain() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let infc = fake_infc(temp.path(), true);
+    let source = project_with_target(temp.path(), "wasm32");
+    let manifest = temp.path().join("Inference.toml");
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(
+        &manifest,
+        format!("{content}\n[features]\ndefault = [\"logging\"]\nlogging = []\n"),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--parse");
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "features (logging) are not supported by the installed toolchain",
+        ));
+    assert!(!temp.path().join("infc-args.log").exists());
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("build")
+        .arg(&source)
+        .arg("--parse")
+        .arg("--no-default-features");
+    cmd.assert().success();
+}
+
+/// Verifies that a cycle in `[features]` is rejected.
+///
+/// **Expected behavior**: The build fails naming the cycle.
+#[test]
+fn build_rejects_feature_cycles() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+    let manifest = temp.path().join("Inference.toml");
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(
+        &manifest,
+        format!("{content}\n[features]\na = [\"b\"]\nb = [\"a\"]\n"),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("build").arg(&source).arg("--parse");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "features imply each other in a cycle: a -> b -> a",
+    ));
+}
+
+/// Verifies that an unknown `[build] optimize` value is rejected.
+///
+/// **Expected behavior**: The build fails naming the value and the known
+/// levels, unless a flag makes the manifest level irrelevant.
+#[test]
+fn build_rejects_unknown_optimize_level() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+    let manifest = temp.path().join("Inference.toml");
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    std::fs::write(&manifest, format!("{content}optimize = \"fast\"\n")).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("build").arg(&source).arg("--codegen");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "unknown [build] optimize value 'fast'; expected one of: debug, release",
+    ));
+}
+
+/// Verifies that JSON diagnostics from infc are rendered with source context.
+///
+/// **Test setup**: A fake infc that advertises `--message-format` and reports
+/// one type error as JSON on stderr.
+///
+/// **Expected behavior**: infs requests JSON output, prints the error with
+/// the offending source line and a caret, and exits with infc's code.
+#[cfg(unix)]
+#[test]
+fn build_renders_infc_diagnostics_with_source() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm32");
+    let log = temp.path().join("infc-args.log");
+    let infc = temp.path().join("infc");
+    let script = format!(
+        "#!/bin/sh\n\
+         if [ \"$1\" = \"--help\" ]; then echo '      --message-format <FORMAT>'; exit 0; fi\n\
+         echo \"$@\" >> '{}'\n\
+         echo 'Parsed: '\"$1\" >&2\n\
+         printf '{{\"code\":null,\"column\":31,\"end_column\":32,\"end_line\":1,\"line\":1,\"message\":\"expected `bool`, found `i32`\",\"path\":\"%s\",\"severity\":\"error\"}}\\n' \"$1\" >&2\n\
+         exit 1\n",
+        log.display()
+    );
+    std::fs::write(&infc, script).unwrap();
+    std::fs::set_permissions(&infc, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.env("INFC_PATH", &infc)
+        .arg("--color")
+        .arg("never")
+        .arg("build")
+        .arg(&source)
+        .arg("--analyze");
+
+    cmd.assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(format!(
+            "Parsed: {}",
+            source.display()
+        )))
+        .stderr(predicate::str::contains(format!(
+            "error: expected `bool`, found `i32`\n --> {}:1:31\n  |\n1 | pub fn main() -> i32 {{ return 0; }}\n  |                               ^\n",
+            source.display()
+        )));
+    let logged = std::fs::read_to_string(&log).unwrap();
+    assert!(
+        logged
+            .trim_end()
+            .ends_with("--analyze --message-format json"),
+        "{logged}"
+    );
+}
+
+/// Verifies that unknown targets are rejected on the command line and in the manifest.
+///
+/// **Expected behavior**: Both fail and list `wasm32` and `wasm64`.
+#[test]
+fn build_rejects_unknown_target() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = project_with_target(temp.path(), "wasm128");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("build")
+        .arg(&source)
+        .arg("--codegen")
+        .arg("--target")
+        .arg("wasm128");
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("possible values: wasm32, wasm64"));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infs"));
+    cmd.arg("build").arg(&source).arg("--codegen");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("wasm128"))
+        .stderr(predicate::str::contains("wasm32"))
+        .stderr(predicate::str::contains("wasm64"));
+}
+
 /// QA: TC-12.3 - Verify recovery from corrupted toolchain metadata.
 ///
 /// **Expected behavior**: No crash, warning about corrupted metadata.
@@ -0,0 +1,14 @@
+(* Generated proof obligations for a small arithmetic module. *)
+Require Import Arith.
+
+Definition add (a b : nat) : nat := a + b.
+
+Theorem add_comm : forall a b, add a b = add b a.
+Proof.
+  intros a b. unfold add. apply Nat.add_comm.
+Qed.
+
+Lemma add_zero_r : forall a, add a 0 = a.
+Proof.
+  intros a. unfold add. apply Nat.add_0_r.
+Qed.
@@ -28,12 +28,12 @@
 //! # Error Handling
 //!
 //! The builder collects errors during construction by checking for tree-sitter ERROR nodes.
-//! If any errors are found, `build_ast()` prints them to stderr and returns an error:
+//! If any errors are found, `build_ast()` returns a
+//! [`SyntaxErrors`](crate::errors::SyntaxErrors) value holding every error with
+//! its source location. Its `Display` joins them with `; `:
 //!
 //! ```text
-//! AST Builder Error: Syntax error at line 5
-//! AST Builder Error: Unexpected token at line 10
-//! Error: AST building failed due to errors
+//! 5:3: unexpected or malformed token; 10:1: syntax error in statement
 //! ```
 //!
 //! # Node ID Assignment
@@ -69,6 +69,7 @@ use crate::nodes::{
 };
 use crate::{
     arena::Arena,
+    errors::{SyntaxError, SyntaxErrors},
     nodes::{
         Argument, ArrayIndexAccessExpression, ArrayLiteral, AssertStatement, AssignStatement,
         AstNode, BinaryExpression, Block, BlockType, BoolLiteral, BreakStatement,
@@ -87,7 +88,7 @@ use tree_sitter::Node;
 pub struct Builder<'a> {
     arena: Arena,
     source_code: Vec<(Node<'a>, &'a [u8])>,
-    errors: Vec<anyhow::Error>,
+    errors: Vec<SyntaxError>,
 }
 
 impl Default for Builder<'_> {
@@ -160,10 +161,7 @@ impl<'a> Builder<'a> {
             self.arena
                 .add_node(AstNode::Ast(Ast::SourceFile(Rc::new(ast))), u32::MAX);
             if !self.errors.is_empty() {
-                for err in &self.errors {
-                    eprintln!("AST Builder Error: {err}");
-                }
-                return Err(anyhow::anyhow!("AST building failed due to errors"));
+                return Err(SyntaxErrors(std::mem::take(&mut self.errors)).into());
             }
         }
         Ok(self.arena.clone())
@@ -314,17 +312,16 @@ impl<'a> Builder<'a> {
                 Definition::Type(self.build_type_definition(parent_id, node, code))
             }
             "ERROR" => {
-                self.errors.push(anyhow::anyhow!(
-                    "Syntax error at {}: unexpected or malformed token",
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    "unexpected or malformed token",
                 ));
                 Self::create_error_definition(node, code)
             }
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected definition kind '{}' at {}",
-                    node.kind(),
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected definition kind `{}`", node.kind()),
                 ));
                 Self::create_error_definition(node, code)
             }
@@ -475,9 +472,9 @@ impl<'a> Builder<'a> {
             returns = Some(self.build_type(id, &returns_node, code));
         }
         let Some(name_node) = node.child_by_field_name("name") else {
-            self.errors.push(anyhow::anyhow!(
-                "Missing function name at {}",
-                Self::get_location(node, code)
+            self.errors.push(SyntaxError::new(
+                Self::get_location(node, code),
+                "missing function name",
             ));
             let placeholder_name = Rc::new(Identifier::new(
                 Self::get_node_id(),
@@ -504,9 +501,9 @@ impl<'a> Builder<'a> {
         let body = if let Some(body_node) = node.child_by_field_name("body") {
             self.build_block(id, &body_node, code)
         } else {
-            self.errors.push(anyhow::anyhow!(
-                "Missing function body at {}",
-                Self::get_location(node, code)
+            self.errors.push(SyntaxError::new(
+                Self::get_location(node, code),
+                "missing function body",
             ));
             BlockType::Block(Rc::new(Block::new(
                 Self::get_node_id(),
@@ -761,17 +758,16 @@ impl<'a> Builder<'a> {
                 BlockType::Block(node)
             }
             "ERROR" => {
-                self.errors.push(anyhow::anyhow!(
-                    "Syntax error in block at {}",
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    "syntax error in block",
                 ));
                 self.create_error_block(node, code, parent_id)
             }
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected block type '{}' at {}",
-                    node.kind(),
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected block type `{}`", node.kind()),
                 ));
                 self.create_error_block(node, code, parent_id)
             }
@@ -846,17 +842,16 @@ impl<'a> Builder<'a> {
                 Statement::ConstantDefinition(self.build_constant_definition(parent_id, node, code))
             }
             "ERROR" => {
-                self.errors.push(anyhow::anyhow!(
-                    "Syntax error in statement at {}",
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    "syntax error in statement",
                 ));
                 self.create_error_statement(node, code, parent_id)
             }
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected statement type '{}' at {}",
-                    node.kind(),
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected statement type `{}`", node.kind()),
                 ));
                 self.create_error_statement(node, code, parent_id)
             }
@@ -917,9 +912,9 @@ impl<'a> Builder<'a> {
         let body = if let Some(body_block) = node.child_by_field_name("body") {
             self.build_block(id, &body_block, code)
         } else {
-            self.errors.push(anyhow::anyhow!(
-                "Missing loop body at {}",
-                Self::get_location(node, code)
+            self.errors.push(SyntaxError::new(
+                Self::get_location(node, code),
+                "missing loop body",
             ));
             BlockType::Block(Rc::new(Block::new(Self::get_node_id(), location, vec![])))
         };
@@ -936,9 +931,9 @@ impl<'a> Builder<'a> {
         let condition = if let Some(condition_node) = node.child_by_field_name("condition") {
             self.build_expression(id, &condition_node, code)
         } else {
-            self.errors.push(anyhow::anyhow!(
-                "Missing if condition at {}",
-                Self::get_location(node, code)
+            self.errors.push(SyntaxError::new(
+                Self::get_location(node, code),
+                "missing if condition",
             ));
             Expression::Identifier(Rc::new(Identifier::new(
                 Self::get_node_id(),
@@ -949,9 +944,9 @@ impl<'a> Builder<'a> {
         let if_arm = if let Some(if_arm_node) = node.child_by_field_name("if_arm") {
             self.build_block(id, &if_arm_node, code)
         } else {
-            self.errors.push(anyhow::anyhow!(
-                "Missing if body at {}",
-                Self::get_location(node, code)
+            self.errors.push(SyntaxError::new(
+                Self::get_location(node, code),
+                "missing if body",
             ));
             BlockType::Block(Rc::new(Block::new(Self::get_node_id(), location, vec![])))
         };
@@ -1047,9 +1042,9 @@ impl<'a> Builder<'a> {
             }
             "identifier" => Expression::Identifier(self.build_identifier(parent_id, node, code)),
             "ERROR" => {
-                self.errors.push(anyhow::anyhow!(
-                    "Syntax error in expression at {}",
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    "syntax error in expression",
                 ));
                 let location = Self::get_location(node, code);
                 Expression::Identifier(Rc::new(Identifier::new(
@@ -1059,10 +1054,9 @@ impl<'a> Builder<'a> {
                 )))
             }
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected expression node kind '{}' at {}",
-                    node_kind,
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected expression node kind `{node_kind}`"),
                 ));
                 let location = Self::get_location(node, code);
                 Expression::Identifier(Rc::new(Identifier::new(
@@ -1398,10 +1392,9 @@ impl<'a> Builder<'a> {
             "&" => OperatorKind::BitAnd,
             "|" => OperatorKind::BitOr,
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected operator '{}' at {}",
-                    operator_kind,
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected operator `{operator_kind}`"),
                 ));
                 OperatorKind::Add
             }
@@ -1425,10 +1418,9 @@ impl<'a> Builder<'a> {
             "number_literal" => Literal::Number(self.build_number_literal(parent_id, node, code)),
             "unit_literal" => Literal::Unit(self.build_unit_literal(parent_id, node, code)),
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected literal type '{}' at {}",
-                    node.kind(),
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected literal type `{}`", node.kind()),
                 ));
                 Literal::Unit(Rc::new(UnitLiteral::new(
                     Self::get_node_id(),
@@ -1470,15 +1462,14 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let text = node.utf8_text(code).unwrap_or("");
+        let text = Self::node_text(node, code);
         let value = match text {
             "true" => true,
             "false" => false,
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected boolean literal value '{}' at {}",
-                    text,
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected boolean literal value `{text}`"),
                 ));
                 false
             }
@@ -1501,7 +1492,7 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let value = node.utf8_text(code).unwrap().to_string();
+        let value = Self::node_text(node, code).to_string();
         let node = Rc::new(StringLiteral::new(id, location, value));
         self.arena.add_node(
             AstNode::Expression(Expression::Literal(Literal::String(node.clone()))),
@@ -1519,7 +1510,7 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let value = node.utf8_text(code).unwrap().to_string();
+        let value = Self::node_text(node, code).to_string();
         let node = Rc::new(NumberLiteral::new(id, location, value));
         self.arena.add_node(
             AstNode::Expression(Expression::Literal(Literal::Number(node.clone()))),
@@ -1569,17 +1560,16 @@ impl<'a> Builder<'a> {
                 Type::Custom(name)
             }
             "ERROR" => {
-                self.errors.push(anyhow::anyhow!(
-                    "Syntax error in type at {}",
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    "syntax error in type",
                 ));
                 Type::Simple(SimpleTypeKind::Unit)
             }
             _ => {
-                self.errors.push(anyhow::anyhow!(
-                    "Unexpected type '{}' at {}",
-                    node_kind,
-                    Self::get_location(node, code)
+                self.errors.push(SyntaxError::new(
+                    Self::get_location(node, code),
+                    format!("unexpected type `{node_kind}`"),
                 ));
                 Type::Simple(SimpleTypeKind::Unit)
             }
@@ -1718,7 +1708,7 @@ impl<'a> Builder<'a> {
         self.collect_errors(node, code);
         let id = Self::get_node_id();
         let location = Self::get_location(node, code);
-        let name = node.utf8_text(code).unwrap().to_string();
+        let name = Self::node_text(node, code).to_string();
         let node = Rc::new(Identifier::new(id, name, location));
         self.arena.add_node(
             AstNode::Expression(Expression::Identifier(node.clone())),
@@ -1736,16 +1726,36 @@ impl<'a> Builder<'a> {
         COUNTER.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Returns the source text of `node`.
+    ///
+    /// With CRLF line endings a node can end on the `\r` before a newline;
+    /// that byte is not part of the node's text.
+    fn node_text<'c>(node: &Node, code: &'c [u8]) -> &'c str {
+        node.utf8_text(code).unwrap_or("").trim_end_matches('\r')
+    }
+
+    /// Returns the location of `node`.
+    ///
+    /// A trailing `\r` is excluded from the span, consistently with
+    /// [`Self::node_text`]. Columns are byte columns counted from the last
+    /// `\n`, so CRLF and LF files report the same columns.
     #[allow(clippy::cast_possible_truncation)]
-    fn get_location(node: &Node, _code: &[u8]) -> Location {
-        let offset_start = node.start_byte() as u32;
-        let offset_end = node.end_byte() as u32;
+    fn get_location(node: &Node, code: &[u8]) -> Location {
         let start_position = node.start_position();
         let end_position = node.end_position();
+        let mut end_byte = node.end_byte();
+        let mut end_col = end_position.column;
+        while end_col > 0 && end_byte > node.start_byte() && code.get(end_byte - 1) == Some(&b'\r')
+        {
+            end_byte -= 1;
+            end_col -= 1;
+        }
+        let offset_start = node.start_byte() as u32;
+        let offset_end = end_byte as u32;
         let start_line = start_position.row as u32 + 1;
         let start_column = start_position.column as u32 + 1;
         let end_line = end_position.row as u32 + 1;
-        let end_column = end_position.column as u32 + 1;
+        let end_column = end_col as u32 + 1;
 
         Location {
             offset_start,
@@ -1765,11 +1775,12 @@ impl<'a> Builder<'a> {
                 let source_snippet = String::from_utf8_lossy(
                     &code[location.offset_start as usize..location.offset_end as usize],
                 );
-                self.errors.push(anyhow::anyhow!(
-                    "Parse error: invalid syntax at line {}:{} near '{}'",
-                    location.start_line,
-                    location.start_column,
-                    source_snippet.chars().take(30).collect::<String>()
+                self.errors.push(SyntaxError::new(
+                    location,
+                    format!(
+                        "invalid syntax near `{}`",
+                        source_snippet.chars().take(30).collect::<String>()
+                    ),
                 ));
             }
         }
@@ -0,0 +1,478 @@
+//! Structural comparison of source files.
+//!
+//! [`diff`] compares two [`SourceFile`]s and lists where their trees differ.
+//! Like the [structural hash](crate::hash), it looks at node kinds, names,
+//! literal values, operator kinds, and visibility, but not at node IDs,
+//! [`Location`](crate::nodes::Location)s or the raw source text, so
+//! reformatting a file produces no differences. It is meant for golden and
+//! round-trip tests, where a list of differing paths is far easier to act on
+//! than an unequal hash.
+//!
+//! Paths name the fields from the source file down to the differing node,
+//! with list indices in brackets, for example
+//! `definitions[0].body.statements[1].expression`.
+
+use std::fmt::{self, Display, Formatter};
+use std::rc::Rc;
+
+use crate::nodes::{
+    ArgumentType, BlockType, Definition, Directive, Expression, Identifier, Literal, OperatorKind,
+    SourceFile, Statement, StructField, Type, UnaryOperatorKind, Visibility,
+};
+
+/// A single difference between two trees.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AstDiff {
+    /// Path from the source file to the differing node or attribute.
+    pub path: String,
+    /// What is at `path` in the first tree, or `none` if nothing is.
+    pub left: String,
+    /// What is at `path` in the second tree, or `none` if nothing is.
+    pub right: String,
+}
+
+impl Display for AstDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {} != {}", self.path, self.left, self.right)
+    }
+}
+
+/// Returns the differences between `a` and `b`, in tree order.
+///
+/// Returns an empty list if the files have the same structure. When two
+/// nodes at the same path have different kinds, that is reported once and
+/// their children are not compared.
+#[must_use]
+pub fn diff(a: &SourceFile, b: &SourceFile) -> Vec<AstDiff> {
+    let mut diffs = Vec::new();
+    compare_shapes(
+        "",
+        &Shape::source_file(a),
+        &Shape::source_file(b),
+        &mut diffs,
+    );
+    diffs
+}
+
+/// Location-free view of a node: its kind, an optional value for leaves
+/// (names and literals), and its named fields in a fixed order.
+struct Shape {
+    kind: &'static str,
+    value: Option<String>,
+    fields: Vec<(&'static str, Field)>,
+}
+
+/// Content of a node field.
+enum Field {
+    /// An absent optional child.
+    None,
+    /// A scalar attribute, such as visibility or an operator.
+    Value(String),
+    Node(Shape),
+    List(Vec<Shape>),
+}
+
+impl Shape {
+    fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            value: None,
+            fields: Vec::new(),
+        }
+    }
+
+    fn leaf(kind: &'static str, value: String) -> Self {
+        Self {
+            kind,
+            value: Some(value),
+            fields: Vec::new(),
+        }
+    }
+
+    fn with(mut self, name: &'static str, field: Field) -> Self {
+        self.fields.push((name, field));
+        self
+    }
+
+    fn node(self, name: &'static str, shape: Shape) -> Self {
+        self.with(name, Field::Node(shape))
+    }
+
+    fn value(self, name: &'static str, value: impl Into<String>) -> Self {
+        self.with(name, Field::Value(value.into()))
+    }
+
+    fn flag(self, name: &'static str, value: bool) -> Self {
+        self.value(name, value.to_string())
+    }
+
+    fn visibility(self, visibility: &Visibility) -> Self {
+        let value = match visibility {
+            Visibility::Private => "private",
+            Visibility::Public => "pub",
+        };
+        self.value("visibility", value)
+    }
+
+    fn list<T>(self, name: &'static str, items: &[T], lower: impl Fn(&T) -> Shape) -> Self {
+        self.with(name, Field::List(items.iter().map(lower).collect()))
+    }
+
+    fn optional_node<T>(
+        self,
+        name: &'static str,
+        value: Option<&T>,
+        lower: impl Fn(&T) -> Shape,
+    ) -> Self {
+        let field = value.map_or(Field::None, |inner| Field::Node(lower(inner)));
+        self.with(name, field)
+    }
+
+    fn optional_list<T>(
+        self,
+        name: &'static str,
+        items: Option<&Vec<T>>,
+        lower: impl Fn(&T) -> Shape,
+    ) -> Self {
+        let field = items.map_or(Field::None, |items| {
+            Field::List(items.iter().map(lower).collect())
+        });
+        self.with(name, field)
+    }
+
+    fn describe(&self) -> String {
+        match &self.value {
+            Some(value) => format!("{}({value})", self.kind),
+            None => self.kind.to_string(),
+        }
+    }
+
+    fn source_file(file: &SourceFile) -> Self {
+        Self::new("SourceFile")
+            .list("directives", &file.directives, Self::directive)
+            .list("definitions", &file.definitions, Self::definition)
+    }
+
+    fn directive(directive: &Directive) -> Self {
+        match directive {
+            Directive::Use(use_directive) => Self::new("Use")
+                .optional_list(
+                    "imported_types",
+                    use_directive.imported_types.as_ref(),
+                    |id| Self::identifier(id),
+                )
+                .optional_list("segments", use_directive.segments.as_ref(), |id| {
+                    Self::identifier(id)
+                })
+                .optional_node("from", use_directive.from.as_ref(), |from| {
+                    Self::leaf("Path", format!("{from:?}"))
+                }),
+        }
+    }
+
+    fn identifier(identifier: &Identifier) -> Self {
+        Self::leaf("Identifier", identifier.name.clone())
+    }
+
+    fn identifiers(self, name: &'static str, identifiers: Option<&Vec<Rc<Identifier>>>) -> Self {
+        self.optional_list(name, identifiers, |id| Self::identifier(id))
+    }
+
+    fn definition(definition: &Definition) -> Self {
+        match definition {
+            Definition::Spec(spec) => Self::new("Spec")
+                .visibility(&spec.visibility)
+                .node("name", Self::identifier(&spec.name))
+                .list("definitions", &spec.definitions, Self::definition),
+            Definition::Struct(def) => Self::new("Struct")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .list("fields", &def.fields, |field| Self::struct_field(field))
+                .list("methods", &def.methods, |method| {
+                    Self::definition(&Definition::Function(method.clone()))
+                }),
+            Definition::Enum(def) => Self::new("Enum")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .list("variants", &def.variants, |variant| {
+                    Self::identifier(variant)
+                }),
+            Definition::Constant(def) => Self::new("Constant")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .node("ty", Self::ty(&def.ty))
+                .node("value", Self::literal(&def.value)),
+            Definition::Function(def) => Self::new("Function")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .identifiers("type_parameters", def.type_parameters.as_ref())
+                .optional_list("arguments", def.arguments.as_ref(), Self::argument)
+                .optional_node("returns", def.returns.as_ref(), Self::ty)
+                .node("body", Self::block_type(&def.body)),
+            Definition::ExternalFunction(def) => Self::new("ExternalFunction")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .optional_list("arguments", def.arguments.as_ref(), Self::argument)
+                .optional_node("returns", def.returns.as_ref(), Self::ty),
+            Definition::Type(def) => Self::new("TypeDefinition")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .node("ty", Self::ty(&def.ty)),
+            Definition::Module(def) => Self::new("Module")
+                .visibility(&def.visibility)
+                .node("name", Self::identifier(&def.name))
+                .optional_list("body", def.body.as_ref(), Self::definition),
+        }
+    }
+
+    fn struct_field(field: &StructField) -> Self {
+        Self::new("StructField")
+            .node("name", Self::identifier(&field.name))
+            .node("ty", Self::ty(&field.type_))
+    }
+
+    fn argument(argument: &ArgumentType) -> Self {
+        match argument {
+            ArgumentType::SelfReference(self_ref) => {
+                Self::new("SelfReference").flag("is_mut", self_ref.is_mut)
+            }
+            ArgumentType::IgnoreArgument(ignore) => {
+                Self::new("IgnoreArgument").node("ty", Self::ty(&ignore.ty))
+            }
+            ArgumentType::Argument(arg) => Self::new("Argument")
+                .node("name", Self::identifier(&arg.name))
+                .flag("is_mut", arg.is_mut)
+                .node("ty", Self::ty(&arg.ty)),
+            ArgumentType::Type(ty) => Self::new("TypeArgument").node("ty", Self::ty(ty)),
+        }
+    }
+
+    fn block_type(block: &BlockType) -> Self {
+        let (kind, block) = match block {
+            BlockType::Block(b) => ("Block", b),
+            BlockType::Assume(b) => ("Assume", b),
+            BlockType::Forall(b) => ("Forall", b),
+            BlockType::Exists(b) => ("Exists", b),
+            BlockType::Unique(b) => ("Unique", b),
+        };
+        Self::new(kind).list("statements", &block.statements, Self::statement)
+    }
+
+    fn statement(statement: &Statement) -> Self {
+        match statement {
+            Statement::Block(block) => Self::block_type(block),
+            Statement::Expression(expression) => {
+                Self::new("ExpressionStatement").node("expression", Self::expression(expression))
+            }
+            Statement::Assign(assign) => Self::new("Assign")
+                .node("left", Self::expression(&assign.left.borrow()))
+                .node("right", Self::expression(&assign.right.borrow())),
+            Statement::Return(ret) => {
+                Self::new("Return").node("expression", Self::expression(&ret.expression.borrow()))
+            }
+            Statement::Loop(loop_stmt) => Self::new("Loop")
+                .optional_node(
+                    "condition",
+                    loop_stmt.condition.borrow().as_ref(),
+                    Self::expression,
+                )
+                .node("body", Self::block_type(&loop_stmt.body)),
+            Statement::Break(_) => Self::new("Break"),
+            Statement::If(if_stmt) => Self::new("If")
+                .node("condition", Self::expression(&if_stmt.condition.borrow()))
+                .node("if_arm", Self::block_type(&if_stmt.if_arm))
+                .optional_node("else_arm", if_stmt.else_arm.as_ref(), Self::block_type),
+            Statement::VariableDefinition(var) => Self::new("VariableDefinition")
+                .node("name", Self::identifier(&var.name))
+                .node("ty", Self::ty(&var.ty))
+                .optional_node("value", var.value.as_ref(), |value| {
+                    Self::expression(&value.borrow())
+                })
+                .flag("is_uzumaki", var.is_uzumaki),
+            Statement::TypeDefinition(def) => Self::new("TypeDefinitionStatement")
+                .node("name", Self::identifier(&def.name))
+                .node("ty", Self::ty(&def.ty)),
+            Statement::Assert(assert) => Self::new("Assert")
+                .node("expression", Self::expression(&assert.expression.borrow())),
+            Statement::ConstantDefinition(def) => {
+                Self::definition(&Definition::Constant(def.clone()))
+            }
+        }
+    }
+
+    fn expression(expression: &Expression) -> Self {
+        match expression {
+            Expression::ArrayIndexAccess(access) => Self::new("ArrayIndexAccess")
+                .node("array", Self::expression(&access.array.borrow()))
+                .node("index", Self::expression(&access.index.borrow())),
+            Expression::Binary(binary) => Self::new("Binary")
+                .value("operator", operator_symbol(&binary.operator))
+                .node("left", Self::expression(&binary.left.borrow()))
+                .node("right", Self::expression(&binary.right.borrow())),
+            Expression::MemberAccess(access) => Self::new("MemberAccess")
+                .node("expression", Self::expression(&access.expression.borrow()))
+                .node("name", Self::identifier(&access.name)),
+            Expression::TypeMemberAccess(access) => Self::new("TypeMemberAccess")
+                .node("expression", Self::expression(&access.expression.borrow()))
+                .node("name", Self::identifier(&access.name)),
+            Expression::FunctionCall(call) => Self::new("FunctionCall")
+                .node("function", Self::expression(&call.function))
+                .identifiers("type_parameters", call.type_parameters.as_ref())
+                .optional_list("arguments", call.arguments.as_ref(), |(label, value)| {
+                    Self::new("CallArgument")
+                        .optional_node("label", label.as_ref(), |label| Self::identifier(label))
+                        .node("value", Self::expression(&value.borrow()))
+                }),
+            Expression::Struct(expr) => Self::new("StructExpression")
+                .node("name", Self::identifier(&expr.name))
+                .optional_list("fields", expr.fields.as_ref(), |(name, value)| {
+                    Self::new("FieldInitializer")
+                        .node("name", Self::identifier(name))
+                        .node("value", Self::expression(&value.borrow()))
+                }),
+            Expression::PrefixUnary(unary) => Self::new("PrefixUnary")
+                .value("operator", unary_operator_symbol(&unary.operator))
+                .node("expression", Self::expression(&unary.expression.borrow())),
+            Expression::Parenthesized(paren) => Self::new("Parenthesized")
+                .node("expression", Self::expression(&paren.expression.borrow())),
+            Expression::Literal(literal) => Self::literal(literal),
+            Expression::Identifier(identifier) => Self::identifier(identifier),
+            Expression::Type(ty) => Self::ty(ty),
+            Expression::Uzumaki(_) => Self::new("Uzumaki"),
+        }
+    }
+
+    fn literal(literal: &Literal) -> Self {
+        match literal {
+            Literal::Array(array) => Self::new("ArrayLiteral").optional_list(
+                "elements",
+                array.elements.as_ref(),
+                |element| Self::expression(&element.borrow()),
+            ),
+            Literal::Bool(value) => Self::leaf("BoolLiteral", value.value.to_string()),
+            Literal::String(value) => Self::leaf("StringLiteral", format!("{:?}", value.value)),
+            Literal::Number(value) => Self::leaf("NumberLiteral", value.value.clone()),
+            Literal::Unit(_) => Self::new("UnitLiteral"),
+        }
+    }
+
+    fn ty(ty: &Type) -> Self {
+        match ty {
+            Type::Array(array) => Self::new("ArrayType")
+                .node("element_type", Self::ty(&array.element_type))
+                .node("size", Self::expression(&array.size)),
+            Type::Simple(kind) => Self::leaf("SimpleType", kind.as_str().to_string()),
+            Type::Generic(generic) => Self::new("GenericType")
+                .node("base", Self::identifier(&generic.base))
+                .list("parameters", &generic.parameters, |param| {
+                    Self::identifier(param)
+                }),
+            Type::Function(function) => Self::new("FunctionType")
+                .optional_list("parameters", function.parameters.as_ref(), Self::ty)
+                .optional_node("returns", function.returns.as_ref(), Self::ty),
+            Type::QualifiedName(name) => Self::new("QualifiedName")
+                .node("qualifier", Self::identifier(&name.qualifier))
+                .node("name", Self::identifier(&name.name)),
+            Type::Qualified(name) => Self::new("TypeQualifiedName")
+                .node("alias", Self::identifier(&name.alias))
+                .node("name", Self::identifier(&name.name)),
+            Type::Custom(identifier) => {
+                Self::new("CustomType").node("name", Self::identifier(identifier))
+            }
+        }
+    }
+}
+
+impl Field {
+    fn describe(&self) -> String {
+        match self {
+            Field::None => "none".to_string(),
+            Field::Value(value) => value.clone(),
+            Field::Node(shape) => shape.describe(),
+            Field::List(items) => format!("{} items", items.len()),
+        }
+    }
+}
+
+fn push(diffs: &mut Vec<AstDiff>, path: &str, left: String, right: String) {
+    diffs.push(AstDiff {
+        path: path.to_string(),
+        left,
+        right,
+    });
+}
+
+fn compare_shapes(path: &str, a: &Shape, b: &Shape, diffs: &mut Vec<AstDiff>) {
+    if a.kind != b.kind || a.value != b.value {
+        push(diffs, path, a.describe(), b.describe());
+        return;
+    }
+    // Nodes of the same kind have the same fields in the same order.
+    for ((name, left), (_, right)) in a.fields.iter().zip(&b.fields) {
+        let field_path = if path.is_empty() {
+            (*name).to_string()
+        } else {
+            format!("{path}.{name}")
+        };
+        compare_fields(&field_path, left, right, diffs);
+    }
+}
+
+fn compare_fields(path: &str, a: &Field, b: &Field, diffs: &mut Vec<AstDiff>) {
+    match (a, b) {
+        (Field::None, Field::None) => {}
+        (Field::Value(left), Field::Value(right)) => {
+            if left != right {
+                push(diffs, path, left.clone(), right.clone());
+            }
+        }
+        (Field::Node(left), Field::Node(right)) => compare_shapes(path, left, right, diffs),
+        (Field::List(left), Field::List(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let item_path = format!("{path}[{index}]");
+                match (left.get(index), right.get(index)) {
+                    (Some(l), Some(r)) => compare_shapes(&item_path, l, r, diffs),
+                    (Some(l), None) => push(diffs, &item_path, l.describe(), "none".to_string()),
+                    (None, Some(r)) => push(diffs, &item_path, "none".to_string(), r.describe()),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => push(diffs, path, a.describe(), b.describe()),
+    }
+}
+
+/// Returns the source spelling of a binary operator.
+fn operator_symbol(operator: &OperatorKind) -> &'static str {
+    match operator {
+        OperatorKind::Pow => "**",
+        OperatorKind::Add => "+",
+        OperatorKind::Sub => "-",
+        OperatorKind::Mul => "*",
+        OperatorKind::Div => "/",
+        OperatorKind::Mod => "%",
+        OperatorKind::And => "&&",
+        OperatorKind::Or => "||",
+        OperatorKind::Eq => "==",
+        OperatorKind::Ne => "!=",
+        OperatorKind::Lt => "<",
+        OperatorKind::Le => "<=",
+        OperatorKind::Gt => ">",
+        OperatorKind::Ge => ">=",
+        OperatorKind::BitAnd => "&",
+        OperatorKind::BitOr => "|",
+        OperatorKind::BitXor => "^",
+        OperatorKind::BitNot => "~",
+        OperatorKind::Shl => "<<",
+        OperatorKind::Shr => ">>",
+    }
+}
+
+/// Returns the source spelling of a unary operator.
+fn unary_operator_symbol(operator: &UnaryOperatorKind) -> &'static str {
+    match operator {
+        UnaryOperatorKind::Not => "!",
+        UnaryOperatorKind::Neg => "-",
+        UnaryOperatorKind::BitNot => "~",
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! This module defines structured errors for AST parsing and module resolution.
 
+use std::fmt::{self, Display, Formatter};
 use std::path::PathBuf;
 
 use thiserror::Error;
 
+use crate::nodes::Location;
+
 /// Errors that can occur during external module parsing and resolution.
 #[derive(Debug, Error)]
 #[must_use = "errors must not be silently ignored"]
@@ -29,4 +32,45 @@ pub enum AstError {
     /// Failed to build AST from parsed tree.
     #[error("failed to build AST for {path}: {reason}")]
     AstBuildError { path: PathBuf, reason: String },
+
+    /// Source is larger than the configured maximum size.
+    #[error("source is {size} bytes, more than the maximum of {max_size} bytes")]
+    SourceTooLarge { size: usize, max_size: usize },
+}
+
+/// A syntax error found while building the AST, tied to its source location.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{location}: {message}")]
+pub struct SyntaxError {
+    /// Description of the error, without the location.
+    pub message: String,
+    /// Span of the malformed source.
+    pub location: Location,
+}
+
+impl SyntaxError {
+    #[must_use]
+    pub fn new(location: Location, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location,
+        }
+    }
 }
+
+/// All syntax errors collected while building an AST.
+///
+/// Returned (inside `anyhow::Error`) by
+/// [`Builder::build_ast`](crate::builder::Builder::build_ast); downcast to
+/// access the individual errors and their locations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxErrors(pub Vec<SyntaxError>);
+
+impl Display for SyntaxErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        f.write_str(&messages.join("; "))
+    }
+}
+
+impl std::error::Error for SyntaxErrors {}
@@ -11,6 +11,7 @@ use rustc_hash::FxHashMap;
 use crate::arena::Arena;
 use crate::builder::Builder;
 use crate::errors::AstError;
+use crate::source::{DEFAULT_MAX_SOURCE_SIZE, check_size, strip_bom};
 
 /// Represents a parsed external module
 #[derive(Clone)]
@@ -70,6 +71,7 @@ pub fn create_empty_prelude() -> ExternPrelude {
 /// Returns an error if:
 /// - No module root file is found in standard locations
 /// - The source file cannot be read
+/// - The source file is larger than [`DEFAULT_MAX_SOURCE_SIZE`]
 /// - The source code fails to parse
 ///
 /// # Panics
@@ -107,6 +109,11 @@ pub fn parse_external_module(
         path: root_path.clone(),
         source: e,
     })?;
+    check_size(source.len(), DEFAULT_MAX_SOURCE_SIZE).map_err(|e| AstError::AstBuildError {
+        path: root_path.clone(),
+        reason: e.to_string(),
+    })?;
+    let source = strip_bom(&source);
 
     let inference_language = tree_sitter_inference::language();
     let mut parser = tree_sitter::Parser::new();
@@ -115,7 +122,7 @@ pub fn parse_external_module(
         .expect("Error loading Inference grammar");
 
     let tree = parser
-        .parse(&source, None)
+        .parse(source, None)
         .ok_or_else(|| AstError::ParseError {
             path: root_path.clone(),
         })?;
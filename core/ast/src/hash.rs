@@ -0,0 +1,491 @@
+//! Structural hashing of AST subtrees.
+//!
+//! [`AstNode::structural_hash`] summarizes the *structure* of a subtree as a
+//! `u64`: node kinds, names, literal values, operator kinds, and visibility
+//! are hashed, while node IDs, [`Location`](crate::nodes::Location)s and the
+//! raw source text are not. Two subtrees that differ only in formatting,
+//! whitespace, or comments therefore hash the same, and a subtree keeps its
+//! hash when it moves to a different place in the file.
+//!
+//! # Stability
+//!
+//! The hash is computed with 64-bit FNV-1a over a self-describing encoding:
+//! every node contributes its kind name, strings are length-prefixed, and
+//! lists and optional children record their length or presence. The encoding
+//! is prefixed with [`STRUCTURAL_HASH_VERSION`].
+//!
+//! For a given `STRUCTURAL_HASH_VERSION`, the hash of a subtree is the same
+//! on every platform, in every process, and in every release of this crate.
+//! Any change that alters the hash of an existing subtree (new fields, a
+//! different encoding) must bump the version, so hashes persisted by earlier
+//! releases (for example in build fingerprints) are invalidated rather than
+//! silently matching different structures.
+
+use std::rc::Rc;
+
+use crate::nodes::{
+    ArgumentType, Ast, AstNode, BlockType, Definition, Directive, Expression, Identifier, Literal,
+    Misc, OperatorKind, SimpleTypeKind, SourceFile, Statement, StructField, Type,
+    UnaryOperatorKind, Visibility,
+};
+
+/// Version of the structural hash encoding, fed into every hash.
+pub const STRUCTURAL_HASH_VERSION: u8 = 1;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl AstNode {
+    /// Returns a hash of this subtree's structure, ignoring IDs and locations.
+    ///
+    /// See the [module documentation](crate::hash) for the stability guarantees.
+    #[must_use]
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = StructuralHasher::new();
+        hasher.node(self);
+        hasher.finish()
+    }
+}
+
+/// Visitor that feeds the structure of AST nodes into an FNV-1a state.
+struct StructuralHasher {
+    state: u64,
+}
+
+impl StructuralHasher {
+    fn new() -> Self {
+        let mut hasher = Self {
+            state: FNV_OFFSET_BASIS,
+        };
+        hasher.write_bytes(&[STRUCTURAL_HASH_VERSION]);
+        hasher
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.write_bytes(&(len as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write_bytes(value.as_bytes());
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[u8::from(value)]);
+    }
+
+    fn kind(&mut self, name: &str) {
+        self.write_str(name);
+    }
+
+    fn option<T>(&mut self, value: Option<&T>, mut visit: impl FnMut(&mut Self, &T)) {
+        match value {
+            Some(inner) => {
+                self.write_bool(true);
+                visit(self, inner);
+            }
+            None => self.write_bool(false),
+        }
+    }
+
+    fn list<T>(&mut self, items: &[T], mut visit: impl FnMut(&mut Self, &T)) {
+        self.write_len(items.len());
+        for item in items {
+            visit(self, item);
+        }
+    }
+
+    fn node(&mut self, node: &AstNode) {
+        match node {
+            AstNode::Ast(Ast::SourceFile(file)) => self.source_file(file),
+            AstNode::Directive(directive) => self.directive(directive),
+            AstNode::Definition(definition) => self.definition(definition),
+            AstNode::BlockType(block) => self.block_type(block),
+            AstNode::Statement(statement) => self.statement(statement),
+            AstNode::Expression(expression) => self.expression(expression),
+            AstNode::Literal(literal) => self.literal(literal),
+            AstNode::Type(ty) => self.ty(ty),
+            AstNode::ArgumentType(argument) => self.argument(argument),
+            AstNode::Misc(Misc::StructField(field)) => self.struct_field(field),
+        }
+    }
+
+    fn source_file(&mut self, file: &SourceFile) {
+        self.kind("SourceFile");
+        self.list(&file.directives, Self::directive);
+        self.list(&file.definitions, Self::definition);
+    }
+
+    fn directive(&mut self, directive: &Directive) {
+        match directive {
+            Directive::Use(use_directive) => {
+                self.kind("Use");
+                self.option(use_directive.imported_types.as_ref(), |h, ids| {
+                    h.list(ids, |h, id| h.identifier(id));
+                });
+                self.option(use_directive.segments.as_ref(), |h, ids| {
+                    h.list(ids, |h, id| h.identifier(id));
+                });
+                self.option(use_directive.from.as_ref(), |h, from| h.write_str(from));
+            }
+        }
+    }
+
+    fn identifier(&mut self, identifier: &Identifier) {
+        self.kind("Identifier");
+        self.write_str(&identifier.name);
+    }
+
+    fn identifiers(&mut self, identifiers: Option<&Vec<Rc<Identifier>>>) {
+        self.option(identifiers, |h, ids| h.list(ids, |h, id| h.identifier(id)));
+    }
+
+    fn visibility(&mut self, visibility: &Visibility) {
+        self.write_bool(*visibility == Visibility::Public);
+    }
+
+    fn definition(&mut self, definition: &Definition) {
+        match definition {
+            Definition::Spec(spec) => {
+                self.kind("Spec");
+                self.visibility(&spec.visibility);
+                self.identifier(&spec.name);
+                self.list(&spec.definitions, Self::definition);
+            }
+            Definition::Struct(def) => {
+                self.kind("Struct");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.list(&def.fields, |h, field| h.struct_field(field));
+                self.list(&def.methods, |h, method| {
+                    h.definition(&Definition::Function(method.clone()));
+                });
+            }
+            Definition::Enum(def) => {
+                self.kind("Enum");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.list(&def.variants, |h, variant| h.identifier(variant));
+            }
+            Definition::Constant(def) => {
+                self.kind("Constant");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.ty(&def.ty);
+                self.literal(&def.value);
+            }
+            Definition::Function(def) => {
+                self.kind("Function");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.identifiers(def.type_parameters.as_ref());
+                self.option(def.arguments.as_ref(), |h, args| {
+                    h.list(args, Self::argument);
+                });
+                self.option(def.returns.as_ref(), Self::ty);
+                self.block_type(&def.body);
+            }
+            Definition::ExternalFunction(def) => {
+                self.kind("ExternalFunction");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.option(def.arguments.as_ref(), |h, args| {
+                    h.list(args, Self::argument);
+                });
+                self.option(def.returns.as_ref(), Self::ty);
+            }
+            Definition::Type(def) => {
+                self.kind("TypeDefinition");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.ty(&def.ty);
+            }
+            Definition::Module(def) => {
+                self.kind("Module");
+                self.visibility(&def.visibility);
+                self.identifier(&def.name);
+                self.option(def.body.as_ref(), |h, body| h.list(body, Self::definition));
+            }
+        }
+    }
+
+    fn struct_field(&mut self, field: &StructField) {
+        self.kind("StructField");
+        self.identifier(&field.name);
+        self.ty(&field.type_);
+    }
+
+    fn argument(&mut self, argument: &ArgumentType) {
+        match argument {
+            ArgumentType::SelfReference(self_ref) => {
+                self.kind("SelfReference");
+                self.write_bool(self_ref.is_mut);
+            }
+            ArgumentType::IgnoreArgument(ignore) => {
+                self.kind("IgnoreArgument");
+                self.ty(&ignore.ty);
+            }
+            ArgumentType::Argument(arg) => {
+                self.kind("Argument");
+                self.identifier(&arg.name);
+                self.write_bool(arg.is_mut);
+                self.ty(&arg.ty);
+            }
+            ArgumentType::Type(ty) => {
+                self.kind("TypeArgument");
+                self.ty(ty);
+            }
+        }
+    }
+
+    fn block_type(&mut self, block: &BlockType) {
+        let (kind, block) = match block {
+            BlockType::Block(b) => ("Block", b),
+            BlockType::Assume(b) => ("Assume", b),
+            BlockType::Forall(b) => ("Forall", b),
+            BlockType::Exists(b) => ("Exists", b),
+            BlockType::Unique(b) => ("Unique", b),
+        };
+        self.kind(kind);
+        self.list(&block.statements, Self::statement);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.block_type(block),
+            Statement::Expression(expression) => {
+                self.kind("ExpressionStatement");
+                self.expression(expression);
+            }
+            Statement::Assign(assign) => {
+                self.kind("Assign");
+                self.expression(&assign.left.borrow());
+                self.expression(&assign.right.borrow());
+            }
+            Statement::Return(ret) => {
+                self.kind("Return");
+                self.expression(&ret.expression.borrow());
+            }
+            Statement::Loop(loop_stmt) => {
+                self.kind("Loop");
+                self.option(loop_stmt.condition.borrow().as_ref(), Self::expression);
+                self.block_type(&loop_stmt.body);
+            }
+            Statement::Break(_) => self.kind("Break"),
+            Statement::If(if_stmt) => {
+                self.kind("If");
+                self.expression(&if_stmt.condition.borrow());
+                self.block_type(&if_stmt.if_arm);
+                self.option(if_stmt.else_arm.as_ref(), Self::block_type);
+            }
+            Statement::VariableDefinition(var) => {
+                self.kind("VariableDefinition");
+                self.identifier(&var.name);
+                self.ty(&var.ty);
+                self.option(var.value.as_ref(), |h, value| h.expression(&value.borrow()));
+                self.write_bool(var.is_uzumaki);
+            }
+            Statement::TypeDefinition(def) => {
+                self.kind("TypeDefinitionStatement");
+                self.identifier(&def.name);
+                self.ty(&def.ty);
+            }
+            Statement::Assert(assert) => {
+                self.kind("Assert");
+                self.expression(&assert.expression.borrow());
+            }
+            Statement::ConstantDefinition(def) => {
+                self.definition(&Definition::Constant(def.clone()));
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::ArrayIndexAccess(access) => {
+                self.kind("ArrayIndexAccess");
+                self.expression(&access.array.borrow());
+                self.expression(&access.index.borrow());
+            }
+            Expression::Binary(binary) => {
+                self.kind("Binary");
+                self.write_str(operator_name(&binary.operator));
+                self.expression(&binary.left.borrow());
+                self.expression(&binary.right.borrow());
+            }
+            Expression::MemberAccess(access) => {
+                self.kind("MemberAccess");
+                self.expression(&access.expression.borrow());
+                self.identifier(&access.name);
+            }
+            Expression::TypeMemberAccess(access) => {
+                self.kind("TypeMemberAccess");
+                self.expression(&access.expression.borrow());
+                self.identifier(&access.name);
+            }
+            Expression::FunctionCall(call) => {
+                self.kind("FunctionCall");
+                self.expression(&call.function);
+                self.identifiers(call.type_parameters.as_ref());
+                self.option(call.arguments.as_ref(), |h, args| {
+                    h.list(args, |h, (label, value)| {
+                        h.option(label.as_ref(), |h, label| h.identifier(label));
+                        h.expression(&value.borrow());
+                    });
+                });
+            }
+            Expression::Struct(expr) => {
+                self.kind("StructExpression");
+                self.identifier(&expr.name);
+                self.option(expr.fields.as_ref(), |h, fields| {
+                    h.list(fields, |h, (name, value)| {
+                        h.identifier(name);
+                        h.expression(&value.borrow());
+                    });
+                });
+            }
+            Expression::PrefixUnary(unary) => {
+                self.kind("PrefixUnary");
+                self.write_str(unary_operator_name(&unary.operator));
+                self.expression(&unary.expression.borrow());
+            }
+            Expression::Parenthesized(paren) => {
+                self.kind("Parenthesized");
+                self.expression(&paren.expression.borrow());
+            }
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::Identifier(identifier) => self.identifier(identifier),
+            Expression::Type(ty) => self.ty(ty),
+            Expression::Uzumaki(_) => self.kind("Uzumaki"),
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::Array(array) => {
+                self.kind("ArrayLiteral");
+                self.option(array.elements.as_ref(), |h, elements| {
+                    h.list(elements, |h, element| h.expression(&element.borrow()));
+                });
+            }
+            Literal::Bool(value) => {
+                self.kind("BoolLiteral");
+                self.write_bool(value.value);
+            }
+            Literal::String(value) => {
+                self.kind("StringLiteral");
+                self.write_str(&value.value);
+            }
+            Literal::Number(value) => {
+                self.kind("NumberLiteral");
+                self.write_str(&value.value);
+            }
+            Literal::Unit(_) => self.kind("UnitLiteral"),
+        }
+    }
+
+    fn ty(&mut self, ty: &Type) {
+        match ty {
+            Type::Array(array) => {
+                self.kind("ArrayType");
+                self.ty(&array.element_type);
+                self.expression(&array.size);
+            }
+            Type::Simple(kind) => {
+                self.kind("SimpleType");
+                self.write_str(simple_type_name(*kind));
+            }
+            Type::Generic(generic) => {
+                self.kind("GenericType");
+                self.identifier(&generic.base);
+                self.list(&generic.parameters, |h, param| h.identifier(param));
+            }
+            Type::Function(function) => {
+                self.kind("FunctionType");
+                self.option(function.parameters.as_ref(), |h, params| {
+                    h.list(params, Self::ty);
+                });
+                self.option(function.returns.as_ref(), Self::ty);
+            }
+            Type::QualifiedName(name) => {
+                self.kind("QualifiedName");
+                self.identifier(&name.qualifier);
+                self.identifier(&name.name);
+            }
+            Type::Qualified(name) => {
+                self.kind("TypeQualifiedName");
+                self.identifier(&name.alias);
+                self.identifier(&name.name);
+            }
+            Type::Custom(identifier) => {
+                self.kind("CustomType");
+                self.identifier(identifier);
+            }
+        }
+    }
+}
+
+/// Returns the name hashed for a simple type.
+///
+/// Spelled out rather than taken from [`SimpleTypeKind::as_str`] so that a
+/// change to the display form cannot change hashes.
+fn simple_type_name(kind: SimpleTypeKind) -> &'static str {
+    match kind {
+        SimpleTypeKind::Unit => "unit",
+        SimpleTypeKind::Bool => "bool",
+        SimpleTypeKind::I8 => "i8",
+        SimpleTypeKind::I16 => "i16",
+        SimpleTypeKind::I32 => "i32",
+        SimpleTypeKind::I64 => "i64",
+        SimpleTypeKind::U8 => "u8",
+        SimpleTypeKind::U16 => "u16",
+        SimpleTypeKind::U32 => "u32",
+        SimpleTypeKind::U64 => "u64",
+    }
+}
+
+/// Returns the name hashed for a binary operator.
+fn operator_name(operator: &OperatorKind) -> &'static str {
+    match operator {
+        OperatorKind::Pow => "Pow",
+        OperatorKind::Add => "Add",
+        OperatorKind::Sub => "Sub",
+        OperatorKind::Mul => "Mul",
+        OperatorKind::Div => "Div",
+        OperatorKind::Mod => "Mod",
+        OperatorKind::And => "And",
+        OperatorKind::Or => "Or",
+        OperatorKind::Eq => "Eq",
+        OperatorKind::Ne => "Ne",
+        OperatorKind::Lt => "Lt",
+        OperatorKind::Le => "Le",
+        OperatorKind::Gt => "Gt",
+        OperatorKind::Ge => "Ge",
+        OperatorKind::BitAnd => "BitAnd",
+        OperatorKind::BitOr => "BitOr",
+        OperatorKind::BitXor => "BitXor",
+        OperatorKind::BitNot => "BitNot",
+        OperatorKind::Shl => "Shl",
+        OperatorKind::Shr => "Shr",
+    }
+}
+
+/// Returns the name hashed for a unary operator.
+fn unary_operator_name(operator: &UnaryOperatorKind) -> &'static str {
+    match operator {
+        UnaryOperatorKind::Not => "Not",
+        UnaryOperatorKind::Neg => "Neg",
+        UnaryOperatorKind::BitNot => "BitNot",
+    }
+}
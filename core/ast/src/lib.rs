@@ -32,8 +32,13 @@
 //! - [`builder::Builder`] - Builds AST from tree-sitter concrete syntax tree
 //! - [`nodes`] - AST node type definitions (`SourceFile`, `FunctionDefinition`, etc.)
 //! - [`extern_prelude`] - External module discovery and parsing
+//! - [`hash`] - Structural hashing of subtrees, independent of formatting
+//! - [`diff`] - Structural differences between source files, for golden tests
+//! - [`obligations`] - Enumeration of spec obligations with stable ids, and spec warnings
 //! - [`parser_context::ParserContext`] - Multi-file parsing context (WIP)
 //! - [`errors`] - Structured error types for AST operations
+//! - [`source`] - BOM stripping and size limits for source text
+//! - [`stats`] - Node counts per source file
 //!
 //! # Key Features
 //!
@@ -60,9 +65,14 @@
 #![warn(clippy::pedantic)]
 pub mod arena;
 pub mod builder;
+pub mod diff;
 pub(crate) mod enums_impl;
 pub mod errors;
 pub mod extern_prelude;
+pub mod hash;
 pub mod nodes;
 pub(crate) mod nodes_impl;
+pub mod obligations;
 pub mod parser_context;
+pub mod source;
+pub mod stats;
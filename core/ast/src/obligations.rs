@@ -0,0 +1,432 @@
+//! Enumeration of specification obligations.
+//!
+//! An obligation is a construct the verifier has to discharge or may rely
+//! on: `assert` statements and `forall`, `exists`, `assume`, and `unique`
+//! blocks. [`collect`] lists them for a whole compilation unit so that
+//! verification results can be reported per obligation.
+//!
+//! Each obligation gets an id of the form `<function path>#<ordinal>`, e.g.
+//! `Spec::check#2`, where the ordinal counts obligations of the function in
+//! source order. Ids depend only on the structure of the program, so they
+//! survive reformatting of the source.
+//!
+//! [`warnings`] reports specification problems found along the way. The only
+//! one so far is [`ObligationWarning::SpecCaptureMutated`]: a variable read
+//! inside a `forall`, `exists`, or `unique` block is assigned later in the
+//! same function, so the fact established by the quantifier may no longer
+//! hold for it. Assignments anywhere in a loop around the quantifier count
+//! too, since they run again after the quantifier on the next iteration.
+//! Shadowing is not tracked, so the analysis errs towards reporting.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::rc::Rc;
+
+use crate::arena::Arena;
+use crate::nodes::{
+    AssertStatement, AssignStatement, Block, BlockType, Definition, Expression, FunctionDefinition,
+    Literal, Location, SourceFile, Statement,
+};
+
+/// Maximum length, in characters, of [`Obligation::expression_summary`].
+pub const SUMMARY_MAX_CHARS: usize = 60;
+
+/// The construct an obligation comes from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum ObligationKind {
+    Assert,
+    Assume,
+    Forall,
+    Exists,
+    Unique,
+}
+
+impl ObligationKind {
+    /// Returns the keyword introducing the construct.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ObligationKind::Assert => "assert",
+            ObligationKind::Assume => "assume",
+            ObligationKind::Forall => "forall",
+            ObligationKind::Exists => "exists",
+            ObligationKind::Unique => "unique",
+        }
+    }
+}
+
+/// A specification obligation found by [`collect`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Obligation {
+    /// Stable identifier, `<function>#<ordinal>`.
+    pub id: String,
+    pub kind: ObligationKind,
+    /// Path of the enclosing function, with spec, module, and struct names
+    /// joined by `::`.
+    pub function: String,
+    pub location: Location,
+    /// Source text of the asserted expression or of the whole block, with
+    /// whitespace collapsed and cut to [`SUMMARY_MAX_CHARS`].
+    pub expression_summary: String,
+}
+
+/// A problem with a specification found by [`warnings`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ObligationWarning {
+    /// `variable` is read inside the quantifier `obligation` and assigned at
+    /// `mutation` after it, or inside a loop around it.
+    SpecCaptureMutated {
+        /// Id of the quantifier obligation.
+        obligation: String,
+        variable: String,
+        /// Location of the quantifier block.
+        quantifier: Location,
+        /// Location of the assignment.
+        mutation: Location,
+    },
+}
+
+impl Display for ObligationWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ObligationWarning::SpecCaptureMutated {
+                obligation,
+                variable,
+                quantifier,
+                mutation,
+            } => write!(
+                f,
+                "`{variable}` is captured by quantifier {obligation} at {quantifier} and assigned at {mutation}"
+            ),
+        }
+    }
+}
+
+/// Returns the obligations of every source file in `arena`, in source order.
+///
+/// Source files are visited in the order they were added to the arena.
+/// Obligations nested in a block come after the block itself.
+#[must_use]
+pub fn collect(arena: &Arena) -> Vec<Obligation> {
+    sorted_source_files(arena)
+        .iter()
+        .flat_map(|source_file| Collector::run(source_file).obligations)
+        .collect()
+}
+
+/// Returns the specification warnings of every source file in `arena`.
+///
+/// Warnings are ordered by quantifier, then by assignment, in source order.
+#[must_use]
+pub fn warnings(arena: &Arena) -> Vec<ObligationWarning> {
+    sorted_source_files(arena)
+        .iter()
+        .flat_map(|source_file| Collector::run(source_file).warnings)
+        .collect()
+}
+
+fn sorted_source_files(arena: &Arena) -> Vec<Rc<SourceFile>> {
+    let mut source_files = arena.source_files();
+    source_files.sort_by_key(|source_file| source_file.id);
+    source_files
+}
+
+/// A quantifier block of the function being collected.
+struct Quantifier {
+    id: String,
+    location: Location,
+    /// Loops enclosing the quantifier, outermost first.
+    loops: Vec<Location>,
+    /// Variables defined inside the block.
+    defined: BTreeSet<String>,
+    /// Variables read inside the block before any definition there.
+    free: BTreeSet<String>,
+}
+
+struct Collector<'a> {
+    source: &'a str,
+    obligations: Vec<Obligation>,
+    warnings: Vec<ObligationWarning>,
+    scope: Vec<String>,
+    function: String,
+    ordinal: usize,
+    quantifiers: Vec<Quantifier>,
+    /// Indices into `quantifiers` of the blocks being visited.
+    open: Vec<usize>,
+    loops: Vec<Location>,
+    /// Assigned variables of the function with the assignment location.
+    assignments: Vec<(String, Location)>,
+}
+
+impl<'a> Collector<'a> {
+    fn run(source_file: &'a SourceFile) -> Self {
+        let mut collector = Collector {
+            source: &source_file.source,
+            obligations: Vec::new(),
+            warnings: Vec::new(),
+            scope: Vec::new(),
+            function: String::new(),
+            ordinal: 0,
+            quantifiers: Vec::new(),
+            open: Vec::new(),
+            loops: Vec::new(),
+            assignments: Vec::new(),
+        };
+        collector.definitions(&source_file.definitions);
+        collector
+    }
+
+    fn definitions(&mut self, definitions: &[Definition]) {
+        for definition in definitions {
+            match definition {
+                Definition::Spec(spec) => {
+                    self.scope.push(spec.name.name.clone());
+                    self.definitions(&spec.definitions);
+                    self.scope.pop();
+                }
+                Definition::Module(module) => {
+                    self.scope.push(module.name.name.clone());
+                    self.definitions(module.body.as_deref().unwrap_or_default());
+                    self.scope.pop();
+                }
+                Definition::Struct(struct_definition) => {
+                    self.scope.push(struct_definition.name.name.clone());
+                    for method in &struct_definition.methods {
+                        self.function(method);
+                    }
+                    self.scope.pop();
+                }
+                Definition::Function(function) => self.function(function),
+                Definition::Enum(_)
+                | Definition::Constant(_)
+                | Definition::ExternalFunction(_)
+                | Definition::Type(_) => {}
+            }
+        }
+    }
+
+    fn function(&mut self, function: &FunctionDefinition) {
+        self.scope.push(function.name.name.clone());
+        self.function = self.scope.join("::");
+        self.scope.pop();
+        self.ordinal = 0;
+        self.quantifiers.clear();
+        self.assignments.clear();
+        self.block_type(&function.body);
+        self.capture_warnings();
+    }
+
+    fn block_type(&mut self, block_type: &BlockType) {
+        let (kind, block) = match block_type {
+            BlockType::Block(block) => (None, block),
+            BlockType::Assume(block) => (Some(ObligationKind::Assume), block),
+            BlockType::Forall(block) => (Some(ObligationKind::Forall), block),
+            BlockType::Exists(block) => (Some(ObligationKind::Exists), block),
+            BlockType::Unique(block) => (Some(ObligationKind::Unique), block),
+        };
+        let quantifier = matches!(
+            kind,
+            Some(ObligationKind::Forall | ObligationKind::Exists | ObligationKind::Unique)
+        );
+        if quantifier {
+            self.open.push(self.quantifiers.len());
+            self.quantifiers.push(Quantifier {
+                id: format!("{}#{}", self.function, self.ordinal),
+                location: block.location,
+                loops: self.loops.clone(),
+                defined: BTreeSet::new(),
+                free: BTreeSet::new(),
+            });
+        }
+        if let Some(kind) = kind {
+            self.push(kind, block.location, block.location);
+        }
+        self.block(block);
+        if quantifier {
+            self.open.pop();
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        for statement in &block.statements {
+            match statement {
+                Statement::Block(block_type) => self.block_type(block_type),
+                Statement::Assert(assert) => self.assert(assert),
+                Statement::Loop(loop_statement) => {
+                    if let Some(condition) = &*loop_statement.condition.borrow() {
+                        self.expression(condition);
+                    }
+                    self.loops.push(loop_statement.location);
+                    self.block_type(&loop_statement.body);
+                    self.loops.pop();
+                }
+                Statement::If(if_statement) => {
+                    self.expression(&if_statement.condition.borrow());
+                    self.block_type(&if_statement.if_arm);
+                    if let Some(else_arm) = &if_statement.else_arm {
+                        self.block_type(else_arm);
+                    }
+                }
+                Statement::Expression(expression) => self.expression(expression),
+                Statement::Assign(assign) => self.assign(assign),
+                Statement::Return(return_statement) => {
+                    self.expression(&return_statement.expression.borrow());
+                }
+                Statement::VariableDefinition(definition) => {
+                    if let Some(value) = &definition.value {
+                        self.expression(&value.borrow());
+                    }
+                    self.define(&definition.name.name);
+                }
+                Statement::Break(_)
+                | Statement::TypeDefinition(_)
+                | Statement::ConstantDefinition(_) => {}
+            }
+        }
+    }
+
+    fn assert(&mut self, assert: &AssertStatement) {
+        let expression = assert.expression.borrow();
+        self.push(
+            ObligationKind::Assert,
+            assert.location,
+            expression.location(),
+        );
+        self.expression(&expression);
+    }
+
+    fn assign(&mut self, assign: &AssignStatement) {
+        let left = assign.left.borrow();
+        self.expression(&left);
+        self.expression(&assign.right.borrow());
+        if let Some(variable) = assigned_variable(&left) {
+            self.assignments.push((variable, assign.location));
+        }
+    }
+
+    /// Records the variables read by `expression` in the open quantifiers.
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.read(&identifier.name),
+            Expression::ArrayIndexAccess(access) => {
+                self.expression(&access.array.borrow());
+                self.expression(&access.index.borrow());
+            }
+            Expression::Binary(binary) => {
+                self.expression(&binary.left.borrow());
+                self.expression(&binary.right.borrow());
+            }
+            Expression::MemberAccess(access) => self.expression(&access.expression.borrow()),
+            Expression::FunctionCall(call) => {
+                // A plain identifier names the function, not a variable.
+                if !matches!(call.function, Expression::Identifier(_)) {
+                    self.expression(&call.function);
+                }
+                for (_, argument) in call.arguments.iter().flatten() {
+                    self.expression(&argument.borrow());
+                }
+            }
+            Expression::Struct(struct_expression) => {
+                for (_, value) in struct_expression.fields.iter().flatten() {
+                    self.expression(&value.borrow());
+                }
+            }
+            Expression::PrefixUnary(unary) => self.expression(&unary.expression.borrow()),
+            Expression::Parenthesized(parenthesized) => {
+                self.expression(&parenthesized.expression.borrow());
+            }
+            Expression::Literal(Literal::Array(array)) => {
+                for element in array.elements.iter().flatten() {
+                    self.expression(&element.borrow());
+                }
+            }
+            Expression::Literal(_)
+            | Expression::TypeMemberAccess(_)
+            | Expression::Type(_)
+            | Expression::Uzumaki(_) => {}
+        }
+    }
+
+    fn read(&mut self, name: &str) {
+        for &index in &self.open {
+            let quantifier = &mut self.quantifiers[index];
+            if !quantifier.defined.contains(name) {
+                quantifier.free.insert(name.to_string());
+            }
+        }
+    }
+
+    fn define(&mut self, name: &str) {
+        for &index in &self.open {
+            self.quantifiers[index].defined.insert(name.to_string());
+        }
+    }
+
+    /// Reports the assignments of the current function to variables captured
+    /// by one of its quantifiers.
+    fn capture_warnings(&mut self) {
+        for quantifier in &self.quantifiers {
+            for (variable, mutation) in &self.assignments {
+                if !quantifier.free.contains(variable) {
+                    continue;
+                }
+                let after = mutation.offset_start >= quantifier.location.offset_end;
+                let in_loop = quantifier.loops.iter().any(|loop_location| {
+                    loop_location.offset_start <= mutation.offset_start
+                        && mutation.offset_end <= loop_location.offset_end
+                });
+                if after || in_loop {
+                    self.warnings.push(ObligationWarning::SpecCaptureMutated {
+                        obligation: quantifier.id.clone(),
+                        variable: variable.clone(),
+                        quantifier: quantifier.location,
+                        mutation: *mutation,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Records an obligation at `location`, summarized by the source text at
+    /// `summary`.
+    fn push(&mut self, kind: ObligationKind, location: Location, summary: Location) {
+        let text = self
+            .source
+            .get(summary.offset_start as usize..summary.offset_end as usize)
+            .unwrap_or_default();
+        self.obligations.push(Obligation {
+            id: format!("{}#{}", self.function, self.ordinal),
+            kind,
+            function: self.function.clone(),
+            location,
+            expression_summary: summarize(text),
+        });
+        self.ordinal += 1;
+    }
+}
+
+/// Returns the variable whose value an assignment to `target` changes: `x`
+/// for `x`, `x[i]`, and `x.field`.
+fn assigned_variable(target: &Expression) -> Option<String> {
+    match target {
+        Expression::Identifier(identifier) => Some(identifier.name.clone()),
+        Expression::ArrayIndexAccess(access) => assigned_variable(&access.array.borrow()),
+        Expression::MemberAccess(access) => assigned_variable(&access.expression.borrow()),
+        Expression::Parenthesized(parenthesized) => {
+            assigned_variable(&parenthesized.expression.borrow())
+        }
+        _ => None,
+    }
+}
+
+/// Collapses whitespace runs in `text` and cuts it to [`SUMMARY_MAX_CHARS`],
+/// ending with `...` when cut.
+fn summarize(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= SUMMARY_MAX_CHARS {
+        return collapsed;
+    }
+    let mut summary: String = collapsed.chars().take(SUMMARY_MAX_CHARS - 3).collect();
+    summary.push_str("...");
+    summary
+}
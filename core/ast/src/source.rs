@@ -0,0 +1,42 @@
+//! Preparation of source text before parsing.
+//!
+//! Editors on Windows often save files with a leading UTF-8 byte order mark
+//! and CRLF line endings:
+//!
+//! - [`strip_bom`] removes the byte order mark so that it never reaches the
+//!   parser. Byte offsets in the AST are relative to the text after the mark,
+//!   which is also the text stored in
+//!   [`SourceFile::source`](crate::nodes::SourceFile), so slicing it with a
+//!   node's [`Location`](crate::nodes::Location) stays correct.
+//! - CRLF line endings need no preprocessing. The builder keeps a trailing
+//!   `\r` out of node text and spans, and columns are counted from the last
+//!   `\n`, so they match the LF version of the same file.
+//!
+//! [`check_size`] rejects sources above a size limit before they are parsed,
+//! so that passing a large binary file by accident fails immediately.
+
+use crate::errors::AstError;
+
+/// The UTF-8 byte order mark.
+pub const BOM: char = '\u{FEFF}';
+
+/// Default maximum source size in bytes (16 MiB).
+pub const DEFAULT_MAX_SOURCE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Returns `source` without its leading byte order mark, if any.
+#[must_use]
+pub fn strip_bom(source: &str) -> &str {
+    source.strip_prefix(BOM).unwrap_or(source)
+}
+
+/// Checks that a source of `size` bytes is at most `max_size` bytes.
+///
+/// # Errors
+///
+/// Returns [`AstError::SourceTooLarge`] if `size` exceeds `max_size`.
+pub fn check_size(size: usize, max_size: usize) -> Result<(), AstError> {
+    if size > max_size {
+        return Err(AstError::SourceTooLarge { size, max_size });
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Node counts for a source file.
+//!
+//! [`SourceFile::stats`] walks the descendants of a source file in the
+//! [`Arena`] and counts them by kind, for dashboards and for tracking how
+//! test programs grow over time.
+
+use crate::arena::Arena;
+use crate::nodes::{AstNode, Definition, SourceFile};
+
+/// Counts of the nodes in a source file, nested ones included.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SourceStats {
+    /// Function definitions, including methods and functions in specs.
+    pub functions: usize,
+    /// `external fn` declarations.
+    pub external_functions: usize,
+    /// Struct, enum, and type alias definitions.
+    pub types: usize,
+    /// Constant definitions, including ones inside function bodies.
+    pub constants: usize,
+    /// Spec definitions.
+    pub specs: usize,
+    /// Statements, counting every block (function bodies included) as one.
+    pub statements: usize,
+    /// Expressions, counting literals and identifiers. Names of
+    /// declarations are identifiers too, so they are included.
+    pub expressions: usize,
+}
+
+impl SourceFile {
+    /// Counts the nodes of this source file in `arena`.
+    ///
+    /// Returns all zeros if the source file is not in `arena`.
+    #[must_use]
+    pub fn stats(&self, arena: &Arena) -> SourceStats {
+        let mut stats = SourceStats::default();
+        for node in arena.get_children_cmp(self.id, |_| true) {
+            match node {
+                AstNode::Definition(definition) => match definition {
+                    Definition::Function(_) => stats.functions += 1,
+                    Definition::ExternalFunction(_) => stats.external_functions += 1,
+                    Definition::Struct(_) | Definition::Enum(_) | Definition::Type(_) => {
+                        stats.types += 1;
+                    }
+                    Definition::Constant(_) => stats.constants += 1,
+                    Definition::Spec(_) => stats.specs += 1,
+                    Definition::Module(_) => {}
+                },
+                AstNode::Statement(_) => stats.statements += 1,
+                AstNode::Expression(_) => stats.expressions += 1,
+                _ => {}
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,188 @@
+//! Compile-time defines passed with `-D`.
+//!
+//! `-D feature=NAME` enables the feature `NAME`, and `-D feature=NAME=false`
+//! declares it disabled. Features reach the program as predefined boolean
+//! constants, appended to the source before it is parsed:
+//!
+//! ```text
+//! infc src/main.inf --codegen -o -D feature=runtime-checks -D feature=logging=false
+//! // the source is compiled as if it ended with
+//! const FEATURE_LOGGING: bool = false;
+//! const FEATURE_RUNTIME_CHECKS: bool = true;
+//! ```
+//!
+//! The constant name is the feature name in upper case, with `-` replaced by
+//! `_`. Passing disabled features too lets the same source build with and
+//! without them. The constants are appended rather than prepended so that
+//! line numbers in diagnostics still match the file on disk.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+/// Key of a feature define.
+const FEATURE_KEY: &str = "feature";
+
+/// A `-D KEY=VALUE` define.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Define {
+    /// `feature=NAME[=BOOL]`: the feature `NAME` is enabled or disabled.
+    Feature {
+        /// Feature name as given.
+        name: String,
+        /// Whether the feature is enabled.
+        enabled: bool,
+    },
+}
+
+impl Define {
+    /// Returns the name of the predefined constant for this define.
+    fn constant_name(&self) -> String {
+        match self {
+            Self::Feature { name, .. } => {
+                format!("FEATURE_{}", name.to_uppercase().replace('-', "_"))
+            }
+        }
+    }
+
+    /// Returns the value of the predefined constant for this define.
+    fn value(&self) -> bool {
+        match self {
+            Self::Feature { enabled, .. } => *enabled,
+        }
+    }
+}
+
+/// Parses a `-D` value.
+///
+/// # Errors
+///
+/// Returns a message for clap if the value is not `feature=NAME`,
+/// `feature=NAME=true` or `feature=NAME=false` with a name made of letters,
+/// digits, `_` and `-` that starts with a letter or `_`.
+pub(crate) fn parse_define(value: &str) -> Result<Define, String> {
+    let Some((key, feature)) = value.split_once('=') else {
+        return Err(format!("expected KEY=VALUE, found `{value}`"));
+    };
+    if key != FEATURE_KEY {
+        return Err(format!("unknown define `{key}`; expected `{FEATURE_KEY}`"));
+    }
+    let (name, enabled) = match feature.split_once('=') {
+        None => (feature, true),
+        Some((name, "true")) => (name, true),
+        Some((name, "false")) => (name, false),
+        Some((_, state)) => {
+            return Err(format!(
+                "invalid feature state `{state}`; expected `true` or `false`"
+            ));
+        }
+    };
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(format!("invalid feature name `{name}`"));
+    }
+    Ok(Define::Feature {
+        name: name.to_string(),
+        enabled,
+    })
+}
+
+/// Returns `source` followed by one constant definition per define.
+///
+/// Repeated defines produce a single constant. Without defines the source is
+/// returned unchanged.
+///
+/// # Errors
+///
+/// Returns a message if two different feature names map to the same
+/// constant (e.g. `a-b` and `a_b`), a feature is both enabled and disabled,
+/// or `source` already declares a constant with the name of a feature
+/// constant.
+pub(crate) fn with_predefined_constants(
+    source: &str,
+    defines: &[Define],
+) -> Result<String, String> {
+    let mut constants: BTreeMap<String, &Define> = BTreeMap::new();
+    for define in defines {
+        let constant = define.constant_name();
+        match constants.get(&constant) {
+            None => {
+                constants.insert(constant, define);
+            }
+            Some(existing) if *existing == define => {}
+            Some(Define::Feature { name, .. }) => {
+                let Define::Feature { name: other, .. } = define;
+                return Err(if name == other {
+                    format!("feature `{name}` is both enabled and disabled")
+                } else {
+                    format!("features `{name}` and `{other}` both define `{constant}`")
+                });
+            }
+        }
+    }
+    let declared = declared_constants(source);
+    if let Some((constant, Define::Feature { name, enabled })) = constants
+        .iter()
+        .find(|(constant, _)| declared.contains(constant.as_str()))
+    {
+        let flag = if *enabled {
+            format!("feature={name}")
+        } else {
+            format!("feature={name}=false")
+        };
+        return Err(format!(
+            "`-D {flag}` defines `{constant}`, which the source already declares"
+        ));
+    }
+    let mut out = source.to_string();
+    if !constants.is_empty() && !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    for (constant, define) in &constants {
+        let _ = writeln!(out, "const {constant}: bool = {};", define.value());
+    }
+    Ok(out)
+}
+
+/// Returns the names that follow the `const` keyword in `source`.
+///
+/// This is a token scan rather than a parse: it skips `//` comments and
+/// string literals, which cannot span lines, and treats any `const NAME` as a
+/// declaration.
+fn declared_constants(source: &str) -> BTreeSet<&str> {
+    let mut declared = BTreeSet::new();
+    let mut after_const = false;
+    for line in source.lines() {
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            if rest.starts_with("//") {
+                break;
+            }
+            if c == '"' {
+                rest = rest[1..].find('"').map_or("", |end| &rest[end + 2..]);
+                after_const = false;
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let word = &rest[..end];
+                if after_const {
+                    declared.insert(word);
+                }
+                after_const = word == "const";
+                rest = &rest[end..];
+            } else {
+                if !c.is_whitespace() {
+                    after_const = false;
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    declared
+}
@@ -0,0 +1,115 @@
+//! Reporting of located compiler diagnostics.
+//!
+//! Syntax errors, type errors, and type checker warnings carry a source
+//! [`Location`]. They are printed one per line on stderr in the format chosen
+//! with `--message-format`:
+//!
+//! ```text
+//! src/main.inf:3:12: error: use of undeclared variable `x`
+//! src/main.inf:1:1: warning[unused_import]: unused import `self::Point`
+//! ```
+//!
+//! With `--message-format json` each diagnostic is a JSON object instead:
+//!
+//! ```json
+//! {"code":null,"column":12,"end_column":13,"end_line":3,"line":3,
+//!  "message":"use of undeclared variable `x`","path":"src/main.inf","severity":"error"}
+//! ```
+//!
+//! Lines and columns are 1-based; `end_column` is exclusive.
+
+use std::path::Path;
+
+use inference::{Location, SyntaxErrors, TypeCheckErrors, TypeCheckWarning};
+
+use crate::parser::MessageFormat;
+
+/// A compiler message tied to a span of the source file.
+pub(crate) struct Diagnostic {
+    severity: &'static str,
+    code: Option<&'static str>,
+    message: String,
+    location: Location,
+}
+
+impl Diagnostic {
+    /// Extracts located diagnostics from a parse or type check error.
+    ///
+    /// Returns `None` if the error carries no locations.
+    pub(crate) fn from_error(error: &anyhow::Error) -> Option<Vec<Self>> {
+        if let Some(errors) = error.downcast_ref::<SyntaxErrors>() {
+            return Some(
+                errors
+                    .0
+                    .iter()
+                    .map(|e| Self::error(e.message.clone(), e.location))
+                    .collect(),
+            );
+        }
+        if let Some(errors) = error.downcast_ref::<TypeCheckErrors>() {
+            return Some(
+                errors
+                    .0
+                    .iter()
+                    .map(|e| Self::error(e.message(), *e.location()))
+                    .collect(),
+            );
+        }
+        None
+    }
+
+    /// Creates a diagnostic for a type checker warning.
+    pub(crate) fn from_warning(warning: &TypeCheckWarning) -> Self {
+        Self {
+            severity: "warning",
+            code: Some(warning.code().as_str()),
+            message: warning.message(),
+            location: *warning.location(),
+        }
+    }
+
+    fn error(message: String, location: Location) -> Self {
+        Self {
+            severity: "error",
+            code: None,
+            message,
+            location,
+        }
+    }
+
+    /// Prints the diagnostic to stderr in `format`.
+    pub(crate) fn emit(&self, path: &Path, format: MessageFormat) {
+        match format {
+            MessageFormat::Human => eprintln!("{}", self.to_human(path)),
+            MessageFormat::Json => eprintln!("{}", self.to_json(path)),
+        }
+    }
+
+    fn to_human(&self, path: &Path) -> String {
+        let severity = match self.code {
+            Some(code) => format!("{}[{code}]", self.severity),
+            None => self.severity.to_string(),
+        };
+        format!(
+            "{}:{}:{}: {severity}: {}",
+            path.display(),
+            self.location.start_line,
+            self.location.start_column,
+            self.message
+        )
+    }
+
+    fn to_json(&self, path: &Path) -> String {
+        serde_json::json!({
+            "severity": self.severity,
+            "code": self.code,
+            "message": self.message,
+            "path": path.display().to_string(),
+            "line": self.location.start_line,
+            "column": self.location.start_column,
+            "end_line": self.location.end_line,
+            "end_column": self.location.end_column,
+        })
+        .to_string()
+    }
+}
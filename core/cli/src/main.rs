@@ -42,15 +42,33 @@
 //!
 //! At least one phase flag must be specified.
 //!
+//! ## Features
+//!
+//! `-D feature=NAME` enables a feature and `-D feature=NAME=false` disables
+//! it. The program sees each feature as a predefined constant,
+//! `FEATURE_NAME: bool`, so `infs` can build the same source with and
+//! without optional code (see [`defines`]).
+//!
 //! ## Output Artifacts
 //!
-//! All output files are written to an `out/` directory relative to the current
-//! working directory:
+//! All output files are written to the directory given by `--out-dir`
+//! (default: `out/` relative to the current working directory):
 //!
-//! - `out/<source_name>.wasm` – WebAssembly binary (when `-o` is specified)
-//! - `out/<source_name>.v` – Rocq translation (when `-v` is specified)
+//! - `<out-dir>/<source_name>.wasm` – WebAssembly binary (`-o` or `--emit wasm`)
+//! - `<out-dir>/<source_name>.names.json` – Source lines of the functions in
+//!   the binary, for mapping runtime backtraces (written with the binary)
+//! - `<out-dir>/<source_name>.wat` – WebAssembly text (`--emit wat`)
+//! - `<out-dir>/<source_name>.v` – Rocq translation (when `-v` is specified)
 //!
 //! The output directory is created automatically if it doesn't exist.
+//! `--emit` implies `--codegen`.
+//!
+//! `--target wasm64` compiles for 64-bit linear memory. For targets other than
+//! the default `wasm32`, file names include the target, such as
+//! `<out-dir>/<source_name>.wasm64.wasm`.
+//!
+//! Output is deterministic: compiling the same source twice, in one process or
+//! in separate processes, produces byte-identical files.
 //!
 //! ## Error Handling
 //!
@@ -59,7 +77,13 @@
 //! - **Parse errors**: Syntax errors, malformed AST nodes
 //! - **Type errors**: Type mismatches, undefined symbols
 //! - **Codegen errors**: LLVM compilation failures
-//! - **IO errors**: File not found, permission issues
+//! - **IO errors**: File not found, permission issues, source files larger
+//!   than `--max-source-size` (16 MiB by default)
+//!
+//! Parse errors, type errors, and type checker warnings are reported with
+//! their source location, one per line (`path:line:col: error: message`).
+//! `--message-format json` prints them as JSON objects instead, for tools
+//! that render the source themselves.
 //!
 //! All errors cause the process to exit with code 1.
 //!
@@ -77,6 +101,11 @@
 //! infc example.inf --parse
 //! ```
 //!
+//! Parse and print node counts (functions, types, statements, ...):
+//! ```bash
+//! infc example.inf --stats
+//! ```
+//!
 //! Type check without generating code:
 //! ```bash
 //! infc example.inf --analyze
@@ -97,6 +126,16 @@
 //! infc example.inf --codegen -v
 //! ```
 //!
+//! Emit the binary and textual module into `build/`:
+//! ```bash
+//! infc example.inf --emit wasm,wat --out-dir build
+//! ```
+//!
+//! Compile with the `runtime-checks` feature enabled:
+//! ```bash
+//! infc example.inf --codegen -o -D feature=runtime-checks
+//! ```
+//!
 //! ## Relationship to `infs`
 //!
 //! The Inference ecosystem provides two CLI tools:
@@ -109,7 +148,6 @@
 //! ## Current Limitations
 //!
 //! - Single-file compilation only (multi-file projects not yet supported)
-//! - Output directory is relative to CWD, not source file location
 //! - Analysis phase is work-in-progress
 //!
 //! ## Tests
@@ -122,13 +160,17 @@
 //!
 //! See `README.md` in this crate for comprehensive usage documentation.
 
+mod defines;
+mod diagnostic;
+mod names;
 mod parser;
 use clap::Parser;
-use inference::{analyze, codegen, parse, type_check, wasm_to_v};
-use parser::Cli;
+use diagnostic::Diagnostic;
+use inference::{AstError, CompileOptions, Compiler, SourceStats, Target, wasm_to_v};
+use parser::{Cli, EmitKind};
 use std::{
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{self},
 };
 
@@ -145,7 +187,9 @@ use std::{
 ///    - Analyze: Type check and semantic validation
 ///    - Codegen: Generate LLVM IR and compile to WebAssembly
 /// 4. **Generate output files** (if requested):
-///    - Write WASM binary with `-o` flag
+///    - Write WASM binary with `-o` or `--emit wasm`, and the function name
+///      table next to it
+///    - Write WebAssembly text with `--emit wat`
 ///    - Write Rocq translation with `-v` flag
 ///
 /// ## Error Handling
@@ -169,10 +213,11 @@ use std::{
 ///
 /// ## Output Management
 ///
-/// Output files are written to `out/` directory relative to CWD:
+/// Output files are written to `--out-dir` (default `out/` relative to CWD):
 /// - Directory is created if it doesn't exist
-/// - File names are derived from source file stem
-/// - Both `-o` and `-v` flags can be used simultaneously
+/// - File names are derived from source file stem, plus the target name for
+///   targets other than `wasm32` (`example.wasm64.wasm`)
+/// - `-o`, `-v`, and `--emit` can be combined freely
 ///
 /// ## Implementation Notes
 ///
@@ -188,16 +233,33 @@ fn main() {
         process::exit(1);
     }
 
-    let output_path = PathBuf::from("out");
-    let need_parse = args.parse;
+    let output_path = args.out_dir.clone();
+    let need_parse = args.parse || args.stats;
     let need_analyze = args.analyze;
-    let need_codegen = args.codegen;
+    let need_codegen = args.codegen || !args.emit.is_empty();
 
     if !(need_parse || need_analyze || need_codegen) {
-        eprintln!("Error: at least one of --parse, --analyze, or --codegen must be specified");
+        eprintln!(
+            "Error: at least one of --parse, --stats, --analyze, --codegen, or --emit must be specified"
+        );
         process::exit(1);
     }
 
+    if let Ok(metadata) = fs::metadata(&args.path) {
+        let size = usize::try_from(metadata.len()).unwrap_or(usize::MAX);
+        if size > args.max_source_size {
+            let error = AstError::SourceTooLarge {
+                size,
+                max_size: args.max_source_size,
+            };
+            eprintln!(
+                "Error: {}: {error} (raise the limit with --max-source-size)",
+                args.path.display()
+            );
+            process::exit(1);
+        }
+    }
+
     let source_code = match fs::read_to_string(&args.path) {
         Ok(content) => content,
         Err(e) => {
@@ -205,21 +267,40 @@ fn main() {
             process::exit(1);
         }
     };
+    let compiler = Compiler::new(
+        CompileOptions::new()
+            .with_target(args.target)
+            .with_emit_wat(args.emit.contains(&EmitKind::Wat))
+            .with_max_source_size(args.max_source_size),
+    );
     let mut t_ast = None;
     if need_codegen || need_analyze || need_parse {
-        match parse(source_code.as_str()) {
-            Ok(ast) => {
+        let source_code = match defines::with_predefined_constants(&source_code, &args.defines) {
+            Ok(source_code) => source_code,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        };
+        match compiler.parse(source_code.as_str()) {
+            Ok(parsed) => {
                 println!("Parsed: {}", args.path.display());
-                t_ast = Some(ast);
+                if args.stats {
+                    let ast = parsed.arena();
+                    for source_file in ast.source_files() {
+                        print_stats(&args.path, &source_file.stats(ast));
+                    }
+                }
+                t_ast = Some(parsed);
             }
             Err(e) => {
-                eprintln!("Parse error: {e}");
+                report_failure("Parse error", &e, &args);
                 process::exit(1);
             }
         }
     }
 
-    let Some(arena) = t_ast else {
+    let Some(parsed) = t_ast else {
         eprintln!("Internal error: parse phase did not produce AST");
         process::exit(1);
     };
@@ -227,14 +308,17 @@ fn main() {
     let mut typed_context = None;
 
     if need_codegen || need_analyze {
-        match type_check(arena) {
+        match compiler.type_check(parsed) {
             Err(e) => {
-                eprintln!("Type checking failed: {e}");
+                report_failure("Type checking failed", &e, &args);
                 process::exit(1);
             }
-            Ok(tctx) => {
-                typed_context = Some(tctx);
-                if let Err(e) = analyze(typed_context.as_ref().unwrap()) {
+            Ok(typed) => {
+                for warning in typed.warnings() {
+                    Diagnostic::from_warning(warning).emit(&args.path, args.message_format);
+                }
+                typed_context = Some(typed);
+                if let Err(e) = compiler.analyze(typed_context.as_ref().unwrap()) {
                     eprintln!("Analysis failed: {e}");
                     process::exit(1);
                 }
@@ -243,12 +327,12 @@ fn main() {
         }
     }
     if need_codegen {
-        let Some(tctx) = typed_context else {
+        let Some(typed) = typed_context else {
             eprintln!("Internal error: type check phase did not produce typed context");
             process::exit(1);
         };
-        let wasm = match codegen(&tctx) {
-            Ok(w) => w,
+        let artifacts = match compiler.codegen(&typed) {
+            Ok(artifacts) => artifacts,
             Err(e) => {
                 eprintln!("Codegen failed: {e}");
                 process::exit(1);
@@ -261,30 +345,32 @@ fn main() {
             .unwrap_or_else(|| std::ffi::OsStr::new("module"))
             .to_str()
             .unwrap();
-        if args.generate_wasm_output {
-            let wasm_file_path = output_path.join(format!("{source_fname}.wasm"));
-            if let Err(e) = fs::create_dir_all(&output_path) {
-                eprintln!("Failed to create output directory: {e}");
-                process::exit(1);
-            }
-            if let Err(e) = fs::write(&wasm_file_path, &wasm) {
-                eprintln!("Failed to write WASM file: {e}");
-                process::exit(1);
-            }
+        let output_stem = output_stem(source_fname, args.target);
+        if args.generate_wasm_output || args.emit.contains(&EmitKind::Wasm) {
+            let wasm_file_path = write_output(
+                &output_path,
+                &format!("{output_stem}.wasm"),
+                &artifacts.wasm,
+                "WASM",
+            );
             println!("WASM generated at: {}", wasm_file_path.to_string_lossy());
+            write_output(
+                &output_path,
+                &format!("{output_stem}.{}", names::NAMES_EXTENSION),
+                names::to_json(&args.path, &artifacts.names_map),
+                "function name table",
+            );
+        }
+        if let Some(wat) = &artifacts.wat {
+            let wat_file_path =
+                write_output(&output_path, &format!("{output_stem}.wat"), wat, "WAT");
+            println!("WAT generated at: {}", wat_file_path.to_string_lossy());
         }
         if args.generate_v_output {
-            match wasm_to_v(source_fname, &wasm) {
+            match wasm_to_v(source_fname, &artifacts.wasm) {
                 Ok(v_output) => {
-                    let v_file_path = output_path.join(format!("{source_fname}.v"));
-                    if let Err(e) = fs::create_dir_all(&output_path) {
-                        eprintln!("Failed to create output directory: {e}");
-                        process::exit(1);
-                    }
-                    if let Err(e) = fs::write(&v_file_path, v_output) {
-                        eprintln!("Failed to write V file: {e}");
-                        process::exit(1);
-                    }
+                    let v_file_path =
+                        write_output(&output_path, &format!("{output_stem}.v"), &v_output, "V");
                     println!("V generated at: {}", v_file_path.to_string_lossy());
                 }
                 Err(e) => {
@@ -297,6 +383,68 @@ fn main() {
     process::exit(0);
 }
 
+/// Reports a failed compilation phase on stderr.
+///
+/// Errors that carry source locations are printed as one diagnostic each in
+/// the `--message-format` format; any other error is printed as
+/// `{context}: {error}`.
+fn report_failure(context: &str, error: &anyhow::Error, args: &Cli) {
+    match Diagnostic::from_error(error) {
+        Some(diagnostics) => {
+            for diagnostic in diagnostics {
+                diagnostic.emit(&args.path, args.message_format);
+            }
+        }
+        None => eprintln!("{context}: {error}"),
+    }
+}
+
+/// Prints the node counts of `path` for `--stats`.
+fn print_stats(path: &Path, stats: &SourceStats) {
+    println!("Stats: {}", path.display());
+    println!("  functions:          {}", stats.functions);
+    println!("  external functions: {}", stats.external_functions);
+    println!("  types:              {}", stats.types);
+    println!("  constants:          {}", stats.constants);
+    println!("  specs:              {}", stats.specs);
+    println!("  statements:         {}", stats.statements);
+    println!("  expressions:        {}", stats.expressions);
+}
+
+/// Returns the file stem for output artifacts.
+///
+/// The default target keeps the source name; other targets append the target
+/// name so that builds for different targets don't overwrite each other.
+fn output_stem(source_fname: &str, target: Target) -> String {
+    if target == Target::default() {
+        source_fname.to_string()
+    } else {
+        format!("{source_fname}.{target}")
+    }
+}
+
+/// Writes an output artifact into `output_path`, creating the directory if
+/// needed, and returns the written file path.
+///
+/// Exits the process with code 1 if the directory or file cannot be written.
+fn write_output(
+    output_path: &Path,
+    file_name: &str,
+    contents: impl AsRef<[u8]>,
+    kind: &str,
+) -> PathBuf {
+    if let Err(e) = fs::create_dir_all(output_path) {
+        eprintln!("Failed to create output directory: {e}");
+        process::exit(1);
+    }
+    let file_path = output_path.join(file_name);
+    if let Err(e) = fs::write(&file_path, contents) {
+        eprintln!("Failed to write {kind} file: {e}");
+        process::exit(1);
+    }
+    file_path
+}
+
 /// Unit test helpers for the CLI module.
 ///
 /// Most CLI testing is done through integration tests in `tests/cli_integration.rs`
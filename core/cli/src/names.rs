@@ -0,0 +1,31 @@
+//! Function name table written next to the WASM binary.
+//!
+//! The WASM module keeps source function names in its `name` section, so
+//! runtime backtraces show frames such as `example.wasm!helper`. The table
+//! maps those names back to the source, for tools like `infs run` that
+//! rewrite the backtraces:
+//!
+//! ```json
+//! {"functions":[{"line":3,"name":"helper"},{"line":7,"name":"main"}],"path":"src/main.inf"}
+//! ```
+//!
+//! Only top-level functions are listed, since they are the ones compiled into
+//! the module. Lines are 1-based and point at the start of the definition.
+
+use std::path::Path;
+
+/// Extension of the table file, appended to the output stem.
+pub(crate) const NAMES_EXTENSION: &str = "names.json";
+
+/// Renders the table for the functions of `path`, given as name and line.
+pub(crate) fn to_json(path: &Path, functions: &[(String, u32)]) -> String {
+    let functions: Vec<_> = functions
+        .iter()
+        .map(|(name, line)| serde_json::json!({ "name": name, "line": line }))
+        .collect();
+    serde_json::json!({
+        "path": path.display().to_string(),
+        "functions": functions,
+    })
+    .to_string()
+}
@@ -5,7 +5,10 @@
 //!
 //! For comprehensive usage documentation, see `README.md` in this crate.
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use inference::{DEFAULT_MAX_SOURCE_SIZE, Target};
+
+use crate::defines::{Define, parse_define};
 
 /// Command line interface definition for the Inference compiler.
 ///
@@ -21,10 +24,16 @@ use clap::Parser;
 ///
 /// ## Output Flags
 ///
-/// - `-o`: Generate WASM binary file in `out/` directory
-/// - `-v`: Generate Rocq (.v) translation in `out/` directory
+/// - `--stats`: Print node counts after parsing (implies `--parse`)
+/// - `-o`: Generate WASM binary file in the output directory
+/// - `-v`: Generate Rocq (.v) translation in the output directory
+/// - `--emit wasm|wat`: Generate the binary or textual module (implies `--codegen`)
+/// - `--out-dir <DIR>`: Output directory (default: `out`)
+/// - `--target <TARGET>`: WebAssembly target (default: `wasm32`)
+/// - `-D feature=<NAME>`: Enable a feature as a predefined constant
+/// - `--max-source-size <BYTES>`: Largest accepted source file (default: 16 MiB)
 ///
-/// Output flags only take effect when `--codegen` is specified.
+/// `-o` and `-v` only take effect when `--codegen` is specified.
 ///
 /// ## Examples
 ///
@@ -42,6 +51,11 @@ use clap::Parser;
 /// ```bash
 /// infc example.inf --codegen -o -v
 /// ```
+///
+/// Binary and textual module in a custom directory:
+/// ```bash
+/// infc example.inf --emit wasm --emit wat --out-dir build
+/// ```
 #[derive(Parser)]
 #[command(
     name = "infc",
@@ -69,6 +83,13 @@ pub(crate) struct Cli {
     #[clap(long = "parse", action = clap::ArgAction::SetTrue)]
     pub(crate) parse: bool,
 
+    /// Print node counts for the source file after parsing.
+    ///
+    /// Reports functions, external functions, types, constants, specs,
+    /// statements, and expressions. Implies `--parse`.
+    #[clap(long = "stats", action = clap::ArgAction::SetTrue)]
+    pub(crate) stats: bool,
+
     /// Run the analyze phase for semantic and type inference.
     ///
     /// This phase performs type checking and semantic validation on the AST.
@@ -93,7 +114,7 @@ pub(crate) struct Cli {
     /// Generate output WASM binary file.
     ///
     /// When specified with `--codegen`, writes the compiled WebAssembly binary
-    /// to `out/<source_name>.wasm` relative to the current working directory.
+    /// to `<out-dir>/<source_name>.wasm`. Equivalent to `--emit wasm`.
     ///
     /// This flag has no effect without `--codegen`.
     #[clap(short = 'o', action = clap::ArgAction::SetTrue)]
@@ -102,8 +123,7 @@ pub(crate) struct Cli {
     /// Generate Rocq (.v) translation file.
     ///
     /// When specified with `--codegen`, translates the compiled WebAssembly
-    /// to Rocq (Coq) format and writes it to `out/<source_name>.v` relative
-    /// to the current working directory.
+    /// to Rocq (Coq) format and writes it to `<out-dir>/<source_name>.v`.
     ///
     /// This enables formal verification of the compiled program using the
     /// Rocq proof assistant.
@@ -111,4 +131,84 @@ pub(crate) struct Cli {
     /// This flag has no effect without `--codegen`.
     #[clap(short = 'v', action = clap::ArgAction::SetTrue)]
     pub(crate) generate_v_output: bool,
+
+    /// Emit the compiled module in the given format.
+    ///
+    /// `wasm` writes the binary module to `<out-dir>/<source_name>.wasm` and
+    /// `wat` writes the WebAssembly text to `<out-dir>/<source_name>.wat`.
+    /// Repeat the flag or separate kinds with commas to emit both. Implies
+    /// `--codegen`.
+    ///
+    /// Output is deterministic: compiling the same source twice produces
+    /// byte-identical files.
+    #[clap(long = "emit", value_enum, value_name = "KIND", value_delimiter = ',')]
+    pub(crate) emit: Vec<EmitKind>,
+
+    /// Directory for output files.
+    ///
+    /// Relative paths are resolved against the current working directory.
+    /// The directory is created if it doesn't exist.
+    #[clap(long = "out-dir", value_name = "DIR", default_value = "out")]
+    pub(crate) out_dir: std::path::PathBuf,
+
+    /// WebAssembly target to compile for.
+    ///
+    /// Supported targets: `wasm32` (default) and `wasm64`. For targets other
+    /// than the default, output files include the target name, for example
+    /// `<out-dir>/<source_name>.wasm64.wasm`.
+    #[clap(long = "target", value_name = "TARGET", default_value_t = Target::default())]
+    pub(crate) target: Target,
+
+    /// Define a compile-time value, as `KEY=VALUE`.
+    ///
+    /// `feature=NAME` enables the feature `NAME`, which the program sees as
+    /// the predefined constant `FEATURE_NAME: bool = true` (upper case, with
+    /// `-` replaced by `_`). `feature=NAME=false` defines it as `false`.
+    /// Repeat the flag for several features.
+    #[clap(short = 'D', long = "define", value_name = "KEY=VALUE", value_parser = parse_define)]
+    pub(crate) defines: Vec<Define>,
+
+    /// Format of parse and type check diagnostics on stderr.
+    ///
+    /// `human` prints one `path:line:col: error: message` line per diagnostic.
+    /// `json` prints one JSON object per line with the full source span, for
+    /// tools such as `infs` that render the offending source themselves.
+    #[clap(
+        long = "message-format",
+        value_enum,
+        value_name = "FORMAT",
+        default_value = "human"
+    )]
+    pub(crate) message_format: MessageFormat,
+
+    /// Largest source file to compile, in bytes.
+    ///
+    /// Larger files are rejected before they are read, so that passing a
+    /// binary file by accident fails immediately instead of parsing for
+    /// minutes.
+    #[clap(
+        long = "max-source-size",
+        value_name = "BYTES",
+        default_value_t = DEFAULT_MAX_SOURCE_SIZE
+    )]
+    pub(crate) max_source_size: usize,
+}
+
+/// Module formats accepted by `--emit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum EmitKind {
+    /// WebAssembly binary (`.wasm`).
+    Wasm,
+    /// WebAssembly text (`.wat`).
+    Wat,
+}
+
+/// Diagnostic formats accepted by `--message-format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum MessageFormat {
+    /// One `path:line:col: severity: message` line per diagnostic.
+    #[default]
+    Human,
+    /// One JSON object per diagnostic.
+    Json,
 }
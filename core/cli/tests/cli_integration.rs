@@ -165,3 +165,301 @@ fn shows_version() {
         .success()
         .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")));
 }
+
+/// Verifies that `--emit` output is byte-identical across separate compiler runs.
+///
+/// **Test setup**: Compiles the non-deterministic codegen fixture twice, each run
+/// in its own process writing to its own `--out-dir`.
+///
+/// **Expected behavior**: Both runs succeed and produce identical `.wasm` and
+/// `.wat` files; the text contains the non-deterministic instructions.
+#[test]
+fn emit_output_is_deterministic_across_processes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let src = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("tests")
+        .join("test_data")
+        .join("codegen")
+        .join("wasm")
+        .join("base")
+        .join("nondet.inf");
+
+    for out_dir in ["first", "second"] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+        cmd.current_dir(temp.path())
+            .arg(&src)
+            .arg("--emit")
+            .arg("wasm,wat")
+            .arg("--out-dir")
+            .arg(out_dir);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("WAT generated at:"));
+    }
+
+    for file in ["nondet.wasm", "nondet.wat"] {
+        let first = std::fs::read(temp.child("first").child(file).path()).unwrap();
+        let second = std::fs::read(temp.child("second").child(file).path()).unwrap();
+        assert_eq!(first, second, "{file} differs between compiler runs");
+    }
+
+    let wat = std::fs::read_to_string(temp.child("first").child("nondet.wat").path()).unwrap();
+    assert!(wat.contains("forall"));
+    assert!(wat.contains("i32.uzumaki"));
+}
+
+/// Verifies that `--emit` requires a known output kind.
+///
+/// **Expected behavior**: Exit with a usage error listing the accepted values.
+#[test]
+fn emit_rejects_unknown_kind() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(example_file("example.inf"))
+        .arg("--emit")
+        .arg("asm");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values: wasm, wat"));
+}
+
+/// Verifies that `--target` rejects targets the code generator doesn't support.
+///
+/// **Expected behavior**: Exit with a usage error naming the target and listing
+/// the supported ones.
+#[test]
+fn target_rejects_unsupported_target() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(example_file("example.inf"))
+        .arg("--codegen")
+        .arg("--target")
+        .arg("wasm128");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unsupported target `wasm128`"))
+        .stderr(predicate::str::contains(
+            "supported targets: wasm32, wasm64",
+        ));
+}
+
+/// Verifies that `--stats` parses the file and prints node counts.
+///
+/// **Expected behavior**: Exit with code 0, print "Parsed:" and the
+/// definition counts of the example file.
+#[test]
+fn stats_prints_node_counts() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(example_file("example.inf")).arg("--stats");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Parsed:"))
+        .stdout(predicate::str::contains("Stats:"))
+        .stdout(predicate::str::contains("functions:          100\n"))
+        .stdout(predicate::str::contains("specs:              12\n"));
+}
+
+/// Verifies that sources above `--max-source-size` are rejected before parsing.
+///
+/// **Expected behavior**: Exit with code 1 and an error naming the file, its
+/// size, and the limit.
+#[test]
+fn rejects_source_larger_than_max_source_size() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(example_file("example.inf"))
+        .arg("--parse")
+        .arg("--max-source-size")
+        .arg("8");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("example.inf"))
+        .stderr(predicate::str::contains("more than the maximum of 8 bytes"))
+        .stderr(predicate::str::contains("--max-source-size"));
+}
+
+/// Verifies that non-default targets are included in output file names.
+///
+/// **Test setup**: Compiles the trivial codegen fixture for `wasm64`.
+///
+/// **Expected behavior**: The binary is written to `out/trivial.wasm64.wasm`
+/// and no `out/trivial.wasm` is created.
+#[test]
+fn target_wasm64_names_output_after_target() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let src = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("tests")
+        .join("test_data")
+        .join("codegen")
+        .join("wasm")
+        .join("base")
+        .join("trivial.inf");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.current_dir(temp.path())
+        .arg(&src)
+        .arg("--codegen")
+        .arg("-o")
+        .arg("--target")
+        .arg("wasm64");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("trivial.wasm64.wasm"));
+
+    temp.child("out")
+        .child("trivial.wasm64.wasm")
+        .assert(predicate::path::exists());
+    temp.child("out")
+        .child("trivial.wasm")
+        .assert(predicate::path::missing());
+}
+
+/// Verifies that the function name table is written next to the binary.
+///
+/// **Test setup**: Compiles a file with two top-level functions using `-o`.
+///
+/// **Expected behavior**: `out/functions.names.json` lists both functions with the
+/// line of their definition.
+#[test]
+fn wasm_output_writes_function_name_table() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = temp.child("functions.inf");
+    source
+        .write_str(
+            "fn helper() -> i32 {\n    return 1;\n}\n\npub fn entry() -> i32 {\n    return 2;\n}\n",
+        )
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.current_dir(temp.path())
+        .arg(source.path())
+        .arg("--codegen")
+        .arg("-o");
+    cmd.assert().success();
+
+    let table = std::fs::read_to_string(temp.child("out").child("functions.names.json").path())
+        .expect("Function name table should be written");
+    let table: serde_json::Value = serde_json::from_str(&table).unwrap();
+    assert_eq!(
+        table["functions"],
+        serde_json::json!([
+            { "name": "helper", "line": 1 },
+            { "name": "entry", "line": 5 },
+        ])
+    );
+}
+
+/// Verifies that `-D feature=NAME` defines a constant for the feature.
+///
+/// **Test setup**: Writes a function that returns `FEATURE_RUNTIME_CHECKS`.
+///
+/// **Expected behavior**: Analysis succeeds with the feature enabled or
+/// disabled and reports the constant as undeclared without it. Malformed
+/// defines, names mapping to the same constant, conflicting states, and
+/// features whose constant the source already declares are rejected.
+#[test]
+fn feature_defines_become_constants() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = temp.child("features.inf");
+    source
+        .write_str("fn checks() -> bool {\n    return FEATURE_RUNTIME_CHECKS;\n}\n")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(source.path())
+        .arg("--analyze")
+        .arg("-D")
+        .arg("feature=runtime-checks");
+    cmd.assert().success();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(source.path()).arg("--analyze");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "features.inf:2:12: error: use of undeclared variable `FEATURE_RUNTIME_CHECKS`",
+    ));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(source.path())
+        .arg("--analyze")
+        .arg("-D")
+        .arg("feature=runtime-checks=false");
+    cmd.assert().success();
+
+    for defines in [
+        ["feature=runtime-checks", "feature=runtime_checks"],
+        ["feature=runtime-checks", "feature=runtime-checks=false"],
+    ] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+        cmd.arg(source.path())
+            .arg("--analyze")
+            .arg("-D")
+            .arg(defines[0])
+            .arg("-D")
+            .arg(defines[1]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("Error: feature"));
+    }
+
+    let declared = temp.child("declared.inf");
+    declared
+        .write_str("const FEATURE_RUNTIME_CHECKS: bool = true;\n")
+        .unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(declared.path())
+        .arg("--analyze")
+        .arg("-D")
+        .arg("feature=runtime-checks=false");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Error: `-D feature=runtime-checks=false` defines `FEATURE_RUNTIME_CHECKS`",
+    ));
+
+    for define in ["runtime-checks", "opt=3", "feature=1st", "feature=a=maybe"] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+        cmd.arg(source.path())
+            .arg("--analyze")
+            .arg("-D")
+            .arg(define);
+        cmd.assert().failure();
+    }
+}
+
+/// Verifies that type errors are reported with their source location.
+///
+/// **Test setup**: Writes a function that returns an undeclared variable.
+///
+/// **Expected behavior**: The default format prints `path:line:col: error: ...`;
+/// `--message-format json` prints a JSON object with the span.
+#[test]
+fn type_errors_are_reported_with_location() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let source = temp.child("undeclared.inf");
+    source
+        .write_str("fn f() -> i32 {\n    return x;\n}\n")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(source.path()).arg("--analyze");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "undeclared.inf:2:12: error: use of undeclared variable `x`",
+    ));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("infc"));
+    cmd.arg(source.path())
+        .arg("--analyze")
+        .arg("--message-format")
+        .arg("json");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(r#""severity":"error""#))
+        .stderr(predicate::str::contains(
+            r#""message":"use of undeclared variable `x`""#,
+        ))
+        .stderr(predicate::str::contains(r#""line":2"#))
+        .stderr(predicate::str::contains(r#""column":12"#));
+}
@@ -0,0 +1,268 @@
+//! Staged compiler facade.
+//!
+//! [`Compiler`] runs the pipeline one stage at a time, with every setting in
+//! a [`CompileOptions`] value instead of function parameters. New settings
+//! are added as options, so existing callers keep compiling.
+//!
+//! ```rust,no_run
+//! use inference::{CompileOptions, Compiler, Target};
+//!
+//! let compiler = Compiler::new(
+//!     CompileOptions::new()
+//!         .with_target(Target::Wasm64)
+//!         .with_emit_wat(true),
+//! );
+//! let parsed = compiler.parse("pub fn answer() -> i32 { return 42; }")?;
+//! let typed = compiler.type_check(parsed)?;
+//! compiler.analyze(&typed)?;
+//! let artifacts = compiler.codegen(&typed)?;
+//! assert!(artifacts.wat.is_some());
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use inference_ast::{
+    arena::Arena,
+    builder::Builder,
+    source::{DEFAULT_MAX_SOURCE_SIZE, check_size, strip_bom},
+};
+use inference_type_checker::{errors::TypeCheckWarning, typed_context::TypedContext};
+use inference_wasm_codegen::Target;
+
+/// Settings for a [`Compiler`].
+///
+/// Built with chained `with_*` setters starting from [`CompileOptions::new`]; the
+/// fields are private so that new settings can be added without breaking
+/// callers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileOptions {
+    target: Target,
+    emit_wat: bool,
+    max_source_size: usize,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            target: Target::default(),
+            emit_wat: false,
+            max_source_size: DEFAULT_MAX_SOURCE_SIZE,
+        }
+    }
+}
+
+impl CompileOptions {
+    /// Returns the default options: `wasm32`, no WAT output, and a source
+    /// size limit of [`DEFAULT_MAX_SOURCE_SIZE`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the WebAssembly target.
+    #[must_use]
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets whether [`Compiler::codegen`] also renders WebAssembly text.
+    #[must_use]
+    pub fn with_emit_wat(mut self, emit_wat: bool) -> Self {
+        self.emit_wat = emit_wat;
+        self
+    }
+
+    /// Sets the largest accepted source, in bytes.
+    #[must_use]
+    pub fn with_max_source_size(mut self, max_source_size: usize) -> Self {
+        self.max_source_size = max_source_size;
+        self
+    }
+
+    /// Returns the WebAssembly target.
+    #[must_use]
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    /// Returns whether WebAssembly text is rendered.
+    #[must_use]
+    pub fn emit_wat(&self) -> bool {
+        self.emit_wat
+    }
+
+    /// Returns the largest accepted source, in bytes.
+    #[must_use]
+    pub fn max_source_size(&self) -> usize {
+        self.max_source_size
+    }
+}
+
+/// Output of [`Compiler::parse`].
+pub struct Parsed {
+    arena: Arena,
+}
+
+impl Parsed {
+    /// Returns the AST.
+    #[must_use]
+    pub fn arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    /// Returns the AST, consuming the stage output.
+    #[must_use]
+    pub fn into_arena(self) -> Arena {
+        self.arena
+    }
+}
+
+/// Output of [`Compiler::type_check`].
+pub struct Typed {
+    context: TypedContext,
+}
+
+impl Typed {
+    /// Returns the typed context.
+    #[must_use]
+    pub fn context(&self) -> &TypedContext {
+        &self.context
+    }
+
+    /// Returns the warnings reported during type checking.
+    #[must_use]
+    pub fn warnings(&self) -> &[TypeCheckWarning] {
+        self.context.warnings()
+    }
+
+    /// Returns the typed context, consuming the stage output.
+    #[must_use]
+    pub fn into_context(self) -> TypedContext {
+        self.context
+    }
+}
+
+/// Output of [`Compiler::analyze`].
+///
+/// The analysis phase does not perform any checks yet, so the report is
+/// always clean.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AnalysisReport {}
+
+/// Output of [`Compiler::codegen`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Artifacts {
+    /// The WebAssembly binary.
+    pub wasm: Vec<u8>,
+    /// The binary rendered as WebAssembly text, if
+    /// [`CompileOptions::emit_wat`] is set.
+    pub wat: Option<String>,
+    /// Name and 1-based start line of each top-level function, in source
+    /// order. These are the functions named in the module's `name` section.
+    pub names_map: Vec<(String, u32)>,
+}
+
+/// Runs the compilation pipeline with a fixed set of [`CompileOptions`].
+#[derive(Debug, Clone, Default)]
+pub struct Compiler {
+    options: CompileOptions,
+}
+
+impl Compiler {
+    /// Creates a compiler with `options`.
+    #[must_use]
+    pub fn new(options: CompileOptions) -> Self {
+        Self { options }
+    }
+
+    /// Returns the options of this compiler.
+    #[must_use]
+    pub fn options(&self) -> &CompileOptions {
+        &self.options
+    }
+
+    /// Parses source code into an AST.
+    ///
+    /// A leading UTF-8 byte order mark is stripped, so files saved by
+    /// editors that add one parse the same as files without it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AstError::SourceTooLarge`](crate::AstError::SourceTooLarge)
+    /// if the source is larger than [`CompileOptions::max_source_size`],
+    /// [`SyntaxErrors`](crate::SyntaxErrors) for syntax errors, and an error
+    /// if the Inference grammar cannot be loaded into tree-sitter.
+    pub fn parse(&self, source_code: &str) -> anyhow::Result<Parsed> {
+        check_size(source_code.len(), self.options.max_source_size)?;
+        let source_code = strip_bom(source_code);
+        let inference_language = tree_sitter_inference::language();
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&inference_language)
+            .map_err(|e| anyhow::anyhow!("Failed to load Inference grammar: {e}"))?;
+        let tree = parser
+            .parse(source_code, None)
+            .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
+        let mut builder = Builder::new();
+        builder.add_source_code(tree.root_node(), source_code.as_bytes());
+        Ok(Parsed {
+            arena: builder.build_ast()?,
+        })
+    }
+
+    /// Type checks a parsed program.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TypeCheckErrors`](crate::TypeCheckErrors) if the program
+    /// has type errors.
+    #[allow(clippy::unused_self)]
+    pub fn type_check(&self, parsed: Parsed) -> anyhow::Result<Typed> {
+        let builder =
+            inference_type_checker::TypeCheckerBuilder::build_typed_context(parsed.arena)?;
+        Ok(Typed {
+            context: builder.typed_context(),
+        })
+    }
+
+    /// Runs semantic analysis on a typed program.
+    ///
+    /// # Errors
+    ///
+    /// Currently never fails; the analysis phase is a work in progress.
+    #[allow(clippy::unused_self)]
+    pub fn analyze(&self, _typed: &Typed) -> anyhow::Result<AnalysisReport> {
+        Ok(AnalysisReport::default())
+    }
+
+    /// Generates WebAssembly for [`CompileOptions::target`], and WebAssembly
+    /// text if [`CompileOptions::emit_wat`] is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if code generation fails, or if the module cannot
+    /// be rendered by [`crate::wasm_to_wat`] when WebAssembly text is
+    /// requested.
+    pub fn codegen(&self, typed: &Typed) -> anyhow::Result<Artifacts> {
+        let wasm = inference_wasm_codegen::codegen_for_target(&typed.context, self.options.target)?;
+        let wat = if self.options.emit_wat {
+            Some(crate::wasm_to_wat(&wasm)?)
+        } else {
+            None
+        };
+        let names_map = typed
+            .context
+            .source_files()
+            .iter()
+            .flat_map(|source_file| source_file.function_definitions())
+            .map(|function| (function.name(), function.location.start_line))
+            .collect();
+        Ok(Artifacts {
+            wasm,
+            wat,
+            names_map,
+        })
+    }
+}
@@ -13,22 +13,27 @@
 //! .inf source → tree-sitter → Typed AST → Type Check → LLVM IR → WASM → Rocq (.v)
 //! ```
 //!
-//! Each phase is exposed as a standalone function in this crate, allowing flexible
-//! control over which compilation stages to execute.
+//! Each phase is a method of [`Compiler`], allowing flexible control over which
+//! compilation stages to execute. Settings such as the target and whether to
+//! render WebAssembly text are given once, as [`CompileOptions`].
 //!
 //! ## Quick Start
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, codegen};
+//! use inference::Compiler;
 //!
 //! fn compile(source_code: &str) -> anyhow::Result<Vec<u8>> {
-//!     let arena = parse(source_code)?;
-//!     let typed_context = type_check(arena)?;
-//!     let wasm_bytes = codegen(&typed_context)?;
-//!     Ok(wasm_bytes)
+//!     let compiler = Compiler::default();
+//!     let parsed = compiler.parse(source_code)?;
+//!     let typed = compiler.type_check(parsed)?;
+//!     let artifacts = compiler.codegen(&typed)?;
+//!     Ok(artifacts.wasm)
 //! }
 //! ```
 //!
+//! The free functions [`parse`], [`type_check`], [`analyze`], [`codegen`] and
+//! [`codegen_for_target`] are deprecated wrappers around [`Compiler`].
+//!
 //! ## Compilation Pipeline
 //!
 //! ### Phase 1: Parse
@@ -36,10 +41,11 @@
 //! Transforms source code into an arena-based Abstract Syntax Tree (AST).
 //!
 //! ```rust,no_run
-//! use inference::parse;
+//! use inference::Compiler;
 //!
 //! let source = r#"fn main() { return 42; }"#;
-//! let arena = parse(source)?;
+//! let parsed = Compiler::default().parse(source)?;
+//! let arena = parsed.arena();
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
@@ -54,11 +60,12 @@
 //! Performs type inference and validation on the AST.
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check};
+//! use inference::Compiler;
 //!
+//! let compiler = Compiler::default();
 //! let source = "fn add(x: i32, y: i32) -> i32 { return x + y; }";
-//! let arena = parse(source)?;
-//! let typed_context = type_check(arena)?;
+//! let typed = compiler.type_check(compiler.parse(source)?)?;
+//! let typed_context = typed.context();
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
@@ -80,31 +87,36 @@
 //! analysis passes.
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, analyze};
+//! use inference::Compiler;
 //!
+//! let compiler = Compiler::default();
 //! let source = "fn main() { return 0; }";
-//! let arena = parse(source)?;
-//! let typed_context = type_check(arena)?;
-//! analyze(&typed_context)?;
+//! let typed = compiler.type_check(compiler.parse(source)?)?;
+//! compiler.analyze(&typed)?;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
-//! **Status**: Work in progress. Currently returns `Ok(())` without performing checks.
+//! **Status**: Work in progress. Currently returns an empty [`AnalysisReport`]
+//! without performing checks.
 //!
 //! ### Phase 4: Codegen
 //!
 //! Generates WebAssembly binary format from the typed AST.
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, codegen};
+//! use inference::{CompileOptions, Compiler, Target};
 //!
+//! let compiler = Compiler::new(CompileOptions::new().with_target(Target::Wasm32));
 //! let source = "fn factorial(n: i32) -> i32 { if n <= 1 { return 1; } else { return n * factorial(n - 1); } }";
-//! let arena = parse(source)?;
-//! let typed_context = type_check(arena)?;
-//! let wasm_bytes = codegen(&typed_context)?;
+//! let typed = compiler.type_check(compiler.parse(source)?)?;
+//! let artifacts = compiler.codegen(&typed)?;
+//! let wasm_bytes = artifacts.wasm;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
+//! [`Artifacts`] also lists the generated functions with their source lines
+//! in `names_map`.
+//!
 //! The code generator uses LLVM IR as an intermediate representation and supports
 //! custom intrinsics for non-deterministic instructions specific to Inference:
 //! - `@` (uzumaki) - Non-deterministic value generation (rvalue)
@@ -118,13 +130,13 @@
 //! Translates WebAssembly binary to Rocq (Coq) verification code.
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, codegen, wasm_to_v};
+//! use inference::{Compiler, wasm_to_v};
 //!
+//! let compiler = Compiler::default();
 //! let source = "fn is_even(n: i32) -> bool { return n % 2 == 0; }";
-//! let arena = parse(source)?;
-//! let typed_context = type_check(arena)?;
-//! let wasm_bytes = codegen(&typed_context)?;
-//! let rocq_code = wasm_to_v("MyModule", &wasm_bytes)?;
+//! let typed = compiler.type_check(compiler.parse(source)?)?;
+//! let artifacts = compiler.codegen(&typed)?;
+//! let rocq_code = wasm_to_v("MyModule", &artifacts.wasm)?;
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 //!
@@ -132,6 +144,13 @@
 //! program properties. Non-deterministic instructions are translated to Rocq axioms
 //! that enable reasoning about all possible execution paths.
 //!
+//! ### WAT Output
+//!
+//! [`wasm_to_wat`] renders the generated binary as WebAssembly text, for
+//! inspection or for formatting with `wat-fmt`. With
+//! [`CompileOptions::with_emit_wat`], [`Compiler::codegen`] does this itself
+//! and returns the text in [`Artifacts::wat`].
+//!
 //! ## Architecture
 //!
 //! This crate acts as a thin orchestration layer that delegates to specialized crates:
@@ -163,10 +182,10 @@
 //! all issues at once rather than fixing one error at a time.
 //!
 //! ```rust,no_run
-//! use inference::parse;
+//! use inference::Compiler;
 //!
 //! let invalid_source = "fn main( { return 42 }"; // missing closing paren
-//! match parse(invalid_source) {
+//! match Compiler::default().parse(invalid_source) {
 //!     Ok(_) => println!("Success"),
 //!     Err(e) => eprintln!("Parse error: {}", e),
 //! }
@@ -177,33 +196,34 @@
 //! ### Standard Compilation
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, analyze, codegen};
-//!
-//! fn compile_to_wasm(source_code: &str) -> anyhow::Result<Vec<u8>> {
-//!     let arena = parse(source_code)?;
-//!     let typed_context = type_check(arena)?;
-//!     analyze(&typed_context)?;
-//!     codegen(&typed_context)
+//! use inference::{Artifacts, CompileOptions, Compiler};
+//!
+//! fn compile_to_wasm(source_code: &str) -> anyhow::Result<Artifacts> {
+//!     let compiler = Compiler::new(CompileOptions::new().with_emit_wat(true));
+//!     let parsed = compiler.parse(source_code)?;
+//!     let typed = compiler.type_check(parsed)?;
+//!     compiler.analyze(&typed)?;
+//!     compiler.codegen(&typed)
 //! }
 //! ```
 //!
 //! ### Verification Workflow
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, codegen, wasm_to_v};
+//! use inference::{Compiler, wasm_to_v};
 //!
 //! fn compile_to_rocq(source_code: &str, module_name: &str) -> anyhow::Result<String> {
-//!     let arena = parse(source_code)?;
-//!     let typed_context = type_check(arena)?;
-//!     let wasm = codegen(&typed_context)?;
-//!     wasm_to_v(module_name, &wasm)
+//!     let compiler = Compiler::default();
+//!     let typed = compiler.type_check(compiler.parse(source_code)?)?;
+//!     let artifacts = compiler.codegen(&typed)?;
+//!     wasm_to_v(module_name, &artifacts.wasm)
 //! }
 //! ```
 //!
 //! ### Non-Deterministic Program Example
 //!
 //! ```rust,no_run
-//! use inference::{parse, type_check, codegen};
+//! use inference::Compiler;
 //!
 //! fn compile_nondet_example() -> anyhow::Result<Vec<u8>> {
 //!     let source = r#"
@@ -219,9 +239,9 @@
 //!         }
 //!     "#;
 //!
-//!     let arena = parse(source)?;
-//!     let typed_context = type_check(arena)?;
-//!     codegen(&typed_context)
+//!     let compiler = Compiler::default();
+//!     let typed = compiler.type_check(compiler.parse(source)?)?;
+//!     Ok(compiler.codegen(&typed)?.wasm)
 //! }
 //! ```
 //!
@@ -230,7 +250,7 @@
 //! - **Single-file support**: Multi-file compilation is not yet implemented.
 //!   The AST expects a single source file as input.
 //! - **Analyze phase**: The semantic analysis phase is work-in-progress and
-//!   currently returns `Ok(())` without performing any checks.
+//!   currently returns an empty report without performing any checks.
 //! - **External dependencies**: Code generation requires `inf-llc` and `rust-lld`
 //!   binaries in the `external/bin/` directory.
 //!
@@ -252,6 +272,7 @@
 //! - [`inference_type_checker::TypeCheckerBuilder`] - Type checking entry point
 //! - [`inference_type_checker::typed_context::TypedContext`] - Type information storage
 //! - [`inference_wasm_codegen::codegen`] - WebAssembly code generation entry point
+//! - [`inference_wasm_codegen::Target`] - Supported WebAssembly targets
 //! - [`inference_wasm_to_v_translator::wasm_parser`] - WASM to Rocq translation
 //!
 //! ### External Resources
@@ -261,15 +282,32 @@
 //! - [Tree-sitter Grammar](https://github.com/Inferara/tree-sitter-inference)
 //! - [LLVM Intrinsics for Non-deterministic Instructions](https://github.com/Inferara/llvm-project/pull/2)
 
-use inference_ast::{arena::Arena, builder::Builder};
+use inference_ast::arena::Arena;
+pub use inference_ast::{
+    errors::{AstError, SyntaxError, SyntaxErrors},
+    nodes::Location,
+    source::DEFAULT_MAX_SOURCE_SIZE,
+    stats::SourceStats,
+};
+pub use inference_type_checker::errors::{TypeCheckError, TypeCheckErrors, TypeCheckWarning};
 use inference_type_checker::typed_context::TypedContext;
+pub use inference_wasm_codegen::{Target, UnsupportedTargetError};
+
+mod compiler;
+
+pub use compiler::{AnalysisReport, Artifacts, CompileOptions, Compiler, Parsed, Typed};
 
 /// Parses source code and builds an arena-based Abstract Syntax Tree.
 ///
 /// This function orchestrates the parsing pipeline:
-/// 1. Initializes a tree-sitter parser with the Inference grammar
-/// 2. Parses the source code into a Concrete Syntax Tree (CST)
-/// 3. Transforms the CST into an arena-based AST using [`Builder`]
+/// 1. Rejects sources larger than [`DEFAULT_MAX_SOURCE_SIZE`] and strips a
+///    leading UTF-8 byte order mark
+/// 2. Initializes a tree-sitter parser with the Inference grammar
+/// 3. Parses the source code into a Concrete Syntax Tree (CST)
+/// 4. Transforms the CST into an arena-based AST using [`Builder`]
+///
+/// Byte offsets in the AST are relative to the source without the byte order
+/// mark. CRLF line endings are accepted and report the same columns as LF.
 ///
 /// The resulting [`Arena`] stores all AST nodes with unique IDs and maintains
 /// parent-child relationships for efficient traversal. Root nodes are
@@ -332,37 +370,45 @@ use inference_type_checker::typed_context::TypedContext;
 /// # Errors
 ///
 /// Returns an error if:
+/// - The source code is larger than [`DEFAULT_MAX_SOURCE_SIZE`]
+///   ([`AstError::SourceTooLarge`])
 /// - The source code contains syntax errors that prevent AST construction
 /// - The tree-sitter parser fails to generate a valid CST
 /// - The [`Builder`] encounters malformed nodes during AST construction
+/// - The Inference grammar cannot be loaded into the tree-sitter parser, which
+///   indicates a broken `tree-sitter-inference` dependency
+///
+/// Syntax errors are reported as [`SyntaxErrors`], which can be recovered with
+/// `downcast_ref` to get each error's [`Location`].
 ///
 /// The error collection mechanism reports all parsing errors at once rather than
 /// failing on the first error, enabling faster iteration during development.
 ///
-/// # Panics
-///
-/// This function will panic if the Inference language grammar cannot be loaded
-/// into the tree-sitter parser. This indicates a critical setup issue with the
-/// `tree-sitter-inference` dependency and should never occur in normal operation.
-///
 /// [`SourceFile`]: inference_ast::nodes::SourceFile
 /// [`Builder`]: inference_ast::builder::Builder
 /// [`Arena`]: inference_ast::arena::Arena
+#[deprecated(since = "0.0.1", note = "use `Compiler::parse` instead")]
 pub fn parse(source_code: &str) -> anyhow::Result<Arena> {
-    let inference_language = tree_sitter_inference::language();
-    let mut parser = tree_sitter::Parser::new();
-    parser
-        .set_language(&inference_language)
-        .map_err(|e| anyhow::anyhow!("Failed to load Inference grammar: {e}"))?;
-    let tree = parser
-        .parse(source_code, None)
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse source code"))?;
-    let code = source_code.as_bytes();
-    let root_node = tree.root_node();
-    let mut builder = Builder::new();
-    builder.add_source_code(root_node, code);
-    let arena = builder.build_ast()?;
-    Ok(arena)
+    Compiler::default()
+        .parse(source_code)
+        .map(Parsed::into_arena)
+}
+
+/// Parses source code like [`parse`], with a maximum source size of
+/// `max_size` bytes instead of [`DEFAULT_MAX_SOURCE_SIZE`].
+///
+/// # Errors
+///
+/// Returns [`AstError::SourceTooLarge`] if `source_code` is larger than
+/// `max_size` bytes, and otherwise the same errors as [`parse`].
+#[deprecated(
+    since = "0.0.1",
+    note = "use `Compiler::parse` with `CompileOptions::with_max_source_size` instead"
+)]
+pub fn parse_with_max_size(source_code: &str, max_size: usize) -> anyhow::Result<Arena> {
+    Compiler::new(CompileOptions::new().with_max_source_size(max_size))
+        .parse(source_code)
+        .map(Parsed::into_arena)
 }
 
 /// Performs bidirectional type checking and inference on the AST.
@@ -474,9 +520,12 @@ pub fn parse(source_code: &str) -> anyhow::Result<Arena> {
 /// - Import resolution fails
 ///
 /// The error message aggregates all type checking errors found during analysis.
+/// The error is a [`TypeCheckErrors`]; recover it with `downcast_ref` to get
+/// each error's [`Location`].
 ///
 /// [`TypeInfo`]: inference_type_checker::type_info::TypeInfo
 /// [`TypedContext`]: inference_type_checker::typed_context::TypedContext
+#[deprecated(since = "0.0.1", note = "use `Compiler::type_check` instead")]
 pub fn type_check(arena: Arena) -> anyhow::Result<TypedContext> {
     let type_checker_builder =
         inference_type_checker::TypeCheckerBuilder::build_typed_context(arena)?;
@@ -526,6 +575,7 @@ pub fn type_check(arena: Arena) -> anyhow::Result<TypedContext> {
 /// # Parameters
 ///
 /// - `typed_context`: The typed AST context from [`type_check`]
+#[deprecated(since = "0.0.1", note = "use `Compiler::analyze` instead")]
 pub fn analyze(_: &TypedContext) -> anyhow::Result<()> {
     // todo!("Type analysis not yet implemented");
     Ok(())
@@ -639,6 +689,11 @@ pub fn analyze(_: &TypedContext) -> anyhow::Result<()> {
 /// - **Export section**: Public API exports (functions marked `pub`)
 /// - **Code section**: Function bodies in WASM bytecode
 ///
+/// # Determinism
+///
+/// Compiling the same source twice, in one process or in separate processes,
+/// produces byte-identical output.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -667,10 +722,70 @@ pub fn analyze(_: &TypedContext) -> anyhow::Result<()> {
 /// - Windows x86-64 (requires DLLs in `external/bin/windows/`)
 ///
 /// [`TypedContext`]: inference_type_checker::typed_context::TypedContext
+#[deprecated(since = "0.0.1", note = "use `Compiler::codegen` instead")]
 pub fn codegen(typed_context: &TypedContext) -> anyhow::Result<Vec<u8>> {
     inference_wasm_codegen::codegen(typed_context)
 }
 
+/// Generates WebAssembly for a specific [`Target`].
+///
+/// [`codegen`] always targets `wasm32`; use this function to select another
+/// target such as `wasm64`. Parse target names with [`str::parse`]; unknown
+/// names produce an [`UnsupportedTargetError`] that lists the supported ones.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inference::{CompileOptions, Compiler, Target};
+///
+/// let target: Target = "wasm64".parse()?;
+/// let compiler = Compiler::new(CompileOptions::new().with_target(target));
+/// let parsed = compiler.parse("pub fn answer() -> i32 { return 42; }")?;
+/// let typed = compiler.type_check(parsed)?;
+/// let wasm = compiler.codegen(&typed)?.wasm;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`codegen`].
+#[deprecated(
+    since = "0.0.1",
+    note = "use `Compiler::codegen` with `CompileOptions::with_target` instead"
+)]
+pub fn codegen_for_target(typed_context: &TypedContext, target: Target) -> anyhow::Result<Vec<u8>> {
+    inference_wasm_codegen::codegen_for_target(typed_context, target)
+}
+
+/// Renders a WebAssembly binary as WebAssembly text (WAT).
+///
+/// The output covers the non-deterministic instructions (`forall`, `exists`,
+/// `assume`, `unique`, `i32.uzumaki`, `i64.uzumaki`) and depends only on the
+/// input bytes, so identical binaries always produce identical text.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inference::{Compiler, wasm_to_wat};
+///
+/// let compiler = Compiler::default();
+/// let parsed = compiler.parse("pub fn answer() -> i32 { return 42; }")?;
+/// let typed = compiler.type_check(parsed)?;
+/// let wasm = compiler.codegen(&typed)?.wasm;
+/// let wat = wasm_to_wat(&wasm)?;
+/// assert!(wat.contains("i32.const 42"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the binary is malformed or uses instructions the
+/// printer does not support (anything beyond the WebAssembly MVP,
+/// sign-extension, and saturating conversions).
+pub fn wasm_to_wat(wasm: &[u8]) -> anyhow::Result<String> {
+    inference_wasm_to_v_translator::wat_printer::translate_to_wat(wasm)
+}
+
 /// Translates WebAssembly binary to Rocq (Coq) verification code.
 ///
 /// This function parses a WebAssembly binary and generates equivalent Rocq
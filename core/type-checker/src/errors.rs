@@ -47,6 +47,15 @@
 //! - [`TypeCheckError::ArrayIndexNotNumeric`] - Non-numeric array index
 //! - And more...
 //!
+//! ## Warnings
+//!
+//! Non-fatal diagnostics are reported as [`TypeCheckWarning`] values on the
+//! [`TypedContext`](crate::typed_context::TypedContext). Each warning has a
+//! stable [`WarningCode`] so lint configuration can target it:
+//!
+//! - [`WarningCode::UnusedImport`] (`unused_import`) - Import never referenced
+//! - [`WarningCode::ImportShadowed`] (`import_shadowed`) - Local definition hides an import
+//!
 //! ## Error Recovery
 //!
 //! The type checker implements error recovery to collect multiple errors:
@@ -64,6 +73,7 @@
 //!
 //! ```ignore
 //! use inference_type_checker::TypeCheckerBuilder;
+//! use inference_type_checker::errors::TypeCheckErrors;
 //!
 //! match TypeCheckerBuilder::build_typed_context(arena) {
 //!     Ok(completed) => {
@@ -72,8 +82,10 @@
 //!     Err(e) => {
 //!         // Error contains all collected errors
 //!         eprintln!("Type checking failed:");
-//!         for error_msg in e.to_string().split("; ") {
-//!             eprintln!("  - {}", error_msg);
+//!         if let Some(errors) = e.downcast_ref::<TypeCheckErrors>() {
+//!             for error in &errors.0 {
+//!                 eprintln!("  - {}: {}", error.location(), error.message());
+//!             }
 //!         }
 //!     }
 //! }
@@ -458,6 +470,111 @@ impl TypeCheckError {
             | TypeCheckError::AssociatedFunctionCalledAsMethod { location, .. } => location,
         }
     }
+
+    /// Returns the error message without the leading location.
+    ///
+    /// Diagnostic renderers print the location separately, next to the
+    /// offending source line.
+    #[must_use]
+    pub fn message(&self) -> String {
+        strip_location(&self.to_string(), self.location())
+    }
+}
+
+/// All errors collected during a type checking run.
+///
+/// Returned (inside `anyhow::Error`) when type checking fails; downcast to
+/// access the individual errors and their locations. `Display` joins the
+/// errors with `; `.
+#[derive(Debug, Clone)]
+pub struct TypeCheckErrors(pub Vec<TypeCheckError>);
+
+impl Display for TypeCheckErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        f.write_str(&messages.join("; "))
+    }
+}
+
+impl std::error::Error for TypeCheckErrors {}
+
+/// Removes the `"{location}: "` prefix that every error message starts with.
+fn strip_location(message: &str, location: &Location) -> String {
+    let prefix = format!("{location}: ");
+    message.strip_prefix(&prefix).unwrap_or(message).to_string()
+}
+
+/// Stable identifier of a warning kind.
+///
+/// The string form (see [`WarningCode::as_str`]) is what diagnostics print and
+/// what lint configuration will use to allow or deny a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningCode {
+    UnusedImport,
+    ImportShadowed,
+}
+
+impl WarningCode {
+    /// Returns the snake_case name of this warning code.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WarningCode::UnusedImport => "unused_import",
+            WarningCode::ImportShadowed => "import_shadowed",
+        }
+    }
+}
+
+impl Display for WarningCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents a non-fatal diagnostic found during type checking.
+///
+/// Warnings never fail type checking; they are available through
+/// [`TypedContext::warnings`](crate::typed_context::TypedContext::warnings).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TypeCheckWarning {
+    #[error("{location}: unused import `{path}`")]
+    UnusedImport { path: String, location: Location },
+
+    #[error("{location}: definition of `{name}` shadows the import at {import_location}")]
+    ImportShadowed {
+        name: String,
+        location: Location,
+        import_location: Location,
+    },
+}
+
+impl TypeCheckWarning {
+    /// Returns the code identifying this kind of warning.
+    #[must_use]
+    pub fn code(&self) -> WarningCode {
+        match self {
+            TypeCheckWarning::UnusedImport { .. } => WarningCode::UnusedImport,
+            TypeCheckWarning::ImportShadowed { .. } => WarningCode::ImportShadowed,
+        }
+    }
+
+    /// Returns the source location associated with this warning.
+    ///
+    /// For [`TypeCheckWarning::ImportShadowed`] this is the shadowing definition;
+    /// the import location is carried in the variant.
+    #[must_use]
+    pub fn location(&self) -> &Location {
+        match self {
+            TypeCheckWarning::UnusedImport { location, .. }
+            | TypeCheckWarning::ImportShadowed { location, .. } => location,
+        }
+    }
+
+    /// Returns the warning message without the leading location.
+    #[must_use]
+    pub fn message(&self) -> String {
+        strip_location(&self.to_string(), self.location())
+    }
 }
 
 #[cfg(test)]
@@ -1030,4 +1147,62 @@ mod tests {
         assert!(msg.contains("new"));
         assert!(msg.contains("cannot be called on an instance"));
     }
+
+    #[test]
+    fn display_warnings_with_codes() {
+        let unused = TypeCheckWarning::UnusedImport {
+            path: "self::Point".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(unused.to_string(), "1:5: unused import `self::Point`");
+        assert_eq!(unused.code().as_str(), "unused_import");
+
+        let shadowed = TypeCheckWarning::ImportShadowed {
+            name: "Point".to_string(),
+            location: Location {
+                start_line: 3,
+                start_column: 10,
+                ..test_location()
+            },
+            import_location: test_location(),
+        };
+        assert_eq!(
+            shadowed.to_string(),
+            "3:10: definition of `Point` shadows the import at 1:5"
+        );
+        assert_eq!(shadowed.code(), WarningCode::ImportShadowed);
+        assert_eq!(shadowed.location().start_line, 3);
+    }
+
+    #[test]
+    fn message_omits_location() {
+        let unknown = TypeCheckError::UnknownIdentifier {
+            name: "x".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(unknown.message(), "use of undeclared variable `x`");
+
+        let unused = TypeCheckWarning::UnusedImport {
+            path: "self::Point".to_string(),
+            location: test_location(),
+        };
+        assert_eq!(unused.message(), "unused import `self::Point`");
+    }
+
+    #[test]
+    fn display_type_check_errors_joins_messages() {
+        let errors = TypeCheckErrors(vec![
+            TypeCheckError::UnknownIdentifier {
+                name: "x".to_string(),
+                location: test_location(),
+            },
+            TypeCheckError::EmptyGlobImport {
+                location: test_location(),
+            },
+        ]);
+        assert_eq!(
+            errors.to_string(),
+            "1:5: use of undeclared variable `x`; 1:5: glob import path cannot be empty"
+        );
+    }
 }
@@ -41,6 +41,10 @@ pub(crate) struct FuncInfo {
     pub(crate) return_type: TypeInfo,
     pub(crate) visibility: Visibility,
     pub(crate) definition_scope_id: u32,
+    /// Parameter names of an `external fn`, `_` for unnamed parameters;
+    /// `None` for functions defined in Inference. Arguments of calls to
+    /// external functions are checked against `param_types`.
+    pub(crate) external_params: Option<Vec<String>>,
 }
 
 /// Information about a struct field.
@@ -613,6 +617,36 @@ impl SymbolTable {
                 return_type: TypeInfo::new_with_type_params(return_type, &type_params),
                 visibility,
                 definition_scope_id: scope_id,
+                external_params: None,
+            };
+            scope
+                .borrow_mut()
+                .insert_symbol(name, Symbol::Function(sig))
+                .map_err(|e| e.to_string())
+        } else {
+            Err("No active scope to register function".to_string())
+        }
+    }
+
+    /// Registers an `external fn` with its named parameters.
+    ///
+    /// `params` pairs each parameter name (`_` if unnamed) with its type.
+    pub(crate) fn register_external_function(
+        &mut self,
+        name: &str,
+        params: &[(String, Type)],
+        return_type: &Type,
+    ) -> Result<(), String> {
+        if let Some(scope) = &self.current_scope {
+            let scope_id = scope.borrow().id;
+            let sig = FuncInfo {
+                name: name.to_string(),
+                type_params: vec![],
+                param_types: params.iter().map(|(_, ty)| TypeInfo::new(ty)).collect(),
+                return_type: TypeInfo::new(return_type),
+                visibility: Visibility::Private,
+                definition_scope_id: scope_id,
+                external_params: Some(params.iter().map(|(name, _)| name.clone()).collect()),
             };
             scope
                 .borrow_mut()
@@ -745,6 +779,11 @@ impl SymbolTable {
         self.current_scope.as_ref().map(|s| s.borrow().id)
     }
 
+    #[must_use = "this is a pure lookup with no side effects"]
+    pub(crate) fn root_scope_id(&self) -> Option<u32> {
+        self.root_scope.as_ref().map(|s| s.borrow().id)
+    }
+
     #[must_use = "this is a pure lookup with no side effects"]
     pub(crate) fn get_scope(&self, scope_id: u32) -> Option<ScopeRef> {
         self.scopes.get(&scope_id).cloned()
@@ -1300,6 +1339,7 @@ mod tests {
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
                     definition_scope_id: 0,
+                    external_params: None,
                 },
                 visibility: Visibility::Private,
                 scope_id: 0,
@@ -1318,6 +1358,7 @@ mod tests {
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Public,
                     definition_scope_id: 0,
+                    external_params: None,
                 },
                 visibility: Visibility::Public,
                 scope_id: 0,
@@ -1337,6 +1378,7 @@ mod tests {
                 return_type: TypeInfo::default(),
                 visibility: Visibility::Public,
                 definition_scope_id: 0,
+                external_params: None,
             };
             let result = table.register_method("TestType", sig, Visibility::Public, true);
             assert!(result.is_ok());
@@ -1358,6 +1400,7 @@ mod tests {
                 return_type: TypeInfo::default(),
                 visibility: Visibility::Public,
                 definition_scope_id: 0,
+                external_params: None,
             };
             let result = table.register_method("TestType", sig, Visibility::Public, false);
             assert!(result.is_ok());
@@ -1378,6 +1421,7 @@ mod tests {
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
                     definition_scope_id: 0,
+                    external_params: None,
                 },
                 visibility: Visibility::Private,
                 scope_id: 0,
@@ -1391,6 +1435,7 @@ mod tests {
                     return_type: TypeInfo::default(),
                     visibility: Visibility::Private,
                     definition_scope_id: 0,
+                    external_params: None,
                 },
                 visibility: Visibility::Private,
                 scope_id: 0,
@@ -8,23 +8,26 @@
 //! 3. **resolve_imports** - Bind import paths to symbols
 //! 4. **collect_function_and_constant_definitions** - Register functions
 //! 5. **infer_variables** - Type-check function bodies
+//! 6. **check_imports** - Warn about unused and shadowed imports
 //!
 //! The type checker continues after encountering errors to collect all issues
 //! before returning. Errors are deduplicated to avoid repeated reports.
 
 use std::rc::Rc;
 
-use anyhow::bail;
 use inference_ast::extern_prelude::ExternPrelude;
 use inference_ast::nodes::{
-    ArgumentType, Definition, Directive, Expression, FunctionDefinition, Identifier, Literal,
-    Location, ModuleDefinition, OperatorKind, SimpleTypeKind, Statement, Type, UnaryOperatorKind,
-    UseDirective, Visibility,
+    ArgumentType, AstNode, Definition, Directive, Expression, ExternalFunctionDefinition,
+    FunctionDefinition, Identifier, Literal, Location, ModuleDefinition, OperatorKind,
+    SimpleTypeKind, Statement, Type, UnaryOperatorKind, UseDirective, Visibility,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    errors::{RegistrationKind, TypeCheckError, TypeMismatchContext, VisibilityContext},
+    errors::{
+        RegistrationKind, TypeCheckError, TypeCheckErrors, TypeCheckWarning, TypeMismatchContext,
+        VisibilityContext,
+    },
     symbol_table::{FuncInfo, Import, ImportItem, ImportKind, ResolvedImport, SymbolTable},
     type_info::{NumberType, TypeInfo, TypeInfoKind},
     typed_context::TypedContext,
@@ -34,6 +37,7 @@ use crate::{
 pub(crate) struct TypeChecker {
     symbol_table: SymbolTable,
     errors: Vec<TypeCheckError>,
+    warnings: Vec<TypeCheckWarning>,
     glob_resolution_in_progress: FxHashSet<u32>,
     reported_error_keys: FxHashSet<String>,
 }
@@ -68,6 +72,7 @@ impl TypeChecker {
     /// 3. `resolve_imports()` - Bind import paths to symbols
     /// 4. `collect_function_and_constant_definitions()` - Register functions
     /// 5. Infer variable types in function bodies
+    /// 6. `check_imports()` - Report unused and shadowed imports as warnings
     pub fn infer_types(&mut self, ctx: &mut TypedContext) -> anyhow::Result<SymbolTable> {
         self.process_directives(ctx);
         self.register_types(ctx);
@@ -94,13 +99,11 @@ impl TypeChecker {
                 }
             }
         }
+        self.check_imports(ctx);
         if !self.errors.is_empty() {
-            let error_messages: Vec<String> = std::mem::take(&mut self.errors)
-                .into_iter()
-                .map(|e| e.to_string())
-                .collect();
-            bail!(error_messages.join("; "))
+            return Err(TypeCheckErrors(std::mem::take(&mut self.errors)).into());
         }
+        ctx.warnings = std::mem::take(&mut self.warnings);
         Ok(self.symbol_table.clone())
     }
 
@@ -194,6 +197,7 @@ impl TypeChecker {
                                 return_type,
                                 visibility: method.visibility.clone(),
                                 definition_scope_id,
+                                external_params: None,
                             };
 
                             self.symbol_table
@@ -369,36 +373,7 @@ impl TypeChecker {
                         }
                     }
                     Definition::ExternalFunction(external_function_definition) => {
-                        if let Err(err) = self.symbol_table.register_function(
-                            &external_function_definition.name(),
-                            vec![],
-                            &external_function_definition
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_argument) => {
-                                        Some(ignore_argument.ty.clone())
-                                    }
-                                    ArgumentType::Argument(argument) => Some(argument.ty.clone()),
-                                    ArgumentType::Type(ty) => Some(ty.clone()),
-                                })
-                                .collect::<Vec<_>>(),
-                            &external_function_definition
-                                .returns
-                                .as_ref()
-                                .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
-                                .clone(),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Function,
-                                name: external_function_definition.name(),
-                                reason: Some(err),
-                                location: external_function_definition.location,
-                            });
-                        }
+                        self.register_external_function(external_function_definition);
                     }
                     Definition::Spec(_)
                     | Definition::Struct(_)
@@ -410,6 +385,44 @@ impl TypeChecker {
         }
     }
 
+    /// Registers an `external fn` in the current scope.
+    ///
+    /// Parameters keep their names so that argument type mismatches at call
+    /// sites can name the parameter. A missing return type means unit.
+    fn register_external_function(&mut self, definition: &ExternalFunctionDefinition) {
+        let params = definition
+            .arguments
+            .as_ref()
+            .unwrap_or(&vec![])
+            .iter()
+            .filter_map(|param| match param {
+                ArgumentType::SelfReference(_) => None,
+                ArgumentType::IgnoreArgument(ignore_argument) => {
+                    Some((String::from("_"), ignore_argument.ty.clone()))
+                }
+                ArgumentType::Argument(argument) => {
+                    Some((argument.name.name.clone(), argument.ty.clone()))
+                }
+                ArgumentType::Type(ty) => Some((String::from("_"), ty.clone())),
+            })
+            .collect::<Vec<_>>();
+        let returns = definition
+            .returns
+            .clone()
+            .unwrap_or(Type::Simple(SimpleTypeKind::Unit));
+        if let Err(err) =
+            self.symbol_table
+                .register_external_function(&definition.name(), &params, &returns)
+        {
+            self.errors.push(TypeCheckError::RegistrationFailed {
+                kind: RegistrationKind::Function,
+                name: definition.name(),
+                reason: Some(err),
+                location: definition.location,
+            });
+        }
+    }
+
     /// Validates that a type reference is well-formed.
     ///
     /// Checks that:
@@ -1211,17 +1224,19 @@ impl TypeChecker {
                     }
                     return None;
                 };
-                if let Some(arguments) = &function_call_expression.arguments
-                    && arguments.len() != signature.param_types.len()
-                {
+                let arg_count = function_call_expression
+                    .arguments
+                    .as_ref()
+                    .map_or(0, Vec::len);
+                if arg_count != signature.param_types.len() {
                     self.errors.push(TypeCheckError::ArgumentCountMismatch {
                         kind: "function",
                         name: function_call_expression.name(),
                         expected: signature.param_types.len(),
-                        found: arguments.len(),
+                        found: arg_count,
                         location: function_call_expression.location,
                     });
-                    for arg in arguments {
+                    for arg in function_call_expression.arguments.iter().flatten() {
                         self.infer_expression(&arg.1.borrow(), ctx);
                     }
                     return None;
@@ -1283,10 +1298,29 @@ impl TypeChecker {
                 // Apply substitution to return type
                 let return_type = signature.return_type.substitute(&substitutions);
 
-                // Infer argument types
+                // Infer argument types; those of external functions must
+                // match the declared parameter types exactly.
                 if let Some(arguments) = &function_call_expression.arguments {
-                    for arg in arguments {
-                        self.infer_expression(&arg.1.borrow(), ctx);
+                    for (arg_index, arg) in arguments.iter().enumerate() {
+                        let arg_type = self.infer_expression(&arg.1.borrow(), ctx);
+                        let Some(param_names) = &signature.external_params else {
+                            continue;
+                        };
+                        let expected = &signature.param_types[arg_index];
+                        if let Some(found) = arg_type
+                            && found != *expected
+                        {
+                            self.errors.push(TypeCheckError::TypeMismatch {
+                                expected: expected.clone(),
+                                found,
+                                context: TypeMismatchContext::FunctionArgument {
+                                    function_name: function_call_expression.name(),
+                                    arg_name: param_names[arg_index].clone(),
+                                    arg_index,
+                                },
+                                location: function_call_expression.location,
+                            });
+                        }
                     }
                 }
 
@@ -1703,36 +1737,7 @@ impl TypeChecker {
                         }
                     }
                     Definition::ExternalFunction(external_function_definition) => {
-                        if let Err(err) = self.symbol_table.register_function(
-                            &external_function_definition.name(),
-                            vec![],
-                            &external_function_definition
-                                .arguments
-                                .as_ref()
-                                .unwrap_or(&vec![])
-                                .iter()
-                                .filter_map(|param| match param {
-                                    ArgumentType::SelfReference(_) => None,
-                                    ArgumentType::IgnoreArgument(ignore_argument) => {
-                                        Some(ignore_argument.ty.clone())
-                                    }
-                                    ArgumentType::Argument(argument) => Some(argument.ty.clone()),
-                                    ArgumentType::Type(ty) => Some(ty.clone()),
-                                })
-                                .collect::<Vec<_>>(),
-                            &external_function_definition
-                                .returns
-                                .as_ref()
-                                .unwrap_or(&Type::Simple(SimpleTypeKind::Unit))
-                                .clone(),
-                        ) {
-                            self.errors.push(TypeCheckError::RegistrationFailed {
-                                kind: RegistrationKind::Function,
-                                name: external_function_definition.name(),
-                                reason: Some(err),
-                                location: external_function_definition.location,
-                            });
-                        }
+                        self.register_external_function(external_function_definition);
                     }
                 }
             }
@@ -1906,6 +1911,160 @@ impl TypeChecker {
         }
     }
 
+    /// Report unused and shadowed imports of the root scope (runs after inference).
+    ///
+    /// An import is used when its local name appears anywhere outside a
+    /// declaration, including as the qualifier of a path such as
+    /// `Counter::zero()`. An import is shadowed when a local definition has the
+    /// same name: a top-level definition that is not the imported item itself,
+    /// or a type or constant defined inside a function body. Unresolved imports
+    /// are already errors and glob imports are not checked.
+    fn check_imports(&mut self, ctx: &TypedContext) {
+        let Some(root_scope) = self
+            .symbol_table
+            .root_scope_id()
+            .and_then(|id| self.symbol_table.get_scope(id))
+        else {
+            return;
+        };
+        let root_scope_id = root_scope.borrow().id;
+        let imports = root_scope.borrow().imports.clone();
+        if imports.is_empty() {
+            return;
+        }
+
+        // (local name, full path, import location, defined in root scope)
+        let mut imported: Vec<(String, String, Location, bool)> = Vec::new();
+        for import in &imports {
+            let names: Vec<(String, Vec<String>)> = match &import.kind {
+                ImportKind::Plain => import
+                    .path
+                    .last()
+                    .map(|name| (name.clone(), import.path.clone()))
+                    .into_iter()
+                    .collect(),
+                ImportKind::Partial(items) => items
+                    .iter()
+                    .map(|item| {
+                        let mut full_path = import.path.clone();
+                        full_path.push(item.name.clone());
+                        (
+                            item.alias.clone().unwrap_or_else(|| item.name.clone()),
+                            full_path,
+                        )
+                    })
+                    .collect(),
+                ImportKind::Glob => Vec::new(),
+            };
+            for (local_name, full_path) in names {
+                let definition_scope_id = root_scope
+                    .borrow()
+                    .lookup_resolved_import(&local_name)
+                    .map(|resolved| resolved.definition_scope_id);
+                if let Some(definition_scope_id) = definition_scope_id {
+                    imported.push((
+                        local_name,
+                        full_path.join("::"),
+                        import.location,
+                        definition_scope_id == root_scope_id,
+                    ));
+                }
+            }
+        }
+
+        // Identifiers that name something rather than refer to it.
+        let mut declaration_ids: FxHashSet<u32> = FxHashSet::default();
+        let mut top_level_definition_ids: FxHashSet<u32> = FxHashSet::default();
+        // (name, defined at top level)
+        let mut local_definitions: Vec<(Rc<Identifier>, bool)> = Vec::new();
+        for source_file in ctx.source_files() {
+            for directive in &source_file.directives {
+                match directive {
+                    Directive::Use(use_directive) => {
+                        for segment in use_directive
+                            .segments
+                            .iter()
+                            .chain(use_directive.imported_types.iter())
+                            .flatten()
+                        {
+                            declaration_ids.insert(segment.id);
+                        }
+                    }
+                }
+            }
+            for definition in &source_file.definitions {
+                let name = definition_name(definition);
+                top_level_definition_ids.insert(definition.id());
+                declaration_ids.insert(name.id);
+                local_definitions.push((name, true));
+            }
+        }
+        for node in ctx.filter_nodes(|node| {
+            matches!(
+                node,
+                AstNode::Statement(Statement::TypeDefinition(_))
+                    | AstNode::Definition(Definition::Constant(_))
+            )
+        }) {
+            let name = match &node {
+                AstNode::Statement(Statement::TypeDefinition(type_definition)) => {
+                    type_definition.name.clone()
+                }
+                AstNode::Definition(Definition::Constant(constant))
+                    if !top_level_definition_ids.contains(&constant.id) =>
+                {
+                    constant.name.clone()
+                }
+                _ => continue,
+            };
+            declaration_ids.insert(name.id);
+            local_definitions.push((name, false));
+        }
+
+        let referenced_names: FxHashSet<String> = ctx
+            .filter_nodes(|node| {
+                matches!(
+                    node,
+                    AstNode::Expression(Expression::Identifier(identifier))
+                        if !declaration_ids.contains(&identifier.id)
+                )
+            })
+            .into_iter()
+            .filter_map(|node| match node {
+                AstNode::Expression(Expression::Identifier(identifier)) => {
+                    Some(identifier.name.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        for (local_name, path, import_location, defined_in_root) in &imported {
+            if !referenced_names.contains(local_name) {
+                warnings.push(TypeCheckWarning::UnusedImport {
+                    path: path.clone(),
+                    location: *import_location,
+                });
+            }
+            for (name, top_level) in &local_definitions {
+                // `use self::Item;` next to `struct Item` imports the definition
+                // itself, so it does not hide anything.
+                if name.name == *local_name && !(*top_level && *defined_in_root) {
+                    warnings.push(TypeCheckWarning::ImportShadowed {
+                        name: local_name.clone(),
+                        location: name.location,
+                        import_location: *import_location,
+                    });
+                }
+            }
+        }
+        warnings.sort_by_key(|warning| {
+            let location = warning.location();
+            (location.offset_start, location.offset_end)
+        });
+        self.warnings.extend(warnings);
+    }
+
     /// Resolve a glob import (`use path::*`) by importing all public symbols from the target module.
     fn resolve_glob_import(&mut self, path: &[String], location: &Location, into_scope_id: u32) {
         if path.is_empty() {
@@ -2099,3 +2258,17 @@ impl TypeChecker {
         self.errors.push(error);
     }
 }
+
+/// Returns the name identifier of a definition.
+fn definition_name(definition: &Definition) -> Rc<Identifier> {
+    match definition {
+        Definition::Spec(spec) => spec.name.clone(),
+        Definition::Struct(struct_definition) => struct_definition.name.clone(),
+        Definition::Enum(enum_definition) => enum_definition.name.clone(),
+        Definition::Constant(constant) => constant.name.clone(),
+        Definition::Function(function) => function.name.clone(),
+        Definition::ExternalFunction(external) => external.name.clone(),
+        Definition::Type(type_definition) => type_definition.name.clone(),
+        Definition::Module(module) => module.name.clone(),
+    }
+}
@@ -70,6 +70,7 @@
 //! - [`filter_nodes`](TypedContext::filter_nodes) - Find nodes matching predicate
 //! - [`source_files`](TypedContext::source_files) - Get all source files
 //! - [`functions`](TypedContext::functions) - Get all function definitions
+//! - [`warnings`](TypedContext::warnings) - Get non-fatal diagnostics such as unused imports
 //!
 //! ## Arena Integration
 //!
@@ -82,6 +83,7 @@
 use std::rc::Rc;
 
 use crate::{
+    errors::TypeCheckWarning,
     symbol_table::SymbolTable,
     type_info::{NumberType, TypeInfo, TypeInfoKind},
 };
@@ -95,6 +97,7 @@ use rustc_hash::FxHashMap;
 pub struct TypedContext {
     pub(crate) symbol_table: SymbolTable,
     node_types: FxHashMap<u32, TypeInfo>,
+    pub(crate) warnings: Vec<TypeCheckWarning>,
     arena: Arena,
 }
 
@@ -103,6 +106,7 @@ impl TypedContext {
         Self {
             symbol_table: SymbolTable::default(),
             node_types: FxHashMap::default(),
+            warnings: Vec::new(),
             arena,
         }
     }
@@ -148,6 +152,23 @@ impl TypedContext {
         self.arena.functions()
     }
 
+    /// Returns the warnings reported during type checking, ordered by location.
+    ///
+    /// Warnings do not fail type checking. Each one carries a
+    /// [`WarningCode`](crate::errors::WarningCode) identifying its kind.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for warning in typed_context.warnings() {
+    ///     eprintln!("warning[{}]: {}", warning.code(), warning);
+    /// }
+    /// ```
+    #[must_use = "returns warnings without side effects"]
+    pub fn warnings(&self) -> &[TypeCheckWarning] {
+        &self.warnings
+    }
+
     /// Filters AST nodes using a predicate function.
     ///
     /// This method traverses all nodes in the arena and returns those that match
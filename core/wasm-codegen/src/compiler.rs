@@ -89,7 +89,7 @@
 
 //TODO: don't forget to remove
 #![allow(dead_code)]
-use crate::utils;
+use crate::{target::Target, utils};
 use inference_ast::nodes::{
     BlockType, Expression, FunctionDefinition, Literal, SimpleTypeKind, Statement, Type, Visibility,
 };
@@ -197,7 +197,7 @@ const UNIQUE_END_INTRINSIC: &str = "llvm.wasm.unique.end";
 /// }
 ///
 /// // Compile to WebAssembly
-/// let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3)?;
+/// let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3, crate::Target::Wasm32)?;
 /// ```
 pub(crate) struct Compiler<'ctx> {
     /// LLVM context for creating types and values.
@@ -961,6 +961,7 @@ impl<'ctx> Compiler<'ctx> {
     ///
     /// - `output_fname` - Base filename for intermediate files (extension will be added)
     /// - `optimization_level` - LLVM optimization level (0-3, higher is more optimized)
+    /// - `target` - WebAssembly target that selects the triple and memory model
     ///
     /// # Returns
     ///
@@ -976,8 +977,15 @@ impl<'ctx> Compiler<'ctx> {
         &self,
         output_fname: &str,
         optimization_level: u32,
+        target: Target,
     ) -> anyhow::Result<Vec<u8>> {
         let has_main = *self.has_main.borrow();
-        utils::compile_to_wasm(&self.module, output_fname, optimization_level, has_main)
+        utils::compile_to_wasm(
+            &self.module,
+            output_fname,
+            optimization_level,
+            target,
+            has_main,
+        )
     }
 }
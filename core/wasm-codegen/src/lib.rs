@@ -58,22 +58,31 @@
 //!
 //! - [`compiler`] - LLVM IR generation and intrinsic handling (private)
 //! - [`utils`] - External toolchain invocation and environment setup (private)
+//! - [`target`] - Supported WebAssembly targets
 //! - [`codegen`] - Public API for WebAssembly generation
+//!
+//! # Targets
+//!
+//! [`codegen`] emits `wasm32` modules. Use [`codegen_for_target`] to select
+//! another [`Target`], such as `wasm64`.
 
 #![warn(clippy::pedantic)]
 
 use inference_type_checker::typed_context::TypedContext;
 use inkwell::{
     context::Context,
-    targets::{InitializationConfig, Target},
+    targets::{InitializationConfig, Target as LlvmTarget},
 };
 
 use crate::compiler::Compiler;
 
 mod compiler;
+pub mod target;
 mod utils;
 
-/// Generates WebAssembly bytecode from a typed AST.
+pub use target::{Target, UnsupportedTargetError};
+
+/// Generates WebAssembly bytecode from a typed AST for the default `wasm32` target.
 ///
 /// # Errors
 ///
@@ -82,19 +91,28 @@ mod utils;
 ///
 /// Returns an error if code generation fails.
 pub fn codegen(typed_context: &TypedContext) -> anyhow::Result<Vec<u8>> {
-    Target::initialize_webassembly(&InitializationConfig::default());
+    codegen_for_target(typed_context, Target::default())
+}
+
+/// Generates WebAssembly bytecode from a typed AST for the given target.
+///
+/// # Errors
+///
+/// Returns the same errors as [`codegen`].
+pub fn codegen_for_target(typed_context: &TypedContext, target: Target) -> anyhow::Result<Vec<u8>> {
+    LlvmTarget::initialize_webassembly(&InitializationConfig::default());
     let context = Context::create();
     let compiler = Compiler::new(&context, "wasm_module");
 
     if typed_context.source_files().is_empty() {
-        return compiler.compile_to_wasm("output.wasm", 3);
+        return compiler.compile_to_wasm("output.wasm", 3, target);
     }
     if typed_context.source_files().len() > 1 {
         todo!("Multi-file support not yet implemented");
     }
 
     traverse_t_ast_with_compiler(typed_context, &compiler);
-    let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3)?;
+    let wasm_bytes = compiler.compile_to_wasm("output.wasm", 3, target)?;
     Ok(wasm_bytes)
 }
 
@@ -115,8 +133,15 @@ pub fn codegen(typed_context: &TypedContext) -> anyhow::Result<Vec<u8>> {
 /// - Only function definitions are compiled
 /// - Type definitions, constants, and other top-level items are ignored
 /// - Multi-file compilation is not fully tested (see `codegen` function)
+///
+/// # Determinism
+///
+/// The arena stores nodes in a hash map, so source files are sorted by node ID
+/// before traversal. Functions within a file are emitted in source order.
 fn traverse_t_ast_with_compiler(typed_context: &TypedContext, compiler: &Compiler) {
-    for source_file in &typed_context.source_files() {
+    let mut source_files = typed_context.source_files();
+    source_files.sort_by_key(|source_file| source_file.id);
+    for source_file in &source_files {
         for func_def in source_file.function_definitions() {
             compiler.visit_function_definition(&func_def, typed_context);
         }
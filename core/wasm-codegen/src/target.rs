@@ -0,0 +1,91 @@
+//! WebAssembly targets supported by the code generator.
+//!
+//! The target selects the LLVM target triple passed to inf-llc and the
+//! memory model requested from rust-lld. `wasm32` is the default; `wasm64`
+//! produces modules that use the memory64 proposal.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// WebAssembly target to generate code for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// 32-bit linear memory (`wasm32-unknown-unknown`).
+    #[default]
+    Wasm32,
+    /// 64-bit linear memory (`wasm64-unknown-unknown`).
+    Wasm64,
+}
+
+impl Target {
+    /// All targets supported by this code generator.
+    pub const ALL: &'static [Target] = &[Target::Wasm32, Target::Wasm64];
+
+    /// Returns the short name used on the command line and in manifests.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Target::Wasm32 => "wasm32",
+            Target::Wasm64 => "wasm64",
+        }
+    }
+
+    /// Returns the LLVM target triple for this target.
+    #[must_use]
+    pub fn triple(self) -> &'static str {
+        match self {
+            Target::Wasm32 => "wasm32-unknown-unknown",
+            Target::Wasm64 => "wasm64-unknown-unknown",
+        }
+    }
+
+    /// Returns the supported target names as a comma-separated list.
+    #[must_use]
+    pub fn supported_names() -> String {
+        Self::ALL
+            .iter()
+            .map(|target| target.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown target name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedTargetError {
+    /// The rejected target name.
+    pub name: String,
+}
+
+impl fmt::Display for UnsupportedTargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsupported target `{}`; supported targets: {}",
+            self.name,
+            Target::supported_names()
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedTargetError {}
+
+impl FromStr for Target {
+    type Err = UnsupportedTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|target| target.as_str() == s)
+            .ok_or_else(|| UnsupportedTargetError {
+                name: s.to_string(),
+            })
+    }
+}
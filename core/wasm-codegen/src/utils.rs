@@ -25,7 +25,8 @@
 //! ## Stage 1: IR Emission
 //!
 //! The LLVM module is serialized to a temporary `.ll` file (LLVM IR text format).
-//! The module is configured with the `wasm32-unknown-unknown` target triple.
+//! The module is configured with the target triple of the requested [`Target`]
+//! (`wasm32-unknown-unknown` by default, `wasm64-unknown-unknown` for `wasm64`).
 //!
 //! ## Stage 2: Object Compilation (inf-llc)
 //!
@@ -42,6 +43,7 @@
 //! - `-flavor wasm` - Use WebAssembly linker mode
 //! - `--no-entry` - Reactor model (no implicit `_start` function)
 //! - `--export=main` - Explicitly export `main` function if present
+//! - `-mwasm64` - Link a memory64 module (only for the `wasm64` target)
 //!
 //! Output: `.wasm` WebAssembly module
 //!
@@ -119,6 +121,8 @@ use std::{path::PathBuf, process::Command};
 use inkwell::{module::Module, targets::TargetTriple};
 use tempfile::tempdir;
 
+use crate::target::Target;
+
 /// Compiles an LLVM module to WebAssembly bytecode via external toolchain.
 ///
 /// This function orchestrates the complete compilation pipeline from LLVM IR to WASM,
@@ -127,7 +131,7 @@ use tempfile::tempdir;
 /// # Compilation Stages
 ///
 /// 1. **IR emission** - Write LLVM module to temporary `.ll` file
-/// 2. **Object compilation** - Invoke inf-llc with target wasm32-unknown-unknown
+/// 2. **Object compilation** - Invoke inf-llc with the target triple of `target`
 /// 3. **Linking** - Invoke rust-lld with wasm flavor to produce final module
 /// 4. **Cleanup** - Read WASM bytes and remove temporary object file
 ///
@@ -136,6 +140,7 @@ use tempfile::tempdir;
 /// - `module` - LLVM module containing the IR to compile
 /// - `output_fname` - Base filename for intermediate files (extensions added automatically)
 /// - `optimization_level` - LLVM optimization level (0-3, clamped to max 3)
+/// - `target` - WebAssembly target; `wasm64` also passes `-mwasm64` to rust-lld
 /// - `has_main` - Whether to export a `main` function (only if the module contains one)
 ///
 /// # Returns
@@ -180,13 +185,14 @@ pub(crate) fn compile_to_wasm(
     module: &Module,
     output_fname: &str,
     optimization_level: u32,
+    target: Target,
     has_main: bool,
 ) -> anyhow::Result<Vec<u8>> {
     let llc_path = get_inf_llc_path()?;
     let temp_dir = tempdir()?;
     let obj_path = temp_dir.path().join(output_fname).with_extension("o");
     let ir_path = temp_dir.path().join(output_fname).with_extension("ll");
-    let triple = TargetTriple::create("wasm32-unknown-unknown");
+    let triple = TargetTriple::create(target.triple());
     module.set_triple(&triple);
     let ir_str = module.print_to_string().to_string();
     std::fs::write(&ir_path, ir_str)?;
@@ -220,6 +226,9 @@ pub(crate) fn compile_to_wasm(
         .arg("wasm")
         .arg(&obj_path)
         .arg("--no-entry");
+    if target == Target::Wasm64 {
+        lld_cmd.arg("-mwasm64");
+    }
     if has_main {
         lld_cmd.arg("--export=main");
     }
@@ -52,6 +52,12 @@
 //! Implements error recovery to collect multiple translation failures before
 //! reporting.
 //!
+//! ### WAT Output ([`wat_printer`])
+//!
+//! [`wat_printer::translate_to_wat`] reuses the parsing phase to render the
+//! same module as WebAssembly text, including the non-deterministic extension
+//! instructions.
+//!
 //! ### WASM Sections Supported
 //!
 //! - **Type Section**: Function signatures as recursion groups
@@ -162,6 +168,7 @@
 
 pub mod translator;
 pub mod wasm_parser;
+pub mod wat_printer;
 
 #[cfg(test)]
 mod tests {
@@ -257,4 +264,19 @@ mod tests {
             (success_count as f64 / wasm_files.len() as f64) * 100.0
         );
     }
+
+    #[test]
+    fn test_translation_is_deterministic() {
+        let wasm_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join("func_ptrs.0.wasm");
+        let bytes = fs::read(&wasm_path).expect("Failed to read func_ptrs.0.wasm");
+
+        let first = translate_bytes("func_ptrs", &bytes).expect("Translation should succeed");
+        let second = translate_bytes("func_ptrs", &bytes).expect("Translation should succeed");
+
+        assert_eq!(first, second);
+        // The module has no name section, so functions are named by index.
+        assert!(first.contains("func_1"));
+    }
 }
@@ -117,7 +117,8 @@
 //! Generated Rocq identifiers follow these rules:
 //!
 //! - **Named functions**: Use names from custom name section if available
-//! - **Anonymous functions**: Generate unique names using UUID (`func_<uuid>`)
+//! - **Anonymous functions**: Named after their function index (`func_<index>`), so
+//!   repeated translations of the same module are byte-identical
 //! - **Module name**: Use name from custom section, or parameter to `translate_bytes`
 //!
 //! ## Output Format
@@ -166,7 +167,6 @@ use inf_wasmparser::{
     FunctionBody, Global, Import, MemoryType, Operator, OperatorsIterator, OperatorsReader,
     RecGroup, RefType, Table, TableType, TypeRef, ValType as wpValType,
 };
-use uuid::Uuid;
 
 const LCB: &str = "{|\n";
 const RCB_DOT: &str = "|}.\n";
@@ -507,14 +507,12 @@ impl WasmParseData<'_> {
     fn translate_functions(&mut self) -> anyhow::Result<()> {
         for (index, function_body) in self.function_bodies.iter().enumerate() {
             let modfunc_type = *self.function_type_indexes.get(index).unwrap_or(&0);
-            let func_name = if let Some(func_names_map) = &self.func_names_map {
-                func_names_map
-                    .get(&(index as u32))
-                    .unwrap_or(&format!("func_{}", get_id()))
-                    .to_owned()
-            } else {
-                format!("func_{}", get_id())
-            };
+            let func_name = self
+                .func_names_map
+                .as_ref()
+                .and_then(|names| names.get(&(index as u32)))
+                .cloned()
+                .unwrap_or_else(|| format!("func_{index}"));
             self.translated_function_names.push(func_name.clone());
 
             let mut modfunc_locals = String::new();
@@ -2009,9 +2007,3 @@ fn translate_data(data: &Data) -> anyhow::Result<String> {
     res.push_str("|}");
     Ok(res)
 }
-
-fn get_id() -> String {
-    let uuid = Uuid::new_v4().to_string();
-    let mut parts = uuid.split('-');
-    parts.next().unwrap().to_string()
-}
@@ -141,16 +141,16 @@ use crate::translator::WasmParseData;
 /// Integration with Inference compiler:
 ///
 /// ```ignore
-/// use inference::{parse, type_check, codegen};
+/// use inference::Compiler;
 /// use inference_wasm_to_v_translator::wasm_parser::translate_bytes;
 ///
 /// let source = std::fs::read_to_string("program.inf")?;
-/// let arena = parse(&source)?;
-/// let typed_context = type_check(arena)?;
-/// let wasm_bytes = codegen(&typed_context)?;
+/// let compiler = Compiler::default();
+/// let typed = compiler.type_check(compiler.parse(&source)?)?;
+/// let artifacts = compiler.codegen(&typed)?;
 ///
 /// // Translate to Rocq
-/// let rocq_code = translate_bytes("Program", &wasm_bytes)?;
+/// let rocq_code = translate_bytes("Program", &artifacts.wasm)?;
 /// std::fs::write("program.v", rocq_code)?;
 /// ```
 pub fn translate_bytes(mod_name: &str, bytes: &[u8]) -> anyhow::Result<String> {
@@ -201,7 +201,7 @@ pub fn translate_bytes(mod_name: &str, bytes: &[u8]) -> anyhow::Result<String> {
 ///
 /// Returns an error if WASM bytecode is malformed or contains invalid section data.
 #[allow(clippy::match_same_arms)]
-fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>> {
+pub(crate) fn parse(mod_name: String, data: &'_ [u8]) -> anyhow::Result<WasmParseData<'_>> {
     let parser = Parser::new(0);
     let mut wasm_parse_data = WasmParseData::new(mod_name);
 
@@ -0,0 +1,984 @@
+//! WebAssembly Text Format (WAT) Printer
+//!
+//! This module renders a WASM binary produced by the Inference code generator
+//! as WebAssembly text, including the non-deterministic extension instructions
+//! (`forall`, `exists`, `assume`, `unique`, `i32.uzumaki`, `i64.uzumaki`).
+//!
+//! The printer reuses [`crate::wasm_parser`] to collect module sections and
+//! then walks them in section order. The output is a pure function of the
+//! input bytes: sections are printed in binary order, functions are named from
+//! the custom name section when a name is a valid, unique WAT identifier, and
+//! everything else is referenced by index. Two identical binaries always yield
+//! byte-identical text.
+//!
+//! ## Supported Instructions
+//!
+//! The printer covers the WebAssembly MVP instruction set, sign-extension and
+//! saturating conversions, `ref.func`, and the Inference extensions. Any other
+//! instruction is reported as an error rather than printed incorrectly.
+//!
+//! ## Output Format
+//!
+//! ```wat
+//! (module
+//!   (type (;0;) (func (result i32)))
+//!   (import "env" "__linear_memory" (memory (;0;) 0))
+//!   (func $hello_uzumaki (;0;) (type 0) (result i32)
+//!     i32.uzumaki
+//!   )
+//!   (export "hello_uzumaki" (func $hello_uzumaki))
+//! )
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use inf_wasmparser::{
+    BlockType, CompositeInnerType, ConstExpr, DataKind, ElementItems, ElementKind, ExternalKind,
+    MemArg, MemoryType, Operator, RefType, TableType, TypeRef, ValType,
+};
+
+use crate::{translator::WasmParseData, wasm_parser::parse};
+
+const INDENT: &str = "  ";
+
+/// Renders WebAssembly bytecode as WebAssembly text.
+///
+/// # Errors
+///
+/// Returns an error if the bytecode is malformed or contains instructions or
+/// types outside the supported set (see the module documentation).
+///
+/// # Examples
+///
+/// ```ignore
+/// use inference_wasm_to_v_translator::wat_printer::translate_to_wat;
+///
+/// let wasm_bytes = std::fs::read("output.wasm")?;
+/// let wat = translate_to_wat(&wasm_bytes)?;
+/// std::fs::write("output.wat", wat)?;
+/// ```
+pub fn translate_to_wat(bytes: &[u8]) -> anyhow::Result<String> {
+    let data = parse(String::new(), bytes)?;
+    WatPrinter::new(&data).print()
+}
+
+/// Per-module state needed while printing.
+struct WatPrinter<'a, 'b> {
+    data: &'b WasmParseData<'a>,
+    /// Function signatures by type index; `None` for non-function types.
+    func_types: Vec<Option<(Vec<ValType>, Vec<ValType>)>>,
+    /// WAT identifiers (without `$`) for functions that have a usable name.
+    func_ids: HashMap<u32, String>,
+    imported_funcs: u32,
+    imported_tables: u32,
+    imported_memories: u32,
+    imported_globals: u32,
+    out: String,
+}
+
+impl<'a, 'b> WatPrinter<'a, 'b> {
+    fn new(data: &'b WasmParseData<'a>) -> Self {
+        let func_types = data
+            .function_types
+            .iter()
+            .flat_map(|rec_group| {
+                rec_group
+                    .types()
+                    .map(|ty| match &ty.composite_type.inner {
+                        CompositeInnerType::Func(ft) => {
+                            Some((ft.params().to_vec(), ft.results().to_vec()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let count = |pred: fn(&TypeRef) -> bool| {
+            u32::try_from(data.imports.iter().filter(|i| pred(&i.ty)).count()).unwrap_or(u32::MAX)
+        };
+
+        Self {
+            data,
+            func_types,
+            func_ids: func_identifiers(data.func_names_map.as_ref()),
+            imported_funcs: count(|ty| matches!(ty, TypeRef::Func(_))),
+            imported_tables: count(|ty| matches!(ty, TypeRef::Table(_))),
+            imported_memories: count(|ty| matches!(ty, TypeRef::Memory(_))),
+            imported_globals: count(|ty| matches!(ty, TypeRef::Global(_))),
+            out: String::new(),
+        }
+    }
+
+    fn print(mut self) -> anyhow::Result<String> {
+        self.out.push_str("(module");
+        if !self.data.mod_name.is_empty() && is_identifier(&self.data.mod_name) {
+            let _ = write!(self.out, " ${}", self.data.mod_name);
+        }
+        self.out.push('\n');
+
+        self.print_types()?;
+        self.print_imports()?;
+        self.print_functions()?;
+        self.print_tables()?;
+        self.print_memories();
+        self.print_globals()?;
+        self.print_exports()?;
+        if let Some(start) = self.data.start_function {
+            let start = self.func_ref(start);
+            let _ = writeln!(self.out, "{INDENT}(start {start})");
+        }
+        self.print_elements()?;
+        self.print_data()?;
+
+        self.out.push_str(")\n");
+        Ok(self.out)
+    }
+
+    fn print_types(&mut self) -> anyhow::Result<()> {
+        for (index, signature) in self.func_types.iter().enumerate() {
+            let Some((params, results)) = signature else {
+                return Err(anyhow::anyhow!(
+                    "Type {index} is not a function type and cannot be printed"
+                ));
+            };
+            let signature = signature_text(params, results)?;
+            let _ = writeln!(self.out, "{INDENT}(type (;{index};) (func{signature}))");
+        }
+        Ok(())
+    }
+
+    fn print_imports(&mut self) -> anyhow::Result<()> {
+        let (mut funcs, mut tables, mut memories, mut globals) = (0, 0, 0, 0);
+        for import in &self.data.imports {
+            let desc = match import.ty {
+                TypeRef::Func(type_index) => {
+                    let id = self.func_id_prefix(funcs);
+                    let signature = self.type_use(type_index)?;
+                    let desc = format!("(func {id}(;{funcs};) (type {type_index}){signature})");
+                    funcs += 1;
+                    desc
+                }
+                TypeRef::Table(table_type) => {
+                    let desc = format!("(table (;{tables};) {})", table_type_text(&table_type)?);
+                    tables += 1;
+                    desc
+                }
+                TypeRef::Memory(memory_type) => {
+                    let desc = format!("(memory (;{memories};) {})", limits_text(&memory_type));
+                    memories += 1;
+                    desc
+                }
+                TypeRef::Global(global_type) => {
+                    let ty = global_type_text(global_type.content_type, global_type.mutable)?;
+                    let desc = format!("(global (;{globals};) {ty})");
+                    globals += 1;
+                    desc
+                }
+                TypeRef::Tag(_) => {
+                    return Err(anyhow::anyhow!("Tag imports are not supported"));
+                }
+            };
+            let _ = writeln!(
+                self.out,
+                "{INDENT}(import {} {} {desc})",
+                string_literal(import.module.as_bytes()),
+                string_literal(import.name.as_bytes())
+            );
+        }
+        Ok(())
+    }
+
+    fn print_functions(&mut self) -> anyhow::Result<()> {
+        for (offset, body) in self.data.function_bodies.iter().enumerate() {
+            let index = self.imported_funcs + u32::try_from(offset)?;
+            let type_index = *self
+                .data
+                .function_type_indexes
+                .get(offset)
+                .ok_or_else(|| anyhow::anyhow!("Function {index} has no type"))?;
+            let id = self.func_id_prefix(index);
+            let signature = self.type_use(type_index)?;
+            let _ = write!(
+                self.out,
+                "{INDENT}(func {id}(;{index};) (type {type_index}){signature}"
+            );
+
+            let mut locals = Vec::new();
+            for local in body.get_locals_reader()? {
+                let (count, ty) = local?;
+                let ty = val_type_text(&ty)?;
+                for _ in 0..count {
+                    locals.push(ty);
+                }
+            }
+            if !locals.is_empty() {
+                let _ = write!(self.out, " (local {})", locals.join(" "));
+            }
+            self.out.push('\n');
+
+            let mut depth = 0usize;
+            for op in body.get_operators_reader()? {
+                let op = op?;
+                match op {
+                    Operator::End if depth == 0 => break,
+                    Operator::End => depth -= 1,
+                    Operator::Else => depth -= 1,
+                    _ => {}
+                }
+                let text = self.instruction(&op)?;
+                let _ = writeln!(self.out, "{}{text}", INDENT.repeat(depth + 2));
+                if opens_block(&op) {
+                    depth += 1;
+                }
+            }
+            let _ = writeln!(self.out, "{INDENT})");
+        }
+        Ok(())
+    }
+
+    fn print_tables(&mut self) -> anyhow::Result<()> {
+        for (offset, table) in self.data.tables.iter().enumerate() {
+            let index = self.imported_tables + u32::try_from(offset)?;
+            let ty = table_type_text(&table.ty)?;
+            let _ = writeln!(self.out, "{INDENT}(table (;{index};) {ty})");
+        }
+        Ok(())
+    }
+
+    fn print_memories(&mut self) {
+        for (offset, memory) in self.data.memory_types.iter().enumerate() {
+            let index = self.imported_memories as usize + offset;
+            let limits = limits_text(memory);
+            let _ = writeln!(self.out, "{INDENT}(memory (;{index};) {limits})");
+        }
+    }
+
+    fn print_globals(&mut self) -> anyhow::Result<()> {
+        for (offset, global) in self.data.globals.iter().enumerate() {
+            let index = self.imported_globals as usize + offset;
+            let ty = global_type_text(global.ty.content_type, global.ty.mutable)?;
+            let init = self.const_expr(&global.init_expr)?;
+            let _ = writeln!(self.out, "{INDENT}(global (;{index};) {ty} {init})");
+        }
+        Ok(())
+    }
+
+    fn print_exports(&mut self) -> anyhow::Result<()> {
+        for export in &self.data.exports {
+            let desc = match export.kind {
+                ExternalKind::Func => format!("(func {})", self.func_ref(export.index)),
+                ExternalKind::Table => format!("(table {})", export.index),
+                ExternalKind::Memory => format!("(memory {})", export.index),
+                ExternalKind::Global => format!("(global {})", export.index),
+                ExternalKind::Tag => return Err(anyhow::anyhow!("Tag exports are not supported")),
+            };
+            let name = string_literal(export.name.as_bytes());
+            let _ = writeln!(self.out, "{INDENT}(export {name} {desc})");
+        }
+        Ok(())
+    }
+
+    fn print_elements(&mut self) -> anyhow::Result<()> {
+        for (index, element) in self.data.elements.iter().enumerate() {
+            let mode = match &element.kind {
+                ElementKind::Active {
+                    table_index,
+                    offset_expr,
+                } => {
+                    let table = table_index
+                        .map(|t| format!("(table {t}) "))
+                        .unwrap_or_default();
+                    format!(" {table}{}", self.const_expr_list("offset", offset_expr)?)
+                }
+                ElementKind::Passive => String::new(),
+                ElementKind::Declared => String::from(" declare"),
+            };
+            let items = match &element.items {
+                ElementItems::Functions(functions) => {
+                    let mut items = String::from("func");
+                    for function in functions.clone() {
+                        let _ = write!(items, " {}", self.func_ref(function?));
+                    }
+                    items
+                }
+                ElementItems::Expressions(ref_type, exprs) => {
+                    let mut items = vec![ref_type_text(ref_type)?.to_string()];
+                    for expr in exprs.clone() {
+                        items.push(self.const_expr_list("item", &expr?)?);
+                    }
+                    items.join(" ")
+                }
+            };
+            let _ = writeln!(self.out, "{INDENT}(elem (;{index};){mode} {items})");
+        }
+        Ok(())
+    }
+
+    fn print_data(&mut self) -> anyhow::Result<()> {
+        for (index, data) in self.data.data.iter().enumerate() {
+            let mode = match &data.kind {
+                DataKind::Active {
+                    memory_index,
+                    offset_expr,
+                } => {
+                    let memory = if *memory_index == 0 {
+                        String::new()
+                    } else {
+                        format!("(memory {memory_index}) ")
+                    };
+                    format!(" {memory}{}", self.const_expr_list("offset", offset_expr)?)
+                }
+                DataKind::Passive => String::new(),
+            };
+            let bytes = string_literal(data.data);
+            let _ = writeln!(self.out, "{INDENT}(data (;{index};){mode} {bytes})");
+        }
+        Ok(())
+    }
+
+    /// Renders a constant expression as folded instructions, e.g.
+    /// `(i32.const 1024)`.
+    fn const_expr(&self, expr: &ConstExpr) -> anyhow::Result<String> {
+        let parts = self.const_expr_parts(expr)?;
+        Ok(parts
+            .iter()
+            .map(|part| format!("({part})"))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    /// Renders a segment offset or element item: a single folded instruction
+    /// when possible, otherwise an explicit `(<keyword> ...)` list.
+    fn const_expr_list(&self, keyword: &str, expr: &ConstExpr) -> anyhow::Result<String> {
+        let parts = self.const_expr_parts(expr)?;
+        match parts.as_slice() {
+            [single] => Ok(format!("({single})")),
+            _ => Ok(format!("({keyword} {})", parts.join(" "))),
+        }
+    }
+
+    /// Renders the instructions of a constant expression, without the
+    /// terminating `end`.
+    fn const_expr_parts(&self, expr: &ConstExpr) -> anyhow::Result<Vec<String>> {
+        let mut parts = Vec::new();
+        for op in expr.get_operators_reader() {
+            let op = op?;
+            if matches!(op, Operator::End) {
+                break;
+            }
+            parts.push(self.instruction(&op)?);
+        }
+        Ok(parts)
+    }
+
+    /// Renders the ` (param ...) (result ...)` suffix of a type use.
+    fn type_use(&self, type_index: u32) -> anyhow::Result<String> {
+        match self.func_types.get(type_index as usize) {
+            Some(Some((params, results))) => signature_text(params, results),
+            _ => Err(anyhow::anyhow!(
+                "Type index {type_index} does not refer to a function type"
+            )),
+        }
+    }
+
+    /// Returns `$name ` for a named function, or an empty string.
+    fn func_id_prefix(&self, index: u32) -> String {
+        self.func_ids
+            .get(&index)
+            .map(|id| format!("${id} "))
+            .unwrap_or_default()
+    }
+
+    /// Returns `$name` for a named function, or its index.
+    fn func_ref(&self, index: u32) -> String {
+        self.func_ids
+            .get(&index)
+            .map_or_else(|| index.to_string(), |id| format!("${id}"))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn instruction(&self, op: &Operator) -> anyhow::Result<String> {
+        let text = match op {
+            Operator::Unreachable => String::from("unreachable"),
+            Operator::Nop => String::from("nop"),
+            Operator::Block { blockty } => format!("block{}", block_type_text(blockty)?),
+            Operator::Loop { blockty } => format!("loop{}", block_type_text(blockty)?),
+            Operator::If { blockty } => format!("if{}", block_type_text(blockty)?),
+            Operator::Forall { blockty } => format!("forall{}", block_type_text(blockty)?),
+            Operator::Exists { blockty } => format!("exists{}", block_type_text(blockty)?),
+            Operator::Assume { blockty } => format!("assume{}", block_type_text(blockty)?),
+            Operator::Unique { blockty } => format!("unique{}", block_type_text(blockty)?),
+            Operator::Else => String::from("else"),
+            Operator::End => String::from("end"),
+            Operator::Br { relative_depth } => format!("br {relative_depth}"),
+            Operator::BrIf { relative_depth } => format!("br_if {relative_depth}"),
+            Operator::BrTable { targets } => {
+                let mut text = String::from("br_table");
+                for target in targets.targets() {
+                    let _ = write!(text, " {}", target?);
+                }
+                let _ = write!(text, " {}", targets.default());
+                text
+            }
+            Operator::Return => String::from("return"),
+            Operator::Call { function_index } => format!("call {}", self.func_ref(*function_index)),
+            Operator::CallIndirect {
+                type_index,
+                table_index,
+            } => {
+                if *table_index == 0 {
+                    format!("call_indirect (type {type_index})")
+                } else {
+                    format!("call_indirect {table_index} (type {type_index})")
+                }
+            }
+            Operator::Drop => String::from("drop"),
+            Operator::Select => String::from("select"),
+            Operator::LocalGet { local_index } => format!("local.get {local_index}"),
+            Operator::LocalSet { local_index } => format!("local.set {local_index}"),
+            Operator::LocalTee { local_index } => format!("local.tee {local_index}"),
+            Operator::GlobalGet { global_index } => format!("global.get {global_index}"),
+            Operator::GlobalSet { global_index } => format!("global.set {global_index}"),
+            Operator::I32Load { memarg } => memory_access("i32.load", memarg),
+            Operator::I64Load { memarg } => memory_access("i64.load", memarg),
+            Operator::F32Load { memarg } => memory_access("f32.load", memarg),
+            Operator::F64Load { memarg } => memory_access("f64.load", memarg),
+            Operator::I32Load8S { memarg } => memory_access("i32.load8_s", memarg),
+            Operator::I32Load8U { memarg } => memory_access("i32.load8_u", memarg),
+            Operator::I32Load16S { memarg } => memory_access("i32.load16_s", memarg),
+            Operator::I32Load16U { memarg } => memory_access("i32.load16_u", memarg),
+            Operator::I64Load8S { memarg } => memory_access("i64.load8_s", memarg),
+            Operator::I64Load8U { memarg } => memory_access("i64.load8_u", memarg),
+            Operator::I64Load16S { memarg } => memory_access("i64.load16_s", memarg),
+            Operator::I64Load16U { memarg } => memory_access("i64.load16_u", memarg),
+            Operator::I64Load32S { memarg } => memory_access("i64.load32_s", memarg),
+            Operator::I64Load32U { memarg } => memory_access("i64.load32_u", memarg),
+            Operator::I32Store { memarg } => memory_access("i32.store", memarg),
+            Operator::I64Store { memarg } => memory_access("i64.store", memarg),
+            Operator::F32Store { memarg } => memory_access("f32.store", memarg),
+            Operator::F64Store { memarg } => memory_access("f64.store", memarg),
+            Operator::I32Store8 { memarg } => memory_access("i32.store8", memarg),
+            Operator::I32Store16 { memarg } => memory_access("i32.store16", memarg),
+            Operator::I64Store8 { memarg } => memory_access("i64.store8", memarg),
+            Operator::I64Store16 { memarg } => memory_access("i64.store16", memarg),
+            Operator::I64Store32 { memarg } => memory_access("i64.store32", memarg),
+            Operator::MemorySize { mem } => memory_index("memory.size", *mem),
+            Operator::MemoryGrow { mem } => memory_index("memory.grow", *mem),
+            Operator::I32Uzumaki { .. } => String::from("i32.uzumaki"),
+            Operator::I64Uzumaki { .. } => String::from("i64.uzumaki"),
+            Operator::I32Const { value } => format!("i32.const {value}"),
+            Operator::I64Const { value } => format!("i64.const {value}"),
+            Operator::F32Const { value } => {
+                let bits = value.bits();
+                let nan_payload = u64::from(bits & 0x007f_ffff);
+                let text = float_text(f32::from_bits(bits), nan_payload, 1 << 22);
+                format!("f32.const {text}")
+            }
+            Operator::F64Const { value } => {
+                let bits = value.bits();
+                let nan_payload = bits & 0x000f_ffff_ffff_ffff;
+                let text = float_text(f64::from_bits(bits), nan_payload, 1 << 51);
+                format!("f64.const {text}")
+            }
+            Operator::RefFunc { function_index } => {
+                format!("ref.func {}", self.func_ref(*function_index))
+            }
+            other => return numeric_mnemonic(other),
+        };
+        Ok(text)
+    }
+}
+
+/// Chooses WAT identifiers for named functions.
+///
+/// A name is used only if it is a valid identifier and no other function
+/// shares it; otherwise the function is referenced by index.
+fn func_identifiers(names: Option<&HashMap<u32, String>>) -> HashMap<u32, String> {
+    let Some(names) = names else {
+        return HashMap::new();
+    };
+    let mut seen = HashSet::new();
+    let mut duplicates = HashSet::new();
+    for name in names.values() {
+        if !seen.insert(name.as_str()) {
+            duplicates.insert(name.as_str());
+        }
+    }
+    names
+        .iter()
+        .filter(|(_, name)| is_identifier(name) && !duplicates.contains(name.as_str()))
+        .map(|(index, name)| (*index, name.clone()))
+        .collect()
+}
+
+/// Returns `true` if `name` only contains characters allowed in a WAT `$id`.
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-./:<=>?@\\^_`|~".contains(c))
+}
+
+fn opens_block(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Block { .. }
+            | Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::Else
+            | Operator::Forall { .. }
+            | Operator::Exists { .. }
+            | Operator::Assume { .. }
+            | Operator::Unique { .. }
+    )
+}
+
+fn signature_text(params: &[ValType], results: &[ValType]) -> anyhow::Result<String> {
+    let mut text = String::new();
+    for (keyword, types) in [("param", params), ("result", results)] {
+        if types.is_empty() {
+            continue;
+        }
+        let types = types
+            .iter()
+            .map(val_type_text)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let _ = write!(text, " ({keyword} {})", types.join(" "));
+    }
+    Ok(text)
+}
+
+fn val_type_text(val_type: &ValType) -> anyhow::Result<&'static str> {
+    match val_type {
+        ValType::I32 => Ok("i32"),
+        ValType::I64 => Ok("i64"),
+        ValType::F32 => Ok("f32"),
+        ValType::F64 => Ok("f64"),
+        ValType::V128 => Ok("v128"),
+        ValType::Ref(ref_type) => ref_type_text(ref_type),
+    }
+}
+
+fn ref_type_text(ref_type: &RefType) -> anyhow::Result<&'static str> {
+    if *ref_type == RefType::FUNCREF {
+        Ok("funcref")
+    } else if *ref_type == RefType::EXTERNREF {
+        Ok("externref")
+    } else {
+        Err(anyhow::anyhow!("Unsupported reference type {ref_type:?}"))
+    }
+}
+
+fn global_type_text(content_type: ValType, mutable: bool) -> anyhow::Result<String> {
+    let ty = val_type_text(&content_type)?;
+    if mutable {
+        Ok(format!("(mut {ty})"))
+    } else {
+        Ok(ty.to_string())
+    }
+}
+
+fn block_type_text(block_type: &BlockType) -> anyhow::Result<String> {
+    match block_type {
+        BlockType::Empty => Ok(String::new()),
+        BlockType::Type(val_type) => Ok(format!(" (result {})", val_type_text(val_type)?)),
+        BlockType::FuncType(index) => Ok(format!(" (type {index})")),
+    }
+}
+
+fn limits_text(memory_type: &MemoryType) -> String {
+    match memory_type.maximum {
+        Some(maximum) => format!("{} {maximum}", memory_type.initial),
+        None => memory_type.initial.to_string(),
+    }
+}
+
+fn table_type_text(table_type: &TableType) -> anyhow::Result<String> {
+    let element = ref_type_text(&table_type.element_type)?;
+    Ok(match table_type.maximum {
+        Some(maximum) => format!("{} {maximum} {element}", table_type.initial),
+        None => format!("{} {element}", table_type.initial),
+    })
+}
+
+fn memory_access(mnemonic: &str, memarg: &MemArg) -> String {
+    let mut text = String::from(mnemonic);
+    if memarg.memory != 0 {
+        let _ = write!(text, " {}", memarg.memory);
+    }
+    if memarg.offset != 0 {
+        let _ = write!(text, " offset={}", memarg.offset);
+    }
+    if memarg.align != memarg.max_align {
+        let _ = write!(text, " align={}", 1u64 << memarg.align);
+    }
+    text
+}
+
+fn memory_index(mnemonic: &str, mem: u32) -> String {
+    if mem == 0 {
+        mnemonic.to_string()
+    } else {
+        format!("{mnemonic} {mem}")
+    }
+}
+
+/// Formats a float constant so that it parses back to the same bits.
+///
+/// `nan_payload` is the mantissa of the value and `canonical_payload` the
+/// mantissa of the canonical NaN for the float width.
+fn float_text<F>(value: F, nan_payload: u64, canonical_payload: u64) -> String
+where
+    F: Into<f64> + std::fmt::Debug + Copy,
+{
+    let wide: f64 = value.into();
+    let sign = if wide.is_sign_negative() { "-" } else { "" };
+    if wide.is_nan() {
+        if nan_payload == canonical_payload {
+            format!("{sign}nan")
+        } else {
+            format!("{sign}nan:0x{nan_payload:x}")
+        }
+    } else if wide.is_infinite() {
+        format!("{sign}inf")
+    } else {
+        format!("{value:?}")
+    }
+}
+
+/// Renders bytes as a WAT string literal, escaping everything but printable
+/// ASCII.
+fn string_literal(bytes: &[u8]) -> String {
+    let mut text = String::from("\"");
+    for &byte in bytes {
+        if (0x20..0x7f).contains(&byte) && byte != b'"' && byte != b'\\' {
+            text.push(char::from(byte));
+        } else {
+            let _ = write!(text, "\\{byte:02x}");
+        }
+    }
+    text.push('"');
+    text
+}
+
+/// Returns the mnemonic of an operand-less numeric instruction.
+#[allow(clippy::too_many_lines)]
+fn numeric_mnemonic(op: &Operator) -> anyhow::Result<String> {
+    let mnemonic = match op {
+        Operator::I32Eqz => "i32.eqz",
+        Operator::I32Eq => "i32.eq",
+        Operator::I32Ne => "i32.ne",
+        Operator::I32LtS => "i32.lt_s",
+        Operator::I32LtU => "i32.lt_u",
+        Operator::I32GtS => "i32.gt_s",
+        Operator::I32GtU => "i32.gt_u",
+        Operator::I32LeS => "i32.le_s",
+        Operator::I32LeU => "i32.le_u",
+        Operator::I32GeS => "i32.ge_s",
+        Operator::I32GeU => "i32.ge_u",
+        Operator::I64Eqz => "i64.eqz",
+        Operator::I64Eq => "i64.eq",
+        Operator::I64Ne => "i64.ne",
+        Operator::I64LtS => "i64.lt_s",
+        Operator::I64LtU => "i64.lt_u",
+        Operator::I64GtS => "i64.gt_s",
+        Operator::I64GtU => "i64.gt_u",
+        Operator::I64LeS => "i64.le_s",
+        Operator::I64LeU => "i64.le_u",
+        Operator::I64GeS => "i64.ge_s",
+        Operator::I64GeU => "i64.ge_u",
+        Operator::F32Eq => "f32.eq",
+        Operator::F32Ne => "f32.ne",
+        Operator::F32Lt => "f32.lt",
+        Operator::F32Gt => "f32.gt",
+        Operator::F32Le => "f32.le",
+        Operator::F32Ge => "f32.ge",
+        Operator::F64Eq => "f64.eq",
+        Operator::F64Ne => "f64.ne",
+        Operator::F64Lt => "f64.lt",
+        Operator::F64Gt => "f64.gt",
+        Operator::F64Le => "f64.le",
+        Operator::F64Ge => "f64.ge",
+        Operator::I32Clz => "i32.clz",
+        Operator::I32Ctz => "i32.ctz",
+        Operator::I32Popcnt => "i32.popcnt",
+        Operator::I32Add => "i32.add",
+        Operator::I32Sub => "i32.sub",
+        Operator::I32Mul => "i32.mul",
+        Operator::I32DivS => "i32.div_s",
+        Operator::I32DivU => "i32.div_u",
+        Operator::I32RemS => "i32.rem_s",
+        Operator::I32RemU => "i32.rem_u",
+        Operator::I32And => "i32.and",
+        Operator::I32Or => "i32.or",
+        Operator::I32Xor => "i32.xor",
+        Operator::I32Shl => "i32.shl",
+        Operator::I32ShrS => "i32.shr_s",
+        Operator::I32ShrU => "i32.shr_u",
+        Operator::I32Rotl => "i32.rotl",
+        Operator::I32Rotr => "i32.rotr",
+        Operator::I64Clz => "i64.clz",
+        Operator::I64Ctz => "i64.ctz",
+        Operator::I64Popcnt => "i64.popcnt",
+        Operator::I64Add => "i64.add",
+        Operator::I64Sub => "i64.sub",
+        Operator::I64Mul => "i64.mul",
+        Operator::I64DivS => "i64.div_s",
+        Operator::I64DivU => "i64.div_u",
+        Operator::I64RemS => "i64.rem_s",
+        Operator::I64RemU => "i64.rem_u",
+        Operator::I64And => "i64.and",
+        Operator::I64Or => "i64.or",
+        Operator::I64Xor => "i64.xor",
+        Operator::I64Shl => "i64.shl",
+        Operator::I64ShrS => "i64.shr_s",
+        Operator::I64ShrU => "i64.shr_u",
+        Operator::I64Rotl => "i64.rotl",
+        Operator::I64Rotr => "i64.rotr",
+        Operator::F32Abs => "f32.abs",
+        Operator::F32Neg => "f32.neg",
+        Operator::F32Ceil => "f32.ceil",
+        Operator::F32Floor => "f32.floor",
+        Operator::F32Trunc => "f32.trunc",
+        Operator::F32Nearest => "f32.nearest",
+        Operator::F32Sqrt => "f32.sqrt",
+        Operator::F32Add => "f32.add",
+        Operator::F32Sub => "f32.sub",
+        Operator::F32Mul => "f32.mul",
+        Operator::F32Div => "f32.div",
+        Operator::F32Min => "f32.min",
+        Operator::F32Max => "f32.max",
+        Operator::F32Copysign => "f32.copysign",
+        Operator::F64Abs => "f64.abs",
+        Operator::F64Neg => "f64.neg",
+        Operator::F64Ceil => "f64.ceil",
+        Operator::F64Floor => "f64.floor",
+        Operator::F64Trunc => "f64.trunc",
+        Operator::F64Nearest => "f64.nearest",
+        Operator::F64Sqrt => "f64.sqrt",
+        Operator::F64Add => "f64.add",
+        Operator::F64Sub => "f64.sub",
+        Operator::F64Mul => "f64.mul",
+        Operator::F64Div => "f64.div",
+        Operator::F64Min => "f64.min",
+        Operator::F64Max => "f64.max",
+        Operator::F64Copysign => "f64.copysign",
+        Operator::I32WrapI64 => "i32.wrap_i64",
+        Operator::I32TruncF32S => "i32.trunc_f32_s",
+        Operator::I32TruncF32U => "i32.trunc_f32_u",
+        Operator::I32TruncF64S => "i32.trunc_f64_s",
+        Operator::I32TruncF64U => "i32.trunc_f64_u",
+        Operator::I64ExtendI32S => "i64.extend_i32_s",
+        Operator::I64ExtendI32U => "i64.extend_i32_u",
+        Operator::I64TruncF32S => "i64.trunc_f32_s",
+        Operator::I64TruncF32U => "i64.trunc_f32_u",
+        Operator::I64TruncF64S => "i64.trunc_f64_s",
+        Operator::I64TruncF64U => "i64.trunc_f64_u",
+        Operator::F32ConvertI32S => "f32.convert_i32_s",
+        Operator::F32ConvertI32U => "f32.convert_i32_u",
+        Operator::F32ConvertI64S => "f32.convert_i64_s",
+        Operator::F32ConvertI64U => "f32.convert_i64_u",
+        Operator::F32DemoteF64 => "f32.demote_f64",
+        Operator::F64ConvertI32S => "f64.convert_i32_s",
+        Operator::F64ConvertI32U => "f64.convert_i32_u",
+        Operator::F64ConvertI64S => "f64.convert_i64_s",
+        Operator::F64ConvertI64U => "f64.convert_i64_u",
+        Operator::F64PromoteF32 => "f64.promote_f32",
+        Operator::I32ReinterpretF32 => "i32.reinterpret_f32",
+        Operator::I64ReinterpretF64 => "i64.reinterpret_f64",
+        Operator::F32ReinterpretI32 => "f32.reinterpret_i32",
+        Operator::F64ReinterpretI64 => "f64.reinterpret_i64",
+        Operator::I32Extend8S => "i32.extend8_s",
+        Operator::I32Extend16S => "i32.extend16_s",
+        Operator::I64Extend8S => "i64.extend8_s",
+        Operator::I64Extend16S => "i64.extend16_s",
+        Operator::I64Extend32S => "i64.extend32_s",
+        Operator::I32TruncSatF32S => "i32.trunc_sat_f32_s",
+        Operator::I32TruncSatF32U => "i32.trunc_sat_f32_u",
+        Operator::I32TruncSatF64S => "i32.trunc_sat_f64_s",
+        Operator::I32TruncSatF64U => "i32.trunc_sat_f64_u",
+        Operator::I64TruncSatF32S => "i64.trunc_sat_f32_s",
+        Operator::I64TruncSatF32U => "i64.trunc_sat_f32_u",
+        Operator::I64TruncSatF64S => "i64.trunc_sat_f64_s",
+        Operator::I64TruncSatF64U => "i64.trunc_sat_f64_u",
+        other => {
+            return Err(anyhow::anyhow!(
+                "Unsupported instruction in WAT output: {other:?}"
+            ));
+        }
+    };
+    Ok(String::from(mnemonic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::translate_to_wat;
+    use std::path::PathBuf;
+
+    /// Assembles WAT text with the Inference fork of `wast`.
+    fn assemble(wat: &str) -> Vec<u8> {
+        let buffer = inf_wast::parser::ParseBuffer::new(wat).expect("Should lex WAT");
+        let mut module =
+            inf_wast::parser::parse::<inf_wast::Wat>(&buffer).expect("Should parse WAT");
+        module.encode().expect("Should encode WAT")
+    }
+
+    #[test]
+    fn prints_nondeterministic_instructions() {
+        let wasm = assemble(
+            r#"(module
+                (func $pick (result i32) i32.uzumaki)
+                (func $check (param i64) (result i64)
+                    forall
+                        assume
+                            local.get 0
+                            drop
+                        end
+                    end
+                    i64.uzumaki)
+                (export "pick" (func $pick)))"#,
+        );
+
+        let wat = translate_to_wat(&wasm).expect("Should print WAT");
+
+        assert!(wat.contains("(func $pick (;0;) (type 0) (result i32)\n    i32.uzumaki\n"));
+        assert!(wat.contains("    forall\n      assume\n        local.get 0\n"));
+        assert!(wat.contains("    i64.uzumaki\n"));
+        assert!(wat.contains("(export \"pick\" (func $pick))"));
+    }
+
+    #[test]
+    fn prints_numeric_and_memory_instructions() {
+        let wasm = assemble(
+            r#"(module
+                (memory 1 2)
+                (global (mut i32) (i32.const 1024))
+                (func (param f32) (result i32)
+                    local.get 0
+                    i32.trunc_sat_f32_s
+                    i32.const 8
+                    i32.load8_u offset=4 align=1
+                    i32.add
+                    f64.const -0x1.8p+1
+                    i64.trunc_f64_u
+                    i32.wrap_i64
+                    i32.extend8_s
+                    i32.rem_u)
+                (data (i32.const 16) "hi\00\"\\"))"#,
+        );
+
+        let wat = translate_to_wat(&wasm).expect("Should print WAT");
+
+        for expected in [
+            "i32.trunc_sat_f32_s",
+            "i32.load8_u offset=4\n",
+            "f64.const -3.0",
+            "i64.trunc_f64_u",
+            "i32.wrap_i64",
+            "i32.extend8_s",
+            "i32.rem_u",
+            "(memory (;0;) 1 2)",
+            "(global (;0;) (mut i32) (i32.const 1024))",
+            r#"(data (;0;) (i32.const 16) "hi\00\22\5c")"#,
+        ] {
+            assert!(wat.contains(expected), "missing {expected:?} in:\n{wat}");
+        }
+    }
+
+    /// Printing is deterministic, and re-assembling the printed text yields a
+    /// module that prints identically.
+    #[test]
+    fn printed_test_data_round_trips() {
+        let test_data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data");
+        let mut wasm_files: Vec<PathBuf> = std::fs::read_dir(&test_data_dir)
+            .expect("Failed to read test_data directory")
+            .map(|entry| entry.expect("Failed to read directory entry").path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("wasm"))
+            .collect();
+        wasm_files.sort();
+
+        let mut printed = 0;
+        for wasm_path in &wasm_files {
+            let bytes = std::fs::read(wasm_path).expect("Should read test data");
+            // Modules using instructions outside the supported set are skipped.
+            let Ok(wat) = translate_to_wat(&bytes) else {
+                continue;
+            };
+            assert_eq!(
+                translate_to_wat(&bytes).expect("Should print again"),
+                wat,
+                "{}",
+                wasm_path.display()
+            );
+            let reprinted = translate_to_wat(&assemble(&wat)).expect("Should print reassembled");
+            assert_eq!(reprinted, wat, "{}", wasm_path.display());
+            printed += 1;
+        }
+
+        assert!(printed > 0, "No test data module could be printed");
+    }
+
+    /// Decodes the instructions of every function body in `wasm`.
+    fn function_operators(wasm: &[u8]) -> Vec<Vec<String>> {
+        inf_wasmparser::Parser::new(0)
+            .parse_all(wasm)
+            .filter_map(|payload| match payload.expect("Should parse module") {
+                inf_wasmparser::Payload::CodeSectionEntry(body) => Some(
+                    body.get_operators_reader()
+                        .expect("Should read function body")
+                        .into_iter()
+                        .map(|op| format!("{:?}", op.expect("Should decode instruction")))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Every module the codegen tests expect prints, and re-assembling the
+    /// printed text yields the same instructions. The binaries are not
+    /// compared byte for byte: LLVM pads relocatable indices to five bytes.
+    #[test]
+    fn codegen_output_round_trips() {
+        let codegen_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .and_then(|core| core.parent())
+            .expect("Should find workspace root")
+            .join("tests/test_data/codegen/wasm/base");
+        let mut wasm_files: Vec<PathBuf> = std::fs::read_dir(&codegen_dir)
+            .expect("Failed to read codegen test data directory")
+            .map(|entry| entry.expect("Failed to read directory entry").path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("wasm"))
+            .collect();
+        wasm_files.sort();
+        assert!(!wasm_files.is_empty(), "No codegen test data found");
+
+        for wasm_path in &wasm_files {
+            let bytes = std::fs::read(wasm_path).expect("Should read codegen output");
+            let wat =
+                translate_to_wat(&bytes).unwrap_or_else(|e| panic!("{}: {e}", wasm_path.display()));
+            let reassembled = assemble(&wat);
+            assert_eq!(
+                function_operators(&reassembled),
+                function_operators(&bytes),
+                "{}:\n{wat}",
+                wasm_path.display()
+            );
+            let reprinted = translate_to_wat(&reassembled).expect("Should print reassembled");
+            assert_eq!(reprinted, wat, "{}", wasm_path.display());
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_instructions() {
+        let wasm = assemble("(module (func (param v128) local.get 0 i8x16.popcnt drop))");
+
+        let err = translate_to_wat(&wasm).expect_err("SIMD is not supported");
+
+        assert!(err.to_string().contains("I8x16Popcnt"), "{err}");
+    }
+}
@@ -3,7 +3,7 @@ use crate::utils::{
 };
 use inference_ast::builder::Builder;
 use inference_ast::nodes::{
-    AstNode, Definition, Expression, Literal, OperatorKind, Statement, Visibility,
+    ArgumentType, AstNode, Definition, Expression, Literal, OperatorKind, Statement, Visibility,
 };
 
 // --- Parse Error Detection Tests ---
@@ -737,6 +737,28 @@ fn test_parse_external_function_basic() {
     }
 }
 
+#[test]
+fn test_parse_external_function_typed_arguments() {
+    let source = r#"external fn host_log(level: i32, flag: bool);"#;
+    let arena = build_ast(source.to_string());
+
+    let ext_funcs = arena
+        .filter_nodes(|node| matches!(node, AstNode::Definition(Definition::ExternalFunction(_))));
+    assert_eq!(ext_funcs.len(), 1);
+
+    if let AstNode::Definition(Definition::ExternalFunction(ext_func)) = &ext_funcs[0] {
+        let args = ext_func.arguments.as_ref().expect("Should have arguments");
+        let names: Vec<_> = args
+            .iter()
+            .map(|arg| match arg {
+                ArgumentType::Argument(argument) => argument.name.name.as_str(),
+                other => panic!("Expected a named argument, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(names, ["level", "flag"]);
+    }
+}
+
 // --- Visibility Tests ---
 
 #[test]
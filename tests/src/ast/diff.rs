@@ -0,0 +1,101 @@
+use crate::utils::build_ast;
+use inference_ast::diff::{AstDiff, diff};
+
+/// Tests for structural diffs, which ignore node IDs, locations and formatting.
+
+fn diff_sources(a: &str, b: &str) -> Vec<AstDiff> {
+    let left = build_ast(a.to_string());
+    let right = build_ast(b.to_string());
+    diff(&left.source_files()[0], &right.source_files()[0])
+}
+
+#[test]
+fn test_diff_of_identical_files_is_empty() {
+    let source = r#"fn add(a: i32, b: i32) -> i32 { let c: i32 = a + b; return c; }"#;
+    assert!(diff_sources(source, source).is_empty());
+}
+
+#[test]
+fn test_diff_ignores_whitespace_and_comments() {
+    let compact = r#"fn add(a: i32, b: i32) -> i32 { let c: i32 = a + b; return c; }"#;
+    let formatted = r#"
+// Adds two numbers.
+fn add(a: i32,   b: i32) -> i32 {
+    let c: i32 = a + b;
+
+    return c;
+}
+"#;
+    assert_eq!(diff_sources(compact, formatted), vec![]);
+}
+
+#[test]
+fn test_diff_reports_changed_literal() {
+    let original = r#"fn answer() -> i32 { return 42; }"#;
+    let changed = r#"fn answer() -> i32 { return 43; }"#;
+    let diffs = diff_sources(original, changed);
+    assert_eq!(
+        diffs,
+        vec![AstDiff {
+            path: "definitions[0].body.statements[0].expression".to_string(),
+            left: "NumberLiteral(42)".to_string(),
+            right: "NumberLiteral(43)".to_string(),
+        }]
+    );
+    assert_eq!(
+        diffs[0].to_string(),
+        "definitions[0].body.statements[0].expression: NumberLiteral(42) != NumberLiteral(43)"
+    );
+}
+
+#[test]
+fn test_diff_reports_changed_operator_and_kind() {
+    let original = r#"fn f(a: i32) -> i32 { return a + 1; }"#;
+    let changed = r#"fn f(a: i32) -> i32 { return a * a; }"#;
+    assert_eq!(
+        diff_sources(original, changed),
+        vec![
+            AstDiff {
+                path: "definitions[0].body.statements[0].expression.operator".to_string(),
+                left: "+".to_string(),
+                right: "*".to_string(),
+            },
+            AstDiff {
+                path: "definitions[0].body.statements[0].expression.right".to_string(),
+                left: "NumberLiteral(1)".to_string(),
+                right: "Identifier(a)".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_reports_added_and_removed_definitions() {
+    let one = r#"const X: i32 = 1;"#;
+    let two = r#"const X: i32 = 1; fn f() {}"#;
+    let added = diff_sources(one, two);
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].path, "definitions[1]");
+    assert_eq!(
+        (added[0].left.as_str(), added[0].right.as_str()),
+        ("none", "Function")
+    );
+
+    let removed = diff_sources(two, one);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(
+        (removed[0].left.as_str(), removed[0].right.as_str()),
+        ("Function", "none")
+    );
+}
+
+#[test]
+fn test_diff_reports_renamed_function_and_visibility() {
+    let original = r#"fn first() {}"#;
+    let changed = r#"pub fn second() {}"#;
+    let paths: Vec<String> = diff_sources(original, changed)
+        .into_iter()
+        .map(|d| d.path)
+        .collect();
+    assert_eq!(paths, ["definitions[0].visibility", "definitions[0].name"]);
+}
@@ -0,0 +1,70 @@
+use crate::utils::build_ast;
+use inference_ast::nodes::{Ast, AstNode};
+
+/// Tests for structural hashing, which ignores node IDs, locations and formatting.
+
+fn file_hash(source: &str) -> u64 {
+    let arena = build_ast(source.to_string());
+    let source_file = arena.source_files()[0].clone();
+    AstNode::Ast(Ast::SourceFile(source_file)).structural_hash()
+}
+
+fn definition_hashes(source: &str) -> Vec<u64> {
+    let arena = build_ast(source.to_string());
+    arena.source_files()[0]
+        .definitions
+        .iter()
+        .map(|definition| AstNode::Definition(definition.clone()).structural_hash())
+        .collect()
+}
+
+#[test]
+fn test_structural_hash_ignores_whitespace_and_comments() {
+    let compact = r#"fn add(a: i32, b: i32) -> i32 { let c: i32 = a + b; return c; }"#;
+    let formatted = r#"
+// Adds two numbers.
+fn add(a: i32,   b: i32) -> i32 {
+    let c: i32 = a + b;
+
+    return c;
+}
+"#;
+    assert_eq!(file_hash(compact), file_hash(formatted));
+    assert_eq!(definition_hashes(compact), definition_hashes(formatted));
+}
+
+#[test]
+fn test_structural_hash_changes_when_variable_is_renamed() {
+    let original = r#"fn test() -> i32 { let value: i32 = 42; return value; }"#;
+    let renamed = r#"fn test() -> i32 { let other: i32 = 42; return other; }"#;
+    assert_ne!(file_hash(original), file_hash(renamed));
+    assert_ne!(definition_hashes(original), definition_hashes(renamed));
+}
+
+#[test]
+fn test_structural_hash_distinguishes_operators_and_literals() {
+    let add = r#"fn f() -> i32 { return 1 + 2; }"#;
+    let sub = r#"fn f() -> i32 { return 1 - 2; }"#;
+    let other_literal = r#"fn f() -> i32 { return 1 + 3; }"#;
+    assert_ne!(file_hash(add), file_hash(sub));
+    assert_ne!(file_hash(add), file_hash(other_literal));
+}
+
+#[test]
+fn test_structural_hash_of_swapped_definitions() {
+    let first = r#"fn a() -> i32 { return 1; } fn b() -> i32 { return 2; }"#;
+    let swapped = r#"fn b() -> i32 { return 2; } fn a() -> i32 { return 1; }"#;
+
+    assert_ne!(file_hash(first), file_hash(swapped));
+
+    let first_hashes = definition_hashes(first);
+    let swapped_hashes = definition_hashes(swapped);
+    assert_eq!(first_hashes[0], swapped_hashes[1]);
+    assert_eq!(first_hashes[1], swapped_hashes[0]);
+}
+
+#[test]
+fn test_structural_hash_is_deterministic() {
+    let source = r#"fn test() -> i32 { return 42; }"#;
+    assert_eq!(file_hash(source), file_hash(source));
+}
@@ -1,5 +1,10 @@
 mod arena;
 mod builder;
 mod builder_features;
+mod diff;
+mod hash;
 mod nodes;
+mod obligations;
 mod primitive_type;
+mod source;
+mod stats;
@@ -0,0 +1,155 @@
+use crate::utils::build_ast;
+use inference_ast::obligations::{
+    Obligation, ObligationKind, ObligationWarning, collect, warnings,
+};
+
+/// Tests for enumerating specification obligations and their stable ids.
+
+const FIXTURE: &str = r#"
+fn check(x: i32) {
+    forall {
+        assert x >= 0;
+        exists { assert x > 1; }
+    }
+    assume { assert x != 5; }
+}
+
+spec Bounds {
+    fn unique_max() {
+        unique { assert true; }
+    }
+}
+
+fn plain() -> i32 { return 0; }
+"#;
+
+const REFORMATTED: &str = r#"fn check(x: i32) { forall { assert x >= 0; exists {
+  assert   x > 1;
+} } assume { assert x != 5; } }
+// Comments do not change ids.
+spec Bounds { fn unique_max() { unique { assert true; } } }
+fn plain() -> i32 { return 0; }"#;
+
+fn obligations(source: &str) -> Vec<Obligation> {
+    collect(&build_ast(source.to_string()))
+}
+
+#[test]
+fn test_obligations_are_collected_in_source_order() {
+    let kinds: Vec<(ObligationKind, String)> = obligations(FIXTURE)
+        .into_iter()
+        .map(|obligation| (obligation.kind, obligation.id))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ObligationKind::Forall, "check#0".to_string()),
+            (ObligationKind::Assert, "check#1".to_string()),
+            (ObligationKind::Exists, "check#2".to_string()),
+            (ObligationKind::Assert, "check#3".to_string()),
+            (ObligationKind::Assume, "check#4".to_string()),
+            (ObligationKind::Assert, "check#5".to_string()),
+            (ObligationKind::Unique, "Bounds::unique_max#0".to_string()),
+            (ObligationKind::Assert, "Bounds::unique_max#1".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_obligations_record_function_location_and_summary() {
+    let obligations = obligations(FIXTURE);
+    let assert = &obligations[1];
+    assert_eq!(assert.function, "check");
+    assert_eq!(assert.expression_summary, "x >= 0");
+    assert_eq!(assert.location.start_line, 4);
+
+    let exists = &obligations[2];
+    assert_eq!(exists.expression_summary, "exists { assert x > 1; }");
+    assert_eq!(obligations[6].function, "Bounds::unique_max");
+}
+
+#[test]
+fn test_obligation_ids_survive_reformatting() {
+    let ids = |source: &str| -> Vec<(String, ObligationKind, String)> {
+        obligations(source)
+            .into_iter()
+            .map(|o| (o.id, o.kind, o.expression_summary))
+            .collect()
+    };
+    assert_eq!(ids(FIXTURE), ids(REFORMATTED));
+}
+
+#[test]
+fn test_function_without_specs_has_no_obligations() {
+    assert!(obligations("fn plain() -> i32 { return 0; }").is_empty());
+}
+
+#[test]
+fn test_long_summaries_are_truncated() {
+    let source = r#"fn f() { assert aaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbb + cccccccccccccccccccc + dddddddddd > 0; }"#;
+    let summary = &obligations(source)[0].expression_summary;
+    assert_eq!(
+        summary.chars().count(),
+        inference_ast::obligations::SUMMARY_MAX_CHARS
+    );
+    assert!(summary.ends_with("..."));
+}
+
+fn capture_warnings(source: &str) -> Vec<ObligationWarning> {
+    warnings(&build_ast(source.to_string()))
+}
+
+#[test]
+fn test_mutation_before_quantifier_is_not_reported() {
+    let source = r#"fn f() { let mut y: i32 = 0; y = 1; forall { assert y >= 0; } }"#;
+    assert!(capture_warnings(source).is_empty());
+}
+
+#[test]
+fn test_mutation_after_quantifier_is_reported() {
+    let source = r#"
+fn f(x: i32) {
+    let mut y: i32 = x;
+    forall {
+        let z: i32 = y;
+        assert z >= x;
+    }
+    z = 1;
+    y = 2;
+}
+"#;
+    let warnings = capture_warnings(source);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let ObligationWarning::SpecCaptureMutated {
+        obligation,
+        variable,
+        quantifier,
+        mutation,
+    } = &warnings[0];
+    assert_eq!(obligation, "f#0");
+    assert_eq!(variable, "y");
+    assert_eq!(quantifier.start_line, 4);
+    assert_eq!(mutation.start_line, 9);
+}
+
+#[test]
+fn test_mutation_in_loop_around_quantifier_is_reported() {
+    let source = r#"
+fn f() {
+    let mut y: i32 = 0;
+    loop y < 10 {
+        y = y + 1;
+        exists { assert y > 0; }
+    }
+}
+"#;
+    let warnings = capture_warnings(source);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let ObligationWarning::SpecCaptureMutated {
+        obligation,
+        mutation,
+        ..
+    } = &warnings[0];
+    assert_eq!(obligation, "f#0");
+    assert_eq!(mutation.start_line, 5);
+}
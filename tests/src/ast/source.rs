@@ -0,0 +1,121 @@
+use inference::{AstError, CompileOptions, Compiler};
+use inference_ast::arena::Arena;
+use inference_ast::nodes::{AstNode, Expression, Location};
+
+/// Tests for sources saved with a byte order mark, CRLF line endings, or
+/// above the size limit.
+
+const LF_FIXTURE: &str = "fn add(a: i32, b: i32) -> i32 {
+    let total: i32 = a + b;
+    return total;
+}
+";
+
+fn crlf_fixture() -> String {
+    LF_FIXTURE.replace('\n', "\r\n")
+}
+
+fn parse(source: &str) -> anyhow::Result<Arena> {
+    Compiler::default()
+        .parse(source)
+        .map(|parsed| parsed.into_arena())
+}
+
+fn parse_with_max_size(source: &str, max_size: usize) -> anyhow::Result<Arena> {
+    Compiler::new(CompileOptions::new().with_max_source_size(max_size))
+        .parse(source)
+        .map(|parsed| parsed.into_arena())
+}
+
+/// Returns the name, line, and columns of every identifier, in source order.
+fn identifiers(source: &str) -> Vec<(String, u32, u32, u32)> {
+    let arena = parse(source).unwrap();
+    let mut identifiers: Vec<(String, Location)> = arena
+        .filter_nodes(|node| matches!(node, AstNode::Expression(Expression::Identifier(_))))
+        .into_iter()
+        .filter_map(|node| match node {
+            AstNode::Expression(Expression::Identifier(identifier)) => {
+                Some((identifier.name.clone(), identifier.location))
+            }
+            _ => None,
+        })
+        .collect();
+    identifiers.sort_by_key(|(_, location)| location.offset_start);
+    identifiers
+        .into_iter()
+        .map(|(name, location)| {
+            (
+                name,
+                location.start_line,
+                location.start_column,
+                location.end_column,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_crlf_identifiers_have_no_carriage_return() {
+    let identifiers = identifiers(&crlf_fixture());
+    assert!(!identifiers.is_empty());
+    for (name, ..) in &identifiers {
+        assert!(!name.contains('\r'), "identifier {name:?} contains \\r");
+    }
+    assert!(identifiers.contains(&("total".to_string(), 3, 12, 17)));
+}
+
+#[test]
+fn test_crlf_columns_match_lf() {
+    assert_eq!(identifiers(&crlf_fixture()), identifiers(LF_FIXTURE));
+}
+
+#[test]
+fn test_crlf_spans_exclude_line_endings() {
+    let source = crlf_fixture();
+    let arena = parse(&source).unwrap();
+    let function = &arena.functions()[0];
+    let source_file = &arena.source_files()[0];
+    let location = function.location;
+    let text = &source_file.source[location.offset_start as usize..location.offset_end as usize];
+    assert!(text.starts_with("fn add"));
+    assert!(text.ends_with('}'));
+    assert_eq!(location.end_line, 4);
+    assert_eq!(location.end_column, 2);
+}
+
+#[test]
+fn test_bom_is_stripped_before_parsing() {
+    let source = format!("\u{FEFF}{LF_FIXTURE}");
+    let arena = parse(&source).unwrap();
+    let source_file = &arena.source_files()[0];
+    assert_eq!(source_file.source, LF_FIXTURE);
+
+    let name = &arena.functions()[0].name;
+    assert_eq!(name.name, "add");
+    assert_eq!(name.location.start_line, 1);
+    assert_eq!(name.location.start_column, 4);
+    assert_eq!(
+        &source_file.source[name.location.offset_start as usize..name.location.offset_end as usize],
+        "add"
+    );
+    assert_eq!(identifiers(&source), identifiers(LF_FIXTURE));
+}
+
+#[test]
+fn test_bom_with_crlf_matches_lf() {
+    let source = format!("\u{FEFF}{}", crlf_fixture());
+    assert_eq!(identifiers(&source), identifiers(LF_FIXTURE));
+}
+
+#[test]
+fn test_source_above_max_size_is_rejected() {
+    let err = parse_with_max_size(LF_FIXTURE, 16).unwrap_err();
+    match err.downcast_ref::<AstError>() {
+        Some(AstError::SourceTooLarge { size, max_size }) => {
+            assert_eq!(*size, LF_FIXTURE.len());
+            assert_eq!(*max_size, 16);
+        }
+        other => panic!("expected SourceTooLarge, got {other:?}"),
+    }
+    assert!(parse_with_max_size(LF_FIXTURE, LF_FIXTURE.len()).is_ok());
+}
@@ -0,0 +1,50 @@
+use crate::utils::{build_ast, get_test_data_path};
+use inference_ast::stats::SourceStats;
+
+/// Tests for per-source-file node counts.
+
+const FIXTURE: &str = r#"const LIMIT: i32 = 10;
+fn add(a: i32, b: i32) -> i32 {
+    let sum: i32 = a + b;
+    return sum;
+}
+"#;
+
+fn stats(source: &str) -> SourceStats {
+    let arena = build_ast(source.to_string());
+    let source_file = &arena.source_files()[0];
+    source_file.stats(&arena)
+}
+
+#[test]
+fn test_stats_count_nodes_of_small_file() {
+    // Statements: the function body, `let`, and `return`. Expressions: the
+    // names LIMIT, add, a, b, and sum, the literal 10, `a + b` with its two
+    // operands, and `sum` in the return.
+    assert_eq!(
+        stats(FIXTURE),
+        SourceStats {
+            functions: 1,
+            external_functions: 0,
+            types: 0,
+            constants: 1,
+            specs: 0,
+            statements: 3,
+            expressions: 10,
+        }
+    );
+}
+
+#[test]
+fn test_stats_count_definitions_of_example_file() {
+    let source =
+        std::fs::read_to_string(get_test_data_path().join("inf").join("example.inf")).unwrap();
+    let stats = stats(&source);
+    assert_eq!(stats.functions, 100);
+    assert_eq!(stats.external_functions, 5);
+    assert_eq!(stats.types, 17);
+    assert_eq!(stats.constants, 14);
+    assert_eq!(stats.specs, 12);
+    assert!(stats.statements > stats.functions);
+    assert!(stats.expressions > stats.statements);
+}
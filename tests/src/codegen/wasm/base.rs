@@ -92,4 +92,26 @@ mod base_codegen_tests {
         //     .unwrap_or_else(|e| panic!("Failed to write actual-nondet.wasm: {}", e));
         assert_wasms_modules_equivalence(&expected, &actual);
     }
+
+    /// Compiling the same source twice in one process must produce
+    /// byte-identical binary and text output, even though AST node IDs differ
+    /// between the two runs.
+    #[test]
+    fn nondet_codegen_is_deterministic() {
+        let test_file_path = get_test_file_path(module_path!(), "nondet");
+        let source_code = std::fs::read_to_string(&test_file_path)
+            .unwrap_or_else(|_| panic!("Failed to read test file: {test_file_path:?}"));
+
+        let first = wasm_codegen(&source_code);
+        let second = wasm_codegen(&source_code);
+        assert_eq!(first, second, "WASM output differs between compilations");
+
+        let first_wat = inference::wasm_to_wat(&first)
+            .unwrap_or_else(|e| panic!("Failed to print WAT: {}", e));
+        let second_wat = inference::wasm_to_wat(&second)
+            .unwrap_or_else(|e| panic!("Failed to print WAT: {}", e));
+        assert_eq!(first_wat, second_wat, "WAT output differs between compilations");
+        assert!(first_wat.contains("forall"));
+        assert!(first_wat.contains("i32.uzumaki"));
+    }
 }
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod compiler_tests {
+    use inference::{CompileOptions, Compiler, Target};
+
+    const SOURCE: &str = "pub fn hello_world() -> i32 {
+    return 42;
+}
+
+pub fn answer() -> i32 {
+    return hello_world();
+}
+";
+
+    fn compile(options: CompileOptions) -> inference::Artifacts {
+        let compiler = Compiler::new(options);
+        let parsed = compiler.parse(SOURCE).unwrap();
+        let typed = compiler.type_check(parsed).unwrap();
+        compiler.analyze(&typed).unwrap();
+        compiler.codegen(&typed).unwrap()
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_functions_match_compiler() {
+        let arena = inference::parse(SOURCE).unwrap();
+        let typed_context = inference::type_check(arena).unwrap();
+        inference::analyze(&typed_context).unwrap();
+        let wasm = inference::codegen(&typed_context).unwrap();
+        assert_eq!(wasm, compile(CompileOptions::new()).wasm);
+
+        let wasm64 = inference::codegen_for_target(&typed_context, Target::Wasm64).unwrap();
+        assert_eq!(
+            wasm64,
+            compile(CompileOptions::new().with_target(Target::Wasm64)).wasm
+        );
+    }
+
+    #[test]
+    fn target_option_selects_target() {
+        let wasm32 = compile(CompileOptions::new().with_target(Target::Wasm32));
+        let wasm64 = compile(CompileOptions::new().with_target(Target::Wasm64));
+        assert_eq!(wasm32.wasm, compile(CompileOptions::new()).wasm);
+        assert_ne!(wasm32.wasm, wasm64.wasm);
+    }
+
+    #[test]
+    fn emit_wat_option_renders_text() {
+        assert_eq!(compile(CompileOptions::new()).wat, None);
+
+        let artifacts = compile(CompileOptions::new().with_emit_wat(true));
+        let wat = artifacts.wat.expect("WAT requested");
+        assert_eq!(wat, inference::wasm_to_wat(&artifacts.wasm).unwrap());
+        assert!(wat.contains("hello_world"));
+    }
+
+    #[test]
+    fn names_map_lists_functions_in_source_order() {
+        let artifacts = compile(CompileOptions::new());
+        assert_eq!(
+            artifacts.names_map,
+            [("hello_world".to_string(), 1), ("answer".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn parse_respects_max_source_size() {
+        let compiler = Compiler::new(CompileOptions::new().with_max_source_size(16));
+        let err = compiler.parse(SOURCE).err().expect("source is too large");
+        assert!(matches!(
+            err.downcast_ref::<inference::AstError>(),
+            Some(inference::AstError::SourceTooLarge { max_size: 16, .. })
+        ));
+    }
+}
@@ -4,6 +4,7 @@
 
 mod ast;
 mod codegen;
+mod compiler;
 mod type_checker;
 mod utils;
 
@@ -1049,10 +1049,9 @@ mod function_registration_coverage {
         }
     }
 
-    // FIXME: Test disabled due to parser or type checker limitation
-    // #[test]
+    #[test]
     fn test_external_function_registration() {
-        let source = r#"extern fn external_func(x: i32) -> i32; fn test() -> i32 { return external_func(42); }"#;
+        let source = r#"external fn external_func(x: i32) -> i32; fn test() -> i32 { return external_func(42); }"#;
         let result = try_type_check(source);
         assert!(
             result.is_ok(),
@@ -1061,6 +1060,34 @@ mod function_registration_coverage {
         );
     }
 
+    #[test]
+    fn test_external_function_argument_type_mismatch() {
+        let source =
+            r#"external fn host_log(flag: bool); fn test() -> i32 { host_log(1); return 0; }"#;
+        let result = try_type_check(source);
+        assert!(result.is_err(), "Mismatched external argument should fail");
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("argument 0 `flag` of function `host_log`"),
+            "Error should name the parameter: {}",
+            error_msg
+        );
+    }
+
+    #[test]
+    fn test_external_function_argument_count_mismatch() {
+        let source =
+            r#"external fn host_log(msg: i32); fn test() -> i32 { host_log(); return 0; }"#;
+        let result = try_type_check(source);
+        assert!(result.is_err(), "Missing external argument should fail");
+        let error_msg = result.err().unwrap().to_string();
+        assert!(
+            error_msg.contains("expects 1 arguments, but 0 provided"),
+            "Error should report the argument count: {}",
+            error_msg
+        );
+    }
+
     #[test]
     fn test_constant_definition_at_module_level() {
         let source = r#"const MY_CONST: i32 = 42; fn test() -> i32 { return MY_CONST; }"#;
@@ -490,6 +490,65 @@ mod import_tests {
         }
     }
 
+    /// Tests for `unused_import` and `import_shadowed` warnings
+    mod import_warnings {
+        use super::*;
+        use inference_type_checker::errors::{TypeCheckWarning, WarningCode};
+
+        #[test]
+        fn test_unused_import_is_reported() {
+            let source =
+                "use self::Point;\nstruct Point { x: i32; }\nfn test() -> i32 { return 42; }";
+            let ctx = try_type_check(source).expect("Unused import is not an error");
+            let warnings = ctx.warnings();
+            assert_eq!(warnings.len(), 1, "Expected one warning, got: {warnings:?}");
+            assert_eq!(warnings[0].code(), WarningCode::UnusedImport);
+            match &warnings[0] {
+                TypeCheckWarning::UnusedImport { path, location } => {
+                    assert_eq!(path, "self::Point");
+                    assert_eq!(location.start_line, 1);
+                }
+                other => panic!("Expected unused import, got: {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_import_used_only_through_qualified_path() {
+            let source = r#"
+            use self::Counter;
+            struct Counter { value: i32; fn zero() -> i32 { return 0; } }
+            fn test() -> i32 { return Counter::zero(); }
+            "#;
+            let ctx = try_type_check(source).expect("Qualified path use should type check");
+            assert!(
+                ctx.warnings().is_empty(),
+                "Qualified path use should count as a use, got: {:?}",
+                ctx.warnings()
+            );
+        }
+
+        #[test]
+        fn test_local_definition_shadowing_import_is_reported() {
+            let source = "use self::Point;\nstruct Point { x: i32; }\nfn test(p: Point) -> i32 { type Point = i32; return 42; }";
+            let ctx = try_type_check(source).expect("Shadowing is not an error");
+            let warnings = ctx.warnings();
+            assert_eq!(warnings.len(), 1, "Expected one warning, got: {warnings:?}");
+            assert_eq!(warnings[0].code().as_str(), "import_shadowed");
+            match &warnings[0] {
+                TypeCheckWarning::ImportShadowed {
+                    name,
+                    location,
+                    import_location,
+                } => {
+                    assert_eq!(name, "Point");
+                    assert_eq!(location.start_line, 3);
+                    assert_eq!(import_location.start_line, 1);
+                }
+                other => panic!("Expected shadowed import, got: {other:?}"),
+            }
+        }
+    }
+
     /// Tests for glob imports and external prelude
     mod extern_prelude_tests {
         use super::*;
@@ -0,0 +1,34 @@
+//! Criterion benchmarks for the formatter.
+//!
+//! Run with `cargo bench -p wat-fmt`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+#[path = "../tests/support/mod.rs"]
+mod support;
+
+/// Input sizes: a small module, a medium (100KB) and a large (5MB) one.
+const SIZES: [(&str, usize); 3] = [
+    ("small", 2 * 1024),
+    ("medium", 100 * 1024),
+    ("large", 5 * 1024 * 1024),
+];
+
+fn bench_format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
+    for (name, size) in SIZES {
+        let input = support::generate_module(size);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        if size > 1024 * 1024 {
+            group.sample_size(10);
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(name), &input, |b, input| {
+            b.iter(|| wat_fmt::format(black_box(input)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_format);
+criterion_main!(benches);
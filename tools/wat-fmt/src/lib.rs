@@ -1,12 +1,80 @@
+//! A pretty formatter for WebAssembly Text Format with support for
+//! Inference non-deterministic instructions.
+//!
+//! ```
+//! let formatted = wat_fmt::format("(module (func $f i32.const 1 drop))");
+//! assert!(formatted.starts_with("(module\n  (func $f"));
+//! ```
+//!
+//! Use [`format_with_config`] with a [`FormatConfig`] to change the
+//! defaults. [`specs::extract`] lists the spec blocks (`forall`, `exists`,
+//! `assume`, `unique`) in a module.
+//!
+//! ## Benchmarks
+//!
+//! `cargo bench -p wat-fmt` runs the criterion benchmarks in
+//! `benches/format.rs` against generated small, medium (100KB), and large
+//! (5MB) modules. `tests/allocations.rs` asserts that formatting the medium
+//! module stays under a fixed allocation ceiling; update the ceiling when a
+//! change intentionally reduces allocations.
 #![no_std]
 #![warn(clippy::pedantic)]
 extern crate alloc;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::ops::Range;
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+pub mod specs;
+
+use specs::SpecKind;
+
+/// Options controlling the formatter output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// Preferred maximum line width. Element segments whose index list
+    /// does not fit are wrapped with continuation indentation.
+    pub max_width: usize,
+    /// Maximum length, in bytes of literal text, of each string in a data
+    /// segment. Longer payloads are split into adjacent string literals,
+    /// one per line. Escape sequences are never split.
+    pub data_chunk_bytes: usize,
+    /// Append a comment naming the declaration referenced by numeric
+    /// local, global, and function indices (e.g. `local.get 3 ;; $x`).
+    /// References that cannot be resolved are left unannotated.
+    pub annotate_indices: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 80,
+            data_chunk_bytes: 64,
+            annotate_indices: false,
+        }
+    }
+}
+
+/// Declared name and type of an indexed item.
+#[derive(Clone)]
+struct IndexEntry {
+    name: Option<String>,
+    ty: Option<String>,
+}
+
+/// Index spaces visible to the instructions being formatted.
+#[derive(Clone, Default)]
+struct IndexTables {
+    funcs: Vec<IndexEntry>,
+    globals: Vec<IndexEntry>,
+    /// `None` when the local index space cannot be determined, e.g. when
+    /// the parameters come from a `(type ...)` reference.
+    locals: Option<Vec<IndexEntry>>,
+}
+
 enum Token {
     LParen,
     RParen,
@@ -15,42 +83,54 @@ enum Token {
 
 fn tokenize(input: &str) -> Vec<Token> {
     let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
+    tokenize_with(input, |token, _| tokens.push(token));
+    tokens
+}
 
-    while let Some(c) = chars.next() {
+/// Splits `input` into tokens, passing each one to `emit` together with its
+/// byte range in `input`.
+fn tokenize_with(input: &str, mut emit: impl FnMut(Token, Range<usize>)) {
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
         if c.is_whitespace() {
             continue;
         }
         if c == '(' {
-            tokens.push(Token::LParen);
+            emit(Token::LParen, start..start + 1);
         } else if c == ')' {
-            tokens.push(Token::RParen);
+            emit(Token::RParen, start..start + 1);
         } else if c == '"' {
             let mut s = String::new();
             s.push('"');
-            while let Some(&next) = chars.peek() {
+            while let Some(&(_, next)) = chars.peek() {
                 s.push(next);
                 chars.next();
-                if next == '"' {
+                if next == '\\' {
+                    // Keep the escaped character so `\"` does not end the string.
+                    if let Some((_, escaped)) = chars.next() {
+                        s.push(escaped);
+                    }
+                } else if next == '"' {
                     break;
                 }
             }
-            tokens.push(Token::Atom(s));
+            let end = start + s.len();
+            emit(Token::Atom(s), start..end);
         } else {
             let mut s = String::new();
             s.push(c);
-            while let Some(&next) = chars.peek() {
+            while let Some(&(_, next)) = chars.peek() {
                 if next.is_whitespace() || next == '(' || next == ')' {
                     break;
                 }
                 s.push(next);
                 chars.next();
             }
-            tokens.push(Token::Atom(s));
+            let end = start + s.len();
+            emit(Token::Atom(s), start..end);
         }
     }
-
-    tokens
 }
 
 enum Node {
@@ -171,8 +251,129 @@ fn is_opcode(token: &str) -> bool {
     true
 }
 
+/// Returns the keyword of a list node, if any.
+fn list_keyword(node: &Node) -> Option<&str> {
+    match node {
+        Node::List(children) => match children.first() {
+            Some(Node::Atom(keyword)) => Some(keyword),
+            _ => None,
+        },
+        Node::Atom(_) => None,
+    }
+}
+
+/// Returns the entry declared by a `func` or `global` field.
+///
+/// `children` starts with the field keyword; inline `export` and `import`
+/// abbreviations are skipped when looking for the type.
+fn declared_entry(children: &[Node]) -> IndexEntry {
+    let name = match children.get(1) {
+        Some(Node::Atom(name)) if name.starts_with('$') => Some(name.clone()),
+        _ => None,
+    };
+    let ty = children
+        .iter()
+        .skip(if name.is_some() { 2 } else { 1 })
+        .find(|node| !matches!(list_keyword(node), Some("export" | "import")))
+        .map(format_node_inline);
+    IndexEntry { name, ty }
+}
+
+/// Builds the function and global index spaces of a module.
+fn module_tables(children: &[Node]) -> IndexTables {
+    let mut tables = IndexTables::default();
+    for child in children.iter().skip(1) {
+        let Node::List(field) = child else {
+            continue;
+        };
+        match list_keyword(child) {
+            Some("func") => tables.funcs.push(declared_entry(field)),
+            Some("global") => tables.globals.push(declared_entry(field)),
+            Some("import") => {
+                if let Some(desc_node @ Node::List(desc)) = field.last() {
+                    match list_keyword(desc_node) {
+                        Some("func") => tables.funcs.push(declared_entry(desc)),
+                        Some("global") => tables.globals.push(declared_entry(desc)),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for func in &mut tables.funcs {
+        // Function signatures are not useful as annotations.
+        func.ty = None;
+    }
+    tables
+}
+
+/// Builds the local index space of a function from its `param` and `local`
+/// declarations.
+///
+/// Returns `None` if the parameters are only given by a `(type ...)` use.
+fn func_locals(children: &[Node]) -> Option<Vec<IndexEntry>> {
+    let mut locals = Vec::new();
+    let mut has_params = false;
+    let mut has_type_use = false;
+    for child in children {
+        let Node::List(decl) = child else {
+            continue;
+        };
+        match list_keyword(child) {
+            Some("type") => has_type_use = true,
+            Some(keyword @ ("param" | "local")) => {
+                has_params |= keyword == "param";
+                match decl.get(1) {
+                    Some(Node::Atom(name)) if name.starts_with('$') => locals.push(IndexEntry {
+                        name: Some(name.clone()),
+                        ty: decl.get(2).map(format_node_inline),
+                    }),
+                    _ => locals.extend(decl.iter().skip(1).map(|ty| IndexEntry {
+                        name: None,
+                        ty: Some(format_node_inline(ty)),
+                    })),
+                }
+            }
+            _ => {}
+        }
+    }
+    if has_type_use && !has_params {
+        return None;
+    }
+    Some(locals)
+}
+
+/// Returns a comment describing the declaration referenced by a numeric
+/// index operand in an instruction line such as `local.get 3`.
+fn index_annotation(line: &str, tables: &IndexTables) -> Option<String> {
+    let mut parts = line.split(' ');
+    let opcode = parts.next()?;
+    let index: usize = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let (kind, entries) = match opcode {
+        "local.get" | "local.set" | "local.tee" => ("local", tables.locals.as_ref()?),
+        "global.get" | "global.set" => ("global", &tables.globals),
+        "call" | "return_call" | "ref.func" => ("func", &tables.funcs),
+        _ => return None,
+    };
+    let entry = entries.get(index)?;
+    match (&entry.name, &entry.ty) {
+        (Some(name), _) => Some(format!(";; {name}")),
+        (None, Some(ty)) => Some(format!(";; {kind} #{index}: {ty}")),
+        (None, None) => None,
+    }
+}
+
 /// Format the instructions in a more readable way.
-fn format_instructions(nodes: &[Node], base_indent: usize) -> String {
+fn format_instructions(
+    nodes: &[Node],
+    base_indent: usize,
+    config: &FormatConfig,
+    tables: Option<&IndexTables>,
+) -> String {
     let mut result = String::new();
     let mut current_indent = base_indent;
     let mut i = 0;
@@ -220,6 +421,10 @@ fn format_instructions(nodes: &[Node], base_indent: usize) -> String {
                             break;
                         }
                     }
+                    if let Some(annotation) = tables.and_then(|t| index_annotation(&line, t)) {
+                        line.push(' ');
+                        line.push_str(&annotation);
+                    }
                     result.push('\n');
                     result.push_str(&indent_str(current_indent));
                     result.push_str(&line);
@@ -234,7 +439,7 @@ fn format_instructions(nodes: &[Node], base_indent: usize) -> String {
             Node::List(_) => {
                 result.push('\n');
                 result.push_str(&indent_str(current_indent));
-                result.push_str(&format_node(&nodes[i], current_indent));
+                result.push_str(&format_node(&nodes[i], current_indent, config, tables));
                 i += 1;
             }
         }
@@ -242,8 +447,61 @@ fn format_instructions(nodes: &[Node], base_indent: usize) -> String {
     result
 }
 
+/// Format a `func` field: the name and signature inline, followed by the
+/// body as instructions.
+fn format_func(
+    children: &[Node],
+    indent: usize,
+    config: &FormatConfig,
+    tables: Option<&IndexTables>,
+) -> String {
+    let mut s = String::new();
+    s.push('(');
+    // Always print the “func” keyword inline.
+    s.push_str(&format_node_inline(&children[0]));
+    let mut i = 1;
+    // Inline printing for function name and inline signatures.
+    while i < children.len() {
+        // If this is an atom and it looks like an opcode (i.e. an instruction),
+        // then stop printing inline.
+        if let Node::Atom(ref tok) = children[i]
+            && is_opcode(tok)
+        {
+            break;
+        }
+        if let Node::List(_) = children[i]
+            && !is_inline_signature(&children[i])
+        {
+            break;
+        }
+        s.push(' ');
+        s.push_str(&format_node_inline(&children[i]));
+        i += 1;
+    }
+    // Format the remaining nodes as instructions.
+    let func_tables = config.annotate_indices.then(|| IndexTables {
+        locals: func_locals(children),
+        ..tables.cloned().unwrap_or_default()
+    });
+    s.push_str(&format_instructions(
+        &children[i..],
+        indent + 1,
+        config,
+        func_tables.as_ref(),
+    ));
+    s.push('\n');
+    s.push_str(&indent_str(indent));
+    s.push(')');
+    s
+}
+
 /// Format a node with indentation.
-fn format_node(node: &Node, indent: usize) -> String {
+fn format_node(
+    node: &Node,
+    indent: usize,
+    config: &FormatConfig,
+    tables: Option<&IndexTables>,
+) -> String {
     match node {
         Node::Atom(s) => s.clone(),
         Node::List(children) => {
@@ -253,57 +511,48 @@ fn format_node(node: &Node, indent: usize) -> String {
             // Special handling for “module”:
             if let Some(Node::Atom(ident)) = children.first() {
                 if ident == "module" {
+                    let module_tables = config.annotate_indices.then(|| module_tables(children));
                     let mut s = String::new();
                     s.push('(');
                     s.push_str(ident);
                     for child in children.iter().skip(1) {
                         s.push('\n');
                         s.push_str(&indent_str(indent + 1));
-                        s.push_str(&format_node(child, indent + 1));
+                        s.push_str(&format_node(
+                            child,
+                            indent + 1,
+                            config,
+                            module_tables.as_ref(),
+                        ));
                     }
                     s.push('\n');
                     s.push_str(&indent_str(indent));
                     s.push(')');
                     return s;
                 } else if ident == "func" {
-                    let mut s = String::new();
-                    s.push('(');
-                    // Always print the “func” keyword inline.
-                    s.push_str(&format_node_inline(&children[0]));
-                    let mut i = 1;
-                    // Inline printing for function name and inline signatures.
-                    while i < children.len() {
-                        // If this is an atom and it looks like an opcode (i.e. an instruction),
-                        // then stop printing inline.
-                        if let Node::Atom(ref tok) = children[i]
-                            && is_opcode(tok)
-                        {
-                            break;
-                        }
-                        if let Node::List(_) = children[i]
-                            && !is_inline_signature(&children[i])
-                        {
-                            break;
-                        }
-                        s.push(' ');
-                        s.push_str(&format_node_inline(&children[i]));
-                        i += 1;
-                    }
-                    // Format the remaining nodes as instructions.
-                    s.push_str(&format_instructions(&children[i..], indent + 1));
-                    s.push('\n');
-                    s.push_str(&indent_str(indent));
-                    s.push(')');
-                    return s;
-                } else if ["forall", "exists", "assume", "unique"].contains(&ident.as_str()) {
+                    return format_func(children, indent, config, tables);
+                } else if SpecKind::from_keyword(ident).is_some() {
                     let mut s = String::new();
                     s.push('(');
                     s.push_str(ident);
-                    s.push_str(&format_instructions(&children[1..], indent + 1));
+                    s.push_str(&format_instructions(
+                        &children[1..],
+                        indent + 1,
+                        config,
+                        tables,
+                    ));
                     s.push('\n');
                     s.push_str(&indent_str(indent));
                     s.push(')');
                     return s;
+                } else if ident == "data"
+                    && let Some(s) = format_data_segment(children, indent, config)
+                {
+                    return s;
+                } else if ident == "elem"
+                    && let Some(s) = format_elem_segment(children, indent, config)
+                {
+                    return s;
                 }
             }
             // For lists that are flat, use the inline formatter.
@@ -315,12 +564,12 @@ fn format_node(node: &Node, indent: usize) -> String {
                 let mut first = true;
                 for child in children {
                     if first {
-                        s.push_str(&format_node(child, indent + 1));
+                        s.push_str(&format_node(child, indent + 1, config, tables));
                         first = false;
                     } else {
                         s.push('\n');
                         s.push_str(&indent_str(indent + 1));
-                        s.push_str(&format_node(child, indent + 1));
+                        s.push_str(&format_node(child, indent + 1, config, tables));
                     }
                 }
                 s.push('\n');
@@ -332,18 +581,178 @@ fn format_node(node: &Node, indent: usize) -> String {
     }
 }
 
+/// Returns true if the atom is a complete string literal.
+fn is_string_atom(node: &Node) -> bool {
+    matches!(node, Node::Atom(s) if s.len() >= 2 && s.starts_with('"') && s.ends_with('"'))
+}
+
+/// Returns true if the atom is a symbolic or numeric index.
+fn is_index_atom(node: &Node) -> bool {
+    match node {
+        Node::Atom(s) => {
+            s.starts_with('$') || (!s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+        }
+        Node::List(_) => false,
+    }
+}
+
+/// Returns the byte length of the first character or escape sequence in `s`.
+fn escape_unit_len(s: &str) -> usize {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some('\\') => match chars.next() {
+            Some('u') => s.find('}').map_or(s.len(), |i| i + 1),
+            Some(c) if c.is_ascii_hexdigit() => {
+                let low = chars.next().filter(char::is_ascii_hexdigit);
+                1 + c.len_utf8() + low.map_or(0, char::len_utf8)
+            }
+            Some(c) => 1 + c.len_utf8(),
+            None => 1,
+        },
+        Some(c) => c.len_utf8(),
+        None => 0,
+    }
+}
+
+/// Splits string literal contents into chunks of at most `max_bytes` bytes
+/// without breaking characters or escape sequences.
+fn split_string_contents(content: &str, max_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    while end < content.len() {
+        let unit = escape_unit_len(&content[end..]);
+        if end > start && end + unit - start > max_bytes {
+            chunks.push(&content[start..end]);
+            start = end;
+        }
+        end += unit;
+    }
+    if end > start {
+        chunks.push(&content[start..end]);
+    }
+    chunks
+}
+
+/// Formats a `data` segment whose string payload is longer than
+/// `config.data_chunk_bytes`, placing one chunk per line.
+///
+/// The WAT grammar concatenates adjacent strings, so the output is
+/// equivalent to the input. Returns `None` if the segment should be
+/// formatted normally.
+fn format_data_segment(children: &[Node], indent: usize, config: &FormatConfig) -> Option<String> {
+    let first_string = children.iter().position(is_string_atom)?;
+    let payload = &children[first_string..];
+    if !payload.iter().all(is_string_atom) {
+        return None;
+    }
+
+    let mut content = String::new();
+    for node in payload {
+        if let Node::Atom(literal) = node {
+            content.push_str(&literal[1..literal.len() - 1]);
+        }
+    }
+    if content.len() <= config.data_chunk_bytes {
+        return None;
+    }
+
+    let mut s = String::new();
+    s.push('(');
+    for (i, child) in children[..first_string].iter().enumerate() {
+        if i > 0 {
+            s.push(' ');
+        }
+        s.push_str(&format_node_inline(child));
+    }
+    for chunk in split_string_contents(&content, config.data_chunk_bytes.max(1)) {
+        s.push('\n');
+        s.push_str(&indent_str(indent + 1));
+        s.push('"');
+        s.push_str(chunk);
+        s.push('"');
+    }
+    s.push('\n');
+    s.push_str(&indent_str(indent));
+    s.push(')');
+    Some(s)
+}
+
+/// Formats an `elem` segment whose trailing index list does not fit within
+/// `config.max_width`, wrapping the indices with continuation indentation.
+///
+/// Returns `None` if the segment should be formatted normally.
+fn format_elem_segment(children: &[Node], indent: usize, config: &FormatConfig) -> Option<String> {
+    let index_count = children
+        .iter()
+        .rev()
+        .take_while(|n| is_index_atom(n))
+        .count();
+    let split = children.len() - index_count;
+    if index_count == 0 || split == 0 || !is_flat_list(children) {
+        return None;
+    }
+
+    let mut header = String::new();
+    header.push('(');
+    for (i, child) in children[..split].iter().enumerate() {
+        if i > 0 {
+            header.push(' ');
+        }
+        header.push_str(&format_node_inline(child));
+    }
+
+    let items: Vec<String> = children[split..].iter().map(format_node_inline).collect();
+    let inline_len =
+        indent * 2 + header.len() + items.iter().map(|i| i.len() + 1).sum::<usize>() + 1;
+    if inline_len <= config.max_width {
+        return None;
+    }
+
+    let continuation = indent_str(indent + 2);
+    let mut s = header;
+    let mut line = String::new();
+    for item in &items {
+        if !line.is_empty() && continuation.len() + line.len() + 1 + item.len() > config.max_width {
+            s.push('\n');
+            s.push_str(&continuation);
+            s.push_str(&line);
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(item);
+    }
+    if !line.is_empty() {
+        s.push('\n');
+        s.push_str(&continuation);
+        s.push_str(&line);
+    }
+    s.push('\n');
+    s.push_str(&indent_str(indent));
+    s.push(')');
+    Some(s)
+}
+
 /// Format the input `WAT` string into a readable format.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[must_use]
 pub fn format(input: &str) -> String {
+    format_with_config(input, &FormatConfig::default())
+}
+
+/// Format the input `WAT` string using the given options.
+#[must_use]
+pub fn format_with_config(input: &str, config: &FormatConfig) -> String {
     let tokens = tokenize(input);
     let nodes = parse_all(&tokens);
     if nodes.len() == 1 {
-        format_node(&nodes[0], 0)
+        format_node(&nodes[0], 0, config, None)
     } else {
         let mut s = String::new();
         for node in nodes {
-            s.push_str(&format_node(&node, 0));
+            s.push_str(&format_node(&node, 0, config, None));
             s.push('\n');
         }
         s
@@ -353,6 +762,7 @@ pub fn format(input: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use core::fmt::Write as _;
 
     #[test]
     fn test_format() {
@@ -371,4 +781,196 @@ mod tests {
         let output = format(input);
         assert_eq!(output, expected);
     }
+
+    /// Re-tokenizes WAT text, merging adjacent string literals into one so
+    /// that differently chunked data payloads compare equal.
+    fn normalized_tokens(input: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        let mut previous_string = false;
+        for token in tokenize(input) {
+            let text = match token {
+                Token::LParen => String::from("("),
+                Token::RParen => String::from(")"),
+                Token::Atom(s) => s,
+            };
+            let is_string = text.starts_with('"');
+            if is_string
+                && previous_string
+                && let Some(last) = result.last_mut()
+            {
+                last.pop();
+                last.push_str(&text[1..]);
+            } else {
+                result.push(text);
+            }
+            previous_string = is_string;
+        }
+        result
+    }
+
+    fn long_data_module() -> String {
+        let mut payload = String::new();
+        for i in 0..40 {
+            payload.push_str("hello, world ");
+            payload.push_str(if i % 2 == 0 { "\\00\\ff" } else { "\\\"q\\\"" });
+        }
+        alloc::format!("(module (memory 1) (data (i32.const 16) \"{payload}\"))")
+    }
+
+    #[test]
+    fn test_data_segment_split_into_chunks() {
+        let input = long_data_module();
+        let output = format(&input);
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines.contains(&"  (data (i32.const 16)"));
+        let chunks: Vec<&str> = lines
+            .iter()
+            .filter(|l| l.starts_with("    \""))
+            .copied()
+            .collect();
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            let content = &chunk.trim()[1..chunk.trim().len() - 1];
+            assert!(content.len() <= 64, "chunk too long: {chunk}");
+        }
+        assert_eq!(normalized_tokens(&input), normalized_tokens(&output));
+    }
+
+    #[test]
+    fn test_data_segment_respects_chunk_size() {
+        let input = long_data_module();
+        let config = FormatConfig {
+            data_chunk_bytes: 16,
+            ..FormatConfig::default()
+        };
+        let output = format_with_config(&input, &config);
+        for line in output.lines().filter(|l| l.trim_start().starts_with('"')) {
+            assert!(line.trim().len() - 2 <= 16, "chunk too long: {line}");
+        }
+        assert_eq!(normalized_tokens(&input), normalized_tokens(&output));
+    }
+
+    #[test]
+    fn test_data_segment_does_not_split_escapes() {
+        assert_eq!(
+            split_string_contents("\\00\\01\\u{1F600}ab", 5),
+            ["\\00", "\\01", "\\u{1F600}", "ab"]
+        );
+        assert_eq!(split_string_contents("\\n\\t\\\"", 4), ["\\n\\t", "\\\""]);
+        assert_eq!(split_string_contents("ééé", 3), ["é", "é", "é"]);
+    }
+
+    #[test]
+    fn test_short_data_segment_stays_inline() {
+        let input = r#"(module (data (i32.const 0) "hi" "there"))"#;
+        let expected = "(module\n  (data (i32.const 0) \"hi\" \"there\")\n)";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn test_data_segment_formatting_is_idempotent() {
+        let once = format(&long_data_module());
+        assert_eq!(format(&once), once);
+    }
+
+    #[test]
+    fn test_tokenizer_keeps_escaped_quotes() {
+        let input = r#"(data (i32.const 0) "a\"b")"#;
+        assert_eq!(format(input), input);
+    }
+
+    #[test]
+    fn test_elem_segment_wraps_long_index_list() {
+        let mut input = String::from("(module (table 64 funcref) (elem (i32.const 0) func");
+        for i in 0..40 {
+            let _ = write!(input, " $function_{i}");
+        }
+        input.push_str("))");
+
+        let output = format(&input);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[2], "  (elem (i32.const 0) func");
+        assert_eq!(lines[lines.len() - 2], "  )");
+        let wrapped: Vec<&&str> = lines.iter().filter(|l| l.starts_with("      $")).collect();
+        assert!(wrapped.len() > 1);
+        for line in &lines {
+            assert!(line.len() <= 80, "line too long: {line}");
+        }
+        assert_eq!(normalized_tokens(&input), normalized_tokens(&output));
+        assert_eq!(format(&output), output);
+    }
+
+    fn annotated(input: &str) -> String {
+        let config = FormatConfig {
+            annotate_indices: true,
+            ..FormatConfig::default()
+        };
+        format_with_config(input, &config)
+    }
+
+    #[test]
+    fn test_annotate_indices_with_named_declarations() {
+        let input = "(module (import \"env\" \"log\" (func $log (param i32))) \
+            (global $counter (mut i32) (i32.const 0)) \
+            (func $add (param $a i32) (param $b i32) (result i32) (local $sum i32) \
+            local.get 0 local.get 1 i32.add local.tee 2 call 0 global.get 0 drop local.get $sum))";
+        let expected = "(module
+  (import \"env\" \"log\" (func $log (param i32)))
+  (global $counter (mut i32) (i32.const 0))
+  (func $add (param $a i32) (param $b i32) (result i32)
+    (local $sum i32)
+    local.get 0 ;; $a
+    local.get 1 ;; $b
+    i32.add
+    local.tee 2 ;; $sum
+    call 0 ;; $log
+    global.get 0 ;; $counter
+    drop
+    local.get $sum
+  )
+)";
+        assert_eq!(annotated(input), expected);
+    }
+
+    #[test]
+    fn test_annotate_indices_with_numeric_declarations() {
+        let input = "(module (global (mut i64) (i64.const 0)) (func (param i32 f32) (local i64) \
+            local.get 1 drop local.get 2 global.set 0 call 0 local.get 7 global.get 3))";
+        let expected = "(module
+  (global (mut i64) (i64.const 0))
+  (func (param i32 f32)
+    (local i64)
+    local.get 1 ;; local #1: f32
+    drop
+    local.get 2 ;; local #2: i64
+    global.set 0 ;; global #0: (mut i64)
+    call 0
+    local.get 7
+    global.get 3
+  )
+)";
+        assert_eq!(annotated(input), expected);
+    }
+
+    #[test]
+    fn test_annotate_indices_skips_unresolved_type_use() {
+        let input = "(module (type $t (func (param i32))) (func (type $t) local.get 0 drop))";
+        let output = annotated(input);
+        assert!(!output.contains(";;"));
+        assert_eq!(output, format(input));
+    }
+
+    #[test]
+    fn test_annotate_indices_disabled_by_default() {
+        let input = "(module (func $f (param $a i32) local.get 0 drop))";
+        assert!(!format(input).contains(";;"));
+    }
+
+    #[test]
+    fn test_short_elem_segment_stays_inline() {
+        let input = "(module (elem (i32.const 0) func $a $b 2))";
+        let expected = "(module\n  (elem (i32.const 0) func $a $b 2)\n)";
+        assert_eq!(format(input), expected);
+    }
 }
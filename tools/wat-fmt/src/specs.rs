@@ -0,0 +1,250 @@
+//! Extraction of Inference spec blocks from WAT text.
+//!
+//! Spec blocks are the non-deterministic `forall`, `exists`, `assume`, and
+//! `unique` forms. [`extract`] lists every block in a module, including
+//! blocks nested in other blocks, without requiring callers to parse the
+//! S-expressions themselves.
+//!
+//! ```
+//! use wat_fmt::specs::{self, SpecKind};
+//!
+//! let blocks = specs::extract("(module (func $f (forall i32.uzumaki drop)))");
+//! assert_eq!(blocks.len(), 1);
+//! assert_eq!(blocks[0].kind, SpecKind::Forall);
+//! assert_eq!(blocks[0].enclosing_func.as_deref(), Some("$f"));
+//! assert_eq!(blocks[0].text, "(forall\n  i32.uzumaki\n  drop\n)");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{FormatConfig, Token, format_node, parse_node, tokenize_with};
+
+/// The kind of a spec block, named after its keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecKind {
+    Forall,
+    Exists,
+    Assume,
+    Unique,
+}
+
+impl SpecKind {
+    /// Returns the kind introduced by `keyword`, if it starts a spec block.
+    #[must_use]
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "forall" => Some(Self::Forall),
+            "exists" => Some(Self::Exists),
+            "assume" => Some(Self::Assume),
+            "unique" => Some(Self::Unique),
+            _ => None,
+        }
+    }
+
+    /// Returns the keyword that starts a block of this kind.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Forall => "forall",
+            Self::Exists => "exists",
+            Self::Assume => "assume",
+            Self::Unique => "unique",
+        }
+    }
+}
+
+/// A spec block found by [`extract`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecBlock {
+    pub kind: SpecKind,
+    /// Name (including the `$`) of the nearest enclosing `func`. `None`
+    /// outside functions and inside functions without a name.
+    pub enclosing_func: Option<String>,
+    /// Index in the extracted list of the innermost spec block containing
+    /// this one.
+    pub parent: Option<usize>,
+    /// Byte range of the block in the input, from its `(` to its `)`.
+    /// Unterminated blocks extend to the end of the input.
+    pub span: Range<usize>,
+    /// The block formatted on its own, as [`crate::format`] would print it
+    /// at the top level.
+    pub text: String,
+}
+
+/// An open list while walking the token stream.
+enum Frame {
+    Func(Option<String>),
+    Spec(usize),
+    Other,
+}
+
+/// Returns all spec blocks in `input`, outer blocks before the blocks
+/// nested in them.
+#[must_use]
+pub fn extract(input: &str) -> Vec<SpecBlock> {
+    let mut tokens = Vec::new();
+    let mut ranges = Vec::new();
+    tokenize_with(input, |token, range| {
+        tokens.push(token);
+        ranges.push(range);
+    });
+
+    let config = FormatConfig::default();
+    let mut blocks: Vec<SpecBlock> = Vec::new();
+    let mut open: Vec<Frame> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => {
+                let keyword = match tokens.get(i + 1) {
+                    Some(Token::Atom(keyword)) => keyword.as_str(),
+                    _ => "",
+                };
+                let frame = if keyword == "func" {
+                    match tokens.get(i + 2) {
+                        Some(Token::Atom(name)) if name.starts_with('$') => {
+                            Frame::Func(Some(name.clone()))
+                        }
+                        _ => Frame::Func(None),
+                    }
+                } else if let Some(kind) = SpecKind::from_keyword(keyword) {
+                    let (node, _) = parse_node(&tokens, i);
+                    blocks.push(SpecBlock {
+                        kind,
+                        enclosing_func: open
+                            .iter()
+                            .rev()
+                            .find_map(|frame| match frame {
+                                Frame::Func(name) => Some(name.clone()),
+                                _ => None,
+                            })
+                            .flatten(),
+                        parent: open.iter().rev().find_map(|frame| match frame {
+                            Frame::Spec(index) => Some(*index),
+                            _ => None,
+                        }),
+                        span: ranges[i].start..input.len(),
+                        text: format_node(&node, 0, &config, None),
+                    });
+                    Frame::Spec(blocks.len() - 1)
+                } else {
+                    Frame::Other
+                };
+                open.push(frame);
+            }
+            Token::RParen => {
+                if let Some(Frame::Spec(index)) = open.pop() {
+                    blocks[index].span.end = ranges[i].end;
+                }
+            }
+            Token::Atom(_) => {}
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = "(module
+  (global $g i32 (i32.const 0))
+  (assume global.get $g drop)
+  (func $main (param $a i32)
+    (forall i32.uzumaki local.set $a (exists i32.uzumaki drop (unique i32.const 1 drop)))
+    (assume local.get $a drop))
+  (func (unique i32.const 2 drop)))";
+
+    fn span_text(block: &SpecBlock) -> &str {
+        &MODULE[block.span.clone()]
+    }
+
+    #[test]
+    fn extracts_blocks_in_source_order() {
+        let blocks = extract(MODULE);
+        let kinds: Vec<SpecKind> = blocks.iter().map(|block| block.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SpecKind::Assume,
+                SpecKind::Forall,
+                SpecKind::Exists,
+                SpecKind::Unique,
+                SpecKind::Assume,
+                SpecKind::Unique,
+            ]
+        );
+    }
+
+    #[test]
+    fn records_enclosing_function() {
+        let blocks = extract(MODULE);
+        let funcs: Vec<Option<&str>> = blocks
+            .iter()
+            .map(|block| block.enclosing_func.as_deref())
+            .collect();
+        assert_eq!(
+            funcs,
+            [
+                None,
+                Some("$main"),
+                Some("$main"),
+                Some("$main"),
+                Some("$main"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_blocks_reference_their_parent() {
+        let blocks = extract(MODULE);
+        let parents: Vec<Option<usize>> = blocks.iter().map(|block| block.parent).collect();
+        assert_eq!(parents, [None, None, Some(1), Some(2), None, None]);
+    }
+
+    #[test]
+    fn spans_cover_the_block_source() {
+        let blocks = extract(MODULE);
+        assert_eq!(span_text(&blocks[0]), "(assume global.get $g drop)");
+        assert_eq!(span_text(&blocks[3]), "(unique i32.const 1 drop)");
+        assert_eq!(
+            span_text(&blocks[1]),
+            "(forall i32.uzumaki local.set $a (exists i32.uzumaki drop (unique i32.const 1 drop)))"
+        );
+    }
+
+    #[test]
+    fn text_is_formatted_like_the_formatter_output() {
+        let blocks = extract(MODULE);
+        assert_eq!(
+            blocks[2].text,
+            "(exists\n  i32.uzumaki\n  drop\n  (unique\n    i32.const 1\n    drop\n  )\n)"
+        );
+        for block in &blocks {
+            assert_eq!(block.text, crate::format(span_text(block)));
+        }
+    }
+
+    #[test]
+    fn unterminated_block_extends_to_end_of_input() {
+        let input = "(func $f (forall i32.uzumaki drop";
+        let blocks = extract(input);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].span, 9..input.len());
+    }
+
+    #[test]
+    fn keyword_round_trips() {
+        for kind in [
+            SpecKind::Forall,
+            SpecKind::Exists,
+            SpecKind::Assume,
+            SpecKind::Unique,
+        ] {
+            assert_eq!(SpecKind::from_keyword(kind.as_str()), Some(kind));
+        }
+        assert_eq!(SpecKind::from_keyword("func"), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! Allocation-count regression test for the formatter.
+//!
+//! A counting global allocator records every allocation made while
+//! formatting the medium benchmark module. The ceiling is a budget: lower it
+//! when a refactor reduces allocations, and raise it only deliberately.
+
+mod support;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Size of the medium benchmark input.
+const MEDIUM_MODULE_BYTES: usize = 100 * 1024;
+
+/// Maximum number of allocations allowed when formatting the medium module.
+const MEDIUM_MODULE_ALLOCATION_CEILING: usize = 100_000;
+
+/// Global allocator that counts allocations and reallocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// SAFETY: All calls are forwarded to the system allocator unchanged.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: The caller upholds the `GlobalAlloc::alloc` contract.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: The caller upholds the `GlobalAlloc::dealloc` contract.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: The caller upholds the `GlobalAlloc::realloc` contract.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn medium_module_allocations_stay_under_ceiling() {
+    let input = support::generate_module(MEDIUM_MODULE_BYTES);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let output = wat_fmt::format(&input);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    assert!(!output.is_empty());
+    assert!(
+        allocations <= MEDIUM_MODULE_ALLOCATION_CEILING,
+        "formatting the medium module ({} bytes) made {allocations} allocations, \
+         ceiling is {MEDIUM_MODULE_ALLOCATION_CEILING}",
+        input.len()
+    );
+}
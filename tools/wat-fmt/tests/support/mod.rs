@@ -0,0 +1,81 @@
+//! Builders for representative WAT modules used by benchmarks and tests.
+//!
+//! Modules are generated deterministically so that benchmark results and
+//! allocation counts are comparable between runs.
+
+use std::fmt::Write as _;
+
+/// Returns an unformatted, single-line module of at least `target_bytes`
+/// bytes.
+///
+/// The module contains imports, globals, a memory with data segments, a
+/// table with an element segment, exports, and functions mixing flat
+/// instructions, `if`/`else` blocks, folded expressions, and Inference
+/// non-deterministic blocks.
+#[must_use]
+pub fn generate_module(target_bytes: usize) -> String {
+    let mut module = String::with_capacity(target_bytes + 1024);
+    module.push_str("(module ");
+    module.push_str(r#"(import "env" "log" (func $log (param i32))) "#);
+    module.push_str(r#"(import "env" "mem_base" (global $mem_base i32)) "#);
+    module.push_str("(global $counter (mut i32) (i32.const 0)) ");
+    module.push_str("(global (mut i64) (i64.const 0)) ");
+    module.push_str("(memory 1) ");
+
+    let mut funcs = 0;
+    while module.len() < target_bytes {
+        push_func(&mut module, funcs);
+        funcs += 1;
+        if funcs % 16 == 0 {
+            push_data_segment(&mut module, funcs);
+        }
+    }
+
+    let _ = write!(module, "(table {funcs} funcref) (elem (i32.const 0) func");
+    for i in 0..funcs {
+        let _ = write!(module, " $f{i}");
+    }
+    module.push_str(") ");
+    for i in (0..funcs).step_by(8) {
+        let _ = write!(module, r#"(export "f{i}" (func $f{i})) "#);
+    }
+    module.push(')');
+    module
+}
+
+/// Appends one function whose shape varies with `index`.
+fn push_func(module: &mut String, index: usize) {
+    let _ = write!(
+        module,
+        "(func $f{index} (param $a i32) (param $b i32) (result i32) (local $c i32) (local i64) "
+    );
+    match index % 4 {
+        0 => module.push_str(
+            "local.get 0 local.get 1 i32.add local.set 2 local.get $c \
+             global.get $counter i32.add global.set $counter local.get 2 ",
+        ),
+        1 => module.push_str(
+            "local.get $a i32.const 0 i32.gt_s if local.get $a call $log \
+             else local.get $b call $log end local.get $b i32.const 3 i32.mul ",
+        ),
+        2 => module.push_str(
+            "(local.set $c (i32.add (local.get $a) (local.get $b))) \
+             (forall i32.uzumaki local.set $c) (assume local.get $c i32.const 0 i32.ge_s drop) \
+             local.get $c ",
+        ),
+        _ => module.push_str(
+            "(exists i32.uzumaki local.set $c (unique local.get $c i32.eqz drop)) \
+             i64.const 42 local.set 3 local.get 3 i32.wrap_i64 ",
+        ),
+    }
+    module.push_str(") ");
+}
+
+/// Appends a data segment with a payload mixing text and escapes.
+fn push_data_segment(module: &mut String, index: usize) {
+    let _ = write!(module, r#"(data (i32.const {}) ""#, index * 128);
+    for i in 0..12 {
+        let _ = write!(module, "segment {index} line {i}\\0a\\{:02x}", i * 7);
+    }
+    module.push_str(r#"") "#);
+}